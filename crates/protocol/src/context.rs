@@ -0,0 +1,103 @@
+//! `ui.context.update`: where the user is working, pushed by the client whenever it changes.
+//! The runtime keeps the latest one for `context.inspect` and the next `run.start`.
+
+use crate::message::notification_message;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// 0-based.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UiPosition {
+    pub line: u64,
+    pub column: u64,
+}
+
+/// `end` is exclusive.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UiRange {
+    pub start: UiPosition,
+    pub end: UiPosition,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UiActiveFile {
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UiSelection {
+    pub path: String,
+    pub range: UiRange,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_text: Option<String>,
+}
+
+/// `ui.context.update` params; unset fields are left out and keep the runtime's last value.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UiContextUpdate {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace_root: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_file: Option<UiActiveFile>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selection: Option<UiSelection>,
+}
+
+impl UiContextUpdate {
+    /// A notification: the runtime sends no answer.
+    pub fn to_message(&self) -> Value {
+        notification_message("ui.context.update", json!(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{UiActiveFile, UiContextUpdate, UiSelection};
+    use serde_json::json;
+
+    #[test]
+    fn context_update_is_a_notification_without_unset_fields() {
+        let update: UiContextUpdate = serde_json::from_value(json!({
+            "active_file": { "path": "src/main.rs" },
+            "selection": {
+                "path": "src/main.rs",
+                "range": { "start": { "line": 3, "column": 0 }, "end": { "line": 5, "column": 0 } }
+            }
+        }))
+        .expect("deserialize");
+        assert_eq!(
+            update.active_file,
+            Some(UiActiveFile {
+                path: "src/main.rs".to_string(),
+                language_id: None,
+            })
+        );
+        assert_eq!(update.selection.as_ref().map(|s| s.range.end.line), Some(5));
+        assert_eq!(
+            UiContextUpdate {
+                cwd: Some("/repo".to_string()),
+                selection: Some(UiSelection::default()),
+                ..UiContextUpdate::default()
+            }
+            .to_message(),
+            json!({
+                "jsonrpc": "2.0",
+                "method": "ui.context.update",
+                "params": {
+                    "cwd": "/repo",
+                    "selection": {
+                        "path": "",
+                        "range": {
+                            "start": { "line": 0, "column": 0 },
+                            "end": { "line": 0, "column": 0 }
+                        }
+                    }
+                }
+            })
+        );
+    }
+}
@@ -0,0 +1,339 @@
+//! Notifications about a run: `agent.event` payloads, `run.status`, `run.context` and
+//! `run.usage`.
+
+use crate::message::{bool_field, str_field, string_field};
+use serde::{Serialize, Serializer};
+use serde_json::{json, Value};
+
+/// `params.event` of an `agent.event` notification.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AgentEvent {
+    /// A complete assistant message.
+    Text {
+        content: String,
+    },
+    /// A chunk of the assistant message being streamed.
+    TextDelta {
+        content: String,
+    },
+    Reasoning {
+        content: String,
+    },
+    /// The run's final answer.
+    Final {
+        content: String,
+    },
+    StepStart,
+    StepComplete,
+    CompactionStart,
+    CompactionComplete {
+        compacted: bool,
+    },
+    ToolCall {
+        tool: String,
+        tool_call_id: Option<String>,
+        args: Value,
+    },
+    ToolResult {
+        tool: String,
+        tool_call_id: Option<String>,
+        /// A string, or JSON for structured results.
+        result: Value,
+        is_error: bool,
+        /// Tool output cache ref of the full result, when the runtime persisted it.
+        output_ref_id: Option<String>,
+    },
+    /// What a tool is about to change, sent before its permission prompt.
+    PermissionPreview {
+        tool: String,
+        tool_call_id: Option<String>,
+        file_path: Option<String>,
+        language: Option<String>,
+        diff: Option<String>,
+        summary: Option<String>,
+        truncated: bool,
+    },
+    /// The permission prompt for the tool call follows.
+    PermissionReady {
+        tool: String,
+        tool_call_id: Option<String>,
+    },
+    HiddenUserMessage {
+        content: String,
+    },
+    /// An event type this crate does not know; `event` is the whole payload.
+    Other {
+        event_type: String,
+        event: Value,
+    },
+}
+
+impl AgentEvent {
+    pub fn from_value(event: &Value) -> Self {
+        let content = || string_field(event, "content").unwrap_or_default();
+        let tool = || string_field(event, "tool").unwrap_or_else(|| "tool".to_string());
+        let tool_call_id = || string_field(event, "tool_call_id");
+        let event_type = str_field(event, "type").unwrap_or("event");
+        match event_type {
+            "text" => Self::Text { content: content() },
+            "text_delta" => Self::TextDelta { content: content() },
+            "reasoning" => Self::Reasoning { content: content() },
+            "final" => Self::Final { content: content() },
+            "step_start" => Self::StepStart,
+            "step_complete" => Self::StepComplete,
+            "compaction_start" => Self::CompactionStart,
+            "compaction_complete" => Self::CompactionComplete {
+                compacted: bool_field(event, "compacted"),
+            },
+            "tool_call" => Self::ToolCall {
+                tool: tool(),
+                tool_call_id: tool_call_id(),
+                args: event.get("args").cloned().unwrap_or(Value::Null),
+            },
+            "tool_result" => Self::ToolResult {
+                tool: tool(),
+                tool_call_id: tool_call_id(),
+                result: event.get("result").cloned().unwrap_or(Value::Null),
+                is_error: bool_field(event, "is_error"),
+                output_ref_id: string_field(event, "output_ref_id"),
+            },
+            "permission.preview" => Self::PermissionPreview {
+                tool: tool(),
+                tool_call_id: tool_call_id(),
+                file_path: string_field(event, "file_path"),
+                language: string_field(event, "language"),
+                diff: string_field(event, "diff"),
+                summary: string_field(event, "summary"),
+                truncated: bool_field(event, "truncated"),
+            },
+            "permission.ready" => Self::PermissionReady {
+                tool: tool(),
+                tool_call_id: tool_call_id(),
+            },
+            "hidden_user_message" => Self::HiddenUserMessage { content: content() },
+            _ => Self::Other {
+                event_type: event_type.to_string(),
+                event: event.clone(),
+            },
+        }
+    }
+
+    /// The wire `type` of the event.
+    pub fn event_type(&self) -> &str {
+        match self {
+            Self::Text { .. } => "text",
+            Self::TextDelta { .. } => "text_delta",
+            Self::Reasoning { .. } => "reasoning",
+            Self::Final { .. } => "final",
+            Self::StepStart => "step_start",
+            Self::StepComplete => "step_complete",
+            Self::CompactionStart => "compaction_start",
+            Self::CompactionComplete { .. } => "compaction_complete",
+            Self::ToolCall { .. } => "tool_call",
+            Self::ToolResult { .. } => "tool_result",
+            Self::PermissionPreview { .. } => "permission.preview",
+            Self::PermissionReady { .. } => "permission.ready",
+            Self::HiddenUserMessage { .. } => "hidden_user_message",
+            Self::Other { event_type, .. } => event_type,
+        }
+    }
+
+    /// The event as the runtime sends it; `from_value` reads it back unchanged.
+    pub fn to_value(&self) -> Value {
+        let mut value = match self {
+            Self::Text { content }
+            | Self::TextDelta { content }
+            | Self::Reasoning { content }
+            | Self::Final { content }
+            | Self::HiddenUserMessage { content } => json!({ "content": content }),
+            Self::StepStart | Self::StepComplete | Self::CompactionStart => json!({}),
+            Self::CompactionComplete { compacted } => json!({ "compacted": compacted }),
+            Self::ToolCall {
+                tool,
+                tool_call_id,
+                args,
+            } => json!({ "tool": tool, "tool_call_id": tool_call_id, "args": args }),
+            Self::ToolResult {
+                tool,
+                tool_call_id,
+                result,
+                is_error,
+                output_ref_id,
+            } => json!({
+                "tool": tool,
+                "tool_call_id": tool_call_id,
+                "result": result,
+                "is_error": is_error,
+                "output_ref_id": output_ref_id,
+            }),
+            Self::PermissionPreview {
+                tool,
+                tool_call_id,
+                file_path,
+                language,
+                diff,
+                summary,
+                truncated,
+            } => json!({
+                "tool": tool,
+                "tool_call_id": tool_call_id,
+                "file_path": file_path,
+                "language": language,
+                "diff": diff,
+                "summary": summary,
+                "truncated": truncated,
+            }),
+            Self::PermissionReady { tool, tool_call_id } => {
+                json!({ "tool": tool, "tool_call_id": tool_call_id })
+            }
+            Self::Other { event, .. } => return event.clone(),
+        };
+        value["type"] = json!(self.event_type());
+        value
+    }
+}
+
+impl Serialize for AgentEvent {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_value().serialize(serializer)
+    }
+}
+
+/// `run.status`: `running`, `awaiting_ui`, or one of the terminal `completed`, `error`,
+/// `cancelled`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RunStatus {
+    pub run_id: Option<String>,
+    pub status: String,
+    /// Empty when the runtime sent none.
+    pub message: String,
+}
+
+impl RunStatus {
+    pub fn from_params(params: &Value) -> Self {
+        Self {
+            run_id: string_field(params, "run_id"),
+            status: string_field(params, "status").unwrap_or_else(|| "unknown".to_string()),
+            message: string_field(params, "message").unwrap_or_default(),
+        }
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        matches!(self.status.as_str(), "completed" | "error" | "cancelled")
+    }
+}
+
+/// `run.context`: how much of the model's context window is left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct RunContext {
+    /// Capped at 100.
+    pub context_left_percent: Option<u8>,
+}
+
+impl RunContext {
+    pub fn from_params(params: &Value) -> Self {
+        Self {
+            context_left_percent: params
+                .get("context_left_percent")
+                .and_then(Value::as_u64)
+                .map(|percent| percent.min(100) as u8),
+        }
+    }
+}
+
+/// `run.usage`: tokens of one LLM call. `input_tokens` includes the cached ones.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RunUsage {
+    pub run_id: Option<String>,
+    pub provider: Option<String>,
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cached_input_tokens: u64,
+    pub cache_creation_tokens: u64,
+}
+
+impl RunUsage {
+    pub fn from_params(params: &Value) -> Self {
+        let tokens = |key| params.get(key).and_then(Value::as_u64).unwrap_or(0);
+        Self {
+            run_id: string_field(params, "run_id"),
+            provider: string_field(params, "provider"),
+            model: string_field(params, "model").unwrap_or_else(|| "unknown".to_string()),
+            input_tokens: tokens("input_tokens"),
+            output_tokens: tokens("output_tokens"),
+            cached_input_tokens: tokens("cached_input_tokens"),
+            cache_creation_tokens: tokens("cache_creation_tokens"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AgentEvent, RunContext, RunStatus, RunUsage};
+    use serde_json::json;
+
+    #[test]
+    fn events_round_trip_and_keep_unknown_types() {
+        let events = [
+            AgentEvent::TextDelta {
+                content: "Hel".to_string(),
+            },
+            AgentEvent::CompactionComplete { compacted: true },
+            AgentEvent::ToolCall {
+                tool: "shell".to_string(),
+                tool_call_id: Some("call-1".to_string()),
+                args: json!({ "command": "ls" }),
+            },
+            AgentEvent::ToolResult {
+                tool: "read".to_string(),
+                tool_call_id: Some("call-2".to_string()),
+                result: json!("1 | fn main() {}"),
+                is_error: false,
+                output_ref_id: None,
+            },
+            AgentEvent::PermissionPreview {
+                tool: "edit".to_string(),
+                tool_call_id: None,
+                file_path: Some("src/main.rs".to_string()),
+                language: None,
+                diff: Some("@@ -1 +1 @@".to_string()),
+                summary: None,
+                truncated: true,
+            },
+            AgentEvent::Other {
+                event_type: "plan_update".to_string(),
+                event: json!({ "type": "plan_update", "steps": 3 }),
+            },
+        ];
+        for event in events {
+            let wire = serde_json::to_string(&event).expect("serialize");
+            let value = serde_json::from_str(&wire).expect("json");
+            assert_eq!(AgentEvent::from_value(&value), event, "{wire}");
+        }
+
+        let lenient = AgentEvent::from_value(&json!({ "type": "tool_call", "args": [] }));
+        assert_eq!(
+            lenient,
+            AgentEvent::ToolCall {
+                tool: "tool".to_string(),
+                tool_call_id: None,
+                args: json!([]),
+            }
+        );
+        assert_eq!(AgentEvent::from_value(&json!({})).event_type(), "event");
+
+        let status = RunStatus::from_params(&json!({ "run_id": "r1", "status": "cancelled" }));
+        assert!(status.is_terminal());
+        assert_eq!(status.message, "");
+        assert_eq!(
+            RunContext::from_params(&json!({ "context_left_percent": 140 })).context_left_percent,
+            Some(100)
+        );
+        let usage = RunUsage::from_params(&json!({
+            "run_id": "r1", "model": "gpt-5", "input_tokens": 1200, "output_tokens": 80
+        }));
+        assert_eq!((usage.input_tokens, usage.cached_input_tokens), (1200, 0));
+        assert_eq!(usage.provider, None);
+    }
+}
@@ -0,0 +1,25 @@
+//! Messages of the codelia runtime protocol: newline-delimited JSON-RPC 2.0 over the runtime's
+//! stdin/stdout.
+//!
+//! - [`message`] classifies incoming lines and frames outgoing ones.
+//! - [`event`] and [`ui`] are the typed notifications and requests the runtime sends.
+//! - [`request`] builds the requests and replies a client sends; [`context`] is the
+//!   `ui.context.update` notification.
+//! - [`schema`] reports incoming messages whose fields drifted from what clients read.
+//!
+//! Parsing is lenient like the runtime's other clients: a missing or mistyped field falls back
+//! to its default, and [`schema::validate_runtime_message`] is how drift gets noticed.
+
+pub mod context;
+pub mod event;
+pub mod message;
+pub mod request;
+pub mod schema;
+pub mod ui;
+
+pub use context::{UiActiveFile, UiContextUpdate, UiPosition, UiRange, UiSelection};
+pub use event::{AgentEvent, RunContext, RunStatus, RunUsage};
+pub use message::{classify, Incoming, RpcResponse};
+pub use request::{ConfirmHunkDecision, ModelSet, Request, RunStart};
+pub use schema::{validate_runtime_message, SchemaMismatch};
+pub use ui::{ClientToolRequest, UiConfirmRequest, UiPickItem, UiPickRequest, UiPromptRequest};
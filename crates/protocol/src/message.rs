@@ -0,0 +1,183 @@
+//! JSON-RPC framing: what an incoming line is, and how outgoing lines are written.
+
+use crate::event::{AgentEvent, RunContext, RunStatus, RunUsage};
+use crate::ui::{ClientToolRequest, UiConfirmRequest, UiPickRequest, UiPromptRequest};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::io::Write;
+
+pub const JSONRPC_VERSION: &str = "2.0";
+
+/// The answer to a request the client sent; exactly one of `result` and `error` is usually set.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RpcResponse {
+    pub id: String,
+    pub result: Option<Value>,
+    pub error: Option<Value>,
+}
+
+/// One message from the runtime.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Incoming {
+    ConfirmRequest(UiConfirmRequest),
+    PromptRequest(UiPromptRequest),
+    PickRequest(UiPickRequest),
+    ClientToolCall(ClientToolRequest),
+    AgentEvent(AgentEvent),
+    RunStatus(RunStatus),
+    RunContext(RunContext),
+    RunUsage(RunUsage),
+    /// `run.diagnostics` params; their shape depends on `kind` and is left to the client.
+    RunDiagnostics(Value),
+    Response(RpcResponse),
+    /// Notifications with other methods and anything that is not JSON-RPC.
+    Unknown,
+}
+
+/// Classifies a decoded line. Unknown methods fall through to the response check, so a
+/// future method never hides a response.
+pub fn classify(value: &Value) -> Incoming {
+    let params = value.get("params").unwrap_or(&Value::Null);
+    let request_id = || string_field(value, "id").unwrap_or_default();
+    match str_field(value, "method") {
+        Some("ui.confirm.request") => {
+            return Incoming::ConfirmRequest(UiConfirmRequest::from_params(request_id(), params))
+        }
+        Some("ui.prompt.request") => {
+            return Incoming::PromptRequest(UiPromptRequest::from_params(request_id(), params))
+        }
+        Some("ui.pick.request") => {
+            return Incoming::PickRequest(UiPickRequest::from_params(request_id(), params))
+        }
+        Some("client.tool.call") => {
+            return Incoming::ClientToolCall(ClientToolRequest::from_params(request_id(), params))
+        }
+        Some("agent.event") => {
+            let event = params.get("event").unwrap_or(&Value::Null);
+            return Incoming::AgentEvent(AgentEvent::from_value(event));
+        }
+        Some("run.status") => return Incoming::RunStatus(RunStatus::from_params(params)),
+        Some("run.context") => return Incoming::RunContext(RunContext::from_params(params)),
+        Some("run.usage") => return Incoming::RunUsage(RunUsage::from_params(params)),
+        Some("run.diagnostics") => return Incoming::RunDiagnostics(params.clone()),
+        _ => {}
+    }
+    if value.get("id").is_none() {
+        return Incoming::Unknown;
+    }
+    let result = value.get("result").cloned();
+    let error = value.get("error").cloned();
+    if result.is_none() && error.is_none() {
+        return Incoming::Unknown;
+    }
+    Incoming::Response(RpcResponse {
+        id: str_field(value, "id").unwrap_or("").to_string(),
+        result,
+        error,
+    })
+}
+
+/// `{"jsonrpc":"2.0","id":…,"method":…,"params":…}`.
+pub fn request_message(id: &str, method: &str, params: Value) -> Value {
+    json!({
+        "jsonrpc": JSONRPC_VERSION,
+        "id": id,
+        "method": method,
+        "params": params
+    })
+}
+
+/// `{"jsonrpc":"2.0","method":…,"params":…}`; no id, so nothing answers it.
+pub fn notification_message(method: &str, params: Value) -> Value {
+    json!({
+        "jsonrpc": JSONRPC_VERSION,
+        "method": method,
+        "params": params
+    })
+}
+
+/// The reply to a runtime request such as `ui.confirm.request`.
+pub fn result_message(id: &str, result: Value) -> Value {
+    json!({
+        "jsonrpc": JSONRPC_VERSION,
+        "id": id,
+        "result": result
+    })
+}
+
+/// Writes one message as a line and flushes, so the runtime sees it right away.
+pub fn write_message(writer: &mut impl Write, message: &Value) -> std::io::Result<()> {
+    writer.write_all((message.to_string() + "\n").as_bytes())?;
+    writer.flush()
+}
+
+pub(crate) fn str_field<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(Value::as_str)
+}
+
+pub(crate) fn string_field(value: &Value, key: &str) -> Option<String> {
+    str_field(value, key).map(str::to_string)
+}
+
+pub(crate) fn bool_field(value: &Value, key: &str) -> bool {
+    value.get(key).and_then(Value::as_bool).unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{classify, request_message, write_message, Incoming, RpcResponse};
+    use crate::event::AgentEvent;
+    use serde_json::json;
+
+    #[test]
+    fn classifies_requests_events_and_responses() {
+        let confirm = classify(&json!({
+            "jsonrpc": "2.0", "id": "7", "method": "ui.confirm.request",
+            "params": { "title": "Run?", "message": "ls" }
+        }));
+        assert!(matches!(confirm, Incoming::ConfirmRequest(request) if request.id == "7"));
+        assert_eq!(
+            classify(&json!({
+                "method": "agent.event",
+                "params": { "event": { "type": "final", "content": "done" } }
+            })),
+            Incoming::AgentEvent(AgentEvent::Final {
+                content: "done".to_string()
+            })
+        );
+        assert_eq!(
+            classify(&json!({ "id": "3", "error": { "message": "nope" } })),
+            Incoming::Response(RpcResponse {
+                id: "3".to_string(),
+                result: None,
+                error: Some(json!({ "message": "nope" })),
+            })
+        );
+        assert_eq!(
+            classify(&json!({ "id": "4", "method": "future.thing", "result": 1 })),
+            Incoming::Response(RpcResponse {
+                id: "4".to_string(),
+                result: Some(json!(1)),
+                error: None,
+            })
+        );
+        assert_eq!(
+            classify(&json!({ "method": "future.thing" })),
+            Incoming::Unknown
+        );
+        assert_eq!(classify(&json!({ "id": "5" })), Incoming::Unknown);
+
+        let mut out = Vec::new();
+        write_message(
+            &mut out,
+            &request_message("1", "cwd.set", json!({ "path": "/tmp" })),
+        )
+        .expect("write");
+        let line = String::from_utf8(out).expect("utf8");
+        assert!(line.ends_with('\n') && line.matches('\n').count() == 1);
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&line).expect("json"),
+            json!({ "jsonrpc": "2.0", "id": "1", "method": "cwd.set", "params": { "path": "/tmp" } })
+        );
+    }
+}
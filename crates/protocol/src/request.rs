@@ -0,0 +1,373 @@
+//! What a client sends: requests to the runtime, and replies to the runtime's own requests.
+
+use crate::message::{request_message, result_message};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+pub const PROTOCOL_VERSION: &str = "0";
+
+/// A client → runtime request; [`Request::to_message`] adds the JSON-RPC envelope.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Request {
+    pub method: String,
+    pub params: Value,
+}
+
+/// Optional fields are left out rather than sent as `null`.
+fn insert_some(params: &mut Map<String, Value>, key: &str, value: Option<impl Serialize>) {
+    if let Some(value) = value {
+        params.insert(key.to_string(), json!(value));
+    }
+}
+
+impl Request {
+    /// Any method, e.g. to re-send a request under a new id.
+    pub fn new(method: &str, params: Value) -> Self {
+        Self {
+            method: method.to_string(),
+            params,
+        }
+    }
+
+    pub fn to_message(&self, id: &str) -> Value {
+        request_message(id, &self.method, self.params.clone())
+    }
+
+    /// The first request; the runtime only sends the UI requests the client says it supports.
+    pub fn initialize(client_name: &str, client_version: &str) -> Self {
+        Self::new(
+            "initialize",
+            json!({
+                "protocol_version": PROTOCOL_VERSION,
+                "client": { "name": client_name, "version": client_version },
+                "ui_capabilities": {
+                    "supports_confirm": true,
+                    "supports_prompt": true,
+                    "supports_pick": true,
+                    "supports_permission_preflight_events": true
+                }
+            }),
+        )
+    }
+
+    pub fn run_start(run: RunStart) -> Self {
+        let mut params = Map::new();
+        params.insert("input".to_string(), run.input);
+        insert_some(&mut params, "session_id", run.session_id.as_deref());
+        if run.force_compaction {
+            params.insert("force_compaction".to_string(), json!(true));
+        }
+        if run.dry_run {
+            params.insert("dry_run".to_string(), json!(true));
+        }
+        if !run.disabled_tools.is_empty() {
+            params.insert("disabled_tools".to_string(), json!(run.disabled_tools));
+        }
+        insert_some(&mut params, "tools", run.tools);
+        Self::new("run.start", Value::Object(params))
+    }
+
+    pub fn run_cancel(run_id: &str, reason: Option<&str>) -> Self {
+        let mut params = Map::new();
+        params.insert("run_id".to_string(), json!(run_id));
+        insert_some(&mut params, "reason", reason);
+        Self::new("run.cancel", Value::Object(params))
+    }
+
+    /// `show_all` lists sessions of every workspace instead of the current one.
+    pub fn session_list(limit: Option<usize>, show_all: bool) -> Self {
+        let mut params = Map::new();
+        insert_some(&mut params, "limit", limit);
+        let scope = if show_all { "all" } else { "current_workspace" };
+        params.insert("scope".to_string(), json!(scope));
+        Self::new("session.list", Value::Object(params))
+    }
+
+    pub fn session_history(
+        session_id: &str,
+        max_runs: Option<usize>,
+        max_events: Option<usize>,
+    ) -> Self {
+        let mut params = Map::new();
+        params.insert("session_id".to_string(), json!(session_id));
+        insert_some(&mut params, "max_runs", max_runs);
+        insert_some(&mut params, "max_events", max_events);
+        Self::new("session.history", Value::Object(params))
+    }
+
+    pub fn auth_logout(clear_session: bool) -> Self {
+        Self::new("auth.logout", json!({ "clear_session": clear_session }))
+    }
+
+    pub fn model_list(provider: Option<&str>, include_details: bool) -> Self {
+        let mut params = Map::new();
+        insert_some(&mut params, "provider", provider);
+        params.insert("include_details".to_string(), json!(include_details));
+        Self::new("model.list", Value::Object(params))
+    }
+
+    pub fn model_set(model: ModelSet) -> Self {
+        let mut params = Map::new();
+        if !model.reset {
+            params.insert("name".to_string(), json!(model.model));
+        }
+        insert_some(&mut params, "provider", model.provider.as_deref());
+        insert_some(&mut params, "reasoning", model.reasoning.as_deref());
+        insert_some(&mut params, "fast", model.fast);
+        insert_some(&mut params, "scope", model.scope.as_deref());
+        insert_some(&mut params, "base_url", model.base_url.as_deref());
+        if model.reset {
+            params.insert("reset".to_string(), json!(true));
+        }
+        Self::new("model.set", Value::Object(params))
+    }
+
+    pub fn cwd_set(path: &str) -> Self {
+        Self::new("cwd.set", json!({ "path": path }))
+    }
+
+    pub fn theme_set(name: &str) -> Self {
+        Self::new("theme.set", json!({ "name": name }))
+    }
+
+    pub fn cache_list() -> Self {
+        Self::new("cache.list", json!({}))
+    }
+
+    pub fn cache_read(ref_id: &str, max_bytes: usize) -> Self {
+        Self::new(
+            "cache.read",
+            json!({ "ref_id": ref_id, "max_bytes": max_bytes }),
+        )
+    }
+
+    pub fn cache_delete(ref_id: &str) -> Self {
+        Self::new("cache.delete", json!({ "ref_id": ref_id }))
+    }
+
+    /// `shell.exec` runs to completion; `shell.start` returns a task id right away.
+    pub fn shell(
+        background: bool,
+        command: &str,
+        timeout_seconds: Option<u64>,
+        env: Option<&Map<String, Value>>,
+    ) -> Self {
+        let mut params = Map::new();
+        params.insert("command".to_string(), json!(command));
+        insert_some(&mut params, "timeout_seconds", timeout_seconds);
+        insert_some(&mut params, "env", env);
+        let method = if background {
+            "shell.start"
+        } else {
+            "shell.exec"
+        };
+        Self::new(method, Value::Object(params))
+    }
+
+    pub fn shell_wait(task_id: &str) -> Self {
+        Self::new("shell.wait", json!({ "task_id": task_id }))
+    }
+
+    pub fn shell_detach(task_id: &str) -> Self {
+        Self::new("shell.detach", json!({ "task_id": task_id }))
+    }
+
+    pub fn task_list() -> Self {
+        Self::new("task.list", json!({}))
+    }
+
+    pub fn task_status(task_id: &str) -> Self {
+        Self::new("task.status", json!({ "task_id": task_id }))
+    }
+
+    pub fn task_cancel(task_id: &str) -> Self {
+        Self::new("task.cancel", json!({ "task_id": task_id }))
+    }
+
+    /// Calls a runtime tool directly. With `require_permission` the call goes through the
+    /// session permission policy and UI confirm first; a denial answers `ok: false`.
+    pub fn tool_call(name: &str, arguments: Value, require_permission: bool) -> Self {
+        let mut params = json!({ "name": name, "arguments": arguments });
+        if require_permission {
+            params["require_permission"] = json!(true);
+        }
+        Self::new("tool.call", params)
+    }
+
+    pub fn mcp_list(scope: Option<&str>) -> Self {
+        let mut params = Map::new();
+        insert_some(&mut params, "scope", scope);
+        Self::new("mcp.list", Value::Object(params))
+    }
+
+    pub fn context_inspect(include_agents: bool, include_skills: bool) -> Self {
+        Self::new(
+            "context.inspect",
+            json!({ "include_agents": include_agents, "include_skills": include_skills }),
+        )
+    }
+
+    pub fn skills_list(force_reload: bool) -> Self {
+        Self::new("skills.list", json!({ "force_reload": force_reload }))
+    }
+}
+
+/// `run.start` params; `input` is `{"type":"text",…}` or `{"type":"parts",…}`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunStart {
+    pub input: Value,
+    pub session_id: Option<String>,
+    pub force_compaction: bool,
+    pub dry_run: bool,
+    pub disabled_tools: Vec<String>,
+    /// Client tool definitions; the runtime answers their calls with `client.tool.call`.
+    pub tools: Option<Value>,
+}
+
+/// `model.set` params; `reset` drops the override at `scope` and ignores `model`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelSet {
+    pub model: String,
+    pub provider: Option<String>,
+    pub reasoning: Option<String>,
+    pub fast: Option<bool>,
+    pub scope: Option<String>,
+    pub reset: bool,
+    /// Endpoint for the `local` provider (an Ollama or llama.cpp server).
+    pub base_url: Option<String>,
+}
+
+/// Per-hunk verdict from a large-diff review; `index` is 1-based like the preview list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfirmHunkDecision {
+    pub index: usize,
+    pub label: String,
+    pub accepted: bool,
+}
+
+/// Answers `ui.confirm.request`.
+pub fn confirm_reply(
+    id: &str,
+    ok: bool,
+    remember: bool,
+    reason: Option<&str>,
+    hunks: &[ConfirmHunkDecision],
+) -> Value {
+    let mut result = json!({ "ok": ok, "remember": remember, "reason": reason });
+    if !hunks.is_empty() {
+        result["hunks"] = hunks
+            .iter()
+            .map(|hunk| {
+                json!({ "index": hunk.index, "header": hunk.label, "accepted": hunk.accepted })
+            })
+            .collect();
+    }
+    result_message(id, result)
+}
+
+/// Answers `ui.prompt.request`; `None` cancels.
+pub fn prompt_reply(id: &str, value: Option<&str>) -> Value {
+    result_message(id, json!({ "value": value }))
+}
+
+/// Answers `ui.pick.request`; no ids cancels.
+pub fn pick_reply(id: &str, ids: &[String]) -> Value {
+    result_message(id, json!({ "ids": ids }))
+}
+
+/// Answers `client.tool.call` with a JSON result.
+pub fn client_tool_json_reply(id: &str, value: Value) -> Value {
+    result_message(
+        id,
+        json!({ "ok": true, "result": { "type": "json", "value": value } }),
+    )
+}
+
+pub fn client_tool_text_reply(id: &str, text: &str) -> Value {
+    result_message(
+        id,
+        json!({ "ok": true, "result": { "type": "text", "text": text } }),
+    )
+}
+
+pub fn client_tool_error_reply(id: &str, error: &str) -> Value {
+    result_message(id, json!({ "ok": false, "error": error }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{confirm_reply, ConfirmHunkDecision, ModelSet, Request, RunStart};
+    use serde_json::json;
+
+    #[test]
+    fn requests_leave_out_unset_fields() {
+        let run = Request::run_start(RunStart {
+            input: json!({ "type": "text", "text": "hi" }),
+            dry_run: true,
+            ..RunStart::default()
+        });
+        assert_eq!(
+            run.to_message("1"),
+            json!({
+                "jsonrpc": "2.0",
+                "id": "1",
+                "method": "run.start",
+                "params": { "input": { "type": "text", "text": "hi" }, "dry_run": true }
+            })
+        );
+        let reset = Request::model_set(ModelSet {
+            model: "ignored".to_string(),
+            scope: Some("workspace".to_string()),
+            reset: true,
+            ..ModelSet::default()
+        });
+        assert_eq!(reset.params, json!({ "scope": "workspace", "reset": true }));
+        let local = Request::model_set(ModelSet {
+            model: "llama3.1:8b".to_string(),
+            provider: Some("local".to_string()),
+            base_url: Some("http://127.0.0.1:11434".to_string()),
+            ..ModelSet::default()
+        });
+        assert_eq!(
+            local.params,
+            json!({
+                "name": "llama3.1:8b",
+                "provider": "local",
+                "base_url": "http://127.0.0.1:11434"
+            })
+        );
+        assert_eq!(
+            Request::shell(true, "npm run dev", None, None),
+            Request::new("shell.start", json!({ "command": "npm run dev" }))
+        );
+        assert_eq!(
+            Request::tool_call("read", json!({}), true).params["require_permission"],
+            true
+        );
+
+        let reply = confirm_reply(
+            "9",
+            true,
+            false,
+            None,
+            &[ConfirmHunkDecision {
+                index: 1,
+                label: "@@ -1 +1 @@".to_string(),
+                accepted: false,
+            }],
+        );
+        assert_eq!(
+            reply,
+            json!({
+                "jsonrpc": "2.0",
+                "id": "9",
+                "result": {
+                    "ok": true,
+                    "remember": false,
+                    "reason": null,
+                    "hunks": [{ "index": 1, "header": "@@ -1 +1 @@", "accepted": false }]
+                }
+            })
+        );
+    }
+}
@@ -0,0 +1,310 @@
+//! Field checks of incoming messages against what clients read.
+
+use serde::Serialize;
+use serde_json::Value;
+
+const RAW_PAYLOAD_MAX_CHARS: usize = 2_000;
+
+/// A runtime message whose shape differs from what clients read; lenient parsing still
+/// produced a value with defaults.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SchemaMismatch {
+    /// `run.status`, or `agent.event/<type>` for agent events.
+    pub method: String,
+    pub problems: Vec<String>,
+    /// The offending line, capped for debug logs.
+    pub raw: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldType {
+    Str,
+    Bool,
+    UInt,
+    Array,
+    Object,
+    Any,
+}
+
+impl FieldType {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Str => "string",
+            Self::Bool => "boolean",
+            Self::UInt => "non-negative integer",
+            Self::Array => "array",
+            Self::Object => "object",
+            Self::Any => "value",
+        }
+    }
+
+    fn accepts(self, value: &Value) -> bool {
+        match self {
+            Self::Str => value.is_string(),
+            Self::Bool => value.is_boolean(),
+            Self::UInt => value.is_u64(),
+            Self::Array => value.is_array(),
+            Self::Object => value.is_object(),
+            Self::Any => true,
+        }
+    }
+}
+
+/// A field clients read; `required` ones fall back to a default when absent.
+struct FieldSpec {
+    name: &'static str,
+    ty: FieldType,
+    required: bool,
+}
+
+const fn req(name: &'static str, ty: FieldType) -> FieldSpec {
+    FieldSpec {
+        name,
+        ty,
+        required: true,
+    }
+}
+
+const fn opt(name: &'static str, ty: FieldType) -> FieldSpec {
+    FieldSpec {
+        name,
+        ty,
+        required: false,
+    }
+}
+
+use FieldType::{Any, Array, Bool, Object, Str, UInt};
+
+/// `params` of each method in [`crate::Incoming`]; only the fields actually read.
+const PARAM_SCHEMAS: &[(&str, &[FieldSpec])] = &[
+    (
+        "ui.confirm.request",
+        &[
+            req("title", Str),
+            req("message", Str),
+            opt("danger_level", Str),
+            opt("confirm_label", Str),
+            opt("cancel_label", Str),
+            opt("allow_remember", Bool),
+            opt("allow_reason", Bool),
+            opt("tool_call_id", Str),
+        ],
+    ),
+    (
+        "ui.prompt.request",
+        &[
+            req("title", Str),
+            req("message", Str),
+            opt("default_value", Str),
+            opt("multiline", Bool),
+            opt("secret", Bool),
+        ],
+    ),
+    (
+        "ui.pick.request",
+        &[req("title", Str), req("items", Array), opt("multi", Bool)],
+    ),
+    (
+        "client.tool.call",
+        &[req("name", Str), opt("arguments", Object)],
+    ),
+    ("agent.event", &[req("event", Object)]),
+    ("run.context", &[req("context_left_percent", UInt)]),
+    (
+        "run.usage",
+        &[
+            req("model", Str),
+            req("input_tokens", UInt),
+            req("output_tokens", UInt),
+            opt("provider", Str),
+            opt("cached_input_tokens", UInt),
+            opt("cache_creation_tokens", UInt),
+        ],
+    ),
+    ("run.diagnostics", &[req("kind", Str)]),
+    (
+        "run.status",
+        &[req("run_id", Str), req("status", Str), opt("message", Str)],
+    ),
+];
+
+/// `params.event` of each typed [`crate::AgentEvent`].
+const EVENT_SCHEMAS: &[(&str, &[FieldSpec])] = &[
+    ("text", &[req("content", Str)]),
+    ("text_delta", &[req("content", Str)]),
+    ("reasoning", &[req("content", Str)]),
+    ("final", &[req("content", Str)]),
+    ("hidden_user_message", &[req("content", Str)]),
+    ("compaction_complete", &[req("compacted", Bool)]),
+    (
+        "tool_call",
+        &[
+            req("tool", Str),
+            req("tool_call_id", Str),
+            req("args", Object),
+        ],
+    ),
+    (
+        "tool_result",
+        &[
+            req("tool", Str),
+            req("tool_call_id", Str),
+            req("result", Any),
+            opt("is_error", Bool),
+            opt("output_ref_id", Str),
+        ],
+    ),
+    (
+        "permission.preview",
+        &[
+            req("tool", Str),
+            opt("tool_call_id", Str),
+            opt("file_path", Str),
+            opt("language", Str),
+            opt("diff", Str),
+            opt("summary", Str),
+            opt("truncated", Bool),
+        ],
+    ),
+    (
+        "permission.ready",
+        &[req("tool", Str), opt("tool_call_id", Str)],
+    ),
+];
+
+const REQUEST_METHODS: &[&str] = &[
+    "ui.confirm.request",
+    "ui.prompt.request",
+    "ui.pick.request",
+    "client.tool.call",
+];
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn check_fields(container: Option<&Value>, path: &str, specs: &[FieldSpec], out: &mut Vec<String>) {
+    let Some(container) = container.filter(|value| !value.is_null()) else {
+        if specs.iter().any(|spec| spec.required) {
+            out.push(format!("missing field `{path}`"));
+        }
+        return;
+    };
+    let Some(object) = container.as_object() else {
+        out.push(format!(
+            "`{path}` should be an object, got {}",
+            json_type(container)
+        ));
+        return;
+    };
+    for spec in specs {
+        match object.get(spec.name).filter(|value| !value.is_null()) {
+            None if spec.required => out.push(format!(
+                "missing field `{path}.{}` ({})",
+                spec.name,
+                spec.ty.label()
+            )),
+            None => {}
+            Some(value) if !spec.ty.accepts(value) => out.push(format!(
+                "unexpected type for `{path}.{}`: expected {}, got {}",
+                spec.name,
+                spec.ty.label(),
+                json_type(value)
+            )),
+            Some(_) => {}
+        }
+    }
+}
+
+fn schema_for<'a>(schemas: &'a [(&str, &'a [FieldSpec])], name: &str) -> Option<&'a [FieldSpec]> {
+    schemas
+        .iter()
+        .find(|(candidate, _)| *candidate == name)
+        .map(|(_, specs)| *specs)
+}
+
+/// Checks a runtime notification or request against the fields clients read. Unknown
+/// methods and event types, responses, and extra fields are not reported, so newer runtimes
+/// can add to the protocol without tripping the check.
+pub fn validate_runtime_message(value: &Value, raw: &str) -> Option<SchemaMismatch> {
+    let method = value.get("method")?.as_str()?;
+    let specs = schema_for(PARAM_SCHEMAS, method)?;
+    let mut label = method.to_string();
+    let mut problems = Vec::new();
+    if REQUEST_METHODS.contains(&method) && !value.get("id").is_some_and(Value::is_string) {
+        problems.push("missing request `id` (string)".to_string());
+    }
+    let params = value.get("params");
+    check_fields(params, "params", specs, &mut problems);
+
+    if method == "agent.event" {
+        if let Some(event) = params
+            .and_then(|params| params.get("event"))
+            .filter(|event| event.is_object())
+        {
+            match event.get("type").and_then(Value::as_str) {
+                None => problems.push("missing field `params.event.type` (string)".to_string()),
+                Some(event_type) => {
+                    label = format!("agent.event/{event_type}");
+                    if let Some(specs) = schema_for(EVENT_SCHEMAS, event_type) {
+                        check_fields(Some(event), "params.event", specs, &mut problems);
+                    }
+                }
+            }
+        }
+    }
+
+    (!problems.is_empty()).then(|| SchemaMismatch {
+        method: label,
+        problems,
+        raw: raw.chars().take(RAW_PAYLOAD_MAX_CHARS).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate_runtime_message;
+    use serde_json::json;
+
+    #[test]
+    fn reports_drifted_fields_and_ignores_unknown_ones() {
+        let value = json!({
+            "jsonrpc": "2.0",
+            "method": "agent.event",
+            "params": { "event": { "type": "tool_call", "tool": 3, "args": {} } }
+        });
+        let mismatch = validate_runtime_message(&value, &"x".repeat(3_000)).expect("mismatch");
+        assert_eq!(mismatch.method, "agent.event/tool_call");
+        assert_eq!(
+            mismatch.problems,
+            vec![
+                "unexpected type for `params.event.tool`: expected string, got number",
+                "missing field `params.event.tool_call_id` (string)",
+            ]
+        );
+        assert_eq!(mismatch.raw.len(), 2_000);
+
+        let confirm =
+            json!({ "method": "ui.confirm.request", "params": { "title": "t", "message": "m" } });
+        assert_eq!(
+            validate_runtime_message(&confirm, "")
+                .expect("mismatch")
+                .problems,
+            vec!["missing request `id` (string)"]
+        );
+        for value in [
+            json!({ "method": "future.method", "params": 1 }),
+            json!({ "id": "1", "result": {} }),
+            json!({ "method": "run.context", "params": { "context_left_percent": 40, "extra": true } }),
+        ] {
+            assert!(validate_runtime_message(&value, "").is_none(), "{value}");
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Requests the runtime sends to the client and waits on: confirm, prompt and pick dialogs,
+//! and calls of client-side tools. Answer them with the replies in [`crate::request`].
+
+use crate::message::{bool_field, string_field};
+use serde::Serialize;
+use serde_json::Value;
+
+/// `ui.confirm.request`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UiConfirmRequest {
+    pub id: String,
+    pub title: String,
+    pub message: String,
+    pub danger_level: Option<String>,
+    pub confirm_label: Option<String>,
+    pub cancel_label: Option<String>,
+    pub allow_remember: bool,
+    pub allow_reason: bool,
+    /// Set on permission confirms: the call announced by `permission.preview`/`permission.ready`.
+    pub tool_call_id: Option<String>,
+}
+
+impl UiConfirmRequest {
+    pub fn from_params(id: String, params: &Value) -> Self {
+        Self {
+            id,
+            title: string_field(params, "title").unwrap_or_else(|| "Confirm".to_string()),
+            message: string_field(params, "message").unwrap_or_default(),
+            danger_level: string_field(params, "danger_level"),
+            confirm_label: string_field(params, "confirm_label"),
+            cancel_label: string_field(params, "cancel_label"),
+            allow_remember: bool_field(params, "allow_remember"),
+            allow_reason: bool_field(params, "allow_reason"),
+            tool_call_id: string_field(params, "tool_call_id"),
+        }
+    }
+}
+
+/// `ui.prompt.request`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UiPromptRequest {
+    pub id: String,
+    pub title: String,
+    pub message: String,
+    pub default_value: Option<String>,
+    pub multiline: bool,
+    pub secret: bool,
+}
+
+impl UiPromptRequest {
+    pub fn from_params(id: String, params: &Value) -> Self {
+        Self {
+            id,
+            title: string_field(params, "title").unwrap_or_else(|| "Prompt".to_string()),
+            message: string_field(params, "message").unwrap_or_default(),
+            default_value: string_field(params, "default_value"),
+            multiline: bool_field(params, "multiline"),
+            secret: bool_field(params, "secret"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UiPickItem {
+    pub id: String,
+    pub label: String,
+    pub detail: Option<String>,
+}
+
+/// `ui.pick.request`; items that are not objects are skipped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UiPickRequest {
+    pub id: String,
+    pub title: String,
+    pub items: Vec<UiPickItem>,
+    pub multi: bool,
+}
+
+impl UiPickRequest {
+    pub fn from_params(id: String, params: &Value) -> Self {
+        let items = params
+            .get("items")
+            .and_then(Value::as_array)
+            .map(|items| {
+                items
+                    .iter()
+                    .filter(|item| item.is_object())
+                    .map(|item| UiPickItem {
+                        id: string_field(item, "id").unwrap_or_default(),
+                        label: string_field(item, "label").unwrap_or_default(),
+                        detail: string_field(item, "detail"),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            id,
+            title: string_field(params, "title").unwrap_or_else(|| "Pick".to_string()),
+            items,
+            multi: bool_field(params, "multi"),
+        }
+    }
+}
+
+/// `client.tool.call`: the model called a tool the client registered with `run.start`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ClientToolRequest {
+    pub id: String,
+    pub name: String,
+    pub arguments: Value,
+}
+
+impl ClientToolRequest {
+    pub fn from_params(id: String, params: &Value) -> Self {
+        Self {
+            id,
+            name: string_field(params, "name").unwrap_or_default(),
+            arguments: params.get("arguments").cloned().unwrap_or(Value::Null),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{UiConfirmRequest, UiPickRequest};
+    use serde_json::json;
+
+    #[test]
+    fn missing_fields_fall_back_to_dialog_defaults() {
+        let confirm = UiConfirmRequest::from_params(
+            "c1".to_string(),
+            &json!({ "message": "Run rm?", "allow_remember": true, "danger_level": 3 }),
+        );
+        assert_eq!(confirm.title, "Confirm");
+        assert_eq!(confirm.message, "Run rm?");
+        assert!(confirm.allow_remember && !confirm.allow_reason);
+        assert_eq!(confirm.danger_level, None);
+
+        let pick = UiPickRequest::from_params(
+            "p1".to_string(),
+            &json!({ "items": [{ "id": "a", "label": "A", "detail": "first" }, "b"] }),
+        );
+        assert_eq!(pick.title, "Pick");
+        assert_eq!(pick.items.len(), 1);
+        assert_eq!(pick.items[0].detail.as_deref(), Some("first"));
+        assert_eq!(
+            serde_json::to_value(&pick).expect("serialize")["items"][0]["label"],
+            "A"
+        );
+    }
+}
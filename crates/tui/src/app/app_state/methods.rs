@@ -1,12 +1,21 @@
 use super::{
-    new_composer_nonce, AppState, ErrorDetailMode, ERROR_DETAIL_MAX_LINES, ERROR_SUMMARY_MAX_CHARS,
+    new_composer_nonce, AppState, ErrorDetailMode, ReadPageRecord, ERROR_DETAIL_MAX_LINES,
+    ERROR_SUMMARY_MAX_CHARS,
 };
+use crate::app::render::glyphs::ascii_glyphs;
 use crate::app::state::{
-    ConfirmPhase, LogKind, LogLine, LogTone, PendingImageAttachment, RenderState, StatusLineMode,
-    SyncPhase,
+    model_key, next_esc_action, run_status_announcement, terminal_title, ConfirmPhase, EscAction,
+    EscContext, FailureStreakChange, LogKind, LogLine, LogTone, PendingFileAttachment,
+    PendingImageAttachment, RenderState, ScrollAnchor, StatusLineMode, SyncPhase,
+    TerminalTitleFields,
 };
+use crate::app::util::file_mentions::mentions_file;
+use crate::app::util::settings::SpinnerStyle;
 use crate::app::util::{attachments::referenced_attachment_ids, PerfMemorySample};
-use std::time::{Duration, Instant};
+use std::borrow::Cow;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const MAX_READ_PAGE_RECORDS: usize = 64;
 
 fn truncate_chars(text: &str, max: usize) -> String {
     if max == 0 {
@@ -64,6 +73,7 @@ impl AppState {
         self.log_version = self.log_version.wrapping_add(1);
         self.wrapped_log_cache = None;
         self.log_changed = true;
+        self.log_timeline.record(self.log.len(), Instant::now());
     }
 
     pub fn is_running(&self) -> bool {
@@ -124,11 +134,22 @@ impl AppState {
                 self.run_started_at = Some(Instant::now());
                 self.run_elapsed = None;
                 self.context_left_percent = None;
+                let key = model_key(
+                    self.runtime_info.current_provider.as_deref().unwrap_or("-"),
+                    self.runtime_info.current_model.as_deref().unwrap_or("-"),
+                );
+                self.provider_stats.start_run(&key);
             }
             if matches!(status.as_str(), "completed" | "error" | "cancelled") {
                 if let Some(start) = self.run_started_at {
                     self.run_elapsed = Some(start.elapsed());
                 }
+                self.provider_stats.finish_run(&status, self.run_elapsed);
+            }
+            if let Some(text) =
+                run_status_announcement(previous.as_deref(), &status, self.run_elapsed)
+            {
+                self.live_region.announce(text);
             }
         }
     }
@@ -138,6 +159,37 @@ impl AppState {
             .or_else(|| self.run_started_at.map(|start| start.elapsed()))
     }
 
+    /// What Esc on the main screen does next under the `esc_policy` setting.
+    pub fn next_esc_action(&self, now: Instant) -> Option<EscAction> {
+        let composer_empty =
+            self.input.current().is_empty() && self.pending_image_attachments.is_empty();
+        next_esc_action(
+            self.settings.esc_policy,
+            &EscContext {
+                search_open: self.log_search.is_some(),
+                scrolled_back: self.scroll_from_bottom > 0,
+                empty_shell_mode: self.bang_input_mode && composer_empty,
+                composer_has_content: !composer_empty,
+                cancellable_run: self.is_running()
+                    && self.rpc_pending.run_cancel_id.is_none()
+                    && self.runtime_info.active_run_id.is_some(),
+                cancel_armed_at: self.esc_cancel_armed_at,
+                motd_visible: self.motd.is_some(),
+            },
+            now,
+        )
+    }
+
+    /// Terminal/tmux title; `launch_dir` stands in until the runtime reports its working dir.
+    pub fn terminal_title(&self, launch_dir: Option<&str>) -> String {
+        terminal_title(&TerminalTitleFields {
+            working_dir: self.runtime_info.working_dir.as_deref().or(launch_dir),
+            session_id: self.runtime_info.session_id.as_deref(),
+            run_status: self.run_status.as_deref(),
+            elapsed: self.run_duration(),
+        })
+    }
+
     pub fn toggle_status_line_mode(&mut self) {
         self.status_line_mode = match self.status_line_mode {
             StatusLineMode::Info => StatusLineMode::Help,
@@ -147,6 +199,41 @@ impl AppState {
 
     pub fn set_error_detail_mode(&mut self, mode: ErrorDetailMode) {
         self.error_detail_mode = mode;
+        // An explicit choice outlives the failure streak that may have changed the mode.
+        self.failure_streak.restore_summary = false;
+    }
+
+    /// Tracks tool call and run outcomes; a failure streak shows error details until a success.
+    pub fn record_outcome(&mut self, ok: bool) {
+        let change = if ok {
+            self.failure_streak.record_success()
+        } else {
+            self.failure_streak.record_failure()
+        };
+        match change {
+            Some(FailureStreakChange::Escalated(count)) => {
+                if self.error_detail_mode == ErrorDetailMode::Summary {
+                    self.error_detail_mode = ErrorDetailMode::Detail;
+                    self.failure_streak.restore_summary = true;
+                }
+                self.push_line(
+                    LogKind::Error,
+                    format!(
+                        "{count} consecutive failures — showing error details until the next success; consider /errors show or /runtime"
+                    ),
+                );
+            }
+            Some(FailureStreakChange::Recovered)
+                if std::mem::take(&mut self.failure_streak.restore_summary) =>
+            {
+                self.error_detail_mode = ErrorDetailMode::Summary;
+                self.push_line(
+                    LogKind::Status,
+                    "Back to summary error details after a success.",
+                );
+            }
+            _ => {}
+        }
     }
 
     fn push_error_detail_lines(lines: &mut Vec<LogLine>, detail: &str) {
@@ -276,9 +363,31 @@ impl AppState {
         true
     }
 
+    /// `reduce_effects = on|off` in tui.toml overrides slow-terminal detection.
+    pub fn effects_reduced(&self) -> bool {
+        self.low_bandwidth
+            || self
+                .settings
+                .reduce_effects
+                .unwrap_or(self.slow_terminal.detected)
+    }
+
+    /// `--low-bandwidth` does not animate: every frame is a redraw sent over the link.
+    fn spinner_style(&self) -> SpinnerStyle {
+        if self.low_bandwidth {
+            return SpinnerStyle::None;
+        }
+        self.settings.spinner
+    }
+
+    /// ASCII glyphs animate with the `line` frames; the others would all become `*`.
     pub fn spinner_frame(&self) -> &'static str {
-        const FRAMES: [&str; 8] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧"];
-        FRAMES[self.spinner_index % FRAMES.len()]
+        let style = match self.spinner_style() {
+            SpinnerStyle::None => SpinnerStyle::None,
+            _ if ascii_glyphs() => SpinnerStyle::Line,
+            style => style,
+        };
+        style.frame(self.spinner_index)
     }
 
     pub fn update_spinner(&mut self, now: Instant) -> bool {
@@ -287,7 +396,8 @@ impl AppState {
             self.spinner_last_tick = now;
             return false;
         }
-        if now.duration_since(self.spinner_last_tick) >= Duration::from_millis(120) {
+        let interval = self.spinner_style().interval(self.effects_reduced());
+        if now.duration_since(self.spinner_last_tick) >= interval {
             self.spinner_last_tick = now;
             self.spinner_index = self.spinner_index.saturating_add(1);
             return true;
@@ -297,12 +407,17 @@ impl AppState {
 
     pub fn clear_log(&mut self) {
         self.log.clear();
+        self.log_timeline.reset(Instant::now());
         self.pending_component_lines.clear();
         self.progress_component_lines.clear();
         self.compaction_sequence_by_scope.clear();
         self.active_compaction_component_by_scope.clear();
         self.permission_preview_by_tool_call.clear();
         self.permission_ready_tool_call_ids.clear();
+        self.pending_hunk_reviews.clear();
+        self.permission_tool_by_call.clear();
+        self.read_pages.clear();
+        self.context_pins.take_mark();
         self.last_error_detail = None;
         self.scroll_from_bottom = 0;
         self.mark_log_changed();
@@ -316,6 +431,25 @@ impl AppState {
         };
     }
 
+    /// Remembers a pageable read result and returns the number used by `/page <n>`.
+    pub fn register_read_page(&mut self, ref_id: String, label: String) -> usize {
+        let number = self.next_read_page_number;
+        self.next_read_page_number = number.saturating_add(1);
+        self.read_pages.insert(
+            number,
+            ReadPageRecord {
+                ref_id,
+                label,
+                next_offset: 0,
+                exhausted: false,
+            },
+        );
+        while self.read_pages.len() > MAX_READ_PAGE_RECORDS {
+            self.read_pages.pop_first();
+        }
+        number
+    }
+
     pub fn referenced_attachment_ids(&self) -> Vec<String> {
         referenced_attachment_ids(
             &self.input.current(),
@@ -328,6 +462,17 @@ impl AppState {
         self.referenced_attachment_ids().len()
     }
 
+    /// `/batch` items not yet handed to the runtime (queued, dispatching, or held for startup).
+    pub fn prompt_batch_waiting(&self) -> Option<usize> {
+        let batch = self.prompt_batch.as_ref()?;
+        let waiting_ids = self
+            .pending_prompt_queue
+            .iter()
+            .chain(self.dispatching_prompt.iter())
+            .map(|prompt| prompt.queue_id.as_str());
+        Some(batch.waiting(waiting_ids))
+    }
+
     pub fn prune_unreferenced_attachments(&mut self) {
         let keep = self
             .referenced_attachment_ids()
@@ -340,10 +485,19 @@ impl AppState {
     pub fn clear_composer(&mut self) {
         self.input.clear();
         self.pending_image_attachments.clear();
+        self.pending_file_attachments.clear();
         self.composer_nonce = new_composer_nonce();
         self.bang_input_mode = false;
     }
 
+    /// Attached files whose `@path` is still in `text`.
+    pub fn referenced_file_attachments(&self, text: &str) -> Vec<&PendingFileAttachment> {
+        self.pending_file_attachments
+            .iter()
+            .filter(|file| mentions_file(text, &file.path))
+            .collect()
+    }
+
     pub fn next_image_attachment_id(&mut self) -> String {
         self.next_attachment_id = self.next_attachment_id.saturating_add(1);
         format!("img{}", self.next_attachment_id)
@@ -353,15 +507,122 @@ impl AppState {
         self.pending_image_attachments.insert(id, attachment);
     }
 
-    pub fn replace_log_line(&mut self, index: usize, line: LogLine) {
+    /// Rewrites any stored secret value in log spans before they reach the transcript.
+    fn redact_log_line(&self, mut line: LogLine) -> LogLine {
+        if self.secrets.is_empty() {
+            return line;
+        }
+        for span in &mut line.spans {
+            if let Cow::Owned(redacted) = self.secrets.redact(&span.text) {
+                span.text = redacted;
+            }
+        }
+        line
+    }
+
+    /// Fills provenance the producer did not set from the current run and turn.
+    fn stamp_log_line(&self, mut line: LogLine) -> LogLine {
+        let meta = &mut line.meta;
+        if meta.run_id.is_none() {
+            meta.run_id = self.runtime_info.active_run_id.clone();
+        }
+        if meta.turn.is_none() && self.log_turn > 0 {
+            meta.turn = Some(self.log_turn);
+        }
+        if meta.at_unix_ms == 0 {
+            meta.at_unix_ms = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|value| value.as_millis() as u64)
+                .unwrap_or(0);
+        }
+        line
+    }
+
+    fn prepare_log_line(&self, line: LogLine) -> LogLine {
+        self.stamp_log_line(self.redact_log_line(line))
+    }
+
+    /// Starts a new prompt turn; lines pushed from here on carry its number.
+    pub fn begin_log_turn(&mut self) {
+        self.log_turn = self.log_turn.saturating_add(1);
+    }
+
+    /// A replacement without its own provenance keeps the replaced line's.
+    pub fn replace_log_line(&mut self, index: usize, mut line: LogLine) {
+        if let Some(slot) = self.log.get(index) {
+            if line.meta.is_empty() {
+                line.meta = slot.meta.clone();
+            }
+        }
+        let line = self.prepare_log_line(line);
         if let Some(slot) = self.log.get_mut(index) {
             *slot = line;
             self.mark_log_changed();
         }
     }
 
+    /// First log line that must stay in place: the live streaming segment, component spans
+    /// still being updated, and the `/pin mark` start.
+    pub fn log_trim_limit(&self) -> usize {
+        let streaming = self
+            .streaming_assistant
+            .as_ref()
+            .map(|stream| stream.segment_start);
+        let components = self
+            .pending_component_lines
+            .values()
+            .map(|span| span.start)
+            .chain(self.progress_component_lines.values().copied());
+        streaming
+            .into_iter()
+            .chain(components)
+            .chain(self.context_pins.mark())
+            .fold(self.log.len(), usize::min)
+    }
+
+    /// Drops the first `count` log lines, which wrapped to `rows` rows, and shifts everything
+    /// that points into the log. Callers keep `count` within `log_trim_limit`.
+    pub fn drop_log_front(&mut self, count: usize, rows: usize) {
+        let count = count.min(self.log_trim_limit());
+        if count == 0 {
+            return;
+        }
+        self.log.drain(..count);
+        self.log_timeline.drop_front(count);
+        if let Some(stream) = self.streaming_assistant.as_mut() {
+            stream.segment_start -= count;
+        }
+        for span in self.pending_component_lines.values_mut() {
+            span.start -= count;
+            span.end = span.end.saturating_sub(count);
+        }
+        for index in self.progress_component_lines.values_mut() {
+            *index -= count;
+        }
+        self.context_pins.shift_mark(count);
+        self.scroll_anchor = None;
+        let render = &mut self.render_state;
+        render.inserted_until = render.inserted_until.saturating_sub(rows);
+        render.visible_start = render.visible_start.saturating_sub(rows);
+        render.visible_end = render.visible_end.saturating_sub(rows);
+        render.wrapped_total = render.wrapped_total.saturating_sub(rows);
+        self.mark_log_changed();
+    }
+
+    /// Drops the log from `start` on and appends `lines` in its place.
+    pub fn replace_log_tail(&mut self, start: usize, lines: Vec<LogLine>) {
+        self.log.truncate(start);
+        let lines = lines
+            .into_iter()
+            .map(|line| self.prepare_log_line(line))
+            .collect::<Vec<_>>();
+        self.log.extend(lines);
+        self.mark_log_changed();
+    }
+
     pub fn push_line(&mut self, kind: LogKind, text: impl Into<String>) {
-        self.log.push(LogLine::new(kind, text));
+        let line = self.prepare_log_line(LogLine::new(kind, text));
+        self.log.push(line);
         self.mark_log_changed();
     }
 
@@ -369,10 +630,62 @@ impl AppState {
         if lines.is_empty() {
             return;
         }
+        let lines = lines
+            .into_iter()
+            .map(|line| self.prepare_log_line(line))
+            .collect::<Vec<_>>();
         self.log.extend(lines);
         self.mark_log_changed();
     }
 
+    /// Inserts `lines` before log line `index` and shifts everything that points past it.
+    /// Callers keep `index` below what inline mode already wrote to the terminal scrollback.
+    pub fn insert_log_lines(&mut self, index: usize, lines: Vec<LogLine>) {
+        if index >= self.log.len() {
+            self.extend_lines(lines);
+            return;
+        }
+        let count = lines.len();
+        if count == 0 {
+            return;
+        }
+        let lines = lines
+            .into_iter()
+            .map(|line| self.prepare_log_line(line))
+            .collect::<Vec<_>>();
+        self.log.splice(index..index, lines);
+        self.log_timeline.insert_at(index, count);
+        if let Some(stream) = self
+            .streaming_assistant
+            .as_mut()
+            .filter(|stream| stream.segment_start >= index)
+        {
+            stream.segment_start += count;
+        }
+        for span in self.pending_component_lines.values_mut() {
+            if span.start >= index {
+                span.start += count;
+            }
+            if span.end > index {
+                span.end += count;
+            }
+        }
+        for line in self.progress_component_lines.values_mut() {
+            if *line >= index {
+                *line += count;
+            }
+        }
+        self.context_pins.shift_mark_for_insert(index, count);
+        if let Some(anchor) = self
+            .scroll_anchor
+            .as_mut()
+            .filter(|anchor| anchor.log_index >= index)
+        {
+            anchor.log_index += count;
+        }
+        self.mark_log_changed();
+    }
+
     pub fn scroll_up(&mut self, lines: usize) {
         self.scroll_from_bottom = self.scroll_from_bottom.saturating_add(lines);
     }
@@ -381,6 +694,23 @@ impl AppState {
         self.scroll_from_bottom = self.scroll_from_bottom.saturating_sub(lines);
     }
 
+    /// Scrolls back so log line `index` is at the top; the draw pass resolves it to a row.
+    pub fn scroll_to_log_line(&mut self, index: usize) {
+        let scroll_from_bottom = self.scroll_from_bottom.max(1);
+        self.scroll_from_bottom = scroll_from_bottom;
+        self.scroll_anchor = Some(ScrollAnchor {
+            log_index: index,
+            row_offset: 0,
+            line_rows: 1,
+            scroll_from_bottom,
+        });
+    }
+
+    /// Oldest log line; the draw pass clamps the offset to the wrapped total.
+    pub fn scroll_to_top(&mut self) {
+        self.scroll_from_bottom = usize::MAX;
+    }
+
     pub fn scroll_page_up(&mut self) {
         let page = self.last_log_viewport_height.saturating_sub(1).max(1);
         self.scroll_up(page);
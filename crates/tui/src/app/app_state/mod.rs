@@ -1,14 +1,39 @@
-use crate::app::state::InputState;
+use crate::app::runtime::reconnect::RuntimeReconnect;
+use crate::app::runtime::EditCallArgs;
 use crate::app::state::LogLine;
 use crate::app::state::{
-    ConfirmDialogState, ContextPanelState, LaneListPanelState, ModelListMode, ModelListPanelState,
-    ModelPickerState, ModelSetScope, PendingImageAttachment, PerfDebugStats, PickDialogState,
-    PromptDialogState, ProviderPickerState, ReasoningPickerState, RenderState,
-    SessionListPanelState, SkillsListItemState, SkillsListPanelState, SkillsScopeFilter,
-    StatusLineMode, ThemeListPanelState, WrappedLogCache,
+    AssistantMessages, ChordBuffer, ConnectionHealth, ContextHistory, ContextPins,
+    CorrectionTracker, FailureStreak, FileArtifact, InputState, LinkTarget, LiveRegion, LogSearch,
+    LogTimeline, MinimapCache, PromptBatch, ProviderStats, RegenCandidates, RunTraces,
+    RuntimeWatchdog, Scratchpad, ScrollAnchor, SessionNotes, StreamingAssistant, TabRequest,
+    TabStrip, ToolPane, UsageTracker,
 };
+use crate::app::state::{
+    CacheListPanelState, CacheReadAction, ConfirmDialogState, ContextPanelState,
+    EditConflictPanelState, HunkReviewState, LaneAttachState, LaneListPanelState,
+    McpListPanelState, ModelListMode, ModelListPanelState, ModelPickerState, ModelSetScope,
+    PanelStack, PendingFileAttachment, PendingImageAttachment, PerfDebugStats,
+    PermissionsPanelState, PickDialogState, PromptDialogState, ProviderPickerState,
+    QueuePanelState, ReasoningPickerState, RenderState, SecretsPanelState, SessionListPanelState,
+    SkillsListItemState, SkillsListPanelState, SkillsScopeFilter, SlowTerminalMonitor,
+    StatusLineMode, ThemeListPanelState, ToolsPanelState, WhatsNewPanelState, WrappedLogCache,
+};
+use crate::app::util::draft::DraftAutosave;
+use crate::app::util::file_mentions::{MentionMatches, WorkspaceIndex, WorkspaceIndexLoad};
+use crate::app::util::forge::ci::CiWatchState;
+use crate::app::util::forge::{ForgeRefState, PullRequestDraft};
+use crate::app::util::json_output::JsonOutputRequest;
+use crate::app::util::local_models::LocalModelProbeState;
+use crate::app::util::run_tee::RunTee;
+use crate::app::util::secrets::SecretStore;
+use crate::app::util::settings::{SettingsWatcher, TuiSettings};
+use crate::app::util::stack_trace::{ResolvedFrame, StackFrame};
+use crate::app::util::translate::TranslationState;
+use crate::app::util::update::UpdateCheckState;
+use codelia_protocol::UiContextUpdate;
 use serde_json::Value;
-use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 pub(crate) const PROMPT_DISPATCH_RETRY_BACKOFF: Duration = Duration::from_millis(200);
@@ -42,6 +67,38 @@ pub struct PendingPromptRun {
     pub attachment_count: usize,
     pub shell_result_count: usize,
     pub dispatch_attempts: u32,
+    /// Plan-only run: the runtime previews mutating tools instead of executing them.
+    pub dry_run: bool,
+    /// `/json` request baked into this prompt; the answer is checked when the run completes.
+    pub json_request: Option<JsonOutputRequest>,
+    pub attachments: QueuedAttachments,
+}
+
+/// The composer's attachments when a prompt was queued, so `/queue` can put it back for editing.
+#[derive(Debug, Clone, Default)]
+pub struct QueuedAttachments {
+    /// Nonce of the attachment tokens in `user_text`.
+    pub composer_nonce: String,
+    pub images: Vec<(String, PendingImageAttachment)>,
+    pub files: Vec<PendingFileAttachment>,
+}
+
+/// `/json` structured-output requests: armed for the next prompt, then tracked for its run.
+#[derive(Debug, Clone, Default)]
+pub struct JsonOutputState {
+    pub armed: Option<JsonOutputRequest>,
+    pub active: Option<JsonOutputRequest>,
+    /// Last parsed JSON answer, for `/json save` and `/json show`.
+    pub last: Option<Value>,
+}
+
+/// Run outputs retained for `/bundle` after they leave the composer/shell queues.
+#[derive(Debug, Clone, Default)]
+pub struct RunArtifactsState {
+    pub last_prompt: Option<String>,
+    pub diffs: Vec<String>,
+    pub shell_results: Vec<PendingShellResult>,
+    pub attachments: Vec<(String, PendingImageAttachment)>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -51,6 +108,33 @@ pub struct PermissionPreviewRecord {
     pub diff_fingerprint: Option<String>,
 }
 
+/// Cached tool output selected in the `/cache` panel, waiting for `cache.read`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedOutputTarget {
+    pub ref_id: String,
+    pub tool: String,
+    pub action: CacheReadAction,
+}
+
+/// Cached tool output attached from `/cache`, sent as a `<tool_output>` block with the next prompt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedOutputContext {
+    pub ref_id: String,
+    pub tool: String,
+    pub content: String,
+    pub truncated: bool,
+}
+
+/// A read result whose full output can be paged from the tool output cache with `/page`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadPageRecord {
+    pub ref_id: String,
+    pub label: String,
+    /// 0-based line offset of the next page in the cached output.
+    pub next_offset: usize,
+    pub exhausted: bool,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct LogComponentSpan {
     pub start: usize,
@@ -70,6 +154,13 @@ impl LogComponentSpan {
     }
 }
 
+/// A `model.set`, `theme.set` or `lane_create` request that can be sent again as written.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryableRpc {
+    pub method: String,
+    pub params: Value,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ErrorDetailMode {
     Summary,
@@ -91,12 +182,22 @@ pub struct RpcPendingState {
     pub lane_status_id: Option<String>,
     pub lane_close_id: Option<String>,
     pub lane_create_id: Option<String>,
+    pub lane_tail_id: Option<String>,
+    pub tool_pane_list_id: Option<String>,
+    pub tool_pane_logs_id: Option<String>,
+    /// `model.list` with details for a provider whose prices `/usage` lacks.
+    pub usage_pricing_id: Option<String>,
     pub new_lane_seed_context: Option<String>,
     pub mcp_list_id: Option<String>,
     pub mcp_detail_id: Option<String>,
     pub context_inspect_id: Option<String>,
     pub skills_list_id: Option<String>,
     pub theme_set_id: Option<String>,
+    pub cwd_set_id: Option<String>,
+    pub cwd_set_prompt: Option<String>,
+    pub runtime_report_id: Option<String>,
+    pub runtime_report_copy: bool,
+    pub tools_list_id: Option<String>,
     pub skills_query: Option<String>,
     pub skills_scope: Option<SkillsScopeFilter>,
     pub logout_id: Option<String>,
@@ -107,7 +208,24 @@ pub struct RpcPendingState {
     pub task_list_id: Option<String>,
     pub task_status_id: Option<String>,
     pub task_cancel_id: Option<String>,
+    pub pr_create_id: Option<String>,
+    pub read_page_id: Option<String>,
+    pub read_page_number: Option<usize>,
+    pub cache_list_id: Option<String>,
+    pub cache_read_id: Option<String>,
+    pub cache_read_target: Option<CachedOutputTarget>,
+    pub cache_delete_id: Option<String>,
+    pub file_save_id: Option<String>,
+    pub file_save_path: Option<String>,
+    pub edit_merge_read_id: Option<String>,
+    pub edit_merge_tool_call_id: Option<String>,
+    pub ping_id: Option<String>,
+    pub ping_sent_at: Option<Instant>,
+    pub keepalive_id: Option<String>,
     pub client_tool_choice_ids: HashSet<String>,
+    /// Request behind the response being handled, while its handler runs; a failing handler
+    /// moves it to `AppState::failed_rpc`.
+    pub retry_candidate: Option<RetryableRpc>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -126,6 +244,10 @@ pub enum PendingRpcMatch {
     LaneStatus,
     LaneClose,
     LaneCreate,
+    LaneTail,
+    ToolPaneList,
+    ToolPaneLogs,
+    UsagePricing,
     SkillsList,
     ContextInspect,
     Logout,
@@ -136,7 +258,33 @@ pub enum PendingRpcMatch {
     TaskList,
     TaskStatus,
     TaskCancel,
+    PrCreate,
+    ReadPage {
+        number: usize,
+    },
+    CacheList,
+    CacheRead {
+        target: CachedOutputTarget,
+    },
+    CacheDelete,
+    FileSave {
+        path: String,
+    },
+    EditMergeRead {
+        tool_call_id: String,
+    },
+    Ping {
+        sent_at: Option<Instant>,
+    },
+    Keepalive,
     ThemeSet,
+    CwdSet {
+        prompt: Option<String>,
+    },
+    ToolsList,
+    RuntimeReport {
+        copy: bool,
+    },
     RunStart,
     RunCancel,
 }
@@ -154,11 +302,88 @@ pub struct RuntimeInfoState {
     pub supports_skills_list: bool,
     pub supports_context_inspect: bool,
     pub supports_tool_call: bool,
+    pub supports_tool_call_permission: bool,
     pub supports_theme_set: bool,
+    pub supports_cache: bool,
     pub supports_shell_exec: bool,
     pub supports_shell_tasks: bool,
     pub supports_shell_detach: bool,
+    pub supports_shell_env: bool,
+    pub supports_dry_run: bool,
     pub supports_tasks: bool,
+    pub supports_cwd_set: bool,
+    pub supports_disabled_tools: bool,
+    pub working_dir: Option<String>,
+    pub sandbox_root: Option<String>,
+    pub server_name: Option<String>,
+    pub server_version: Option<String>,
+    pub protocol_version: Option<String>,
+    pub server_capabilities: serde_json::Map<String, Value>,
+    pub launch: Option<RuntimeLaunchInfo>,
+    /// Runtime messages per method (`agent.event/<type>` for events) that failed the schema
+    /// check; shown by `/runtime`.
+    pub schema_mismatches: BTreeMap<String, u64>,
+}
+
+/// How the TUI started the runtime process; shown by `/runtime`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeLaunchInfo {
+    pub program: String,
+    pub args: Vec<String>,
+    pub pid: u32,
+}
+
+/// `ssh` options that take a value, so the host is the first other non-option argument.
+const SSH_OPTIONS_WITH_VALUE: &str = "BbcDEeFIiJLlmOoPpQRSWw";
+
+impl RuntimeLaunchInfo {
+    pub fn command_line(&self) -> String {
+        std::iter::once(self.program.as_str())
+            .chain(self.args.iter().map(String::as_str))
+            .map(|part| {
+                if part.is_empty() || part.contains(char::is_whitespace) {
+                    format!("'{part}'")
+                } else {
+                    part.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Host of a runtime launched through `ssh` (`CODELIA_RUNTIME_CMD=ssh`).
+    pub fn ssh_host(&self) -> Option<&str> {
+        let program = std::path::Path::new(&self.program)
+            .file_stem()
+            .and_then(|stem| stem.to_str())?;
+        if program != "ssh" {
+            return None;
+        }
+        let mut args = self.args.iter();
+        while let Some(arg) = args.next() {
+            if let Some(flags) = arg.strip_prefix('-') {
+                // In a cluster such as `-4vp 22` or `-oX=y`, the first flag that takes a value
+                // consumes the rest of the cluster, or the next argument when nothing follows it.
+                let value_flag = flags
+                    .char_indices()
+                    .find(|(_, flag)| SSH_OPTIONS_WITH_VALUE.contains(*flag));
+                if value_flag.is_some_and(|(index, flag)| index + flag.len_utf8() == flags.len()) {
+                    args.next();
+                }
+                continue;
+            }
+            return Some(arg.as_str());
+        }
+        None
+    }
+
+    pub fn transport(&self) -> &'static str {
+        if self.ssh_host().is_some() {
+            "ssh"
+        } else {
+            "local (stdio)"
+        }
+    }
 }
 
 impl ErrorDetailMode {
@@ -175,6 +400,9 @@ impl RpcPendingState {
         self.model_list_id.is_some()
             || self.model_set_id.is_some()
             || self.theme_set_id.is_some()
+            || self.cwd_set_id.is_some()
+            || self.runtime_report_id.is_some()
+            || self.tools_list_id.is_some()
             || self.session_list_id.is_some()
             || self.session_history_id.is_some()
             || self.mcp_list_id.is_some()
@@ -192,6 +420,12 @@ impl RpcPendingState {
             || self.task_list_id.is_some()
             || self.task_status_id.is_some()
             || self.task_cancel_id.is_some()
+            || self.pr_create_id.is_some()
+            || self.read_page_id.is_some()
+            || self.edit_merge_read_id.is_some()
+            || self.cache_list_id.is_some()
+            || self.cache_read_id.is_some()
+            || self.cache_delete_id.is_some()
     }
 
     pub fn take_match_for_response(&mut self, response_id: &str) -> Option<PendingRpcMatch> {
@@ -247,6 +481,26 @@ impl RpcPendingState {
             return Some(PendingRpcMatch::LaneCreate);
         }
 
+        if self.lane_tail_id.as_deref() == Some(response_id) {
+            self.lane_tail_id = None;
+            return Some(PendingRpcMatch::LaneTail);
+        }
+
+        if self.tool_pane_list_id.as_deref() == Some(response_id) {
+            self.tool_pane_list_id = None;
+            return Some(PendingRpcMatch::ToolPaneList);
+        }
+
+        if self.tool_pane_logs_id.as_deref() == Some(response_id) {
+            self.tool_pane_logs_id = None;
+            return Some(PendingRpcMatch::ToolPaneLogs);
+        }
+
+        if self.usage_pricing_id.as_deref() == Some(response_id) {
+            self.usage_pricing_id = None;
+            return Some(PendingRpcMatch::UsagePricing);
+        }
+
         if self.skills_list_id.as_deref() == Some(response_id) {
             self.skills_list_id = None;
             return Some(PendingRpcMatch::SkillsList);
@@ -297,11 +551,85 @@ impl RpcPendingState {
             return Some(PendingRpcMatch::TaskCancel);
         }
 
+        if self.pr_create_id.as_deref() == Some(response_id) {
+            self.pr_create_id = None;
+            return Some(PendingRpcMatch::PrCreate);
+        }
+
+        if self.read_page_id.as_deref() == Some(response_id) {
+            self.read_page_id = None;
+            if let Some(number) = self.read_page_number.take() {
+                return Some(PendingRpcMatch::ReadPage { number });
+            }
+        }
+
+        if self.cache_list_id.as_deref() == Some(response_id) {
+            self.cache_list_id = None;
+            return Some(PendingRpcMatch::CacheList);
+        }
+
+        if self.cache_read_id.as_deref() == Some(response_id) {
+            self.cache_read_id = None;
+            if let Some(target) = self.cache_read_target.take() {
+                return Some(PendingRpcMatch::CacheRead { target });
+            }
+        }
+
+        if self.cache_delete_id.as_deref() == Some(response_id) {
+            self.cache_delete_id = None;
+            return Some(PendingRpcMatch::CacheDelete);
+        }
+
+        if self.ping_id.as_deref() == Some(response_id) {
+            self.ping_id = None;
+            return Some(PendingRpcMatch::Ping {
+                sent_at: self.ping_sent_at.take(),
+            });
+        }
+
+        if self.keepalive_id.as_deref() == Some(response_id) {
+            self.keepalive_id = None;
+            return Some(PendingRpcMatch::Keepalive);
+        }
+
+        if self.file_save_id.as_deref() == Some(response_id) {
+            self.file_save_id = None;
+            if let Some(path) = self.file_save_path.take() {
+                return Some(PendingRpcMatch::FileSave { path });
+            }
+        }
+
+        if self.edit_merge_read_id.as_deref() == Some(response_id) {
+            self.edit_merge_read_id = None;
+            if let Some(tool_call_id) = self.edit_merge_tool_call_id.take() {
+                return Some(PendingRpcMatch::EditMergeRead { tool_call_id });
+            }
+        }
+
         if self.theme_set_id.as_deref() == Some(response_id) {
             self.theme_set_id = None;
             return Some(PendingRpcMatch::ThemeSet);
         }
 
+        if self.cwd_set_id.as_deref() == Some(response_id) {
+            self.cwd_set_id = None;
+            return Some(PendingRpcMatch::CwdSet {
+                prompt: self.cwd_set_prompt.take(),
+            });
+        }
+
+        if self.runtime_report_id.as_deref() == Some(response_id) {
+            self.runtime_report_id = None;
+            return Some(PendingRpcMatch::RuntimeReport {
+                copy: std::mem::take(&mut self.runtime_report_copy),
+            });
+        }
+
+        if self.tools_list_id.as_deref() == Some(response_id) {
+            self.tools_list_id = None;
+            return Some(PendingRpcMatch::ToolsList);
+        }
+
         if self.run_start_id.as_deref() == Some(response_id) {
             self.run_start_id = None;
             return Some(PendingRpcMatch::RunStart);
@@ -322,21 +650,73 @@ const ERROR_DETAIL_MAX_LINES: usize = 24;
 pub struct AppState {
     pub log: Vec<LogLine>,
     pub log_version: u64,
+    pub log_timeline: LogTimeline,
+    /// Prompt number stamped into `LogMeta::turn`; bumped by `begin_log_turn`.
+    pub log_turn: u32,
     pub wrapped_log_cache: Option<WrappedLogCache>,
     pub debug_perf_enabled: bool,
     pub perf_debug: PerfDebugStats,
+    pub slow_terminal: SlowTerminalMonitor,
     pub input: InputState,
     pub scroll_from_bottom: usize,
+    /// Content at the top of the log view while scrolled back; see `ScrollAnchor`.
+    pub scroll_anchor: Option<ScrollAnchor>,
+    /// Turn from a `--resume codelia://session/<id>#turn-<n>` link, scrolled to once the
+    /// session history has been replayed.
+    pub pending_turn_jump: Option<usize>,
+    /// Ctrl+F scrollback search, while open.
+    pub log_search: Option<LogSearch>,
     pub log_changed: bool,
-    pub last_wrapped_total: usize,
     pub last_wrap_width: usize,
+    /// Minimap marks for the current log/width/height; only built while `settings.minimap` is on.
+    pub minimap_cache: Option<MinimapCache>,
     pub last_log_viewport_height: usize,
     pub render_state: RenderState,
     pub run_status: Option<String>,
     pub context_left_percent: Option<u8>,
+    /// Every `run.context` sample, for the status sparkline and `/context graph`.
+    pub context_history: ContextHistory,
+    /// `run.usage` token totals and their cost estimate, for `/usage` and the status line.
+    pub usage: UsageTracker,
+    /// Runs, failures, latency and tokens per provider/model, for `/providers stats`.
+    pub provider_stats: ProviderStats,
+    /// Timelines of the run in flight and the last one, for `/trace`.
+    pub run_traces: RunTraces,
     pub mouse_capture_enabled: bool,
     pub last_assistant_text: Option<String>,
+    /// Recent assistant messages for `/copy pick`.
+    pub assistant_messages: AssistantMessages,
+    /// Assistant message being streamed as `text_delta` events, until its `text`/`final`.
+    pub streaming_assistant: Option<StreamingAssistant>,
+    /// Complete files in the latest answer, saved with Alt+S.
+    pub file_artifacts: Vec<FileArtifact>,
+    /// `/regen` answer pair, until one is picked or a new prompt replaces them.
+    pub regen_candidates: Option<RegenCandidates>,
+    /// Snippets pinned with Alt+P or `/scratch`; kept across `/clear` and new sessions.
+    pub scratchpad: Scratchpad,
+    /// Log snippets from `/pin`, sent ahead of the next few prompts.
+    pub context_pins: ContextPins,
+    /// `/note` memory sent with every prompt.
+    pub session_notes: SessionNotes,
+    /// Repeated corrections, for the `Alt+A` AGENTS.md suggestion.
+    pub corrections: CorrectionTracker,
+    /// Run state announcements drawn on the last row under the `accessible` setting.
+    pub live_region: LiveRegion,
+    /// Keys held back while a multi-key chord (`Ctrl+X g`, `Ctrl+X Ctrl+E`) is being typed.
+    pub chord: ChordBuffer,
+    /// `Ctrl+X Ctrl+E` / `/edit` asked for `$EDITOR`; the run loop opens it.
+    pub external_edit_requested: bool,
+    /// `Ctrl+Z` on an empty composer; the run loop stops the process (job control).
+    pub suspend_requested: bool,
     pub run_started_at: Option<Instant>,
+    /// First Esc of a `layered` double-Esc run cancel.
+    pub esc_cancel_armed_at: Option<Instant>,
+    /// `(tool_call_id, tool)` of the tool call currently in flight, for `{tool}` on the run line.
+    pub active_tool: Option<(String, String)>,
+    /// Right pane of the split layout (`split_pane` setting, Alt+T).
+    pub tool_pane: ToolPane,
+    /// Whether the last frame drew the tool pane; shell output is polled only then.
+    pub tool_pane_shown: bool,
     pub run_elapsed: Option<Duration>,
     pub spinner_index: usize,
     pub spinner_last_tick: Instant,
@@ -346,17 +726,30 @@ pub struct AppState {
     pub model_list_panel: Option<ModelListPanelState>,
     pub session_list_panel: Option<SessionListPanelState>,
     pub lane_list_panel: Option<LaneListPanelState>,
+    pub lane_attach: Option<LaneAttachState>,
+    pub mcp_list_panel: Option<McpListPanelState>,
+    /// List panels waiting behind the open detail panel; `Esc` on the detail goes back.
+    pub panel_stack: PanelStack,
     pub context_panel: Option<ContextPanelState>,
     pub skills_list_panel: Option<SkillsListPanelState>,
     pub theme_list_panel: Option<ThemeListPanelState>,
+    pub cache_list_panel: Option<CacheListPanelState>,
+    pub whats_new_panel: Option<WhatsNewPanelState>,
     pub confirm_dialog: Option<ConfirmDialogState>,
     pub pending_confirm_dialog: Option<ConfirmDialogState>,
+    /// Hunks of large permission previews by tool call id, attached to the confirm for that call.
+    pub pending_hunk_reviews: HashMap<String, HunkReviewState>,
     pub confirm_input: InputState,
     pub prompt_dialog: Option<PromptDialogState>,
     pub prompt_input: InputState,
     pub pick_dialog: Option<PickDialogState>,
+    /// Targets behind the open Alt+O quick-jump pick dialog, in item order.
+    pub link_targets: Vec<LinkTarget>,
     pub rpc_pending: RpcPendingState,
+    /// Last failed retryable request, re-sent with Alt+R.
+    pub failed_rpc: Option<RetryableRpc>,
     pub runtime_info: RuntimeInfoState,
+    pub runtime_reconnect: RuntimeReconnect,
     pub skills_catalog_items: Vec<SkillsListItemState>,
     pub skills_catalog_loaded: bool,
     pub disabled_skill_paths: BTreeSet<String>,
@@ -364,6 +757,8 @@ pub struct AppState {
     pub status_line_mode: StatusLineMode,
     pub error_detail_mode: ErrorDetailMode,
     pub last_error_detail: Option<String>,
+    /// Consecutive failed tool calls/runs; a long streak switches error details on.
+    pub failure_streak: FailureStreak,
     pub pending_shift_enter_backslash: Option<Instant>,
     pub pending_component_lines: HashMap<String, LogComponentSpan>,
     pub progress_component_lines: HashMap<String, usize>,
@@ -371,7 +766,41 @@ pub struct AppState {
     pub active_compaction_component_by_scope: HashMap<String, String>,
     pub permission_preview_by_tool_call: HashMap<String, PermissionPreviewRecord>,
     pub permission_ready_tool_call_ids: HashSet<String>,
+    /// Tool of each `permission.ready` by `tool_call_id`, taken by the confirm request carrying
+    /// the same id for the audit log.
+    pub permission_tool_by_call: HashMap<String, String>,
+    /// `audit.jsonl` for confirm decisions; `None` (tests, no config dir) disables recording.
+    pub audit_log_path: Option<PathBuf>,
+    /// Pageable read results keyed by the number shown in their `/page` hint.
+    pub read_pages: BTreeMap<usize, ReadPageRecord>,
+    pub next_read_page_number: usize,
+    pub pending_cache_outputs: Vec<CachedOutputContext>,
     pub pending_image_attachments: HashMap<String, PendingImageAttachment>,
+    /// Files attached with `@path` in the composer, in attach order.
+    pub pending_file_attachments: Vec<PendingFileAttachment>,
+    /// Workspace file index for `@` completion, built on first use.
+    pub workspace_index: Option<WorkspaceIndex>,
+    /// Background build of `workspace_index`, started by the first `@` word.
+    pub workspace_index_load: WorkspaceIndexLoad,
+    /// Candidates for the `@` word being typed; refreshed by the run loop when the query changes.
+    pub mention_matches: Option<MentionMatches>,
+    /// Unreferenced attachments held while the attachment fix-up dialog is open.
+    pub stashed_attachments: Vec<(String, PendingImageAttachment)>,
+    /// Long paste held back while the sampling offer is open.
+    pub paste_sample_offer: Option<String>,
+    /// `/tee` file for the current run's assistant text and tool summaries.
+    pub run_tee: Option<RunTee>,
+    /// Tab change for the run loop to apply (`/tab`, `Ctrl+Tab`).
+    pub tab_request: Option<TabRequest>,
+    /// Every tab's state, for the status line and `/tab`.
+    pub tab_strip: TabStrip,
+    /// Frames from a pasted stack trace while the resolve offer is open.
+    pub stack_trace_offer: Option<Vec<StackFrame>>,
+    /// Resolved traces sent as `<stack_trace_context>` with the next prompt.
+    pub pending_stack_traces: Vec<Vec<ResolvedFrame>>,
+    /// `edit` calls waiting for their result, or for the merge over a stale one, by tool call id.
+    pub edit_calls: HashMap<String, EditCallArgs>,
+    pub edit_conflict_panel: Option<EditConflictPanelState>,
     pub composer_nonce: String,
     pub next_attachment_id: u64,
     pub pending_shell_results: Vec<PendingShellResult>,
@@ -380,7 +809,50 @@ pub struct AppState {
     pub dispatching_prompt: Option<PendingPromptRun>,
     pub next_prompt_queue_id: u64,
     pub next_queue_dispatch_retry_at: Option<Instant>,
+    /// Prompt file being worked through by `/batch` or `--prompt-file`.
+    pub prompt_batch: Option<PromptBatch>,
+    pub json_output: JsonOutputState,
     pub bang_input_mode: bool,
+    pub run_artifacts: RunArtifactsState,
+    pub forge_refs: ForgeRefState,
+    pub pending_pull_request: Option<PullRequestDraft>,
+    pub ci_watch: CiWatchState,
+    pub secrets: SecretStore,
+    pub secrets_panel: Option<SecretsPanelState>,
+    pub permissions_panel: Option<PermissionsPanelState>,
+    pub tools_panel: Option<ToolsPanelState>,
+    pub queue_panel: Option<QueuePanelState>,
+    /// Composer autosave for `/draft restore`.
+    pub draft: DraftAutosave,
+    /// Tool names the runtime reported via `context.inspect` (`None` before its first run).
+    pub runtime_tools: Option<Vec<String>>,
+    /// Tools switched off with `/tools` for this session; sent as `run.start.disabled_tools`.
+    pub disabled_tools: BTreeSet<String>,
+    /// `--read-only` launch profile; fixed for the TUI process.
+    pub read_only: bool,
+    /// `--low-bandwidth` launch profile: reduced effects, no spinner frames, 16 colors.
+    pub low_bandwidth: bool,
+    pub dry_run_active: bool,
+    pub planned_steps: Vec<String>,
+    /// Prompt of the last completed dry run, ready to re-dispatch for real.
+    pub ready_plan: Option<String>,
+    pub settings: TuiSettings,
+    pub settings_watcher: SettingsWatcher,
+    /// Silence detection for active runs (`stall_timeout` setting).
+    pub runtime_watchdog: RuntimeWatchdog,
+    /// Keepalive pings and round-trip time for a runtime reached over ssh.
+    pub connection_health: ConnectionHealth,
+    /// Desktop notification body for a finished run (`notifications` setting); the run loop
+    /// sends it, ringing the bell when no notifier is available.
+    pub pending_notification: Option<String>,
+    /// Runtime message of the day shown above the composer until dismissed with Esc.
+    pub motd: Option<String>,
+    pub update_check: UpdateCheckState,
+    pub local_model_probe: LocalModelProbeState,
+    /// Latest file/selection from an `--editor-socket` client, as sent with `ui.context.update`.
+    pub editor_context: Option<UiContextUpdate>,
+    /// `/translate <lang>` mode and the answer translations in flight.
+    pub translation: TranslationState,
 }
 
 fn new_composer_nonce() -> String {
@@ -396,21 +868,47 @@ impl Default for AppState {
         Self {
             log: Vec::new(),
             log_version: 0,
+            log_timeline: LogTimeline::default(),
+            log_turn: 0,
             wrapped_log_cache: None,
             debug_perf_enabled: false,
             perf_debug: PerfDebugStats::default(),
+            slow_terminal: SlowTerminalMonitor::default(),
             input: InputState::default(),
             scroll_from_bottom: 0,
+            scroll_anchor: None,
+            pending_turn_jump: None,
+            log_search: None,
             log_changed: false,
-            last_wrapped_total: 0,
             last_wrap_width: 0,
+            minimap_cache: None,
             last_log_viewport_height: 0,
             render_state: RenderState::default(),
             run_status: None,
             context_left_percent: None,
+            context_history: ContextHistory::default(),
+            usage: UsageTracker::default(),
+            provider_stats: ProviderStats::default(),
+            run_traces: RunTraces::default(),
             mouse_capture_enabled: false,
             last_assistant_text: None,
+            assistant_messages: AssistantMessages::default(),
+            streaming_assistant: None,
+            file_artifacts: Vec::new(),
+            regen_candidates: None,
+            scratchpad: Scratchpad::default(),
+            context_pins: ContextPins::default(),
+            session_notes: SessionNotes::default(),
+            corrections: CorrectionTracker::default(),
+            live_region: LiveRegion::default(),
+            chord: ChordBuffer::default(),
+            external_edit_requested: false,
+            suspend_requested: false,
             run_started_at: None,
+            esc_cancel_armed_at: None,
+            active_tool: None,
+            tool_pane: ToolPane::default(),
+            tool_pane_shown: false,
             run_elapsed: None,
             spinner_index: 0,
             spinner_last_tick: Instant::now(),
@@ -420,17 +918,26 @@ impl Default for AppState {
             model_list_panel: None,
             session_list_panel: None,
             lane_list_panel: None,
+            lane_attach: None,
+            mcp_list_panel: None,
+            panel_stack: PanelStack::default(),
             context_panel: None,
             skills_list_panel: None,
             theme_list_panel: None,
+            cache_list_panel: None,
+            whats_new_panel: None,
             confirm_dialog: None,
             pending_confirm_dialog: None,
+            pending_hunk_reviews: HashMap::new(),
             confirm_input: InputState::default(),
             prompt_dialog: None,
             prompt_input: InputState::default(),
             pick_dialog: None,
+            link_targets: Vec::new(),
             rpc_pending: RpcPendingState::default(),
+            failed_rpc: None,
             runtime_info: RuntimeInfoState::default(),
+            runtime_reconnect: RuntimeReconnect::default(),
             skills_catalog_items: Vec::new(),
             skills_catalog_loaded: false,
             disabled_skill_paths: BTreeSet::new(),
@@ -438,6 +945,7 @@ impl Default for AppState {
             status_line_mode: StatusLineMode::Info,
             error_detail_mode: ErrorDetailMode::Summary,
             last_error_detail: None,
+            failure_streak: FailureStreak::default(),
             pending_shift_enter_backslash: None,
             pending_component_lines: HashMap::new(),
             progress_component_lines: HashMap::new(),
@@ -445,7 +953,25 @@ impl Default for AppState {
             active_compaction_component_by_scope: HashMap::new(),
             permission_preview_by_tool_call: HashMap::new(),
             permission_ready_tool_call_ids: HashSet::new(),
+            permission_tool_by_call: HashMap::new(),
+            audit_log_path: None,
+            read_pages: BTreeMap::new(),
+            next_read_page_number: 1,
+            pending_cache_outputs: Vec::new(),
             pending_image_attachments: HashMap::new(),
+            pending_file_attachments: Vec::new(),
+            workspace_index: None,
+            workspace_index_load: WorkspaceIndexLoad::default(),
+            mention_matches: None,
+            stashed_attachments: Vec::new(),
+            paste_sample_offer: None,
+            run_tee: None,
+            tab_request: None,
+            tab_strip: TabStrip::default(),
+            stack_trace_offer: None,
+            pending_stack_traces: Vec::new(),
+            edit_calls: HashMap::new(),
+            edit_conflict_panel: None,
             composer_nonce: new_composer_nonce(),
             next_attachment_id: 0,
             pending_shell_results: Vec::new(),
@@ -454,7 +980,36 @@ impl Default for AppState {
             dispatching_prompt: None,
             next_prompt_queue_id: 1,
             next_queue_dispatch_retry_at: None,
+            prompt_batch: None,
+            json_output: JsonOutputState::default(),
             bang_input_mode: false,
+            run_artifacts: RunArtifactsState::default(),
+            forge_refs: ForgeRefState::default(),
+            pending_pull_request: None,
+            ci_watch: CiWatchState::default(),
+            secrets: SecretStore::default(),
+            secrets_panel: None,
+            permissions_panel: None,
+            tools_panel: None,
+            queue_panel: None,
+            draft: DraftAutosave::default(),
+            runtime_tools: None,
+            disabled_tools: BTreeSet::new(),
+            read_only: false,
+            low_bandwidth: false,
+            dry_run_active: false,
+            planned_steps: Vec::new(),
+            ready_plan: None,
+            settings: TuiSettings::default(),
+            settings_watcher: SettingsWatcher::default(),
+            pending_notification: None,
+            runtime_watchdog: RuntimeWatchdog::default(),
+            connection_health: ConnectionHealth::default(),
+            motd: None,
+            update_check: UpdateCheckState::default(),
+            local_model_probe: LocalModelProbeState::default(),
+            editor_context: None,
+            translation: TranslationState::default(),
         }
     }
 }
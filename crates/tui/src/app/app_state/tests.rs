@@ -1,8 +1,9 @@
 use super::{
     AppState, ConfirmDialogState, ErrorDetailMode, ModelListMode, ModelSetScope, PendingRpcMatch,
-    RpcPendingState, SkillsListItemState, SkillsListPanelState, SkillsScopeFilter,
+    RpcPendingState, RuntimeLaunchInfo, SkillsListItemState, SkillsListPanelState,
+    SkillsScopeFilter,
 };
-use crate::app::state::{ConfirmMode, ConfirmPhase, CursorPhase, SyncPhase};
+use crate::app::state::{ConfirmAccelerators, ConfirmMode, ConfirmPhase, CursorPhase, SyncPhase};
 use crate::app::state::{LogKind, LogLine};
 
 fn sample_panel() -> SkillsListPanelState {
@@ -10,6 +11,7 @@ fn sample_panel() -> SkillsListPanelState {
         title: "Skills".to_string(),
         header: String::new(),
         rows: Vec::new(),
+        wide: None,
         filtered_indices: Vec::new(),
         items: vec![
             SkillsListItemState {
@@ -30,6 +32,7 @@ fn sample_panel() -> SkillsListPanelState {
         selected: 0,
         search_query: String::new(),
         scope_filter: SkillsScopeFilter::All,
+        applied_filter: None,
     };
     panel.rebuild();
     panel
@@ -53,9 +56,27 @@ fn skills_panel_rebuild_filters_by_query() {
     assert!(panel.rows[0].contains("user-helper"));
 }
 
+#[test]
+fn skills_panel_query_narrows_and_resets_when_shortened() {
+    let mut panel = sample_panel();
+    panel.search_query = "e".to_string();
+    panel.rebuild();
+    assert_eq!(panel.filtered_indices, vec![0, 1]);
+    panel.search_query = "re".to_string();
+    panel.rebuild();
+    assert_eq!(panel.filtered_indices, vec![0]);
+    panel.search_query = "r".to_string();
+    panel.rebuild();
+    assert_eq!(panel.filtered_indices, vec![0, 1]);
+    panel.scope_filter = SkillsScopeFilter::User;
+    panel.rebuild();
+    assert_eq!(panel.filtered_indices, vec![1]);
+}
+
 fn sample_confirm_dialog() -> ConfirmDialogState {
     ConfirmDialogState {
         id: "confirm_1".to_string(),
+        tool: None,
         title: "Permission".to_string(),
         message: "Allow tool?".to_string(),
         danger_level: None,
@@ -66,6 +87,8 @@ fn sample_confirm_dialog() -> ConfirmDialogState {
         command_view: false,
         selected: 0,
         mode: ConfirmMode::Select,
+        accelerators: ConfirmAccelerators::default(),
+        hunk_review: None,
     }
 }
 
@@ -237,3 +260,88 @@ fn rpc_pending_take_match_extracts_model_mode_and_clears_it() {
     assert!(pending.model_list_mode.is_none());
     assert!(pending.model_list_scope.is_none());
 }
+
+#[test]
+fn failure_streak_switches_error_details_until_a_success() {
+    let mut app = AppState::default();
+    app.record_outcome(false);
+    app.record_outcome(false);
+    assert_eq!(app.error_detail_mode, ErrorDetailMode::Summary);
+    app.record_outcome(false);
+    assert_eq!(app.error_detail_mode, ErrorDetailMode::Detail);
+    assert!(app
+        .log
+        .last()
+        .expect("banner")
+        .plain_text()
+        .starts_with("3 consecutive failures"));
+
+    app.record_outcome(true);
+    assert_eq!(app.error_detail_mode, ErrorDetailMode::Summary);
+
+    // A mode picked during the streak is kept after it ends.
+    for _ in 0..3 {
+        app.record_outcome(false);
+    }
+    app.set_error_detail_mode(ErrorDetailMode::Detail);
+    app.record_outcome(true);
+    assert_eq!(app.error_detail_mode, ErrorDetailMode::Detail);
+}
+
+#[test]
+fn esc_action_follows_policy_and_skips_cancel_while_one_is_pending() {
+    use crate::app::state::{EscAction, EscPolicy};
+    use std::time::Instant;
+
+    let now = Instant::now();
+    let mut app = AppState::default();
+    assert_eq!(app.next_esc_action(now), None);
+
+    app.update_run_status("running".to_string());
+    app.runtime_info.active_run_id = Some("run-1".to_string());
+    app.input.set_from("draft");
+    assert_eq!(app.next_esc_action(now), Some(EscAction::ClearComposer));
+
+    app.input.set_from("");
+    assert_eq!(app.next_esc_action(now), Some(EscAction::ArmCancel));
+    app.esc_cancel_armed_at = Some(now);
+    assert_eq!(app.next_esc_action(now), Some(EscAction::CancelRun));
+
+    app.rpc_pending.run_cancel_id = Some("id-9".to_string());
+    assert_eq!(app.next_esc_action(now), None);
+
+    app.rpc_pending.run_cancel_id = None;
+    app.settings.esc_policy = EscPolicy::Never;
+    assert_eq!(app.next_esc_action(now), None);
+}
+
+fn ssh_launch(args: &[&str]) -> RuntimeLaunchInfo {
+    RuntimeLaunchInfo {
+        program: "/usr/bin/ssh".to_string(),
+        args: args.iter().map(|arg| arg.to_string()).collect(),
+        pid: 1,
+    }
+}
+
+#[test]
+fn ssh_host_skips_option_values_in_flag_clusters() {
+    let host = |args: &[&str]| ssh_launch(args).ssh_host().map(str::to_string);
+
+    assert_eq!(
+        host(&["-oX=y", "host", "codelia-runtime"]).as_deref(),
+        Some("host")
+    );
+    assert_eq!(host(&["-p22", "host"]).as_deref(), Some("host"));
+    assert_eq!(host(&["-4v", "host"]).as_deref(), Some("host"));
+    assert_eq!(host(&["-4vp", "22", "host"]).as_deref(), Some("host"));
+    assert_eq!(host(&["-o", "X=y", "host"]).as_deref(), Some("host"));
+    assert_eq!(
+        RuntimeLaunchInfo {
+            program: "bun".to_string(),
+            args: vec!["host".to_string()],
+            pid: 1,
+        }
+        .ssh_host(),
+        None
+    );
+}
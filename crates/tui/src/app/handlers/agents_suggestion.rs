@@ -0,0 +1,127 @@
+use crate::app::runtime::{send_permissioned_tool_call, RuntimeStdin};
+use crate::app::state::{LogKind, LogLine, LogSpan, LogTone};
+use crate::app::AppState;
+use serde_json::json;
+use std::path::PathBuf;
+
+const AGENTS_FILE: &str = "AGENTS.md";
+
+/// Watches sent prompts for a correction the user keeps repeating and offers to turn it
+/// into an AGENTS.md rule.
+pub(crate) fn note_correction(app: &mut AppState, user_text: &str) {
+    let Some(suggestion) = app.corrections.observe(user_text) else {
+        return;
+    };
+    let line = LogLine::new_with_spans(vec![
+        LogSpan::new(
+            LogKind::Status,
+            LogTone::Detail,
+            format!(
+                "  Corrected {} times: \"{}\" · ",
+                suggestion.count, suggestion.instruction
+            ),
+        ),
+        LogSpan::new(LogKind::Status, LogTone::Summary, "Alt+A"),
+        LogSpan::new(
+            LogKind::Status,
+            LogTone::Detail,
+            format!(" adds it to {AGENTS_FILE}"),
+        ),
+    ]);
+    app.extend_lines(vec![line]);
+}
+
+/// `AGENTS.md` at the runtime's working directory (the TUI's when unknown).
+fn agents_path(app: &AppState) -> PathBuf {
+    app.runtime_info
+        .working_dir
+        .as_deref()
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join(AGENTS_FILE)
+}
+
+/// `content` with `- instruction` appended as the last line.
+fn append_rule(content: &str, instruction: &str) -> String {
+    let body = content.trim_end_matches('\n');
+    if body.is_empty() {
+        return format!("- {instruction}\n");
+    }
+    format!("{body}\n- {instruction}\n")
+}
+
+/// Alt+A: appends the suggested rule with the runtime's `edit` tool, so the permission policy
+/// and confirm diff apply as for an agent edit. The answer is reported like a file save.
+pub(crate) fn append_agents_suggestion(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) -> bool {
+    let Some(suggestion) = app.corrections.suggestion.clone() else {
+        app.push_line(LogKind::Status, "No AGENTS.md suggestion yet");
+        return true;
+    };
+    if !app.runtime_info.supports_tool_call_permission {
+        app.push_line(
+            LogKind::Status,
+            "Runtime cannot check permissions for tool.call; add the rule to AGENTS.md manually",
+        );
+        return true;
+    }
+    if app.rpc_pending.file_save_id.is_some() {
+        app.push_line(LogKind::Status, "A file save is already pending");
+        return true;
+    }
+    let path = agents_path(app);
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(error) => {
+            app.push_error_report("AGENTS.md read failed", error.to_string());
+            return true;
+        }
+    };
+    let file_path = path.display().to_string();
+    // An empty `old_string` replaces the whole file, and creates it when missing.
+    let arguments = json!({
+        "file_path": file_path,
+        "old_string": "",
+        "new_string": append_rule(&content, &suggestion.instruction),
+    });
+    let id = next_id();
+    app.rpc_pending.file_save_id = Some(id.clone());
+    app.rpc_pending.file_save_path = Some(file_path);
+    if let Err(error) = send_permissioned_tool_call(child_stdin, &id, "edit", arguments) {
+        app.rpc_pending.file_save_id = None;
+        app.rpc_pending.file_save_path = None;
+        app.push_error_report("send error", error.to_string());
+        return true;
+    }
+    app.corrections.suggestion = None;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{append_rule, note_correction};
+    use crate::app::AppState;
+
+    #[test]
+    fn second_similar_correction_offers_an_agents_rule() {
+        let mut app = AppState::default();
+        note_correction(&mut app, "no, run the tests with cargo nextest");
+        assert!(app.corrections.suggestion.is_none());
+        note_correction(&mut app, "I told you: cargo nextest for the tests");
+        let hint = app.log.last().expect("hint").plain_text();
+        assert_eq!(
+            hint,
+            "  Corrected 2 times: \"Cargo nextest for the tests.\" · Alt+A adds it to AGENTS.md"
+        );
+
+        assert_eq!(append_rule("", "Use pnpm."), "- Use pnpm.\n");
+        assert_eq!(
+            append_rule("# Rules\n- Be brief.\n\n", "Use pnpm."),
+            "# Rules\n- Be brief.\n- Use pnpm.\n"
+        );
+    }
+}
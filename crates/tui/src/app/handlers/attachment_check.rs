@@ -0,0 +1,173 @@
+use crate::app::handlers::command::start_prompt_run;
+use crate::app::runtime::RuntimeStdin;
+use crate::app::state::LogKind;
+use crate::app::util::attachments::{
+    check_attachment_tokens, make_attachment_token, strip_orphaned_attachment_tokens,
+};
+use crate::app::{AppState, PickDialogItem, PickDialogState};
+use crossterm::event::KeyCode;
+
+pub(crate) const ATTACHMENT_FIXUP_PICK_ID: &str = "attachment-fixup";
+const SHOWN_ORPHANED_TOKENS: usize = 3;
+
+/// Checks the composer before a prompt is sent; on a mismatch opens the fix-up dialog and
+/// returns true so the caller keeps the composer as is.
+pub(crate) fn open_attachment_fixup_if_needed(app: &mut AppState, raw_input: &str) -> bool {
+    let check = check_attachment_tokens(
+        raw_input,
+        &app.composer_nonce,
+        &app.pending_image_attachments,
+    );
+    if check.is_clean() {
+        return false;
+    }
+    let mut details = Vec::new();
+    if !check.orphaned_tokens.is_empty() {
+        let mut shown = check
+            .orphaned_tokens
+            .iter()
+            .take(SHOWN_ORPHANED_TOKENS)
+            .cloned()
+            .collect::<Vec<_>>();
+        if check.orphaned_tokens.len() > SHOWN_ORPHANED_TOKENS {
+            shown.push(format!(
+                "+{} more",
+                check.orphaned_tokens.len() - SHOWN_ORPHANED_TOKENS
+            ));
+        }
+        details.push(format!(
+            "{} image reference(s) match no attached image: {}",
+            check.orphaned_tokens.len(),
+            shown.join(", ")
+        ));
+    }
+    if !check.unreferenced_ids.is_empty() {
+        details.push(format!(
+            "{} attached image(s) are not referenced in the text",
+            check.unreferenced_ids.len()
+        ));
+    }
+    let mut items = Vec::new();
+    if !check.orphaned_tokens.is_empty() {
+        items.push(PickDialogItem {
+            id: "strip".to_string(),
+            label: "Remove broken references and send".to_string(),
+            detail: None,
+        });
+    }
+    if !check.unreferenced_ids.is_empty() {
+        items.push(PickDialogItem {
+            id: "attach".to_string(),
+            label: "Append unreferenced images and send".to_string(),
+            detail: None,
+        });
+    }
+    items.push(PickDialogItem {
+        id: "send".to_string(),
+        label: "Send as typed".to_string(),
+        detail: Some(
+            "broken references go as plain text; unreferenced images are dropped".to_string(),
+        ),
+    });
+    items.push(PickDialogItem {
+        id: "edit".to_string(),
+        label: "Keep editing".to_string(),
+        detail: None,
+    });
+    // Enter's redraw prunes unreferenced attachments; hold them until the dialog resolves.
+    app.stashed_attachments = check
+        .unreferenced_ids
+        .iter()
+        .filter_map(|attachment_id| {
+            app.pending_image_attachments
+                .get(attachment_id)
+                .map(|image| (attachment_id.clone(), image.clone()))
+        })
+        .collect();
+    app.pick_dialog = Some(PickDialogState {
+        id: ATTACHMENT_FIXUP_PICK_ID.to_string(),
+        title: "Attachment references don't match".to_string(),
+        message: Some(details.join("\n")),
+        chosen: vec![false; items.len()],
+        items,
+        selected: 0,
+        multi: false,
+    });
+    true
+}
+
+fn apply_fixup_choice(
+    app: &mut AppState,
+    choice: &str,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) {
+    let stashed = std::mem::take(&mut app.stashed_attachments);
+    let mut input = app.input.current();
+    match choice {
+        "strip" => {
+            input = strip_orphaned_attachment_tokens(
+                &input,
+                &app.composer_nonce,
+                &app.pending_image_attachments,
+            );
+        }
+        "attach" => {
+            for (attachment_id, image) in stashed {
+                input.push(' ');
+                input.push_str(&make_attachment_token(&app.composer_nonce, &attachment_id));
+                app.pending_image_attachments.insert(attachment_id, image);
+            }
+        }
+        "send" => {}
+        _ => {
+            app.push_line(
+                LogKind::Status,
+                "Prompt not sent; fix the image references and press Enter",
+            );
+            return;
+        }
+    }
+    if input.trim().is_empty() {
+        app.input.set_from(&input);
+        app.push_line(LogKind::Status, "Nothing left to send");
+        return;
+    }
+    if start_prompt_run(app, child_stdin, next_id, &input) {
+        app.clear_composer();
+    }
+}
+
+/// Keys for the fix-up dialog; Esc keeps editing.
+pub(crate) fn handle_attachment_fixup_key(
+    app: &mut AppState,
+    key: KeyCode,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) -> bool {
+    let Some(pick) = app.pick_dialog.as_mut() else {
+        return false;
+    };
+    let choice = match key {
+        KeyCode::Esc => "edit".to_string(),
+        KeyCode::Up => {
+            pick.selected = pick.selected.saturating_sub(1);
+            return true;
+        }
+        KeyCode::Down => {
+            if pick.selected + 1 < pick.items.len() {
+                pick.selected += 1;
+            }
+            return true;
+        }
+        KeyCode::Enter => pick
+            .items
+            .get(pick.selected)
+            .map(|item| item.id.clone())
+            .unwrap_or_default(),
+        _ => return false,
+    };
+    app.pick_dialog = None;
+    apply_fixup_choice(app, &choice, child_stdin, next_id);
+    true
+}
@@ -0,0 +1,162 @@
+use crate::app::state::LogKind;
+use crate::app::util::audit::{
+    append_audit_entry, format_utc_timestamp, load_audit_log, AuditEntry,
+};
+use crate::app::{AppState, ContextPanelState};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn append_decision(
+    app: &mut AppState,
+    tool: Option<String>,
+    title: String,
+    message: &str,
+    allowed: bool,
+    remember: bool,
+    reason: Option<&str>,
+) {
+    let Some(path) = app.audit_log_path.clone() else {
+        return;
+    };
+    let entry = AuditEntry {
+        ts_unix_ms: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or(0),
+        tool,
+        title,
+        summary: AuditEntry::summarize(message),
+        allowed,
+        remember,
+        reason: reason.map(str::to_string),
+    };
+    if let Err(error) = append_audit_entry(&path, &entry) {
+        app.push_error_report("audit log write failed", error);
+    }
+}
+
+/// Appends the decision for the open confirm dialog; call before the dialog is cleared.
+pub(crate) fn record_confirm_decision(
+    app: &mut AppState,
+    allowed: bool,
+    remember: bool,
+    reason: Option<&str>,
+) {
+    let Some(confirm) = app.confirm_dialog.as_ref() else {
+        return;
+    };
+    let (tool, title, message) = (
+        confirm.tool.clone(),
+        confirm.title.clone(),
+        confirm.message.clone(),
+    );
+    append_decision(app, tool, title, &message, allowed, remember, reason);
+}
+
+/// `/audit`: every recorded confirm decision, newest selected, with the chain check in the title.
+pub(crate) fn open_audit_panel(app: &mut AppState) {
+    let Some(path) = app.audit_log_path.clone() else {
+        app.push_line(
+            LogKind::Status,
+            "Audit log unavailable (no config directory)",
+        );
+        return;
+    };
+    let log = match load_audit_log(&path) {
+        Ok(log) => log,
+        Err(error) => {
+            app.push_error_report(
+                "audit log read failed",
+                format!("{}: {error}", path.display()),
+            );
+            return;
+        }
+    };
+    if log.entries.is_empty() {
+        app.push_line(
+            LogKind::Status,
+            format!("No confirm decisions recorded yet ({})", path.display()),
+        );
+        return;
+    }
+    let rows = log
+        .entries
+        .iter()
+        .map(|entry| {
+            let reason = entry
+                .reason
+                .as_deref()
+                .map(|reason| format!(" — {reason}"))
+                .unwrap_or_default();
+            format!(
+                "{}  {:<14} {:<12} {} {}{reason}",
+                format_utc_timestamp(entry.ts_unix_ms),
+                entry.decision_label(),
+                entry.tool.as_deref().unwrap_or("-"),
+                entry.title,
+                entry.summary
+            )
+        })
+        .collect::<Vec<_>>();
+    let integrity = match log.broken_at {
+        Some(number) => format!("⚠ chain broken at #{number}: file was edited"),
+        None => "chain verified".to_string(),
+    };
+    app.skills_list_panel = None;
+    app.theme_list_panel = None;
+    app.context_panel = Some(ContextPanelState {
+        title: format!("Audit log ({} decisions, {integrity})", rows.len()),
+        header: format!(
+            "{:<20}  {:<14} {:<12} request  ({})",
+            "time (UTC)",
+            "decision",
+            "tool",
+            path.display()
+        ),
+        selected: rows.len() - 1,
+        rows,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{open_audit_panel, record_confirm_decision};
+    use crate::app::state::ConfirmAccelerators;
+    use crate::app::{AppState, ConfirmDialogState, ConfirmMode};
+
+    #[test]
+    fn confirm_decisions_are_recorded_and_listed() {
+        let path =
+            std::env::temp_dir().join(format!("codelia-audit-panel-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut app = AppState {
+            audit_log_path: Some(path.clone()),
+            ..AppState::default()
+        };
+        app.confirm_dialog = Some(ConfirmDialogState {
+            id: "confirm-1".to_string(),
+            tool: Some("bash".to_string()),
+            title: "Run command?".to_string(),
+            message: "rm -rf build\nremember: bash rm".to_string(),
+            danger_level: None,
+            confirm_label: "Allow".to_string(),
+            cancel_label: "Deny".to_string(),
+            allow_remember: true,
+            allow_reason: true,
+            command_view: false,
+            selected: 0,
+            mode: ConfirmMode::Select,
+            accelerators: ConfirmAccelerators::default(),
+            hunk_review: None,
+        });
+        record_confirm_decision(&mut app, true, true, None);
+        record_confirm_decision(&mut app, false, false, Some("wrong dir"));
+
+        open_audit_panel(&mut app);
+        let _ = std::fs::remove_file(&path);
+        let panel = app.context_panel.as_ref().expect("audit panel");
+        assert!(panel.title.contains("2 decisions, chain verified"));
+        assert!(panel.rows[0].contains("allow+remember bash         Run command? rm -rf build"));
+        assert!(panel.rows[1].ends_with("— wrong dir"));
+        assert_eq!(panel.selected, 1);
+    }
+}
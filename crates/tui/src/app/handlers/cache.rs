@@ -0,0 +1,146 @@
+use crate::app::runtime::{send_cache_delete, send_cache_list, send_cache_read, RuntimeStdin};
+use crate::app::state::LogKind;
+use crate::app::{AppState, CacheReadAction, CachedOutputContext, CachedOutputTarget};
+use serde_json::json;
+
+/// Preview and attach only need the head of an output; save asks for up to 1 MiB.
+const CACHE_PREVIEW_MAX_BYTES: usize = 64 * 1024;
+const CACHE_ATTACH_MAX_BYTES: usize = 32 * 1024;
+pub(crate) const CACHE_SAVE_MAX_BYTES: usize = 1024 * 1024;
+
+fn close_other_panels(app: &mut AppState) {
+    app.model_list_panel = None;
+    app.reasoning_picker = None;
+    app.session_list_panel = None;
+    app.context_panel = None;
+    app.skills_list_panel = None;
+    app.lane_list_panel = None;
+    app.mcp_list_panel = None;
+    app.theme_list_panel = None;
+    app.secrets_panel = None;
+    app.tools_panel = None;
+}
+
+pub(crate) fn request_cache_list(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) {
+    if !app.runtime_info.supports_cache {
+        app.push_line(LogKind::Status, "Tool output cache browser unavailable");
+        return;
+    }
+    close_other_panels(app);
+    let id = next_id();
+    app.rpc_pending.cache_list_id = Some(id.clone());
+    if let Err(error) = send_cache_list(child_stdin, &id) {
+        app.rpc_pending.cache_list_id = None;
+        app.push_error_report("send error", error.to_string());
+    }
+}
+
+pub(crate) fn request_cache_read(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+    action: CacheReadAction,
+) {
+    let Some(item) = app
+        .cache_list_panel
+        .as_ref()
+        .and_then(|panel| panel.items.get(panel.selected))
+        .cloned()
+    else {
+        return;
+    };
+    if app.rpc_pending.cache_read_id.is_some() {
+        app.push_line(LogKind::Status, "A cached output is already loading.");
+        return;
+    }
+    let max_bytes = match action {
+        CacheReadAction::Preview => CACHE_PREVIEW_MAX_BYTES,
+        CacheReadAction::Attach => CACHE_ATTACH_MAX_BYTES,
+        CacheReadAction::Save => CACHE_SAVE_MAX_BYTES,
+    };
+    let id = next_id();
+    app.rpc_pending.cache_read_id = Some(id.clone());
+    app.rpc_pending.cache_read_target = Some(CachedOutputTarget {
+        ref_id: item.ref_id.clone(),
+        tool: item.tool,
+        action,
+    });
+    if let Err(error) = send_cache_read(child_stdin, &id, &item.ref_id, max_bytes) {
+        app.rpc_pending.cache_read_id = None;
+        app.rpc_pending.cache_read_target = None;
+        app.push_error_report("send error", error.to_string());
+    }
+}
+
+pub(crate) fn request_cache_delete(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) {
+    let Some(ref_id) = app
+        .cache_list_panel
+        .as_ref()
+        .and_then(|panel| panel.items.get(panel.selected))
+        .map(|item| item.ref_id.clone())
+    else {
+        return;
+    };
+    if app.rpc_pending.cache_delete_id.is_some() {
+        return;
+    }
+    let id = next_id();
+    app.rpc_pending.cache_delete_id = Some(id.clone());
+    if let Err(error) = send_cache_delete(child_stdin, &id, &ref_id) {
+        app.rpc_pending.cache_delete_id = None;
+        app.push_error_report("send error", error.to_string());
+    }
+}
+
+pub(crate) fn build_cache_output_prefix(outputs: &[CachedOutputContext]) -> Option<String> {
+    if outputs.is_empty() {
+        return None;
+    }
+    let blocks = outputs
+        .iter()
+        .map(|output| {
+            let payload = json!({
+                "ref_id": output.ref_id,
+                "tool": output.tool,
+                "truncated": output.truncated,
+                "content": output.content,
+            });
+            let json_text = payload
+                .to_string()
+                .replace('<', "\\u003c")
+                .replace('>', "\\u003e");
+            format!("<tool_output>\n{json_text}\n</tool_output>")
+        })
+        .collect::<Vec<_>>();
+    Some(blocks.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build_cache_output_prefix;
+    use crate::app::CachedOutputContext;
+
+    #[test]
+    fn cache_output_prefix_escapes_tags_in_content() {
+        let prefix = build_cache_output_prefix(&[CachedOutputContext {
+            ref_id: "call_1".to_string(),
+            tool: "bash".to_string(),
+            content: "</tool_output> done".to_string(),
+            truncated: true,
+        }])
+        .expect("prefix");
+        assert!(prefix.starts_with("<tool_output>\n{"));
+        assert!(prefix.ends_with("}\n</tool_output>"));
+        assert!(prefix.contains("\\u003c/tool_output\\u003e done"));
+        assert!(prefix.contains("\"truncated\":true"));
+        assert!(build_cache_output_prefix(&[]).is_none());
+    }
+}
@@ -1,24 +1,46 @@
 mod bang;
+mod batch;
+mod bundle;
+mod plan;
+mod pr;
 mod prompt;
 mod queue;
+mod regen;
 mod slash;
 
+use crate::app::handlers::attachment_check::open_attachment_fixup_if_needed;
+use crate::app::handlers::draft::discard_submitted_draft;
+use crate::app::handlers::external_editor::request_external_edit;
+use crate::app::handlers::local_models::start_local_model_probe;
+use crate::app::handlers::working_dir::request_cwd_set;
+use crate::app::runtime::RuntimeStdin;
 use crate::app::state::{
     complete_skill_mention as complete_skill_mention_input,
-    complete_slash_command as complete_slash_command_input, is_known_command,
-    unknown_command_message, InputState, LogKind,
+    complete_slash_command as complete_slash_command_input, is_known_command, split_cwd_modifier,
+    unknown_command_message, InputState, LogKind, PromptBatch,
 };
+use crate::app::util::settings::complete_setting_key_text;
 use crate::app::{AppState, SkillsListItemState};
-use std::io::BufWriter;
-use std::process::ChildStdin;
 
 use bang::{build_shell_result_prefix, handle_bang_command};
+use batch::handle_batch_command;
+use bundle::handle_bundle_command;
+use plan::handle_plan_command;
+use pr::handle_pr_command;
 use queue::handle_queue_command;
+use regen::handle_regen_command;
 use slash::{
-    handle_compact_command, handle_context_command, handle_errors_command, handle_fast_command,
-    handle_help_command, handle_lane_command, handle_logout_command, handle_mcp_command,
-    handle_model_command, handle_model_session_command, handle_skills_command,
-    handle_tasks_command, handle_theme_command,
+    handle_audit_command, handle_cache_command, handle_cd_command, handle_ci_command,
+    handle_compact_command, handle_context_command, handle_copy_command, handle_debug_keys_command,
+    handle_draft_command, handle_errors_command, handle_export_command, handle_fast_command,
+    handle_glyphs_command, handle_help_command, handle_issue_command, handle_json_command,
+    handle_lane_command, handle_link_command, handle_logout_command, handle_mcp_command,
+    handle_model_command, handle_model_session_command, handle_note_command, handle_notify_command,
+    handle_page_command, handle_permissions_command, handle_pin_command, handle_providers_command,
+    handle_runtime_command, handle_scratch_command, handle_secrets_command, handle_set_command,
+    handle_skills_command, handle_tab_command, handle_tasks_command, handle_tee_command,
+    handle_theme_command, handle_tools_command, handle_trace_command, handle_translate_command,
+    handle_usage_command, handle_whatsnew_command,
 };
 
 const MODEL_PROVIDERS: &[&str] = &[
@@ -28,19 +50,55 @@ const MODEL_PROVIDERS: &[&str] = &[
     "moonshot",
     "zai",
     "xai",
+    "local",
 ];
 const COMMAND_SUGGESTION_LIMIT: usize = 12;
 const QUEUE_PREVIEW_MAX_CHARS: usize = 72;
 const QUEUE_LIST_LIMIT: usize = 5;
 const QUEUE_EMPTY_MESSAGE: &str = "queue is empty";
-const QUEUE_USAGE_MESSAGE: &str = "usage: /queue [cancel [id|index]|clear]";
+const QUEUE_USAGE_MESSAGE: &str = "usage: /queue [list|cancel [id|index]|clear]";
 const QUEUE_CANCEL_USAGE_MESSAGE: &str = "usage: /queue cancel [id|index]";
 const QUEUE_CLEAR_USAGE_MESSAGE: &str = "usage: /queue clear";
 const TASKS_USAGE_MESSAGE: &str = "usage: /tasks [list|show <task_id>|cancel <task_id>]";
-
-type RuntimeStdin = BufWriter<ChildStdin>;
+const BUNDLE_USAGE_MESSAGE: &str = "usage: /bundle [dir]";
+const ISSUE_USAGE_MESSAGE: &str = "usage: /issue <number|url>";
+const CI_USAGE_MESSAGE: &str = "usage: /ci [watch|stop|logs]";
+const PR_USAGE_MESSAGE: &str = "usage: /pr [--draft] [title...]|confirm|cancel";
+const PLAN_USAGE_MESSAGE: &str = "usage: /plan <prompt>|run|discard";
+const PAGE_USAGE_MESSAGE: &str = "usage: /page [n]";
+const SET_USAGE_MESSAGE: &str = "usage: /set [<key> [value]]";
+const NOTIFY_USAGE_MESSAGE: &str = "usage: /notify [on|off|threshold <secs>]";
+const GLYPHS_USAGE_MESSAGE: &str = "usage: /glyphs [ascii|unicode|auto]";
+const WHATSNEW_USAGE_MESSAGE: &str = "usage: /whatsnew";
+const USAGE_USAGE_MESSAGE: &str = "usage: /usage";
+const PROVIDERS_USAGE_MESSAGE: &str = "usage: /providers [stats]";
+const DRAFT_USAGE_MESSAGE: &str = "usage: /draft [restore|discard]";
+const TRACE_USAGE_MESSAGE: &str = "usage: /trace";
+const AUDIT_USAGE_MESSAGE: &str = "usage: /audit";
+const DEBUG_KEYS_USAGE_MESSAGE: &str = "usage: /debug-keys [file]";
+const BATCH_USAGE_MESSAGE: &str = "usage: /batch <file>";
+const JSON_USAGE_MESSAGE: &str = "usage: /json <schema|description>|off|show|save [file]";
+const TRANSLATE_USAGE_MESSAGE: &str = "usage: /translate [<lang>|off|show [<turn>]]";
+const RUNTIME_USAGE_MESSAGE: &str = "usage: /runtime [copy]";
+const EXPORT_USAGE_MESSAGE: &str = "usage: /export <ansi|cast|md|html> [--run[=<id>]] [file]";
+const LINK_USAGE_MESSAGE: &str = "usage: /link [turn]";
+const COPY_USAGE_MESSAGE: &str = "usage: /copy [code|pick]";
+const TEE_USAGE_MESSAGE: &str = "usage: /tee <file>|off";
+const TAB_USAGE_MESSAGE: &str = "usage: /tab [new|next|prev|close [n]|<n>]";
+const REGEN_USAGE_MESSAGE: &str = "usage: /regen [diff [split]|pick <1|2>]";
+const TOOLS_USAGE_MESSAGE: &str = "usage: /tools [enable|disable <name>]";
+const PIN_USAGE_MESSAGE: &str = "usage: /pin [add [name] [--turns N]|mark|drop <name>|clear]";
+const NOTE_USAGE_MESSAGE: &str =
+    "usage: /note [--turns N|--for <30m|2h|1d>] <text> | /note [drop <id>|clear]";
+const SCRATCH_USAGE_MESSAGE: &str = "usage: /scratch [pin|add <text>|drop <n>|clear|export [file]]";
+const SECRETS_USAGE_MESSAGE: &str = "usage: /secrets [set <NAME> [--keychain]|rm|on|off <NAME>]";
+const PERMISSIONS_USAGE_MESSAGE: &str = "usage: /permissions [add|rm <pattern>]";
 
 pub(crate) fn complete_slash_command(input: &mut InputState) -> bool {
+    if let Some(completed) = complete_setting_key_text(&input.current()) {
+        input.set_from(&completed);
+        return true;
+    }
     complete_slash_command_input(input)
 }
 
@@ -94,6 +152,78 @@ pub(crate) fn handle_enter(
         handle_queue_command(app, &mut parts);
     } else if command == "/tasks" {
         handle_tasks_command(app, child_stdin, next_id, &mut parts);
+    } else if command == "/bundle" {
+        handle_bundle_command(app, &mut parts);
+    } else if command == "/issue" {
+        handle_issue_command(app, &mut parts);
+    } else if command == "/pr" {
+        handle_pr_command(app, child_stdin, next_id, &mut parts);
+    } else if command == "/ci" {
+        handle_ci_command(app, &mut parts);
+    } else if command == "/plan" {
+        clear_input = handle_plan_command(app, child_stdin, next_id, &trimmed);
+    } else if command == "/secrets" {
+        handle_secrets_command(app, &mut parts);
+    } else if command == "/permissions" {
+        handle_permissions_command(app, &mut parts);
+    } else if command == "/cache" {
+        handle_cache_command(app, child_stdin, next_id, &mut parts);
+    } else if command == "/page" {
+        handle_page_command(app, child_stdin, next_id, &mut parts);
+    } else if command == "/audit" {
+        handle_audit_command(app, &mut parts);
+    } else if command == "/debug-keys" {
+        handle_debug_keys_command(app, &mut parts);
+    } else if command == "/batch" {
+        handle_batch_command(app, child_stdin, next_id, &trimmed);
+    } else if command == "/json" {
+        handle_json_command(app, &trimmed, &mut parts);
+    } else if command == "/translate" {
+        handle_translate_command(app, &mut parts);
+    } else if command == "/cd" {
+        handle_cd_command(app, child_stdin, next_id, &trimmed);
+    } else if command == "/runtime" {
+        handle_runtime_command(app, child_stdin, next_id, &mut parts);
+    } else if command == "/export" {
+        handle_export_command(app, &mut parts);
+    } else if command == "/tab" {
+        handle_tab_command(app, &mut parts);
+    } else if command == "/tee" {
+        handle_tee_command(app, &trimmed);
+    } else if command == "/link" {
+        handle_link_command(app, &mut parts);
+    } else if command == "/edit" {
+        request_external_edit(app);
+    } else if command == "/copy" {
+        handle_copy_command(app, &mut parts);
+    } else if command == "/local-models" {
+        start_local_model_probe(app);
+    } else if command == "/regen" {
+        handle_regen_command(app, child_stdin, next_id, &mut parts);
+    } else if command == "/tools" {
+        handle_tools_command(app, child_stdin, next_id, &mut parts);
+    } else if command == "/pin" {
+        handle_pin_command(app, &mut parts);
+    } else if command == "/scratch" {
+        handle_scratch_command(app, &trimmed, &mut parts);
+    } else if command == "/note" {
+        handle_note_command(app, &mut parts);
+    } else if command == "/set" {
+        handle_set_command(app, &mut parts);
+    } else if command == "/notify" {
+        handle_notify_command(app, &mut parts);
+    } else if command == "/glyphs" {
+        handle_glyphs_command(app, &mut parts);
+    } else if command == "/whatsnew" {
+        handle_whatsnew_command(app, &mut parts);
+    } else if command == "/usage" {
+        handle_usage_command(app, &mut parts);
+    } else if command == "/providers" {
+        handle_providers_command(app, &mut parts);
+    } else if command == "/trace" {
+        handle_trace_command(app, &mut parts);
+    } else if command == "/draft" {
+        clear_input = handle_draft_command(app, &mut parts);
     } else if command == "/help" {
         handle_help_command(app, &mut parts);
     } else if trimmed.starts_with("!") {
@@ -101,11 +231,19 @@ pub(crate) fn handle_enter(
     } else if !is_known_command(command) && command.starts_with('/') {
         app.push_line(LogKind::Error, unknown_command_message(command));
         clear_input = false;
+    } else if open_attachment_fixup_if_needed(app, &raw_input) {
+        clear_input = false;
+    } else if let Some((path, prompt)) = split_cwd_modifier(&raw_input) {
+        let prompt = (!prompt.is_empty()).then(|| prompt.to_string());
+        clear_input = request_cwd_set(app, child_stdin, next_id, path, prompt);
     } else {
         clear_input = start_prompt_run(app, child_stdin, next_id, &raw_input);
     }
 
     if clear_input {
+        if command != "/draft" {
+            discard_submitted_draft(app);
+        }
         app.clear_composer();
     }
     true
@@ -123,28 +261,67 @@ pub(crate) fn try_dispatch_queued_prompt(
     prompt::try_dispatch_queued_prompt(app, child_stdin, next_id)
 }
 
+pub(crate) fn run_ready_plan(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) -> bool {
+    plan::run_ready_plan(app, child_stdin, next_id)
+}
+
+pub(crate) fn finish_dry_run(app: &mut AppState, status: &str) {
+    plan::finish_dry_run(app, status);
+}
+
+pub(crate) fn record_regenerated_answer(app: &mut AppState, answer: &str) {
+    regen::record_regenerated_answer(app, answer);
+}
+
 pub(crate) fn start_prompt_run(
     app: &mut AppState,
     child_stdin: &mut RuntimeStdin,
     next_id: &mut impl FnMut() -> String,
     raw_input: &str,
 ) -> bool {
-    prompt::start_prompt_run(app, child_stdin, next_id, raw_input)
+    prompt::start_prompt_run(app, child_stdin, next_id, raw_input, false)
+}
+
+pub(crate) fn load_prompt_batch(path: &std::path::Path) -> Result<PromptBatch, String> {
+    batch::load_prompt_batch(path)
+}
+
+pub(crate) fn start_pending_prompt_batch(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) -> bool {
+    batch::start_pending_prompt_batch(app, child_stdin, next_id)
+}
+
+pub(crate) fn finish_prompt_batch_if_done(app: &mut AppState) -> bool {
+    batch::finish_prompt_batch_if_done(app)
 }
 
 #[cfg(test)]
 mod tests {
     use super::bang::resolve_bang_command;
     use super::{
-        build_shell_result_prefix, handle_enter, try_dispatch_queued_prompt, MODEL_PROVIDERS,
-        QUEUE_EMPTY_MESSAGE,
+        build_shell_result_prefix, finish_dry_run, finish_prompt_batch_if_done, handle_enter,
+        run_ready_plan, try_dispatch_queued_prompt, BATCH_USAGE_MESSAGE, BUNDLE_USAGE_MESSAGE,
+        MODEL_PROVIDERS, PIN_USAGE_MESSAGE, QUEUE_EMPTY_MESSAGE, SCRATCH_USAGE_MESSAGE,
+        TAB_USAGE_MESSAGE,
+    };
+    use crate::app::handlers::attachment_check::{
+        handle_attachment_fixup_key, ATTACHMENT_FIXUP_PICK_ID,
     };
+    use crate::app::runtime::RuntimeStdin;
+    use crate::app::state::{LogKind, TabIndicator, TabRequest};
     use crate::app::util::attachments::make_attachment_token;
     use crate::app::{AppState, PendingShellResult};
-    use std::io::{BufWriter, Write};
+    use crossterm::event::KeyCode;
     use std::process::Stdio;
 
-    fn with_runtime_writer<T>(f: impl FnOnce(&mut BufWriter<std::process::ChildStdin>) -> T) -> T {
+    fn with_runtime_writer<T>(f: impl FnOnce(&mut RuntimeStdin) -> T) -> T {
         #[cfg(windows)]
         let mut command = {
             let mut command = std::process::Command::new("cmd");
@@ -162,10 +339,9 @@ mod tests {
             .expect("spawn runtime writer helper");
 
         let child_stdin = child.stdin.take().expect("child stdin");
-        let mut runtime_writer = BufWriter::new(child_stdin);
+        let mut runtime_writer = RuntimeStdin::new(child_stdin);
         let out = f(&mut runtime_writer);
 
-        let _ = runtime_writer.flush();
         let _ = child.kill();
         let _ = child.wait();
         out
@@ -230,6 +406,29 @@ mod tests {
         });
     }
 
+    #[test]
+    fn bang_command_is_refused_in_read_only_mode() {
+        with_runtime_writer(|writer| {
+            let mut app = AppState {
+                read_only: true,
+                ..AppState::default()
+            };
+            app.runtime_info.supports_shell_exec = true;
+            app.runtime_info.supports_shell_tasks = true;
+            let mut next_id = || "id-1".to_string();
+
+            app.input.set_from("!touch file");
+            assert!(handle_enter(&mut app, writer, &mut next_id));
+            assert_eq!(app.input.current(), "!touch file");
+            assert!(app.rpc_pending.shell_start_id.is_none());
+            assert!(app.rpc_pending.shell_exec_id.is_none());
+            assert!(app
+                .log
+                .iter()
+                .any(|line| line.plain_text().contains("disabled in read-only mode")));
+        });
+    }
+
     #[test]
     fn tasks_command_starts_task_list_request() {
         with_runtime_writer(|writer| {
@@ -247,6 +446,297 @@ mod tests {
         });
     }
 
+    #[test]
+    fn context_graph_command_opens_usage_panel_without_runtime_call() {
+        with_runtime_writer(|writer| {
+            let mut app = AppState::default();
+            let mut next_id = || "id-1".to_string();
+            app.input.set_from("/context graph");
+            handle_enter(&mut app, writer, &mut next_id);
+            assert!(app.context_panel.is_none());
+
+            app.context_history.record(Some("run-a"), 90);
+            app.context_history.record(Some("run-b"), 60);
+            app.input.set_from("/context graph");
+            handle_enter(&mut app, writer, &mut next_id);
+            let panel = app.context_panel.as_ref().expect("graph panel");
+            assert_eq!(panel.rows.len(), 2);
+            assert_eq!(panel.selected, 1);
+            assert!(panel.rows[1].contains("40% used  +30%"));
+            assert!(app.rpc_pending.context_inspect_id.is_none());
+        });
+    }
+
+    #[test]
+    fn scratch_command_pins_answer_and_notes_then_exports_markdown() {
+        with_runtime_writer(|writer| {
+            let mut app = AppState::default();
+            let mut next_id = || "id-1".to_string();
+            app.last_assistant_text = Some("API sketch\nfn get(id: u64)".to_string());
+            app.input.set_from("/scratch pin");
+            handle_enter(&mut app, writer, &mut next_id);
+            app.input.set_from("/scratch add  - [ ] run migrations");
+            handle_enter(&mut app, writer, &mut next_id);
+            assert_eq!(app.scratchpad.entries().len(), 2);
+            assert_eq!(app.scratchpad.entries()[1].text, "- [ ] run migrations");
+
+            app.input.set_from("/scratch");
+            handle_enter(&mut app, writer, &mut next_id);
+            let panel = app.context_panel.as_ref().expect("scratchpad panel");
+            assert_eq!(panel.rows[0], "#1 answer: API sketch");
+            assert_eq!(panel.rows[2], "  fn get(id: u64)");
+
+            app.input.set_from("/scratch drop 1");
+            handle_enter(&mut app, writer, &mut next_id);
+            let panel = app.context_panel.as_ref().expect("refreshed panel");
+            assert_eq!(panel.rows[0], "#1 note: - [ ] run migrations");
+
+            let path = std::env::temp_dir()
+                .join(format!("codelia-scratchpad-test-{}.md", std::process::id()));
+            app.input
+                .set_from(&format!("/scratch export {}", path.display()));
+            handle_enter(&mut app, writer, &mut next_id);
+            let written = std::fs::read_to_string(&path).expect("exported file");
+            let _ = std::fs::remove_file(&path);
+            assert!(written.contains("## 1. note: - [ ] run migrations"));
+
+            app.input.set_from("/scratch drop x");
+            handle_enter(&mut app, writer, &mut next_id);
+            let last = app.log.last().expect("usage line").plain_text();
+            assert_eq!(last, SCRATCH_USAGE_MESSAGE);
+        });
+    }
+
+    #[test]
+    fn pin_command_sends_marked_log_range_with_the_next_prompts() {
+        with_runtime_writer(|writer| {
+            let mut app = AppState::default();
+            app.update_run_status("running".to_string());
+            let mut next_id = || "id-1".to_string();
+            app.push_line(LogKind::Status, "before the mark");
+            app.input.set_from("/pin mark");
+            handle_enter(&mut app, writer, &mut next_id);
+            app.push_line(LogKind::Error, "error[E0308]: mismatched types");
+            app.push_line(LogKind::Error, "  --> src/lib.rs:3:5");
+            app.input.set_from("/pin add e0308 --turns 2");
+            handle_enter(&mut app, writer, &mut next_id);
+            assert!(app.context_pins.mark().is_none());
+            assert_eq!(
+                app.context_pins.pins()[0].text,
+                "error[E0308]: mismatched types\n  --> src/lib.rs:3:5"
+            );
+
+            for prompt in ["fix it", "and the test", "anything else?"] {
+                app.input.set_from(prompt);
+                handle_enter(&mut app, writer, &mut next_id);
+            }
+            let payload = |index: usize| {
+                app.pending_prompt_queue[index].input_payload["text"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            assert!(payload(0).starts_with("<pinned_context>\n{"));
+            assert!(payload(0).contains("src/lib.rs:3:5"));
+            assert!(payload(0).ends_with("\n\nfix it"));
+            assert!(payload(1).contains("<pinned_context>"));
+            assert_eq!(payload(2), "anything else?");
+            assert!(app.context_pins.is_empty());
+
+            app.input.set_from("/pin drop");
+            handle_enter(&mut app, writer, &mut next_id);
+            let last = app.log.last().expect("usage line").plain_text();
+            assert_eq!(last, PIN_USAGE_MESSAGE);
+        });
+    }
+
+    #[test]
+    fn batch_command_queues_file_prompts_in_order_and_reports_completion() {
+        with_runtime_writer(|writer| {
+            let mut app = AppState::default();
+            app.update_run_status("running".to_string());
+            let mut next_id = || "id-1".to_string();
+
+            let path =
+                std::env::temp_dir().join(format!("codelia-batch-test-{}.txt", std::process::id()));
+            std::fs::write(&path, "first prompt\ncontinued\n\n\nsecond prompt\n").unwrap();
+            app.input.set_from(&format!("/batch {}", path.display()));
+            handle_enter(&mut app, writer, &mut next_id);
+            let _ = std::fs::remove_file(&path);
+
+            let previews = app
+                .pending_prompt_queue
+                .iter()
+                .map(|prompt| prompt.preview.as_str())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                previews,
+                vec!["[batch 1/2] first prompt", "[batch 2/2] second prompt"]
+            );
+            assert_eq!(
+                app.pending_prompt_queue[0].user_text,
+                "first prompt\ncontinued"
+            );
+            let waiting = app.prompt_batch_waiting().expect("batch tracked");
+            let batch = app.prompt_batch.as_ref().expect("batch tracked");
+            assert_eq!(batch.status_segment(waiting), "batch: 0/2");
+
+            app.input.set_from("/batch");
+            handle_enter(&mut app, writer, &mut next_id);
+            let last = app.log.last().expect("usage line").plain_text();
+            assert_eq!(last, BATCH_USAGE_MESSAGE);
+
+            app.pending_prompt_queue.clear();
+            assert!(!finish_prompt_batch_if_done(&mut app));
+            app.update_run_status("completed".to_string());
+            assert!(finish_prompt_batch_if_done(&mut app));
+            assert!(app.prompt_batch.is_none());
+        });
+    }
+
+    #[test]
+    fn json_command_attaches_output_request_to_next_prompt_only() {
+        with_runtime_writer(|writer| {
+            let mut app = AppState::default();
+            app.update_run_status("running".to_string());
+            let mut next_id = || "id-1".to_string();
+
+            app.input.set_from("/json a list of changed file paths");
+            handle_enter(&mut app, writer, &mut next_id);
+            assert!(app.json_output.armed.is_some());
+
+            app.input.set_from("what changed?");
+            handle_enter(&mut app, writer, &mut next_id);
+            app.input.set_from("and why?");
+            handle_enter(&mut app, writer, &mut next_id);
+            assert!(app.json_output.armed.is_none());
+
+            let first = &app.pending_prompt_queue[0];
+            assert_eq!(first.preview, "[json] what changed?");
+            assert!(first.json_request.is_some());
+            let payload = first.input_payload["text"].as_str().unwrap_or_default();
+            assert!(payload.starts_with("what changed?\n\nReply with a single JSON value"));
+            assert!(app.pending_prompt_queue[1].json_request.is_none());
+
+            app.input.set_from("/json save");
+            handle_enter(&mut app, writer, &mut next_id);
+            let last = app.log.last().expect("status line").plain_text();
+            assert!(last.starts_with("No JSON output yet"));
+        });
+    }
+
+    #[test]
+    fn cwd_modifier_holds_prompt_until_the_directory_is_accepted() {
+        with_runtime_writer(|writer| {
+            let mut app = AppState::default();
+            let mut next_id = || "id-1".to_string();
+
+            app.input.set_from("@cwd:packages/api fix the failing test");
+            handle_enter(&mut app, writer, &mut next_id);
+            let last = app.log.last().expect("error line").plain_text();
+            assert_eq!(last, "Working directory change unavailable");
+            assert_eq!(
+                app.input.current(),
+                "@cwd:packages/api fix the failing test"
+            );
+
+            app.runtime_info.supports_cwd_set = true;
+            handle_enter(&mut app, writer, &mut next_id);
+            assert_eq!(app.rpc_pending.cwd_set_id.as_deref(), Some("id-1"));
+            assert_eq!(
+                app.rpc_pending.cwd_set_prompt.as_deref(),
+                Some("fix the failing test")
+            );
+            assert!(app.pending_prompt_queue.is_empty());
+            assert!(app.input.current().is_empty());
+
+            app.input.set_from("/cd ..");
+            handle_enter(&mut app, writer, &mut next_id);
+            let last = app.log.last().expect("status line").plain_text();
+            assert_eq!(last, "Working directory change already in progress");
+        });
+    }
+
+    #[test]
+    fn bundle_command_rejects_extra_arguments() {
+        with_runtime_writer(|writer| {
+            let mut app = AppState::default();
+            let mut next_id = || "id-1".to_string();
+
+            app.input.set_from("/bundle out extra");
+            assert!(handle_enter(&mut app, writer, &mut next_id));
+            let last = app.log.last().expect("usage line").plain_text();
+            assert_eq!(last, BUNDLE_USAGE_MESSAGE);
+        });
+    }
+
+    #[test]
+    fn pr_command_previews_draft_then_confirm_sends_shell_exec() {
+        with_runtime_writer(|writer| {
+            let mut app = AppState::default();
+            app.runtime_info.supports_shell_exec = true;
+            app.update_run_status("completed".to_string());
+            app.last_assistant_text = Some("Fixed the login redirect.".to_string());
+            app.run_artifacts.last_prompt = Some("Fix login redirect\nmore".to_string());
+            let mut seq = 0_u64;
+            let mut next_id = || {
+                seq += 1;
+                format!("id-{seq}")
+            };
+
+            app.input.set_from("/pr --draft");
+            assert!(handle_enter(&mut app, writer, &mut next_id));
+            let draft = app.pending_pull_request.clone().expect("draft");
+            assert_eq!(draft.title, "Fix login redirect");
+            assert!(draft.draft);
+            assert!(app.rpc_pending.pr_create_id.is_none());
+
+            app.input.set_from("/pr confirm");
+            assert!(handle_enter(&mut app, writer, &mut next_id));
+            assert_eq!(app.rpc_pending.pr_create_id.as_deref(), Some("id-1"));
+            assert!(app.pending_pull_request.is_none());
+        });
+    }
+
+    #[test]
+    fn plan_command_dispatches_dry_run_then_replays_for_real() {
+        with_runtime_writer(|writer| {
+            let mut app = AppState::default();
+            let mut seq = 0_u64;
+            let mut next_id = || {
+                seq += 1;
+                format!("id-{seq}")
+            };
+
+            app.input.set_from("/plan rename the config loader");
+            assert!(handle_enter(&mut app, writer, &mut next_id));
+            assert!(app.rpc_pending.run_start_id.is_none());
+            assert_eq!(app.input.current(), "/plan rename the config loader");
+
+            app.runtime_info.supports_dry_run = true;
+            assert!(handle_enter(&mut app, writer, &mut next_id));
+            assert!(app.dry_run_active);
+            assert_eq!(app.rpc_pending.run_start_id.as_deref(), Some("id-1"));
+
+            app.planned_steps.push("edit: src/config.rs".to_string());
+            app.rpc_pending.run_start_id = None;
+            app.dispatching_prompt = None;
+            finish_dry_run(&mut app, "completed");
+            assert!(!app.dry_run_active);
+            assert_eq!(app.ready_plan.as_deref(), Some("rename the config loader"));
+            assert!(app
+                .log
+                .iter()
+                .any(|line| line.plain_text() == "  1. edit: src/config.rs"));
+
+            app.update_run_status("completed".to_string());
+            assert!(run_ready_plan(&mut app, writer, &mut next_id));
+            assert!(app.ready_plan.is_none());
+            assert!(!app.dry_run_active);
+            assert_eq!(app.rpc_pending.run_start_id.as_deref(), Some("id-2"));
+        });
+    }
+
     #[test]
     fn enqueue_while_run_active_snapshots_payload_and_clears_shell_results_once() {
         with_runtime_writer(|writer| {
@@ -293,6 +783,7 @@ mod tests {
             assert!(app.pending_shell_results.is_empty());
             assert!(app.input.current().is_empty());
             assert!(app.pending_image_attachments.is_empty());
+            assert_eq!(app.run_artifacts.attachments.len(), 1);
 
             let queued = app.pending_prompt_queue.front().expect("queued");
             assert_eq!(queued.shell_result_count, 1);
@@ -315,6 +806,43 @@ mod tests {
         });
     }
 
+    #[test]
+    fn enter_with_broken_image_reference_opens_fixup_before_sending() {
+        with_runtime_writer(|writer| {
+            let mut app = AppState::default();
+            app.update_run_status("running".to_string());
+            let stale = make_attachment_token("old-nonce", "img1");
+            app.input.set_from(&format!("look at {stale} please"));
+
+            let mut seq = 0_u64;
+            let mut next_id = || {
+                seq += 1;
+                format!("id-{seq}")
+            };
+            assert!(handle_enter(&mut app, writer, &mut next_id));
+            assert!(app.pending_prompt_queue.is_empty());
+            assert!(app.input.current().contains(&stale));
+            let pick = app.pick_dialog.as_ref().expect("fix-up dialog");
+            assert_eq!(pick.id, ATTACHMENT_FIXUP_PICK_ID);
+            assert_eq!(pick.items[0].id, "strip");
+            assert!(pick
+                .message
+                .as_deref()
+                .is_some_and(|message| message.contains("1 image reference(s)")));
+
+            assert!(handle_attachment_fixup_key(
+                &mut app,
+                KeyCode::Enter,
+                writer,
+                &mut next_id
+            ));
+            assert!(app.pick_dialog.is_none());
+            assert!(app.input.current().is_empty());
+            let queued = app.pending_prompt_queue.front().expect("queued");
+            assert_eq!(queued.user_text, "look at  please");
+        });
+    }
+
     #[test]
     fn queue_commands_cancel_and_clear() {
         with_runtime_writer(|writer| {
@@ -432,4 +960,38 @@ mod tests {
             assert!(app.rpc_pending.run_start_id.is_some());
         });
     }
+
+    #[test]
+    fn tab_command_queues_requests_for_the_run_loop() {
+        with_runtime_writer(|writer| {
+            let mut app = AppState::default();
+            let mut next_id = || "id".to_string();
+            app.input.set_from("/tab new");
+            handle_enter(&mut app, writer, &mut next_id);
+            assert_eq!(app.tab_request, None);
+            assert!(app
+                .log
+                .last()
+                .is_some_and(|line| line.kind() == LogKind::Error));
+
+            app.tab_strip.tabs = vec![TabIndicator::default(); 2];
+            for (command, expected) in [
+                ("/tab new", Some(TabRequest::New)),
+                ("/tab prev", Some(TabRequest::Previous)),
+                ("/tab 2", Some(TabRequest::Switch(2))),
+                ("/tab close", Some(TabRequest::Close(None))),
+                ("/tab close 1", Some(TabRequest::Close(Some(1)))),
+                ("/tab close x", None),
+            ] {
+                app.tab_request = None;
+                app.input.set_from(command);
+                handle_enter(&mut app, writer, &mut next_id);
+                assert_eq!(app.tab_request, expected, "{command}");
+            }
+            assert!(app
+                .log
+                .last()
+                .is_some_and(|line| line.plain_text() == TAB_USAGE_MESSAGE));
+        });
+    }
 }
@@ -1,7 +1,7 @@
 use crate::app::runtime::{send_shell_exec, send_shell_start};
 use crate::app::state::LogKind;
 use crate::app::{AppState, PendingShellResult};
-use serde_json::json;
+use serde_json::{json, Map, Value};
 
 use super::RuntimeStdin;
 
@@ -38,6 +38,10 @@ pub(super) fn build_shell_result_prefix(results: &[PendingShellResult]) -> Optio
     Some(blocks.join("\n"))
 }
 
+fn secret_names(env: &Map<String, Value>) -> String {
+    env.keys().cloned().collect::<Vec<_>>().join(", ")
+}
+
 pub(super) fn resolve_bang_command(raw_input: &str, bang_mode: bool) -> String {
     let trimmed = raw_input.trim();
     if bang_mode {
@@ -55,6 +59,13 @@ pub(super) fn handle_bang_command(
     next_id: &mut impl FnMut() -> String,
     raw_input: &str,
 ) -> bool {
+    if app.read_only {
+        app.push_line(
+            LogKind::Status,
+            "Shell commands are disabled in read-only mode.",
+        );
+        return false;
+    }
     if !app.runtime_info.supports_shell_exec {
         app.push_line(LogKind::Status, "Bang shell mode unavailable");
         return false;
@@ -83,11 +94,25 @@ pub(super) fn handle_bang_command(
         app.push_line(LogKind::Error, "bang command is empty");
         return false;
     }
+    let env = app.secrets.injected_env();
+    if env.is_some() && !app.runtime_info.supports_shell_env {
+        app.push_line(
+            LogKind::Status,
+            "Runtime does not support shell env; secrets were not injected.",
+        );
+    }
+    let env = env.filter(|_| app.runtime_info.supports_shell_env);
     let id = next_id();
     app.push_line(LogKind::Status, format!("bang exec started: {}", command));
+    if let Some(env) = env.as_ref() {
+        app.push_line(
+            LogKind::Status,
+            format!("  with secrets: {}", secret_names(env)),
+        );
+    }
     if app.runtime_info.supports_shell_tasks {
         app.rpc_pending.shell_start_id = Some(id.clone());
-        if let Err(error) = send_shell_start(child_stdin, &id, &command, None) {
+        if let Err(error) = send_shell_start(child_stdin, &id, &command, None, env.as_ref()) {
             app.rpc_pending.shell_start_id = None;
             app.push_line(LogKind::Error, format!("send error: {error}"));
             return false;
@@ -95,7 +120,7 @@ pub(super) fn handle_bang_command(
         return true;
     }
     app.rpc_pending.shell_exec_id = Some(id.clone());
-    if let Err(error) = send_shell_exec(child_stdin, &id, &command, None) {
+    if let Err(error) = send_shell_exec(child_stdin, &id, &command, None, env.as_ref()) {
         app.rpc_pending.shell_exec_id = None;
         app.push_line(LogKind::Error, format!("send error: {error}"));
         return false;
@@ -0,0 +1,138 @@
+use crate::app::handlers::runtime_response::can_auto_start_initial_message;
+use crate::app::state::{split_prompt_blocks, LogKind, PromptBatch};
+use crate::app::AppState;
+use std::path::Path;
+
+use super::prompt::start_batch_prompt_run;
+use super::{RuntimeStdin, BATCH_USAGE_MESSAGE};
+
+const BATCH_PREVIEW_LIST_LIMIT: usize = 10;
+
+/// Reads a prompt file; every blank-line-separated block becomes one prompt.
+pub(super) fn load_prompt_batch(path: &Path) -> Result<PromptBatch, String> {
+    let text =
+        std::fs::read_to_string(path).map_err(|error| format!("{}: {error}", path.display()))?;
+    let prompts = split_prompt_blocks(&text);
+    if prompts.is_empty() {
+        return Err(format!("{}: no prompts found", path.display()));
+    }
+    let label = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string());
+    Ok(PromptBatch {
+        label,
+        unstarted: prompts,
+        queue_ids: Vec::new(),
+    })
+}
+
+fn enqueue_prompt_batch(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+    mut batch: PromptBatch,
+) {
+    let prompts = std::mem::take(&mut batch.unstarted);
+    let total = prompts.len();
+    let mut previews = Vec::new();
+    for (index, prompt) in prompts.iter().enumerate() {
+        if let Some((queue_id, preview)) =
+            start_batch_prompt_run(app, child_stdin, next_id, prompt, index + 1, total)
+        {
+            batch.queue_ids.push(queue_id);
+            previews.push(preview);
+        }
+    }
+    app.push_line(
+        LogKind::Status,
+        format!(
+            "Batch {}: queued {} prompt(s); they run one after another",
+            batch.label,
+            batch.queue_ids.len()
+        ),
+    );
+    for preview in previews.iter().take(BATCH_PREVIEW_LIST_LIMIT) {
+        app.push_line(LogKind::Status, format!("  {preview}"));
+    }
+    if previews.len() > BATCH_PREVIEW_LIST_LIMIT {
+        app.push_line(
+            LogKind::Status,
+            format!(
+                "  ... {} more (/queue lists them, /queue clear stops the batch)",
+                previews.len() - BATCH_PREVIEW_LIST_LIMIT
+            ),
+        );
+    }
+    app.prompt_batch = Some(batch);
+}
+
+pub(super) fn handle_batch_command(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+    trimmed: &str,
+) {
+    let path = trimmed.strip_prefix("/batch").unwrap_or_default().trim();
+    if path.is_empty() {
+        app.push_line(LogKind::Error, BATCH_USAGE_MESSAGE);
+        return;
+    }
+    if let (Some(batch), Some(waiting)) = (app.prompt_batch.as_ref(), app.prompt_batch_waiting()) {
+        app.push_line(
+            LogKind::Error,
+            format!(
+                "Batch {} is still running ({}); wait for it or /queue clear",
+                batch.label,
+                batch.status_segment(waiting)
+            ),
+        );
+        return;
+    }
+    match load_prompt_batch(Path::new(path)) {
+        Ok(batch) => enqueue_prompt_batch(app, child_stdin, next_id, batch),
+        Err(error) => app.push_error_report("batch load failed", error),
+    }
+}
+
+/// Enqueues a `--prompt-file` batch once the runtime is ready, like `--initial-message`.
+pub(super) fn start_pending_prompt_batch(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) -> bool {
+    let has_unstarted = app
+        .prompt_batch
+        .as_ref()
+        .is_some_and(|batch| !batch.unstarted.is_empty());
+    if !has_unstarted || !can_auto_start_initial_message(app) {
+        return false;
+    }
+    let Some(batch) = app.prompt_batch.take() else {
+        return false;
+    };
+    enqueue_prompt_batch(app, child_stdin, next_id, batch);
+    true
+}
+
+/// Reports completion once every batch item has been dispatched and the last run has ended.
+pub(super) fn finish_prompt_batch_if_done(app: &mut AppState) -> bool {
+    if app.prompt_batch_waiting() != Some(0)
+        || app.is_running()
+        || app.rpc_pending.run_start_id.is_some()
+    {
+        return false;
+    }
+    let Some(batch) = app.prompt_batch.take() else {
+        return false;
+    };
+    app.push_line(
+        LogKind::Status,
+        format!(
+            "Batch {} finished: {} prompt(s) dispatched",
+            batch.label,
+            batch.queue_ids.len()
+        ),
+    );
+    true
+}
@@ -0,0 +1,70 @@
+use crate::app::state::LogKind;
+use crate::app::util::bundle::{resolve_bundle_dir, write_run_bundle, RunBundleInput};
+use crate::app::AppState;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::BUNDLE_USAGE_MESSAGE;
+
+fn transcript_lines(app: &AppState) -> Vec<String> {
+    app.log
+        .iter()
+        .filter(|line| !matches!(line.kind(), LogKind::Runtime | LogKind::Rpc))
+        .map(|line| line.plain_text())
+        .collect()
+}
+
+pub(super) fn handle_bundle_command<'a>(
+    app: &mut AppState,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    let target = parts.next();
+    if parts.next().is_some() {
+        app.push_line(LogKind::Error, BUNDLE_USAGE_MESSAGE);
+        return;
+    }
+
+    let created_at_unix_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|value| value.as_millis())
+        .unwrap_or(0);
+    let dir = resolve_bundle_dir(target, created_at_unix_ms);
+    let transcript = transcript_lines(app);
+    let model = match (
+        app.runtime_info.current_provider.as_deref(),
+        app.runtime_info.current_model.as_deref(),
+    ) {
+        (Some(provider), Some(model)) => Some(format!("{provider}/{model}")),
+        (None, Some(model)) => Some(model.to_string()),
+        _ => None,
+    };
+    let result = write_run_bundle(
+        &dir,
+        &RunBundleInput {
+            transcript: &transcript,
+            diffs: &app.run_artifacts.diffs,
+            shell_results: &app.run_artifacts.shell_results,
+            attachments: &app.run_artifacts.attachments,
+            session_id: app.runtime_info.session_id.as_deref(),
+            model: model.as_deref(),
+            created_at_unix_ms,
+        },
+    );
+    match result {
+        Ok(summary) => {
+            app.push_line(
+                LogKind::Status,
+                format!(
+                    "Bundle written: {} ({} file(s), diffs={}, shell={}, images={})",
+                    dir.display(),
+                    summary.file_count,
+                    summary.diff_count,
+                    summary.shell_result_count,
+                    summary.attachment_count
+                ),
+            );
+        }
+        Err(error) => {
+            app.push_error_report("bundle error", format!("{}: {error}", dir.display()));
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use crate::app::state::{LogKind, LogLine, LogTone};
+use crate::app::AppState;
+
+use super::prompt::start_prompt_run;
+use super::{RuntimeStdin, PLAN_USAGE_MESSAGE};
+
+const PLAN_EXECUTE_PREFIX: &str =
+    "Carry out the plan from the previous dry run for real; tools will execute this time.";
+const PLAN_STEP_LIST_LIMIT: usize = 20;
+
+/// Re-dispatches the last completed dry run as a normal run.
+pub(super) fn run_ready_plan(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) -> bool {
+    let Some(prompt) = app.ready_plan.take() else {
+        app.push_line(LogKind::Status, "No plan ready; run /plan <prompt> first.");
+        return false;
+    };
+    app.push_line(LogKind::Status, "Running the reviewed plan for real...");
+    start_prompt_run(
+        app,
+        child_stdin,
+        next_id,
+        &format!("{PLAN_EXECUTE_PREFIX}\n\n{prompt}"),
+        false,
+    )
+}
+
+/// Called on a terminal run status; turns the collected previews into a reviewable plan.
+pub(super) fn finish_dry_run(app: &mut AppState, status: &str) {
+    if !app.dry_run_active {
+        return;
+    }
+    app.dry_run_active = false;
+    let steps = std::mem::take(&mut app.planned_steps);
+    if status != "completed" {
+        return;
+    }
+    app.ready_plan = app.run_artifacts.last_prompt.clone();
+    if steps.is_empty() {
+        app.push_line(
+            LogKind::Status,
+            "Plan (dry run): no edits or commands would run.",
+        );
+    } else {
+        app.push_line(
+            LogKind::Status,
+            format!(
+                "Plan (dry run): {} step(s) would edit files or run commands:",
+                steps.len()
+            ),
+        );
+        let mut lines = steps
+            .iter()
+            .take(PLAN_STEP_LIST_LIMIT)
+            .enumerate()
+            .map(|(index, step)| {
+                LogLine::new_with_tone(
+                    LogKind::Status,
+                    LogTone::Detail,
+                    format!("  {}. {step}", index + 1),
+                )
+            })
+            .collect::<Vec<_>>();
+        if steps.len() > PLAN_STEP_LIST_LIMIT {
+            lines.push(LogLine::new_with_tone(
+                LogKind::Status,
+                LogTone::Detail,
+                format!("  ... ({} more)", steps.len() - PLAN_STEP_LIST_LIMIT),
+            ));
+        }
+        app.extend_lines(lines);
+    }
+    app.push_line(
+        LogKind::Status,
+        "Press Ctrl+G (or /plan run) to execute it for real, /plan discard to drop it.",
+    );
+}
+
+pub(super) fn handle_plan_command(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+    trimmed: &str,
+) -> bool {
+    let prompt = trimmed.strip_prefix("/plan").unwrap_or_default().trim();
+    match prompt {
+        "" => {
+            match app.ready_plan.as_deref() {
+                Some(ready) => app.push_line(
+                    LogKind::Status,
+                    format!("Plan ready: {ready} (Ctrl+G or /plan run to execute)"),
+                ),
+                None => app.push_line(LogKind::Error, PLAN_USAGE_MESSAGE),
+            }
+            true
+        }
+        "run" => {
+            run_ready_plan(app, child_stdin, next_id);
+            true
+        }
+        "discard" => {
+            if app.ready_plan.take().is_some() {
+                app.push_line(LogKind::Status, "Discarded the ready plan.");
+            } else {
+                app.push_line(LogKind::Status, "No plan ready.");
+            }
+            true
+        }
+        _ if !app.runtime_info.supports_dry_run => {
+            app.push_line(
+                LogKind::Error,
+                "/plan requires runtime dry-run support (supports_dry_run)",
+            );
+            false
+        }
+        _ => start_prompt_run(app, child_stdin, next_id, prompt, true),
+    }
+}
@@ -0,0 +1,157 @@
+use crate::app::runtime::send_shell_exec;
+use crate::app::state::LogKind;
+use crate::app::util::forge::{default_pull_request_title, ForgeKind, PullRequestDraft};
+use crate::app::AppState;
+
+use super::{RuntimeStdin, PR_USAGE_MESSAGE};
+
+const PR_CREATE_TIMEOUT_SECONDS: u64 = 120;
+const PR_PREVIEW_BODY_LINES: usize = 6;
+
+fn push_pull_request_preview(app: &mut AppState, draft: &PullRequestDraft) {
+    let kind = match draft.kind {
+        ForgeKind::GitHub => "pull request (gh)",
+        ForgeKind::GitLab => "merge request (glab)",
+    };
+    let draft_label = if draft.draft { " [draft]" } else { "" };
+    app.push_line(LogKind::Status, format!("PR preview: {kind}{draft_label}"));
+    app.push_line(LogKind::Status, format!("  Title: {}", draft.title));
+    let body_lines = draft.body.lines().collect::<Vec<_>>();
+    for line in body_lines.iter().take(PR_PREVIEW_BODY_LINES) {
+        app.push_line(LogKind::Status, format!("  | {line}"));
+    }
+    if body_lines.len() > PR_PREVIEW_BODY_LINES {
+        app.push_line(
+            LogKind::Status,
+            format!(
+                "  | ... ({} more line(s))",
+                body_lines.len() - PR_PREVIEW_BODY_LINES
+            ),
+        );
+    }
+    app.push_line(
+        LogKind::Status,
+        "Run /pr confirm to push the branch and open it, or /pr cancel to discard.",
+    );
+}
+
+fn prepare_pull_request<'a>(app: &mut AppState, parts: impl Iterator<Item = &'a str>) {
+    if app.rpc_pending.run_start_id.is_some() || app.is_running() {
+        app.push_line(
+            LogKind::Status,
+            "Run is still active; wait for completion before running /pr.",
+        );
+        return;
+    }
+    if app.run_status.as_deref() != Some("completed") {
+        app.push_line(
+            LogKind::Error,
+            "/pr needs a completed run to summarize; review the result first.",
+        );
+        return;
+    }
+    let Some(body) = app.last_assistant_text.clone() else {
+        app.push_line(
+            LogKind::Error,
+            "/pr needs a run summary (no assistant reply yet).",
+        );
+        return;
+    };
+
+    let mut draft = false;
+    let mut title_words = Vec::new();
+    for part in parts {
+        if part == "--draft" {
+            draft = true;
+        } else {
+            title_words.push(part);
+        }
+    }
+    let title = if title_words.is_empty() {
+        app.run_artifacts
+            .last_prompt
+            .as_deref()
+            .and_then(default_pull_request_title)
+    } else {
+        Some(title_words.join(" "))
+    };
+    let Some(title) = title else {
+        app.push_line(LogKind::Error, PR_USAGE_MESSAGE);
+        return;
+    };
+
+    let kind = app
+        .forge_refs
+        .config()
+        .default_repo
+        .as_ref()
+        .map(|repo| repo.kind)
+        .unwrap_or(ForgeKind::GitHub);
+    let pull_request = PullRequestDraft::new(kind, &title, &body, draft);
+    push_pull_request_preview(app, &pull_request);
+    app.pending_pull_request = Some(pull_request);
+}
+
+fn confirm_pull_request(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) {
+    if !app.runtime_info.supports_shell_exec {
+        app.push_line(LogKind::Error, "/pr requires runtime shell.exec support");
+        return;
+    }
+    if app.rpc_pending.pr_create_id.is_some() {
+        app.push_line(LogKind::Status, "Pull request creation is already running.");
+        return;
+    }
+    let Some(pull_request) = app.pending_pull_request.take() else {
+        app.push_line(LogKind::Error, "No pull request prepared; run /pr first.");
+        return;
+    };
+    let id = next_id();
+    app.rpc_pending.pr_create_id = Some(id.clone());
+    app.push_line(
+        LogKind::Status,
+        "Pushing branch and opening pull request...",
+    );
+    if let Err(error) = send_shell_exec(
+        child_stdin,
+        &id,
+        &pull_request.shell_command(),
+        Some(PR_CREATE_TIMEOUT_SECONDS),
+        None,
+    ) {
+        app.rpc_pending.pr_create_id = None;
+        app.pending_pull_request = Some(pull_request);
+        app.push_error_report("send error", error.to_string());
+    }
+}
+
+pub(super) fn handle_pr_command<'a>(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    let mut parts = parts.peekable();
+    match parts.peek().copied() {
+        Some("confirm") => {
+            parts.next();
+            if parts.next().is_some() {
+                app.push_line(LogKind::Error, PR_USAGE_MESSAGE);
+                return;
+            }
+            confirm_pull_request(app, child_stdin, next_id);
+        }
+        Some("cancel") => {
+            parts.next();
+            if app.pending_pull_request.take().is_some() {
+                app.push_line(LogKind::Status, "Discarded prepared pull request.");
+            } else {
+                app.push_line(LogKind::Status, "No pull request prepared.");
+            }
+        }
+        _ => prepare_pull_request(app, parts),
+    }
+}
@@ -1,10 +1,23 @@
+use crate::app::handlers::agents_suggestion::note_correction;
+use crate::app::handlers::cache::build_cache_output_prefix;
+use crate::app::handlers::context_pins::consume_pin_turn;
+use crate::app::handlers::forge::request_forge_references;
+use crate::app::handlers::queue_panel::refresh_queue_panel;
+use crate::app::handlers::session_notes::consume_note_turn;
 use crate::app::runtime::send_run_start;
 use crate::app::state::LogKind;
 use crate::app::util::attachments::{
-    build_run_input_payload, referenced_attachment_ids, render_input_text_with_attachment_labels,
+    build_run_input_payload, prepend_text_parts, referenced_attachment_ids,
+    render_input_text_with_attachment_labels,
 };
-use crate::app::{AppState, PendingPromptRun, PROMPT_DISPATCH_RETRY_BACKOFF};
-use std::time::Instant;
+use crate::app::util::dir_tree::DIR_MENTION_PREFIX;
+use crate::app::util::file_mentions::file_mention_text;
+use crate::app::util::forge::build_issue_context_prefix;
+use crate::app::util::forge::ci::build_ci_log_prefix;
+use crate::app::util::settings::utc_clock_label;
+use crate::app::util::stack_trace::build_stack_trace_prefix;
+use crate::app::{AppState, PendingPromptRun, QueuedAttachments, PROMPT_DISPATCH_RETRY_BACKOFF};
+use std::time::{Instant, SystemTime};
 
 use super::{build_shell_result_prefix, RuntimeStdin, QUEUE_PREVIEW_MAX_CHARS};
 
@@ -33,35 +46,86 @@ fn build_prompt_preview(input: &str) -> String {
     truncate_preview(first, QUEUE_PREVIEW_MAX_CHARS)
 }
 
-fn make_prompt_submission(app: &AppState, raw_input: &str) -> PendingPromptRun {
+fn make_prompt_submission(app: &AppState, raw_input: &str, dry_run: bool) -> PendingPromptRun {
     let user_text = raw_input.trim().to_string();
     let shell_result_count = app.pending_shell_results.len();
-    let final_input =
+    let mut final_input =
         if let Some(shell_prefix) = build_shell_result_prefix(&app.pending_shell_results) {
             format!("{shell_prefix}\n\n{user_text}")
         } else {
             user_text.clone()
         };
-    let attachment_count = referenced_attachment_ids(
+    if let Some(issue_prefix) = build_issue_context_prefix(&app.forge_refs.pending_contexts) {
+        final_input = format!("{issue_prefix}\n\n{final_input}");
+    }
+    if let Some(ci_prefix) = build_ci_log_prefix(&app.ci_watch.pending_logs) {
+        final_input = format!("{ci_prefix}\n\n{final_input}");
+    }
+    if let Some(cache_prefix) = build_cache_output_prefix(&app.pending_cache_outputs) {
+        final_input = format!("{cache_prefix}\n\n{final_input}");
+    }
+    if let Some(trace_prefix) = build_stack_trace_prefix(&app.pending_stack_traces) {
+        final_input = format!("{trace_prefix}\n\n{final_input}");
+    }
+    if let Some(pin_prefix) = app.context_pins.prefix() {
+        final_input = format!("{pin_prefix}\n\n{final_input}");
+    }
+    if let Some(note_prefix) = app.session_notes.prefix(Instant::now()) {
+        final_input = format!("{note_prefix}\n\n{final_input}");
+    }
+    let json_request = app.json_output.armed.clone();
+    if let Some(request) = json_request.as_ref() {
+        final_input = format!("{final_input}\n\n{}", request.instruction());
+    }
+    let images = referenced_attachment_ids(
         &user_text,
         &app.composer_nonce,
         &app.pending_image_attachments,
     )
-    .len();
-    let input_payload = build_run_input_payload(
-        &final_input,
-        &app.composer_nonce,
-        &app.pending_image_attachments,
+    .into_iter()
+    .filter_map(|id| {
+        let image = app.pending_image_attachments.get(&id)?.clone();
+        Some((id, image))
+    })
+    .collect::<Vec<_>>();
+    let files = app
+        .referenced_file_attachments(&user_text)
+        .into_iter()
+        .cloned()
+        .collect::<Vec<_>>();
+    let file_texts = files.iter().map(file_mention_text).collect::<Vec<_>>();
+    let input_payload = prepend_text_parts(
+        build_run_input_payload(
+            &final_input,
+            &app.composer_nonce,
+            &app.pending_image_attachments,
+        ),
+        file_texts,
     );
+    let mut preview = build_prompt_preview(&user_text);
+    if json_request.is_some() {
+        preview = format!("[json] {preview}");
+    }
     PendingPromptRun {
         queue_id: String::new(),
         queued_at: Instant::now(),
-        preview: build_prompt_preview(&user_text),
+        preview: if dry_run {
+            format!("[plan] {preview}")
+        } else {
+            preview
+        },
         user_text,
         input_payload,
-        attachment_count,
+        attachment_count: images.len(),
         shell_result_count,
         dispatch_attempts: 0,
+        dry_run,
+        json_request,
+        attachments: QueuedAttachments {
+            composer_nonce: app.composer_nonce.clone(),
+            images,
+            files,
+        },
     }
 }
 
@@ -71,7 +135,15 @@ fn push_user_prompt_lines(app: &mut AppState, message: &str) {
         &app.composer_nonce,
         &app.pending_image_attachments,
     );
-    app.push_line(LogKind::User, " ");
+    app.begin_log_turn();
+    if app.settings.timestamps {
+        app.push_line(
+            LogKind::User,
+            format!(" {} UTC", utc_clock_label(SystemTime::now())),
+        );
+    } else {
+        app.push_line(LogKind::User, " ");
+    }
     for (index, line) in display_text.lines().enumerate() {
         let prefix = if index == 0 { "> " } else { "  " };
         app.push_line(LogKind::User, format!("{prefix}{line}"));
@@ -91,6 +163,24 @@ fn push_user_prompt_lines(app: &mut AppState, message: &str) {
             );
         }
     }
+    let files = app
+        .referenced_file_attachments(message)
+        .into_iter()
+        .map(|file| {
+            let truncated = if file.truncated { ", truncated" } else { "" };
+            if let Some(dir) = file.path.strip_prefix(DIR_MENTION_PREFIX) {
+                return format!("  [tree {dir}{truncated}]");
+            }
+            format!(
+                "  [file {} {}KB{truncated}]",
+                file.path,
+                file.bytes.div_ceil(1024)
+            )
+        })
+        .collect::<Vec<_>>();
+    for line in files {
+        app.push_line(LogKind::User, line);
+    }
     app.push_line(LogKind::User, " ");
 }
 
@@ -103,18 +193,37 @@ fn dispatch_prompt_submission(
     app.input.record_history(&submission.user_text);
     app.scroll_from_bottom = 0;
     app.last_assistant_text = None;
+    // A fresh prompt after a finished `/regen` leaves nothing to compare.
+    app.regen_candidates
+        .take_if(|candidates| candidates.alternative.is_some());
     push_user_prompt_lines(app, &submission.user_text);
+    app.run_artifacts.last_prompt = Some(submission.user_text.clone());
+    request_forge_references(app, &submission.user_text);
+    app.ready_plan = None;
+    app.dry_run_active = submission.dry_run;
+    app.json_output.active = submission.json_request.clone();
+    app.planned_steps.clear();
+    if submission.dry_run {
+        app.push_line(
+            LogKind::Status,
+            "Plan only (dry run): edits and commands are previewed, not executed.",
+        );
+    }
     app.update_run_status("starting".to_string());
     let id = next_id();
     app.rpc_pending.run_start_id = Some(id.clone());
+    let disabled_tools = app.disabled_tools.iter().cloned().collect::<Vec<_>>();
     if let Err(error) = send_run_start(
         child_stdin,
         &id,
         app.runtime_info.session_id.as_deref(),
         submission.input_payload.clone(),
         false,
+        submission.dry_run,
+        &disabled_tools,
     ) {
         app.rpc_pending.run_start_id = None;
+        app.dry_run_active = false;
         app.update_run_status("error".to_string());
         app.push_error_report("send error", error.to_string());
         return false;
@@ -125,6 +234,7 @@ fn dispatch_prompt_submission(
 pub(super) fn can_dispatch_prompt_now(app: &AppState) -> bool {
     if app.rpc_pending.run_start_id.is_some()
         || app.rpc_pending.run_cancel_id.is_some()
+        || app.rpc_pending.cwd_set_id.is_some()
         || app.is_running()
     {
         return false;
@@ -139,9 +249,19 @@ pub(super) fn can_dispatch_prompt_now(app: &AppState) -> bool {
         && app.model_list_panel.is_none()
         && app.session_list_panel.is_none()
         && app.lane_list_panel.is_none()
+        && app.lane_attach.is_none()
+        && app.mcp_list_panel.is_none()
         && app.context_panel.is_none()
         && app.skills_list_panel.is_none()
         && app.theme_list_panel.is_none()
+        && app.secrets_panel.is_none()
+        && app.permissions_panel.is_none()
+        && app.edit_conflict_panel.is_none()
+        && app.tools_panel.is_none()
+        && app.queue_panel.as_ref().is_none_or(|panel| panel.review)
+        && app.cache_list_panel.is_none()
+        && app.whats_new_panel.is_none()
+        && !app.runtime_reconnect.reconnecting()
 }
 
 pub(super) fn try_dispatch_queued_prompt(
@@ -165,6 +285,7 @@ pub(super) fn try_dispatch_queued_prompt(
         return false;
     };
     app.dispatching_prompt = Some(next_prompt);
+    refresh_queue_panel(app);
 
     let sent = if let Some(dispatching) = app.dispatching_prompt.clone() {
         dispatch_prompt_submission(app, child_stdin, next_id, &dispatching)
@@ -174,12 +295,19 @@ pub(super) fn try_dispatch_queued_prompt(
 
     if sent {
         app.next_queue_dispatch_retry_at = None;
+        if let Some(dispatching) = app.dispatching_prompt.as_ref() {
+            app.run_traces
+                .dispatched(&dispatching.queue_id, Instant::now());
+        }
         return true;
     }
 
     if let Some(failed) = app.dispatching_prompt.take() {
         app.pending_prompt_queue.push_front(failed);
+        refresh_queue_panel(app);
     }
+    app.run_traces
+        .retry("run.start could not be sent", Instant::now());
     app.next_queue_dispatch_retry_at = Some(Instant::now() + PROMPT_DISPATCH_RETRY_BACKOFF);
     app.push_line(
         LogKind::Status,
@@ -196,23 +324,77 @@ pub(super) fn start_prompt_run(
     child_stdin: &mut RuntimeStdin,
     next_id: &mut impl FnMut() -> String,
     raw_input: &str,
+    dry_run: bool,
 ) -> bool {
+    let submission = make_prompt_submission(app, raw_input, dry_run);
+    let user_text = submission.user_text.clone();
+    let queued = enqueue_prompt_submission(app, child_stdin, next_id, submission, true).is_some();
+    if queued {
+        note_correction(app, &user_text);
+    }
+    queued
+}
+
+/// Enqueues one `/batch` item with a `[batch i/n]` preview; returns its queue id and preview.
+pub(super) fn start_batch_prompt_run(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+    raw_input: &str,
+    position: usize,
+    total: usize,
+) -> Option<(String, String)> {
+    let mut submission = make_prompt_submission(app, raw_input, false);
+    submission.preview = format!("[batch {position}/{total}] {}", submission.preview);
+    let preview = submission.preview.clone();
+    // One summary line covers the whole batch instead of a "Queued prompt" line per item.
+    let queue_id = enqueue_prompt_submission(app, child_stdin, next_id, submission, false)?;
+    Some((queue_id, preview))
+}
+
+fn enqueue_prompt_submission(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+    mut submission: PendingPromptRun,
+    announce_queued: bool,
+) -> Option<String> {
     // Keep a single submission path: always snapshot+enqueue first, then opportunistically
     // dispatch immediately when gates are open.
-    let mut submission = make_prompt_submission(app, raw_input);
     if submission.user_text.is_empty() {
-        return false;
+        return None;
     }
 
     let was_blocked = !can_dispatch_prompt_now(app);
     submission.queue_id = format!("q{}", app.next_prompt_queue_id);
     app.next_prompt_queue_id = app.next_prompt_queue_id.saturating_add(1);
     app.pending_prompt_queue.push_back(submission.clone());
+    for attachment_id in referenced_attachment_ids(
+        &submission.user_text,
+        &app.composer_nonce,
+        &app.pending_image_attachments,
+    ) {
+        if let Some(image) = app.pending_image_attachments.get(&attachment_id) {
+            app.run_artifacts
+                .attachments
+                .push((attachment_id, image.clone()));
+        }
+    }
     if submission.shell_result_count > 0 {
         app.pending_shell_results.clear();
     }
+    if submission.json_request.is_some() {
+        app.json_output.armed = None;
+    }
+    app.forge_refs.pending_contexts.clear();
+    app.ci_watch.pending_logs.clear();
+    app.pending_cache_outputs.clear();
+    app.pending_stack_traces.clear();
+    consume_pin_turn(app);
+    consume_note_turn(app);
+    refresh_queue_panel(app);
 
-    if was_blocked {
+    if was_blocked && announce_queued {
         app.push_line(
             LogKind::Status,
             format!(
@@ -223,6 +405,7 @@ pub(super) fn start_prompt_run(
         );
     }
 
+    let queue_id = submission.queue_id.clone();
     let _ = try_dispatch_queued_prompt(app, child_stdin, next_id);
-    true
+    Some(queue_id)
 }
@@ -1,3 +1,4 @@
+use crate::app::handlers::queue_panel::{open_queue_panel, queue_age_label};
 use crate::app::state::LogKind;
 use crate::app::AppState;
 use std::time::Instant;
@@ -7,16 +8,6 @@ use super::{
     QUEUE_USAGE_MESSAGE,
 };
 
-fn queue_age_label(queued_at: Instant, now: Instant) -> String {
-    let elapsed = now.saturating_duration_since(queued_at).as_secs();
-    if elapsed < 60 {
-        return format!("{elapsed}s");
-    }
-    let minutes = elapsed / 60;
-    let seconds = elapsed % 60;
-    format!("{minutes}m{seconds:02}s")
-}
-
 fn parse_queue_index_token(token: &str) -> Option<usize> {
     let normalized = token.strip_prefix('#').unwrap_or(token);
     normalized
@@ -66,6 +57,47 @@ fn handle_queue_cancel_target(app: &mut AppState, target: &str) {
     }
 }
 
+fn push_queue_list(app: &mut AppState) {
+    let now = Instant::now();
+    app.push_line(
+        LogKind::Status,
+        format!(
+            "queue: {} pending (showing first {})",
+            app.pending_prompt_queue.len(),
+            QUEUE_LIST_LIMIT.min(app.pending_prompt_queue.len())
+        ),
+    );
+    let queue_rows = app
+        .pending_prompt_queue
+        .iter()
+        .take(QUEUE_LIST_LIMIT)
+        .enumerate()
+        .map(|(index, item)| {
+            format!(
+                "  {}. {} [{} ago] {}",
+                index + 1,
+                item.queue_id,
+                queue_age_label(item.queued_at, now),
+                item.preview
+            )
+        })
+        .collect::<Vec<_>>();
+    for row in queue_rows {
+        app.push_line(LogKind::Status, row);
+    }
+    if app.pending_prompt_queue.len() > QUEUE_LIST_LIMIT {
+        app.push_line(
+            LogKind::Status,
+            format!(
+                "  ... {} more",
+                app.pending_prompt_queue
+                    .len()
+                    .saturating_sub(QUEUE_LIST_LIMIT)
+            ),
+        );
+    }
+}
+
 pub(super) fn handle_queue_command<'a>(
     app: &mut AppState,
     parts: &mut impl Iterator<Item = &'a str>,
@@ -75,48 +107,22 @@ pub(super) fn handle_queue_command<'a>(
             app.push_line(LogKind::Status, QUEUE_EMPTY_MESSAGE);
             return;
         }
-        let now = Instant::now();
-        app.push_line(
-            LogKind::Status,
-            format!(
-                "queue: {} pending (showing first {})",
-                app.pending_prompt_queue.len(),
-                QUEUE_LIST_LIMIT.min(app.pending_prompt_queue.len())
-            ),
-        );
-        let queue_rows = app
-            .pending_prompt_queue
-            .iter()
-            .take(QUEUE_LIST_LIMIT)
-            .enumerate()
-            .map(|(index, item)| {
-                format!(
-                    "  {}. {} [{} ago] {}",
-                    index + 1,
-                    item.queue_id,
-                    queue_age_label(item.queued_at, now),
-                    item.preview
-                )
-            })
-            .collect::<Vec<_>>();
-        for row in queue_rows {
-            app.push_line(LogKind::Status, row);
-        }
-        if app.pending_prompt_queue.len() > QUEUE_LIST_LIMIT {
-            app.push_line(
-                LogKind::Status,
-                format!(
-                    "  ... {} more",
-                    app.pending_prompt_queue
-                        .len()
-                        .saturating_sub(QUEUE_LIST_LIMIT)
-                ),
-            );
-        }
+        open_queue_panel(app);
         return;
     };
 
     match subcommand {
+        "list" => {
+            if parts.next().is_some() {
+                app.push_line(LogKind::Error, QUEUE_USAGE_MESSAGE);
+                return;
+            }
+            if app.pending_prompt_queue.is_empty() {
+                app.push_line(LogKind::Status, QUEUE_EMPTY_MESSAGE);
+                return;
+            }
+            push_queue_list(app);
+        }
         "cancel" => {
             if app.pending_prompt_queue.is_empty() {
                 app.push_line(LogKind::Status, QUEUE_EMPTY_MESSAGE);
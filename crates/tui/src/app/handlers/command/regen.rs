@@ -0,0 +1,143 @@
+use crate::app::state::{answer_diff_rows, AnswerDiffLayout, LogKind, RegenCandidates};
+use crate::app::{AppState, ContextPanelState};
+
+use super::prompt::{can_dispatch_prompt_now, start_prompt_run};
+use super::{RuntimeStdin, REGEN_USAGE_MESSAGE};
+
+/// Side-by-side width when nothing has been drawn yet (line mode).
+const SIDE_BY_SIDE_FALLBACK_WIDTH: usize = 100;
+
+pub(super) fn handle_regen_command<'a>(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    match (parts.next(), parts.next(), parts.next()) {
+        (None, _, _) => regenerate(app, child_stdin, next_id),
+        (Some("diff"), None, _) => open_answer_diff(app, AnswerDiffLayout::Unified),
+        (Some("diff"), Some("split"), None) => open_answer_diff(app, AnswerDiffLayout::SideBySide),
+        (Some("pick"), Some(choice @ ("1" | "2")), None) => pick_answer(app, choice == "1"),
+        _ => app.push_line(LogKind::Error, REGEN_USAGE_MESSAGE),
+    }
+}
+
+fn regenerate(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) {
+    if !can_dispatch_prompt_now(app) {
+        app.push_line(
+            LogKind::Status,
+            "Run is still active; wait for completion before running /regen.",
+        );
+        return;
+    }
+    let (Some(prompt), Some(original)) = (
+        app.run_artifacts.last_prompt.clone(),
+        app.last_assistant_text.clone(),
+    ) else {
+        app.push_line(LogKind::Error, "/regen needs a finished answer to replace");
+        return;
+    };
+    app.regen_candidates = Some(RegenCandidates {
+        original,
+        alternative: None,
+    });
+    if !start_prompt_run(app, child_stdin, next_id, &prompt, false) {
+        app.regen_candidates = None;
+    }
+}
+
+/// Called with each final answer; the first one after `/regen` becomes the alternative.
+pub(super) fn record_regenerated_answer(app: &mut AppState, answer: &str) {
+    let Some(candidates) = app
+        .regen_candidates
+        .as_mut()
+        .filter(|candidates| candidates.alternative.is_none())
+    else {
+        return;
+    };
+    candidates.alternative = Some(answer.to_string());
+    let message = if candidates.original == answer {
+        "Regenerated answer is identical to the previous one"
+    } else {
+        "Alternative answer ready: /regen diff [split] to compare, /regen pick <1|2> to keep one"
+    };
+    app.push_line(LogKind::Status, message);
+}
+
+fn open_answer_diff(app: &mut AppState, layout: AnswerDiffLayout) {
+    let Some((original, alternative)) = app.regen_candidates.as_ref().and_then(|candidates| {
+        candidates
+            .alternative
+            .as_deref()
+            .map(|alternative| (candidates.original.as_str(), alternative))
+    }) else {
+        app.push_line(
+            LogKind::Status,
+            "No alternative answer yet; run /regen and wait for it to finish",
+        );
+        return;
+    };
+    let width = match app.last_wrap_width {
+        0 => SIDE_BY_SIDE_FALLBACK_WIDTH,
+        width => width,
+    };
+    let rows = answer_diff_rows(original, alternative, layout, width);
+    app.context_panel = Some(ContextPanelState {
+        title: "Answer diff".to_string(),
+        header: "answer 1 = previous, answer 2 = regenerated · /regen pick <1|2>".to_string(),
+        rows,
+        selected: 0,
+    });
+}
+
+/// Keeps one candidate as the answer `/json`, `/pr`, and the scratchpad work from.
+fn pick_answer(app: &mut AppState, original: bool) {
+    let Some(candidates) = app
+        .regen_candidates
+        .take_if(|candidates| candidates.alternative.is_some())
+    else {
+        app.push_line(LogKind::Error, "No alternative answer to pick from");
+        return;
+    };
+    let (label, answer) = if original {
+        ("previous", candidates.original)
+    } else {
+        ("regenerated", candidates.alternative.unwrap_or_default())
+    };
+    app.last_assistant_text = Some(answer);
+    app.push_line(LogKind::Status, format!("Keeping the {label} answer"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{open_answer_diff, pick_answer, record_regenerated_answer};
+    use crate::app::state::{AnswerDiffLayout, RegenCandidates};
+    use crate::app::AppState;
+
+    #[test]
+    fn regenerated_answer_can_be_diffed_and_picked() {
+        let mut app = AppState {
+            regen_candidates: Some(RegenCandidates {
+                original: "old answer".to_string(),
+                alternative: None,
+            }),
+            ..AppState::default()
+        };
+        record_regenerated_answer(&mut app, "new answer");
+        // Only the first final after /regen is kept.
+        record_regenerated_answer(&mut app, "later answer");
+
+        open_answer_diff(&mut app, AnswerDiffLayout::Unified);
+        let panel = app.context_panel.as_ref().expect("diff panel");
+        assert_eq!(panel.rows[2..], ["- old answer", "+ new answer"]);
+
+        app.last_assistant_text = Some("new answer".to_string());
+        pick_answer(&mut app, true);
+        assert_eq!(app.last_assistant_text.as_deref(), Some("old answer"));
+        assert!(app.regen_candidates.is_none());
+    }
+}
@@ -1,17 +1,75 @@
+use crate::app::handlers::audit::open_audit_panel;
+use crate::app::handlers::cache::request_cache_list;
+use crate::app::handlers::context_pins::{
+    clear_pins, drop_pin, mark_pin_start, open_pins_panel, pin_log_region,
+};
+use crate::app::handlers::copy::{copy_last_message, open_copy_picker};
+use crate::app::handlers::draft::{discard_draft, restore_draft, show_draft_status};
+use crate::app::handlers::forge::{
+    request_ci_failure_logs, request_ci_status, request_issue_attachment,
+};
+use crate::app::handlers::json_output::{
+    arm_json_output, disarm_json_output, open_json_output_panel, save_last_json_output,
+};
+use crate::app::handlers::log_export::{export_log, LogExportScope};
+use crate::app::handlers::permalink::copy_turn_link;
+use crate::app::handlers::permissions::{
+    add_allowed_command, open_permissions_panel, remove_allowed_command,
+};
+use crate::app::handlers::run_tee::{start_run_tee, stop_run_tee};
+use crate::app::handlers::run_trace::open_trace_panel;
+use crate::app::handlers::runtime_report::request_runtime_report;
+use crate::app::handlers::scratchpad::{
+    clear_scratchpad, drop_scratchpad_entry, export_scratchpad, open_scratchpad_panel,
+    pin_last_answer, pin_text,
+};
+use crate::app::handlers::secrets::{
+    open_secrets_panel, prompt_secret_value, remove_secret, set_secret_inject,
+};
+use crate::app::handlers::session_notes::{
+    add_session_note, clear_session_notes, drop_session_note, open_notes_panel,
+};
+use crate::app::handlers::settings::{set_setting, show_settings};
+use crate::app::handlers::tabs::{request_tab, show_tabs};
+use crate::app::handlers::theme::select_custom_theme;
+use crate::app::handlers::tools_panel::{request_tools_panel, set_tool_enabled_by_name};
+use crate::app::handlers::translate::{
+    open_translation_panel, set_translation_target, show_translation_status, stop_translation,
+};
+use crate::app::handlers::usage::{open_provider_stats_panel, open_usage_panel};
+use crate::app::handlers::whats_new::open_current_whats_new;
+use crate::app::handlers::working_dir::{request_cwd_set, show_working_dir};
+use crate::app::render::glyphs::ascii_glyphs;
 use crate::app::runtime::{
     send_auth_logout, send_context_inspect, send_mcp_list, send_model_set, send_run_start,
     send_skills_list, send_task_cancel, send_task_list, send_task_status, send_theme_set,
     send_tool_call,
 };
 use crate::app::state::{
-    command_suggestion_rows, parse_theme_name, theme_options, LogKind, ThemeListPanelState,
+    command_suggestion_rows, context_usage_bar, parse_note_ttl, parse_theme_name, theme_options,
+    LogKind, NoteExpiry, TabRequest, ThemeListPanelState, DEFAULT_PIN_TURNS,
 };
+use crate::app::util::input_log::{export_input_events, input_recording};
+use crate::app::util::log_export::LogExportFormat;
+use crate::app::util::secrets::is_valid_secret_name;
+use crate::app::util::settings::{custom_themes, load_custom_themes, resolve_theme_id, themes_dir};
 use crate::app::{
-    AppState, ErrorDetailMode, ModelListMode, ModelSetScope, ProviderPickerState, SkillsScopeFilter,
+    AppState, ContextPanelState, ErrorDetailMode, ModelListMode, ModelSetScope,
+    ProviderPickerState, SkillsScopeFilter,
 };
 use serde_json::json;
+use std::time::Instant;
 
-use super::{RuntimeStdin, COMMAND_SUGGESTION_LIMIT, MODEL_PROVIDERS, TASKS_USAGE_MESSAGE};
+use super::{
+    RuntimeStdin, AUDIT_USAGE_MESSAGE, CI_USAGE_MESSAGE, COMMAND_SUGGESTION_LIMIT,
+    COPY_USAGE_MESSAGE, DEBUG_KEYS_USAGE_MESSAGE, DRAFT_USAGE_MESSAGE, EXPORT_USAGE_MESSAGE,
+    GLYPHS_USAGE_MESSAGE, ISSUE_USAGE_MESSAGE, JSON_USAGE_MESSAGE, LINK_USAGE_MESSAGE,
+    MODEL_PROVIDERS, NOTE_USAGE_MESSAGE, NOTIFY_USAGE_MESSAGE, PAGE_USAGE_MESSAGE,
+    PERMISSIONS_USAGE_MESSAGE, PIN_USAGE_MESSAGE, PROVIDERS_USAGE_MESSAGE, RUNTIME_USAGE_MESSAGE,
+    SCRATCH_USAGE_MESSAGE, SECRETS_USAGE_MESSAGE, SET_USAGE_MESSAGE, TAB_USAGE_MESSAGE,
+    TASKS_USAGE_MESSAGE, TEE_USAGE_MESSAGE, TOOLS_USAGE_MESSAGE, TRACE_USAGE_MESSAGE,
+    TRANSLATE_USAGE_MESSAGE, USAGE_USAGE_MESSAGE, WHATSNEW_USAGE_MESSAGE,
+};
 
 fn parse_scope_filter(value: &str) -> Option<SkillsScopeFilter> {
     match value {
@@ -57,6 +115,8 @@ pub(super) fn handle_compact_command<'a>(
         app.runtime_info.session_id.as_deref(),
         json!({ "type": "text", "text": "" }),
         true,
+        false,
+        &[],
     ) {
         app.rpc_pending.run_start_id = None;
         app.update_run_status("error".to_string());
@@ -252,23 +312,27 @@ pub(super) fn handle_context_command<'a>(
     next_id: &mut impl FnMut() -> String,
     parts: &mut impl Iterator<Item = &'a str>,
 ) {
+    let first_arg = parts.next();
+    if parts.next().is_some() {
+        app.push_line(LogKind::Error, "usage: /context [brief|graph]");
+        return;
+    }
+    if first_arg == Some("graph") {
+        open_context_graph_panel(app);
+        return;
+    }
     if !app.runtime_info.supports_context_inspect {
         app.push_line(LogKind::Status, "Context inspect unavailable");
         return;
     }
-    let first_arg = parts.next();
     let (include_agents, include_skills) = match first_arg {
         None => (true, true),
         Some("brief") => (false, false),
         Some(_) => {
-            app.push_line(LogKind::Error, "usage: /context [brief]");
+            app.push_line(LogKind::Error, "usage: /context [brief|graph]");
             return;
         }
     };
-    if parts.next().is_some() {
-        app.push_line(LogKind::Error, "usage: /context [brief]");
-        return;
-    }
     let id = next_id();
     app.rpc_pending.context_inspect_id = Some(id.clone());
     app.skills_list_panel = None;
@@ -279,6 +343,57 @@ pub(super) fn handle_context_command<'a>(
     }
 }
 
+const CONTEXT_GRAPH_BAR_WIDTH: usize = 30;
+
+/// Per-run context consumption from `run.context` samples; no runtime round trip needed.
+fn open_context_graph_panel(app: &mut AppState) {
+    if app.context_history.is_empty() {
+        app.push_line(
+            LogKind::Status,
+            "No context usage recorded yet; run a prompt first.",
+        );
+        return;
+    }
+    let rows = app
+        .context_history
+        .runs()
+        .map(|run| {
+            let consumed = run.consumed();
+            let change = if run.start_left.is_none() {
+                "first sample".to_string()
+            } else if consumed < 0 {
+                format!("freed {}%", -consumed)
+            } else {
+                format!("+{consumed}%")
+            };
+            let run_id = run
+                .run_id
+                .as_deref()
+                .map(|id| id.chars().take(12).collect::<String>())
+                .unwrap_or_else(|| "-".to_string());
+            format!(
+                "#{:<3} {} {:>3}% used  {:<12} {run_id}",
+                run.number,
+                context_usage_bar(run, CONTEXT_GRAPH_BAR_WIDTH),
+                100 - u16::from(run.end_left),
+                change
+            )
+        })
+        .collect::<Vec<_>>();
+    let selected = rows.len() - 1;
+    app.skills_list_panel = None;
+    app.theme_list_panel = None;
+    app.context_panel = Some(ContextPanelState {
+        title: format!("Context usage by run ({} runs)", rows.len()),
+        header: format!(
+            "run  {:<CONTEXT_GRAPH_BAR_WIDTH$} used       change       run id",
+            "█ before · ▓ this run · ░ free"
+        ),
+        rows,
+        selected,
+    });
+}
+
 pub(super) fn handle_skills_command<'a>(
     app: &mut AppState,
     child_stdin: &mut RuntimeStdin,
@@ -389,10 +504,21 @@ pub(super) fn handle_theme_command<'a>(
         app.push_line(LogKind::Error, "theme options unavailable");
         return;
     }
+    // Rescanned on every use so new or edited theme files show up without a restart.
+    for warning in load_custom_themes() {
+        app.push_line(LogKind::Error, format!("theme: {warning}"));
+    }
+    crate::app::theme::reload_active_theme();
 
     if let Some(value) = arg {
         let Some(target) = parse_theme_name(value) else {
-            app.push_line(LogKind::Error, format!("unknown theme: {value}"));
+            match resolve_theme_id(value) {
+                Some(custom) => {
+                    app.theme_list_panel = None;
+                    select_custom_theme(app, &custom);
+                }
+                None => app.push_line(LogKind::Error, format!("unknown theme: {value}")),
+            }
             return;
         };
         if !app.runtime_info.supports_theme_set {
@@ -414,12 +540,13 @@ pub(super) fn handle_theme_command<'a>(
         return;
     }
 
-    let active = crate::app::theme::active_theme_name();
+    let active = crate::app::theme::active_theme_id();
+    let custom = custom_themes();
 
-    let mut rows = Vec::with_capacity(options.len());
-    let mut theme_ids = Vec::with_capacity(options.len());
+    let mut rows = Vec::with_capacity(options.len() + custom.len());
+    let mut theme_ids = Vec::with_capacity(options.len() + custom.len());
     let mut selected = 0_usize;
-    for (index, option) in options.iter().enumerate() {
+    for option in options {
         let id = option.name.as_str();
         let aliases = option.name.aliases();
         let alias_suffix = if aliases.is_empty() {
@@ -427,18 +554,37 @@ pub(super) fn handle_theme_command<'a>(
         } else {
             format!(" (alias: {})", aliases.join(","))
         };
-        let marker = if option.name == active { "✓" } else { " " };
+        let marker = if id == active { "✓" } else { " " };
+        if id == active {
+            selected = rows.len();
+        }
         rows.push(format!(
             "{marker} {:<8} - {}{}",
             id, option.preview, alias_suffix
         ));
         theme_ids.push(id.to_string());
-        if option.name == active {
-            selected = index;
+    }
+    for theme in custom {
+        let marker = if theme.name == active { "✓" } else { " " };
+        if theme.name == active {
+            selected = rows.len();
         }
+        let description = if theme.description.is_empty() {
+            format!("custom, based on {}", theme.base.as_str())
+        } else {
+            format!("{} (custom)", theme.description)
+        };
+        rows.push(format!("{marker} {:<8} - {description}", theme.name));
+        theme_ids.push(theme.name);
     }
 
-    let header = "Enter: apply & save theme  Esc: close".to_string();
+    let header = match themes_dir() {
+        Some(dir) => format!(
+            "Enter: apply & save theme  Esc: close  Custom: {}/*.toml",
+            dir.display()
+        ),
+        None => "Enter: apply & save theme  Esc: close".to_string(),
+    };
 
     app.model_list_panel = None;
     app.reasoning_picker = None;
@@ -446,6 +592,7 @@ pub(super) fn handle_theme_command<'a>(
     app.context_panel = None;
     app.skills_list_panel = None;
     app.lane_list_panel = None;
+    app.mcp_list_panel = None;
     app.provider_picker = None;
     app.model_picker = None;
     app.theme_list_panel = Some(ThemeListPanelState {
@@ -561,6 +708,566 @@ pub(super) fn handle_tasks_command<'a>(
     }
 }
 
+pub(super) fn handle_issue_command<'a>(
+    app: &mut AppState,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    let (Some(argument), None) = (parts.next(), parts.next()) else {
+        app.push_line(LogKind::Error, ISSUE_USAGE_MESSAGE);
+        return;
+    };
+    request_issue_attachment(app, argument);
+}
+
+pub(super) fn handle_ci_command<'a>(app: &mut AppState, parts: &mut impl Iterator<Item = &'a str>) {
+    let subcommand = parts.next();
+    if parts.next().is_some() {
+        app.push_line(LogKind::Error, CI_USAGE_MESSAGE);
+        return;
+    }
+    match subcommand {
+        None => {
+            if request_ci_status(app, true) {
+                app.push_line(LogKind::Status, "Fetching CI status...");
+            }
+        }
+        Some("watch") => {
+            if request_ci_status(app, true) {
+                app.ci_watch.watching = true;
+                app.push_line(
+                    LogKind::Status,
+                    "Watching CI for the current branch (/ci stop to end).",
+                );
+            }
+        }
+        Some("stop") => {
+            app.ci_watch.watching = false;
+            app.ci_watch.next_poll_at = None;
+            app.push_line(LogKind::Status, "CI watch stopped.");
+        }
+        Some("logs") => request_ci_failure_logs(app),
+        Some(_) => app.push_line(LogKind::Error, CI_USAGE_MESSAGE),
+    }
+}
+
+pub(super) fn handle_secrets_command<'a>(
+    app: &mut AppState,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    let Some(subcommand) = parts.next() else {
+        open_secrets_panel(app);
+        return;
+    };
+    let Some(name) = parts.next() else {
+        app.push_line(LogKind::Error, SECRETS_USAGE_MESSAGE);
+        return;
+    };
+    if !is_valid_secret_name(name) {
+        app.push_line(
+            LogKind::Error,
+            format!("invalid secret name: {name} (use letters, digits, and _)"),
+        );
+        return;
+    }
+    let flag = parts.next();
+    if parts.next().is_some() {
+        app.push_line(LogKind::Error, SECRETS_USAGE_MESSAGE);
+        return;
+    }
+    match (subcommand, flag) {
+        ("set", None) => prompt_secret_value(app, name, false),
+        ("set", Some("--keychain")) => prompt_secret_value(app, name, true),
+        ("rm", None) => remove_secret(app, name),
+        ("on", None) => set_secret_inject(app, name, true),
+        ("off", None) => set_secret_inject(app, name, false),
+        _ => app.push_line(LogKind::Error, SECRETS_USAGE_MESSAGE),
+    }
+}
+
+pub(super) fn handle_permissions_command<'a>(
+    app: &mut AppState,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    let Some(subcommand) = parts.next() else {
+        open_permissions_panel(app);
+        return;
+    };
+    let pattern = parts.collect::<Vec<_>>().join(" ");
+    match subcommand {
+        "add" if !pattern.is_empty() => add_allowed_command(app, &pattern),
+        "rm" if !pattern.is_empty() => remove_allowed_command(app, &pattern),
+        _ => app.push_line(LogKind::Error, PERMISSIONS_USAGE_MESSAGE),
+    }
+}
+
+pub(super) fn handle_audit_command<'a>(
+    app: &mut AppState,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    if parts.next().is_some() {
+        app.push_line(LogKind::Error, AUDIT_USAGE_MESSAGE);
+        return;
+    }
+    open_audit_panel(app);
+}
+
+pub(super) fn handle_whatsnew_command<'a>(
+    app: &mut AppState,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    if parts.next().is_some() {
+        app.push_line(LogKind::Error, WHATSNEW_USAGE_MESSAGE);
+        return;
+    }
+    open_current_whats_new(app);
+}
+
+pub(super) fn handle_usage_command<'a>(
+    app: &mut AppState,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    if parts.next().is_some() {
+        app.push_line(LogKind::Error, USAGE_USAGE_MESSAGE);
+        return;
+    }
+    open_usage_panel(app);
+}
+
+pub(super) fn handle_providers_command<'a>(
+    app: &mut AppState,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    match (parts.next(), parts.next()) {
+        (None | Some("stats"), None) => open_provider_stats_panel(app),
+        _ => app.push_line(LogKind::Error, PROVIDERS_USAGE_MESSAGE),
+    }
+}
+
+pub(super) fn handle_trace_command<'a>(
+    app: &mut AppState,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    if parts.next().is_some() {
+        app.push_line(LogKind::Error, TRACE_USAGE_MESSAGE);
+        return;
+    }
+    open_trace_panel(app);
+}
+
+/// Returns whether the composer should be cleared; a restored draft stays in it.
+pub(super) fn handle_draft_command<'a>(
+    app: &mut AppState,
+    parts: &mut impl Iterator<Item = &'a str>,
+) -> bool {
+    let action = parts.next();
+    if parts.next().is_some() {
+        app.push_line(LogKind::Error, DRAFT_USAGE_MESSAGE);
+        return true;
+    }
+    match action {
+        None => show_draft_status(app),
+        Some("restore") => return !restore_draft(app),
+        Some("discard") => discard_draft(app),
+        Some(_) => app.push_line(LogKind::Error, DRAFT_USAGE_MESSAGE),
+    }
+    true
+}
+
+pub(super) fn handle_debug_keys_command<'a>(
+    app: &mut AppState,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    let target = parts.next();
+    if parts.next().is_some() {
+        app.push_line(LogKind::Error, DEBUG_KEYS_USAGE_MESSAGE);
+        return;
+    }
+    if !input_recording() {
+        app.push_line(
+            LogKind::Status,
+            "Input recording is off; `/set record_input on` keeps the last 200 key/mouse/paste events for /debug-keys and crash reports",
+        );
+        return;
+    }
+    match export_input_events(target.map(std::path::Path::new)) {
+        Ok((path, count)) => app.push_line(
+            LogKind::Status,
+            format!("Exported {count} input events to {}", path.display()),
+        ),
+        Err(error) => app.push_error_report("debug-keys export failed", error),
+    }
+}
+
+pub(super) fn handle_json_command<'a>(
+    app: &mut AppState,
+    trimmed: &str,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    match parts.next() {
+        None => app.push_line(LogKind::Error, JSON_USAGE_MESSAGE),
+        Some("off") if parts.next().is_none() => disarm_json_output(app),
+        Some("show") if parts.next().is_none() => open_json_output_panel(app),
+        Some("save") => {
+            let target = parts.next();
+            if parts.next().is_some() {
+                app.push_line(LogKind::Error, JSON_USAGE_MESSAGE);
+                return;
+            }
+            save_last_json_output(app, target);
+        }
+        Some(_) => arm_json_output(app, trimmed.strip_prefix("/json").unwrap_or_default()),
+    }
+}
+
+pub(super) fn handle_translate_command<'a>(
+    app: &mut AppState,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    match (parts.next(), parts.next()) {
+        (None, None) => show_translation_status(app),
+        (Some("off"), None) => stop_translation(app),
+        (Some("show"), turn) => open_translation_panel(app, turn),
+        (Some(language), None) => set_translation_target(app, language),
+        _ => app.push_line(LogKind::Error, TRANSLATE_USAGE_MESSAGE),
+    }
+}
+
+pub(super) fn handle_cd_command(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+    trimmed: &str,
+) {
+    // Keep the rest of the line so paths with spaces work.
+    let path = trimmed.strip_prefix("/cd").unwrap_or_default().trim();
+    if path.is_empty() {
+        show_working_dir(app);
+        return;
+    }
+    request_cwd_set(app, child_stdin, next_id, path, None);
+}
+
+pub(super) fn handle_export_command<'a>(
+    app: &mut AppState,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    let Some(format) = parts.next().and_then(LogExportFormat::parse) else {
+        app.push_line(LogKind::Error, EXPORT_USAGE_MESSAGE);
+        return;
+    };
+    let mut scope = LogExportScope::Session;
+    let mut target = None;
+    for part in parts {
+        match part.strip_prefix("--run") {
+            Some("") => scope = LogExportScope::LastRun,
+            Some(rest) => match rest.strip_prefix('=').filter(|id| !id.is_empty()) {
+                Some(run_id) => scope = LogExportScope::Run(run_id.to_string()),
+                None => {
+                    app.push_line(LogKind::Error, EXPORT_USAGE_MESSAGE);
+                    return;
+                }
+            },
+            None if target.is_none() => target = Some(part),
+            None => {
+                app.push_line(LogKind::Error, EXPORT_USAGE_MESSAGE);
+                return;
+            }
+        }
+    }
+    export_log(app, format, &scope, target);
+}
+
+pub(super) fn handle_tab_command<'a>(
+    app: &mut AppState,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    let number = |value: &str| value.parse::<usize>().ok();
+    let request = match (parts.next(), parts.next(), parts.next()) {
+        (None, _, _) => {
+            show_tabs(app);
+            return;
+        }
+        (Some("new"), None, _) => Some(TabRequest::New),
+        (Some("next"), None, _) => Some(TabRequest::Next),
+        (Some("prev"), None, _) => Some(TabRequest::Previous),
+        (Some("close"), None, _) => Some(TabRequest::Close(None)),
+        (Some("close"), Some(value), None) => number(value).map(|n| TabRequest::Close(Some(n))),
+        (Some(value), None, _) => number(value).map(TabRequest::Switch),
+        _ => None,
+    };
+    match request {
+        Some(request) => {
+            request_tab(app, request);
+        }
+        None => app.push_line(LogKind::Error, TAB_USAGE_MESSAGE),
+    }
+}
+
+/// The rest of the line is the path, so file names may contain spaces.
+pub(super) fn handle_tee_command(app: &mut AppState, trimmed: &str) {
+    let target = trimmed.strip_prefix("/tee").unwrap_or_default().trim();
+    if target.is_empty() || target == "off" {
+        if !stop_run_tee(app) {
+            app.push_line(
+                LogKind::Status,
+                format!("No tee is open; {TEE_USAGE_MESSAGE}"),
+            );
+        }
+        return;
+    }
+    start_run_tee(app, target);
+}
+
+pub(super) fn handle_link_command<'a>(
+    app: &mut AppState,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    let turn = match (parts.next(), parts.next()) {
+        (None, _) => None,
+        (Some(value), None) => match value.parse::<usize>() {
+            Ok(turn) => Some(turn),
+            Err(_) => {
+                app.push_line(LogKind::Error, LINK_USAGE_MESSAGE);
+                return;
+            }
+        },
+        _ => {
+            app.push_line(LogKind::Error, LINK_USAGE_MESSAGE);
+            return;
+        }
+    };
+    copy_turn_link(app, turn);
+}
+
+pub(super) fn handle_copy_command<'a>(
+    app: &mut AppState,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    match (parts.next(), parts.next()) {
+        (None, _) => copy_last_message(app, false),
+        (Some("code"), None) => copy_last_message(app, true),
+        (Some("pick"), None) => open_copy_picker(app),
+        _ => app.push_line(LogKind::Error, COPY_USAGE_MESSAGE),
+    }
+}
+
+pub(super) fn handle_runtime_command<'a>(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    let copy = match (parts.next(), parts.next()) {
+        (None, _) => false,
+        (Some("copy"), None) => true,
+        _ => {
+            app.push_line(LogKind::Error, RUNTIME_USAGE_MESSAGE);
+            return;
+        }
+    };
+    request_runtime_report(app, child_stdin, next_id, copy);
+}
+
+pub(super) fn handle_tools_command<'a>(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    match (parts.next(), parts.next(), parts.next()) {
+        (None, _, _) => request_tools_panel(app, child_stdin, next_id),
+        (Some("enable"), Some(name), None) => set_tool_enabled_by_name(app, name, true),
+        (Some("disable"), Some(name), None) => set_tool_enabled_by_name(app, name, false),
+        _ => app.push_line(LogKind::Error, TOOLS_USAGE_MESSAGE),
+    }
+}
+
+pub(super) fn handle_pin_command<'a>(
+    app: &mut AppState,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    let subcommand = parts.next();
+    if subcommand == Some("add") {
+        let mut name = None;
+        let mut turns = DEFAULT_PIN_TURNS;
+        while let Some(part) = parts.next() {
+            if part == "--turns" {
+                match parts.next().and_then(|value| value.parse::<usize>().ok()) {
+                    Some(value) if value > 0 => turns = value,
+                    _ => {
+                        app.push_line(LogKind::Error, PIN_USAGE_MESSAGE);
+                        return;
+                    }
+                }
+            } else if name.is_none() {
+                name = Some(part);
+            } else {
+                app.push_line(LogKind::Error, PIN_USAGE_MESSAGE);
+                return;
+            }
+        }
+        pin_log_region(app, name, turns);
+        return;
+    }
+    match (subcommand, parts.next(), parts.next()) {
+        (None, _, _) => open_pins_panel(app),
+        (Some("mark"), None, _) => mark_pin_start(app),
+        (Some("clear"), None, _) => clear_pins(app),
+        (Some("drop"), Some(name), None) => drop_pin(app, name),
+        _ => app.push_line(LogKind::Error, PIN_USAGE_MESSAGE),
+    }
+}
+
+pub(super) fn handle_note_command<'a>(
+    app: &mut AppState,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    let mut parts = parts.peekable();
+    match parts.peek().copied() {
+        None => return open_notes_panel(app),
+        Some("clear") | Some("drop") => {
+            let subcommand = parts.next();
+            let id = parts
+                .next()
+                .and_then(|id| id.trim_start_matches('#').parse::<usize>().ok());
+            match (subcommand, id, parts.next()) {
+                (Some("clear"), None, None) => clear_session_notes(app),
+                (Some("drop"), Some(id), None) => drop_session_note(app, id),
+                _ => app.push_line(LogKind::Error, NOTE_USAGE_MESSAGE),
+            }
+            return;
+        }
+        _ => {}
+    }
+    let mut expiry = NoteExpiry::Never;
+    while let Some(option) = parts.next_if(|part| part.starts_with("--")) {
+        let value = parts.next().unwrap_or_default();
+        expiry = match option {
+            "--turns" => match value.parse::<usize>() {
+                Ok(turns) if turns > 0 => NoteExpiry::Turns(turns),
+                _ => return app.push_line(LogKind::Error, NOTE_USAGE_MESSAGE),
+            },
+            "--for" => match parse_note_ttl(value) {
+                Some(ttl) => NoteExpiry::At(Instant::now() + ttl),
+                None => return app.push_line(LogKind::Error, NOTE_USAGE_MESSAGE),
+            },
+            _ => return app.push_line(LogKind::Error, NOTE_USAGE_MESSAGE),
+        };
+    }
+    let text = parts.collect::<Vec<_>>().join(" ");
+    if text.is_empty() {
+        app.push_line(LogKind::Error, NOTE_USAGE_MESSAGE);
+        return;
+    }
+    add_session_note(app, &text, expiry);
+}
+
+pub(super) fn handle_scratch_command<'a>(
+    app: &mut AppState,
+    trimmed: &str,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    let subcommand = parts.next();
+    if subcommand == Some("add") {
+        // Keep the snippet's own spacing and line breaks rather than the split words.
+        let text = trimmed
+            .strip_prefix("/scratch")
+            .map(str::trim_start)
+            .and_then(|rest| rest.strip_prefix("add"))
+            .unwrap_or_default()
+            .trim();
+        if text.is_empty() {
+            app.push_line(LogKind::Error, SCRATCH_USAGE_MESSAGE);
+        } else {
+            pin_text(app, text);
+        }
+        return;
+    }
+    let arg = parts.next();
+    if parts.next().is_some() {
+        app.push_line(LogKind::Error, SCRATCH_USAGE_MESSAGE);
+        return;
+    }
+    match (subcommand, arg) {
+        (None, None) => open_scratchpad_panel(app),
+        (Some("pin"), None) => pin_last_answer(app),
+        (Some("clear"), None) => clear_scratchpad(app),
+        (Some("export"), target) => export_scratchpad(app, target),
+        (Some("drop"), Some(number)) => match number.parse::<usize>() {
+            Ok(number) => drop_scratchpad_entry(app, number),
+            Err(_) => app.push_line(LogKind::Error, SCRATCH_USAGE_MESSAGE),
+        },
+        _ => app.push_line(LogKind::Error, SCRATCH_USAGE_MESSAGE),
+    }
+}
+
+pub(super) fn handle_set_command<'a>(
+    app: &mut AppState,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    let Some(key) = parts.next() else {
+        show_settings(app, None);
+        return;
+    };
+    let value = parts.collect::<Vec<_>>().join(" ");
+    if value.is_empty() {
+        show_settings(app, Some(key));
+    } else if value.contains(char::is_whitespace) && key != "status_format" {
+        // Only the free-text run line template may contain spaces.
+        app.push_line(LogKind::Error, SET_USAGE_MESSAGE);
+    } else {
+        set_setting(app, key, &value);
+    }
+}
+
+/// Shortcuts for the `notifications` and `notify_threshold` settings.
+pub(super) fn handle_notify_command<'a>(
+    app: &mut AppState,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    match (parts.next(), parts.next(), parts.next()) {
+        (None, _, _) => {
+            let threshold = match app.settings.notify_threshold {
+                0 => "only when the terminal is unfocused".to_string(),
+                secs => format!("when unfocused or after {secs}s"),
+            };
+            let state = if app.settings.notifications {
+                format!("on ({threshold})")
+            } else {
+                "off".to_string()
+            };
+            app.push_line(
+                LogKind::Status,
+                format!("Run notifications: {state}; {NOTIFY_USAGE_MESSAGE}"),
+            );
+        }
+        (Some(value @ ("on" | "off")), None, _) => set_setting(app, "notifications", value),
+        (Some("threshold"), Some(secs), None) => set_setting(app, "notify_threshold", secs),
+        _ => app.push_line(LogKind::Error, NOTIFY_USAGE_MESSAGE),
+    }
+}
+
+/// Shortcut for the `glyphs` setting.
+pub(super) fn handle_glyphs_command<'a>(
+    app: &mut AppState,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    match (parts.next(), parts.next()) {
+        (None, _) => {
+            let active = if ascii_glyphs() { "ascii" } else { "unicode" };
+            app.push_line(
+                LogKind::Status,
+                format!(
+                    "Glyphs: {} (drawing {active}); {GLYPHS_USAGE_MESSAGE}",
+                    app.settings.glyphs.as_str()
+                ),
+            );
+        }
+        (Some(value @ ("ascii" | "unicode" | "auto")), None) => set_setting(app, "glyphs", value),
+        _ => app.push_line(LogKind::Error, GLYPHS_USAGE_MESSAGE),
+    }
+}
+
 pub(super) fn handle_errors_command<'a>(
     app: &mut AppState,
     parts: &mut impl Iterator<Item = &'a str>,
@@ -624,6 +1331,8 @@ pub(super) fn handle_lane_command<'a>(
     app.context_panel = None;
     app.skills_list_panel = None;
     app.lane_list_panel = None;
+    app.lane_attach = None;
+    app.mcp_list_panel = None;
     app.theme_list_panel = None;
 
     let id = next_id();
@@ -634,6 +1343,86 @@ pub(super) fn handle_lane_command<'a>(
     }
 }
 
+pub(super) fn handle_cache_command<'a>(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    if parts.next().is_some() {
+        app.push_line(LogKind::Error, "usage: /cache");
+        return;
+    }
+    request_cache_list(app, child_stdin, next_id);
+}
+
+/// Cached output lines fetched per `/page` request.
+const READ_PAGE_LINES: usize = 40;
+
+pub(super) fn handle_page_command<'a>(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+    parts: &mut impl Iterator<Item = &'a str>,
+) {
+    let requested = parts.next();
+    if parts.next().is_some() {
+        app.push_line(LogKind::Error, PAGE_USAGE_MESSAGE);
+        return;
+    }
+    let number = match requested {
+        Some(value) => match value.trim_start_matches('#').parse::<usize>() {
+            Ok(number) => number,
+            Err(_) => {
+                app.push_line(LogKind::Error, PAGE_USAGE_MESSAGE);
+                return;
+            }
+        },
+        None => match app.read_pages.keys().next_back() {
+            Some(number) => *number,
+            None => {
+                app.push_line(LogKind::Status, "No read results to page yet.");
+                return;
+            }
+        },
+    };
+    let Some(record) = app.read_pages.get(&number) else {
+        app.push_line(
+            LogKind::Error,
+            format!("Read result {number} is no longer available."),
+        );
+        return;
+    };
+    if record.exhausted {
+        app.push_line(
+            LogKind::Status,
+            format!("{}: already at end of output", record.label),
+        );
+        return;
+    }
+    if !app.runtime_info.supports_tool_call {
+        app.push_line(LogKind::Status, "Read paging unavailable");
+        return;
+    }
+    if app.rpc_pending.read_page_id.is_some() {
+        app.push_line(LogKind::Status, "A read page is already loading.");
+        return;
+    }
+    let arguments = json!({
+        "ref_id": record.ref_id,
+        "offset": record.next_offset,
+        "limit": READ_PAGE_LINES,
+    });
+    let id = next_id();
+    app.rpc_pending.read_page_id = Some(id.clone());
+    app.rpc_pending.read_page_number = Some(number);
+    if let Err(error) = send_tool_call(child_stdin, &id, "tool_output_cache", arguments) {
+        app.rpc_pending.read_page_id = None;
+        app.rpc_pending.read_page_number = None;
+        app.push_error_report("send error", error.to_string());
+    }
+}
+
 pub(super) fn handle_logout_command<'a>(
     app: &mut AppState,
     child_stdin: &mut RuntimeStdin,
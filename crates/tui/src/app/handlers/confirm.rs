@@ -1,29 +1,67 @@
-use crate::app::runtime::{send_confirm_response, UiConfirmRequest};
+use crate::app::handlers::audit::record_confirm_decision;
+use crate::app::runtime::{
+    send_confirm_response, ConfirmHunkDecision, RuntimeStdin, UiConfirmRequest,
+};
+use crate::app::state::{ConfirmAccelerators, HunkReviewState, LogKind, LogLine, LogTone};
 use crate::app::{AppState, ConfirmMode, ConfirmPhase};
 use crossterm::event::{KeyCode, KeyModifiers};
-use std::io::BufWriter;
-use std::process::ChildStdin;
 
 pub fn handle_confirm_request(app: &mut AppState, request: UiConfirmRequest) {
     app.scroll_from_bottom = 0;
     app.confirm_input.clear();
     app.confirm_dialog = None;
+    let confirm_label = request.confirm_label.unwrap_or_else(|| "Yes".to_string());
+    let cancel_label = request.cancel_label.unwrap_or_else(|| "No".to_string());
+    // Read-only sessions never persist allow rules.
+    let allow_remember = request.allow_remember && !app.read_only;
+    let hunk_review = request
+        .tool_call_id
+        .as_ref()
+        .and_then(|tool_call_id| app.pending_hunk_reviews.remove(tool_call_id));
+    let accelerators = ConfirmAccelerators::derive(
+        &confirm_label,
+        &cancel_label,
+        allow_remember,
+        &reserved_confirm_keys(
+            allow_remember || request.allow_reason,
+            hunk_review.is_some(),
+        ),
+        app.settings.confirm_keys,
+    );
     app.pending_confirm_dialog = Some(crate::app::ConfirmDialogState {
         id: request.id,
+        tool: request
+            .tool_call_id
+            .as_ref()
+            .and_then(|tool_call_id| app.permission_tool_by_call.remove(tool_call_id)),
         title: request.title,
         message: request.message,
         danger_level: request.danger_level,
-        confirm_label: request.confirm_label.unwrap_or_else(|| "Yes".to_string()),
-        cancel_label: request.cancel_label.unwrap_or_else(|| "No".to_string()),
-        allow_remember: request.allow_remember,
+        confirm_label,
+        cancel_label,
+        allow_remember,
         allow_reason: request.allow_reason,
         command_view: false,
         selected: 0,
         mode: ConfirmMode::Select,
+        accelerators,
+        hunk_review,
     });
     app.render_state.confirm_phase = ConfirmPhase::Pending;
 }
 
+/// Letters the dialog already binds: `d` for the details view, `h` for the hunk review.
+fn reserved_confirm_keys(has_details: bool, has_hunk_review: bool) -> Vec<char> {
+    let mut reserved = Vec::new();
+    if has_details {
+        reserved.push('d');
+    }
+    if has_hunk_review {
+        reserved.push('h');
+    }
+    reserved
+}
+
 pub fn activate_pending_confirm_dialog(app: &mut AppState) -> bool {
     if app.render_state.confirm_phase != ConfirmPhase::Pending || app.confirm_dialog.is_some() {
         return false;
@@ -32,6 +70,8 @@ pub fn activate_pending_confirm_dialog(app: &mut AppState) -> bool {
         app.render_state.confirm_phase = ConfirmPhase::None;
         return false;
     };
+    app.live_region
+        .announce(format!("Awaiting approval: {}", pending_confirm.title));
     app.confirm_dialog = Some(pending_confirm);
     app.render_state.confirm_phase = ConfirmPhase::Active;
     true
@@ -73,6 +113,7 @@ fn handle_confirm_select_key(
     allow_remember: bool,
     allow_reason: bool,
     command_view: bool,
+    accelerators: ConfirmAccelerators,
 ) -> ConfirmKeyUpdate {
     let mut update = ConfirmKeyUpdate {
         selected,
@@ -94,6 +135,30 @@ fn handle_confirm_select_key(
         return update;
     }
 
+    if let KeyCode::Char(ch) = key {
+        let ch = ch.to_ascii_lowercase();
+        if accelerators.allow == Some(ch) {
+            update.response = Some(ConfirmResponse {
+                ok: true,
+                remember: allow_remember && selected == 1,
+                reason: None,
+            });
+            return update;
+        }
+        if accelerators.deny == Some(ch) {
+            update.response = Some(ConfirmResponse {
+                ok: false,
+                remember: false,
+                reason: None,
+            });
+            return update;
+        }
+        if accelerators.remember == Some(ch) && allow_remember {
+            update.selected = if selected == 1 { 0 } else { 1 };
+            return update;
+        }
+    }
+
     match key {
         KeyCode::Up => {
             update.selected = update.selected.saturating_sub(1);
@@ -220,13 +285,86 @@ fn handle_confirm_reason_key(
     update
 }
 
+/// Keys for the large-diff hunk list; `None` leaves the key to the regular confirm handling.
+fn handle_hunk_review_key(review: &mut HunkReviewState, key: KeyCode) -> Option<Vec<LogLine>> {
+    if !review.active {
+        if matches!(key, KeyCode::Char('h') | KeyCode::Char('H')) {
+            review.active = true;
+            return Some(Vec::new());
+        }
+        return None;
+    }
+    let last = review.hunks.len().saturating_sub(1);
+    let decide = |review: &mut HunkReviewState, accepted: bool| {
+        if let Some(hunk) = review.hunks.get_mut(review.selected) {
+            hunk.decision = Some(accepted);
+        }
+        review.selected = (review.selected + 1).min(last);
+    };
+    match key {
+        KeyCode::Up => review.selected = review.selected.saturating_sub(1),
+        KeyCode::Down => review.selected = (review.selected + 1).min(last),
+        KeyCode::Char('a') | KeyCode::Char('A') => decide(review, true),
+        KeyCode::Char('x') | KeyCode::Char('X') => decide(review, false),
+        KeyCode::Char(' ') | KeyCode::Char('e') | KeyCode::Char('E') => {
+            let Some(hunk) = review.hunks.get(review.selected) else {
+                return Some(Vec::new());
+            };
+            let mut lines = vec![LogLine::new_with_tone(
+                LogKind::DiffMeta,
+                LogTone::Detail,
+                format!("  hunk #{} {}", review.selected + 1, hunk.label),
+            )];
+            lines.extend(hunk.lines.iter().cloned());
+            return Some(lines);
+        }
+        KeyCode::Char('h') | KeyCode::Char('H') | KeyCode::Esc | KeyCode::Enter => {
+            review.active = false;
+        }
+        _ => {}
+    }
+    Some(Vec::new())
+}
+
+fn hunk_decisions(review: &HunkReviewState) -> Vec<ConfirmHunkDecision> {
+    review
+        .hunks
+        .iter()
+        .enumerate()
+        .filter_map(|(index, hunk)| {
+            hunk.decision.map(|accepted| ConfirmHunkDecision {
+                index: index + 1,
+                label: hunk.label.clone(),
+                accepted,
+            })
+        })
+        .collect()
+}
+
+fn hunk_decision_summary(decisions: &[ConfirmHunkDecision]) -> String {
+    let list = |accepted: bool| {
+        decisions
+            .iter()
+            .filter(|decision| decision.accepted == accepted)
+            .map(|decision| format!("#{}", decision.index))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let (accepted, rejected) = (list(true), list(false));
+    match (accepted.is_empty(), rejected.is_empty()) {
+        (false, false) => format!("Hunk review: accepted {accepted}; rejected {rejected}"),
+        (false, true) => format!("Hunk review: accepted {accepted}"),
+        _ => format!("Hunk review: rejected {rejected}"),
+    }
+}
+
 pub fn handle_confirm_key(
     app: &mut AppState,
     key: KeyCode,
     modifiers: KeyModifiers,
-    child_stdin: &mut BufWriter<ChildStdin>,
+    child_stdin: &mut RuntimeStdin,
 ) -> Option<bool> {
-    let (confirm_id, mode, selected, allow_remember, allow_reason, command_view) = {
+    let (confirm_id, mode, selected, allow_remember, allow_reason, command_view, accelerators) = {
         let confirm = app.confirm_dialog.as_ref()?;
         (
             confirm.id.clone(),
@@ -235,17 +373,49 @@ pub fn handle_confirm_key(
             confirm.allow_remember,
             confirm.allow_reason,
             confirm.command_view,
+            confirm.accelerators,
         )
     };
 
-    let update = match mode {
-        ConfirmMode::Select => {
-            handle_confirm_select_key(key, selected, allow_remember, allow_reason, command_view)
+    if mode == ConfirmMode::Select && !command_view {
+        let review = app
+            .confirm_dialog
+            .as_mut()
+            .and_then(|confirm| confirm.hunk_review.as_mut());
+        if let Some(expanded) = review.and_then(|review| handle_hunk_review_key(review, key)) {
+            app.extend_lines(expanded);
+            return Some(true);
         }
+    }
+
+    let update = match mode {
+        ConfirmMode::Select => handle_confirm_select_key(
+            key,
+            selected,
+            allow_remember,
+            allow_reason,
+            command_view,
+            accelerators,
+        ),
         ConfirmMode::Reason => handle_confirm_reason_key(app, key, modifiers, selected),
     };
 
     if let Some(response) = update.response {
+        let hunks = app
+            .confirm_dialog
+            .as_ref()
+            .and_then(|confirm| confirm.hunk_review.as_ref())
+            .map(hunk_decisions)
+            .unwrap_or_default();
+        if !hunks.is_empty() {
+            app.push_line(LogKind::Status, hunk_decision_summary(&hunks));
+        }
+        record_confirm_decision(
+            app,
+            response.ok,
+            response.remember,
+            response.reason.as_deref(),
+        );
         app.confirm_dialog = None;
         app.pending_confirm_dialog = None;
         app.render_state.confirm_phase = ConfirmPhase::None;
@@ -259,6 +429,7 @@ pub fn handle_confirm_key(
             response.ok,
             response.remember,
             response.reason.as_deref(),
+            &hunks,
         ) {
             app.push_error_report("confirm response error", error.to_string());
         }
@@ -282,13 +453,53 @@ pub fn handle_confirm_key(
 
 #[cfg(test)]
 mod tests {
-    use super::handle_confirm_select_key;
+    use super::{handle_confirm_select_key, handle_hunk_review_key, hunk_decisions};
+    use crate::app::state::{ConfirmAccelerators, HunkReviewState, ReviewHunk};
     use crate::app::ConfirmMode;
     use crossterm::event::KeyCode;
 
+    #[test]
+    fn hunk_review_records_decisions_and_expands_on_demand() {
+        let hunk = |label: &str| ReviewHunk {
+            label: label.to_string(),
+            added: 1,
+            removed: 1,
+            lines: Vec::new(),
+            decision: None,
+        };
+        let mut review = HunkReviewState {
+            hunks: vec![hunk("@@ -1 +1 @@"), hunk("@@ -9 +9 @@")],
+            ..HunkReviewState::default()
+        };
+        assert!(handle_hunk_review_key(&mut review, KeyCode::Char('y')).is_none());
+        assert!(handle_hunk_review_key(&mut review, KeyCode::Char('h')).is_some());
+        assert!(review.active);
+
+        handle_hunk_review_key(&mut review, KeyCode::Char('a'));
+        assert_eq!(review.selected, 1);
+        let expanded = handle_hunk_review_key(&mut review, KeyCode::Char(' ')).expect("consumed");
+        assert_eq!(expanded[0].plain_text(), "  hunk #2 @@ -9 +9 @@");
+        handle_hunk_review_key(&mut review, KeyCode::Char('x'));
+        handle_hunk_review_key(&mut review, KeyCode::Esc);
+        assert!(!review.active);
+
+        let decisions = hunk_decisions(&review)
+            .into_iter()
+            .map(|decision| (decision.index, decision.accepted))
+            .collect::<Vec<_>>();
+        assert_eq!(decisions, vec![(1, true), (2, false)]);
+    }
+
     #[test]
     fn d_enters_command_view_in_select_mode() {
-        let update = handle_confirm_select_key(KeyCode::Char('d'), 0, true, true, false);
+        let update = handle_confirm_select_key(
+            KeyCode::Char('d'),
+            0,
+            true,
+            true,
+            false,
+            ConfirmAccelerators::default(),
+        );
 
         assert_eq!(update.selected, 0);
         assert_eq!(update.mode, ConfirmMode::Select);
@@ -299,7 +510,14 @@ mod tests {
 
     #[test]
     fn d_does_not_toggle_non_permission_confirm() {
-        let update = handle_confirm_select_key(KeyCode::Char('d'), 0, false, false, false);
+        let update = handle_confirm_select_key(
+            KeyCode::Char('d'),
+            0,
+            false,
+            false,
+            false,
+            ConfirmAccelerators::default(),
+        );
 
         assert_eq!(update.command_view, None);
         assert!(!update.consume);
@@ -307,11 +525,41 @@ mod tests {
 
     #[test]
     fn esc_leaves_command_view_without_denying() {
-        let update = handle_confirm_select_key(KeyCode::Esc, 1, true, true, true);
+        let update = handle_confirm_select_key(
+            KeyCode::Esc,
+            1,
+            true,
+            true,
+            true,
+            ConfirmAccelerators::default(),
+        );
 
         assert_eq!(update.selected, 1);
         assert_eq!(update.command_view, Some(false));
         assert!(update.consume);
         assert!(update.response.is_none());
     }
+
+    #[test]
+    fn label_accelerators_answer_and_toggle_remember() {
+        let keys = ConfirmAccelerators {
+            allow: Some('a'),
+            deny: Some('e'),
+            remember: Some('r'),
+        };
+        let toggled = handle_confirm_select_key(KeyCode::Char('r'), 0, true, true, false, keys);
+        assert_eq!(toggled.selected, 1);
+        assert!(toggled.response.is_none());
+
+        let allowed = handle_confirm_select_key(KeyCode::Char('A'), 1, true, true, false, keys);
+        let response = allowed.response.expect("allow");
+        assert!(response.ok && response.remember);
+
+        let denied = handle_confirm_select_key(KeyCode::Char('e'), 1, true, true, false, keys);
+        assert!(!denied.response.expect("deny").ok);
+
+        // Keymap letters do not fire while the command details are open.
+        let details = handle_confirm_select_key(KeyCode::Char('a'), 0, true, true, true, keys);
+        assert!(details.response.is_none());
+    }
 }
@@ -0,0 +1,36 @@
+use crate::app::runtime::{send_request, RuntimeStdin};
+use crate::app::AppState;
+use serde_json::json;
+use std::time::Instant;
+
+/// Sends a silent `ping` to an ssh runtime every `KEEPALIVE_INTERVAL`; local runtimes are
+/// never pinged. A failed write is left to exit detection (or the keepalive timeout).
+pub(crate) fn send_keepalive_if_due(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+    now: Instant,
+) {
+    let over_ssh = app
+        .runtime_info
+        .launch
+        .as_ref()
+        .is_some_and(|launch| launch.ssh_host().is_some());
+    if !over_ssh
+        || app.runtime_reconnect.reconnecting()
+        || app.rpc_pending.keepalive_id.is_some()
+        || !app.connection_health.due(now)
+    {
+        return;
+    }
+    let id = next_id();
+    if send_request(child_stdin, &id, "ping", json!({})).is_ok() {
+        app.rpc_pending.keepalive_id = Some(id);
+    }
+    app.connection_health.sent(now);
+}
+
+/// Any answer counts, including "method not found" from runtimes without `ping`.
+pub(crate) fn handle_keepalive_response(app: &mut AppState) {
+    app.connection_health.answered(Instant::now());
+}
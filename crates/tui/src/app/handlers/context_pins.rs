@@ -0,0 +1,160 @@
+use crate::app::log_wrap::visible_log_lines;
+use crate::app::state::{LogKind, LogLine};
+use crate::app::{AppState, ContextPanelState};
+
+const PINS_PANEL_TITLE_PREFIX: &str = "Pinned context (";
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+/// `/pin mark`: later captures start at the next log line instead of the visible rows.
+pub(crate) fn mark_pin_start(app: &mut AppState) {
+    app.context_pins.set_mark(app.log.len());
+    app.push_line(
+        LogKind::Status,
+        "Pin mark set; /pin [name] captures everything logged after this line",
+    );
+}
+
+/// Captures the marked range, or the visible log rows when no mark is set.
+pub(crate) fn pin_log_region(app: &mut AppState, name: Option<&str>, turns: usize) {
+    let (lines, source) = match app.context_pins.mark() {
+        // The status line just pushed by `/pin mark` is not part of the range.
+        Some(mark) => (
+            app.log
+                .get(mark.saturating_add(1)..)
+                .unwrap_or_default()
+                .iter()
+                .map(LogLine::plain_text)
+                .collect::<Vec<_>>(),
+            "marked range",
+        ),
+        None => (
+            visible_log_lines(app)
+                .iter()
+                .map(LogLine::plain_text)
+                .collect::<Vec<_>>(),
+            "visible log",
+        ),
+    };
+    let text = lines.join("\n");
+    let name = name
+        .map(str::to_string)
+        .unwrap_or_else(|| app.context_pins.next_default_name());
+    if !app.context_pins.add(&name, &text, turns) {
+        app.push_line(LogKind::Status, format!("Nothing to pin in the {source}"));
+        return;
+    }
+    app.context_pins.take_mark();
+    app.push_line(
+        LogKind::Status,
+        format!(
+            "Pinned {} line{} from the {source} as '{name}' for the next {turns} prompt{} (/pin to view)",
+            lines.len(),
+            plural(lines.len()),
+            plural(turns)
+        ),
+    );
+    refresh_pins_panel(app);
+}
+
+pub(crate) fn open_pins_panel(app: &mut AppState) {
+    if app.context_pins.is_empty() {
+        let hint = if app.context_pins.mark().is_some() {
+            "No pinned context; a mark is set, /pin [name] captures from it."
+        } else {
+            "No pinned context. /pin [name] keeps the visible log rows for the next prompts."
+        };
+        app.push_line(LogKind::Status, hint);
+        return;
+    }
+    app.skills_list_panel = None;
+    app.theme_list_panel = None;
+    let count = app.context_pins.pins().len();
+    app.context_panel = Some(ContextPanelState {
+        title: format!("{PINS_PANEL_TITLE_PREFIX}{count} snippet{})", plural(count)),
+        header: "/pin drop <name> · /pin clear · Esc close".to_string(),
+        rows: app.context_pins.panel_rows(),
+        selected: 0,
+    });
+}
+
+/// Rebuilds an open pins panel; other context panels are left alone.
+fn refresh_pins_panel(app: &mut AppState) {
+    let showing = app
+        .context_panel
+        .as_ref()
+        .is_some_and(|panel| panel.title.starts_with(PINS_PANEL_TITLE_PREFIX));
+    if !showing {
+        return;
+    }
+    if app.context_pins.is_empty() {
+        app.context_panel = None;
+        return;
+    }
+    let selected = app
+        .context_panel
+        .as_ref()
+        .map(|panel| panel.selected)
+        .unwrap_or(0);
+    open_pins_panel(app);
+    if let Some(panel) = app.context_panel.as_mut() {
+        panel.selected = selected.min(panel.rows.len().saturating_sub(1));
+    }
+}
+
+pub(crate) fn drop_pin(app: &mut AppState, name: &str) {
+    match app.context_pins.remove(name) {
+        Some(_) => {
+            app.push_line(LogKind::Status, format!("Dropped pinned context '{name}'"));
+            refresh_pins_panel(app);
+        }
+        None => app.push_line(LogKind::Error, format!("no pinned context named '{name}'")),
+    }
+}
+
+pub(crate) fn clear_pins(app: &mut AppState) {
+    app.context_pins.clear();
+    app.context_pins.take_mark();
+    refresh_pins_panel(app);
+    app.push_line(LogKind::Status, "Pinned context cleared");
+}
+
+/// Called once a prompt carrying the pins is queued.
+pub(crate) fn consume_pin_turn(app: &mut AppState) {
+    let expired = app.context_pins.consume_turn();
+    if expired.is_empty() {
+        return;
+    }
+    app.push_line(
+        LogKind::Status,
+        format!("Pinned context expired: {}", expired.join(", ")),
+    );
+    refresh_pins_panel(app);
+}
+
+/// Rows for the `/context` snapshot.
+pub(crate) fn pinned_context_rows(app: &AppState) -> Vec<String> {
+    if app.context_pins.is_empty() {
+        return vec!["(none)".to_string()];
+    }
+    app.context_pins
+        .pins()
+        .iter()
+        .map(|pin| {
+            format!(
+                "{}: {} line{}, {} prompt{} left",
+                pin.name,
+                pin.text.lines().count(),
+                plural(pin.text.lines().count()),
+                pin.remaining,
+                plural(pin.remaining)
+            )
+        })
+        .collect()
+}
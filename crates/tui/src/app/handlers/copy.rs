@@ -0,0 +1,154 @@
+use crate::app::state::{code_blocks, message_preview, LogKind};
+use crate::app::util::clipboard::write_clipboard_text;
+use crate::app::{AppState, PickDialogItem, PickDialogState};
+use crossterm::event::KeyCode;
+
+pub(crate) const COPY_PICK_ID: &str = "copy-message";
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+fn copy_to_clipboard(app: &mut AppState, text: &str, what: &str) {
+    let lines = text.lines().count().max(1);
+    match write_clipboard_text(text) {
+        Ok(()) => app.push_line(
+            LogKind::Status,
+            format!(
+                "Copied {what} ({lines} line{}) to the clipboard",
+                plural(lines)
+            ),
+        ),
+        // The text is still in the log for manual selection (SSH, headless).
+        Err(error) => app.push_line(LogKind::Error, format!("Clipboard unavailable: {error}")),
+    }
+}
+
+/// Copies `message`, or only its last fenced code block with `code_only`.
+fn copy_message(app: &mut AppState, message: &str, what: &str, code_only: bool) {
+    if !code_only {
+        copy_to_clipboard(app, message, what);
+        return;
+    }
+    match code_blocks(message).pop() {
+        Some(block) => copy_to_clipboard(app, &block, &format!("the last code block of {what}")),
+        None => app.push_line(LogKind::Status, format!("No code block in {what}")),
+    }
+}
+
+/// `/copy [code]` and `Alt+C`: the last assistant message, or its last code block.
+pub(crate) fn copy_last_message(app: &mut AppState, code_only: bool) {
+    let Some(message) = app.last_assistant_text.clone() else {
+        app.push_line(LogKind::Status, "No assistant message to copy yet");
+        return;
+    };
+    copy_message(app, &message, "the last answer", code_only);
+}
+
+/// `/copy pick`: recent assistant messages, newest first.
+pub(crate) fn open_copy_picker(app: &mut AppState) {
+    if app.assistant_messages.is_empty() {
+        app.push_line(LogKind::Status, "No assistant message to copy yet");
+        return;
+    }
+    let items: Vec<PickDialogItem> = (0..app.assistant_messages.len())
+        .filter_map(|age| {
+            let message = app.assistant_messages.get(age)?;
+            let lines = message.lines().count();
+            let blocks = code_blocks(message).len();
+            let mut detail = format!("{lines} line{}", plural(lines));
+            if blocks > 0 {
+                detail.push_str(&format!(" · {blocks} code block{}", plural(blocks)));
+            }
+            Some(PickDialogItem {
+                id: age.to_string(),
+                label: message_preview(message),
+                detail: Some(detail),
+            })
+        })
+        .collect();
+    app.pick_dialog = Some(PickDialogState {
+        id: COPY_PICK_ID.to_string(),
+        title: "Copy an assistant message".to_string(),
+        message: Some("Enter copies the message · c copies its last code block".to_string()),
+        chosen: vec![false; items.len()],
+        items,
+        selected: 0,
+        multi: false,
+    });
+}
+
+pub(crate) fn handle_copy_pick_key(app: &mut AppState, key: KeyCode) -> bool {
+    let Some(pick) = app.pick_dialog.as_mut() else {
+        return false;
+    };
+    let code_only = match key {
+        KeyCode::Esc => {
+            app.pick_dialog = None;
+            return true;
+        }
+        KeyCode::Up => {
+            pick.selected = pick.selected.saturating_sub(1);
+            return true;
+        }
+        KeyCode::Down => {
+            if pick.selected + 1 < pick.items.len() {
+                pick.selected += 1;
+            }
+            return true;
+        }
+        KeyCode::Enter => false,
+        KeyCode::Char('c') | KeyCode::Char('C') => true,
+        _ => return false,
+    };
+    let age = pick.selected;
+    app.pick_dialog = None;
+    let Some(message) = app.assistant_messages.get(age).map(str::to_string) else {
+        return true;
+    };
+    let what = if age == 0 {
+        "the last answer".to_string()
+    } else {
+        format!("the answer {age} back")
+    };
+    copy_message(app, &message, &what, code_only);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{handle_copy_pick_key, open_copy_picker, COPY_PICK_ID};
+    use crate::app::AppState;
+    use crossterm::event::KeyCode;
+
+    #[test]
+    fn picker_lists_answers_newest_first() {
+        let mut app = AppState::default();
+        open_copy_picker(&mut app);
+        assert!(app.pick_dialog.is_none());
+
+        app.assistant_messages.record("Older answer");
+        app.assistant_messages
+            .record("Use this:\n```sh\ncargo test\n```");
+        open_copy_picker(&mut app);
+        let pick = app.pick_dialog.as_ref().expect("picker");
+        assert_eq!(pick.id, COPY_PICK_ID);
+        assert_eq!(pick.items[0].label, "Use this:");
+        assert_eq!(
+            pick.items[0].detail.as_deref(),
+            Some("4 lines · 1 code block")
+        );
+        assert_eq!(pick.items[1].label, "Older answer");
+
+        assert!(handle_copy_pick_key(&mut app, KeyCode::Down));
+        assert_eq!(app.pick_dialog.as_ref().map(|pick| pick.selected), Some(1));
+        assert!(handle_copy_pick_key(&mut app, KeyCode::Char('c')));
+        assert!(app.pick_dialog.is_none());
+        let status = app.log.last().expect("status").plain_text();
+        assert_eq!(status, "No code block in the answer 1 back");
+    }
+}
@@ -0,0 +1,305 @@
+use crate::app::state::LogKind;
+use crate::app::util::attachments::referenced_attachment_ids;
+use crate::app::util::dir_tree::{read_dir_mention, DIR_MENTION_PREFIX};
+use crate::app::util::draft::{
+    draft_file_stamp, draft_path, load_draft, remove_draft, save_draft, ComposerDraft, DraftOnDisk,
+};
+use crate::app::util::file_mentions::{read_file_mention, WorkspaceIndex};
+use crate::app::AppState;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// How long the composer must be still before its text is written.
+const DRAFT_SAVE_DEBOUNCE: Duration = Duration::from_secs(1);
+const DRAFT_PREVIEW_CHARS: usize = 60;
+
+fn unix_ms_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn current_dir() -> Option<String> {
+    std::env::current_dir()
+        .ok()
+        .map(|dir| dir.to_string_lossy().into_owned())
+}
+
+fn composer_draft(app: &AppState, text: String) -> ComposerDraft {
+    // Only attachments whose token is still in the text; removed ones linger in the maps.
+    let images =
+        referenced_attachment_ids(&text, &app.composer_nonce, &app.pending_image_attachments)
+            .into_iter()
+            .filter_map(|id| {
+                let image = app.pending_image_attachments.get(&id)?.clone();
+                Some((id, image))
+            })
+            .collect();
+    let files = app
+        .pending_file_attachments
+        .iter()
+        .filter(|file| text.contains(&format!("@{}", file.path)))
+        .map(|file| file.path.clone())
+        .collect();
+    ComposerDraft {
+        text,
+        composer_nonce: app.composer_nonce.clone(),
+        images,
+        files,
+        cwd: current_dir(),
+        saved_at_unix_ms: unix_ms_now(),
+    }
+}
+
+fn write_draft(app: &mut AppState, text: String) {
+    let Some(path) = app.draft.path.clone() else {
+        return;
+    };
+    // Marked even on failure so a broken config dir reports once per edit, not every tick.
+    app.draft.mark_saved(&text);
+    match save_draft(&path, &composer_draft(app, text)) {
+        Ok(()) => {
+            app.draft.on_disk = Some(DraftOnDisk {
+                composer_nonce: app.composer_nonce.clone(),
+                stamp: draft_file_stamp(&path),
+            });
+        }
+        Err(error) => app.push_error_report("draft save failed", error),
+    }
+}
+
+/// Per tick: writes the composer once it has been still for `DRAFT_SAVE_DEBOUNCE`.
+pub(crate) fn autosave_draft(app: &mut AppState, now: Instant) {
+    let text = app.input.current();
+    // Typing `/draft restore` must not replace the draft it is about to restore.
+    if text.trim_start().starts_with("/draft") {
+        return;
+    }
+    if let Some(text) = app.draft.due(&text, now, DRAFT_SAVE_DEBOUNCE) {
+        write_draft(app, text);
+    }
+}
+
+/// On exit: writes what the debounce has not caught up with yet.
+pub(crate) fn flush_draft(app: &mut AppState) {
+    let text = app.input.current();
+    if text.trim().is_empty()
+        || text.trim_start().starts_with("/draft")
+        || app.draft.saved_text.as_deref() == Some(text.as_str())
+    {
+        return;
+    }
+    write_draft(app, text);
+}
+
+/// The composer was sent (or run as a command): its draft is no longer unsent. Only a draft
+/// this tab wrote for this composer is removed, and only while the file still has the size
+/// and time it left it with, so one left by `Esc` (which starts a new composer) or
+/// rewritten by another tab is kept. The file is never read here.
+pub(crate) fn discard_submitted_draft(app: &mut AppState) {
+    let Some(path) = app.draft.path.clone() else {
+        return;
+    };
+    let submitted = app
+        .draft
+        .on_disk
+        .as_ref()
+        .is_some_and(|on_disk| on_disk.composer_nonce == app.composer_nonce);
+    if !submitted {
+        return;
+    }
+    let on_disk = app.draft.on_disk.take();
+    app.draft.saved_text = None;
+    if on_disk.is_some_and(|on_disk| on_disk.stamp == draft_file_stamp(&path)) {
+        let _ = remove_draft(&path);
+    }
+}
+
+fn age_label(saved_at_unix_ms: u64) -> String {
+    let seconds = unix_ms_now().saturating_sub(saved_at_unix_ms) / 1000;
+    match seconds {
+        0..=59 => "just now".to_string(),
+        60..=3599 => format!("{}m ago", seconds / 60),
+        3600..=86_399 => format!("{}h ago", seconds / 3600),
+        _ => format!("{}d ago", seconds / 86_400),
+    }
+}
+
+fn draft_summary(draft: &ComposerDraft) -> String {
+    let line = draft.text.lines().next().unwrap_or_default().trim();
+    let mut preview: String = line.chars().take(DRAFT_PREVIEW_CHARS).collect();
+    if line.chars().count() > DRAFT_PREVIEW_CHARS || draft.text.trim().contains('\n') {
+        preview.push('…');
+    }
+    let attachments = draft.images.len() + draft.files.len();
+    let mut summary = format!("\"{preview}\" · {}", age_label(draft.saved_at_unix_ms));
+    if attachments > 0 {
+        summary.push_str(&format!(
+            " · {attachments} attachment{}",
+            if attachments == 1 { "" } else { "s" }
+        ));
+    }
+    if draft.cwd.is_some() && draft.cwd != current_dir() {
+        summary.push_str(&format!(
+            " · from {}",
+            draft.cwd.as_deref().unwrap_or_default()
+        ));
+    }
+    summary
+}
+
+fn saved_draft(app: &mut AppState) -> Option<ComposerDraft> {
+    let path = app.draft.path.clone()?;
+    match load_draft(&path) {
+        Ok(draft) => draft,
+        Err(error) => {
+            app.push_error_report("draft load failed", error);
+            None
+        }
+    }
+}
+
+/// Full-screen startup: turns autosave on and mentions a draft left by the last session.
+pub(crate) fn offer_saved_draft(app: &mut AppState) {
+    app.draft.path = draft_path();
+    let Some(draft) = saved_draft(app) else {
+        return;
+    };
+    app.push_line(
+        LogKind::Status,
+        format!(
+            "Unsent draft {} · /draft restore · /draft discard",
+            draft_summary(&draft)
+        ),
+    );
+}
+
+pub(crate) fn show_draft_status(app: &mut AppState) {
+    if app.draft.path.is_none() {
+        app.push_line(LogKind::Status, "Draft autosave is off in this mode");
+        return;
+    }
+    match saved_draft(app) {
+        Some(draft) => app.push_line(
+            LogKind::Status,
+            format!("Saved draft {}", draft_summary(&draft)),
+        ),
+        None => app.push_line(LogKind::Status, "No saved draft"),
+    }
+}
+
+/// `/draft restore`: puts the saved text and attachments in the empty composer. `@file`
+/// attachments are read again, so they carry the files' current contents. Returns whether
+/// there was a draft.
+pub(crate) fn restore_draft(app: &mut AppState) -> bool {
+    let Some(draft) = saved_draft(app) else {
+        app.push_line(LogKind::Status, "No saved draft");
+        return false;
+    };
+    app.clear_composer();
+    app.composer_nonce = draft.composer_nonce.clone();
+    app.pending_image_attachments.extend(draft.images.clone());
+    for path in &draft.files {
+        let attached = match path.strip_prefix(DIR_MENTION_PREFIX) {
+            Some(dir) => {
+                let index = app.workspace_index.get_or_insert_with(WorkspaceIndex::load);
+                read_dir_mention(dir, &index.tree)
+            }
+            None => read_file_mention(path),
+        };
+        match attached {
+            Ok(file) => app.pending_file_attachments.push(file),
+            Err(error) => app.push_line(LogKind::Error, format!("Cannot attach {error}")),
+        }
+    }
+    app.input.set_from(&draft.text);
+    app.draft.mark_saved(&draft.text);
+    if let Some(path) = app.draft.path.as_deref() {
+        app.draft.on_disk = Some(DraftOnDisk {
+            composer_nonce: draft.composer_nonce.clone(),
+            stamp: draft_file_stamp(path),
+        });
+    }
+    app.push_line(
+        LogKind::Status,
+        format!("Restored draft {}", draft_summary(&draft)),
+    );
+    true
+}
+
+pub(crate) fn discard_draft(app: &mut AppState) {
+    let Some(path) = app.draft.path.clone() else {
+        app.push_line(LogKind::Status, "No saved draft");
+        return;
+    };
+    app.draft.saved_text = None;
+    app.draft.on_disk = None;
+    match remove_draft(&path) {
+        Ok(()) => app.push_line(LogKind::Status, "Discarded the saved draft"),
+        Err(error) => app.push_error_report("draft discard failed", error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{autosave_draft, discard_submitted_draft, flush_draft, restore_draft};
+    use crate::app::state::PendingImageAttachment;
+    use crate::app::util::draft::{load_draft, save_draft};
+    use crate::app::util::make_attachment_token;
+    use crate::app::AppState;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn draft_survives_esc_and_is_dropped_once_sent() {
+        let dir =
+            std::env::temp_dir().join(format!("codelia-draft-handler-{}", std::process::id()));
+        let path = dir.join("tui-draft.json");
+        let mut app = AppState::default();
+        app.draft.path = Some(path.clone());
+        app.pending_image_attachments.insert(
+            "img-a".to_string(),
+            PendingImageAttachment {
+                data_url: "data:image/png;base64,AA==".to_string(),
+                width: 1,
+                height: 1,
+                encoded_bytes: 4,
+            },
+        );
+        let text = format!(
+            "see {}",
+            make_attachment_token(&app.composer_nonce, "img-a")
+        );
+        app.input.set_from(&text);
+        let t0 = Instant::now();
+        autosave_draft(&mut app, t0);
+        autosave_draft(&mut app, t0 + Duration::from_millis(1_200));
+        let saved = load_draft(&path).expect("load").expect("draft");
+        assert_eq!(saved.text, text);
+        assert_eq!(saved.images.len(), 1);
+
+        // Esc clears the composer; the draft stays and restores with its image.
+        app.clear_composer();
+        autosave_draft(&mut app, t0 + Duration::from_secs(5));
+        flush_draft(&mut app);
+        app.input.set_from("/trace");
+        discard_submitted_draft(&mut app);
+        app.clear_composer();
+        assert!(load_draft(&path).expect("load").is_some());
+        assert!(restore_draft(&mut app));
+        assert_eq!(app.input.current(), text);
+        assert!(app.pending_image_attachments.contains_key("img-a"));
+
+        discard_submitted_draft(&mut app);
+        assert!(load_draft(&path).expect("load").is_none());
+
+        // Another tab rewrote the file since: sending here keeps its draft.
+        app.input.set_from("second prompt");
+        flush_draft(&mut app);
+        let mut other = load_draft(&path).expect("load").expect("draft");
+        other.text = "the other tab's longer draft".to_string();
+        save_draft(&path, &other).expect("save");
+        discard_submitted_draft(&mut app);
+        assert!(load_draft(&path).expect("load").is_some());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,394 @@
+use crate::app::runtime::{
+    send_permissioned_tool_call, send_tool_call, EditCallArgs, RpcResponse, RuntimeStdin,
+};
+use crate::app::state::{LogKind, LogLine};
+use crate::app::util::audit::sha256_hex;
+use crate::app::util::merge3::{locate_edited_region, three_way_merge, MergeChoice};
+use crate::app::{AppState, EditConflictPanelState};
+use crossterm::event::KeyCode;
+use serde_json::{json, Value};
+
+const READ_METADATA_MARKER: &str = "\n\n[read_metadata] content_sha256=";
+
+pub(crate) fn remember_edit_call(app: &mut AppState, tool_call_id: &str, args: EditCallArgs) {
+    app.edit_calls.insert(tool_call_id.to_string(), args);
+}
+
+/// On an `edit` result: when it failed because the file changed after it was read, reads the
+/// file back through the runtime (so a remote workspace is merged against its own copy) and
+/// keeps the call until the merge is resolved; else forgets the call. Returns the line to log.
+pub(crate) fn offer_edit_merge(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+    tool_call_id: &str,
+    stale_read: bool,
+) -> Option<LogLine> {
+    if !stale_read {
+        app.edit_calls.remove(tool_call_id);
+        return None;
+    }
+    let file_path = app.edit_calls.get(tool_call_id)?.file_path.clone();
+    if let Some(previous) = app.rpc_pending.edit_merge_tool_call_id.take() {
+        app.edit_calls.remove(&previous);
+    }
+    let id = next_id();
+    app.rpc_pending.edit_merge_read_id = Some(id.clone());
+    app.rpc_pending.edit_merge_tool_call_id = Some(tool_call_id.to_string());
+    if let Err(error) = send_tool_call(child_stdin, &id, "read", json!({ "file_path": file_path }))
+    {
+        app.rpc_pending.edit_merge_read_id = None;
+        app.rpc_pending.edit_merge_tool_call_id = None;
+        app.edit_calls.remove(tool_call_id);
+        return Some(LogLine::new(LogKind::Error, format!("send error: {error}")));
+    }
+    None
+}
+
+/// Rebuilds the file from a `read` result: `%5d  line` rows, then the content hash. `None` for a
+/// partial preview (truncated output, clipped lines, more lines left) or text that does not hash
+/// to the reported content, such as CRLF files.
+fn parse_read_output(output: &str) -> Option<(String, String)> {
+    let (body, hash) = output.rsplit_once(READ_METADATA_MARKER)?;
+    let hash = hash.trim();
+    let lines = body
+        .split('\n')
+        .enumerate()
+        .map(|(index, row)| {
+            row.trim_start()
+                .strip_prefix(&(index + 1).to_string())?
+                .strip_prefix("  ")
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let text = lines.join("\n");
+    (sha256_hex(text.as_bytes()) == hash).then(|| (text, hash.to_string()))
+}
+
+/// Opens the merge panel over the edited region once the file read for a stale edit arrives.
+pub(crate) fn handle_edit_merge_read_response(
+    app: &mut AppState,
+    tool_call_id: &str,
+    response: RpcResponse,
+) {
+    let Some(edit) = app.edit_calls.get(tool_call_id).cloned() else {
+        return;
+    };
+    let file = response
+        .result
+        .filter(|result| result.get("ok").and_then(Value::as_bool) == Some(true))
+        .and_then(|result| result.get("result")?.as_str().and_then(parse_read_output));
+    let Some((text, expected_hash)) = file else {
+        app.edit_calls.remove(tool_call_id);
+        app.push_line(
+            LogKind::Status,
+            format!(
+                "{} changed on disk and could not be read back in full; no merge offered.",
+                edit.file_path
+            ),
+        );
+        return;
+    };
+    let Some(range) = locate_edited_region(&text, &edit.old_string) else {
+        app.edit_calls.remove(tool_call_id);
+        app.push_line(
+            LogKind::Status,
+            format!(
+                "{} changed on disk and the edited lines were not found; no merge offered.",
+                edit.file_path
+            ),
+        );
+        return;
+    };
+    let current = text[range].to_string();
+    let merge = three_way_merge(&edit.old_string, &edit.new_string, &current);
+    let conflicts = merge.undecided();
+    let replaced = app.edit_conflict_panel.replace(EditConflictPanelState {
+        file_path: edit.file_path.clone(),
+        current,
+        expected_hash,
+        tool_call_id: tool_call_id.to_string(),
+        merge,
+        selected: 0,
+    });
+    if let Some(replaced) = replaced {
+        app.edit_calls.remove(&replaced.tool_call_id);
+    }
+    app.push_line(
+        LogKind::Status,
+        format!(
+            "{} changed on disk while the agent edited it; merge panel open ({conflicts} conflicting hunk{}).",
+            edit.file_path,
+            if conflicts == 1 { "" } else { "s" }
+        ),
+    );
+}
+
+/// Writes the merged region with an `edit` `tool.call` guarded by the hash of the file the
+/// merge was built against, so the permission policy and confirm apply as for an agent edit
+/// and a file that changed again is refused instead of overwritten.
+fn apply_resolution(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) {
+    let Some(panel) = app.edit_conflict_panel.as_ref() else {
+        return;
+    };
+    let Some(merged) = panel.merge.merged_text() else {
+        let undecided = panel.merge.undecided();
+        app.push_line(
+            LogKind::Status,
+            format!(
+                "{undecided} hunk{} still need a choice (a agent, u user, b both, o original).",
+                if undecided == 1 { "" } else { "s" }
+            ),
+        );
+        return;
+    };
+    if merged != panel.current {
+        if !app.runtime_info.supports_tool_call_permission {
+            app.push_line(
+                LogKind::Status,
+                "Runtime cannot check permissions for tool.call; apply the merge manually",
+            );
+            return;
+        }
+        if app.rpc_pending.file_save_id.is_some() {
+            app.push_line(LogKind::Status, "A file save is already pending");
+            return;
+        }
+    }
+    let Some(panel) = app.edit_conflict_panel.take() else {
+        return;
+    };
+    app.edit_calls.remove(&panel.tool_call_id);
+    if merged == panel.current {
+        app.push_line(
+            LogKind::Status,
+            format!("Kept {} as it is on disk.", panel.file_path),
+        );
+        return;
+    }
+    let arguments = json!({
+        "file_path": panel.file_path,
+        "old_string": panel.current,
+        "new_string": merged,
+        "expected_hash": panel.expected_hash,
+    });
+    let id = next_id();
+    app.rpc_pending.file_save_id = Some(id.clone());
+    app.rpc_pending.file_save_path = Some(panel.file_path);
+    if let Err(error) = send_permissioned_tool_call(child_stdin, &id, "edit", arguments) {
+        app.rpc_pending.file_save_id = None;
+        app.rpc_pending.file_save_path = None;
+        app.push_error_report("send error", error.to_string());
+    }
+}
+
+pub(crate) fn handle_edit_conflict_key(
+    app: &mut AppState,
+    key: KeyCode,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) -> Option<bool> {
+    let panel = app.edit_conflict_panel.as_mut()?;
+    let choice = match key {
+        KeyCode::Esc => {
+            let path = panel.file_path.clone();
+            let tool_call_id = panel.tool_call_id.clone();
+            app.edit_conflict_panel = None;
+            app.edit_calls.remove(&tool_call_id);
+            app.push_line(LogKind::Status, format!("Discarded the merge for {path}."));
+            return Some(true);
+        }
+        KeyCode::Up => {
+            panel.selected = panel.selected.saturating_sub(1);
+            return Some(true);
+        }
+        KeyCode::Down => {
+            if panel.selected + 1 < panel.merge.hunks.len() {
+                panel.selected += 1;
+            }
+            return Some(true);
+        }
+        KeyCode::Enter => {
+            apply_resolution(app, child_stdin, next_id);
+            return Some(true);
+        }
+        KeyCode::Char('a') => MergeChoice::Agent,
+        KeyCode::Char('u') => MergeChoice::User,
+        KeyCode::Char('b') => MergeChoice::Both,
+        KeyCode::Char('o') => MergeChoice::Base,
+        _ => return Some(false),
+    };
+    if let Some(hunk) = panel.merge.hunks.get_mut(panel.selected) {
+        hunk.choice = Some(choice);
+        if panel.selected + 1 < panel.merge.hunks.len() {
+            panel.selected += 1;
+        }
+    }
+    Some(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        handle_edit_conflict_key, handle_edit_merge_read_response, offer_edit_merge,
+        parse_read_output, remember_edit_call,
+    };
+    use crate::app::runtime::{EditCallArgs, RpcResponse, RuntimeStdin};
+    use crate::app::util::audit::sha256_hex;
+    use crate::app::{AppState, PendingRpcMatch};
+    use crossterm::event::KeyCode;
+    use serde_json::json;
+    use std::process::Stdio;
+
+    fn with_runtime_writer<T>(f: impl FnOnce(&mut RuntimeStdin) -> T) -> T {
+        #[cfg(windows)]
+        let mut command = {
+            let mut command = std::process::Command::new("cmd");
+            command.args(["/C", "more"]);
+            command
+        };
+        #[cfg(not(windows))]
+        let mut command = std::process::Command::new("cat");
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+            .expect("spawn runtime writer");
+        let mut writer = RuntimeStdin::new(child.stdin.take().expect("child stdin"));
+        let out = f(&mut writer);
+        drop(writer);
+        let _ = child.kill();
+        let _ = child.wait();
+        out
+    }
+
+    fn read_output(text: &str) -> String {
+        let rows = text
+            .split('\n')
+            .enumerate()
+            .map(|(index, line)| format!("{:>5}  {line}", index + 1))
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!(
+            "{rows}\n\n[read_metadata] content_sha256={}",
+            sha256_hex(text.as_bytes())
+        )
+    }
+
+    #[test]
+    fn read_output_is_rebuilt_only_when_complete() {
+        let text = "head\n\n  indented\ntail\n";
+        assert_eq!(
+            parse_read_output(&read_output(text)),
+            Some((text.to_string(), sha256_hex(text.as_bytes())))
+        );
+        let truncated = read_output("a\nb").replace(
+            "\n\n[read_metadata]",
+            "\n\nOutput has more lines. Use offset to read beyond line 2.\n\n[read_metadata]",
+        );
+        assert_eq!(parse_read_output(&truncated), None);
+        let crlf = read_output("a\nb").replace(&sha256_hex(b"a\nb"), &sha256_hex(b"a\r\nb"));
+        assert_eq!(parse_read_output(&crlf), None);
+        assert_eq!(parse_read_output("File not found: lib.rs"), None);
+    }
+
+    #[test]
+    fn stale_edit_reads_the_file_through_the_runtime_and_resolution_is_sent_as_a_guarded_edit() {
+        let on_disk = "head\nfn a() {\n    user();\n}\ntail\n";
+        let mut app = AppState::default();
+        app.runtime_info.supports_tool_call_permission = true;
+        let edit = EditCallArgs {
+            file_path: "lib.rs".to_string(),
+            old_string: "fn a() {\n    old();\n}\n".to_string(),
+            new_string: "fn a() {\n    agent();\n}\n".to_string(),
+        };
+
+        with_runtime_writer(|writer| {
+            let mut seq = 0_u64;
+            let mut next_id = || {
+                seq += 1;
+                seq.to_string()
+            };
+            remember_edit_call(&mut app, "edit-1", edit.clone());
+            assert!(offer_edit_merge(&mut app, writer, &mut next_id, "edit-1", false).is_none());
+            assert!(app.edit_calls.is_empty());
+
+            remember_edit_call(&mut app, "edit-2", edit);
+            assert!(offer_edit_merge(&mut app, writer, &mut next_id, "edit-2", true).is_none());
+            let sent = writer.take_sent_request("1").expect("read tool.call");
+            assert_eq!(sent.params["name"], "read");
+            assert_eq!(sent.params["arguments"], json!({ "file_path": "lib.rs" }));
+            let Some(PendingRpcMatch::EditMergeRead { tool_call_id }) =
+                app.rpc_pending.take_match_for_response("1")
+            else {
+                panic!("edit merge read is pending");
+            };
+            handle_edit_merge_read_response(
+                &mut app,
+                &tool_call_id,
+                RpcResponse {
+                    id: "1".to_string(),
+                    result: Some(json!({ "ok": true, "result": read_output(on_disk) })),
+                    error: None,
+                },
+            );
+            let status = app.log.last().expect("status line").plain_text();
+            assert!(status.contains("1 conflicting hunk"));
+            assert_eq!(
+                app.edit_conflict_panel.as_ref().expect("panel").current,
+                "fn a() {\n    user();\n}\n"
+            );
+            assert!(app.edit_calls.contains_key("edit-2"));
+
+            handle_edit_conflict_key(&mut app, KeyCode::Enter, writer, &mut next_id);
+            assert!(app.edit_conflict_panel.is_some());
+            handle_edit_conflict_key(&mut app, KeyCode::Char('b'), writer, &mut next_id);
+            handle_edit_conflict_key(&mut app, KeyCode::Enter, writer, &mut next_id);
+            assert!(app.edit_conflict_panel.is_none());
+            assert!(app.edit_calls.is_empty());
+            assert_eq!(app.rpc_pending.file_save_id.as_deref(), Some("2"));
+            let sent = writer.take_sent_request("2").expect("tool.call");
+            assert_eq!(sent.method, "tool.call");
+            assert_eq!(sent.params["name"], "edit");
+            assert_eq!(
+                sent.params["arguments"],
+                json!({
+                    "file_path": "lib.rs",
+                    "old_string": "fn a() {\n    user();\n}\n",
+                    "new_string": "fn a() {\n    agent();\n    user();\n}\n",
+                    "expected_hash": sha256_hex(on_disk.as_bytes()),
+                })
+            );
+        });
+    }
+
+    #[test]
+    fn stale_edit_without_a_full_read_offers_no_merge() {
+        let mut app = AppState::default();
+        remember_edit_call(
+            &mut app,
+            "edit-1",
+            EditCallArgs {
+                file_path: "lib.rs".to_string(),
+                old_string: "a\n".to_string(),
+                new_string: "b\n".to_string(),
+            },
+        );
+        handle_edit_merge_read_response(
+            &mut app,
+            "edit-1",
+            RpcResponse {
+                id: "1".to_string(),
+                result: Some(json!({ "ok": true, "result": "File not found: lib.rs" })),
+                error: None,
+            },
+        );
+        assert!(app.edit_conflict_panel.is_none());
+        assert!(app.edit_calls.is_empty());
+        let status = app.log.last().expect("status line").plain_text();
+        assert!(status.contains("could not be read back in full"));
+    }
+}
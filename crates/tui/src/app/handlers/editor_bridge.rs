@@ -0,0 +1,80 @@
+use crate::app::handlers::command::start_prompt_run;
+use crate::app::handlers::links::preview_file;
+use crate::app::runtime::{send_ui_context_update, RuntimeStdin};
+use crate::app::state::LogKind;
+use crate::app::util::editor_bridge::{
+    editor_prompt_text, EditorBridge, EditorCall, EditorRequest,
+};
+use crate::app::AppState;
+use codelia_protocol::UiContextUpdate;
+use serde_json::json;
+
+/// Keeps the editor's file/selection and pushes it to the runtime, where `context.inspect`
+/// shows it as `ui_context`.
+fn update_editor_context(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    context: UiContextUpdate,
+) -> Result<(), String> {
+    send_ui_context_update(child_stdin, &context).map_err(|error| error.to_string())?;
+    app.editor_context = Some(context);
+    Ok(())
+}
+
+fn has_location(context: &UiContextUpdate) -> bool {
+    context.active_file.is_some() || context.selection.is_some()
+}
+
+/// Answers editor requests that arrived since the last tick, against the active tab.
+pub(crate) fn apply_editor_requests(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+    bridge: &EditorBridge,
+) -> bool {
+    let requests = bridge.drain();
+    if requests.is_empty() {
+        return false;
+    }
+    for EditorRequest { call, reply } in requests {
+        let answer = match call {
+            EditorCall::Context(context) => {
+                update_editor_context(app, child_stdin, context).map(|()| json!({ "ok": true }))
+            }
+            EditorCall::Prompt { text, context } => {
+                let sent = if has_location(&context) {
+                    update_editor_context(app, child_stdin, context)
+                } else {
+                    Ok(())
+                };
+                sent.map(|()| {
+                    let context = app.editor_context.clone().unwrap_or_default();
+                    let prompt = editor_prompt_text(&text, &context);
+                    let queued = start_prompt_run(app, child_stdin, next_id, &prompt);
+                    json!({ "queued": queued })
+                })
+            }
+            EditorCall::LastAnswer => Ok(json!({
+                "text": app.last_assistant_text,
+                "running": app.runtime_info.active_run_id.is_some(),
+            })),
+            EditorCall::Open { path, line } => {
+                preview_file(app, &path, line);
+                Ok(json!({ "ok": true }))
+            }
+        };
+        reply.send(answer);
+    }
+    true
+}
+
+/// Logged once the socket is up, so the path can be copied into the editor plugin.
+pub(crate) fn announce_editor_bridge(app: &mut AppState, bridge: &Result<EditorBridge, String>) {
+    match bridge {
+        Ok(bridge) => app.push_line(
+            LogKind::Status,
+            format!("Editor socket: {}", bridge.path().display()),
+        ),
+        Err(error) => app.push_error_report("editor socket unavailable", error.clone()),
+    }
+}
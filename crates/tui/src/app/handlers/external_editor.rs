@@ -0,0 +1,61 @@
+use crate::app::state::LogKind;
+use crate::app::AppState;
+
+/// `Ctrl+X Ctrl+E` / `/edit`: the run loop opens the editor before the next frame, since it
+/// owns the terminal.
+pub(crate) fn request_external_edit(app: &mut AppState) {
+    if app.prompt_dialog.is_some() || app.confirm_dialog.is_some() {
+        app.push_line(
+            LogKind::Status,
+            "Close the dialog before opening the editor",
+        );
+        return;
+    }
+    app.external_edit_requested = true;
+}
+
+/// Loads the saved text into the composer as one undo step; a failed or aborted edit keeps it.
+pub(crate) fn apply_external_edit(
+    app: &mut AppState,
+    editor: &str,
+    result: Result<String, String>,
+) {
+    match result {
+        Ok(text) if text == app.input.current() => {
+            app.push_line(LogKind::Status, format!("{editor}: no changes"));
+        }
+        Ok(text) => {
+            app.input.set_from(&text);
+        }
+        Err(error) => app.push_line(
+            LogKind::Error,
+            format!("External editor failed, composer unchanged: {error}"),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_external_edit, request_external_edit};
+    use crate::app::AppState;
+
+    #[test]
+    fn edited_text_replaces_the_composer_and_undoes_in_one_step() {
+        let mut app = AppState::default();
+        app.input.set_from("draft");
+        request_external_edit(&mut app);
+        assert!(app.external_edit_requested);
+
+        apply_external_edit(&mut app, "vi", Ok("line one\nline two".to_string()));
+        assert_eq!(app.input.current(), "line one\nline two");
+        assert!(app.input.undo());
+        assert_eq!(app.input.current(), "draft");
+
+        apply_external_edit(&mut app, "vi", Err("vi exited with 1".to_string()));
+        assert_eq!(app.input.current(), "draft");
+        assert_eq!(
+            app.log.last().expect("error").plain_text(),
+            "External editor failed, composer unchanged: vi exited with 1"
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use crate::app::runtime::{send_permissioned_tool_call, RpcResponse, RuntimeStdin};
+use crate::app::state::{detect_file_artifacts, FileArtifact, LogKind, LogLine, LogSpan, LogTone};
+use crate::app::{AppState, PickDialogItem, PickDialogState};
+use crossterm::event::KeyCode;
+use serde_json::{json, Value};
+
+pub(crate) const FILE_ARTIFACT_PICK_ID: &str = "file-artifact-save";
+
+fn save_hint(prefix: &str, artifact: &FileArtifact, suffix: &str) -> LogLine {
+    LogLine::new_with_spans(vec![
+        LogSpan::new(LogKind::Status, LogTone::Detail, format!("  {prefix}[")),
+        LogSpan::new(LogKind::Status, LogTone::Summary, "s"),
+        LogSpan::new(
+            LogKind::Status,
+            LogTone::Detail,
+            format!(
+                "]ave as {} ({} lines){suffix}",
+                artifact.path,
+                artifact.line_count()
+            ),
+        ),
+    ])
+}
+
+/// After a final answer: remembers the complete files in it for Alt+S and returns the
+/// `[s]ave as` hints to show under the answer.
+pub(crate) fn offer_file_artifacts(app: &mut AppState, answer: &str) -> Vec<LogLine> {
+    app.file_artifacts = detect_file_artifacts(answer);
+    match app.file_artifacts.as_slice() {
+        [] => Vec::new(),
+        [artifact] => vec![save_hint("", artifact, " with Alt+S")],
+        artifacts => artifacts
+            .iter()
+            .enumerate()
+            .map(|(index, artifact)| {
+                let suffix = if index == 0 { " · Alt+S to pick" } else { "" };
+                save_hint(&format!("{} ", index + 1), artifact, suffix)
+            })
+            .collect(),
+    }
+}
+
+/// Alt+S: saves the single offered file, or opens a numbered pick when there are several.
+pub(crate) fn open_file_artifact_save(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) -> bool {
+    if app.file_artifacts.is_empty() {
+        app.push_line(LogKind::Status, "No generated files to save");
+        return true;
+    }
+    if app.file_artifacts.len() == 1 {
+        save_file_artifact(app, 0, child_stdin, next_id);
+        return true;
+    }
+    let items = app
+        .file_artifacts
+        .iter()
+        .enumerate()
+        .map(|(index, artifact)| PickDialogItem {
+            id: index.to_string(),
+            label: format!("{} {}", index + 1, artifact.path),
+            detail: Some(format!("{} lines", artifact.line_count())),
+        })
+        .collect::<Vec<_>>();
+    app.pick_dialog = Some(PickDialogState {
+        id: FILE_ARTIFACT_PICK_ID.to_string(),
+        title: "Save file (1-9 / Enter, Esc close)".to_string(),
+        message: None,
+        chosen: vec![false; items.len()],
+        items,
+        selected: 0,
+        multi: false,
+    });
+    true
+}
+
+/// Keys for the save pick; digits save immediately instead of only moving the selection.
+pub(crate) fn handle_file_artifact_key(
+    app: &mut AppState,
+    key: KeyCode,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) -> bool {
+    let Some(pick) = app.pick_dialog.as_mut() else {
+        return false;
+    };
+    let chosen = match key {
+        KeyCode::Esc => None,
+        KeyCode::Up => {
+            pick.selected = pick.selected.saturating_sub(1);
+            return true;
+        }
+        KeyCode::Down => {
+            if pick.selected + 1 < pick.items.len() {
+                pick.selected += 1;
+            }
+            return true;
+        }
+        KeyCode::Enter => Some(pick.selected),
+        KeyCode::Char(ch) if ch.is_ascii_digit() => {
+            let index = ch.to_digit(10).unwrap_or(0) as usize;
+            if index == 0 || index > pick.items.len() {
+                return true;
+            }
+            Some(index - 1)
+        }
+        _ => return false,
+    };
+    app.pick_dialog = None;
+    if let Some(index) = chosen {
+        save_file_artifact(app, index, child_stdin, next_id);
+    }
+    true
+}
+
+/// Writes the file through the runtime's `write` tool, so the permission policy and confirm
+/// dialog apply as for an agent edit.
+fn save_file_artifact(
+    app: &mut AppState,
+    index: usize,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) {
+    let Some(artifact) = app.file_artifacts.get(index).cloned() else {
+        return;
+    };
+    if !app.runtime_info.supports_tool_call_permission {
+        app.push_line(
+            LogKind::Status,
+            "Runtime cannot check permissions for tool.call; copy the file manually",
+        );
+        return;
+    }
+    if app.rpc_pending.file_save_id.is_some() {
+        app.push_line(LogKind::Status, "A file save is already pending");
+        return;
+    }
+    let id = next_id();
+    app.rpc_pending.file_save_id = Some(id.clone());
+    app.rpc_pending.file_save_path = Some(artifact.path.clone());
+    let arguments = json!({ "file_path": artifact.path, "content": artifact.content });
+    if let Err(error) = send_permissioned_tool_call(child_stdin, &id, "write", arguments) {
+        app.rpc_pending.file_save_id = None;
+        app.rpc_pending.file_save_path = None;
+        app.push_error_report("send error", error.to_string());
+    }
+}
+
+pub(crate) fn handle_file_save_response(app: &mut AppState, path: &str, response: RpcResponse) {
+    if let Some(error) = response.error {
+        app.push_error_report("save failed", format!("{path}: {error}"));
+        return;
+    }
+    let result = response.result.unwrap_or(Value::Null);
+    if result.get("ok").and_then(Value::as_bool) == Some(true) {
+        app.push_line(LogKind::Status, format!("Saved {path}"));
+        return;
+    }
+    let reason = result
+        .get("result")
+        .and_then(Value::as_str)
+        .unwrap_or("tool.call failed");
+    app.push_line(LogKind::Status, format!("Not saved {path}: {reason}"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{handle_file_save_response, offer_file_artifacts};
+    use crate::app::runtime::RpcResponse;
+    use crate::app::AppState;
+    use serde_json::json;
+
+    #[test]
+    fn answer_files_are_listed_and_denied_saves_report_the_reason() {
+        let mut app = AppState::default();
+        assert!(offer_file_artifacts(&mut app, "No files here.").is_empty());
+        assert!(app.file_artifacts.is_empty());
+
+        let hints = offer_file_artifacts(
+            &mut app,
+            "`a.rs`:\n```rust\nfn a() {}\n```\n\n`b.rs`:\n```rust\nfn b() {}\n```\n",
+        );
+        assert_eq!(app.file_artifacts.len(), 2);
+        let hints = hints
+            .iter()
+            .map(|line| line.plain_text())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            hints,
+            vec![
+                "  1 [s]ave as a.rs (1 lines) · Alt+S to pick",
+                "  2 [s]ave as b.rs (1 lines)",
+            ]
+        );
+
+        let denied = RpcResponse {
+            id: "1".to_string(),
+            result: Some(json!({ "ok": false, "result": "permission denied" })),
+            error: None,
+        };
+        handle_file_save_response(&mut app, "a.rs", denied);
+        assert_eq!(
+            app.log.last().expect("status").plain_text(),
+            "Not saved a.rs: permission denied"
+        );
+    }
+}
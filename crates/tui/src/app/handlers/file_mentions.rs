@@ -0,0 +1,206 @@
+use crate::app::state::{active_dir_mention_token, active_file_mention_token, LogKind};
+use crate::app::util::dir_tree::{read_dir_mention, DIR_MENTION_PREFIX};
+use crate::app::util::file_mentions::{
+    fuzzy_file_matches, mention_word, read_file_mention, MentionMatches,
+};
+use crate::app::AppState;
+use crate::app::PendingFileAttachment;
+
+/// Candidates listed (and selectable) under an `@` word.
+pub(crate) const MENTION_CANDIDATES: usize = 6;
+
+/// The `@` word being typed: `(true, query)` for `@dir:query`, `(false, query)` for `@query`.
+fn active_mention(current: &str) -> Option<(bool, &str)> {
+    active_dir_mention_token(current)
+        .map(|query| (true, query))
+        .or_else(|| active_file_mention_token(current).map(|query| (false, query)))
+}
+
+/// Takes a finished background index build, starts one the first time an `@` word is typed,
+/// and rescores `mention_matches` only when the query (or the index) changed. True when the
+/// suggestions changed.
+pub(crate) fn refresh_mention_matches(app: &mut AppState) -> bool {
+    let mut changed = false;
+    if let Some(index) = app.workspace_index_load.poll() {
+        app.workspace_index = Some(index);
+        changed = app.mention_matches.take().is_some();
+    }
+    let current = app.input.current();
+    let Some((dir, query)) = active_mention(&current) else {
+        return app.mention_matches.take().is_some() || changed;
+    };
+    if app
+        .mention_matches
+        .as_ref()
+        .is_some_and(|matches| matches.dir == dir && matches.query == query)
+    {
+        return changed;
+    }
+    let Some(index) = app.workspace_index.as_ref() else {
+        if !app.workspace_index_load.is_running() {
+            app.workspace_index_load.spawn();
+        }
+        return app.mention_matches.take().is_some() || changed;
+    };
+    let candidates = if dir { &index.dirs } else { &index.files };
+    let paths = fuzzy_file_matches(query, candidates, MENTION_CANDIDATES)
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    app.mention_matches = Some(MentionMatches {
+        dir,
+        query: query.to_string(),
+        paths,
+        selected: 0,
+    });
+    true
+}
+
+/// Up/Down under an `@` word: moves the candidate Tab attaches, wrapping at either end. False
+/// when there is no candidate list, so the key keeps its composer meaning.
+pub(crate) fn move_mention_selection(app: &mut AppState, down: bool) -> bool {
+    let Some(matches) = app
+        .mention_matches
+        .as_mut()
+        .filter(|matches| !matches.paths.is_empty())
+    else {
+        return false;
+    };
+    let count = matches.paths.len();
+    matches.selected = if down {
+        (matches.selected + 1) % count
+    } else {
+        (matches.selected + count - 1) % count
+    };
+    true
+}
+
+fn attach_mention(
+    app: &mut AppState,
+    current: &str,
+    token_start: usize,
+    attached: Result<PendingFileAttachment, String>,
+) {
+    let file = match attached {
+        Ok(file) => file,
+        Err(error) => {
+            app.push_line(LogKind::Error, format!("Cannot attach {error}"));
+            return;
+        }
+    };
+    app.input.set_from(&format!(
+        "{}{} ",
+        &current[..token_start],
+        mention_word(&file.path)
+    ));
+    app.pending_file_attachments
+        .retain(|attached| attached.path != file.path);
+    app.pending_file_attachments.push(file);
+}
+
+/// Tab on an `@query` or `@dir:query` word: replaces it with the selected candidate (the best
+/// match unless Up/Down moved it) and attaches that file or directory tree.
+pub(crate) fn complete_file_mention(app: &mut AppState) -> bool {
+    refresh_mention_matches(app);
+    let Some(matches) = app.mention_matches.as_ref() else {
+        return false;
+    };
+    let Some(path) = matches.paths.get(matches.selected).cloned() else {
+        return false;
+    };
+    let dir = matches.dir;
+    let current = app.input.current();
+    let Some((_, query)) = active_mention(&current) else {
+        return false;
+    };
+    let token_start = if dir {
+        current.len() - query.len() - DIR_MENTION_PREFIX.len() - 1
+    } else {
+        current.len() - query.len() - 1
+    };
+    let attached = if dir {
+        let Some(index) = app.workspace_index.as_ref() else {
+            return false;
+        };
+        read_dir_mention(&path, &index.tree)
+    } else {
+        read_file_mention(&path)
+    };
+    attach_mention(app, &current, token_start, attached);
+    app.mention_matches = None;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{complete_file_mention, move_mention_selection, refresh_mention_matches};
+    use crate::app::util::file_mentions::WorkspaceIndex;
+    use crate::app::AppState;
+
+    #[test]
+    fn tab_replaces_the_query_with_the_best_match_and_attaches_it() {
+        let dir = std::env::temp_dir().join(format!("codelia-tab-mention-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("dir");
+        let path = dir.join("answer.md").display().to_string();
+        std::fs::write(&path, "42\n").expect("write");
+
+        let mut app = AppState {
+            workspace_index: Some(WorkspaceIndex::from_files(vec![
+                "other.txt".to_string(),
+                path.clone(),
+            ])),
+            ..AppState::default()
+        };
+        app.input.set_from("explain @answ");
+        assert!(complete_file_mention(&mut app));
+        assert_eq!(app.input.current(), format!("explain @{path} "));
+        assert_eq!(app.pending_file_attachments.len(), 1);
+        assert_eq!(app.pending_file_attachments[0].content, "42\n");
+        assert_eq!(
+            app.referenced_file_attachments(&app.input.current()).len(),
+            1
+        );
+
+        app.input.set_from("explain @zzz");
+        assert!(!complete_file_mention(&mut app));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn up_down_pick_a_candidate_and_spaced_paths_are_quoted() {
+        let dir = std::env::temp_dir().join(format!("codelia-pick-mention-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("dir");
+        let first = dir.join("notes.md").display().to_string();
+        let second = dir.join("release notes.md").display().to_string();
+        std::fs::write(&first, "first\n").expect("write");
+        std::fs::write(&second, "second\n").expect("write");
+
+        let mut app = AppState {
+            workspace_index: Some(WorkspaceIndex::from_files(vec![
+                first.clone(),
+                second.clone(),
+            ])),
+            ..AppState::default()
+        };
+        app.input.set_from("see @notes");
+        assert!(refresh_mention_matches(&mut app));
+        let matches = app.mention_matches.as_ref().expect("matches");
+        assert_eq!(matches.paths, vec![first.clone(), second.clone()]);
+
+        assert!(move_mention_selection(&mut app, true));
+        // An unchanged query keeps the scored list and the selection.
+        assert!(!refresh_mention_matches(&mut app));
+        assert_eq!(app.mention_matches.as_ref().expect("matches").selected, 1);
+
+        assert!(complete_file_mention(&mut app));
+        assert_eq!(app.input.current(), format!("see @\"{second}\" "));
+        assert_eq!(app.pending_file_attachments[0].content, "second\n");
+        assert_eq!(
+            app.referenced_file_attachments(&app.input.current()).len(),
+            1
+        );
+        assert!(!refresh_mention_matches(&mut app));
+        assert!(!move_mention_selection(&mut app, true));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,230 @@
+use crate::app::state::{LogKind, LogLine, LogTone};
+use crate::app::util::forge::ci::{CiFetchResult, CiStatus, CiSummary, CI_POLL_INTERVAL};
+use crate::app::util::forge::{
+    extract_forge_refs, format_forge_ref_line, parse_issue_argument, ForgeRepo,
+};
+use crate::app::AppState;
+use std::time::Instant;
+
+/// Starts background lookups for issue/PR references in prompt or assistant text; opt-in with
+/// `forge_refs = on`, since the text (and any link in it) is not the user's choice of target.
+pub(crate) fn request_forge_references(app: &mut AppState, text: &str) {
+    if !app.settings.forge_refs || (!text.contains('#') && !text.contains("://")) {
+        return;
+    }
+    let config = app.forge_refs.config();
+    if !config.has_any_token() {
+        return;
+    }
+    let refs = extract_forge_refs(text, config.default_repo.as_ref());
+    for reference in refs {
+        if !app.forge_refs.requested.insert(reference.key()) {
+            continue;
+        }
+        app.forge_refs.spawn_fetch(reference, false);
+    }
+}
+
+pub(crate) fn request_issue_attachment(app: &mut AppState, argument: &str) {
+    let config = app.forge_refs.config();
+    if !config.has_any_token() {
+        app.push_line(
+            LogKind::Error,
+            "forge token not configured (set GITHUB_TOKEN/GH_TOKEN or GITLAB_TOKEN)",
+        );
+        return;
+    }
+    let Some(reference) = parse_issue_argument(argument, config.default_repo.as_ref()) else {
+        app.push_line(
+            LogKind::Error,
+            format!("cannot resolve issue reference: {argument} (no forge remote for `#N`?)"),
+        );
+        return;
+    };
+    let label = reference.label();
+    if !config.trusts_host(&reference.repo.host) {
+        app.push_line(
+            LogKind::Error,
+            format!(
+                "not sending a forge token to {}: only github.com, gitlab.com and the origin remote's host are trusted",
+                reference.repo.host
+            ),
+        );
+        return;
+    }
+    if app.forge_refs.spawn_fetch(reference, true) {
+        app.push_line(LogKind::Status, format!("Fetching {label}..."));
+    } else {
+        app.push_line(
+            LogKind::Error,
+            format!("forge token not configured for {label}"),
+        );
+    }
+}
+
+/// Applies finished lookups; returns true when the log changed.
+pub(crate) fn apply_forge_results(app: &mut AppState) -> bool {
+    let results = app.forge_refs.drain_results();
+    if results.is_empty() {
+        return false;
+    }
+    for fetched in results {
+        match fetched.result {
+            Ok(info) => {
+                app.extend_lines(vec![LogLine::new_with_tone(
+                    LogKind::Status,
+                    LogTone::Detail,
+                    format_forge_ref_line(&info),
+                )]);
+                if fetched.attach {
+                    app.push_line(
+                        LogKind::Status,
+                        format!("Attached {} as context for the next prompt", info.label),
+                    );
+                    app.forge_refs
+                        .pending_contexts
+                        .retain(|item| item.key != info.key);
+                    app.forge_refs.pending_contexts.push(info);
+                }
+            }
+            Err(error) => {
+                // Passive annotations fail quietly; only explicit `/issue` surfaces errors.
+                app.forge_refs.requested.remove(&fetched.reference.key());
+                if fetched.attach {
+                    app.push_error_report(
+                        format!("issue fetch failed: {}", fetched.reference.label()),
+                        error,
+                    );
+                }
+            }
+        }
+    }
+    true
+}
+
+fn ci_target(app: &mut AppState) -> Result<(ForgeRepo, String), String> {
+    let config = app.forge_refs.config();
+    let repo = config
+        .default_repo
+        .clone()
+        .ok_or_else(|| "no GitHub/GitLab remote (set CODELIA_FORGE_REPO)".to_string())?;
+    let token = config.token_for(&repo).map(str::to_string).ok_or_else(|| {
+        "forge token not configured (set GITHUB_TOKEN/GH_TOKEN or GITLAB_TOKEN)".to_string()
+    })?;
+    Ok((repo, token))
+}
+
+pub(crate) fn request_ci_status(app: &mut AppState, announce: bool) -> bool {
+    match ci_target(app) {
+        Ok((repo, token)) => {
+            app.ci_watch.spawn_summary_fetch(repo, token, announce);
+            app.ci_watch.next_poll_at = Some(Instant::now() + CI_POLL_INTERVAL);
+            true
+        }
+        Err(error) => {
+            app.push_line(LogKind::Error, format!("ci: {error}"));
+            false
+        }
+    }
+}
+
+pub(crate) fn request_ci_failure_logs(app: &mut AppState) {
+    let failed = app
+        .ci_watch
+        .summary
+        .as_ref()
+        .map(|summary| summary.failed_checks().cloned().collect::<Vec<_>>())
+        .unwrap_or_default();
+    if failed.is_empty() {
+        app.push_line(
+            LogKind::Status,
+            "No failing CI checks to attach (run /ci first).",
+        );
+        return;
+    }
+    match ci_target(app) {
+        Ok((repo, token)) => {
+            app.push_line(
+                LogKind::Status,
+                format!("Fetching logs for {} failing check(s)...", failed.len()),
+            );
+            app.ci_watch.spawn_log_fetch(repo, token, failed);
+        }
+        Err(error) => app.push_line(LogKind::Error, format!("ci: {error}")),
+    }
+}
+
+fn push_ci_summary_lines(app: &mut AppState, summary: &CiSummary) {
+    app.push_line(
+        LogKind::Status,
+        format!("CI ({}): {}", summary.branch, summary.status_segment()),
+    );
+    let lines = summary
+        .checks
+        .iter()
+        .map(|check| {
+            let mut text = format!(
+                "  {} {} ({})",
+                check.status.icon(),
+                check.name,
+                check.status.label()
+            );
+            if check.status == CiStatus::Failed {
+                if let Some(url) = check.url.as_deref() {
+                    text.push_str(&format!(" — {url}"));
+                }
+            }
+            LogLine::new_with_tone(LogKind::Status, LogTone::Detail, text)
+        })
+        .collect::<Vec<_>>();
+    app.extend_lines(lines);
+}
+
+/// Polls in watch mode and applies finished CI fetches; returns true when the UI changed.
+pub(crate) fn tick_ci_watch(app: &mut AppState) -> bool {
+    let mut changed = false;
+    if app.ci_watch.poll_due(Instant::now()) {
+        if let Ok((repo, token)) = ci_target(app) {
+            app.ci_watch.spawn_summary_fetch(repo, token, false);
+        }
+        app.ci_watch.next_poll_at = Some(Instant::now() + CI_POLL_INTERVAL);
+    }
+    for fetched in app.ci_watch.drain_results() {
+        changed = true;
+        match fetched {
+            CiFetchResult::Summary { result, announce } => match result {
+                Ok(summary) => {
+                    let previous = app.ci_watch.summary.as_ref().and_then(CiSummary::overall);
+                    // Watch mode only logs transitions; explicit `/ci` always prints the table.
+                    if announce || previous != summary.overall() {
+                        push_ci_summary_lines(app, &summary);
+                    }
+                    app.ci_watch.summary = Some(summary);
+                }
+                Err(error) => {
+                    if announce {
+                        app.push_error_report("ci status failed", error);
+                    }
+                }
+            },
+            CiFetchResult::Logs(logs) => {
+                for log in logs {
+                    match log {
+                        Ok(log) => {
+                            app.push_line(
+                                LogKind::Status,
+                                format!("Attached CI log for {} to the next prompt", log.name),
+                            );
+                            app.ci_watch
+                                .pending_logs
+                                .retain(|item| item.name != log.name);
+                            app.ci_watch.pending_logs.push(log);
+                        }
+                        Err(error) => app.push_error_report("ci log fetch failed", error),
+                    }
+                }
+            }
+        }
+    }
+    changed
+}
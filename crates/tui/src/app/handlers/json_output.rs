@@ -0,0 +1,181 @@
+use crate::app::state::{LogKind, LogLine, LogTone};
+use crate::app::util::json_output::{
+    extract_json_answer, folded_json_lines, save_json_output, validate_json, JsonOutputRequest,
+};
+use crate::app::{AppState, ContextPanelState};
+use std::path::Path;
+
+const JSON_ERROR_LIST_LIMIT: usize = 10;
+
+/// `/json <schema|description>`: attach a structured-output request to the next prompt.
+pub(crate) fn arm_json_output(app: &mut AppState, text: &str) {
+    match JsonOutputRequest::parse(text) {
+        Ok(request) => {
+            app.push_line(
+                LogKind::Status,
+                format!(
+                    "Next prompt asks for JSON matching {} (/json off to cancel)",
+                    request.label()
+                ),
+            );
+            app.json_output.armed = Some(request);
+        }
+        Err(error) => app.push_line(LogKind::Error, format!("/json: {error}")),
+    }
+}
+
+pub(crate) fn disarm_json_output(app: &mut AppState) {
+    if app.json_output.armed.take().is_some() {
+        app.push_line(LogKind::Status, "JSON output request cancelled");
+    } else {
+        app.push_line(LogKind::Status, "No JSON output request is pending");
+    }
+}
+
+fn push_detail(app: &mut AppState, text: String) {
+    app.extend_lines(vec![LogLine::new_with_tone(
+        LogKind::Status,
+        LogTone::Detail,
+        text,
+    )]);
+}
+
+/// Called on a terminal run status; checks the answer of a `/json` run.
+pub(crate) fn finish_json_output(app: &mut AppState, status: &str) {
+    let Some(request) = app.json_output.active.take() else {
+        return;
+    };
+    if status != "completed" {
+        return;
+    }
+    let Some(answer) = app.last_assistant_text.clone() else {
+        app.push_line(LogKind::Error, "JSON output: the run produced no answer");
+        return;
+    };
+    let value = match extract_json_answer(&answer) {
+        Ok(value) => value,
+        Err(error) => {
+            app.push_line(
+                LogKind::Error,
+                format!("JSON output: answer is not valid JSON ({error})"),
+            );
+            return;
+        }
+    };
+    match &request {
+        JsonOutputRequest::Schema(schema) => {
+            let errors = validate_json(&value, schema);
+            if errors.is_empty() {
+                app.push_line(LogKind::Status, "JSON output matches the schema:");
+            } else {
+                app.push_line(
+                    LogKind::Error,
+                    format!(
+                        "JSON output does not match the schema ({} problem(s)):",
+                        errors.len()
+                    ),
+                );
+                for error in errors.iter().take(JSON_ERROR_LIST_LIMIT) {
+                    push_detail(app, format!("  {error}"));
+                }
+                if errors.len() > JSON_ERROR_LIST_LIMIT {
+                    push_detail(
+                        app,
+                        format!("  ... {} more", errors.len() - JSON_ERROR_LIST_LIMIT),
+                    );
+                }
+            }
+        }
+        JsonOutputRequest::Description(_) => {
+            app.push_line(LogKind::Status, "JSON output parsed:");
+        }
+    }
+    app.extend_lines(
+        folded_json_lines(&value)
+            .into_iter()
+            .map(|line| LogLine::new_with_tone(LogKind::Status, LogTone::Detail, line))
+            .collect(),
+    );
+    app.push_line(
+        LogKind::Status,
+        "/json save [file] writes it, /json show opens it unfolded",
+    );
+    app.json_output.last = Some(value);
+}
+
+pub(crate) fn save_last_json_output(app: &mut AppState, target: Option<&str>) {
+    let Some(value) = app.json_output.last.as_ref() else {
+        app.push_line(
+            LogKind::Status,
+            "No JSON output yet; run a prompt after /json first",
+        );
+        return;
+    };
+    match save_json_output(value, target.map(Path::new)) {
+        Ok(path) => app.push_line(
+            LogKind::Status,
+            format!("JSON output saved to {}", path.display()),
+        ),
+        Err(error) => app.push_error_report("json save failed", error),
+    }
+}
+
+pub(crate) fn open_json_output_panel(app: &mut AppState) {
+    let Some(value) = app.json_output.last.as_ref() else {
+        app.push_line(
+            LogKind::Status,
+            "No JSON output yet; run a prompt after /json first",
+        );
+        return;
+    };
+    let rows = serde_json::to_string_pretty(value)
+        .unwrap_or_else(|_| value.to_string())
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    app.skills_list_panel = None;
+    app.theme_list_panel = None;
+    app.context_panel = Some(ContextPanelState {
+        title: format!("JSON output ({} lines)", rows.len()),
+        header: "/json save [file] · Esc close".to_string(),
+        rows,
+        selected: 0,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{arm_json_output, finish_json_output};
+    use crate::app::AppState;
+    use serde_json::json;
+
+    #[test]
+    fn completed_json_runs_are_validated_and_kept_for_saving() {
+        let mut app = AppState::default();
+        arm_json_output(
+            &mut app,
+            r#"{"type":"object","required":["files"],"properties":{"files":{"type":"array"}}}"#,
+        );
+        let request = app.json_output.armed.take().expect("armed");
+
+        app.json_output.active = Some(request.clone());
+        app.last_assistant_text = Some(r#"{"files": "README.md"}"#.to_string());
+        finish_json_output(&mut app, "completed");
+        assert!(app
+            .log
+            .iter()
+            .any(|line| line.plain_text() == "  $.files: expected array, got string"));
+        assert_eq!(app.json_output.last, Some(json!({"files": "README.md"})));
+
+        app.json_output.active = Some(request.clone());
+        app.last_assistant_text = Some("Sorry, I can't do that.".to_string());
+        finish_json_output(&mut app, "completed");
+        assert!(app.log.last().is_some_and(|line| line
+            .plain_text()
+            .starts_with("JSON output: answer is not valid JSON")));
+
+        app.json_output.active = Some(request);
+        finish_json_output(&mut app, "cancelled");
+        assert!(app.json_output.active.is_none());
+    }
+}
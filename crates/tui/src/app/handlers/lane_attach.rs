@@ -0,0 +1,197 @@
+use crate::app::runtime::{send_tool_call, RuntimeStdin};
+use crate::app::state::LogKind;
+use crate::app::{AppState, LaneAttachState};
+use crossterm::event::KeyCode;
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+
+/// Time between `lane_tail` polls while a lane is attached.
+const LANE_TAIL_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Pane lines fetched per poll; this is the scrollback the attach view can show.
+const LANE_TAIL_LINES: usize = 500;
+const PAGE_LINES: usize = 10;
+
+/// Replaces the lane list with the attach view; the first poll goes out on the next tick.
+pub(crate) fn open_lane_attach(app: &mut AppState, lane_id: &str) {
+    app.lane_list_panel = None;
+    app.panel_stack.clear();
+    app.lane_attach = Some(LaneAttachState {
+        lane_id: lane_id.to_string(),
+        lines: Vec::new(),
+        incoming: None,
+        state: "-".to_string(),
+        backend_alive: true,
+        follow: true,
+        scroll: 0,
+        last_poll_at: None,
+        error: None,
+    });
+}
+
+/// Sends the next `lane_tail` once the previous answer is in and the interval has passed.
+/// Polling stops when the lane's tmux session is gone; the last output stays on screen.
+pub(crate) fn poll_lane_attach_if_due(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+    now: Instant,
+) {
+    if app.rpc_pending.lane_tail_id.is_some() || app.runtime_reconnect.reconnecting() {
+        return;
+    }
+    let Some(attach) = app.lane_attach.as_mut() else {
+        return;
+    };
+    let due = attach
+        .last_poll_at
+        .is_none_or(|at| now.saturating_duration_since(at) >= LANE_TAIL_POLL_INTERVAL);
+    if !attach.backend_alive || !due {
+        return;
+    }
+    attach.last_poll_at = Some(now);
+    let request_id = next_id();
+    let args = json!({ "lane_id": attach.lane_id, "lines": LANE_TAIL_LINES });
+    match send_tool_call(child_stdin, &request_id, "lane_tail", args) {
+        Ok(()) => app.rpc_pending.lane_tail_id = Some(request_id),
+        Err(error) => attach.error = Some(error.to_string()),
+    }
+}
+
+/// Answers for a lane that was detached (or replaced) meanwhile are dropped.
+pub(crate) fn apply_lane_tail_result(app: &mut AppState, result: &Value) {
+    let Some(attach) = app.lane_attach.as_mut() else {
+        return;
+    };
+    let lane = result.get("lane").unwrap_or(result);
+    if lane.get("lane_id").and_then(Value::as_str) != Some(attach.lane_id.as_str()) {
+        return;
+    }
+    attach.error = None;
+    if let Some(state) = lane.get("state").and_then(Value::as_str) {
+        attach.state = state.to_string();
+    }
+    attach.backend_alive = result
+        .get("backend_alive")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let lines = result
+        .get("lines")
+        .and_then(Value::as_array)
+        .map(|lines| {
+            lines
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    // A finished lane has no pane left to capture; keep what was last seen.
+    if lines.is_empty() && !attach.backend_alive {
+        return;
+    }
+    if attach.follow {
+        attach.lines = lines;
+    } else if lines != attach.lines {
+        attach.incoming = Some(lines);
+    }
+}
+
+pub(crate) fn apply_lane_tail_error(app: &mut AppState, error: String) {
+    if let Some(attach) = app.lane_attach.as_mut() {
+        attach.error = Some(error);
+    }
+}
+
+fn set_follow(attach: &mut LaneAttachState, follow: bool) {
+    attach.follow = follow;
+    if follow {
+        attach.scroll = 0;
+        if let Some(lines) = attach.incoming.take() {
+            attach.lines = lines;
+        }
+    }
+}
+
+fn scroll_back(attach: &mut LaneAttachState, lines: usize) {
+    let max = attach.lines.len().saturating_sub(1);
+    attach.scroll = attach.scroll.saturating_add(lines).min(max);
+    if attach.scroll > 0 {
+        attach.follow = false;
+    }
+}
+
+fn scroll_forward(attach: &mut LaneAttachState, lines: usize) {
+    attach.scroll = attach.scroll.saturating_sub(lines);
+    if attach.scroll == 0 {
+        set_follow(attach, true);
+    }
+}
+
+pub(crate) fn handle_lane_attach_key(app: &mut AppState, key: KeyCode) -> Option<bool> {
+    let attach = app.lane_attach.as_mut()?;
+    match key {
+        KeyCode::Esc | KeyCode::Char('d') | KeyCode::Char('q') => {
+            let lane_id = attach.lane_id.clone();
+            app.lane_attach = None;
+            app.push_line(LogKind::Status, format!("Detached from lane {lane_id}"));
+        }
+        KeyCode::Up | KeyCode::Char('k') => scroll_back(attach, 1),
+        KeyCode::Down | KeyCode::Char('j') => scroll_forward(attach, 1),
+        KeyCode::PageUp => scroll_back(attach, PAGE_LINES),
+        KeyCode::PageDown => scroll_forward(attach, PAGE_LINES),
+        KeyCode::Home => scroll_back(attach, usize::MAX),
+        KeyCode::End => set_follow(attach, true),
+        KeyCode::Char('f') => {
+            let follow = !attach.follow;
+            set_follow(attach, follow);
+        }
+        _ => return Some(false),
+    }
+    Some(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_lane_tail_result, handle_lane_attach_key, open_lane_attach};
+    use crate::app::AppState;
+    use crossterm::event::KeyCode;
+    use serde_json::json;
+
+    #[test]
+    fn attach_follows_output_until_scrolled_back_and_keeps_it_after_exit() {
+        let mut app = AppState::default();
+        open_lane_attach(&mut app, "lane-1");
+        let tail = |lines: &[&str], alive: bool| {
+            json!({
+                "lane": { "lane_id": "lane-1", "state": if alive { "running" } else { "finished" } },
+                "backend_alive": alive,
+                "lines": lines,
+            })
+        };
+        apply_lane_tail_result(&mut app, &tail(&["a", "b", "c"], true));
+        let attach = app.lane_attach.as_ref().expect("attached");
+        assert_eq!(attach.lines, vec!["a", "b", "c"]);
+        assert_eq!(attach.state, "running");
+
+        assert_eq!(handle_lane_attach_key(&mut app, KeyCode::Up), Some(true));
+        apply_lane_tail_result(&mut app, &tail(&["b", "c", "d"], true));
+        let attach = app.lane_attach.as_ref().expect("attached");
+        assert!(!attach.follow);
+        assert_eq!(attach.scroll, 1);
+        assert_eq!(attach.lines, vec!["a", "b", "c"]);
+
+        handle_lane_attach_key(&mut app, KeyCode::Down);
+        let attach = app.lane_attach.as_ref().expect("attached");
+        assert!(attach.follow);
+        assert_eq!(attach.lines, vec!["b", "c", "d"]);
+
+        apply_lane_tail_result(&mut app, &tail(&[], false));
+        let attach = app.lane_attach.as_ref().expect("attached");
+        assert!(!attach.backend_alive);
+        assert_eq!(attach.state, "finished");
+        assert_eq!(attach.lines, vec!["b", "c", "d"]);
+
+        handle_lane_attach_key(&mut app, KeyCode::Char('d'));
+        assert!(app.lane_attach.is_none());
+    }
+}
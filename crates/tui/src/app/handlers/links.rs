@@ -0,0 +1,195 @@
+use crate::app::log_wrap::visible_log_lines;
+use crate::app::state::{extract_link_targets, LinkTarget, LogKind, LogLine, MAX_LINK_TARGETS};
+use crate::app::util::open::open_url;
+use crate::app::{AppState, ContextPanelState, PickDialogItem, PickDialogState};
+use crossterm::event::KeyCode;
+use std::path::Path;
+
+pub(crate) const QUICK_JUMP_PICK_ID: &str = "quick-jump";
+const PREVIEW_MAX_BYTES: u64 = 1024 * 1024;
+
+fn target_exists(target: &LinkTarget) -> bool {
+    match target {
+        LinkTarget::Url(_) => true,
+        LinkTarget::Path { path, .. } => Path::new(path).is_file(),
+    }
+}
+
+/// Alt+O: numbered pick of the URLs and existing file paths in the visible log rows.
+pub(crate) fn open_quick_jump(app: &mut AppState) {
+    let texts = visible_log_lines(app)
+        .iter()
+        .map(LogLine::plain_text)
+        .collect::<Vec<_>>();
+    let targets = extract_link_targets(&texts)
+        .into_iter()
+        .filter(target_exists)
+        .take(MAX_LINK_TARGETS)
+        .collect::<Vec<_>>();
+    if targets.is_empty() {
+        app.push_line(LogKind::Status, "No links or file paths in view");
+        return;
+    }
+    let items = targets
+        .iter()
+        .enumerate()
+        .map(|(index, target)| PickDialogItem {
+            id: index.to_string(),
+            label: format!("{} {}", index + 1, target.label()),
+            detail: Some(
+                match target {
+                    LinkTarget::Url(_) => "open in browser",
+                    LinkTarget::Path { .. } => "preview file",
+                }
+                .to_string(),
+            ),
+        })
+        .collect::<Vec<_>>();
+    app.pick_dialog = Some(PickDialogState {
+        id: QUICK_JUMP_PICK_ID.to_string(),
+        title: "Open link or path (1-9 / Enter, Esc close)".to_string(),
+        message: None,
+        chosen: vec![false; items.len()],
+        items,
+        selected: 0,
+        multi: false,
+    });
+    app.link_targets = targets;
+}
+
+/// Keys for the quick-jump dialog; digits open immediately instead of only moving the selection.
+pub(crate) fn handle_quick_jump_key(app: &mut AppState, key: KeyCode) -> bool {
+    let Some(pick) = app.pick_dialog.as_mut() else {
+        return false;
+    };
+    let chosen = match key {
+        KeyCode::Esc => None,
+        KeyCode::Up => {
+            pick.selected = pick.selected.saturating_sub(1);
+            return true;
+        }
+        KeyCode::Down => {
+            if pick.selected + 1 < pick.items.len() {
+                pick.selected += 1;
+            }
+            return true;
+        }
+        KeyCode::Enter => Some(pick.selected),
+        KeyCode::Char(ch) if ch.is_ascii_digit() => {
+            let index = ch.to_digit(10).unwrap_or(0) as usize;
+            if index == 0 || index > pick.items.len() {
+                return true;
+            }
+            Some(index - 1)
+        }
+        _ => return false,
+    };
+    app.pick_dialog = None;
+    let targets = std::mem::take(&mut app.link_targets);
+    if let Some(target) = chosen.and_then(|index| targets.into_iter().nth(index)) {
+        open_link_target(app, target);
+    }
+    true
+}
+
+fn open_link_target(app: &mut AppState, target: LinkTarget) {
+    match target {
+        LinkTarget::Url(url) => match open_url(&url) {
+            Ok(()) => app.push_line(LogKind::Status, format!("Opened {url}")),
+            Err(error) => app.push_error_report("open link failed", format!("{url}: {error}")),
+        },
+        LinkTarget::Path { path, line } => preview_file(app, &path, line),
+    }
+}
+
+/// Shows the file in a scrollable panel with the selection on `line`.
+pub(crate) fn preview_file(app: &mut AppState, path: &str, line: Option<usize>) {
+    let too_large = std::fs::metadata(path)
+        .map(|meta| meta.len() > PREVIEW_MAX_BYTES)
+        .unwrap_or(false);
+    if too_large {
+        app.push_line(
+            LogKind::Status,
+            format!("{path} is larger than 1 MiB; not previewing"),
+        );
+        return;
+    }
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) => {
+            app.push_error_report("preview failed", format!("{path}: {error}"));
+            return;
+        }
+    };
+    let number_width = text.lines().count().max(1).to_string().len();
+    let rows = text
+        .lines()
+        .enumerate()
+        .map(|(index, row)| format!("{:>number_width$} │ {row}", index + 1))
+        .collect::<Vec<_>>();
+    if rows.is_empty() {
+        app.push_line(LogKind::Status, format!("{path} is empty"));
+        return;
+    }
+    let selected = line
+        .map(|line| line.saturating_sub(1))
+        .unwrap_or(0)
+        .min(rows.len() - 1);
+    app.skills_list_panel = None;
+    app.theme_list_panel = None;
+    app.context_panel = Some(ContextPanelState {
+        title: match line {
+            Some(line) => format!("{path}:{line}"),
+            None => path.to_string(),
+        },
+        header: format!("{} lines · Esc close", rows.len()),
+        rows,
+        selected,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{handle_quick_jump_key, open_quick_jump, QUICK_JUMP_PICK_ID};
+    use crate::app::log_wrap::cached_wrap_log_lines;
+    use crate::app::state::{LogKind, LogLine};
+    use crate::app::AppState;
+    use crossterm::event::KeyCode;
+
+    #[test]
+    fn quick_jump_lists_visible_targets_and_digit_opens_preview() {
+        let mut app = AppState::default();
+        app.log.push(LogLine::new(
+            LogKind::Assistant,
+            "old: https://hidden.example",
+        ));
+        app.log.push(LogLine::new(
+            LogKind::Assistant,
+            "see https://example.com/a/very/long/path and Cargo.toml:3 or missing.rs",
+        ));
+        let total = cached_wrap_log_lines(&mut app, 20).len();
+        app.render_state.wrapped_total = total;
+        app.render_state.visible_start = 2;
+        app.render_state.visible_end = total;
+
+        open_quick_jump(&mut app);
+        let pick = app.pick_dialog.as_ref().expect("quick-jump dialog");
+        assert_eq!(pick.id, QUICK_JUMP_PICK_ID);
+        let labels = pick
+            .items
+            .iter()
+            .map(|item| item.label.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            labels,
+            vec!["1 https://example.com/a/very/long/path", "2 Cargo.toml:3"]
+        );
+
+        assert!(handle_quick_jump_key(&mut app, KeyCode::Char('2')));
+        assert!(app.pick_dialog.is_none());
+        let panel = app.context_panel.as_ref().expect("file preview");
+        assert_eq!(panel.title, "Cargo.toml:3");
+        assert_eq!(panel.selected, 2);
+        assert!(app.link_targets.is_empty());
+    }
+}
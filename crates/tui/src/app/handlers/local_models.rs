@@ -0,0 +1,164 @@
+use crate::app::state::LogKind;
+use crate::app::util::local_models::{format_model_size, LocalServer, LOCAL_MODEL_PROVIDER};
+use crate::app::{AppState, ModelListPanelState, ModelListSubmitAction, ModelListViewMode};
+
+/// `/local-models`: probes the default Ollama/llama.cpp ports in the background.
+pub(crate) fn start_local_model_probe(app: &mut AppState) {
+    if !app.local_model_probe.spawn() {
+        app.push_line(LogKind::Status, "Still looking for local model servers…");
+        return;
+    }
+    app.push_line(
+        LogKind::Status,
+        "Looking for Ollama (11434) and llama.cpp (8080, 8000) on 127.0.0.1…",
+    );
+}
+
+/// Returns true when the probe finished and the log or a panel changed.
+pub(crate) fn apply_local_model_probe_result(app: &mut AppState) -> bool {
+    let Some(servers) = app.local_model_probe.poll() else {
+        return false;
+    };
+    if servers.is_empty() {
+        app.push_line(
+            LogKind::Status,
+            "No local model server found; start `ollama serve` or `llama-server` and retry",
+        );
+        return true;
+    }
+    for server in &servers {
+        app.push_line(
+            LogKind::Status,
+            format!(
+                "Found {} at {} ({} model(s))",
+                server.kind.label(),
+                server.base_url,
+                server.models.len()
+            ),
+        );
+    }
+    let current = (app.runtime_info.current_provider.as_deref() == Some(LOCAL_MODEL_PROVIDER))
+        .then_some(app.runtime_info.current_model.as_deref())
+        .flatten();
+    match build_local_model_panel(&servers, current) {
+        Some(panel) => {
+            app.skills_list_panel = None;
+            app.theme_list_panel = None;
+            app.context_panel = None;
+            app.model_list_panel = Some(panel);
+        }
+        None => app.push_line(
+            LogKind::Status,
+            "The server has no models yet; pull or load one first",
+        ),
+    }
+    true
+}
+
+/// Selecting a row sends `model.set` with `provider = "local"` and the row's
+/// server as `base_url`, which registers the server with the runtime.
+fn build_local_model_panel(
+    servers: &[LocalServer],
+    current: Option<&str>,
+) -> Option<ModelListPanelState> {
+    let models = servers
+        .iter()
+        .flat_map(|server| server.models.iter().map(move |model| (server, model)))
+        .collect::<Vec<_>>();
+    if models.is_empty() {
+        return None;
+    }
+    let name_width = models
+        .iter()
+        .map(|(_, model)| model.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("model".len());
+    let header = format!(
+        "  {:<name_width$} | {:>7} | {:>6} | {:<8} | server",
+        "model", "size", "params", "quant"
+    );
+    let rows = models
+        .iter()
+        .map(|(server, model)| {
+            let marker = if current == Some(model.name.as_str()) {
+                "*"
+            } else {
+                " "
+            };
+            format!(
+                "{marker} {:<name_width$} | {:>7} | {:>6} | {:<8} | {}",
+                model.name,
+                model.size_bytes.map(format_model_size).unwrap_or_default(),
+                model.parameters.as_deref().unwrap_or("-"),
+                model.quantization.as_deref().unwrap_or("-"),
+                server.kind.label()
+            )
+        })
+        .collect::<Vec<_>>();
+    let selected = models
+        .iter()
+        .position(|(_, model)| current == Some(model.name.as_str()))
+        .unwrap_or(0);
+    Some(ModelListPanelState {
+        title: format!(
+            "Local models ({}) · Enter selects and runs offline",
+            models.len()
+        ),
+        header_limits: header.clone(),
+        rows_limits: rows.clone(),
+        header_cost: header,
+        rows_cost: rows,
+        wide: None,
+        model_ids: models.iter().map(|(_, model)| model.name.clone()).collect(),
+        selected,
+        view_mode: ModelListViewMode::Limits,
+        submit_action: ModelListSubmitAction::LocalModelSet {
+            base_urls: models
+                .iter()
+                .map(|(server, _)| server.base_url.clone())
+                .collect(),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build_local_model_panel;
+    use crate::app::util::local_models::{LocalModel, LocalServer, LocalServerKind};
+    use crate::app::ModelListSubmitAction;
+
+    #[test]
+    fn local_model_panel_lists_metadata() {
+        let servers = vec![LocalServer {
+            kind: LocalServerKind::Ollama,
+            base_url: "http://127.0.0.1:11434".to_string(),
+            models: vec![LocalModel {
+                name: "llama3.1:8b".to_string(),
+                size_bytes: Some(4_920_753_328),
+                quantization: Some("Q4_K_M".to_string()),
+                parameters: Some("8.0B".to_string()),
+            }],
+        }];
+        let panel = build_local_model_panel(&servers, Some("llama3.1:8b")).expect("panel");
+        assert_eq!(
+            panel.title,
+            "Local models (1) · Enter selects and runs offline"
+        );
+        assert_eq!(
+            panel.header_limits,
+            "  model       |    size | params | quant    | server"
+        );
+        assert_eq!(
+            panel.rows_limits,
+            vec!["* llama3.1:8b |  4.9 GB |   8.0B | Q4_K_M   | Ollama"]
+        );
+        assert_eq!(panel.model_ids, vec!["llama3.1:8b"]);
+        match panel.submit_action {
+            ModelListSubmitAction::LocalModelSet { base_urls } => {
+                assert_eq!(base_urls, vec!["http://127.0.0.1:11434"]);
+            }
+            _ => panic!("local model rows should register their server"),
+        }
+    }
+}
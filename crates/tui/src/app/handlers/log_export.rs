@@ -0,0 +1,190 @@
+use crate::app::log_wrap::log_lines_to_lines;
+use crate::app::state::{turn_starts, LogKind, LogLine};
+use crate::app::util::log_export::{
+    default_log_export_path, lines_to_ansi, lines_to_cast, lines_to_html, lines_to_markdown,
+    write_log_export, LogExportFormat,
+};
+use crate::app::AppState;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Recording size when the terminal size is unknown (line mode, pipes).
+const CAST_FALLBACK_SIZE: (u16, u16) = (120, 36);
+
+/// Which part of the log `/export` writes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum LogExportScope {
+    Session,
+    LastRun,
+    /// A run id or a unique prefix of one.
+    Run(String),
+}
+
+/// Log indices of one run: all lines of the prompt turns it touched, so the prompt is
+/// included, or only its own lines when they carry no turn (replayed history).
+fn run_line_indices(log: &[LogLine], run_id: &str) -> Vec<usize> {
+    let in_run = |line: &LogLine| line.meta.run_id.as_deref() == Some(run_id);
+    let turns = log
+        .iter()
+        .filter(|line| in_run(line))
+        .filter_map(|line| line.meta.turn)
+        .collect::<HashSet<_>>();
+    log.iter()
+        .enumerate()
+        .filter(|(_, line)| in_run(line) || line.meta.turn.is_some_and(|t| turns.contains(&t)))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+fn resolve_run_id(log: &[LogLine], scope: &LogExportScope) -> Result<Option<String>, String> {
+    let mut run_ids = log.iter().filter_map(|line| line.meta.run_id.as_deref());
+    match scope {
+        LogExportScope::Session => Ok(None),
+        LogExportScope::LastRun => run_ids
+            .next_back()
+            .map(|run_id| Some(run_id.to_string()))
+            .ok_or_else(|| "No run in the log yet".to_string()),
+        LogExportScope::Run(prefix) => {
+            let matches = run_ids
+                .filter(|run_id| run_id.starts_with(prefix.as_str()))
+                .collect::<HashSet<_>>();
+            match matches.len() {
+                0 => Err(format!("No run {prefix} in the log")),
+                1 => Ok(matches.into_iter().next().map(str::to_string)),
+                count => Err(format!("Run prefix {prefix} matches {count} runs")),
+            }
+        }
+    }
+}
+
+/// `/export <ansi|cast|md|html> [--run[=<id>]] [file]`: write the log, or one run of it; all
+/// but Markdown keep theme colors.
+pub(crate) fn export_log(
+    app: &mut AppState,
+    format: LogExportFormat,
+    scope: &LogExportScope,
+    target: Option<&str>,
+) {
+    if app.log.is_empty() {
+        app.push_line(LogKind::Status, "Log is empty; nothing to export");
+        return;
+    }
+    let run_id = match resolve_run_id(&app.log, scope) {
+        Ok(run_id) => run_id,
+        Err(message) => {
+            app.push_line(LogKind::Error, message);
+            return;
+        }
+    };
+    let indices = match run_id.as_deref() {
+        Some(run_id) => run_line_indices(&app.log, run_id),
+        None => (0..app.log.len()).collect(),
+    };
+    let log: Cow<'_, [LogLine]> = match run_id {
+        Some(_) => Cow::Owned(
+            indices
+                .iter()
+                .map(|&index| app.log[index].clone())
+                .collect(),
+        ),
+        None => Cow::Borrowed(&app.log),
+    };
+    let path = target
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_log_export_path(format));
+    let line_count = log.len();
+    let lines = log_lines_to_lines(&log);
+    let mut title = match app.runtime_info.session_id.as_deref() {
+        Some(session_id) => format!("codelia session {session_id}"),
+        None => "codelia session".to_string(),
+    };
+    if let Some(run_id) = run_id.as_deref() {
+        title.push_str(&format!(" run {run_id}"));
+    }
+    let contents = match format {
+        LogExportFormat::Ansi => lines_to_ansi(&lines),
+        LogExportFormat::Markdown => {
+            let texts = log.iter().map(LogLine::plain_text).collect::<Vec<_>>();
+            lines_to_markdown(&texts, &turn_starts(&log), &title)
+        }
+        LogExportFormat::Html => lines_to_html(&lines, &turn_starts(&log), &title),
+        LogExportFormat::Cast => {
+            // A single run replays from its first line rather than from session start.
+            let base = match (&run_id, indices.first()) {
+                (Some(_), Some(&first)) => app.log_timeline.offset_of(first),
+                _ => Duration::ZERO,
+            };
+            let offsets = indices
+                .iter()
+                .map(|&index| app.log_timeline.offset_of(index).saturating_sub(base))
+                .collect::<Vec<_>>();
+            let size = crossterm::terminal::size().unwrap_or(CAST_FALLBACK_SIZE);
+            lines_to_cast(
+                &lines,
+                &offsets,
+                size,
+                app.log_timeline.started_unix_secs() + base.as_secs(),
+                &title,
+            )
+        }
+    };
+    match write_log_export(&path, &contents) {
+        Ok(()) => {
+            let hint = match format {
+                LogExportFormat::Ansi => "view with `cat` or `less -R`",
+                LogExportFormat::Cast => "replay with `asciinema play`",
+                LogExportFormat::Markdown | LogExportFormat::Html => {
+                    "turns are linkable as #turn-<n>"
+                }
+            };
+            app.push_line(
+                LogKind::Status,
+                format!(
+                    "Log exported to {} ({line_count} lines; {hint})",
+                    path.display()
+                ),
+            );
+        }
+        Err(error) => app.push_error_report("log export failed", error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{resolve_run_id, run_line_indices, LogExportScope};
+    use crate::app::state::{LogKind, LogLine};
+
+    fn line(text: &str, run_id: Option<&str>, turn: Option<u32>) -> LogLine {
+        let mut line = LogLine::new(LogKind::Assistant, text);
+        line.meta.run_id = run_id.map(str::to_string);
+        line.meta.turn = turn;
+        line
+    }
+
+    #[test]
+    fn run_scope_keeps_the_prompt_turn_of_the_run() {
+        let log = vec![
+            line("> first", None, Some(1)),
+            line("answer one", Some("run-a1"), Some(1)),
+            line("> second", None, Some(2)),
+            line("answer two", Some("run-b2"), Some(2)),
+            line("Saved notes.md", None, Some(2)),
+            line("replayed", Some("run-b9"), None),
+        ];
+        assert_eq!(
+            resolve_run_id(&log, &LogExportScope::LastRun),
+            Ok(Some("run-b9".to_string()))
+        );
+        assert_eq!(
+            resolve_run_id(&log, &LogExportScope::Run("run-a".to_string())),
+            Ok(Some("run-a1".to_string()))
+        );
+        assert!(resolve_run_id(&log, &LogExportScope::Run("run-b".to_string())).is_err());
+        assert!(resolve_run_id(&log, &LogExportScope::Run("run-c".to_string())).is_err());
+
+        assert_eq!(run_line_indices(&log, "run-b2"), vec![2, 3, 4]);
+        assert_eq!(run_line_indices(&log, "run-b9"), vec![5]);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::app::log_wrap::refresh_log_search_index;
+use crate::app::state::LogSearch;
+use crate::app::AppState;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// Ctrl+F: opens the search prompt, or goes back to editing an open search's query.
+pub(crate) fn open_log_search(app: &mut AppState) {
+    match app.log_search.as_mut() {
+        Some(search) => search.editing = true,
+        None => app.log_search = Some(LogSearch::open()),
+    }
+}
+
+/// Centers wrapped row `row` in the log view, leaving follow mode unless it is on the last page.
+fn scroll_to_wrapped_row(app: &mut AppState, row: usize) {
+    let total = app
+        .wrapped_log_cache
+        .as_ref()
+        .map_or(0, |cache| cache.wrapped.len());
+    let viewport = app.last_log_viewport_height.max(1);
+    let top = row.saturating_sub(viewport / 2);
+    app.scroll_from_bottom = total.saturating_sub(viewport).saturating_sub(top);
+}
+
+/// `older` moves up the log; a fresh query starts from the newest match either way.
+fn step_log_search(app: &mut AppState, older: bool) {
+    if app.last_wrap_width == 0 {
+        return;
+    }
+    refresh_log_search_index(app, app.last_wrap_width);
+    let first_row = app.render_state.inserted_until;
+    if let Some(found) = app
+        .log_search
+        .as_mut()
+        .and_then(|search| search.step(older, first_row))
+    {
+        scroll_to_wrapped_row(app, found.row);
+    }
+}
+
+/// While the query is being typed every key goes to the search. After Enter only `n`/`N` are
+/// taken, and only while the bar stays open: any other key meant for the composer closes it
+/// and goes through, so `n`/`N` type normally again. Scrolling, `Esc`, and Ctrl/Alt shortcuts
+/// leave it open.
+pub(crate) fn handle_log_search_key(
+    app: &mut AppState,
+    key: KeyCode,
+    modifiers: KeyModifiers,
+) -> Option<bool> {
+    let search = app.log_search.as_mut()?;
+    let plain = !modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+    if !search.editing {
+        let older = match key {
+            KeyCode::Char('n') if plain => true,
+            KeyCode::Char('N') if plain => false,
+            KeyCode::Esc | KeyCode::PageUp | KeyCode::PageDown | KeyCode::F(_) => return None,
+            _ if !plain => return None,
+            _ => {
+                app.log_search = None;
+                return None;
+            }
+        };
+        step_log_search(app, older);
+        return Some(true);
+    }
+    match key {
+        KeyCode::Esc => app.log_search = None,
+        KeyCode::Enter if search.query.is_empty() => app.log_search = None,
+        KeyCode::Enter => {
+            search.editing = false;
+            if search.current_match().is_none() {
+                step_log_search(app, true);
+            }
+        }
+        KeyCode::Backspace => {
+            search.query.pop();
+            step_log_search(app, true);
+        }
+        KeyCode::Up => step_log_search(app, true),
+        KeyCode::Down => step_log_search(app, false),
+        KeyCode::Char('f') if modifiers.contains(KeyModifiers::CONTROL) => {
+            step_log_search(app, true)
+        }
+        KeyCode::Char(ch) if plain => {
+            search.query.push(ch);
+            step_log_search(app, true);
+        }
+        _ => return Some(false),
+    }
+    Some(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{handle_log_search_key, open_log_search};
+    use crate::app::log_wrap::cached_wrap_log_lines;
+    use crate::app::state::LogKind;
+    use crate::app::AppState;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    #[test]
+    fn typing_a_query_jumps_to_the_newest_match_and_n_walks_up() {
+        let mut app = AppState::default();
+        for index in 0..40 {
+            let text = if index % 10 == 3 {
+                format!("needle {index}")
+            } else {
+                format!("hay {index}")
+            };
+            app.push_line(LogKind::Assistant, text);
+        }
+        app.last_wrap_width = 40;
+        app.last_log_viewport_height = 6;
+        let total = cached_wrap_log_lines(&mut app, 40).len();
+
+        open_log_search(&mut app);
+        let key = |app: &mut AppState, code| handle_log_search_key(app, code, KeyModifiers::NONE);
+        for ch in "needle".chars() {
+            assert_eq!(key(&mut app, KeyCode::Char(ch)), Some(true));
+        }
+        let search = app.log_search.as_ref().expect("search");
+        assert_eq!(search.matches().len(), 4);
+        let newest = search.current_match().expect("current").row;
+        assert_eq!(app.scroll_from_bottom, total - 6 - (newest - 3));
+
+        assert_eq!(key(&mut app, KeyCode::Enter), Some(true));
+        assert_eq!(key(&mut app, KeyCode::Char('n')), Some(true));
+        let search = app.log_search.as_ref().expect("search");
+        assert!(search.current_match().expect("current").row < newest);
+        assert_eq!(search.position_label(0), "3/4");
+        assert_eq!(key(&mut app, KeyCode::PageUp), None);
+        assert!(app.log_search.is_some());
+
+        // Typing into the composer closes the bar and releases `n`/`N`.
+        assert_eq!(key(&mut app, KeyCode::Char('x')), None);
+        assert!(app.log_search.is_none());
+        assert_eq!(key(&mut app, KeyCode::Char('n')), None);
+
+        open_log_search(&mut app);
+        assert_eq!(key(&mut app, KeyCode::Esc), Some(true));
+        assert!(app.log_search.is_none());
+    }
+}
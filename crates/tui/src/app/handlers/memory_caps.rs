@@ -0,0 +1,312 @@
+use crate::app::state::{HeldMemory, LogKind, LogLine};
+use crate::app::util::make_attachment_token;
+use crate::app::{AppState, PendingShellResult};
+
+const MIB: usize = 1024 * 1024;
+
+fn shell_result_bytes(result: &PendingShellResult) -> usize {
+    [
+        &result.stdout,
+        &result.stderr,
+        &result.stdout_excerpt,
+        &result.stderr_excerpt,
+    ]
+    .into_iter()
+    .flatten()
+    .map(String::len)
+    .sum::<usize>()
+        + result.command_preview.len()
+}
+
+fn log_line_bytes(line: &LogLine) -> usize {
+    line.spans
+        .iter()
+        .map(|span| span.text.len() + span.link.as_ref().map_or(0, String::len))
+        .sum()
+}
+
+fn image_bytes(app: &AppState) -> usize {
+    app.pending_image_attachments
+        .values()
+        .chain(app.stashed_attachments.iter().map(|(_, image)| image))
+        .chain(app.run_artifacts.attachments.iter().map(|(_, image)| image))
+        .map(|image| image.data_url.len())
+        .sum()
+}
+
+fn shell_bytes(app: &AppState) -> usize {
+    app.pending_shell_results
+        .iter()
+        .chain(&app.run_artifacts.shell_results)
+        .map(shell_result_bytes)
+        .sum()
+}
+
+fn cap_bytes(megabytes: u64) -> Option<usize> {
+    (megabytes > 0).then(|| megabytes as usize * MIB)
+}
+
+/// Drops images, oldest first: unreferenced ones held by the attachment fix-up dialog, then
+/// ones kept for `/bundle`, then ones still in the composer, whose tokens are removed with
+/// them. Returns `(dropped, dropped from the composer)`.
+fn evict_images(app: &mut AppState, cap: usize) -> (usize, usize) {
+    let mut held = image_bytes(app);
+    let mut dropped = 0;
+    for held_images in [
+        &mut app.stashed_attachments,
+        &mut app.run_artifacts.attachments,
+    ] {
+        while held > cap && !held_images.is_empty() {
+            let (_, image) = held_images.remove(0);
+            held -= image.data_url.len();
+            dropped += 1;
+        }
+    }
+    let mut pending = app
+        .pending_image_attachments
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+    // Ids are `img<n>` in attach order.
+    pending.sort_by_key(|id| id.trim_start_matches("img").parse::<u64>().unwrap_or(0));
+    let mut from_composer = 0;
+    for id in pending {
+        if held <= cap {
+            break;
+        }
+        if let Some(image) = app.pending_image_attachments.remove(&id) {
+            held -= image.data_url.len();
+            let token = make_attachment_token(&app.composer_nonce, &id);
+            app.input.remove_occurrences(&token);
+            from_composer += 1;
+        }
+    }
+    (dropped + from_composer, from_composer)
+}
+
+/// Drops `!` shell results, oldest first: ones kept for `/bundle` before ones still queued
+/// for the next prompt.
+fn evict_shell_results(app: &mut AppState, cap: usize) -> usize {
+    let mut held = shell_bytes(app);
+    let mut dropped = 0;
+    for queue in [
+        &mut app.run_artifacts.shell_results,
+        &mut app.pending_shell_results,
+    ] {
+        while held > cap && !queue.is_empty() {
+            held -= shell_result_bytes(&queue.remove(0));
+            dropped += 1;
+        }
+    }
+    dropped
+}
+
+/// Drops the oldest log lines until the log is under 90% of `cap`, so trimming does not run
+/// on every check. Only lines already scrolled off the view (in inline mode: already written
+/// to the terminal scrollback) are dropped, and only while the view follows the bottom.
+fn evict_log_lines(app: &mut AppState, cap: usize, inline: bool) -> usize {
+    if app.scroll_from_bottom > 0 || app.log_search.is_some() {
+        return 0;
+    }
+    let Some(cache) = app.wrapped_log_cache.as_ref() else {
+        return 0;
+    };
+    if cache.log_version != app.log_version {
+        return 0;
+    }
+    let render = &app.render_state;
+    let boundary = if inline {
+        render.inserted_until.min(render.visible_start)
+    } else {
+        render.visible_start
+    };
+    let above_view = cache
+        .line_starts
+        .partition_point(|start| *start <= boundary)
+        .saturating_sub(1);
+    let limit = above_view.min(app.log_trim_limit());
+    let mut held = app.log.iter().map(log_line_bytes).sum::<usize>();
+    let target = cap / 10 * 9;
+    let mut count = 0;
+    while held > target && count < limit {
+        held -= log_line_bytes(&app.log[count]);
+        count += 1;
+    }
+    if count == 0 {
+        return 0;
+    }
+    let rows = cache
+        .line_starts
+        .get(count)
+        .copied()
+        .unwrap_or(cache.wrapped.len());
+    app.drop_log_front(count, rows);
+    count
+}
+
+fn report_eviction(app: &mut AppState, what: String, setting: &str, megabytes: u64) {
+    app.push_line(
+        LogKind::Status,
+        format!("Memory cap: dropped {what} ({setting} = {megabytes}; change with /set {setting})"),
+    );
+}
+
+/// Enforces the `*_memory_mb` caps and refreshes the held-memory numbers in the perf panel.
+/// Called after a draw so the wrapped log matches the log; true when anything was dropped.
+pub(crate) fn enforce_memory_caps(app: &mut AppState, inline: bool) -> bool {
+    let settings = app.settings.clone();
+    let mut evicted = 0;
+    if let Some(cap) = cap_bytes(settings.image_memory_mb) {
+        let (dropped, from_composer) = evict_images(app, cap);
+        if dropped > 0 {
+            let what = if from_composer > 0 {
+                format!("{dropped} oldest image(s), {from_composer} of them from the composer")
+            } else {
+                format!("{dropped} oldest image(s) outside the composer")
+            };
+            report_eviction(app, what, "image_memory_mb", settings.image_memory_mb);
+        }
+        evicted += dropped;
+    }
+    if let Some(cap) = cap_bytes(settings.shell_memory_mb) {
+        let dropped = evict_shell_results(app, cap);
+        if dropped > 0 {
+            report_eviction(
+                app,
+                format!("{dropped} oldest ! shell result(s)"),
+                "shell_memory_mb",
+                settings.shell_memory_mb,
+            );
+        }
+        evicted += dropped;
+    }
+    if let Some(cap) = cap_bytes(settings.log_memory_mb) {
+        let dropped = evict_log_lines(app, cap, inline);
+        if dropped > 0 {
+            report_eviction(
+                app,
+                format!("{dropped} oldest log line(s) from memory"),
+                "log_memory_mb",
+                settings.log_memory_mb,
+            );
+        }
+        evicted += dropped;
+    }
+    app.perf_debug.held_memory = HeldMemory {
+        image_bytes: image_bytes(app),
+        shell_bytes: shell_bytes(app),
+        log_bytes: app.log.iter().map(log_line_bytes).sum(),
+        evicted: app.perf_debug.held_memory.evicted + evicted,
+    };
+    evicted > 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{enforce_memory_caps, MIB};
+    use crate::app::state::{LogKind, WrappedLogCache};
+    use crate::app::util::make_attachment_token;
+    use crate::app::{AppState, PendingImageAttachment, PendingShellResult};
+
+    fn image(bytes: usize) -> PendingImageAttachment {
+        PendingImageAttachment {
+            data_url: "x".repeat(bytes),
+            width: 1,
+            height: 1,
+            encoded_bytes: bytes,
+        }
+    }
+
+    fn shell_result(id: &str, bytes: usize) -> PendingShellResult {
+        PendingShellResult {
+            id: id.to_string(),
+            command_preview: String::new(),
+            exit_code: Some(0),
+            signal: None,
+            duration_ms: 1,
+            stdout: Some("y".repeat(bytes)),
+            stderr: None,
+            stdout_excerpt: None,
+            stderr_excerpt: None,
+            stdout_cache_id: None,
+            stderr_cache_id: None,
+            truncated_stdout: false,
+            truncated_stderr: false,
+            truncated_combined: false,
+        }
+    }
+
+    #[test]
+    fn caps_drop_the_oldest_items_first() {
+        let mut app = AppState::default();
+        app.settings.image_memory_mb = 2;
+        app.settings.shell_memory_mb = 1;
+        app.settings.log_memory_mb = 0;
+        app.run_artifacts
+            .attachments
+            .push(("img1".to_string(), image(MIB)));
+        for id in ["img2", "img3"] {
+            app.add_pending_image_attachment(id.to_string(), image(MIB));
+        }
+        let token2 = make_attachment_token(&app.composer_nonce, "img2");
+        let token3 = make_attachment_token(&app.composer_nonce, "img3");
+        app.input.set_from(&format!("a{token2}b{token3}c"));
+        app.input.cursor = app.input.buffer.len() - 1;
+        app.stashed_attachments
+            .push(("img0".to_string(), image(MIB / 2)));
+        app.run_artifacts
+            .shell_results
+            .push(shell_result("shell_1", MIB / 2));
+        app.pending_shell_results
+            .push(shell_result("shell_2", MIB / 2 + 10));
+
+        assert!(enforce_memory_caps(&mut app, true));
+        assert!(app.stashed_attachments.is_empty());
+        assert!(app.run_artifacts.attachments.is_empty());
+        assert_eq!(app.pending_image_attachments.len(), 2);
+        assert!(app.run_artifacts.shell_results.is_empty());
+        assert_eq!(app.pending_shell_results.len(), 1);
+
+        app.add_pending_image_attachment("img4".to_string(), image(MIB));
+        assert!(enforce_memory_caps(&mut app, true));
+        assert!(!app.pending_image_attachments.contains_key("img2"));
+        assert_eq!(app.input.current(), format!("ab{token3}c"));
+        // The cursor stays before the trailing "c".
+        assert_eq!(app.input.cursor, app.input.buffer.len() - 1);
+        assert_eq!(app.perf_debug.held_memory.image_bytes, 2 * MIB);
+        assert_eq!(app.perf_debug.held_memory.evicted, 4);
+        assert!(app.log.iter().any(|line| line
+            .plain_text()
+            .starts_with("Memory cap: dropped 1 oldest image(s), 1 of them from the composer")));
+    }
+
+    #[test]
+    fn log_cap_drops_only_lines_above_the_view() {
+        let mut app = AppState::default();
+        app.settings.log_memory_mb = 1;
+        for _ in 0..4 {
+            app.push_line(LogKind::Assistant, "z".repeat(MIB / 2));
+        }
+        app.wrapped_log_cache = Some(WrappedLogCache {
+            width: 80,
+            log_version: app.log_version,
+            wrapped: app.log.clone(),
+            line_starts: vec![0, 1, 2, 3],
+        });
+        app.render_state.wrapped_total = 4;
+        app.render_state.inserted_until = 2;
+        app.render_state.visible_start = 2;
+        app.render_state.visible_end = 4;
+
+        assert!(enforce_memory_caps(&mut app, true));
+        // Two lines were in the scrollback; the visible ones stay even though still over cap.
+        assert_eq!(app.log.len(), 3);
+        assert_eq!(app.render_state.inserted_until, 0);
+        assert_eq!(app.render_state.visible_start, 0);
+        assert_eq!(app.render_state.wrapped_total, 2);
+        assert!(app.log[2]
+            .plain_text()
+            .starts_with("Memory cap: dropped 2 oldest log line(s)"));
+        assert!(!enforce_memory_caps(&mut app, true));
+    }
+}
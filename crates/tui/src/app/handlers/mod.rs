@@ -1,15 +1,55 @@
+pub(crate) mod agents_suggestion;
+pub(crate) mod attachment_check;
+pub(crate) mod audit;
+pub(crate) mod cache;
 pub(crate) mod command;
 pub(crate) mod confirm;
+pub(crate) mod connection_health;
+pub(crate) mod context_pins;
+pub(crate) mod copy;
+pub(crate) mod draft;
+pub(crate) mod edit_conflict;
+pub(crate) mod editor_bridge;
+pub(crate) mod external_editor;
+pub(crate) mod file_artifacts;
+pub(crate) mod file_mentions;
+pub(crate) mod forge;
+pub(crate) mod json_output;
+pub(crate) mod lane_attach;
+pub(crate) mod links;
+pub(crate) mod local_models;
+pub(crate) mod log_export;
+pub(crate) mod log_search;
+pub(crate) mod memory_caps;
 pub(crate) mod panels;
+pub(crate) mod paste_sample;
+pub(crate) mod permalink;
+pub(crate) mod permissions;
+pub(crate) mod queue_panel;
+pub(crate) mod rpc_retry;
+pub(crate) mod run_tee;
+pub(crate) mod run_trace;
+pub(crate) mod runtime_report;
 pub(crate) mod runtime_response;
+pub(crate) mod scratchpad;
+pub(crate) mod secrets;
+pub(crate) mod session_notes;
+pub(crate) mod settings;
+pub(crate) mod stack_trace;
+pub(crate) mod tabs;
 pub(crate) mod theme;
+pub(crate) mod tool_pane;
+pub(crate) mod tools_panel;
+pub(crate) mod translate;
+pub(crate) mod update;
+pub(crate) mod usage;
+pub(crate) mod watchdog;
+pub(crate) mod whats_new;
+pub(crate) mod working_dir;
 
+use crate::app::runtime::RuntimeStdin;
 use crate::app::state::InputState;
 use crate::app::{AppState, SkillsListItemState};
-use std::io::BufWriter;
-use std::process::ChildStdin;
-
-type RuntimeStdin = BufWriter<ChildStdin>;
 
 pub(crate) fn complete_slash_command(input: &mut InputState) -> bool {
     command::complete_slash_command(input)
@@ -33,3 +73,11 @@ pub(crate) fn handle_enter(
 pub(crate) fn can_dispatch_prompt_now(app: &AppState) -> bool {
     command::can_dispatch_prompt_now(app)
 }
+
+pub(crate) fn run_ready_plan(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) -> bool {
+    command::run_ready_plan(app, child_stdin, next_id)
+}
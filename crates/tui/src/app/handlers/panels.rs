@@ -1,15 +1,18 @@
+use crate::app::handlers::cache::{request_cache_delete, request_cache_read};
+use crate::app::handlers::secrets::{remove_secret, set_secret_inject};
+use crate::app::handlers::theme::select_custom_theme;
+use crate::app::handlers::tools_panel::toggle_selected_tool;
 use crate::app::runtime::{
-    send_model_list, send_model_set, send_pick_response, send_session_history, send_session_list,
-    send_theme_set,
+    send_local_model_set, send_model_list, send_model_set, send_pick_response,
+    send_session_history, send_session_list, send_theme_set, RuntimeStdin,
 };
 use crate::app::state::parse_theme_name;
 use crate::app::state::LogKind;
-use crate::app::{AppState, ModelListMode, ModelListSubmitAction, ModelSetScope};
+use crate::app::{
+    AppState, CacheReadAction, ContextPanelState, ModelListMode, ModelListSubmitAction,
+    ModelSetScope, StackedPanel,
+};
 use crossterm::event::KeyCode;
-use std::io::BufWriter;
-use std::process::ChildStdin;
-
-type RuntimeStdin = BufWriter<ChildStdin>;
 
 const REASONING_LEVELS: [&str; 5] = ["low", "medium", "high", "xhigh", "max"];
 const SESSION_HISTORY_MAX_EVENTS: usize = 500;
@@ -68,6 +71,8 @@ pub(crate) fn request_session_history(
     next_id: &mut impl FnMut() -> String,
     session_id: &str,
 ) {
+    // Each replayed prompt opens the next turn, so they are numbered as in the live session.
+    app.log_turn = 0;
     let id = next_id();
     app.rpc_pending.session_history_id = Some(id.clone());
     if let Err(error) = send_session_history(
@@ -95,12 +100,38 @@ pub(crate) fn request_session_list(
     }
 }
 
+/// Shows `detail` over the list panel it was opened from; `Esc` on the detail goes back.
+pub(crate) fn open_detail_panel(
+    app: &mut AppState,
+    parent: StackedPanel,
+    detail: ContextPanelState,
+) {
+    app.panel_stack.push(parent, &detail.title);
+    app.context_panel = Some(detail);
+}
+
+fn restore_stacked_panel(app: &mut AppState, panel: StackedPanel) {
+    match panel {
+        StackedPanel::LaneList(panel) => app.lane_list_panel = Some(panel),
+        StackedPanel::McpList(panel) => app.mcp_list_panel = Some(panel),
+    }
+}
+
 pub(crate) fn handle_context_panel_key(app: &mut AppState, key: KeyCode) -> Option<bool> {
     let panel = app.context_panel.as_mut()?;
     let mut needs_redraw = false;
     match key {
-        KeyCode::Esc | KeyCode::Enter => {
+        KeyCode::Esc => {
+            if let Some(panel) = app.context_panel.take() {
+                if let Some(parent) = app.panel_stack.pop_for(&panel.title) {
+                    restore_stacked_panel(app, parent);
+                }
+            }
+            needs_redraw = true;
+        }
+        KeyCode::Enter => {
             app.context_panel = None;
+            app.panel_stack.clear();
             needs_redraw = true;
         }
         KeyCode::Up => {
@@ -233,6 +264,126 @@ pub(crate) fn handle_skills_list_panel_key(app: &mut AppState, key: KeyCode) ->
     Some(needs_redraw)
 }
 
+pub(crate) fn handle_secrets_panel_key(app: &mut AppState, key: KeyCode) -> Option<bool> {
+    let panel = app.secrets_panel.as_mut()?;
+    let mut needs_redraw = false;
+    match key {
+        KeyCode::Esc => {
+            app.secrets_panel = None;
+            needs_redraw = true;
+        }
+        KeyCode::Up => {
+            panel.selected = panel.selected.saturating_sub(1);
+            needs_redraw = true;
+        }
+        KeyCode::Down => {
+            if panel.selected + 1 < panel.rows.len() {
+                panel.selected += 1;
+            }
+            needs_redraw = true;
+        }
+        KeyCode::Char(' ') => {
+            if let Some(name) = panel.names.get(panel.selected).cloned() {
+                let inject = !app
+                    .secrets
+                    .entries()
+                    .iter()
+                    .any(|entry| entry.name == name && entry.inject);
+                set_secret_inject(app, &name, inject);
+            }
+            needs_redraw = true;
+        }
+        KeyCode::Char('d') => {
+            if let Some(name) = panel.names.get(panel.selected).cloned() {
+                remove_secret(app, &name);
+            }
+            needs_redraw = true;
+        }
+        _ => {}
+    }
+    Some(needs_redraw)
+}
+
+pub(crate) fn handle_tools_panel_key(app: &mut AppState, key: KeyCode) -> Option<bool> {
+    let panel = app.tools_panel.as_mut()?;
+    let mut needs_redraw = false;
+    match key {
+        KeyCode::Esc => {
+            app.tools_panel = None;
+            needs_redraw = true;
+        }
+        KeyCode::Up => {
+            panel.selected = panel.selected.saturating_sub(1);
+            needs_redraw = true;
+        }
+        KeyCode::Down => {
+            if panel.selected + 1 < panel.rows.len() {
+                panel.selected += 1;
+            }
+            needs_redraw = true;
+        }
+        KeyCode::Char(' ') | KeyCode::Enter => {
+            toggle_selected_tool(app);
+            needs_redraw = true;
+        }
+        _ => {}
+    }
+    Some(needs_redraw)
+}
+
+pub(crate) fn handle_cache_list_panel_key(
+    app: &mut AppState,
+    key: KeyCode,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) -> Option<bool> {
+    let panel = app.cache_list_panel.as_mut()?;
+    let mut needs_redraw = false;
+    match key {
+        KeyCode::Esc => {
+            app.cache_list_panel = None;
+            needs_redraw = true;
+        }
+        KeyCode::Up => {
+            panel.selected = panel.selected.saturating_sub(1);
+            needs_redraw = true;
+        }
+        KeyCode::Down => {
+            if panel.selected + 1 < panel.rows.len() {
+                panel.selected += 1;
+            }
+            needs_redraw = true;
+        }
+        KeyCode::PageUp => {
+            panel.selected = panel.selected.saturating_sub(5);
+            needs_redraw = true;
+        }
+        KeyCode::PageDown => {
+            let next = panel.selected.saturating_add(5);
+            panel.selected = usize::min(next, panel.rows.len().saturating_sub(1));
+            needs_redraw = true;
+        }
+        KeyCode::Enter | KeyCode::Char('p') => {
+            request_cache_read(app, child_stdin, next_id, CacheReadAction::Preview);
+            needs_redraw = true;
+        }
+        KeyCode::Char('a') => {
+            request_cache_read(app, child_stdin, next_id, CacheReadAction::Attach);
+            needs_redraw = true;
+        }
+        KeyCode::Char('s') => {
+            request_cache_read(app, child_stdin, next_id, CacheReadAction::Save);
+            needs_redraw = true;
+        }
+        KeyCode::Char('d') => {
+            request_cache_delete(app, child_stdin, next_id);
+            needs_redraw = true;
+        }
+        _ => {}
+    }
+    Some(needs_redraw)
+}
+
 pub(crate) fn handle_theme_list_panel_key(
     app: &mut AppState,
     key: KeyCode,
@@ -266,12 +417,12 @@ pub(crate) fn handle_theme_list_panel_key(
             needs_redraw = true;
         }
         KeyCode::Enter => {
-            let selected_theme = panel
-                .theme_ids
-                .get(panel.selected)
-                .and_then(|value| parse_theme_name(value));
+            let selected_id = panel.theme_ids.get(panel.selected).cloned();
+            let selected_theme = selected_id.as_deref().and_then(parse_theme_name);
             app.theme_list_panel = None;
-            if let Some(theme) = selected_theme {
+            if let (None, Some(custom)) = (selected_theme, selected_id.as_deref()) {
+                select_custom_theme(app, custom);
+            } else if let Some(theme) = selected_theme {
                 if !app.runtime_info.supports_theme_set {
                     app.push_line(LogKind::Status, "Theme update unavailable");
                 } else if app.rpc_pending.theme_set_id.is_some() {
@@ -296,7 +447,7 @@ pub(crate) fn handle_model_list_panel_key(
     app: &mut AppState,
     key: KeyCode,
     child_stdin: &mut RuntimeStdin,
-    _next_id: &mut impl FnMut() -> String,
+    next_id: &mut impl FnMut() -> String,
 ) -> Option<bool> {
     let panel = app.model_list_panel.as_mut()?;
     let mut needs_redraw = false;
@@ -304,7 +455,8 @@ pub(crate) fn handle_model_list_panel_key(
         KeyCode::Esc => {
             let pending_pick_id = match &panel.submit_action {
                 ModelListSubmitAction::UiPick { request_id, .. } => Some(request_id.clone()),
-                ModelListSubmitAction::ModelSet { .. } => None,
+                ModelListSubmitAction::ModelSet { .. }
+                | ModelListSubmitAction::LocalModelSet { .. } => None,
             };
             app.model_list_panel = None;
             if let Some(request_id) = pending_pick_id {
@@ -329,6 +481,11 @@ pub(crate) fn handle_model_list_panel_key(
                     request_id: request_id.clone(),
                     item_ids: item_ids.clone(),
                 },
+                ModelListSubmitAction::LocalModelSet { base_urls } => {
+                    ModelListSubmitAction::LocalModelSet {
+                        base_urls: base_urls.clone(),
+                    }
+                }
             };
             app.model_list_panel = None;
             if let Some(model) = model {
@@ -348,6 +505,18 @@ pub(crate) fn handle_model_list_panel_key(
                             }
                         }
                     }
+                    ModelListSubmitAction::LocalModelSet { base_urls } => {
+                        if let Some(base_url) = base_urls.get(selected) {
+                            let id = next_id();
+                            app.rpc_pending.model_set_id = Some(id.clone());
+                            if let Err(error) =
+                                send_local_model_set(child_stdin, &id, &model, base_url)
+                            {
+                                app.rpc_pending.model_set_id = None;
+                                app.push_error_report("send error", error.to_string());
+                            }
+                        }
+                    }
                 }
             }
             needs_redraw = true;
@@ -490,6 +659,11 @@ pub(crate) fn handle_lane_list_panel_key(
                             label: "Status".to_string(),
                             detail: Some("Show lane status".to_string()),
                         },
+                        crate::app::PickDialogItem {
+                            id: "attach".to_string(),
+                            label: "Attach".to_string(),
+                            detail: Some("Stream the lane's terminal output".to_string()),
+                        },
                         crate::app::PickDialogItem {
                             id: "close".to_string(),
                             label: "Close".to_string(),
@@ -498,7 +672,7 @@ pub(crate) fn handle_lane_list_panel_key(
                     ],
                     multi: false,
                     selected: 0,
-                    chosen: vec![false, false],
+                    chosen: vec![false, false, false],
                 });
                 needs_redraw = true;
             }
@@ -509,6 +683,54 @@ pub(crate) fn handle_lane_list_panel_key(
     Some(needs_redraw)
 }
 
+pub(crate) fn handle_mcp_list_panel_key(app: &mut AppState, key: KeyCode) -> Option<bool> {
+    let panel = app.mcp_list_panel.as_mut()?;
+    let mut needs_redraw = false;
+    match key {
+        KeyCode::Esc => {
+            app.mcp_list_panel = None;
+            needs_redraw = true;
+        }
+        KeyCode::Up => {
+            panel.selected = panel.selected.saturating_sub(1);
+            needs_redraw = true;
+        }
+        KeyCode::Down if panel.selected + 1 < panel.rows.len() => {
+            panel.selected += 1;
+            needs_redraw = true;
+        }
+        KeyCode::PageUp => {
+            panel.selected = panel.selected.saturating_sub(5);
+            needs_redraw = true;
+        }
+        KeyCode::PageDown => {
+            let next = panel.selected.saturating_add(5);
+            panel.selected = usize::min(next, panel.rows.len().saturating_sub(1));
+            needs_redraw = true;
+        }
+        KeyCode::Enter => {
+            let selected = panel.selected;
+            if let (Some(id), Some(rows)) = (
+                panel.server_ids.get(selected).cloned(),
+                panel.details.get(selected).cloned(),
+            ) {
+                if let Some(panel) = app.mcp_list_panel.take() {
+                    let detail = ContextPanelState {
+                        title: format!("MCP {id}"),
+                        header: "Esc back · Enter close".to_string(),
+                        rows,
+                        selected: 0,
+                    };
+                    open_detail_panel(app, StackedPanel::McpList(panel), detail);
+                }
+                needs_redraw = true;
+            }
+        }
+        _ => {}
+    }
+    Some(needs_redraw)
+}
+
 pub(crate) fn handle_provider_picker_key(
     app: &mut AppState,
     key: KeyCode,
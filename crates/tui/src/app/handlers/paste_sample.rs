@@ -0,0 +1,155 @@
+use super::stack_trace::offer_stack_trace_resolution;
+use crate::app::util::paste_sample::{
+    sample_long_paste, PasteSampleSize, COMPACT_PASTE_SAMPLE, LONG_PASTE_LINES, WIDE_PASTE_SAMPLE,
+};
+use crate::app::{AppState, PickDialogItem, PickDialogState};
+use crossterm::event::KeyCode;
+
+pub(crate) const PASTE_SAMPLE_PICK_ID: &str = "paste-sample";
+
+fn sample_item(id: &str, text: &str, size: PasteSampleSize) -> Option<PickDialogItem> {
+    let sample = sample_long_paste(text, size)?;
+    Some(PickDialogItem {
+        id: id.to_string(),
+        label: format!(
+            "Insert first {} + last {} lines and {} error line(s)",
+            size.head, size.tail, sample.kept_errors
+        ),
+        detail: Some(format!(
+            "{} lines omitted, {} lines kept",
+            sample.omitted,
+            sample.text.lines().count()
+        )),
+    })
+}
+
+/// Before a long composer paste is inserted: holds it and offers head/tail sampling.
+/// Returns false (nothing held) when the paste is short enough to insert as is.
+pub(crate) fn offer_paste_sampling(app: &mut AppState, text: &str) -> bool {
+    let total = text.lines().count();
+    if total <= LONG_PASTE_LINES {
+        return false;
+    }
+    let mut items = [
+        sample_item("compact", text, COMPACT_PASTE_SAMPLE),
+        sample_item("wide", text, WIDE_PASTE_SAMPLE),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+    items.push(PickDialogItem {
+        id: "full".to_string(),
+        label: format!("Insert all {total} lines"),
+        detail: None,
+    });
+    items.push(PickDialogItem {
+        id: "discard".to_string(),
+        label: "Discard the paste".to_string(),
+        detail: None,
+    });
+    app.pick_dialog = Some(PickDialogState {
+        id: PASTE_SAMPLE_PICK_ID.to_string(),
+        title: format!("Long paste ({total} lines)"),
+        message: Some("Sampling keeps the start, the end and lines that look like errors".into()),
+        chosen: vec![false; items.len()],
+        items,
+        selected: 0,
+        multi: false,
+    });
+    app.paste_sample_offer = Some(text.to_string());
+    true
+}
+
+fn insert_into_composer(app: &mut AppState, text: &str) {
+    app.input.insert_str(text);
+    if !app.bang_input_mode {
+        offer_stack_trace_resolution(app, text);
+    }
+}
+
+/// Keys for the sampling offer; Esc inserts the paste unchanged.
+pub(crate) fn handle_paste_sample_key(app: &mut AppState, key: KeyCode) -> bool {
+    let Some(pick) = app.pick_dialog.as_mut() else {
+        return false;
+    };
+    let choice = match key {
+        KeyCode::Esc => "full".to_string(),
+        KeyCode::Up => {
+            pick.selected = pick.selected.saturating_sub(1);
+            return true;
+        }
+        KeyCode::Down => {
+            if pick.selected + 1 < pick.items.len() {
+                pick.selected += 1;
+            }
+            return true;
+        }
+        KeyCode::Enter => pick
+            .items
+            .get(pick.selected)
+            .map(|item| item.id.clone())
+            .unwrap_or_default(),
+        _ => return false,
+    };
+    app.pick_dialog = None;
+    let Some(text) = app.paste_sample_offer.take() else {
+        return true;
+    };
+    let size = match choice.as_str() {
+        "compact" => Some(COMPACT_PASTE_SAMPLE),
+        "wide" => Some(WIDE_PASTE_SAMPLE),
+        "discard" => return true,
+        _ => None,
+    };
+    match size.and_then(|size| sample_long_paste(&text, size)) {
+        Some(sample) => insert_into_composer(app, &sample.text),
+        None => insert_into_composer(app, &text),
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{handle_paste_sample_key, offer_paste_sampling, PASTE_SAMPLE_PICK_ID};
+    use crate::app::AppState;
+    use crossterm::event::KeyCode;
+
+    fn long_log() -> String {
+        (1..=1000)
+            .map(|n| {
+                if n == 500 {
+                    "error[E0308]: mismatched types".to_string()
+                } else {
+                    format!("   Compiling crate-{n}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn long_pastes_are_held_until_a_sampling_choice() {
+        let mut app = AppState::default();
+        assert!(!offer_paste_sampling(&mut app, "short\npaste"));
+        assert!(app.pick_dialog.is_none());
+
+        let log = long_log();
+        assert!(offer_paste_sampling(&mut app, &log));
+        let pick = app.pick_dialog.as_ref().expect("offer dialog");
+        assert_eq!(pick.id, PASTE_SAMPLE_PICK_ID);
+        assert_eq!(pick.items.len(), 4);
+        assert!(app.input.buffer.is_empty());
+
+        assert!(handle_paste_sample_key(&mut app, KeyCode::Enter));
+        let inserted = app.input.buffer.iter().collect::<String>();
+        assert_eq!(inserted.lines().count(), 40 + 1 + 1 + 1 + 40);
+        assert!(inserted.contains("error[E0308]: mismatched types"));
+        assert!(inserted.contains("[… 459 lines omitted …]"));
+        assert!(app.paste_sample_offer.is_none());
+
+        let mut app = AppState::default();
+        offer_paste_sampling(&mut app, &log);
+        assert!(handle_paste_sample_key(&mut app, KeyCode::Esc));
+        assert_eq!(app.input.buffer.iter().collect::<String>(), log);
+    }
+}
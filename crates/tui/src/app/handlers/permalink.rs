@@ -0,0 +1,100 @@
+use crate::app::state::{session_permalink, turn_starts, LogKind};
+use crate::app::util::clipboard::write_clipboard_text;
+use crate::app::AppState;
+
+/// `/link [n]`: copies the deep link to turn `n` (default: the latest) of this session.
+pub(crate) fn copy_turn_link(app: &mut AppState, turn: Option<usize>) {
+    let Some(session_id) = app.runtime_info.session_id.clone() else {
+        app.push_line(
+            LogKind::Error,
+            "No session yet; send a prompt before linking to a turn",
+        );
+        return;
+    };
+    let starts = turn_starts(&app.log);
+    let (Some(first), Some(last)) = (starts.first(), starts.last()) else {
+        app.push_line(LogKind::Status, "No turns to link to yet");
+        return;
+    };
+    let turn = turn.unwrap_or(last.turn);
+    if !starts.iter().any(|start| start.turn == turn) {
+        app.push_line(
+            LogKind::Error,
+            format!(
+                "No turn {turn} in the log; it has turns {}-{}",
+                first.turn, last.turn
+            ),
+        );
+        return;
+    }
+    let link = session_permalink(&session_id, turn);
+    match write_clipboard_text(&link) {
+        Ok(()) => app.push_line(LogKind::Status, format!("Copied {link}")),
+        // The link is still in the log for manual copying (SSH, headless).
+        Err(error) => app.push_line(
+            LogKind::Status,
+            format!("{link} (clipboard unavailable: {error})"),
+        ),
+    }
+}
+
+/// Scrolls the log to the start of `turn` (1-based); false when the log has no such turn.
+pub(crate) fn scroll_to_turn(app: &mut AppState, turn: usize) -> bool {
+    let Some(start) = turn_starts(&app.log)
+        .into_iter()
+        .find(|start| start.turn == turn)
+    else {
+        return false;
+    };
+    app.scroll_to_log_line(start.index);
+    true
+}
+
+/// Applies a pending `--resume` turn link once the history has been replayed.
+pub(crate) fn apply_pending_turn_jump(app: &mut AppState) {
+    let Some(turn) = app.pending_turn_jump.take() else {
+        return;
+    };
+    if !scroll_to_turn(app, turn) {
+        app.push_line(
+            LogKind::Error,
+            format!("Linked turn {turn} is not in the restored history"),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_pending_turn_jump, copy_turn_link, scroll_to_turn};
+    use crate::app::state::LogKind;
+    use crate::app::AppState;
+
+    #[test]
+    fn pending_turn_jump_anchors_the_linked_prompt() {
+        let mut app = AppState::default();
+        for prompt in ["first", "second", "third"] {
+            app.begin_log_turn();
+            app.push_line(LogKind::User, format!("> {prompt}"));
+            app.push_line(LogKind::Assistant, "ok");
+        }
+        app.pending_turn_jump = Some(2);
+        apply_pending_turn_jump(&mut app);
+        assert!(app.pending_turn_jump.is_none());
+        assert_eq!(app.scroll_anchor.map(|anchor| anchor.log_index), Some(2));
+
+        app.pending_turn_jump = Some(9);
+        apply_pending_turn_jump(&mut app);
+        let last = app.log.last().expect("error line").plain_text();
+        assert_eq!(last, "Linked turn 9 is not in the restored history");
+
+        // Trimming turn 1 from the front leaves turn 2 under its own number.
+        app.drop_log_front(2, 2);
+        assert!(scroll_to_turn(&mut app, 2));
+        assert_eq!(app.scroll_anchor.map(|anchor| anchor.log_index), Some(0));
+        assert!(!scroll_to_turn(&mut app, 1));
+
+        copy_turn_link(&mut app, Some(1));
+        let last = app.log.last().expect("status line").plain_text();
+        assert!(last.starts_with("No session yet"));
+    }
+}
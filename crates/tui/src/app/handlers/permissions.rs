@@ -0,0 +1,195 @@
+use crate::app::handlers::stack_trace::workspace_root;
+use crate::app::state::LogKind;
+use crate::app::util::command_allowlist::{
+    add_command_rule, load_command_rules, project_config_path, remove_command_rule,
+};
+use crate::app::{AppState, PermissionsPanelState, PromptDialogState};
+use crossterm::event::KeyCode;
+use std::path::PathBuf;
+
+pub(crate) const PERMISSIONS_ADD_PROMPT_ID: &str = "permissions:add";
+
+fn config_path(app: &AppState) -> PathBuf {
+    project_config_path(&workspace_root(app))
+}
+
+/// `/permissions`: the shell `command_glob` allow rules of the project's runtime config.
+pub(crate) fn open_permissions_panel(app: &mut AppState) {
+    let path = config_path(app);
+    let rows = match load_command_rules(&path) {
+        Ok(rows) => rows,
+        Err(error) => {
+            app.push_error_report("permissions", error);
+            return;
+        }
+    };
+    let selected = app
+        .permissions_panel
+        .as_ref()
+        .map(|panel| panel.selected.min(rows.len().saturating_sub(1)))
+        .unwrap_or(0);
+    let header = if rows.is_empty() {
+        "No rules yet · a add (e.g. cargo test*) · Esc close"
+    } else {
+        "a add · d delete · Esc close"
+    };
+    app.permissions_panel = Some(PermissionsPanelState {
+        title: format!("Allowed shell commands ({})", path.display()),
+        header: header.to_string(),
+        rows,
+        selected,
+    });
+}
+
+fn refresh_permissions_panel(app: &mut AppState) {
+    if app.permissions_panel.is_some() {
+        open_permissions_panel(app);
+    }
+}
+
+/// The runtime reads `permissions.allow` when it builds its agent, so a change applies from
+/// its next start; until then the confirm dialog still asks.
+pub(crate) fn add_allowed_command(app: &mut AppState, pattern: &str) {
+    match add_command_rule(&config_path(app), pattern) {
+        Ok(true) => {}
+        Ok(false) => {
+            app.push_line(
+                LogKind::Status,
+                format!("`{}` is empty or already allowed.", pattern.trim()),
+            );
+            return;
+        }
+        Err(error) => {
+            app.push_error_report("permissions", error);
+            return;
+        }
+    }
+    refresh_permissions_panel(app);
+    app.push_line(
+        LogKind::Status,
+        format!(
+            "Added `{}` to this project's permissions.allow; the runtime applies it from its next start.",
+            pattern.trim()
+        ),
+    );
+}
+
+pub(crate) fn remove_allowed_command(app: &mut AppState, pattern: &str) {
+    match remove_command_rule(&config_path(app), pattern) {
+        Ok(true) => {}
+        Ok(false) => {
+            app.push_line(
+                LogKind::Status,
+                format!("`{}` is not in this project's allow rules.", pattern.trim()),
+            );
+            return;
+        }
+        Err(error) => {
+            app.push_error_report("permissions", error);
+            return;
+        }
+    }
+    refresh_permissions_panel(app);
+    app.push_line(
+        LogKind::Status,
+        format!(
+            "Removed `{}` from permissions.allow; the runtime drops it on its next start.",
+            pattern.trim()
+        ),
+    );
+}
+
+/// Handles Enter on the add-pattern prompt; returns false for other prompt ids.
+pub(crate) fn submit_permissions_prompt(app: &mut AppState, prompt_id: &str, value: &str) -> bool {
+    if prompt_id != PERMISSIONS_ADD_PROMPT_ID {
+        return false;
+    }
+    add_allowed_command(app, value);
+    true
+}
+
+pub(crate) fn handle_permissions_panel_key(app: &mut AppState, key: KeyCode) -> Option<bool> {
+    let panel = app.permissions_panel.as_mut()?;
+    match key {
+        KeyCode::Esc => app.permissions_panel = None,
+        KeyCode::Up => panel.selected = panel.selected.saturating_sub(1),
+        KeyCode::Down => {
+            if panel.selected + 1 < panel.rows.len() {
+                panel.selected += 1;
+            }
+        }
+        KeyCode::Char('a') => {
+            if app.prompt_dialog.is_some() {
+                return Some(false);
+            }
+            app.prompt_input.clear();
+            app.prompt_dialog = Some(PromptDialogState {
+                id: PERMISSIONS_ADD_PROMPT_ID.to_string(),
+                title: "Allow command".to_string(),
+                message: "Command pattern to run without asking (`*` matches anything)".to_string(),
+                multiline: false,
+                secret: false,
+            });
+        }
+        KeyCode::Char('d') | KeyCode::Delete => {
+            if let Some(pattern) = panel.rows.get(panel.selected).cloned() {
+                remove_allowed_command(app, &pattern);
+            }
+        }
+        _ => return Some(false),
+    }
+    Some(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        handle_permissions_panel_key, open_permissions_panel, submit_permissions_prompt,
+        PERMISSIONS_ADD_PROMPT_ID,
+    };
+    use crate::app::AppState;
+    use crossterm::event::KeyCode;
+
+    #[test]
+    fn panel_adds_and_deletes_project_rules() {
+        let workspace =
+            std::env::temp_dir().join(format!("codelia-permissions-{}", std::process::id()));
+        let mut app = AppState::default();
+        app.runtime_info.working_dir = Some(workspace.display().to_string());
+        open_permissions_panel(&mut app);
+        assert!(app
+            .permissions_panel
+            .as_ref()
+            .expect("panel")
+            .rows
+            .is_empty());
+
+        handle_permissions_panel_key(&mut app, KeyCode::Char('a'));
+        assert_eq!(
+            app.prompt_dialog.as_ref().map(|prompt| prompt.id.as_str()),
+            Some(PERMISSIONS_ADD_PROMPT_ID)
+        );
+        app.prompt_dialog = None;
+        assert!(submit_permissions_prompt(
+            &mut app,
+            PERMISSIONS_ADD_PROMPT_ID,
+            "cargo test*"
+        ));
+        assert!(submit_permissions_prompt(
+            &mut app,
+            PERMISSIONS_ADD_PROMPT_ID,
+            "npm run lint"
+        ));
+        let panel = app.permissions_panel.as_ref().expect("panel");
+        assert_eq!(panel.rows, vec!["cargo test*", "npm run lint"]);
+
+        handle_permissions_panel_key(&mut app, KeyCode::Down);
+        handle_permissions_panel_key(&mut app, KeyCode::Char('d'));
+        let panel = app.permissions_panel.as_ref().expect("panel");
+        assert_eq!(panel.rows, vec!["cargo test*"]);
+        assert_eq!(panel.selected, 0);
+        let config = std::fs::read_to_string(workspace.join(".codelia/config.json")).expect("read");
+        assert!(config.contains(r#""command_glob": "cargo test*""#));
+        let _ = std::fs::remove_dir_all(workspace);
+    }
+}
@@ -0,0 +1,385 @@
+use crate::app::state::LogKind;
+use crate::app::{AppState, PendingPromptRun, QueuePanelState};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::time::Instant;
+
+pub(crate) fn queue_age_label(queued_at: Instant, now: Instant) -> String {
+    let elapsed = now.saturating_duration_since(queued_at).as_secs();
+    if elapsed < 60 {
+        return format!("{elapsed}s");
+    }
+    let minutes = elapsed / 60;
+    let seconds = elapsed % 60;
+    format!("{minutes}m{seconds:02}s")
+}
+
+fn queue_panel_row(index: usize, item: &PendingPromptRun, now: Instant) -> String {
+    let mut meta = Vec::new();
+    let images = item.attachments.images.len();
+    if images > 0 {
+        meta.push(format!(
+            "{images} image{}",
+            if images == 1 { "" } else { "s" }
+        ));
+    }
+    let files = item.attachments.files.len();
+    if files > 0 {
+        meta.push(format!("{files} file{}", if files == 1 { "" } else { "s" }));
+    }
+    meta.push(format!("{} ago", queue_age_label(item.queued_at, now)));
+    format!(
+        "{}. {}  {}  · {}",
+        index + 1,
+        item.queue_id,
+        item.preview,
+        meta.join(" · ")
+    )
+}
+
+/// `/queue`: the prompts waiting to be sent. Nothing is dispatched while it is open, except in
+/// review mode; either way a rebuild keeps the selection on the same prompt.
+pub(crate) fn open_queue_panel(app: &mut AppState) {
+    if app.pending_prompt_queue.is_empty() {
+        app.queue_panel = None;
+        return;
+    }
+    let now = Instant::now();
+    let rows = app
+        .pending_prompt_queue
+        .iter()
+        .enumerate()
+        .map(|(index, item)| queue_panel_row(index, item, now))
+        .collect::<Vec<_>>();
+    let queue_ids: Vec<String> = app
+        .pending_prompt_queue
+        .iter()
+        .map(|item| item.queue_id.clone())
+        .collect();
+    let selected = app
+        .queue_panel
+        .as_ref()
+        .map(|panel| {
+            panel
+                .queue_ids
+                .get(panel.selected)
+                .and_then(|id| queue_ids.iter().position(|queued| queued == id))
+                .unwrap_or(panel.selected)
+                .min(rows.len() - 1)
+        })
+        .unwrap_or(0);
+    let review = app.queue_panel.as_ref().is_some_and(|panel| panel.review);
+    let (title, header) = if review {
+        (
+            format!(
+                "Run finished: still relevant? ({}/{} queued)",
+                selected + 1,
+                rows.len()
+            ),
+            "s keep · d drop · e edit in composer · Esc close",
+        )
+    } else {
+        (
+            format!("Queued prompts ({}, paused while open)", rows.len()),
+            "Shift+↑/↓ move · d delete · e edit in composer · Esc close",
+        )
+    };
+    app.queue_panel = Some(QueuePanelState {
+        title,
+        header: header.to_string(),
+        rows,
+        queue_ids,
+        selected,
+        review,
+    });
+}
+
+/// A run finished with prompts still queued: offer to walk them from the top, since the answer
+/// often makes follow-ups obsolete. Dispatch keeps going, so an ignored offer costs nothing and
+/// a background tab never stalls. Skipped while a `/batch` runs.
+pub(crate) fn open_queue_review(app: &mut AppState) {
+    if !app.settings.queue_review
+        || app.pending_prompt_queue.is_empty()
+        || app.prompt_batch.is_some()
+    {
+        return;
+    }
+    app.queue_panel = Some(QueuePanelState {
+        title: String::new(),
+        header: String::new(),
+        rows: Vec::new(),
+        queue_ids: Vec::new(),
+        selected: 0,
+        review: true,
+    });
+    open_queue_panel(app);
+}
+
+/// `s` in review: keeps the prompt and asks about the next; after the last one the panel
+/// closes.
+fn keep_reviewed_prompt(app: &mut AppState) {
+    let Some(panel) = app.queue_panel.as_mut() else {
+        return;
+    };
+    if panel.selected + 1 < panel.rows.len() {
+        panel.selected += 1;
+        refresh_queue_panel(app);
+        return;
+    }
+    finish_queue_review(app);
+}
+
+fn finish_queue_review(app: &mut AppState) {
+    app.queue_panel = None;
+    let kept = app.pending_prompt_queue.len();
+    app.push_line(
+        LogKind::Status,
+        format!(
+            "Queue reviewed: {kept} prompt{} still queued",
+            if kept == 1 { "" } else { "s" }
+        ),
+    );
+}
+
+/// Rebuilds the open panel after the queue changed underneath it.
+pub(crate) fn refresh_queue_panel(app: &mut AppState) {
+    if app.queue_panel.is_some() {
+        open_queue_panel(app);
+    }
+}
+
+fn selected_position(app: &AppState) -> Option<usize> {
+    let panel = app.queue_panel.as_ref()?;
+    let queue_id = panel.queue_ids.get(panel.selected)?;
+    app.pending_prompt_queue
+        .iter()
+        .position(|item| &item.queue_id == queue_id)
+}
+
+fn move_selected_prompt(app: &mut AppState, up: bool) {
+    let Some(position) = selected_position(app) else {
+        return;
+    };
+    let target = if up {
+        position.checked_sub(1)
+    } else {
+        Some(position + 1).filter(|target| *target < app.pending_prompt_queue.len())
+    };
+    let Some(target) = target else {
+        return;
+    };
+    // The selection follows the moved prompt's id.
+    app.pending_prompt_queue.swap(position, target);
+    refresh_queue_panel(app);
+}
+
+fn delete_selected_prompt(app: &mut AppState) {
+    let Some(position) = selected_position(app) else {
+        return;
+    };
+    let Some(item) = app.pending_prompt_queue.remove(position) else {
+        return;
+    };
+    app.push_line(
+        LogKind::Status,
+        format!(
+            "Cancelled queued prompt {} (queue={})",
+            item.queue_id,
+            app.pending_prompt_queue.len()
+        ),
+    );
+    let reviewed_last = app.queue_panel.as_ref().is_some_and(|panel| panel.review)
+        && position == app.pending_prompt_queue.len();
+    if reviewed_last && !app.pending_prompt_queue.is_empty() {
+        finish_queue_review(app);
+        return;
+    }
+    refresh_queue_panel(app);
+}
+
+/// Takes the prompt out of the queue and puts its text and attachments back in the composer.
+/// Context consumed when it was queued (shell results, issue and CI context) is not restored.
+fn edit_selected_prompt(app: &mut AppState) {
+    if !app.input.current().trim().is_empty() {
+        app.push_line(
+            LogKind::Status,
+            "The composer has a draft; send or clear it before editing a queued prompt.",
+        );
+        return;
+    }
+    let Some(item) =
+        selected_position(app).and_then(|position| app.pending_prompt_queue.remove(position))
+    else {
+        return;
+    };
+    app.clear_composer();
+    app.composer_nonce = item.attachments.composer_nonce;
+    app.pending_image_attachments
+        .extend(item.attachments.images);
+    app.pending_file_attachments = item.attachments.files;
+    if item.json_request.is_some() {
+        app.json_output.armed = item.json_request;
+    }
+    let text = if item.dry_run {
+        format!("/plan {}", item.user_text)
+    } else {
+        item.user_text
+    };
+    app.input.set_from(&text);
+    app.queue_panel = None;
+    app.push_line(
+        LogKind::Status,
+        format!(
+            "Moved queued prompt {} back to the composer (queue={})",
+            item.queue_id,
+            app.pending_prompt_queue.len()
+        ),
+    );
+}
+
+pub(crate) fn handle_queue_panel_key(
+    app: &mut AppState,
+    key: KeyCode,
+    modifiers: KeyModifiers,
+) -> Option<bool> {
+    let panel = app.queue_panel.as_mut()?;
+    let shift = modifiers.contains(KeyModifiers::SHIFT);
+    match key {
+        KeyCode::Esc => app.queue_panel = None,
+        KeyCode::Char('s') if panel.review => keep_reviewed_prompt(app),
+        KeyCode::Up if shift => move_selected_prompt(app, true),
+        KeyCode::Down if shift => move_selected_prompt(app, false),
+        KeyCode::Up => panel.selected = panel.selected.saturating_sub(1),
+        KeyCode::Down => {
+            if panel.selected + 1 < panel.rows.len() {
+                panel.selected += 1;
+            }
+        }
+        KeyCode::Char('d') | KeyCode::Delete => delete_selected_prompt(app),
+        KeyCode::Char('e') | KeyCode::Enter => edit_selected_prompt(app),
+        _ => return Some(false),
+    }
+    Some(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{handle_queue_panel_key, open_queue_panel, open_queue_review, refresh_queue_panel};
+    use crate::app::state::PendingImageAttachment;
+    use crate::app::{AppState, PendingPromptRun, QueuedAttachments};
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use serde_json::json;
+    use std::time::Instant;
+
+    fn queued(id: &str, text: &str) -> PendingPromptRun {
+        PendingPromptRun {
+            queue_id: id.to_string(),
+            queued_at: Instant::now(),
+            preview: text.to_string(),
+            user_text: text.to_string(),
+            input_payload: json!({ "type": "text", "text": text }),
+            attachment_count: 0,
+            shell_result_count: 0,
+            dispatch_attempts: 0,
+            dry_run: false,
+            json_request: None,
+            attachments: QueuedAttachments::default(),
+        }
+    }
+
+    fn queue_ids(app: &AppState) -> Vec<&str> {
+        app.pending_prompt_queue
+            .iter()
+            .map(|item| item.queue_id.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn panel_reorders_deletes_and_edits_queued_prompts() {
+        let mut app = AppState::default();
+        app.pending_prompt_queue.push_back(queued("q1", "first"));
+        app.pending_prompt_queue.push_back(queued("q2", "second"));
+        let mut third = queued("q3", "third [image]");
+        third.attachments = QueuedAttachments {
+            composer_nonce: "n3".to_string(),
+            images: vec![(
+                "img1".to_string(),
+                PendingImageAttachment {
+                    data_url: "data:image/png;base64,AA==".to_string(),
+                    width: 1,
+                    height: 1,
+                    encoded_bytes: 4,
+                },
+            )],
+            files: Vec::new(),
+        };
+        app.pending_prompt_queue.push_back(third);
+        open_queue_panel(&mut app);
+        let panel = app.queue_panel.as_ref().expect("panel");
+        assert!(panel.rows[2].starts_with("3. q3  third [image]  · 1 image · "));
+
+        handle_queue_panel_key(&mut app, KeyCode::Down, KeyModifiers::NONE);
+        handle_queue_panel_key(&mut app, KeyCode::Down, KeyModifiers::NONE);
+        handle_queue_panel_key(&mut app, KeyCode::Up, KeyModifiers::SHIFT);
+        handle_queue_panel_key(&mut app, KeyCode::Up, KeyModifiers::SHIFT);
+        assert_eq!(queue_ids(&app), vec!["q3", "q1", "q2"]);
+        assert_eq!(app.queue_panel.as_ref().expect("panel").selected, 0);
+
+        handle_queue_panel_key(&mut app, KeyCode::Down, KeyModifiers::NONE);
+        handle_queue_panel_key(&mut app, KeyCode::Char('d'), KeyModifiers::NONE);
+        assert_eq!(queue_ids(&app), vec!["q3", "q2"]);
+
+        app.input.set_from("draft");
+        handle_queue_panel_key(&mut app, KeyCode::Up, KeyModifiers::NONE);
+        handle_queue_panel_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(queue_ids(&app), vec!["q3", "q2"]);
+
+        app.clear_composer();
+        handle_queue_panel_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(queue_ids(&app), vec!["q2"]);
+        assert!(app.queue_panel.is_none());
+        assert_eq!(app.input.current(), "third [image]");
+        assert_eq!(app.composer_nonce, "n3");
+        assert!(app.pending_image_attachments.contains_key("img1"));
+    }
+
+    #[test]
+    fn review_walks_the_queue_while_dispatch_continues() {
+        let mut app = AppState::default();
+        for (id, text) in [
+            ("q0", "zeroth"),
+            ("q1", "first"),
+            ("q2", "second"),
+            ("q3", "third"),
+        ] {
+            app.pending_prompt_queue.push_back(queued(id, text));
+        }
+        open_queue_review(&mut app);
+        assert!(app.queue_panel.is_none(), "off by default");
+
+        app.settings.queue_review = true;
+        open_queue_review(&mut app);
+        handle_queue_panel_key(&mut app, KeyCode::Char('s'), KeyModifiers::NONE);
+        app.pending_prompt_queue.pop_front();
+        refresh_queue_panel(&mut app);
+        let panel = app.queue_panel.as_ref().expect("review");
+        assert!(panel.review);
+        assert_eq!(panel.title, "Run finished: still relevant? (1/3 queued)");
+
+        handle_queue_panel_key(&mut app, KeyCode::Char('s'), KeyModifiers::NONE);
+        handle_queue_panel_key(&mut app, KeyCode::Char('d'), KeyModifiers::NONE);
+        let panel = app.queue_panel.as_ref().expect("review");
+        assert_eq!(panel.title, "Run finished: still relevant? (2/2 queued)");
+        assert_eq!(queue_ids(&app), vec!["q1", "q3"]);
+
+        handle_queue_panel_key(&mut app, KeyCode::Char('s'), KeyModifiers::NONE);
+        assert!(app.queue_panel.is_none());
+        assert_eq!(
+            app.log.last().expect("status").plain_text(),
+            "Queue reviewed: 2 prompts still queued"
+        );
+
+        app.settings.queue_review = false;
+        open_queue_review(&mut app);
+        assert!(app.queue_panel.is_none());
+    }
+}
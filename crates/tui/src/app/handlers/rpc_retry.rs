@@ -0,0 +1,136 @@
+use crate::app::runtime::{send_request, RuntimeStdin, SentRequest};
+use crate::app::state::{LogKind, LogLine, LogSpan, LogTone};
+use crate::app::{AppState, RetryableRpc};
+use serde_json::Value;
+
+/// Short name shown in the retry hint; `None` for requests that are not worth re-sending.
+fn retry_label(request: &RetryableRpc) -> Option<&str> {
+    match request.method.as_str() {
+        "model.set" | "theme.set" => Some(request.method.as_str()),
+        "tool.call" => request
+            .params
+            .get("name")
+            .and_then(Value::as_str)
+            .filter(|name| *name == "lane_create"),
+        _ => None,
+    }
+}
+
+/// Keeps the request behind a response while its handler runs, when it could be retried.
+pub(crate) fn retry_candidate(sent: &SentRequest) -> Option<RetryableRpc> {
+    let request = RetryableRpc {
+        method: sent.method.clone(),
+        params: sent.params.clone(),
+    };
+    retry_label(&request).is_some().then_some(request)
+}
+
+/// Called by a response handler after reporting a failure: remembers the request for Alt+R
+/// and adds the `[r]etry` hint under the error.
+pub(crate) fn offer_rpc_retry(app: &mut AppState) {
+    let Some(request) = app.rpc_pending.retry_candidate.take() else {
+        return;
+    };
+    let label = retry_label(&request).unwrap_or_default().to_string();
+    app.failed_rpc = Some(request);
+    app.extend_lines(vec![LogLine::new_with_spans(vec![
+        LogSpan::new(LogKind::Status, LogTone::Detail, "  ["),
+        LogSpan::new(LogKind::Status, LogTone::Summary, "r"),
+        LogSpan::new(
+            LogKind::Status,
+            LogTone::Detail,
+            format!("]etry with Alt+R re-sends the same {label} request"),
+        ),
+    ])]);
+}
+
+/// After a response handler: a request of the same kind that went through supersedes the
+/// failed one, so Alt+R cannot undo it.
+pub(crate) fn settle_retry_candidate(app: &mut AppState) {
+    let Some(done) = app.rpc_pending.retry_candidate.take() else {
+        return;
+    };
+    if app
+        .failed_rpc
+        .as_ref()
+        .is_some_and(|failed| retry_label(failed) == retry_label(&done))
+    {
+        app.failed_rpc = None;
+    }
+}
+
+/// The `RpcPendingState` slot a re-sent request answers to.
+fn pending_slot<'a>(app: &'a mut AppState, request: &RetryableRpc) -> &'a mut Option<String> {
+    match request.method.as_str() {
+        "model.set" => &mut app.rpc_pending.model_set_id,
+        "theme.set" => &mut app.rpc_pending.theme_set_id,
+        _ => &mut app.rpc_pending.lane_create_id,
+    }
+}
+
+/// Alt+R: sends the last failed retryable request again with its original parameters.
+pub(crate) fn retry_failed_rpc(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) -> bool {
+    let Some(request) = app.failed_rpc.take() else {
+        app.push_line(LogKind::Status, "Nothing to retry");
+        return true;
+    };
+    let label = retry_label(&request).unwrap_or_default().to_string();
+    if pending_slot(app, &request).is_some() {
+        app.push_line(
+            LogKind::Status,
+            format!("A {label} request is already running"),
+        );
+        app.failed_rpc = Some(request);
+        return true;
+    }
+    let id = next_id();
+    *pending_slot(app, &request) = Some(id.clone());
+    app.push_line(LogKind::Status, format!("Retrying {label}…"));
+    if let Err(error) = send_request(child_stdin, &id, &request.method, request.params.clone()) {
+        *pending_slot(app, &request) = None;
+        app.push_error_report("send error", error.to_string());
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{offer_rpc_retry, retry_label, settle_retry_candidate};
+    use crate::app::{AppState, RetryableRpc};
+    use serde_json::json;
+
+    #[test]
+    fn failed_retryable_request_is_kept_with_a_hint() {
+        let lane = RetryableRpc {
+            method: "tool.call".to_string(),
+            params: json!({"name": "lane_create", "arguments": {"task_id": "t1"}}),
+        };
+        assert_eq!(retry_label(&lane), Some("lane_create"));
+        let lane_list = RetryableRpc {
+            method: "tool.call".to_string(),
+            params: json!({"name": "lane_list"}),
+        };
+        assert_eq!(retry_label(&lane_list), None);
+
+        let mut app = AppState::default();
+        offer_rpc_retry(&mut app);
+        assert!(app.failed_rpc.is_none());
+
+        app.rpc_pending.retry_candidate = Some(lane.clone());
+        offer_rpc_retry(&mut app);
+        assert_eq!(app.failed_rpc.as_ref(), Some(&lane));
+        assert!(app.rpc_pending.retry_candidate.is_none());
+        let hint = app.log.last().expect("hint").plain_text();
+        app.rpc_pending.retry_candidate = Some(lane.clone());
+        settle_retry_candidate(&mut app);
+        assert!(app.failed_rpc.is_none());
+        assert_eq!(
+            hint,
+            "  [r]etry with Alt+R re-sends the same lane_create request"
+        );
+    }
+}
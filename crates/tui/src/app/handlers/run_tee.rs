@@ -0,0 +1,65 @@
+use crate::app::state::LogKind;
+use crate::app::util::run_tee::RunTee;
+use crate::app::AppState;
+use std::path::Path;
+
+/// `/tee <path>`: appends the current run's output (or the next run's, when idle) to `path`.
+pub(crate) fn start_run_tee(app: &mut AppState, path: &str) {
+    stop_run_tee(app);
+    match RunTee::open(Path::new(path)) {
+        Ok(tee) => {
+            let scope = if app.is_running() {
+                "this run"
+            } else {
+                "the next run"
+            };
+            app.push_line(
+                LogKind::Status,
+                format!(
+                    "Teeing assistant text and tool summaries of {scope} to {}",
+                    tee.path().display()
+                ),
+            );
+            app.run_tee = Some(tee);
+        }
+        Err(error) => app.push_error_report("tee failed", error),
+    }
+}
+
+/// Closes the tee file, if any; false when none was open.
+pub(crate) fn stop_run_tee(app: &mut AppState) -> bool {
+    let Some(tee) = app.run_tee.take() else {
+        return false;
+    };
+    app.push_line(
+        LogKind::Status,
+        format!(
+            "Tee to {} closed ({} bytes written)",
+            tee.path().display(),
+            tee.bytes()
+        ),
+    );
+    true
+}
+
+fn write_run_tee(app: &mut AppState, write: impl FnOnce(&mut RunTee) -> Result<(), String>) {
+    let Some(tee) = app.run_tee.as_mut() else {
+        return;
+    };
+    if let Err(error) = write(tee) {
+        app.run_tee = None;
+        app.push_error_report("tee stopped", error);
+    }
+}
+
+pub(crate) fn tee_assistant_delta(app: &mut AppState, delta: &str) {
+    write_run_tee(app, |tee| tee.append_delta(delta));
+}
+
+pub(crate) fn tee_assistant_message(app: &mut AppState, text: &str) {
+    write_run_tee(app, |tee| tee.append_message(text));
+}
+
+pub(crate) fn tee_tool_summary(app: &mut AppState, summary: &str) {
+    write_run_tee(app, |tee| tee.append_line(summary.trim()));
+}
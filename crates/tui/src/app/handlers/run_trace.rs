@@ -0,0 +1,66 @@
+use crate::app::runtime::ParsedOutput;
+use crate::app::state::{format_token_count, run_trace_rows, LogKind};
+use crate::app::{AppState, ContextPanelState};
+use std::time::Instant;
+
+/// Stamps the run's timeline with what this runtime message says happened.
+pub(crate) fn record_parsed_trace(app: &mut AppState, parsed: &ParsedOutput) {
+    let now = Instant::now();
+    let traces = &mut app.run_traces;
+    if let Some(usage) = parsed.run_usage.as_ref() {
+        traces.model_call(
+            format!(
+                "{} {} in / {} out",
+                usage.model,
+                format_token_count(usage.input_tokens),
+                format_token_count(usage.output_tokens)
+            ),
+            now,
+        );
+    }
+    if parsed.compaction_started {
+        traces.compaction(true, now);
+    }
+    if parsed.compaction_completed {
+        traces.compaction(false, now);
+    }
+    if let Some(tool) = parsed.tool_call_start_tool.as_deref() {
+        let label = match parsed.tool_call_command.as_deref() {
+            Some(command) => format!("{tool} {command}"),
+            None => tool.to_string(),
+        };
+        traces.tool_started(parsed.tool_call_start_id.as_deref(), label, now);
+    }
+    if let Some(result) = parsed.tool_call_result.as_ref() {
+        traces.tool_finished(&result.tool_call_id, result.is_error, now);
+    }
+    if let Some(status) = parsed.status.as_deref() {
+        traces.status(status, now);
+    }
+}
+
+/// `/trace`: the timeline of the run in flight, else of the last run.
+pub(crate) fn open_trace_panel(app: &mut AppState) {
+    let Some(trace) = app.run_traces.latest() else {
+        app.push_line(
+            LogKind::Status,
+            "No run traced yet; /trace shows the next run once it starts.",
+        );
+        return;
+    };
+    let run = trace.run_id.as_deref().unwrap_or(trace.queue_id.as_str());
+    let header = if trace.finished.is_some() {
+        format!("run {run} · Esc close")
+    } else {
+        format!("run {run} (in progress; reopen to refresh) · Esc close")
+    };
+    let rows = run_trace_rows(trace, Instant::now());
+    app.skills_list_panel = None;
+    app.theme_list_panel = None;
+    app.context_panel = Some(ContextPanelState {
+        title: "Trace".to_string(),
+        header,
+        rows,
+        selected: 0,
+    });
+}
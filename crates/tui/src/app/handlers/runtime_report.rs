@@ -0,0 +1,308 @@
+use crate::app::runtime::{send_context_inspect, RuntimeStdin};
+use crate::app::state::{LogKind, LogLine, LogTone};
+use crate::app::util::clipboard::write_clipboard_text;
+use crate::app::{AppState, ContextPanelState};
+use serde_json::Value;
+
+/// One titled block of `key: value` rows; an empty key renders the value alone.
+pub(crate) struct RuntimeReportSection {
+    pub(crate) title: &'static str,
+    pub(crate) rows: Vec<(String, String)>,
+}
+
+/// `/runtime [copy]`: fetch the live working dir, pid, and tools, then show or copy the report.
+pub(crate) fn request_runtime_report(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+    copy: bool,
+) {
+    if !app.runtime_info.supports_context_inspect {
+        // Older runtimes: report what the TUI already knows.
+        finish_runtime_report(app, None, copy);
+        return;
+    }
+    if app.rpc_pending.runtime_report_id.is_some() {
+        app.push_line(LogKind::Status, "Runtime info request already running");
+        return;
+    }
+    let id = next_id();
+    app.rpc_pending.runtime_report_id = Some(id.clone());
+    app.rpc_pending.runtime_report_copy = copy;
+    if let Err(error) = send_context_inspect(child_stdin, &id, false, false) {
+        app.rpc_pending.runtime_report_id = None;
+        app.rpc_pending.runtime_report_copy = false;
+        app.push_error_report("send error", error.to_string());
+    }
+}
+
+fn text_field(result: Option<&Value>, key: &str) -> Option<String> {
+    result
+        .and_then(|result| result.get(key))
+        .and_then(|value| value.as_str())
+        .map(str::to_string)
+}
+
+fn capability_value(value: &Value) -> String {
+    match value {
+        Value::Bool(true) => "yes".to_string(),
+        Value::Bool(false) => "no".to_string(),
+        other => other.to_string(),
+    }
+}
+
+pub(crate) fn runtime_report_sections(
+    app: &AppState,
+    inspect: Option<&Value>,
+) -> Vec<RuntimeReportSection> {
+    let info = &app.runtime_info;
+    let mut runtime = Vec::new();
+    let server = match (info.server_name.as_deref(), info.server_version.as_deref()) {
+        (Some(name), Some(version)) => format!("{name} {version}"),
+        (Some(name), None) => name.to_string(),
+        (None, Some(version)) => version.to_string(),
+        (None, None) => "(not initialized)".to_string(),
+    };
+    let server = match info.protocol_version.as_deref() {
+        Some(protocol) => format!("{server} (protocol {protocol})"),
+        None => server,
+    };
+    runtime.push(("version".to_string(), server));
+    runtime.push((
+        "tui".to_string(),
+        format!("codelia-tui {}", env!("CARGO_PKG_VERSION")),
+    ));
+    if let Some(launch) = info.launch.as_ref() {
+        runtime.push(("transport".to_string(), launch.transport().to_string()));
+        if let Some(host) = launch.ssh_host() {
+            runtime.push(("host".to_string(), host.to_string()));
+        }
+        runtime.push(("command".to_string(), launch.command_line()));
+        runtime.push(("local pid".to_string(), launch.pid.to_string()));
+    }
+    if let Some(pid) = inspect
+        .and_then(|result| result.get("runtime_pid"))
+        .and_then(|value| value.as_u64())
+    {
+        runtime.push(("runtime pid".to_string(), pid.to_string()));
+    }
+    let working_dir =
+        text_field(inspect, "runtime_working_dir").or_else(|| info.working_dir.clone());
+    let sandbox_root =
+        text_field(inspect, "runtime_sandbox_root").or_else(|| info.sandbox_root.clone());
+    runtime.push((
+        "working_dir".to_string(),
+        working_dir.unwrap_or_else(|| "(unknown)".to_string()),
+    ));
+    runtime.push((
+        "sandbox_root".to_string(),
+        sandbox_root.unwrap_or_else(|| "(unknown)".to_string()),
+    ));
+    if let Some(session_id) = info.session_id.as_deref() {
+        runtime.push(("session".to_string(), session_id.to_string()));
+    }
+    if let (Some(provider), Some(model)) = (
+        info.current_provider.as_deref(),
+        info.current_model.as_deref(),
+    ) {
+        runtime.push(("model".to_string(), format!("{provider}/{model}")));
+    }
+
+    let tools = match inspect
+        .and_then(|result| result.get("enabled_tools"))
+        .and_then(|value| value.as_array())
+    {
+        Some(names) if names.is_empty() => vec![(String::new(), "(none)".to_string())],
+        Some(names) => names
+            .iter()
+            .filter_map(|name| name.as_str())
+            .map(|name| (String::new(), name.to_string()))
+            .collect(),
+        None => vec![(
+            String::new(),
+            "(not loaded yet; tools are built on the first run)".to_string(),
+        )],
+    };
+
+    let capabilities = if info.server_capabilities.is_empty() {
+        vec![(String::new(), "(none reported)".to_string())]
+    } else {
+        info.server_capabilities
+            .iter()
+            .map(|(key, value)| (key.clone(), capability_value(value)))
+            .collect()
+    };
+
+    let schema = if info.schema_mismatches.is_empty() {
+        vec![(String::new(), "(none)".to_string())]
+    } else {
+        info.schema_mismatches
+            .iter()
+            .map(|(method, count)| (method.clone(), count.to_string()))
+            .collect()
+    };
+
+    vec![
+        RuntimeReportSection {
+            title: "RUNTIME",
+            rows: runtime,
+        },
+        RuntimeReportSection {
+            title: "TOOLS",
+            rows: tools,
+        },
+        RuntimeReportSection {
+            title: "CAPABILITIES",
+            rows: capabilities,
+        },
+        RuntimeReportSection {
+            title: "SCHEMA MISMATCHES",
+            rows: schema,
+        },
+    ]
+}
+
+fn section_row(key: &str, value: &str) -> String {
+    if key.is_empty() {
+        value.to_string()
+    } else {
+        format!("{key}: {value}")
+    }
+}
+
+pub(crate) fn runtime_report_markdown(sections: &[RuntimeReportSection]) -> String {
+    let mut out = String::from("## Codelia runtime\n");
+    for section in sections {
+        out.push_str(&format!("\n### {}\n", section.title.to_lowercase()));
+        let values_only = section.rows.iter().all(|(key, _)| key.is_empty());
+        if values_only {
+            let joined = section
+                .rows
+                .iter()
+                .map(|(_, value)| format!("`{value}`"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            out.push_str(&format!("{joined}\n"));
+        } else {
+            for (key, value) in &section.rows {
+                out.push_str(&format!("- {}\n", section_row(key, value)));
+            }
+        }
+    }
+    out
+}
+
+fn open_runtime_panel(app: &mut AppState, sections: &[RuntimeReportSection]) {
+    let mut rows = Vec::new();
+    for section in sections {
+        if !rows.is_empty() {
+            rows.push(String::new());
+        }
+        if section.title == "TOOLS" && section.rows.iter().all(|(key, _)| key.is_empty()) {
+            rows.push(format!("TOOLS ({})", section.rows.len()));
+        } else {
+            rows.push(section.title.to_string());
+        }
+        rows.extend(
+            section
+                .rows
+                .iter()
+                .map(|(key, value)| section_row(key, value)),
+        );
+    }
+    app.model_list_panel = None;
+    app.reasoning_picker = None;
+    app.session_list_panel = None;
+    app.lane_list_panel = None;
+    app.mcp_list_panel = None;
+    app.skills_list_panel = None;
+    app.theme_list_panel = None;
+    app.context_panel = Some(ContextPanelState {
+        title: "Runtime".to_string(),
+        header: "/runtime copy copies this as Markdown · Esc close".to_string(),
+        rows,
+        selected: 0,
+    });
+}
+
+/// Shows the report in a panel, or copies it as Markdown for bug reports.
+pub(crate) fn finish_runtime_report(app: &mut AppState, inspect: Option<&Value>, copy: bool) {
+    let sections = runtime_report_sections(app, inspect);
+    if !copy {
+        open_runtime_panel(app, &sections);
+        return;
+    }
+    let markdown = runtime_report_markdown(&sections);
+    match write_clipboard_text(&markdown) {
+        Ok(()) => app.push_line(
+            LogKind::Status,
+            "Copied runtime info to the clipboard as Markdown",
+        ),
+        Err(error) => {
+            // Keep the report reachable when no clipboard is available (SSH, headless).
+            app.push_line(
+                LogKind::Error,
+                format!("Clipboard unavailable ({error}); runtime info follows:"),
+            );
+            app.extend_lines(
+                markdown
+                    .lines()
+                    .map(|line| {
+                        LogLine::new_with_tone(LogKind::Status, LogTone::Detail, line.to_string())
+                    })
+                    .collect(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{runtime_report_markdown, runtime_report_sections};
+    use crate::app::{AppState, RuntimeLaunchInfo};
+    use serde_json::json;
+
+    #[test]
+    fn runtime_report_merges_launch_initialize_and_inspect_info() {
+        let mut app = AppState::default();
+        app.runtime_info.server_name = Some("codelia-runtime".to_string());
+        app.runtime_info.server_version = Some("0.1.0".to_string());
+        app.runtime_info.protocol_version = Some("0".to_string());
+        app.runtime_info
+            .server_capabilities
+            .insert("supports_cwd_set".to_string(), json!(true));
+        app.runtime_info
+            .schema_mismatches
+            .insert("agent.event/tool_call".to_string(), 2);
+        app.runtime_info.launch = Some(RuntimeLaunchInfo {
+            program: "ssh".to_string(),
+            args: vec![
+                "-p".to_string(),
+                "2222".to_string(),
+                "dev@box".to_string(),
+                "codelia-runtime".to_string(),
+            ],
+            pid: 42,
+        });
+        let inspect = json!({
+            "runtime_working_dir": "/repo/api",
+            "runtime_sandbox_root": "/repo",
+            "runtime_pid": 7,
+            "enabled_tools": ["bash", "read"]
+        });
+
+        let markdown = runtime_report_markdown(&runtime_report_sections(&app, Some(&inspect)));
+        assert!(markdown.contains("- version: codelia-runtime 0.1.0 (protocol 0)\n"));
+        assert!(markdown.contains("- transport: ssh\n- host: dev@box\n"));
+        assert!(markdown.contains("- runtime pid: 7\n- working_dir: /repo/api\n"));
+        assert!(markdown.contains("### tools\n`bash`, `read`\n"));
+        assert!(markdown.contains("- supports_cwd_set: yes\n"));
+        assert!(markdown.contains("### schema mismatches\n- agent.event/tool_call: 2\n"));
+
+        let sections = runtime_report_sections(&app, None);
+        assert_eq!(
+            sections[1].rows[0].1,
+            "(not loaded yet; tools are built on the first run)"
+        );
+    }
+}
@@ -0,0 +1,382 @@
+use crate::app::handlers::cache::CACHE_SAVE_MAX_BYTES;
+use crate::app::handlers::stack_trace::workspace_root;
+use crate::app::runtime::RpcResponse;
+use crate::app::state::{LogKind, LogLine, LogTone};
+use crate::app::{
+    AppState, CacheListItem, CacheListPanelState, CacheReadAction, CachedOutputContext,
+    CachedOutputTarget,
+};
+use serde_json::Value;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const CACHE_PREVIEW_LINES: usize = 20;
+const CACHE_PANEL_HEADER: &str =
+    "Enter preview · a attach to prompt · s save · d delete · Esc close";
+
+fn extract_result(response: RpcResponse, label: &str) -> Result<Value, String> {
+    if let Some(error) = response.error {
+        return Err(format!("{label} error: {error}"));
+    }
+    response
+        .result
+        .ok_or_else(|| format!("{label} returned no result"))
+}
+
+fn format_age(age_ms: u64) -> String {
+    let seconds = age_ms / 1000;
+    if seconds < 60 {
+        format!("{seconds}s")
+    } else if seconds < 60 * 60 {
+        format!("{}m", seconds / 60)
+    } else if seconds < 24 * 60 * 60 {
+        format!("{}h", seconds / (60 * 60))
+    } else {
+        format!("{}d", seconds / (24 * 60 * 60))
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{bytes} B")
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+fn cache_panel_row(entry: &Value) -> Option<(String, CacheListItem)> {
+    let ref_id = entry.get("ref_id").and_then(Value::as_str)?.to_string();
+    let tool = entry
+        .get("tool")
+        .and_then(Value::as_str)
+        .unwrap_or("-")
+        .to_string();
+    let size = entry
+        .get("byte_size")
+        .and_then(Value::as_u64)
+        .map(format_size)
+        .unwrap_or_else(|| "-".to_string());
+    let lines = entry
+        .get("line_count")
+        .and_then(Value::as_u64)
+        .map(|count| count.to_string())
+        .unwrap_or_else(|| "-".to_string());
+    let age = entry
+        .get("age_ms")
+        .and_then(Value::as_u64)
+        .map(format_age)
+        .unwrap_or_else(|| "-".to_string());
+    let error_mark = if entry.get("is_error").and_then(Value::as_bool) == Some(true) {
+        " ✖"
+    } else {
+        ""
+    };
+    let row = format!("{age:>4} | {tool:<16} | {size:>9} | {lines:>6} | {ref_id}{error_mark}");
+    Some((row, CacheListItem { ref_id, tool }))
+}
+
+pub(super) fn handle_cache_list_response(app: &mut AppState, response: RpcResponse) {
+    let result = match extract_result(response, "cache.list") {
+        Ok(result) => result,
+        Err(error) => {
+            app.push_error_report("cache.list error", error);
+            return;
+        }
+    };
+    let (rows, items): (Vec<_>, Vec<_>) = result
+        .get("entries")
+        .and_then(Value::as_array)
+        .map(|entries| entries.iter().filter_map(cache_panel_row).unzip())
+        .unwrap_or_default();
+    if items.is_empty() {
+        app.cache_list_panel = None;
+        app.push_line(LogKind::Status, "No cached tool outputs in this session.");
+        return;
+    }
+    let previous = app.cache_list_panel.as_ref().map(|panel| panel.selected);
+    app.cache_list_panel = Some(CacheListPanelState {
+        title: format!("Tool output cache ({} in this session)", items.len()),
+        header: CACHE_PANEL_HEADER.to_string(),
+        rows,
+        selected: previous.unwrap_or(0).min(items.len() - 1),
+        items,
+    });
+}
+
+fn push_cache_preview(app: &mut AppState, target: &CachedOutputTarget, content: &str) {
+    let total = content.lines().count();
+    let mut lines = vec![LogLine::new(
+        LogKind::Status,
+        format!("Cached {} output {}", target.tool, target.ref_id),
+    )];
+    lines.extend(content.lines().take(CACHE_PREVIEW_LINES).map(|line| {
+        LogLine::new_with_tone(LogKind::ToolResult, LogTone::Detail, format!("  {line}"))
+    }));
+    if total > CACHE_PREVIEW_LINES {
+        lines.push(LogLine::new_with_tone(
+            LogKind::Status,
+            LogTone::Detail,
+            format!(
+                "  … {} more lines (a attach · s save in /cache)",
+                total - CACHE_PREVIEW_LINES
+            ),
+        ));
+    }
+    lines.push(LogLine::new(LogKind::Space, ""));
+    app.extend_lines(lines);
+}
+
+/// Names tried per save before giving up: `codelia-cache-<ref>.txt`, then `-2`, `-3`, ….
+const CACHE_SAVE_NAME_ATTEMPTS: usize = 100;
+
+/// Creates a new save file in `dir`; an existing file (another save, or two refs that map to
+/// the same safe name) is never overwritten, the name gets a numeric suffix instead.
+fn create_cache_save_file(dir: &Path, ref_id: &str) -> Result<(PathBuf, File), String> {
+    let safe: String = ref_id
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    for attempt in 1..=CACHE_SAVE_NAME_ATTEMPTS {
+        let suffix = if attempt == 1 {
+            String::new()
+        } else {
+            format!("-{attempt}")
+        };
+        let path = dir.join(format!("codelia-cache-{safe}{suffix}.txt"));
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(file) => return Ok((path, file)),
+            Err(error) if error.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(error) => return Err(format!("{}: {error}", path.display())),
+        }
+    }
+    Err(format!(
+        "codelia-cache-{safe}.txt and {} numbered variants already exist",
+        CACHE_SAVE_NAME_ATTEMPTS - 1
+    ))
+}
+
+fn save_cached_output(
+    app: &mut AppState,
+    target: &CachedOutputTarget,
+    content: &str,
+    truncated: bool,
+) {
+    // Relative to the session's working directory, which follows `/cd`, not the TUI's own.
+    let dir = workspace_root(app);
+    let saved = create_cache_save_file(&dir, &target.ref_id).and_then(|(path, mut file)| {
+        file.write_all(content.as_bytes())
+            .map(|()| path)
+            .map_err(|error| error.to_string())
+    });
+    let path = match saved {
+        Ok(path) => path,
+        Err(error) => {
+            app.push_error_report("cache save failed", error);
+            return;
+        }
+    };
+    app.push_line(
+        LogKind::Status,
+        format!("Saved cached {} output to {}", target.tool, path.display()),
+    );
+    if truncated {
+        app.push_line(
+            LogKind::Error,
+            format!(
+                "Only the first {} of {} was saved; the cached output is longer",
+                format_size(CACHE_SAVE_MAX_BYTES as u64),
+                target.ref_id
+            ),
+        );
+    }
+}
+
+pub(super) fn handle_cache_read_response(
+    app: &mut AppState,
+    target: CachedOutputTarget,
+    response: RpcResponse,
+) {
+    let result = match extract_result(response, "cache.read") {
+        Ok(result) => result,
+        Err(error) => {
+            app.push_error_report("cache.read error", error);
+            return;
+        }
+    };
+    let content = result
+        .get("content")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let truncated = result
+        .get("truncated")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let truncated_note = if truncated { " (truncated)" } else { "" };
+    match target.action {
+        CacheReadAction::Preview => push_cache_preview(app, &target, &content),
+        CacheReadAction::Attach => {
+            app.pending_cache_outputs
+                .retain(|output| output.ref_id != target.ref_id);
+            app.pending_cache_outputs.push(CachedOutputContext {
+                ref_id: target.ref_id.clone(),
+                tool: target.tool.clone(),
+                content,
+                truncated,
+            });
+            app.push_line(
+                LogKind::Status,
+                format!(
+                    "Attached cached {} output {}{truncated_note} to the next prompt",
+                    target.tool, target.ref_id
+                ),
+            );
+        }
+        CacheReadAction::Save => save_cached_output(app, &target, &content, truncated),
+    }
+}
+
+pub(super) fn handle_cache_delete_response(app: &mut AppState, response: RpcResponse) {
+    let result = match extract_result(response, "cache.delete") {
+        Ok(result) => result,
+        Err(error) => {
+            app.push_error_report("cache.delete error", error);
+            return;
+        }
+    };
+    let ref_id = result
+        .get("ref_id")
+        .and_then(Value::as_str)
+        .unwrap_or("-")
+        .to_string();
+    app.pending_cache_outputs
+        .retain(|output| output.ref_id != ref_id);
+    if let Some(panel) = app.cache_list_panel.as_mut() {
+        if let Some(index) = panel.items.iter().position(|item| item.ref_id == ref_id) {
+            panel.items.remove(index);
+            panel.rows.remove(index);
+            panel.selected = panel.selected.min(panel.items.len().saturating_sub(1));
+            panel.title = format!("Tool output cache ({} in this session)", panel.items.len());
+        }
+        if panel.items.is_empty() {
+            app.cache_list_panel = None;
+        }
+    }
+    app.push_line(LogKind::Status, format!("Deleted cached output {ref_id}"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn response(result: Value) -> RpcResponse {
+        RpcResponse {
+            id: "cache-1".to_string(),
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn cache_list_builds_panel_and_delete_removes_row() {
+        let mut app = AppState::default();
+        handle_cache_list_response(
+            &mut app,
+            response(json!({
+                "entries": [
+                    { "ref_id": "call_b", "tool": "bash", "byte_size": 2048, "line_count": 40, "age_ms": 90_000 },
+                    { "ref_id": "call_r", "tool": "read", "byte_size": 12, "line_count": 2, "age_ms": 5_000, "is_error": true }
+                ]
+            })),
+        );
+        let panel = app.cache_list_panel.as_ref().expect("panel");
+        assert_eq!(panel.items.len(), 2);
+        assert_eq!(
+            panel.rows[0],
+            "  1m | bash             |   2.0 KiB |     40 | call_b"
+        );
+        assert!(panel.rows[1].ends_with("call_r ✖"));
+
+        handle_cache_delete_response(
+            &mut app,
+            response(json!({ "ref_id": "call_b", "deleted": true })),
+        );
+        let panel = app.cache_list_panel.as_ref().expect("panel");
+        assert_eq!(panel.items.len(), 1);
+        assert_eq!(panel.items[0].ref_id, "call_r");
+    }
+
+    #[test]
+    fn cache_read_attaches_or_previews_content() {
+        let mut app = AppState::default();
+        let target = |action| CachedOutputTarget {
+            ref_id: "call_b".to_string(),
+            tool: "bash".to_string(),
+            action,
+        };
+        handle_cache_read_response(
+            &mut app,
+            target(CacheReadAction::Attach),
+            response(json!({ "ref_id": "call_b", "content": "ok", "truncated": false })),
+        );
+        assert_eq!(app.pending_cache_outputs.len(), 1);
+        assert_eq!(app.pending_cache_outputs[0].content, "ok");
+
+        let content = (1..=25)
+            .map(|index| format!("line {index}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        handle_cache_read_response(
+            &mut app,
+            target(CacheReadAction::Preview),
+            response(json!({ "ref_id": "call_b", "content": content, "truncated": false })),
+        );
+        let texts = app.log.iter().map(LogLine::plain_text).collect::<Vec<_>>();
+        assert!(texts.iter().any(|line| line == "  line 20"));
+        assert!(!texts.iter().any(|line| line == "  line 21"));
+        assert!(texts
+            .iter()
+            .any(|line| line == "  … 5 more lines (a attach · s save in /cache)"));
+    }
+
+    #[test]
+    fn cache_saves_never_overwrite_an_existing_file() {
+        let dir = std::env::temp_dir().join(format!("codelia-cache-save-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("dir");
+        let (first, _) = create_cache_save_file(&dir, "ref/1").expect("first");
+        let (second, _) = create_cache_save_file(&dir, "ref:1").expect("second");
+        assert_eq!(first, dir.join("codelia-cache-ref_1.txt"));
+        assert_eq!(second, dir.join("codelia-cache-ref_1-2.txt"));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn cache_save_lands_in_the_session_working_directory() {
+        let dir = std::env::temp_dir().join(format!("codelia-cache-cwd-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("dir");
+        let mut app = AppState::default();
+        app.runtime_info.working_dir = Some(dir.display().to_string());
+        handle_cache_read_response(
+            &mut app,
+            CachedOutputTarget {
+                ref_id: "call_b".to_string(),
+                tool: "bash".to_string(),
+                action: CacheReadAction::Save,
+            },
+            response(json!({ "ref_id": "call_b", "content": "ok", "truncated": false })),
+        );
+        let saved = dir.join("codelia-cache-call_b.txt");
+        assert_eq!(std::fs::read_to_string(&saved).expect("saved file"), "ok");
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
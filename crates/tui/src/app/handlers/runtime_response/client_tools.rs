@@ -379,7 +379,7 @@ fn focus_latest_kind(app: &mut AppState, kind: LogKind) -> bool {
     let Some(index) = app.log.iter().rposition(|line| line.kind() == kind) else {
         return false;
     };
-    app.scroll_from_bottom = app.log.len().saturating_sub(index.saturating_add(1));
+    app.scroll_to_log_line(index);
     true
 }
 
@@ -458,7 +458,7 @@ mod tests {
     use crate::app::runtime::ClientToolRequest;
     use crate::app::AppState;
     use serde_json::json;
-    use std::io::{BufWriter, Write};
+
     use std::process::Stdio;
 
     fn with_runtime_writer<T>(f: impl FnOnce(&mut RuntimeStdin) -> T) -> T {
@@ -480,9 +480,8 @@ mod tests {
             .spawn()
             .expect("spawn runtime writer");
         let stdin = child.stdin.take().expect("child stdin");
-        let mut writer = BufWriter::new(stdin);
+        let mut writer = RuntimeStdin::new(stdin);
         let out = f(&mut writer);
-        writer.flush().expect("flush runtime writer");
         drop(writer);
         let _ = child.kill();
         let _ = child.wait();
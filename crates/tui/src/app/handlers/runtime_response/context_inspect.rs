@@ -1,5 +1,9 @@
 use super::formatters::push_rpc_error;
 use super::panel_builders::format_context_file_row;
+use crate::app::handlers::context_pins::pinned_context_rows;
+use crate::app::handlers::runtime_report::finish_runtime_report;
+use crate::app::handlers::session_notes::session_note_rows;
+use crate::app::handlers::tools_panel::finish_tools_list;
 use crate::app::runtime::RpcResponse;
 use crate::app::{AppState, ContextPanelState};
 use serde_json::Value;
@@ -14,6 +18,26 @@ pub(super) fn handle_context_inspect_response(app: &mut AppState, response: RpcR
     }
 }
 
+pub(super) fn handle_runtime_report_response(
+    app: &mut AppState,
+    response: RpcResponse,
+    copy: bool,
+) {
+    if let Some(error) = response.error {
+        push_rpc_error(app, "context.inspect", &error);
+        return;
+    }
+    finish_runtime_report(app, response.result.as_ref(), copy);
+}
+
+pub(super) fn handle_tools_list_response(app: &mut AppState, response: RpcResponse) {
+    if let Some(error) = response.error {
+        push_rpc_error(app, "context.inspect", &error);
+        return;
+    }
+    finish_tools_list(app, response.result.as_ref());
+}
+
 fn apply_context_inspect_result(app: &mut AppState, result: &Value) {
     let mut rows = Vec::new();
 
@@ -186,10 +210,17 @@ fn apply_context_inspect_result(app: &mut AppState, result: &Value) {
     } else {
         rows.push("unavailable".to_string());
     }
+    rows.push(String::new());
+    rows.push("PINNED CONTEXT".to_string());
+    rows.extend(pinned_context_rows(app));
+    rows.push(String::new());
+    rows.push("SESSION NOTES".to_string());
+    rows.extend(session_note_rows(app));
     app.model_list_panel = None;
     app.reasoning_picker = None;
     app.session_list_panel = None;
     app.lane_list_panel = None;
+    app.mcp_list_panel = None;
     app.skills_list_panel = None;
     app.theme_list_panel = None;
     app.context_panel = Some(ContextPanelState {
@@ -1,4 +1,5 @@
 use crate::app::state::{LogKind, LogLine, LogSpan, LogTone};
+use crate::app::util::settings::ToolStyle;
 use crate::app::AppState;
 use serde_json::Value;
 use std::time::Duration;
@@ -195,6 +196,35 @@ pub(super) fn tool_call_summary_with_status_icon(summary: &str, is_error: bool)
     LogLine::new_with_spans(spans)
 }
 
+/// Recolors the tool label span and puts the configured icon in front of it.
+pub(super) fn with_tool_style(line: LogLine, style: Option<&ToolStyle>) -> LogLine {
+    let Some(style) = style else {
+        return line;
+    };
+    let Some(label_index) = line
+        .spans()
+        .iter()
+        .position(|span| span.kind == LogKind::ToolCall && !span.text.trim().is_empty())
+    else {
+        return line;
+    };
+    let mut spans = line.spans;
+    if let Some(color) = style.color {
+        spans[label_index].fg = Some(color);
+    }
+    if let Some(icon) = style.icon.as_deref() {
+        spans.insert(
+            label_index,
+            LogSpan::new(LogKind::ToolCall, LogTone::Summary, " "),
+        );
+        spans.insert(
+            label_index,
+            LogSpan::new_with_fg(LogKind::ToolCall, LogTone::Summary, icon, style.color),
+        );
+    }
+    LogLine::new_with_spans(spans)
+}
+
 pub(crate) fn truncate_bang_preview_line(value: &str) -> String {
     let char_count = value.chars().count();
     if char_count <= BANG_PREVIEW_MAX_LINE_CHARS {
@@ -1,11 +1,14 @@
+use crate::app::handlers::lane_attach::{apply_lane_tail_error, apply_lane_tail_result};
+use crate::app::handlers::panels::open_detail_panel;
+use crate::app::handlers::rpc_retry::offer_rpc_retry;
 use crate::app::runtime::{send_tool_call, RpcResponse};
 use crate::app::state::LogKind;
-use crate::app::{AppState, LaneListItem, LaneListPanelState};
+use crate::app::{AppState, ContextPanelState, LaneListItem, LaneListPanelState, StackedPanel};
 use serde_json::{json, Value};
 
 use super::RuntimeStdin;
 
-fn extract_tool_call_result(response: RpcResponse) -> Result<Value, String> {
+pub(super) fn extract_tool_call_result(response: RpcResponse) -> Result<Value, String> {
     if let Some(error) = response.error {
         return Err(error.to_string());
     }
@@ -49,13 +52,47 @@ pub(super) fn handle_lane_status_response(app: &mut AppState, response: RpcRespo
         .get("state")
         .and_then(|value| value.as_str())
         .unwrap_or("-");
+    let mux_live = result.get("mux_live").and_then(|value| value.as_bool());
+
+    // Opened from the lane list: show the status over it so `Esc` goes back to the list.
+    if let Some(list) = app.lane_list_panel.take() {
+        let mut rows = lane
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter_map(|(key, value)| match value {
+                Value::String(text) => Some(format!("{key}: {text}")),
+                Value::Bool(_) | Value::Number(_) => Some(format!("{key}: {value}")),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if let Some(mux_live) = mux_live {
+            rows.push(format!("mux_live: {mux_live}"));
+        }
+        let detail = ContextPanelState {
+            title: format!("Lane {lane_id}"),
+            header: "Esc back · Enter close".to_string(),
+            rows,
+            selected: 0,
+        };
+        open_detail_panel(app, StackedPanel::LaneList(list), detail);
+        return;
+    }
+
     app.push_line(LogKind::Status, format!("Lane {lane_id}: {state}"));
-    if let Some(mux_live) = result.get("mux_live").and_then(|value| value.as_bool()) {
+    if let Some(mux_live) = mux_live {
         app.push_line(LogKind::Status, format!("  mux_live={mux_live}"));
     }
     app.push_line(LogKind::Space, "");
 }
 
+pub(super) fn handle_lane_tail_response(app: &mut AppState, response: RpcResponse) {
+    match extract_tool_call_result(response) {
+        Ok(result) => apply_lane_tail_result(app, &result),
+        Err(error) => apply_lane_tail_error(app, error),
+    }
+}
+
 pub(super) fn handle_lane_close_response(
     app: &mut AppState,
     response: RpcResponse,
@@ -95,6 +132,7 @@ pub(super) fn handle_lane_create_response(
         Ok(result) => result,
         Err(error) => {
             app.push_error_report("lane_create error", error);
+            offer_rpc_retry(app);
             return;
         }
     };
@@ -168,6 +206,8 @@ pub(crate) fn apply_lane_list_result(app: &mut AppState, result: &Value) {
     app.theme_list_panel = None;
     app.prompt_dialog = None;
     app.pick_dialog = None;
+    app.mcp_list_panel = None;
+    app.panel_stack.clear();
     app.lane_list_panel = Some(LaneListPanelState {
         title: "Lanes".to_string(),
         header: "lane_id | task_id | state | mux".to_string(),
@@ -176,3 +216,48 @@ pub(crate) fn apply_lane_list_result(app: &mut AppState, result: &Value) {
         selected: 0,
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_lane_list_result, handle_lane_status_response};
+    use crate::app::handlers::panels::handle_context_panel_key;
+    use crate::app::runtime::RpcResponse;
+    use crate::app::AppState;
+    use crossterm::event::KeyCode;
+    use serde_json::json;
+
+    #[test]
+    fn lane_status_from_the_list_stacks_over_it_and_esc_goes_back() {
+        let mut app = AppState::default();
+        apply_lane_list_result(
+            &mut app,
+            &json!({ "lanes": [{ "lane_id": "lane-a", "task_id": "task-a", "state": "running" }] }),
+        );
+        handle_lane_status_response(
+            &mut app,
+            RpcResponse {
+                id: "id-1".to_string(),
+                result: Some(json!({
+                    "ok": true,
+                    "result": { "lane": { "lane_id": "lane-a", "state": "running" }, "mux_live": true }
+                })),
+                error: None,
+            },
+        );
+
+        assert!(app.lane_list_panel.is_none());
+        let detail = app.context_panel.as_ref().expect("lane detail");
+        assert_eq!(
+            app.panel_stack.breadcrumb(&detail.title),
+            "Lanes › Lane lane-a"
+        );
+        assert!(detail.rows.iter().any(|row| row == "mux_live: true"));
+
+        assert_eq!(handle_context_panel_key(&mut app, KeyCode::Esc), Some(true));
+        assert!(app.context_panel.is_none());
+        assert_eq!(
+            app.lane_list_panel.as_ref().map(|panel| panel.rows.len()),
+            Some(2)
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use super::formatters::push_rpc_error;
 use crate::app::runtime::RpcResponse;
 use crate::app::state::LogKind;
-use crate::app::AppState;
+use crate::app::{AppState, McpListPanelState};
 use serde_json::Value;
 
 pub(super) fn handle_mcp_list_response(
@@ -18,6 +18,64 @@ pub(super) fn handle_mcp_list_response(
     }
 }
 
+struct McpServerRow<'a> {
+    id: &'a str,
+    transport: &'a str,
+    source: &'a str,
+    enabled: bool,
+    state: &'a str,
+    tools: String,
+    last_error: Option<&'a str>,
+    last_connected_at: Option<&'a str>,
+}
+
+impl<'a> McpServerRow<'a> {
+    fn from_value(server: &'a Value, fallback_id: &'a str) -> Self {
+        let text = |key: &str| server.get(key).and_then(|value| value.as_str());
+        Self {
+            id: text("id").unwrap_or(fallback_id),
+            transport: text("transport").unwrap_or("-"),
+            source: text("source").unwrap_or("-"),
+            enabled: server
+                .get("enabled")
+                .and_then(|value| value.as_bool())
+                .unwrap_or(false),
+            state: text("state").unwrap_or("-"),
+            tools: server
+                .get("tools")
+                .and_then(|value| value.as_u64())
+                .map(|value| value.to_string())
+                .unwrap_or_else(|| "0".to_string()),
+            last_error: text("last_error"),
+            last_connected_at: text("last_connected_at"),
+        }
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "{} {} {} {} {} {}",
+            self.id, self.transport, self.source, self.enabled, self.state, self.tools
+        )
+    }
+
+    fn detail_rows(&self) -> Vec<String> {
+        let mut rows = vec![
+            format!("transport: {}", self.transport),
+            format!("source: {}", self.source),
+            format!("enabled: {}", self.enabled),
+            format!("state: {}", self.state),
+            format!("tools: {}", self.tools),
+        ];
+        if let Some(last_error) = self.last_error {
+            rows.push(format!("last_error: {last_error}"));
+        }
+        if let Some(last_connected_at) = self.last_connected_at {
+            rows.push(format!("last_connected_at: {last_connected_at}"));
+        }
+        rows
+    }
+}
+
 fn apply_mcp_list_result(app: &mut AppState, result: &Value, detail_id: Option<&str>) {
     let servers = result
         .get("servers")
@@ -44,45 +102,19 @@ fn apply_mcp_list_result(app: &mut AppState, result: &Value, detail_id: Option<&
             app.push_line(LogKind::Space, "");
             return;
         };
-        let id = server
-            .get("id")
-            .and_then(|value| value.as_str())
-            .unwrap_or(detail_id);
-        let transport = server
-            .get("transport")
-            .and_then(|value| value.as_str())
-            .unwrap_or("-");
-        let source = server
-            .get("source")
-            .and_then(|value| value.as_str())
-            .unwrap_or("-");
-        let enabled = server
-            .get("enabled")
-            .and_then(|value| value.as_bool())
-            .unwrap_or(false);
-        let state = server
-            .get("state")
-            .and_then(|value| value.as_str())
-            .unwrap_or("-");
-        let tools = server
-            .get("tools")
-            .and_then(|value| value.as_u64())
-            .map(|value| value.to_string())
-            .unwrap_or_else(|| "0".to_string());
-        app.push_line(LogKind::Status, format!("MCP {id}"));
+        let row = McpServerRow::from_value(server, detail_id);
+        app.push_line(LogKind::Status, format!("MCP {}", row.id));
         app.push_line(
             LogKind::Status,
             format!(
-                "  transport={transport} source={source} enabled={enabled} state={state} tools={tools}"
+                "  transport={} source={} enabled={} state={} tools={}",
+                row.transport, row.source, row.enabled, row.state, row.tools
             ),
         );
-        if let Some(last_error) = server.get("last_error").and_then(|value| value.as_str()) {
+        if let Some(last_error) = row.last_error {
             app.push_line(LogKind::Error, format!("  last_error={last_error}"));
         }
-        if let Some(last_connected_at) = server
-            .get("last_connected_at")
-            .and_then(|value| value.as_str())
-        {
+        if let Some(last_connected_at) = row.last_connected_at {
             app.push_line(
                 LogKind::Status,
                 format!("  last_connected_at={last_connected_at}"),
@@ -92,40 +124,32 @@ fn apply_mcp_list_result(app: &mut AppState, result: &Value, detail_id: Option<&
         return;
     }
 
-    app.push_line(
-        LogKind::Status,
-        "MCP servers: id transport source enabled state tools",
-    );
+    let mut rows = Vec::new();
+    let mut server_ids = Vec::new();
+    let mut details = Vec::new();
     for server in &servers {
-        let id = server
-            .get("id")
-            .and_then(|value| value.as_str())
-            .unwrap_or("-");
-        let transport = server
-            .get("transport")
-            .and_then(|value| value.as_str())
-            .unwrap_or("-");
-        let source = server
-            .get("source")
-            .and_then(|value| value.as_str())
-            .unwrap_or("-");
-        let enabled = server
-            .get("enabled")
-            .and_then(|value| value.as_bool())
-            .unwrap_or(false);
-        let state = server
-            .get("state")
-            .and_then(|value| value.as_str())
-            .unwrap_or("-");
-        let tools = server
-            .get("tools")
-            .and_then(|value| value.as_u64())
-            .map(|value| value.to_string())
-            .unwrap_or_else(|| "0".to_string());
-        app.push_line(
-            LogKind::Status,
-            format!("{id} {transport} {source} {enabled} {state} {tools}"),
-        );
+        let row = McpServerRow::from_value(server, "-");
+        rows.push(row.summary());
+        server_ids.push(row.id.to_string());
+        details.push(row.detail_rows());
     }
-    app.push_line(LogKind::Space, "");
+
+    app.model_list_panel = None;
+    app.reasoning_picker = None;
+    app.session_list_panel = None;
+    app.context_panel = None;
+    app.skills_list_panel = None;
+    app.theme_list_panel = None;
+    app.lane_list_panel = None;
+    app.prompt_dialog = None;
+    app.pick_dialog = None;
+    app.panel_stack.clear();
+    app.mcp_list_panel = Some(McpListPanelState {
+        title: "MCP servers".to_string(),
+        header: "id transport source enabled state tools".to_string(),
+        rows,
+        server_ids,
+        details,
+        selected: 0,
+    });
 }
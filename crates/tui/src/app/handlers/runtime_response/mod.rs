@@ -1,3 +1,4 @@
+mod cache;
 mod client_tools;
 mod context_inspect;
 mod formatters;
@@ -6,23 +7,26 @@ mod mcp;
 mod model;
 mod panel_builders;
 mod parsed_output;
+mod read_pages;
 mod run_control;
 mod session;
 mod skills;
+mod streaming;
 mod tasks;
 
 use self::formatters::push_rpc_error;
 use crate::app::handlers;
+use crate::app::handlers::rpc_retry::{retry_candidate, settle_retry_candidate};
+use crate::app::handlers::settings::set_runtime_motd;
 use crate::app::handlers::theme::apply_theme_from_name;
 use crate::app::runtime::{parse_runtime_output, RpcResponse};
 use crate::app::state::LogKind;
 use crate::app::{AppState, PendingRpcMatch};
-use std::io::BufWriter;
-use std::process::ChildStdin;
 use std::sync::mpsc::Receiver;
 use std::sync::mpsc::TryRecvError;
+use std::time::Instant;
 
-pub(crate) type RuntimeStdin = BufWriter<ChildStdin>;
+pub(crate) use crate::app::runtime::RuntimeStdin;
 pub(crate) type RuntimeReceiver = Receiver<String>;
 
 #[cfg(test)]
@@ -56,6 +60,7 @@ pub(crate) fn process_runtime_messages(
         match rx.try_recv() {
             Ok(line) => {
                 processed += 1;
+                app.runtime_watchdog.record_line(&line, Instant::now());
                 let parsed = parse_runtime_output(&line);
                 if parsed_output::apply_parsed_output(app, parsed, child_stdin, next_id) {
                     needs_redraw = true;
@@ -79,14 +84,38 @@ fn update_server_capabilities_from_response(app: &mut AppState, response: &RpcRe
         return;
     };
 
+    // A theme pinned in tui.toml takes precedence over the runtime config.
     if let Some(theme_name) = result
         .get("tui")
+        .filter(|_| app.settings.theme.is_none())
         .and_then(|value| value.as_object())
         .and_then(|tui| tui.get("theme"))
         .and_then(|value| value.as_str())
     {
         let _ = apply_theme_from_name(theme_name);
     }
+    if let Some(motd) = result
+        .get("tui")
+        .and_then(|tui| tui.get("motd"))
+        .and_then(|value| value.as_str())
+    {
+        set_runtime_motd(app, motd);
+    }
+
+    if let Some(server) = result.get("server").and_then(|value| value.as_object()) {
+        let field = |key: &str| {
+            server
+                .get(key)
+                .and_then(|value| value.as_str())
+                .map(str::to_string)
+        };
+        app.runtime_info.server_name = field("name");
+        app.runtime_info.server_version = field("version");
+        app.runtime_info.protocol_version = result
+            .get("protocol_version")
+            .and_then(|value| value.as_str())
+            .map(str::to_string);
+    }
 
     let Some(server_capabilities) = result
         .get("server_capabilities")
@@ -94,6 +123,7 @@ fn update_server_capabilities_from_response(app: &mut AppState, response: &RpcRe
     else {
         return;
     };
+    app.runtime_info.server_capabilities = server_capabilities.clone();
     if let Some(supports_mcp_list) = server_capabilities
         .get("supports_mcp_list")
         .and_then(|value| value.as_bool())
@@ -118,6 +148,12 @@ fn update_server_capabilities_from_response(app: &mut AppState, response: &RpcRe
     {
         app.runtime_info.supports_tool_call = supports_tool_call;
     }
+    if let Some(supports_tool_call_permission) = server_capabilities
+        .get("supports_tool_call_permission")
+        .and_then(|value| value.as_bool())
+    {
+        app.runtime_info.supports_tool_call_permission = supports_tool_call_permission;
+    }
     if let Some(supports_shell_exec) = server_capabilities
         .get("supports_shell_exec")
         .and_then(|value| value.as_bool())
@@ -136,6 +172,18 @@ fn update_server_capabilities_from_response(app: &mut AppState, response: &RpcRe
     {
         app.runtime_info.supports_shell_detach = supports_shell_detach;
     }
+    if let Some(supports_shell_env) = server_capabilities
+        .get("supports_shell_env")
+        .and_then(|value| value.as_bool())
+    {
+        app.runtime_info.supports_shell_env = supports_shell_env;
+    }
+    if let Some(supports_dry_run) = server_capabilities
+        .get("supports_dry_run")
+        .and_then(|value| value.as_bool())
+    {
+        app.runtime_info.supports_dry_run = supports_dry_run;
+    }
     if let Some(supports_tasks) = server_capabilities
         .get("supports_tasks")
         .and_then(|value| value.as_bool())
@@ -148,6 +196,24 @@ fn update_server_capabilities_from_response(app: &mut AppState, response: &RpcRe
     {
         app.runtime_info.supports_theme_set = supports_theme_set;
     }
+    if let Some(supports_cache) = server_capabilities
+        .get("supports_cache")
+        .and_then(|value| value.as_bool())
+    {
+        app.runtime_info.supports_cache = supports_cache;
+    }
+    if let Some(supports_cwd_set) = server_capabilities
+        .get("supports_cwd_set")
+        .and_then(|value| value.as_bool())
+    {
+        app.runtime_info.supports_cwd_set = supports_cwd_set;
+    }
+    if let Some(supports_disabled_tools) = server_capabilities
+        .get("supports_disabled_tools")
+        .and_then(|value| value.as_bool())
+    {
+        app.runtime_info.supports_disabled_tools = supports_disabled_tools;
+    }
 }
 
 fn handle_rpc_response(
@@ -157,6 +223,12 @@ fn handle_rpc_response(
     next_id: &mut impl FnMut() -> String,
 ) -> bool {
     update_server_capabilities_from_response(app, &response);
+    if let Some(sent) = child_stdin.take_sent_request(response.id.as_str()) {
+        app.perf_debug
+            .rpc_latency
+            .record(&sent.method, sent.latency().as_secs_f64() * 1000.0);
+        app.rpc_pending.retry_candidate = retry_candidate(&sent);
+    }
 
     if let Some(pending) = app
         .rpc_pending
@@ -182,6 +254,10 @@ fn handle_rpc_response(
             PendingRpcMatch::LaneCreate => {
                 lane::handle_lane_create_response(app, response, child_stdin, next_id)
             }
+            PendingRpcMatch::LaneTail => lane::handle_lane_tail_response(app, response),
+            PendingRpcMatch::ToolPaneList => tasks::handle_tool_pane_list_response(app, response),
+            PendingRpcMatch::ToolPaneLogs => tasks::handle_tool_pane_logs_response(app, response),
+            PendingRpcMatch::UsagePricing => model::handle_usage_pricing_response(app, response),
             PendingRpcMatch::SkillsList => skills::handle_skills_list_response(app, response),
             PendingRpcMatch::ContextInspect => {
                 context_inspect::handle_context_inspect_response(app, response)
@@ -198,13 +274,49 @@ fn handle_rpc_response(
             PendingRpcMatch::TaskList => tasks::handle_task_list_response(app, response),
             PendingRpcMatch::TaskStatus => tasks::handle_task_status_response(app, response),
             PendingRpcMatch::TaskCancel => tasks::handle_task_cancel_response(app, response),
+            PendingRpcMatch::PrCreate => run_control::handle_pr_create_response(app, response),
+            PendingRpcMatch::ReadPage { number } => {
+                read_pages::handle_read_page_response(app, number, response)
+            }
+            PendingRpcMatch::CacheList => cache::handle_cache_list_response(app, response),
+            PendingRpcMatch::CacheRead { target } => {
+                cache::handle_cache_read_response(app, target, response)
+            }
+            PendingRpcMatch::CacheDelete => cache::handle_cache_delete_response(app, response),
+            PendingRpcMatch::Ping { sent_at } => {
+                handlers::watchdog::handle_ping_response(app, sent_at)
+            }
+            PendingRpcMatch::Keepalive => {
+                handlers::connection_health::handle_keepalive_response(app)
+            }
+            PendingRpcMatch::FileSave { path } => {
+                handlers::file_artifacts::handle_file_save_response(app, &path, response)
+            }
+            PendingRpcMatch::EditMergeRead { tool_call_id } => {
+                handlers::edit_conflict::handle_edit_merge_read_response(
+                    app,
+                    &tool_call_id,
+                    response,
+                )
+            }
             PendingRpcMatch::ThemeSet => run_control::handle_theme_set_response(app, response),
+            PendingRpcMatch::ToolsList => {
+                context_inspect::handle_tools_list_response(app, response)
+            }
+            PendingRpcMatch::RuntimeReport { copy } => {
+                context_inspect::handle_runtime_report_response(app, response, copy)
+            }
+            PendingRpcMatch::CwdSet { prompt } => {
+                run_control::handle_cwd_set_response(app, response, prompt, child_stdin, next_id)
+            }
             PendingRpcMatch::RunStart => run_control::handle_run_start_response(app, response),
             PendingRpcMatch::RunCancel => run_control::handle_run_cancel_response(app, response),
         }
+        settle_retry_candidate(app);
         return true;
     }
 
+    app.rpc_pending.retry_candidate = None;
     if let Some(error) = response.error {
         push_rpc_error(app, "rpc", &error);
         return true;
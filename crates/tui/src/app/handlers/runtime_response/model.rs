@@ -1,5 +1,7 @@
 use super::formatters::push_rpc_error;
 use super::panel_builders::build_model_list_panel;
+use crate::app::handlers::rpc_retry::offer_rpc_retry;
+use crate::app::handlers::usage::record_model_pricing;
 use crate::app::runtime::RpcResponse;
 use crate::app::state::LogKind;
 use crate::app::{AppState, ModelListMode, ModelPickerState, ModelSetScope};
@@ -53,6 +55,7 @@ fn apply_model_list_result(
         .get("source")
         .and_then(|value| value.as_str())
         .map(|value| value.to_string());
+    record_model_pricing(&mut app.usage, result);
     if let Some(provider) = provider.clone() {
         app.runtime_info.current_provider = Some(provider);
     }
@@ -104,9 +107,17 @@ fn apply_model_list_result(
     ));
 }
 
+/// Prices for `/usage`; a failure only leaves the estimate out.
+pub(super) fn handle_usage_pricing_response(app: &mut AppState, response: RpcResponse) {
+    if let Some(result) = response.result {
+        record_model_pricing(&mut app.usage, &result);
+    }
+}
+
 pub(super) fn handle_model_set_response(app: &mut AppState, response: RpcResponse) {
     if let Some(error) = response.error {
         push_rpc_error(app, "model.set", &error);
+        offer_rpc_retry(app);
         return;
     }
     if let Some(result) = response.result {
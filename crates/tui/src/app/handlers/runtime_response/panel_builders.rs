@@ -1,11 +1,14 @@
 use super::formatters::truncate_text;
 use crate::app::runtime::UiPickRequest;
+use crate::app::util::text::truncate_start;
 use crate::app::{
     ModelListPanelState, ModelListSubmitAction, ModelListViewMode, ModelSetScope,
-    SessionListPanelState,
+    SessionListPanelState, WideListRows,
 };
 use serde_json::Value;
 
+const WIDE_SESSION_WORKSPACE_CHARS: usize = 32;
+
 pub(super) fn parse_onboarding_model_provider(title: &str) -> Option<String> {
     let prefix = "Select model (";
     if !title.starts_with(prefix) || !title.ends_with(')') {
@@ -97,6 +100,7 @@ pub(super) fn build_onboarding_model_list_panel(
         rows_limits,
         header_cost,
         rows_cost,
+        wide: None,
         model_ids,
         selected: 0,
         view_mode: ModelListViewMode::Limits,
@@ -131,6 +135,7 @@ pub(super) fn build_session_list_panel(
     current_workspace_root: Option<&str>,
 ) -> SessionListPanelState {
     let mut rows = Vec::new();
+    let mut wide_rows = Vec::new();
     let mut session_ids = Vec::new();
     for session in sessions {
         let session_id = session
@@ -156,8 +161,17 @@ pub(super) fn build_session_list_panel(
             .and_then(|value| value.as_str())
             .map(|value| value.replace('\n', " "))
             .unwrap_or_default();
-        let preview = truncate_text(preview.trim(), 72);
+        let workspace = session
+            .get("workspace_root")
+            .and_then(|value| value.as_str())
+            .map(|value| truncate_start(value.trim(), WIDE_SESSION_WORKSPACE_CHARS))
+            .unwrap_or_else(|| "-".to_string());
         let short_id: String = session_id.chars().take(8).collect();
+        wide_rows.push(format!(
+            "{updated} | {count:>4} | {session_id:<36} | {workspace:<WIDE_SESSION_WORKSPACE_CHARS$} | {}",
+            truncate_text(preview.trim(), 160)
+        ));
+        let preview = truncate_text(preview.trim(), 72);
         rows.push(format!("{updated} | {count:>4} | {short_id} | {preview}"));
         session_ids.push(session_id);
     }
@@ -184,6 +198,13 @@ pub(super) fn build_session_list_panel(
         title,
         header: "Updated (UTC)       | Msgs | Session | Preview".to_string(),
         rows,
+        wide: Some(WideListRows {
+            header: format!(
+                "Updated (UTC)       | Msgs | {:<36} | {:<WIDE_SESSION_WORKSPACE_CHARS$} | Preview",
+                "Session", "Workspace"
+            ),
+            rows: wide_rows,
+        }),
         session_ids,
         selected: 0,
         show_all,
@@ -289,6 +310,23 @@ pub(super) fn build_model_list_panel(
             )
         })
         .collect();
+    let provider_width = provider_label.chars().count().max("provider".len());
+    let wide_header = format!(
+        "  {:<name_width$}  {:<provider_width$}  {:>ctx_width$}  {:>input_width$}  {:>output_width$}  {:>cost_input_width$}  {:>cost_output_width$}",
+        "model", "provider", "ctx", "in", "out", "in$ /1M", "out$ /1M",
+    );
+    let wide_rows = rows
+        .iter()
+        .map(
+            |(model, ctx, input, output, cost_input, cost_output, is_current)| {
+                let marker = if *is_current { "*" } else { " " };
+                format!(
+                    "{marker} {:<name_width$}  {:<provider_width$}  {:>ctx_width$}  {:>input_width$}  {:>output_width$}  {:>cost_input_width$}  {:>cost_output_width$}",
+                    model, provider_label, ctx, input, output, cost_input, cost_output,
+                )
+            },
+        )
+        .collect();
     let rendered_rows_cost = rows
         .into_iter()
         .map(|(model, _, _, _, cost_input, cost_output, is_current)| {
@@ -306,6 +344,10 @@ pub(super) fn build_model_list_panel(
         rows_limits: rendered_rows_limits,
         header_cost,
         rows_cost: rendered_rows_cost,
+        wide: Some(WideListRows {
+            header: wide_header,
+            rows: wide_rows,
+        }),
         model_ids,
         selected,
         view_mode: ModelListViewMode::Limits,
@@ -1,13 +1,30 @@
 use super::formatters::{
     add_kind_spacing, format_duration, last_summary_kind, tool_call_summary_with_status_icon,
-    tool_call_with_status_icon,
+    tool_call_with_status_icon, with_tool_style,
 };
 use super::panel_builders::build_onboarding_model_list_panel;
+use super::streaming::{apply_assistant_delta, finish_streaming_assistant};
+use crate::app::handlers::command::{finish_dry_run, record_regenerated_answer};
 use crate::app::handlers::confirm::handle_confirm_request;
+use crate::app::handlers::edit_conflict::{offer_edit_merge, remember_edit_call};
+use crate::app::handlers::file_artifacts::offer_file_artifacts;
+use crate::app::handlers::forge::request_forge_references;
+use crate::app::handlers::json_output::finish_json_output;
+use crate::app::handlers::queue_panel::open_queue_review;
+use crate::app::handlers::run_tee::{
+    stop_run_tee, tee_assistant_delta, tee_assistant_message, tee_tool_summary,
+};
+use crate::app::handlers::run_trace::record_parsed_trace;
+use crate::app::handlers::translate::start_answer_translation;
+use crate::app::handlers::usage::apply_run_usage;
+use crate::app::render::graphics::{image_preview_lines, preview_protocol};
 use crate::app::runtime::{
-    ParsedOutput, PermissionReadyUpdate, ToolCallResultUpdate, UiPickRequest, UiPromptRequest,
+    ParsedOutput, PermissionReadyUpdate, SchemaMismatch, ToolCallResultUpdate, UiPickRequest,
+    UiPromptRequest,
 };
-use crate::app::state::{LogKind, LogLine, LogTone};
+use crate::app::state::{is_prompt_line, HunkReviewState, LogKind, LogLine, LogTone};
+use crate::app::util::attachments::image_media_type;
+use crate::app::util::notify::{should_notify_run_finished, terminal_focused};
 use crate::app::{
     AppState, LogComponentSpan, PickDialogItem, PickDialogState, PromptDialogState,
     PROMPT_DISPATCH_RETRY_BACKOFF,
@@ -166,18 +183,47 @@ fn tool_result_replacement_mode(tool: &str) -> ToolResultReplacementMode {
     }
 }
 
+/// Counts every mismatch but reports each method once, so a drifting runtime does not flood
+/// the log; `--debug` also shows the raw line each time.
+fn record_schema_mismatch(app: &mut AppState, mismatch: SchemaMismatch) {
+    let count = app
+        .runtime_info
+        .schema_mismatches
+        .entry(mismatch.method.clone())
+        .or_insert(0);
+    *count = count.saturating_add(1);
+    if *count == 1 {
+        app.push_error_report(
+            format!("runtime schema mismatch in {}", mismatch.method),
+            format!(
+                "{}\nFields fell back to defaults; counts are listed in /runtime.",
+                mismatch.problems.join("\n")
+            ),
+        );
+    }
+    if app.enable_debug_print {
+        app.push_line(
+            LogKind::Rpc,
+            format!("schema mismatch payload: {}", mismatch.raw),
+        );
+    }
+}
+
 pub(super) fn apply_parsed_output(
     app: &mut AppState,
     parsed: ParsedOutput,
     child_stdin: &mut RuntimeStdin,
     next_id: &mut impl FnMut() -> String,
 ) -> bool {
+    record_parsed_trace(app, &parsed);
     let ParsedOutput {
         lines,
         status,
         status_run_id,
         context_left_percent,
+        run_usage,
         assistant_text,
+        assistant_delta,
         final_text,
         rpc_response,
         confirm_request,
@@ -185,13 +231,30 @@ pub(super) fn apply_parsed_output(
         pick_request,
         client_tool_request,
         tool_call_start_id,
+        tool_call_start_tool,
+        tool_call_edit,
+        tool_call_command,
         tool_call_result,
         compaction_started,
         compaction_completed,
         permission_preview_update,
         permission_ready_update,
+        schema_mismatch,
     } = parsed;
+    if let Some(mismatch) = schema_mismatch {
+        record_schema_mismatch(app, mismatch);
+    }
+    // Taken before a terminal status clears the active run, so the closing lines keep it.
+    let line_run_id = status_run_id
+        .clone()
+        .or_else(|| app.runtime_info.active_run_id.clone());
+    let line_tool_call_id = tool_call_start_id.clone().or_else(|| {
+        tool_call_result
+            .as_ref()
+            .map(|update| update.tool_call_id.clone())
+    });
 
+    let mut finished_status: Option<String> = None;
     if let Some(status) = status {
         let terminal = matches!(status.as_str(), "completed" | "error" | "cancelled");
         if terminal {
@@ -203,6 +266,10 @@ pub(super) fn apply_parsed_output(
             app.runtime_info.active_run_id = None;
             app.permission_preview_by_tool_call.clear();
             app.permission_ready_tool_call_ids.clear();
+            app.pending_hunk_reviews.clear();
+            app.permission_tool_by_call.clear();
+            app.active_tool = None;
+            app.tool_pane.stop();
             let retry_at = Instant::now() + PROMPT_DISPATCH_RETRY_BACKOFF;
             match app.next_queue_dispatch_retry_at {
                 Some(current) if current >= retry_at => {}
@@ -211,6 +278,12 @@ pub(super) fn apply_parsed_output(
             if let Some(run_scope) = finished_run_scope {
                 clear_component_tracking_for_run(app, &run_scope);
             }
+            finish_dry_run(app, &status);
+            app.streaming_assistant = None;
+            // The run may have added files; `@` completion re-indexes on next use.
+            app.workspace_index = None;
+            app.mention_matches = None;
+            finished_status = Some(status.clone());
         } else if let Some(run_id) = status_run_id {
             app.runtime_info.active_run_id = Some(run_id);
         }
@@ -218,14 +291,52 @@ pub(super) fn apply_parsed_output(
     }
     if let Some(percent) = context_left_percent {
         app.context_left_percent = Some(percent);
+        app.context_history
+            .record(app.runtime_info.active_run_id.as_deref(), percent);
+    }
+    if let Some(usage) = run_usage {
+        apply_run_usage(app, usage, child_stdin, next_id);
+    }
+    if let Some(delta) = assistant_delta {
+        tee_assistant_delta(app, &delta);
+        apply_assistant_delta(app, &delta);
+    }
+    let mut lines = lines;
+    if let Some(content) = final_text.as_deref().or(assistant_text.as_deref()) {
+        if let Some(replacement) = finish_streaming_assistant(app, content) {
+            lines = replacement;
+        }
     }
     if let Some(text) = assistant_text {
+        tee_assistant_message(app, &text);
+        app.assistant_messages.record(&text);
         app.last_assistant_text = Some(text);
     }
 
-    let mut lines = lines;
+    let mut tool_outcome = None;
     let mut pending_component_starts: Vec<PendingComponentStart> = Vec::new();
     if let Some(update) = permission_preview_update {
+        app.tool_pane.append(&update.tool_call_id, &lines);
+        if app.dry_run_active {
+            app.planned_steps.push(match update.target.as_deref() {
+                Some(target) => format!("{}: {target}", update.tool),
+                None => update.tool.clone(),
+            });
+        }
+        match update.large_hunks {
+            Some(hunks) => {
+                app.pending_hunk_reviews.insert(
+                    update.tool_call_id.clone(),
+                    HunkReviewState {
+                        hunks,
+                        ..HunkReviewState::default()
+                    },
+                );
+            }
+            None => {
+                app.pending_hunk_reviews.remove(&update.tool_call_id);
+            }
+        }
         app.permission_preview_by_tool_call.insert(
             update.tool_call_id,
             crate::app::PermissionPreviewRecord {
@@ -235,7 +346,9 @@ pub(super) fn apply_parsed_output(
             },
         );
     }
-    if let Some(PermissionReadyUpdate { tool_call_id }) = permission_ready_update {
+    if let Some(PermissionReadyUpdate { tool_call_id, tool }) = permission_ready_update {
+        app.permission_tool_by_call
+            .insert(tool_call_id.clone(), tool);
         if !app.permission_ready_tool_call_ids.insert(tool_call_id) {
             lines.clear();
         }
@@ -246,10 +359,23 @@ pub(super) fn apply_parsed_output(
         is_error,
         fallback_summary,
         edit_diff_fingerprint,
+        output_ref_id,
+        images,
+        stale_read,
     }) = tool_call_result
     {
         let replacement_mode = tool_result_replacement_mode(&tool);
+        if app
+            .active_tool
+            .as_ref()
+            .is_some_and(|(active_id, _)| *active_id == tool_call_id)
+        {
+            app.active_tool = None;
+        }
         app.permission_ready_tool_call_ids.remove(&tool_call_id);
+        app.pending_hunk_reviews.remove(&tool_call_id);
+        app.permission_tool_by_call.remove(&tool_call_id);
+        tool_outcome = Some(!is_error);
         let preview = app.permission_preview_by_tool_call.remove(&tool_call_id);
         let suppress_edit_diff_lines = matches!(tool.as_str(), "edit" | "apply_patch")
             && preview.as_ref().is_some_and(|record| {
@@ -257,7 +383,14 @@ pub(super) fn apply_parsed_output(
                     && !record.truncated
                     && record.diff_fingerprint.as_deref() == edit_diff_fingerprint.as_deref()
             });
+        if !is_error {
+            if let Some(diff) = edit_diff_fingerprint.as_ref() {
+                app.run_artifacts.diffs.push(diff.clone());
+            }
+        }
         let mut inserted_fallback_summary = false;
+        let mut component_label = None;
+        let mut tee_summary = fallback_summary.plain_text();
         let run_scope = current_run_scope(app);
         let scoped_key = tool_component_key(&run_scope, &tool_call_id);
         let component_key = if app.pending_component_lines.contains_key(&scoped_key) {
@@ -269,17 +402,20 @@ pub(super) fn apply_parsed_output(
             if let Some(index) = take_component_line_index(app, &component_key) {
                 app.pending_component_lines.remove(&component_key);
                 if let Some(existing) = app.log.get(index).cloned() {
+                    component_label = Some(existing.plain_text());
                     let updated = match replacement_mode {
-                        ToolResultReplacementMode::UseFallbackSummary => {
+                        ToolResultReplacementMode::UseFallbackSummary => with_tool_style(
                             tool_call_summary_with_status_icon(
                                 &fallback_summary.plain_text(),
                                 is_error,
-                            )
-                        }
+                            ),
+                            app.settings.tool_styles.resolve(&tool),
+                        ),
                         ToolResultReplacementMode::KeepExistingSummary => {
                             tool_call_with_status_icon(&existing, is_error)
                         }
                     };
+                    tee_summary = updated.plain_text();
                     app.replace_log_line(index, updated);
                 } else {
                     lines.insert(0, fallback_summary);
@@ -297,9 +433,12 @@ pub(super) fn apply_parsed_output(
                 "✖ Read failed" => LogLine::new(LogKind::Error, "✖ Read failed"),
                 _ => fallback_summary,
             };
+            tee_summary = fallback.plain_text();
             lines.insert(0, fallback);
             inserted_fallback_summary = true;
         }
+        tee_tool_summary(app, &tee_summary);
+        app.tool_pane.finish(&tool_call_id, is_error, &lines);
         if suppress_edit_diff_lines {
             if inserted_fallback_summary {
                 lines.truncate(1);
@@ -307,6 +446,57 @@ pub(super) fn apply_parsed_output(
                 lines.clear();
             }
         }
+        if let Some(ref_id) = output_ref_id.filter(|_| tool == "read" && !is_error) {
+            let label = component_label.unwrap_or_else(|| "Read".to_string());
+            let number = app.register_read_page(ref_id, label);
+            lines.push(LogLine::new_with_tone(
+                LogKind::Status,
+                LogTone::Detail,
+                format!("  show more: /page {number}"),
+            ));
+        }
+        if let Some(line) = offer_edit_merge(app, child_stdin, next_id, &tool_call_id, stale_read) {
+            lines.push(line);
+        }
+        let protocol = preview_protocol(&app.settings, app.low_bandwidth);
+        for data_url in images {
+            lines.push(LogLine::new_with_tone(
+                LogKind::ToolResult,
+                LogTone::Detail,
+                format!(
+                    "  ▣ {} ({}KB)",
+                    image_media_type(&data_url),
+                    data_url.len() * 3 / 4 / 1024
+                ),
+            ));
+            lines.extend(image_preview_lines(
+                protocol,
+                &data_url,
+                LogKind::ToolResult,
+            ));
+        }
+    }
+    if let Some(style) = tool_call_start_tool
+        .as_deref()
+        .and_then(|tool| app.settings.tool_styles.resolve(tool))
+    {
+        if let Some(index) = lines
+            .iter()
+            .position(|line| line.kind() == LogKind::ToolCall)
+        {
+            lines[index] = with_tool_style(lines[index].clone(), Some(style));
+        }
+    }
+    if let (Some(tool_call_id), Some(tool)) = (
+        tool_call_start_id.as_deref(),
+        tool_call_start_tool.as_deref(),
+    ) {
+        app.active_tool = Some((tool_call_id.to_string(), tool.to_string()));
+        app.tool_pane
+            .start(tool_call_id, tool, tool_call_command, &lines);
+    }
+    if let (Some(tool_call_id), Some(edit)) = (tool_call_start_id.as_deref(), tool_call_edit) {
+        remember_edit_call(app, tool_call_id, edit);
     }
     if let Some(tool_call_id) = tool_call_start_id.as_deref() {
         let run_scope = current_run_scope(app);
@@ -325,9 +515,14 @@ pub(super) fn apply_parsed_output(
 
     let has_final = final_text.is_some();
     if let Some(final_text) = final_text {
+        record_regenerated_answer(app, &final_text);
         if app.last_assistant_text.as_deref() == Some(final_text.as_str()) {
             lines.clear();
         } else {
+            tee_assistant_message(app, &final_text);
+            request_forge_references(app, &final_text);
+            lines.extend(offer_file_artifacts(app, &final_text));
+            app.assistant_messages.record(&final_text);
             app.last_assistant_text = Some(final_text);
         }
     }
@@ -358,9 +553,40 @@ pub(super) fn apply_parsed_output(
     if has_final && !matches!(lines.last().map(LogLine::kind), Some(LogKind::Space)) {
         lines.push(LogLine::new(LogKind::Space, ""));
     }
+    for line in &mut lines {
+        if line.meta.run_id.is_none() {
+            line.meta.run_id = line_run_id.clone();
+        }
+        if line.meta.tool_call_id.is_none() {
+            line.meta.tool_call_id = line_tool_call_id.clone();
+        }
+    }
+    // Replayed history runs outside `start_prompt_run`, so its prompts open their turns here.
+    if app.rpc_pending.session_history_id.is_some() && lines.iter().any(is_prompt_line) {
+        app.begin_log_turn();
+    }
     let appended_from = app.log.len();
     app.extend_lines(lines);
     register_pending_component_lines(app, appended_from, pending_component_starts);
+    // Below the failing lines, so a streak banner reads after the failure that triggered it.
+    if let Some(ok) = tool_outcome {
+        app.record_outcome(ok);
+    }
+    // After the final answer lines so the check reads below the answer it refers to.
+    if let Some(status) = finished_status {
+        finish_json_output(app, &status);
+        stop_run_tee(app);
+        queue_run_notification(app, &status);
+        match status.as_str() {
+            "completed" => {
+                app.record_outcome(true);
+                start_answer_translation(app);
+                open_queue_review(app);
+            }
+            "error" => app.record_outcome(false),
+            _ => {}
+        }
+    }
 
     let mut needs_redraw = true;
     if let Some(response) = rpc_response {
@@ -388,6 +614,23 @@ pub(super) fn apply_parsed_output(
     needs_redraw
 }
 
+/// Desktop notification for a finished run, when the terminal is unfocused or the run was long.
+fn queue_run_notification(app: &mut AppState, status: &str) {
+    let duration = app.run_duration();
+    if !should_notify_run_finished(&app.settings, terminal_focused(), duration) {
+        return;
+    }
+    let outcome = match status {
+        "completed" => "Run completed",
+        "cancelled" => "Run cancelled",
+        _ => "Run failed",
+    };
+    app.pending_notification = Some(match duration {
+        Some(duration) => format!("{outcome} ({})", format_duration(duration)),
+        None => outcome.to_string(),
+    });
+}
+
 fn handle_prompt_request(app: &mut AppState, request: UiPromptRequest) {
     app.prompt_input.clear();
     if let Some(default_value) = request.default_value.as_deref() {
@@ -435,12 +678,13 @@ mod tests {
         register_pending_component_lines, resolve_tool_component_key, take_active_compaction_key,
         tool_component_key, PendingComponentStart, UNKNOWN_RUN_SCOPE,
     };
+    use crate::app::handlers::run_tee::start_run_tee;
     use crate::app::handlers::runtime_response::RuntimeStdin;
     use crate::app::runtime::parse_runtime_output;
-    use crate::app::state::{LogKind, LogLine};
-    use crate::app::{AppState, LogComponentSpan, PendingPromptRun};
+    use crate::app::state::{LogColor, LogKind, LogLine};
+    use crate::app::{AppState, LogComponentSpan, PendingPromptRun, QueuedAttachments};
     use serde_json::json;
-    use std::io::{BufWriter, Write};
+
     use std::process::Stdio;
     use std::time::Instant;
 
@@ -463,9 +707,8 @@ mod tests {
             .spawn()
             .expect("spawn runtime writer");
         let stdin = child.stdin.take().expect("child stdin");
-        let mut writer = BufWriter::new(stdin);
+        let mut writer = RuntimeStdin::new(stdin);
         let out = f(&mut writer);
-        writer.flush().expect("flush runtime writer");
         drop(writer);
         let _ = child.kill();
         let _ = child.wait();
@@ -485,6 +728,9 @@ mod tests {
                 attachment_count: 0,
                 shell_result_count: 0,
                 dispatch_attempts: 0,
+                dry_run: false,
+                json_request: None,
+                attachments: QueuedAttachments::default(),
             });
             app.runtime_info.active_run_id = Some("run-1".to_string());
             let parsed = parse_runtime_output(
@@ -500,6 +746,49 @@ mod tests {
         });
     }
 
+    #[test]
+    fn tee_appends_streamed_text_and_tool_summaries_until_the_run_ends() {
+        with_runtime_writer(|writer| {
+            let path =
+                std::env::temp_dir().join(format!("codelia-run-tee-{}.txt", std::process::id()));
+            let _ = std::fs::remove_file(&path);
+            let mut app = AppState::default();
+            app.runtime_info.active_run_id = Some("run-1".to_string());
+            start_run_tee(&mut app, &path.display().to_string());
+
+            let event = |event: serde_json::Value| {
+                json!({"method": "agent.event", "params": {"event": event}}).to_string()
+            };
+            for raw in [
+                event(json!({"type": "text_delta", "content": "Build "})),
+                event(json!({"type": "text_delta", "content": "started"})),
+                event(json!({
+                    "type": "tool_result", "tool": "read", "tool_call_id": "read-1",
+                    "is_error": false, "result": "fn main() {}"
+                })),
+                event(json!({"type": "text", "content": "Build started"})),
+                r#"{"method":"run.status","params":{"run_id":"run-1","status":"completed"}}"#
+                    .to_string(),
+            ] {
+                apply_parsed_output(&mut app, parse_runtime_output(&raw), writer, &mut || {
+                    "id-1".to_string()
+                });
+            }
+
+            assert!(app.run_tee.is_none());
+            let teed = std::fs::read_to_string(&path).expect("tee file");
+            let _ = std::fs::remove_file(&path);
+            let lines = teed.lines().collect::<Vec<_>>();
+            assert_eq!(lines.len(), 2, "{teed}");
+            assert_eq!(lines[0], "Build started");
+            assert!(lines[1].starts_with('✔'), "{teed}");
+            assert!(app
+                .log
+                .iter()
+                .any(|line| line.plain_text().starts_with("Tee to ")));
+        });
+    }
+
     #[test]
     fn shell_tool_result_replaces_pending_shell_call_with_fallback_summary() {
         with_runtime_writer(|writer| {
@@ -636,6 +925,44 @@ mod tests {
         });
     }
 
+    #[test]
+    fn read_tool_result_with_output_ref_registers_page_hint() {
+        with_runtime_writer(|writer| {
+            let mut app = AppState::default();
+            app.push_line(LogKind::ToolCall, "Read: crates/tui/src/main.rs");
+            app.pending_component_lines.insert(
+                tool_component_key(UNKNOWN_RUN_SCOPE, "read-result-2"),
+                LogComponentSpan::single(0),
+            );
+
+            let parsed = parse_runtime_output(
+                &json!({
+                    "jsonrpc": "2.0",
+                    "method": "agent.event",
+                    "params": {
+                        "event": {
+                            "type": "tool_result",
+                            "tool": "read",
+                            "tool_call_id": "read-result-2",
+                            "result": "    1  first line",
+                            "output_ref_id": "ref-read-2"
+                        }
+                    }
+                })
+                .to_string(),
+            );
+
+            assert!(apply_parsed_output(&mut app, parsed, writer, &mut || {
+                "id-1".to_string()
+            }));
+            assert_eq!(app.log[1].plain_text(), "  show more: /page 1");
+            let record = &app.read_pages[&1];
+            assert_eq!(record.ref_id, "ref-read-2");
+            assert_eq!(record.label, "Read: crates/tui/src/main.rs");
+            assert_eq!(record.next_offset, 0);
+        });
+    }
+
     #[test]
     fn apply_patch_tool_result_suppresses_duplicate_diff_after_permission_preview() {
         with_runtime_writer(|writer| {
@@ -673,6 +1000,84 @@ mod tests {
         });
     }
 
+    #[test]
+    fn replayed_prompts_open_numbered_turns() {
+        with_runtime_writer(|writer| {
+            let mut app = AppState::default();
+            app.rpc_pending.session_history_id = Some("h1".to_string());
+            for content in ["first", "second"] {
+                let event = json!({ "method": "agent.event", "params": { "event": {
+                    "type": "hidden_user_message",
+                    "content": content,
+                } } });
+                let parsed = parse_runtime_output(&event.to_string());
+                apply_parsed_output(&mut app, parsed, writer, &mut || "id".to_string());
+            }
+            let turns = crate::app::state::turn_starts(&app.log)
+                .iter()
+                .map(|start| start.turn)
+                .collect::<Vec<_>>();
+            assert_eq!(turns, vec![1, 2]);
+            assert_eq!(app.log_turn, 2);
+        });
+    }
+
+    #[test]
+    fn hunk_review_and_audit_tool_attach_to_the_confirm_of_their_own_tool_call() {
+        let large_diff = |hunks: usize| {
+            let mut diff = "--- a/big.txt\n+++ b/big.txt\n".to_string();
+            for hunk in 0..hunks {
+                diff.push_str(&format!("@@ -{0},400 +{0},400 @@\n", hunk * 1000 + 1));
+                for line in 0..400 {
+                    diff.push_str(&format!("-old {line}\n+new {line}\n"));
+                }
+            }
+            diff
+        };
+        let preview = |tool_call_id: &str, hunks: usize| {
+            json!({ "method": "agent.event", "params": { "event": {
+                "type": "permission.preview",
+                "tool": "edit",
+                "tool_call_id": tool_call_id,
+                "diff": large_diff(hunks),
+            } } })
+            .to_string()
+        };
+        with_runtime_writer(|writer| {
+            let mut app = AppState::default();
+            for (tool_call_id, tool, hunks) in [("call-a", "edit", 1), ("call-b", "write", 2)] {
+                let parsed = parse_runtime_output(&preview(tool_call_id, hunks));
+                apply_parsed_output(&mut app, parsed, writer, &mut || "id".to_string());
+                let ready = json!({ "method": "agent.event", "params": { "event": {
+                    "type": "permission.ready",
+                    "tool": tool,
+                    "tool_call_id": tool_call_id,
+                } } });
+                let parsed = parse_runtime_output(&ready.to_string());
+                apply_parsed_output(&mut app, parsed, writer, &mut || "id".to_string());
+            }
+            assert_eq!(app.pending_hunk_reviews.len(), 2);
+
+            let confirm = parse_runtime_output(
+                r#"{"jsonrpc":"2.0","id":"c1","method":"ui.confirm.request","params":{"title":"Edit file?","message":"edit big.txt","tool_call_id":"call-b"}}"#,
+            );
+            apply_parsed_output(&mut app, confirm, writer, &mut || "id".to_string());
+            let dialog = app.pending_confirm_dialog.as_ref().expect("confirm");
+            assert_eq!(dialog.hunk_review.as_ref().expect("review").hunks.len(), 2);
+            assert_eq!(dialog.tool.as_deref(), Some("write"));
+            assert_eq!(
+                app.permission_tool_by_call
+                    .get("call-a")
+                    .map(String::as_str),
+                Some("edit")
+            );
+            assert_eq!(
+                app.pending_hunk_reviews.keys().collect::<Vec<_>>(),
+                vec!["call-a"]
+            );
+        });
+    }
+
     #[test]
     fn permission_ready_replay_with_same_tool_call_id_is_suppressed() {
         with_runtime_writer(|writer| {
@@ -776,6 +1181,46 @@ mod tests {
         assert!(app.log.is_empty());
     }
 
+    #[test]
+    fn tool_call_line_uses_configured_tool_icon_and_color() {
+        with_runtime_writer(|writer| {
+            let mut app = AppState::default();
+            app.settings
+                .tool_styles
+                .set("bash", "icon", "$")
+                .expect("icon");
+            app.settings
+                .tool_styles
+                .set("bash", "color", "#ff8800")
+                .expect("color");
+            let parsed = parse_runtime_output(
+                &json!({
+                    "jsonrpc": "2.0",
+                    "method": "agent.event",
+                    "params": {
+                        "event": {
+                            "type": "tool_call",
+                            "tool": "bash",
+                            "tool_call_id": "bash-1",
+                            "args": { "command": "pwd" }
+                        }
+                    }
+                })
+                .to_string(),
+            );
+
+            apply_parsed_output(&mut app, parsed, writer, &mut || "id-1".to_string());
+            let line = app
+                .log
+                .iter()
+                .find(|line| line.kind() == LogKind::ToolCall)
+                .expect("tool call line");
+            assert!(line.plain_text().starts_with("$ Bash"));
+            assert_eq!(line.spans()[0].fg, Some(LogColor::rgb(0xff, 0x88, 0x00)));
+            assert_eq!(line.spans()[2].fg, Some(LogColor::rgb(0xff, 0x88, 0x00)));
+        });
+    }
+
     #[test]
     fn register_pending_component_lines_stores_tool_component_span() {
         let mut app = AppState::default();
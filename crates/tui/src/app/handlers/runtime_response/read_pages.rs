@@ -0,0 +1,160 @@
+use crate::app::runtime::RpcResponse;
+use crate::app::state::{LogKind, LogLine, LogTone};
+use crate::app::AppState;
+
+use super::lane::extract_tool_call_result;
+
+const OFFSET_EXCEEDED_MESSAGE: &str = "Offset exceeds output length.";
+const MORE_LINES_MARKER: &str = "Use offset to read beyond line ";
+
+#[derive(Debug, PartialEq, Eq)]
+struct CachePage {
+    /// Cached output lines with the cache's own line-number column stripped.
+    lines: Vec<String>,
+    /// Offset to request next; `None` once the cache reports no more lines.
+    next_offset: Option<usize>,
+}
+
+/// `tool_output_cache` numbers every line (`%5d  text`) and appends a
+/// "Use offset to read beyond line N." suffix when more lines remain.
+fn parse_cache_page(text: &str) -> Result<CachePage, String> {
+    if text.trim() == OFFSET_EXCEEDED_MESSAGE {
+        return Ok(CachePage {
+            lines: Vec::new(),
+            next_offset: None,
+        });
+    }
+    let mut lines = Vec::new();
+    let mut next_offset = None;
+    for line in text.lines() {
+        if let Some((_, rest)) = line.split_once(MORE_LINES_MARKER) {
+            next_offset = rest.trim_end_matches('.').trim().parse::<usize>().ok();
+            continue;
+        }
+        let trimmed = line.trim_start();
+        let digits = trimmed.len()
+            - trimmed
+                .trim_start_matches(|ch: char| ch.is_ascii_digit())
+                .len();
+        match trimmed[digits..].strip_prefix("  ") {
+            Some(content) if digits > 0 => lines.push(content.to_string()),
+            _ if digits > 0 && trimmed.len() == digits => lines.push(String::new()),
+            _ => {}
+        }
+    }
+    if lines.is_empty() && next_offset.is_none() {
+        return Err(text.trim().to_string());
+    }
+    Ok(CachePage { lines, next_offset })
+}
+
+pub(super) fn handle_read_page_response(app: &mut AppState, number: usize, response: RpcResponse) {
+    let page = match extract_tool_call_result(response) {
+        Ok(result) => match result.as_str() {
+            Some(text) => parse_cache_page(text),
+            None => Err(result.to_string()),
+        },
+        Err(error) => Err(error),
+    };
+    let page = match page {
+        Ok(page) => page,
+        Err(error) => {
+            app.push_error_report("read page error", error);
+            return;
+        }
+    };
+    let Some(record) = app.read_pages.get_mut(&number) else {
+        return;
+    };
+    let start = record.next_offset;
+    let end = start + page.lines.len();
+    record.next_offset = page.next_offset.unwrap_or(end).max(end);
+    record.exhausted = page.next_offset.is_none();
+    let label = record.label.clone();
+    let exhausted = record.exhausted;
+
+    let mut lines = Vec::with_capacity(page.lines.len() + 3);
+    let header = if page.lines.is_empty() {
+        format!("{label} · no more cached lines")
+    } else {
+        format!("{label} · cached lines {}-{end}", start + 1)
+    };
+    lines.push(LogLine::new(LogKind::Status, header));
+    lines.extend(
+        page.lines
+            .into_iter()
+            .map(|line| LogLine::new_with_tone(LogKind::ToolResult, LogTone::Detail, line)),
+    );
+    let footer = if exhausted {
+        "  end of output".to_string()
+    } else {
+        format!("  show more: /page {number}")
+    };
+    lines.push(LogLine::new_with_tone(
+        LogKind::Status,
+        LogTone::Detail,
+        footer,
+    ));
+    lines.push(LogLine::new(LogKind::Space, ""));
+    app.extend_lines(lines);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn page_response(text: &str) -> RpcResponse {
+        RpcResponse {
+            id: "page-1".to_string(),
+            result: Some(json!({ "ok": true, "result": text })),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn cache_page_strips_cache_numbers_and_reads_next_offset() {
+        let page = parse_cache_page(
+            "    1      10  fn main() {\n    2\n    3      12  }\n\nOutput has more lines. Use offset to read beyond line 3.",
+        )
+        .expect("page");
+        assert_eq!(page.lines, vec!["    10  fn main() {", "", "    12  }"]);
+        assert_eq!(page.next_offset, Some(3));
+
+        let end = parse_cache_page(OFFSET_EXCEEDED_MESSAGE).expect("end");
+        assert!(end.lines.is_empty());
+        assert_eq!(end.next_offset, None);
+
+        assert!(parse_cache_page("Error reading tool output cache: missing").is_err());
+    }
+
+    #[test]
+    fn read_page_response_appends_lines_and_advances_offset() {
+        let mut app = AppState::default();
+        let number = app.register_read_page("ref-1".to_string(), "Read: src/main.rs".to_string());
+
+        handle_read_page_response(
+            &mut app,
+            number,
+            page_response("    1       1  use std::io;\n\nOutput has more lines. Use offset to read beyond line 1."),
+        );
+        let texts = app.log.iter().map(LogLine::plain_text).collect::<Vec<_>>();
+        assert_eq!(texts[0], "Read: src/main.rs · cached lines 1-1");
+        assert_eq!(texts[1], "     1  use std::io;");
+        assert_eq!(texts[2], format!("  show more: /page {number}"));
+        assert_eq!(app.read_pages[&number].next_offset, 1);
+        assert!(!app.read_pages[&number].exhausted);
+
+        handle_read_page_response(
+            &mut app,
+            number,
+            page_response("    2       2  fn main() {}"),
+        );
+        assert_eq!(app.read_pages[&number].next_offset, 2);
+        assert!(app.read_pages[&number].exhausted);
+        assert!(app
+            .log
+            .iter()
+            .any(|line| line.plain_text() == "  end of output"));
+    }
+}
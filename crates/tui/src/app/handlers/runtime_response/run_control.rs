@@ -1,8 +1,11 @@
 use super::formatters::{push_bang_stream_preview, push_rpc_error};
 use super::RuntimeStdin;
-use crate::app::handlers::theme::apply_theme_from_name;
+use crate::app::handlers::command::start_prompt_run;
+use crate::app::handlers::rpc_retry::offer_rpc_retry;
+use crate::app::handlers::theme::{apply_theme_from_name, unpin_custom_theme};
 use crate::app::runtime::{send_shell_wait, RpcResponse};
 use crate::app::state::LogKind;
+use crate::app::util::forge::extract_pull_request_url;
 use crate::app::{
     AppState, PendingShellResult, PROMPT_DISPATCH_MAX_ATTEMPTS, PROMPT_DISPATCH_RETRY_BACKOFF,
 };
@@ -34,15 +37,15 @@ fn queue_pending_shell_result(
     let stdout = result
         .get("stdout")
         .and_then(|value| value.as_str())
-        .map(|value| value.to_string());
+        .map(|value| app.secrets.redact(value).into_owned());
     let stderr = result
         .get("stderr")
         .and_then(|value| value.as_str())
-        .map(|value| value.to_string());
+        .map(|value| app.secrets.redact(value).into_owned());
 
     let shell_result = PendingShellResult {
         id: format!("shell_{}", app.pending_shell_results.len() + 1),
-        command_preview,
+        command_preview: app.secrets.redact(&command_preview).into_owned(),
         exit_code,
         signal,
         duration_ms,
@@ -75,6 +78,7 @@ fn queue_pending_shell_result(
         .map(|code| code.to_string())
         .unwrap_or_else(|| "null".to_string());
     app.pending_shell_results.push(shell_result.clone());
+    app.run_artifacts.shell_results.push(shell_result.clone());
     app.push_line(
         LogKind::Status,
         format!(
@@ -138,6 +142,48 @@ pub(super) fn handle_shell_exec_response(app: &mut AppState, response: RpcRespon
     );
 }
 
+pub(super) fn handle_pr_create_response(app: &mut AppState, response: RpcResponse) {
+    if let Some(error) = response.error {
+        push_rpc_error(app, "shell.exec (/pr)", &error);
+        return;
+    }
+    let Some(result) = response.result else {
+        app.push_line(LogKind::Error, "shell.exec returned no result");
+        return;
+    };
+    let stdout = result
+        .get("stdout")
+        .and_then(|value| value.as_str())
+        .unwrap_or("");
+    let stderr = result
+        .get("stderr")
+        .and_then(|value| value.as_str())
+        .unwrap_or("");
+    let exit_code = result.get("exit_code").and_then(|value| value.as_i64());
+    if exit_code != Some(0) {
+        let exit_label = exit_code
+            .map(|code| code.to_string())
+            .unwrap_or_else(|| "null".to_string());
+        let detail = if stderr.trim().is_empty() {
+            stdout
+        } else {
+            stderr
+        };
+        app.push_error_report(
+            format!("pull request creation failed (exit={exit_label})"),
+            detail.to_string(),
+        );
+        return;
+    }
+    match extract_pull_request_url(stdout).or_else(|| extract_pull_request_url(stderr)) {
+        Some(url) => app.push_line(LogKind::Status, format!("Pull request opened: {url}")),
+        None => app.push_line(
+            LogKind::Status,
+            "Pull request command succeeded, but no URL was printed.",
+        ),
+    }
+}
+
 pub(super) fn handle_shell_start_response(
     app: &mut AppState,
     response: RpcResponse,
@@ -250,6 +296,8 @@ fn requeue_dispatching_prompt(app: &mut AppState, reason: &str) {
                     dispatching.queue_id, dispatching.dispatch_attempts
                 ),
             );
+            app.run_traces.retry(reason, Instant::now());
+            app.run_traces.status("error", Instant::now());
             app.push_line(
                 LogKind::Status,
                 format!("Queue size now {}", app.pending_prompt_queue.len()),
@@ -258,6 +306,7 @@ fn requeue_dispatching_prompt(app: &mut AppState, reason: &str) {
             return;
         }
         app.pending_prompt_queue.push_front(dispatching.clone());
+        app.run_traces.retry(reason, Instant::now());
         app.next_queue_dispatch_retry_at = Some(Instant::now() + PROMPT_DISPATCH_RETRY_BACKOFF);
         app.push_line(
             LogKind::Status,
@@ -293,6 +342,9 @@ pub(crate) fn handle_run_start_response(app: &mut AppState, response: RpcRespons
             app.push_line(LogKind::Error, "run.start returned no run_id");
             return;
         }
+        if let Some(run_id) = run_id.as_deref() {
+            app.run_traces.accepted(run_id, Instant::now());
+        }
         app.runtime_info.active_run_id = run_id;
         app.pending_shell_results.clear();
         app.dispatching_prompt = None;
@@ -351,6 +403,7 @@ pub(super) fn handle_logout_response(app: &mut AppState, response: RpcResponse)
 pub(super) fn handle_theme_set_response(app: &mut AppState, response: RpcResponse) {
     if let Some(error) = response.error {
         push_rpc_error(app, "theme.set", &error);
+        offer_rpc_retry(app);
         return;
     }
     let name = response
@@ -360,6 +413,7 @@ pub(super) fn handle_theme_set_response(app: &mut AppState, response: RpcRespons
         .and_then(|value| value.as_str())
         .unwrap_or("(unknown)");
     let _ = apply_theme_from_name(name);
+    unpin_custom_theme(app);
     let scope = response
         .result
         .as_ref()
@@ -378,6 +432,50 @@ pub(super) fn handle_theme_set_response(app: &mut AppState, response: RpcRespons
     );
 }
 
+pub(super) fn handle_cwd_set_response(
+    app: &mut AppState,
+    response: RpcResponse,
+    prompt: Option<String>,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) {
+    if let Some(error) = response.error {
+        push_rpc_error(app, "cwd.set", &error);
+        if let Some(prompt) = prompt {
+            if app.input.current().trim().is_empty() {
+                app.input.set_from(&prompt);
+                app.push_line(
+                    LogKind::Status,
+                    "Prompt not sent; it is back in the composer",
+                );
+            } else {
+                app.push_line(LogKind::Status, format!("Prompt not sent: {prompt}"));
+            }
+        }
+        return;
+    }
+    let result = response.result.as_ref();
+    let field = |key: &str| {
+        result
+            .and_then(|result| result.get(key))
+            .and_then(|value| value.as_str())
+            .map(str::to_string)
+    };
+    let Some(cwd) = field("cwd") else {
+        app.push_line(LogKind::Error, "cwd.set: missing cwd in response");
+        return;
+    };
+    app.runtime_info.sandbox_root = field("sandbox_root");
+    app.push_line(
+        LogKind::Status,
+        format!("Working directory for next runs: {cwd}"),
+    );
+    app.runtime_info.working_dir = Some(cwd);
+    if let Some(prompt) = prompt {
+        start_prompt_run(app, child_stdin, next_id, &prompt);
+    }
+}
+
 pub(super) fn handle_run_cancel_response(app: &mut AppState, response: RpcResponse) {
     if let Some(error) = response.error {
         push_rpc_error(app, "run.cancel", &error);
@@ -392,14 +490,18 @@ pub(super) fn handle_run_cancel_response(app: &mut AppState, response: RpcRespon
 
 #[cfg(test)]
 mod tests {
-    use super::{handle_shell_start_response, handle_shell_wait_response};
+    use super::{
+        handle_cwd_set_response, handle_pr_create_response, handle_shell_exec_response,
+        handle_shell_start_response, handle_shell_wait_response,
+    };
     use crate::app::runtime::RpcResponse;
+    use crate::app::runtime::RuntimeStdin;
+    use crate::app::util::secrets::SecretSource;
     use crate::app::AppState;
     use serde_json::json;
-    use std::io::{BufWriter, Write};
     use std::process::Stdio;
 
-    fn with_runtime_writer<T>(f: impl FnOnce(&mut BufWriter<std::process::ChildStdin>) -> T) -> T {
+    fn with_runtime_writer<T>(f: impl FnOnce(&mut RuntimeStdin) -> T) -> T {
         #[cfg(windows)]
         let mut command = {
             let mut command = std::process::Command::new("cmd");
@@ -417,15 +519,63 @@ mod tests {
             .expect("spawn runtime writer helper");
 
         let child_stdin = child.stdin.take().expect("child stdin");
-        let mut runtime_writer = BufWriter::new(child_stdin);
+        let mut runtime_writer = RuntimeStdin::new(child_stdin);
         let out = f(&mut runtime_writer);
 
-        let _ = runtime_writer.flush();
         let _ = child.kill();
         let _ = child.wait();
         out
     }
 
+    #[test]
+    fn pr_create_response_reports_url_without_queueing_shell_result() {
+        let mut app = AppState::default();
+        handle_pr_create_response(
+            &mut app,
+            RpcResponse {
+                id: "pr-1".to_string(),
+                result: Some(json!({
+                    "exit_code": 0,
+                    "stdout": "https://github.com/o/r/pull/12\n",
+                    "stderr": ""
+                })),
+                error: None,
+            },
+        );
+        assert!(app.pending_shell_results.is_empty());
+        assert_eq!(
+            app.log.last().map(|line| line.plain_text()).as_deref(),
+            Some("Pull request opened: https://github.com/o/r/pull/12")
+        );
+    }
+
+    #[test]
+    fn shell_exec_response_redacts_secret_values_in_log_and_queued_result() {
+        let mut app = AppState::default();
+        app.secrets
+            .set("API_TOKEN", "tok-123456".to_string(), SecretSource::Session);
+        handle_shell_exec_response(
+            &mut app,
+            RpcResponse {
+                id: "shell-1".to_string(),
+                result: Some(json!({
+                    "command_preview": "echo tok-123456",
+                    "exit_code": 0,
+                    "stdout": "tok-123456\n",
+                    "stderr": ""
+                })),
+                error: None,
+            },
+        );
+        let queued = &app.pending_shell_results[0];
+        assert_eq!(queued.command_preview, "echo [secret:API_TOKEN]");
+        assert_eq!(queued.stdout.as_deref(), Some("[secret:API_TOKEN]\n"));
+        assert!(app
+            .log
+            .iter()
+            .all(|line| !line.plain_text().contains("tok-123456")));
+    }
+
     #[test]
     fn shell_start_response_schedules_shell_wait_and_marks_active_task() {
         with_runtime_writer(|writer| {
@@ -504,4 +654,50 @@ mod tests {
             .plain_text()
             .contains("still running after the wait window")));
     }
+
+    #[test]
+    fn cwd_set_response_starts_held_prompt_or_returns_it_to_the_composer() {
+        with_runtime_writer(|writer| {
+            let mut app = AppState::default();
+            app.update_run_status("running".to_string());
+            let mut next_id = || "id-1".to_string();
+
+            handle_cwd_set_response(
+                &mut app,
+                RpcResponse {
+                    id: "cwd-1".to_string(),
+                    result: None,
+                    error: Some(json!({ "code": -32602, "message": "not a directory: /repo/x" })),
+                },
+                Some("fix the tests".to_string()),
+                writer,
+                &mut next_id,
+            );
+            assert_eq!(app.input.current(), "fix the tests");
+            assert!(app.runtime_info.working_dir.is_none());
+
+            app.input.clear();
+            handle_cwd_set_response(
+                &mut app,
+                RpcResponse {
+                    id: "cwd-2".to_string(),
+                    result: Some(json!({
+                        "cwd": "/repo/packages/api",
+                        "sandbox_root": "/repo"
+                    })),
+                    error: None,
+                },
+                Some("fix the tests".to_string()),
+                writer,
+                &mut next_id,
+            );
+            assert_eq!(
+                app.runtime_info.working_dir.as_deref(),
+                Some("/repo/packages/api")
+            );
+            assert_eq!(app.runtime_info.sandbox_root.as_deref(), Some("/repo"));
+            assert_eq!(app.pending_prompt_queue.len(), 1);
+            assert_eq!(app.pending_prompt_queue[0].preview, "fix the tests");
+        });
+    }
 }
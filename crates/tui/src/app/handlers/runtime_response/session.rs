@@ -1,5 +1,6 @@
 use super::formatters::push_rpc_error;
 use super::panel_builders::build_session_list_panel;
+use crate::app::handlers::permalink::apply_pending_turn_jump;
 use crate::app::runtime::RpcResponse;
 use crate::app::state::LogKind;
 use crate::app::AppState;
@@ -18,6 +19,7 @@ pub(super) fn handle_session_list_response(app: &mut AppState, response: RpcResp
 pub(super) fn handle_session_history_response(app: &mut AppState, response: RpcResponse) {
     if let Some(error) = response.error {
         push_rpc_error(app, "session.history", &error);
+        app.pending_turn_jump = None;
         return;
     }
     if let Some(result) = response.result {
@@ -45,6 +47,7 @@ pub(super) fn handle_session_history_response(app: &mut AppState, response: RpcR
         }
         app.push_line(LogKind::Space, "");
     }
+    apply_pending_turn_jump(app);
 }
 
 fn apply_session_list_result(app: &mut AppState, result: &Value) {
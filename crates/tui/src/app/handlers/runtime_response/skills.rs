@@ -120,11 +120,13 @@ fn apply_skills_list_result(app: &mut AppState, result: &Value) {
         ),
         header: String::new(),
         rows: Vec::new(),
+        wide: None,
         filtered_indices: Vec::new(),
         items,
         selected: 0,
         search_query: query,
         scope_filter,
+        applied_filter: None,
     };
     panel.rebuild();
     app.model_list_panel = None;
@@ -0,0 +1,119 @@
+use crate::app::runtime::assistant_message_lines;
+use crate::app::state::{LogLine, StreamingAssistant};
+use crate::app::AppState;
+
+/// Whether any row of log line `index` was already pushed into the terminal's scrollback
+/// (inline mode). Only a wrap cache from the current log version knows; without one no draw
+/// has happened since the last change, so nothing new can have been inserted.
+fn is_in_terminal_scrollback(app: &AppState, index: usize) -> bool {
+    app.wrapped_log_cache
+        .as_ref()
+        .filter(|cache| cache.log_version == app.log_version)
+        .and_then(|cache| cache.line_starts.get(index))
+        .is_some_and(|row| *row < app.render_state.inserted_until)
+}
+
+/// A continuation segment joins the frozen lines above it, so it drops the message spacer.
+fn segment_lines(text: &str, continuation: bool) -> Vec<LogLine> {
+    let mut lines = assistant_message_lines(text);
+    if continuation && !lines.is_empty() {
+        lines.remove(0);
+    }
+    lines
+}
+
+fn segment_is_replaceable(app: &AppState, stream: &StreamingAssistant) -> bool {
+    stream.is_tail(app.log.len())
+        && (stream.segment_len == 0 || !is_in_terminal_scrollback(app, stream.segment_start))
+}
+
+/// Appends a `text_delta` chunk and re-renders the live segment in place.
+pub(super) fn apply_assistant_delta(app: &mut AppState, delta: &str) {
+    let log_len = app.log.len();
+    let mut stream = app
+        .streaming_assistant
+        .take()
+        .unwrap_or_else(|| StreamingAssistant::new(log_len));
+    if !segment_is_replaceable(app, &stream) {
+        stream.freeze(log_len);
+    }
+    stream.text.push_str(delta);
+    let lines = segment_lines(stream.live_text(), stream.segment_from > 0);
+    stream.segment_len = lines.len();
+    app.replace_log_tail(stream.segment_start, lines);
+    app.streaming_assistant = Some(stream);
+}
+
+/// Ends the stream when the complete message (`text` or `final`) arrives. Returns the lines
+/// to append instead of the parser's, or `None` when the full rendering can be appended as
+/// usual because the streamed lines were removed.
+pub(super) fn finish_streaming_assistant(
+    app: &mut AppState,
+    content: &str,
+) -> Option<Vec<LogLine>> {
+    let stream = app.streaming_assistant.take()?;
+    let frozen = &stream.text[..stream.segment_from];
+    if segment_is_replaceable(app, &stream) {
+        app.replace_log_tail(stream.segment_start, Vec::new());
+        if stream.segment_from == 0 {
+            return None;
+        }
+        if let Some(rest) = content.strip_prefix(frozen) {
+            return Some(segment_lines(rest, true));
+        }
+        return None;
+    }
+    // Everything streamed so far stays; only text the deltas never delivered is added.
+    match content.strip_prefix(stream.text.as_str()) {
+        Some(rest) if !rest.trim().is_empty() => Some(segment_lines(rest, true)),
+        _ => Some(Vec::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_assistant_delta, finish_streaming_assistant};
+    use crate::app::runtime::assistant_message_lines;
+    use crate::app::state::{LogKind, LogLine};
+    use crate::app::AppState;
+
+    fn texts(lines: &[LogLine]) -> Vec<String> {
+        lines.iter().map(LogLine::plain_text).collect()
+    }
+
+    #[test]
+    fn deltas_re_render_in_place_and_the_final_replaces_them() {
+        let mut app = AppState::default();
+        app.push_line(LogKind::User, "> explain");
+        apply_assistant_delta(&mut app, "**Bold");
+        apply_assistant_delta(&mut app, "** start\n\n- one");
+        let shown = texts(&app.log[1..]);
+        assert_eq!(
+            shown,
+            texts(&assistant_message_lines("**Bold** start\n\n- one"))
+        );
+        assert!(shown.iter().all(|line| !line.contains("**")));
+
+        let full = "**Bold** start\n\n- one\n- two";
+        assert!(finish_streaming_assistant(&mut app, full).is_none());
+        assert_eq!(app.log.len(), 1);
+        assert!(app.streaming_assistant.is_none());
+    }
+
+    #[test]
+    fn lines_appended_mid_stream_freeze_what_was_shown() {
+        let mut app = AppState::default();
+        apply_assistant_delta(&mut app, "first part");
+        app.push_line(LogKind::Status, "tool started");
+        apply_assistant_delta(&mut app, " and more");
+        let shown = texts(&app.log);
+        assert!(shown.iter().any(|line| line.contains("first part")));
+        assert_eq!(shown[2], "tool started");
+        assert!(shown[3].contains("and more"));
+
+        let rest = finish_streaming_assistant(&mut app, "first part and more, done")
+            .expect("continuation lines");
+        assert_eq!(app.log.len(), 3);
+        assert!(texts(&rest)[0].contains("and more, done"));
+    }
+}
@@ -1,8 +1,13 @@
+use crate::app::handlers::tool_pane::{
+    apply_tool_pane_list_result, apply_tool_pane_logs_error, apply_tool_pane_logs_result,
+};
 use crate::app::runtime::RpcResponse;
 use crate::app::state::LogKind;
 use crate::app::AppState;
 use serde_json::Value;
 
+use super::lane::extract_tool_call_result;
+
 fn extract_result(response: RpcResponse, label: &str) -> Result<Value, String> {
     if let Some(error) = response.error {
         return Err(format!("{label} error: {error}"));
@@ -153,6 +158,20 @@ pub(super) fn handle_task_cancel_response(app: &mut AppState, response: RpcRespo
     app.push_line(LogKind::Space, "");
 }
 
+/// Split pane polls fail quietly: the pane keeps what it has and the next poll retries.
+pub(super) fn handle_tool_pane_list_response(app: &mut AppState, response: RpcResponse) {
+    if let Ok(result) = extract_tool_call_result(response) {
+        apply_tool_pane_list_result(app, &result);
+    }
+}
+
+pub(super) fn handle_tool_pane_logs_response(app: &mut AppState, response: RpcResponse) {
+    match extract_tool_call_result(response) {
+        Ok(result) => apply_tool_pane_logs_result(app, &result),
+        Err(_) => apply_tool_pane_logs_error(app),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
@@ -0,0 +1,139 @@
+use crate::app::state::LogKind;
+use crate::app::util::text::sanitize_paste;
+use crate::app::{AppState, ContextPanelState};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SCRATCHPAD_LABEL_MAX_CHARS: usize = 48;
+
+/// First non-blank line, shortened, so panel headings stay on one row.
+fn entry_label(kind: &str, text: &str) -> String {
+    let first = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    let mut preview = first
+        .chars()
+        .take(SCRATCHPAD_LABEL_MAX_CHARS)
+        .collect::<String>();
+    if first.chars().count() > SCRATCHPAD_LABEL_MAX_CHARS {
+        preview.push('…');
+    }
+    format!("{kind}: {preview}")
+}
+
+fn pin(app: &mut AppState, kind: &str, text: &str) {
+    let text = sanitize_paste(text);
+    if app.scratchpad.pin(entry_label(kind, &text), &text) {
+        let count = app.scratchpad.entries().len();
+        app.push_line(
+            LogKind::Status,
+            format!("Pinned to scratchpad ({count} entries, /scratch to view)"),
+        );
+        refresh_scratchpad_panel(app);
+    } else {
+        app.push_line(LogKind::Status, "Already pinned to scratchpad");
+    }
+}
+
+/// Alt+P and `/scratch pin`: keep the latest assistant answer.
+pub(crate) fn pin_last_answer(app: &mut AppState) {
+    match app.last_assistant_text.clone() {
+        Some(text) => pin(app, "answer", &text),
+        None => app.push_line(LogKind::Status, "No assistant answer to pin yet"),
+    }
+}
+
+pub(crate) fn pin_text(app: &mut AppState, text: &str) {
+    pin(app, "note", text);
+}
+
+pub(crate) fn open_scratchpad_panel(app: &mut AppState) {
+    if app.scratchpad.is_empty() {
+        app.push_line(
+            LogKind::Status,
+            "Scratchpad is empty. Pin the last answer with Alt+P or /scratch pin.",
+        );
+        return;
+    }
+    app.skills_list_panel = None;
+    app.theme_list_panel = None;
+    app.context_panel = Some(ContextPanelState {
+        title: format!("Scratchpad ({} entries)", app.scratchpad.entries().len()),
+        header: "/scratch export [file] · /scratch drop <n> · Esc close".to_string(),
+        rows: app.scratchpad.panel_rows(),
+        selected: 0,
+    });
+}
+
+/// Rebuilds an open scratchpad panel; other context panels are left alone.
+fn refresh_scratchpad_panel(app: &mut AppState) {
+    let showing = app
+        .context_panel
+        .as_ref()
+        .is_some_and(|panel| panel.title.starts_with("Scratchpad ("));
+    if !showing {
+        return;
+    }
+    if app.scratchpad.is_empty() {
+        app.context_panel = None;
+        return;
+    }
+    let selected = app
+        .context_panel
+        .as_ref()
+        .map(|panel| panel.selected)
+        .unwrap_or(0);
+    open_scratchpad_panel(app);
+    if let Some(panel) = app.context_panel.as_mut() {
+        panel.selected = selected.min(panel.rows.len().saturating_sub(1));
+    }
+}
+
+pub(crate) fn drop_scratchpad_entry(app: &mut AppState, number: usize) {
+    match app.scratchpad.remove(number) {
+        Some(entry) => {
+            app.push_line(
+                LogKind::Status,
+                format!("Removed scratchpad #{number} ({})", entry.label),
+            );
+            refresh_scratchpad_panel(app);
+        }
+        None => app.push_line(LogKind::Error, format!("no scratchpad entry #{number}")),
+    }
+}
+
+pub(crate) fn clear_scratchpad(app: &mut AppState) {
+    app.scratchpad.clear();
+    refresh_scratchpad_panel(app);
+    app.push_line(LogKind::Status, "Scratchpad cleared");
+}
+
+pub(crate) fn export_scratchpad(app: &mut AppState, target: Option<&str>) {
+    if app.scratchpad.is_empty() {
+        app.push_line(LogKind::Status, "Scratchpad is empty; nothing to export");
+        return;
+    }
+    let path = target.map(PathBuf::from).unwrap_or_else(|| {
+        let created_at_unix_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|value| value.as_millis())
+            .unwrap_or(0);
+        PathBuf::from(format!("codelia-scratchpad-{created_at_unix_ms}.md"))
+    });
+    match std::fs::write(&path, app.scratchpad.to_markdown()) {
+        Ok(()) => app.push_line(
+            LogKind::Status,
+            format!(
+                "Scratchpad exported to {} ({} entries)",
+                path.display(),
+                app.scratchpad.entries().len()
+            ),
+        ),
+        Err(error) => app.push_error_report(
+            "scratchpad export failed",
+            format!("{}: {error}", path.display()),
+        ),
+    }
+}
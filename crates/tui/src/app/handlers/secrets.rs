@@ -0,0 +1,194 @@
+use crate::app::state::LogKind;
+use crate::app::util::secrets::{
+    keychain_delete, keychain_store, save_secrets_index, SecretSource,
+};
+use crate::app::{AppState, PromptDialogState, SecretsPanelState};
+
+pub(crate) const SECRETS_SET_PROMPT_PREFIX: &str = "secrets:set:";
+pub(crate) const SECRETS_SET_KEYCHAIN_PROMPT_PREFIX: &str = "secrets:set-keychain:";
+
+fn secrets_panel_rows(app: &AppState) -> (Vec<String>, Vec<String>) {
+    let entries = app.secrets.entries();
+    let name_width = entries
+        .iter()
+        .map(|entry| entry.name.chars().count())
+        .max()
+        .unwrap_or(4)
+        .max(4);
+    let rows = entries
+        .iter()
+        .map(|entry| {
+            format!(
+                "[{}] {:<name_width$}  {:<8}  ********",
+                if entry.inject { "x" } else { " " },
+                entry.name,
+                entry.source.label(),
+            )
+        })
+        .collect();
+    let names = entries.iter().map(|entry| entry.name.clone()).collect();
+    (rows, names)
+}
+
+pub(crate) fn open_secrets_panel(app: &mut AppState) {
+    if app.secrets.is_empty() {
+        app.secrets_panel = None;
+        app.push_line(
+            LogKind::Status,
+            "No secrets stored. Add one with /secrets set <NAME> [--keychain].",
+        );
+        return;
+    }
+    let (rows, names) = secrets_panel_rows(app);
+    app.secrets_panel = Some(SecretsPanelState {
+        title: format!(
+            "Secrets ({} injected into ! commands)",
+            app.secrets.injected_count()
+        ),
+        header: "Space toggle inject · d delete · Esc close".to_string(),
+        rows,
+        names,
+        selected: 0,
+    });
+}
+
+/// Rebuilds rows after a change while keeping the selection in range.
+pub(crate) fn refresh_secrets_panel(app: &mut AppState) {
+    let Some(selected) = app.secrets_panel.as_ref().map(|panel| panel.selected) else {
+        return;
+    };
+    open_secrets_panel(app);
+    if let Some(panel) = app.secrets_panel.as_mut() {
+        panel.selected = selected.min(panel.rows.len().saturating_sub(1));
+    }
+}
+
+fn persist_secrets_index(app: &mut AppState) {
+    if let Err(error) = save_secrets_index(&app.secrets) {
+        app.push_error_report("secrets index save failed", error);
+    }
+}
+
+pub(crate) fn prompt_secret_value(app: &mut AppState, name: &str, keychain: bool) {
+    if app.prompt_dialog.is_some() {
+        app.push_line(LogKind::Status, "Another prompt is open; answer it first.");
+        return;
+    }
+    let prefix = if keychain {
+        SECRETS_SET_KEYCHAIN_PROMPT_PREFIX
+    } else {
+        SECRETS_SET_PROMPT_PREFIX
+    };
+    app.prompt_input.clear();
+    app.prompt_dialog = Some(PromptDialogState {
+        id: format!("{prefix}{name}"),
+        title: "Set secret".to_string(),
+        message: format!("Value for {name} (input hidden)"),
+        multiline: false,
+        secret: true,
+    });
+}
+
+/// Handles Enter on a local `/secrets set` prompt; returns false for other prompt ids.
+pub(crate) fn submit_secret_prompt(app: &mut AppState, prompt_id: &str, value: &str) -> bool {
+    let (name, keychain) = if let Some(name) = prompt_id.strip_prefix(SECRETS_SET_PROMPT_PREFIX) {
+        (name, false)
+    } else if let Some(name) = prompt_id.strip_prefix(SECRETS_SET_KEYCHAIN_PROMPT_PREFIX) {
+        (name, true)
+    } else {
+        return false;
+    };
+    if value.is_empty() {
+        app.push_line(
+            LogKind::Status,
+            format!("Secret {name} not changed (empty)."),
+        );
+        return true;
+    }
+    let mut source = SecretSource::Session;
+    if keychain {
+        match keychain_store(name, value) {
+            Ok(()) => source = SecretSource::Keychain,
+            Err(error) => {
+                app.push_error_report(format!("keychain store failed for {name}"), error);
+                app.push_line(
+                    LogKind::Status,
+                    format!("Keeping {name} for this session only."),
+                );
+            }
+        }
+    }
+    app.secrets.set(name, value.to_string(), source);
+    if source == SecretSource::Keychain {
+        persist_secrets_index(app);
+    }
+    app.push_line(
+        LogKind::Status,
+        format!("Stored secret {name} ({}).", source.label()),
+    );
+    refresh_secrets_panel(app);
+    true
+}
+
+pub(crate) fn is_local_secret_prompt(prompt_id: &str) -> bool {
+    prompt_id.starts_with(SECRETS_SET_PROMPT_PREFIX)
+        || prompt_id.starts_with(SECRETS_SET_KEYCHAIN_PROMPT_PREFIX)
+}
+
+pub(crate) fn remove_secret(app: &mut AppState, name: &str) {
+    let Some(entry) = app.secrets.remove(name) else {
+        app.push_line(LogKind::Error, format!("unknown secret: {name}"));
+        return;
+    };
+    if entry.source == SecretSource::Keychain {
+        if let Err(error) = keychain_delete(name) {
+            app.push_error_report(format!("keychain delete failed for {name}"), error);
+        }
+        persist_secrets_index(app);
+    }
+    app.push_line(LogKind::Status, format!("Removed secret {name}."));
+    refresh_secrets_panel(app);
+}
+
+pub(crate) fn set_secret_inject(app: &mut AppState, name: &str, inject: bool) {
+    if !app.secrets.set_inject(name, inject) {
+        app.push_line(LogKind::Error, format!("unknown secret: {name}"));
+        return;
+    }
+    let keychain = app
+        .secrets
+        .entries()
+        .iter()
+        .any(|entry| entry.name == name && entry.source == SecretSource::Keychain);
+    if keychain {
+        persist_secrets_index(app);
+    }
+    let state = if inject { "on" } else { "off" };
+    app.push_line(LogKind::Status, format!("Secret {name} injection {state}."));
+    refresh_secrets_panel(app);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{open_secrets_panel, submit_secret_prompt, SECRETS_SET_PROMPT_PREFIX};
+    use crate::app::AppState;
+
+    #[test]
+    fn secret_prompt_stores_value_without_logging_it() {
+        let mut app = AppState::default();
+        let prompt_id = format!("{SECRETS_SET_PROMPT_PREFIX}API_TOKEN");
+        assert!(submit_secret_prompt(&mut app, &prompt_id, "hunter2-value"));
+        assert!(!submit_secret_prompt(&mut app, "runtime-prompt", "x"));
+        assert_eq!(app.secrets.injected_count(), 1);
+        assert!(app
+            .log
+            .iter()
+            .all(|line| !line.plain_text().contains("hunter2-value")));
+
+        open_secrets_panel(&mut app);
+        let panel = app.secrets_panel.as_ref().expect("panel");
+        assert_eq!(panel.names, vec!["API_TOKEN".to_string()]);
+        assert!(panel.rows[0].starts_with("[x] API_TOKEN"));
+        assert!(!panel.rows[0].contains("hunter2"));
+    }
+}
@@ -0,0 +1,126 @@
+use crate::app::state::{LogKind, NoteExpiry, SessionNote};
+use crate::app::{AppState, ContextPanelState};
+use std::time::Instant;
+
+const NOTES_PANEL_TITLE_PREFIX: &str = "Session notes (";
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+fn expired_names(expired: &[SessionNote]) -> String {
+    expired
+        .iter()
+        .map(|note| format!("#{}", note.id))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub(crate) fn add_session_note(app: &mut AppState, text: &str, expiry: NoteExpiry) {
+    let Some(id) = app.session_notes.add(text, expiry) else {
+        app.push_line(LogKind::Error, "note text is empty");
+        return;
+    };
+    let until = match expiry {
+        NoteExpiry::Never => "for this session".to_string(),
+        NoteExpiry::Turns(turns) => format!("for the next {turns} prompt{}", plural(turns)),
+        NoteExpiry::At(_) => "until it expires".to_string(),
+    };
+    app.push_line(
+        LogKind::Status,
+        format!("Noted #{id}; sent with every prompt {until} (/note to view)"),
+    );
+    refresh_notes_panel(app);
+}
+
+pub(crate) fn open_notes_panel(app: &mut AppState) {
+    let now = Instant::now();
+    app.session_notes.expire(now);
+    if app.session_notes.is_empty() {
+        app.push_line(
+            LogKind::Status,
+            "No session notes. /note <text> sends a short note with every prompt.",
+        );
+        return;
+    }
+    app.skills_list_panel = None;
+    app.theme_list_panel = None;
+    let count = app.session_notes.notes().len();
+    app.context_panel = Some(ContextPanelState {
+        title: format!("{NOTES_PANEL_TITLE_PREFIX}{count} note{})", plural(count)),
+        header: "/note drop <id> · /note clear · Esc close".to_string(),
+        rows: app.session_notes.rows(now),
+        selected: 0,
+    });
+}
+
+/// Rebuilds an open notes panel; other context panels are left alone.
+fn refresh_notes_panel(app: &mut AppState) {
+    let showing = app
+        .context_panel
+        .as_ref()
+        .is_some_and(|panel| panel.title.starts_with(NOTES_PANEL_TITLE_PREFIX));
+    if !showing {
+        return;
+    }
+    if app.session_notes.is_empty() {
+        app.context_panel = None;
+        return;
+    }
+    let selected = app
+        .context_panel
+        .as_ref()
+        .map(|panel| panel.selected)
+        .unwrap_or(0);
+    open_notes_panel(app);
+    if let Some(panel) = app.context_panel.as_mut() {
+        panel.selected = selected.min(panel.rows.len().saturating_sub(1));
+    }
+}
+
+pub(crate) fn drop_session_note(app: &mut AppState, id: usize) {
+    match app.session_notes.remove(id) {
+        Some(_) => {
+            app.push_line(LogKind::Status, format!("Dropped note #{id}"));
+            refresh_notes_panel(app);
+        }
+        None => app.push_line(LogKind::Error, format!("no note #{id}")),
+    }
+}
+
+pub(crate) fn clear_session_notes(app: &mut AppState) {
+    app.session_notes.clear();
+    refresh_notes_panel(app);
+    app.push_line(LogKind::Status, "Session notes cleared");
+}
+
+/// Called once a prompt carrying the notes is queued.
+pub(crate) fn consume_note_turn(app: &mut AppState) {
+    let mut expired = app.session_notes.consume_turn();
+    expired.extend(app.session_notes.expire(Instant::now()));
+    if expired.is_empty() {
+        return;
+    }
+    app.push_line(
+        LogKind::Status,
+        format!(
+            "Session note{} expired: {}",
+            plural(expired.len()),
+            expired_names(&expired)
+        ),
+    );
+    refresh_notes_panel(app);
+}
+
+/// Rows for the `/context` snapshot.
+pub(crate) fn session_note_rows(app: &AppState) -> Vec<String> {
+    let rows = app.session_notes.rows(Instant::now());
+    if rows.is_empty() {
+        return vec!["(none)".to_string()];
+    }
+    rows
+}
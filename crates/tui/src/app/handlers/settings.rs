@@ -0,0 +1,259 @@
+use crate::app::markdown::set_syntax_highlighting;
+use crate::app::render::glyphs::set_glyph_mode;
+use crate::app::state::{
+    set_ansi_color_passthrough, LogKind, LogLine, LogTone, SLOW_FRAME_BUDGET_MS,
+};
+use crate::app::theme::apply_theme_id;
+use crate::app::util::input_log::set_input_recording;
+use crate::app::util::settings::{
+    find_setting, is_motd_dismissed, save_motd_dismissed, save_setting, settings_path,
+    SettingsWatcher, TuiSettings, SETTING_SPECS,
+};
+use crate::app::util::text::sanitize_for_tui;
+use crate::app::AppState;
+use std::time::{Duration, Instant};
+
+/// Installs settings loaded at startup; file problems are reported but never fatal.
+pub(crate) fn apply_startup_settings(
+    app: &mut AppState,
+    settings: TuiSettings,
+    warnings: Vec<String>,
+) {
+    if let Some(theme) = settings.theme.as_deref() {
+        apply_theme_id(theme);
+    }
+    app.set_error_detail_mode(settings.verbosity);
+    set_ansi_color_passthrough(settings.ansi_colors);
+    set_input_recording(settings.record_input);
+    set_glyph_mode(settings.glyphs);
+    app.settings = settings;
+    sync_effects(app);
+    app.settings_watcher = SettingsWatcher::new(settings_path());
+    push_settings_warnings(app, warnings);
+}
+
+fn push_settings_warnings(app: &mut AppState, warnings: Vec<String>) {
+    for warning in warnings {
+        app.push_line(LogKind::Error, format!("settings: {warning}"));
+    }
+}
+
+fn push_setting_rows(app: &mut AppState, keys: &[&str]) {
+    let lines = keys
+        .iter()
+        .filter_map(|key| {
+            let spec = find_setting(key)?;
+            let value = app.settings.get(key)?;
+            Some(LogLine::new_with_tone(
+                LogKind::Status,
+                LogTone::Detail,
+                format!("  {:<14} {:<10} {}", spec.key, value, spec.summary),
+            ))
+        })
+        .collect::<Vec<_>>();
+    app.extend_lines(lines);
+}
+
+pub(crate) fn show_settings(app: &mut AppState, key: Option<&str>) {
+    if let Some(key) = key {
+        if find_setting(key).is_none() {
+            app.push_line(LogKind::Error, format!("unknown setting: {key}"));
+            return;
+        }
+        push_setting_rows(app, &[key]);
+        return;
+    }
+    let path = settings_path()
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|| "(no config directory)".to_string());
+    app.push_line(LogKind::Status, format!("Settings ({path}):"));
+    let keys = SETTING_SPECS
+        .iter()
+        .map(|spec| spec.key)
+        .collect::<Vec<_>>();
+    push_setting_rows(app, &keys);
+}
+
+/// Applies the live part of a changed setting; returns a note when a restart is needed.
+fn apply_setting_now(app: &mut AppState, key: &str) -> Option<&'static str> {
+    match key {
+        "theme" => match app.settings.theme.as_deref() {
+            Some(theme) => {
+                apply_theme_id(theme);
+            }
+            None => return Some("restart to return to the runtime theme"),
+        },
+        "verbosity" => app.set_error_detail_mode(app.settings.verbosity),
+        "mouse_capture" => app.mouse_capture_enabled = app.settings.mouse_capture,
+        "reduce_effects" => sync_effects(app),
+        "ansi_colors" => {
+            set_ansi_color_passthrough(app.settings.ansi_colors);
+            return Some("applies to new tool output");
+        }
+        "record_input" => set_input_recording(app.settings.record_input),
+        "glyphs" => set_glyph_mode(app.settings.glyphs),
+        "alt_screen" | "banner" | "update_check" => return Some("applies on restart"),
+        "tools" => return Some("applies to new tool calls"),
+        "image_preview" if app.low_bandwidth => {
+            return Some("image previews stay off in low-bandwidth mode")
+        }
+        "image_preview" => return Some("applies to new images"),
+        "keys" => return Some("applies to new dialogs"),
+        _ => {}
+    }
+    None
+}
+
+pub(crate) fn set_setting(app: &mut AppState, key: &str, value: &str) {
+    if let Err(error) = app.settings.set(key, value) {
+        app.push_line(LogKind::Error, error);
+        return;
+    }
+    let note = apply_setting_now(app, key);
+    let shown = app.settings.get(key).unwrap_or_default();
+    let message = match note {
+        Some(note) => format!("Set {key} = {shown} ({note})."),
+        None => format!("Set {key} = {shown}."),
+    };
+    app.push_line(LogKind::Status, message);
+    match save_setting(&app.settings, key) {
+        Ok(_) => app.settings_watcher.mark_current(),
+        Err(error) => app.push_error_report("settings save failed", error),
+    }
+}
+
+/// Hot-applies external edits to tui.toml; returns true when the UI changed.
+pub(crate) fn reload_settings_if_changed(app: &mut AppState) -> bool {
+    let Some((settings, warnings)) = app.settings_watcher.poll(Instant::now()) else {
+        return false;
+    };
+    let changed = app.settings.changed_keys(&settings);
+    let had_warnings = !warnings.is_empty();
+    push_settings_warnings(app, warnings);
+    if changed.is_empty() {
+        return had_warnings;
+    }
+    app.settings = settings;
+    apply_changed_settings(app, &changed);
+    true
+}
+
+fn apply_changed_settings(app: &mut AppState, changed: &[&str]) {
+    let mut notes = Vec::new();
+    for key in changed {
+        if let Some(note) = apply_setting_now(app, key) {
+            notes.push(format!("{key}: {note}"));
+        }
+    }
+    let mut message = format!("Config reloaded (tui.toml): {}", changed.join(", "));
+    if !notes.is_empty() {
+        message.push_str(&format!(" ({})", notes.join("; ")));
+    }
+    app.push_line(LogKind::Status, message);
+}
+
+pub(crate) fn sync_effects(app: &AppState) {
+    set_syntax_highlighting(!app.effects_reduced());
+}
+
+/// Feeds one frame's draw+flush time to slow-terminal detection; returns true when effects changed.
+pub(crate) fn record_frame_time(app: &mut AppState, frame: Duration) -> bool {
+    let Some(median_ms) = app.slow_terminal.record(frame.as_secs_f64() * 1000.0) else {
+        return false;
+    };
+    if app.settings.reduce_effects.is_some() || app.low_bandwidth {
+        return false;
+    }
+    sync_effects(app);
+    let message = if app.slow_terminal.detected {
+        format!(
+            "Slow terminal detected (median frame {median_ms:.0}ms > {SLOW_FRAME_BUDGET_MS:.0}ms): reducing effects (plain code blocks, slower spinner, batched redraws). `/set reduce_effects off` keeps full effects."
+        )
+    } else {
+        format!("Terminal keeps up again (median frame {median_ms:.0}ms): effects restored.")
+    };
+    app.push_line(LogKind::Status, message);
+    true
+}
+
+/// Shows the runtime-provided message of the day unless this exact text was dismissed before.
+pub(crate) fn set_runtime_motd(app: &mut AppState, text: &str) {
+    let text = sanitize_for_tui(text.trim());
+    if text.is_empty() || is_motd_dismissed(&text) {
+        return;
+    }
+    app.motd = Some(text);
+}
+
+pub(crate) fn dismiss_motd(app: &mut AppState) {
+    let Some(text) = app.motd.take() else {
+        return;
+    };
+    if let Err(error) = save_motd_dismissed(&text) {
+        app.push_error_report("motd dismiss save failed", error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_changed_settings, apply_setting_now};
+    use crate::app::{AppState, ErrorDetailMode};
+
+    #[test]
+    fn slow_frames_reduce_effects_unless_overridden() {
+        let mut app = AppState::default();
+        for _ in 0..19 {
+            assert_eq!(app.slow_terminal.record(80.0), None);
+        }
+        assert_eq!(app.slow_terminal.record(80.0), Some(80.0));
+        assert!(app.effects_reduced());
+
+        app.settings.set("reduce_effects", "off").expect("valid");
+        assert!(!app.effects_reduced());
+        app.settings.set("reduce_effects", "auto").expect("valid");
+
+        for _ in 0..19 {
+            assert_eq!(app.slow_terminal.record(20.0), None);
+        }
+        // Between the fast threshold and the budget: stays reduced.
+        assert_eq!(app.slow_terminal.record(20.0), None);
+        for _ in 0..20 {
+            app.slow_terminal.record(2.0);
+        }
+        assert!(!app.effects_reduced());
+    }
+
+    #[test]
+    fn live_settings_update_app_state() {
+        let mut app = AppState::default();
+        app.settings.set("verbosity", "detail").expect("valid");
+        assert_eq!(apply_setting_now(&mut app, "verbosity"), None);
+        assert_eq!(app.error_detail_mode, ErrorDetailMode::Detail);
+
+        app.settings.set("mouse_capture", "on").expect("valid");
+        apply_setting_now(&mut app, "mouse_capture");
+        assert!(app.mouse_capture_enabled);
+
+        app.settings.set("alt_screen", "on").expect("valid");
+        assert_eq!(
+            apply_setting_now(&mut app, "alt_screen"),
+            Some("applies on restart")
+        );
+    }
+
+    #[test]
+    fn reload_reports_changed_keys_and_restart_notes() {
+        let mut app = AppState::default();
+        let mut next = app.settings.clone();
+        next.set("fps_cap", "30").expect("valid");
+        next.set("alt_screen", "on").expect("valid");
+        let changed = app.settings.changed_keys(&next);
+        app.settings = next;
+        apply_changed_settings(&mut app, &changed);
+        assert_eq!(app.settings.fps_cap, 30);
+        assert_eq!(
+            app.log.last().expect("status").plain_text(),
+            "Config reloaded (tui.toml): fps_cap, alt_screen (alt_screen: applies on restart)"
+        );
+    }
+}
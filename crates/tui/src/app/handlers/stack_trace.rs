@@ -0,0 +1,153 @@
+use crate::app::state::LogKind;
+use crate::app::util::stack_trace::{detect_stack_frames, resolve_stack_frames};
+use crate::app::{AppState, PickDialogItem, PickDialogState};
+use crossterm::event::KeyCode;
+use std::path::PathBuf;
+
+pub(crate) const STACK_TRACE_PICK_ID: &str = "stack-trace-resolve";
+
+/// Runtime working directory when known, else the TUI's own.
+pub(crate) fn workspace_root(app: &AppState) -> PathBuf {
+    app.runtime_info
+        .working_dir
+        .as_deref()
+        .map(PathBuf::from)
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// After a composer paste: offers to resolve the frames when the text reads like a stack trace.
+pub(crate) fn offer_stack_trace_resolution(app: &mut AppState, text: &str) {
+    let frames = detect_stack_frames(text);
+    if frames.is_empty() {
+        return;
+    }
+    let root = workspace_root(app);
+    let plural = if frames.len() == 1 { "" } else { "s" };
+    let items = vec![
+        PickDialogItem {
+            id: "resolve".to_string(),
+            label: format!(
+                "Resolve {} frame{plural} and attach them to the next prompt",
+                frames.len()
+            ),
+            detail: Some(format!(
+                "checks the files under {} and adds short code excerpts",
+                root.display()
+            )),
+        },
+        PickDialogItem {
+            id: "plain".to_string(),
+            label: "Keep as plain text".to_string(),
+            detail: None,
+        },
+    ];
+    app.pick_dialog = Some(PickDialogState {
+        id: STACK_TRACE_PICK_ID.to_string(),
+        title: "Pasted text looks like a stack trace".to_string(),
+        message: Some(
+            frames
+                .iter()
+                .take(3)
+                .map(|frame| frame.location())
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+        chosen: vec![false; items.len()],
+        items,
+        selected: 0,
+        multi: false,
+    });
+    app.stack_trace_offer = Some(frames);
+}
+
+fn resolve_offered_trace(app: &mut AppState) {
+    let Some(frames) = app.stack_trace_offer.take() else {
+        return;
+    };
+    let resolved = resolve_stack_frames(&frames, &workspace_root(app));
+    let existing = resolved
+        .iter()
+        .filter(|frame| frame.resolved.is_some())
+        .count();
+    let excerpts = resolved
+        .iter()
+        .filter(|frame| frame.excerpt.is_some())
+        .count();
+    app.pending_stack_traces.push(resolved);
+    app.push_line(
+        LogKind::Status,
+        format!(
+            "Stack trace context attached to the next prompt: {existing}/{} frames found, {excerpts} excerpt(s)",
+            frames.len()
+        ),
+    );
+}
+
+/// Keys for the resolve offer; Esc keeps the paste as plain text.
+pub(crate) fn handle_stack_trace_key(app: &mut AppState, key: KeyCode) -> bool {
+    let Some(pick) = app.pick_dialog.as_mut() else {
+        return false;
+    };
+    let choice = match key {
+        KeyCode::Esc => "plain".to_string(),
+        KeyCode::Up => {
+            pick.selected = pick.selected.saturating_sub(1);
+            return true;
+        }
+        KeyCode::Down => {
+            if pick.selected + 1 < pick.items.len() {
+                pick.selected += 1;
+            }
+            return true;
+        }
+        KeyCode::Enter => pick
+            .items
+            .get(pick.selected)
+            .map(|item| item.id.clone())
+            .unwrap_or_default(),
+        _ => return false,
+    };
+    app.pick_dialog = None;
+    if choice == "resolve" {
+        resolve_offered_trace(app);
+    } else {
+        app.stack_trace_offer = None;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{handle_stack_trace_key, offer_stack_trace_resolution, STACK_TRACE_PICK_ID};
+    use crate::app::AppState;
+    use crossterm::event::KeyCode;
+
+    const TRACE: &str =
+        "Error: boom\n    at run (src/missing.ts:3:14)\n    at main (src/other.ts:9:1)";
+
+    #[test]
+    fn resolving_a_pasted_trace_queues_context_for_the_next_prompt() {
+        let mut app = AppState::default();
+        offer_stack_trace_resolution(&mut app, "just some text\nover two lines");
+        assert!(app.pick_dialog.is_none());
+
+        offer_stack_trace_resolution(&mut app, TRACE);
+        let pick = app.pick_dialog.as_ref().expect("offer dialog");
+        assert_eq!(pick.id, STACK_TRACE_PICK_ID);
+        assert_eq!(
+            pick.message.as_deref(),
+            Some("src/missing.ts:3:14, src/other.ts:9:1")
+        );
+        assert!(handle_stack_trace_key(&mut app, KeyCode::Enter));
+        assert!(app.pick_dialog.is_none());
+        assert!(app.stack_trace_offer.is_none());
+        assert_eq!(app.pending_stack_traces.len(), 1);
+        assert_eq!(app.pending_stack_traces[0].len(), 2);
+
+        offer_stack_trace_resolution(&mut app, TRACE);
+        assert!(handle_stack_trace_key(&mut app, KeyCode::Esc));
+        assert!(app.stack_trace_offer.is_none());
+        assert_eq!(app.pending_stack_traces.len(), 1);
+    }
+}
@@ -0,0 +1,37 @@
+use crate::app::state::{LogKind, TabRequest};
+use crate::app::AppState;
+
+/// Line mode and `--print` have a single runtime and leave the strip empty.
+fn tabs_available(app: &mut AppState) -> bool {
+    if app.tab_strip.tabs.is_empty() {
+        app.push_line(
+            LogKind::Error,
+            "Tabs are only available in the interactive TUI",
+        );
+        return false;
+    }
+    true
+}
+
+pub(crate) fn request_tab(app: &mut AppState, request: TabRequest) -> bool {
+    if !tabs_available(app) {
+        return true;
+    }
+    app.tab_request = Some(request);
+    true
+}
+
+/// Bare `/tab`: one row per tab with its session and run state.
+pub(crate) fn show_tabs(app: &mut AppState) {
+    if !tabs_available(app) {
+        return;
+    }
+    let rows = app.tab_strip.list_rows();
+    app.push_line(
+        LogKind::Status,
+        "Tabs (/tab <n> switch, /tab new, /tab close [n]):",
+    );
+    for row in rows {
+        app.push_line(LogKind::Status, row);
+    }
+}
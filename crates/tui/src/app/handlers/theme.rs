@@ -1,10 +1,37 @@
-use crate::app::state::parse_theme_name;
-use crate::app::theme::apply_theme_name;
+use crate::app::handlers::settings::set_setting;
+use crate::app::state::{parse_theme_name, LogKind};
+use crate::app::theme::apply_theme_id;
+use crate::app::util::settings::save_setting;
+use crate::app::AppState;
 
 pub(crate) fn apply_theme_from_name(name: &str) -> bool {
-    let Some(parsed) = parse_theme_name(name) else {
-        return false;
-    };
-    apply_theme_name(parsed);
-    true
+    apply_theme_id(name)
+}
+
+/// The runtime's `theme.set` only knows the built-ins, so a custom theme is applied here and
+/// pinned in tui.toml instead.
+pub(crate) fn select_custom_theme(app: &mut AppState, name: &str) {
+    set_setting(app, "theme", name);
+}
+
+/// A built-in picked with `/theme` is saved by the runtime; a custom theme still pinned in
+/// tui.toml would otherwise win again on the next start.
+pub(crate) fn unpin_custom_theme(app: &mut AppState) {
+    let pinned_custom = app
+        .settings
+        .theme
+        .as_deref()
+        .is_some_and(|theme| parse_theme_name(theme).is_none());
+    if !pinned_custom {
+        return;
+    }
+    app.settings.theme = None;
+    match save_setting(&app.settings, "theme") {
+        Ok(_) => app.settings_watcher.mark_current(),
+        Err(error) => app.push_error_report("settings save failed", error),
+    }
+    app.push_line(
+        LogKind::Status,
+        "Removed the custom theme pin from tui.toml.",
+    );
 }
@@ -0,0 +1,155 @@
+use crate::app::handlers::settings::set_setting;
+use crate::app::runtime::{send_tool_call, RuntimeStdin};
+use crate::app::AppState;
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+
+/// Time between shell polls; stdout and stderr take turns, so each refreshes every second.
+const SHELL_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const SHELL_TAIL_LINES: usize = 200;
+
+/// Alt+T: flips the `split_pane` setting and saves it like `/set` would.
+pub(crate) fn toggle_split_pane(app: &mut AppState) {
+    let value = if app.settings.split_pane { "off" } else { "on" };
+    set_setting(app, "split_pane", value);
+}
+
+/// Shell calls run as runtime tasks: `shell_list` finds the task of the call on screen, then
+/// `shell_logs` tails it. Only while the pane is drawn and nothing else of ours is in flight.
+pub(crate) fn poll_tool_pane_if_due(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+    now: Instant,
+) {
+    if !app.tool_pane_shown
+        || app.rpc_pending.tool_pane_list_id.is_some()
+        || app.rpc_pending.tool_pane_logs_id.is_some()
+        || app.runtime_reconnect.reconnecting()
+    {
+        return;
+    }
+    let pane = &mut app.tool_pane;
+    let due = pane
+        .last_poll_at
+        .is_none_or(|at| now.saturating_duration_since(at) >= SHELL_POLL_INTERVAL);
+    if !pane.running || pane.tool != "shell" || !due {
+        return;
+    }
+    pane.last_poll_at = Some(now);
+    let request_id = next_id();
+    let Some(key) = pane.shell_key.clone() else {
+        if send_tool_call(child_stdin, &request_id, "shell_list", json!({})).is_ok() {
+            app.rpc_pending.tool_pane_list_id = Some(request_id);
+        }
+        return;
+    };
+    let stream = if pane.poll_stderr { "stderr" } else { "stdout" };
+    pane.poll_stderr = !pane.poll_stderr;
+    let args = json!({ "key": key, "stream": stream, "tail_lines": SHELL_TAIL_LINES });
+    if send_tool_call(child_stdin, &request_id, "shell_logs", args).is_ok() {
+        app.rpc_pending.tool_pane_logs_id = Some(request_id);
+    }
+}
+
+/// Picks the running task whose command is the call's; with no command to match, the only
+/// running one.
+pub(crate) fn apply_tool_pane_list_result(app: &mut AppState, result: &Value) {
+    let pane = &mut app.tool_pane;
+    let running = result
+        .get("tasks")
+        .and_then(Value::as_array)
+        .map(|tasks| {
+            tasks
+                .iter()
+                .filter(|task| task.get("state").and_then(Value::as_str) == Some("running"))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let task = match pane.command.as_deref() {
+        Some(command) => running.iter().find(|task| {
+            task.get("command")
+                .and_then(Value::as_str)
+                .is_some_and(|title| task_title_matches(title, command))
+        }),
+        None if running.len() == 1 => running.first(),
+        None => None,
+    };
+    pane.shell_key = task
+        .and_then(|task| task.get("key"))
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    if pane.shell_key.is_some() {
+        pane.last_poll_at = None;
+    }
+}
+
+/// Task titles are the command with whitespace collapsed, cut off after a limit.
+fn task_title_matches(title: &str, command: &str) -> bool {
+    let command = command.split_whitespace().collect::<Vec<_>>().join(" ");
+    match title.strip_suffix("...[truncated]") {
+        Some(head) => command.starts_with(head),
+        None => title == command,
+    }
+}
+
+/// Tails for a call that is no longer on screen are dropped.
+pub(crate) fn apply_tool_pane_logs_result(app: &mut AppState, result: &Value) {
+    let pane = &mut app.tool_pane;
+    if pane.shell_key.is_none()
+        || result.get("key").and_then(Value::as_str) != pane.shell_key.as_deref()
+    {
+        return;
+    }
+    let stderr = result.get("stream").and_then(Value::as_str) == Some("stderr");
+    let content = result.get("content").and_then(Value::as_str).unwrap_or("");
+    pane.set_shell_output(stderr, content);
+}
+
+/// A task that is gone (finished between polls) is looked up again on the next poll.
+pub(crate) fn apply_tool_pane_logs_error(app: &mut AppState) {
+    app.tool_pane.shell_key = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_tool_pane_list_result, apply_tool_pane_logs_result};
+    use crate::app::AppState;
+    use serde_json::json;
+
+    #[test]
+    fn shell_task_is_matched_by_command_and_tailed() {
+        let mut app = AppState::default();
+        app.tool_pane
+            .start("call-1", "shell", Some("npm  test".to_string()), &[]);
+        apply_tool_pane_list_result(
+            &mut app,
+            &json!({ "tasks": [
+                { "key": "dev-1", "command": "npm run dev", "state": "running" },
+                { "key": "test-2", "command": "npm test", "state": "running" },
+            ] }),
+        );
+        assert_eq!(app.tool_pane.shell_key.as_deref(), Some("test-2"));
+
+        apply_tool_pane_logs_result(
+            &mut app,
+            &json!({ "key": "dev-1", "stream": "stdout", "content": "listening" }),
+        );
+        assert!(app.tool_pane.stdout.is_empty());
+        apply_tool_pane_logs_result(
+            &mut app,
+            &json!({ "key": "test-2", "stream": "stdout", "content": "PASS a\nPASS b\n" }),
+        );
+        assert_eq!(app.tool_pane.stdout, vec!["PASS a", "PASS b"]);
+
+        app.tool_pane.start("call-2", "shell", None, &[]);
+        apply_tool_pane_list_result(
+            &mut app,
+            &json!({ "tasks": [
+                { "key": "dev-1", "state": "running" },
+                { "key": "test-3", "state": "running" },
+            ] }),
+        );
+        assert_eq!(app.tool_pane.shell_key, None);
+    }
+}
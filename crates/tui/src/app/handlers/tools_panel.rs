@@ -0,0 +1,180 @@
+use crate::app::runtime::{send_context_inspect, RuntimeStdin};
+use crate::app::state::LogKind;
+use crate::app::{AppState, ToolsPanelState};
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// `/tools`: refresh the runtime's tool list, then open the toggle panel.
+pub(crate) fn request_tools_panel(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) {
+    if !app.runtime_info.supports_disabled_tools {
+        app.push_line(
+            LogKind::Status,
+            "Per-session tool toggles are not supported by this runtime",
+        );
+        return;
+    }
+    if !app.runtime_info.supports_context_inspect {
+        open_tools_panel(app);
+        return;
+    }
+    if app.rpc_pending.tools_list_id.is_some() {
+        app.push_line(LogKind::Status, "Tool list request already running");
+        return;
+    }
+    let id = next_id();
+    app.rpc_pending.tools_list_id = Some(id.clone());
+    if let Err(error) = send_context_inspect(child_stdin, &id, false, false) {
+        app.rpc_pending.tools_list_id = None;
+        app.push_error_report("send error", error.to_string());
+    }
+}
+
+pub(crate) fn finish_tools_list(app: &mut AppState, inspect: Option<&Value>) {
+    if let Some(names) = inspect
+        .and_then(|result| result.get("enabled_tools"))
+        .and_then(|value| value.as_array())
+    {
+        app.runtime_tools = Some(
+            names
+                .iter()
+                .filter_map(|name| name.as_str())
+                .map(str::to_string)
+                .collect(),
+        );
+    }
+    open_tools_panel(app);
+}
+
+/// Runtime tools plus anything disabled earlier, so a toggle never disappears from the list.
+fn tools_panel_names(app: &AppState) -> Vec<String> {
+    let mut names = app
+        .runtime_tools
+        .iter()
+        .flatten()
+        .cloned()
+        .collect::<BTreeSet<_>>();
+    names.extend(app.disabled_tools.iter().cloned());
+    names.into_iter().collect()
+}
+
+pub(crate) fn open_tools_panel(app: &mut AppState) {
+    let names = tools_panel_names(app);
+    if names.is_empty() {
+        app.tools_panel = None;
+        app.push_line(
+            LogKind::Status,
+            "The runtime builds its tools on the first run; send a prompt, then reopen /tools.",
+        );
+        return;
+    }
+    let rows = names
+        .iter()
+        .map(|name| {
+            if app.disabled_tools.contains(name) {
+                format!("[ ] {name}  (disabled this session)")
+            } else {
+                format!("[x] {name}")
+            }
+        })
+        .collect::<Vec<_>>();
+    let enabled = names.len() - app.disabled_tools.len().min(names.len());
+    app.tools_panel = Some(ToolsPanelState {
+        title: format!("Tools ({enabled} of {} enabled this session)", names.len()),
+        header: "Space toggle · Esc close · applies from the next run".to_string(),
+        rows,
+        names,
+        selected: 0,
+    });
+}
+
+/// Rebuilds rows after a toggle while keeping the selection in range.
+fn refresh_tools_panel(app: &mut AppState) {
+    let Some(selected) = app.tools_panel.as_ref().map(|panel| panel.selected) else {
+        return;
+    };
+    open_tools_panel(app);
+    if let Some(panel) = app.tools_panel.as_mut() {
+        panel.selected = selected.min(panel.rows.len().saturating_sub(1));
+    }
+}
+
+pub(crate) fn set_tool_enabled(app: &mut AppState, name: &str, enabled: bool) {
+    let changed = if enabled {
+        app.disabled_tools.remove(name)
+    } else {
+        app.disabled_tools.insert(name.to_string())
+    };
+    let message = match (changed, enabled) {
+        (true, true) => format!("Enabled {name} for this session (applies from the next run)."),
+        (true, false) => {
+            format!("Disabled {name} for this session (applies from the next run).")
+        }
+        (false, true) => format!("{name} is already enabled."),
+        (false, false) => format!("{name} is already disabled."),
+    };
+    app.push_line(LogKind::Status, message);
+    refresh_tools_panel(app);
+}
+
+pub(crate) fn toggle_selected_tool(app: &mut AppState) {
+    let Some(name) = app
+        .tools_panel
+        .as_ref()
+        .and_then(|panel| panel.names.get(panel.selected).cloned())
+    else {
+        return;
+    };
+    let enabled = app.disabled_tools.contains(&name);
+    set_tool_enabled(app, &name, enabled);
+}
+
+/// `/tools enable|disable <name>`; names are checked once the runtime has reported its tools.
+pub(crate) fn set_tool_enabled_by_name(app: &mut AppState, name: &str, enabled: bool) {
+    let known = app
+        .runtime_tools
+        .as_ref()
+        .is_none_or(|tools| tools.iter().any(|tool| tool == name));
+    if !known && !app.disabled_tools.contains(name) {
+        app.push_line(LogKind::Error, format!("unknown tool: {name} (see /tools)"));
+        return;
+    }
+    set_tool_enabled(app, name, enabled);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{finish_tools_list, set_tool_enabled_by_name, toggle_selected_tool};
+    use crate::app::AppState;
+    use serde_json::json;
+
+    #[test]
+    fn tools_panel_toggles_session_disabled_tools() {
+        let mut app = AppState::default();
+        finish_tools_list(
+            &mut app,
+            Some(&json!({ "enabled_tools": ["shell", "read", "edit"] })),
+        );
+        let panel = app.tools_panel.as_ref().expect("panel");
+        assert_eq!(panel.names, vec!["edit", "read", "shell"]);
+        assert_eq!(panel.title, "Tools (3 of 3 enabled this session)");
+
+        app.tools_panel.as_mut().expect("panel").selected = 2;
+        toggle_selected_tool(&mut app);
+        assert!(app.disabled_tools.contains("shell"));
+        let panel = app.tools_panel.as_ref().expect("panel");
+        assert_eq!(panel.rows[2], "[ ] shell  (disabled this session)");
+        assert_eq!(panel.selected, 2);
+
+        set_tool_enabled_by_name(&mut app, "bash", false);
+        assert_eq!(
+            app.log.last().expect("error").plain_text(),
+            "unknown tool: bash (see /tools)"
+        );
+        set_tool_enabled_by_name(&mut app, "shell", true);
+        assert!(app.disabled_tools.is_empty());
+    }
+}
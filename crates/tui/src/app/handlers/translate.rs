@@ -0,0 +1,276 @@
+use crate::app::state::{LogKind, LogLine, LogTone};
+use crate::app::util::text::sanitize_for_tui;
+use crate::app::util::translate::parse_language;
+use crate::app::{AppState, ContextPanelState};
+
+/// Lines of a translation shown under its answer; `/translate show` opens the whole text.
+const TRANSLATION_PREVIEW_LINES: usize = 3;
+
+/// `/translate <lang>`: mirror every completed answer into `lang` until `/translate off`.
+pub(crate) fn set_translation_target(app: &mut AppState, value: &str) {
+    let Some(language) = parse_language(value) else {
+        app.push_line(
+            LogKind::Error,
+            format!("/translate: expected a language code such as ja, de or pt-BR, got {value}"),
+        );
+        return;
+    };
+    let Some(endpoint) = app.settings.translate_endpoint.as_deref() else {
+        app.push_line(
+            LogKind::Error,
+            "/translate needs an endpoint: /set translate_endpoint <url> (LibreTranslate-compatible)",
+        );
+        return;
+    };
+    app.push_line(
+        LogKind::Status,
+        format!("Translating answers into {language} via {endpoint} (/translate off to stop)"),
+    );
+    app.translation.target = Some(language);
+}
+
+pub(crate) fn stop_translation(app: &mut AppState) {
+    if app.translation.target.take().is_some() {
+        app.push_line(LogKind::Status, "Answer translation off");
+    } else {
+        app.push_line(LogKind::Status, "Answer translation is not on");
+    }
+}
+
+pub(crate) fn show_translation_status(app: &mut AppState) {
+    let pending = app.translation.pending();
+    let message = match app.translation.target.as_deref() {
+        Some(language) if pending > 0 => {
+            format!("Translating answers into {language} ({pending} in flight)")
+        }
+        Some(language) => format!("Translating answers into {language}"),
+        None => "Answer translation is off (/translate <lang> to start)".to_string(),
+    };
+    app.push_line(LogKind::Status, message);
+}
+
+/// Called when a run completes; sends its answer to the endpoint in the background.
+pub(crate) fn start_answer_translation(app: &mut AppState) {
+    let Some(language) = app.translation.target.clone() else {
+        return;
+    };
+    let Some(answer) = app
+        .last_assistant_text
+        .clone()
+        .filter(|answer| !answer.trim().is_empty())
+    else {
+        return;
+    };
+    let Some(endpoint) = app.settings.translate_endpoint.clone() else {
+        app.push_line(
+            LogKind::Status,
+            "Translation skipped: translate_endpoint is no longer set",
+        );
+        return;
+    };
+    let turn = app.log_turn;
+    app.translation.spawn(&endpoint, &answer, &language, turn);
+}
+
+/// A summary row plus the first few lines; longer translations stay folded. `detached` blocks
+/// were appended away from their answer and name its turn.
+fn translation_block_lines(language: &str, turn: u32, text: &str, detached: bool) -> Vec<LogLine> {
+    let lines = text
+        .lines()
+        .map(sanitize_for_tui)
+        .filter(|line| !line.trim().is_empty())
+        .collect::<Vec<_>>();
+    let of_turn = if detached {
+        format!(" of turn {turn}")
+    } else {
+        String::new()
+    };
+    let mut block = vec![LogLine::new(
+        LogKind::Status,
+        format!(
+            "▸ Translation ({language}){of_turn} · {} line(s) · /translate show {turn}",
+            lines.len()
+        ),
+    )];
+    block.extend(
+        lines.iter().take(TRANSLATION_PREVIEW_LINES).map(|line| {
+            LogLine::new_with_tone(LogKind::Status, LogTone::Detail, format!("  {line}"))
+        }),
+    );
+    if lines.len() > TRANSLATION_PREVIEW_LINES {
+        block.push(LogLine::new_with_tone(
+            LogKind::Status,
+            LogTone::Detail,
+            format!("  … {} more", lines.len() - TRANSLATION_PREVIEW_LINES),
+        ));
+    }
+    for line in &mut block {
+        line.meta.turn = Some(turn);
+    }
+    block
+}
+
+/// Just below the last line of `turn`, unless that spot is no longer redrawn (inline mode
+/// already wrote it to the terminal scrollback) or a search holds log indices.
+fn answer_insert_index(app: &AppState, turn: u32, inline: bool) -> Option<usize> {
+    if app.log_search.is_some() {
+        return None;
+    }
+    let index = app
+        .log
+        .iter()
+        .rposition(|line| line.meta.turn == Some(turn))?
+        + 1;
+    if !inline || index == app.log.len() {
+        return Some(index);
+    }
+    let cache = app
+        .wrapped_log_cache
+        .as_ref()
+        .filter(|cache| cache.log_version == app.log_version)?;
+    let start = *cache.line_starts.get(index)?;
+    (start >= app.render_state.inserted_until).then_some(index)
+}
+
+/// Drains finished translations into the log below their answers (at the end when the answer
+/// can no longer move); returns true when the log changed.
+pub(crate) fn apply_translation_results(app: &mut AppState, inline: bool) -> bool {
+    let mut changed = false;
+    while let Some(done) = app.translation.poll() {
+        match done.result {
+            Ok(text) => {
+                let index = answer_insert_index(app, done.turn, inline);
+                let detached = index.is_none() && done.turn != app.log_turn;
+                let block = translation_block_lines(&done.language, done.turn, &text, detached);
+                match index {
+                    Some(index) => app.insert_log_lines(index, block),
+                    None => app.extend_lines(block),
+                }
+                app.translation.remember(done.turn, done.language, text);
+            }
+            Err(error) => app.push_line(
+                LogKind::Error,
+                format!("Translation ({}) failed: {error}", done.language),
+            ),
+        }
+        changed = true;
+    }
+    changed
+}
+
+/// `/translate show [<turn>]`: the translation of that turn's answer, or the latest one.
+pub(crate) fn open_translation_panel(app: &mut AppState, turn: Option<&str>) {
+    let turn = match turn.map(str::parse::<u32>) {
+        None => None,
+        Some(Ok(turn)) => Some(turn),
+        Some(Err(_)) => {
+            app.push_line(LogKind::Error, "/translate show: expected a turn number");
+            return;
+        }
+    };
+    let Some((turn, language, text)) = app.translation.text_for(turn) else {
+        let message = match turn {
+            Some(turn) => format!("No translation kept for turn {turn}"),
+            None => "No translation yet; turn it on with /translate <lang>".to_string(),
+        };
+        app.push_line(LogKind::Status, message);
+        return;
+    };
+    let rows = text.lines().map(sanitize_for_tui).collect::<Vec<_>>();
+    let title = format!(
+        "Translation ({language}, turn {turn}, {} lines)",
+        rows.len()
+    );
+    app.skills_list_panel = None;
+    app.theme_list_panel = None;
+    app.context_panel = Some(ContextPanelState {
+        title,
+        header: "Answer translation · Esc close".to_string(),
+        rows,
+        selected: 0,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        apply_translation_results, open_translation_panel, set_translation_target,
+        translation_block_lines,
+    };
+    use crate::app::state::LogKind;
+    use crate::app::AppState;
+
+    #[test]
+    fn translations_fold_below_a_summary_row() {
+        let mut app = AppState::default();
+        set_translation_target(&mut app, "ja");
+        assert!(app.translation.target.is_none());
+        app.settings.translate_endpoint = Some("http://localhost:5000/translate".to_string());
+        set_translation_target(&mut app, "ja");
+        assert_eq!(app.translation.target.as_deref(), Some("ja"));
+
+        let block =
+            translation_block_lines("ja", 4, "一行目\n\n二行目\n三行目\n四行目\n五行目", false);
+        let texts = block
+            .iter()
+            .map(|line| line.plain_text())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            texts,
+            vec![
+                "▸ Translation (ja) · 5 line(s) · /translate show 4",
+                "  一行目",
+                "  二行目",
+                "  三行目",
+                "  … 2 more",
+            ]
+        );
+    }
+
+    #[test]
+    fn late_translations_land_below_their_own_answer() {
+        let mut app = AppState::default();
+        app.begin_log_turn();
+        app.push_line(LogKind::User, "first question");
+        app.push_line(LogKind::Assistant, "first answer");
+        app.begin_log_turn();
+        app.push_line(LogKind::User, "second question");
+
+        app.translation.push_finished("ja", 1, Ok("最初の答え"));
+        assert!(apply_translation_results(&mut app, false));
+        let texts = app
+            .log
+            .iter()
+            .map(|line| line.plain_text())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            texts,
+            vec![
+                "first question",
+                "first answer",
+                "▸ Translation (ja) · 1 line(s) · /translate show 1",
+                "  最初の答え",
+                "second question",
+            ]
+        );
+        assert_eq!(app.log[3].meta.turn, Some(1));
+
+        // Inline mode without a fresh wrap cache cannot tell what reached the scrollback.
+        app.translation.push_finished("de", 1, Ok("Erste Antwort"));
+        assert!(apply_translation_results(&mut app, true));
+        assert_eq!(
+            app.log[5].plain_text(),
+            "▸ Translation (de) of turn 1 · 1 line(s) · /translate show 1"
+        );
+
+        app.translation.push_finished("ja", 2, Ok("二番目"));
+        apply_translation_results(&mut app, false);
+        open_translation_panel(&mut app, Some("1"));
+        let panel = app.context_panel.take().expect("panel");
+        assert_eq!(panel.title, "Translation (de, turn 1, 1 lines)");
+        assert_eq!(panel.rows, vec!["Erste Antwort"]);
+        open_translation_panel(&mut app, None);
+        let panel = app.context_panel.take().expect("panel");
+        assert_eq!(panel.rows, vec!["二番目"]);
+    }
+}
@@ -0,0 +1,54 @@
+use crate::app::state::LogKind;
+use crate::app::util::update::{current_version, is_newer_version, UPGRADE_COMMAND};
+use crate::app::AppState;
+
+/// Starts the opt-in release lookup (`update_check = on` in tui.toml).
+pub(crate) fn start_update_check(app: &mut AppState) {
+    if app.settings.update_check {
+        app.update_check.spawn();
+    }
+}
+
+/// Returns true when an upgrade notice was added to the log.
+pub(crate) fn apply_update_check_result(app: &mut AppState) -> bool {
+    match app.update_check.poll() {
+        Some(Ok(latest)) => push_update_notice(app, &latest, &current_version()),
+        Some(Err(error)) => {
+            // Offline or blocked registries are normal; stay quiet unless debugging.
+            if app.enable_debug_print {
+                app.push_line(LogKind::Runtime, format!("update check failed: {error}"));
+                return true;
+            }
+            false
+        }
+        None => false,
+    }
+}
+
+fn push_update_notice(app: &mut AppState, latest: &str, current: &str) -> bool {
+    if !is_newer_version(latest, current) {
+        return false;
+    }
+    app.push_line(
+        LogKind::Status,
+        format!("codelia {latest} available (current {current}) — run `{UPGRADE_COMMAND}`"),
+    );
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::push_update_notice;
+    use crate::app::AppState;
+
+    #[test]
+    fn update_notice_only_for_newer_releases() {
+        let mut app = AppState::default();
+        assert!(!push_update_notice(&mut app, "0.1.75", "0.1.75"));
+        assert!(push_update_notice(&mut app, "0.2.0", "0.1.75"));
+        assert_eq!(
+            app.log.last().expect("notice").plain_text(),
+            "codelia 0.2.0 available (current 0.1.75) — run `npm i -g @codelia/cli`"
+        );
+    }
+}
@@ -0,0 +1,267 @@
+use crate::app::runtime::{send_model_list, RunUsage, RuntimeStdin};
+use crate::app::state::usage::{ModelTotals, TokenTotals};
+use crate::app::state::{
+    format_token_count, format_usd, model_key, LogKind, ModelPricing, UsageTracker,
+};
+use crate::app::state::{ProviderStat, ProviderStats};
+use crate::app::{AppState, ContextPanelState};
+use serde_json::Value;
+use std::time::Duration;
+
+/// Adds one call's tokens; the first call of an unpriced model asks `model.list` for its
+/// provider's prices.
+pub(crate) fn apply_run_usage(
+    app: &mut AppState,
+    usage: RunUsage,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) {
+    let provider = usage
+        .provider
+        .clone()
+        .or_else(|| app.runtime_info.current_provider.clone())
+        .unwrap_or_else(|| "-".to_string());
+    app.usage
+        .record(app.runtime_info.session_id.as_deref(), &provider, &usage);
+    app.provider_stats
+        .record_usage(&model_key(&provider, &usage.model), &usage);
+    if provider == "-"
+        || app.usage.has_pricing(&provider, &usage.model)
+        || app.usage.pricing_requested.contains(&provider)
+        || app.rpc_pending.usage_pricing_id.is_some()
+    {
+        return;
+    }
+    let id = next_id();
+    app.usage.pricing_requested.insert(provider.clone());
+    app.rpc_pending.usage_pricing_id = Some(id.clone());
+    if send_model_list(child_stdin, &id, Some(&provider), true).is_err() {
+        // Prices are a nicety; a failed send only leaves the estimate out.
+        app.rpc_pending.usage_pricing_id = None;
+    }
+}
+
+/// Keeps the per-1M token prices of a `model.list` result that carries details.
+pub(crate) fn record_model_pricing(usage: &mut UsageTracker, result: &Value) {
+    let Some(provider) = result.get("provider").and_then(Value::as_str) else {
+        return;
+    };
+    let Some(details) = result.get("details").and_then(Value::as_object) else {
+        return;
+    };
+    for (model, detail) in details {
+        let cost = |key| detail.get(key).and_then(Value::as_f64);
+        if let (Some(input), Some(output)) = (
+            cost("cost_per_1m_input_tokens_usd"),
+            cost("cost_per_1m_output_tokens_usd"),
+        ) {
+            usage.set_pricing(provider, model, ModelPricing { input, output });
+        }
+    }
+}
+
+fn totals_row(label: &str, totals: &TokenTotals, cost: Option<f64>) -> String {
+    let cost = cost
+        .map(format_usd)
+        .unwrap_or_else(|| "no price".to_string());
+    format!(
+        "{label}  {} call(s)  in {} (cached {})  out {}  {cost}",
+        totals.calls,
+        format_token_count(totals.input_tokens),
+        format_token_count(totals.cached_input_tokens),
+        format_token_count(totals.output_tokens),
+    )
+}
+
+fn section_rows(title: &str, tracker: &UsageTracker, models: &ModelTotals) -> Vec<String> {
+    let mut rows = vec![title.to_string()];
+    let mut sum = TokenTotals::default();
+    for (key, totals) in models {
+        rows.push(totals_row(key, totals, tracker.cost_of(key, totals)));
+        sum.calls += totals.calls;
+        sum.input_tokens += totals.input_tokens;
+        sum.output_tokens += totals.output_tokens;
+        sum.cached_input_tokens += totals.cached_input_tokens;
+    }
+    if models.len() > 1 {
+        rows.push(totals_row("total", &sum, tracker.total_cost(models)));
+    }
+    rows
+}
+
+fn usage_panel_rows(tracker: &UsageTracker) -> Vec<String> {
+    let mut rows = section_rows("THIS RUN", tracker, &tracker.run);
+    rows.push(String::new());
+    rows.extend(section_rows("SESSION", tracker, &tracker.session));
+    rows.push(String::new());
+    rows.push("Estimate from model.list prices; cached input is priced as regular input.".into());
+    rows
+}
+
+/// `/usage`: token totals of the latest run and the session, with a cost estimate.
+pub(crate) fn open_usage_panel(app: &mut AppState) {
+    if app.usage.session.is_empty() {
+        app.push_line(
+            LogKind::Status,
+            "No token usage recorded yet; run a prompt first.",
+        );
+        return;
+    }
+    let rows = usage_panel_rows(&app.usage);
+    app.skills_list_panel = None;
+    app.theme_list_panel = None;
+    app.context_panel = Some(ContextPanelState {
+        title: "Usage".to_string(),
+        header: "Tokens per provider/model · Esc close".to_string(),
+        rows,
+        selected: 0,
+    });
+}
+
+fn format_latency(latency: Duration) -> String {
+    let secs = latency.as_secs();
+    if secs >= 60 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{:.1}s", latency.as_secs_f64())
+    }
+}
+
+fn provider_stat_row(key: &str, stat: &ProviderStat, cost: Option<f64>) -> String {
+    let failed = match stat.failures {
+        0 => "0".to_string(),
+        failures => format!("{failures} ({}%)", failures * 100 / stat.runs.max(1)),
+    };
+    let latency = stat
+        .average_latency()
+        .map(format_latency)
+        .unwrap_or_else(|| "-".to_string());
+    let cost = cost
+        .map(format_usd)
+        .unwrap_or_else(|| "no price".to_string());
+    format!(
+        "{key}  {} run(s)  failed {failed}  cancelled {}  avg {latency}  {cost}",
+        stat.runs, stat.cancelled
+    )
+}
+
+fn provider_stats_rows(stats: &ProviderStats, tracker: &UsageTracker) -> Vec<String> {
+    let mut rows: Vec<String> = stats
+        .ranked()
+        .into_iter()
+        .map(|(key, stat)| provider_stat_row(key, stat, tracker.cost_of(key, &stat.tokens)))
+        .collect();
+    rows.push(String::new());
+    rows.push("Average latency counts completed runs; cost is the /usage estimate.".into());
+    rows
+}
+
+/// `/providers stats`: runs, failures, latency and cost per provider/model in this tab.
+pub(crate) fn open_provider_stats_panel(app: &mut AppState) {
+    if app.provider_stats.is_empty() {
+        app.push_line(LogKind::Status, "No runs recorded yet; run a prompt first.");
+        return;
+    }
+    let rows = provider_stats_rows(&app.provider_stats, &app.usage);
+    app.skills_list_panel = None;
+    app.theme_list_panel = None;
+    app.context_panel = Some(ContextPanelState {
+        title: "Provider stats".to_string(),
+        header: "Most used provider/model first · Esc close".to_string(),
+        rows,
+        selected: 0,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{provider_stats_rows, record_model_pricing, usage_panel_rows};
+    use crate::app::runtime::RunUsage;
+    use crate::app::state::{ProviderStats, UsageTracker};
+    use serde_json::json;
+    use std::time::Duration;
+
+    #[test]
+    fn panel_prices_models_listed_with_details() {
+        let mut tracker = UsageTracker::default();
+        let call = |model: &str| RunUsage {
+            run_id: Some("r1".to_string()),
+            provider: Some("openai".to_string()),
+            model: model.to_string(),
+            input_tokens: 200_000,
+            output_tokens: 10_000,
+            cached_input_tokens: 50_000,
+            cache_creation_tokens: 0,
+        };
+        tracker.record(Some("s1"), "openai", &call("gpt-5"));
+        tracker.record(Some("s1"), "openai", &call("o3"));
+        record_model_pricing(
+            &mut tracker,
+            &json!({
+                "provider": "openai",
+                "models": ["gpt-5", "o3"],
+                "details": {
+                    "gpt-5": {
+                        "cost_per_1m_input_tokens_usd": 1.25,
+                        "cost_per_1m_output_tokens_usd": 10.0
+                    },
+                    "o3": { "context_window": 200000 }
+                }
+            }),
+        );
+
+        let rows = usage_panel_rows(&tracker);
+        assert_eq!(rows[0], "THIS RUN");
+        assert_eq!(
+            rows[1],
+            "openai/gpt-5  1 call(s)  in 200.0k (cached 50.0k)  out 10.0k  $0.350"
+        );
+        assert!(rows[2].starts_with("openai/o3 ") && rows[2].ends_with("no price"));
+        assert!(rows[3].starts_with("total  2 call(s)  in 400.0k") && rows[3].ends_with("$0.350"));
+        assert!(rows.contains(&"SESSION".to_string()));
+    }
+
+    #[test]
+    fn provider_stats_rows_show_failure_rate_latency_and_cost() {
+        let mut tracker = UsageTracker::default();
+        let mut stats = ProviderStats::default();
+        let call = RunUsage {
+            run_id: Some("r1".to_string()),
+            provider: Some("openai".to_string()),
+            model: "gpt-5".to_string(),
+            input_tokens: 200_000,
+            output_tokens: 10_000,
+            cached_input_tokens: 0,
+            cache_creation_tokens: 0,
+        };
+        tracker.record(Some("s1"), "openai", &call);
+        record_model_pricing(
+            &mut tracker,
+            &json!({
+                "provider": "openai",
+                "details": {
+                    "gpt-5": {
+                        "cost_per_1m_input_tokens_usd": 1.25,
+                        "cost_per_1m_output_tokens_usd": 10.0
+                    }
+                }
+            }),
+        );
+        for status in ["completed", "error", "completed", "completed"] {
+            stats.start_run("openai/gpt-5");
+            stats.finish_run(status, Some(Duration::from_secs(30)));
+        }
+        stats.record_usage("openai/gpt-5", &call);
+        stats.start_run("-/-");
+
+        let rows = provider_stats_rows(&stats, &tracker);
+        assert_eq!(
+            rows[0],
+            "openai/gpt-5  4 run(s)  failed 1 (25%)  cancelled 0  avg 30.0s  $0.350"
+        );
+        assert_eq!(
+            rows[1],
+            "-/-  1 run(s)  failed 0  cancelled 0  avg -  no price"
+        );
+    }
+}
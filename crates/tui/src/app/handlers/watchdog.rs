@@ -0,0 +1,285 @@
+use crate::app::runtime::{send_request, send_run_cancel, RuntimeStdin};
+use crate::app::state::LogKind;
+use crate::app::{AppState, PickDialogItem, PickDialogState};
+use crossterm::event::KeyCode;
+use serde_json::json;
+use std::fmt::Write as _;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+pub(crate) const RUNTIME_STALL_PICK_ID: &str = "runtime-stall";
+
+const STALL_ACTIONS: &[(&str, &str, &str)] = &[
+    (
+        "ping",
+        "Send ping",
+        "check whether the runtime still answers requests",
+    ),
+    ("cancel", "Cancel run", "send run.cancel for the active run"),
+    (
+        "snapshot",
+        "Save diagnostics snapshot",
+        "pending requests and recent runtime events, for a bug report",
+    ),
+];
+
+/// A run is watched while it should be producing output, not while it waits on the user or
+/// on a tool call (a long build or test run is silent until its result); the silence count
+/// restarts when the tool call ends.
+fn watched_run(app: &AppState) -> bool {
+    matches!(app.run_status.as_deref(), Some("starting" | "running"))
+        && app.active_tool.is_none()
+        && app.confirm_dialog.is_none()
+        && app.pending_confirm_dialog.is_none()
+        && app.prompt_dialog.is_none()
+}
+
+/// Called from the run loop each tick; true when a stall warning was just shown.
+pub(crate) fn check_runtime_stall(app: &mut AppState, now: Instant) -> bool {
+    let timeout = Duration::from_secs(app.settings.stall_timeout);
+    let running = watched_run(app);
+    let Some(silence) = app.runtime_watchdog.check(now, timeout, running) else {
+        return false;
+    };
+    app.push_line(
+        LogKind::Error,
+        format!(
+            "runtime appears stalled (no events for {}s)",
+            silence.as_secs()
+        ),
+    );
+    if app.pick_dialog.is_some() {
+        app.push_line(
+            LogKind::Status,
+            "Close the open dialog and press Esc twice to cancel the run, or keep waiting",
+        );
+        return true;
+    }
+    let items = STALL_ACTIONS
+        .iter()
+        .map(|(id, label, detail)| PickDialogItem {
+            id: id.to_string(),
+            label: label.to_string(),
+            detail: Some(detail.to_string()),
+        })
+        .collect::<Vec<_>>();
+    app.pick_dialog = Some(PickDialogState {
+        id: RUNTIME_STALL_PICK_ID.to_string(),
+        title: "Runtime appears stalled (Enter choose, Esc keep waiting)".to_string(),
+        message: None,
+        chosen: vec![false; items.len()],
+        items,
+        selected: 0,
+        multi: false,
+    });
+    true
+}
+
+/// Keys for the stall dialog; Esc keeps waiting.
+pub(crate) fn handle_runtime_stall_key(
+    app: &mut AppState,
+    key: KeyCode,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) -> bool {
+    let Some(pick) = app.pick_dialog.as_mut() else {
+        return false;
+    };
+    let action = match key {
+        KeyCode::Esc => None,
+        KeyCode::Up => {
+            pick.selected = pick.selected.saturating_sub(1);
+            return true;
+        }
+        KeyCode::Down => {
+            if pick.selected + 1 < pick.items.len() {
+                pick.selected += 1;
+            }
+            return true;
+        }
+        KeyCode::Enter => pick.items.get(pick.selected).map(|item| item.id.clone()),
+        _ => return false,
+    };
+    app.pick_dialog = None;
+    match action.as_deref() {
+        Some("ping") => send_ping(app, child_stdin, next_id),
+        Some("cancel") => cancel_stalled_run(app, child_stdin, next_id),
+        Some("snapshot") => save_diagnostics_snapshot(app, child_stdin),
+        _ => app.push_line(LogKind::Status, "Still waiting for the runtime"),
+    }
+    true
+}
+
+fn send_ping(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) {
+    if app.rpc_pending.ping_id.is_some() {
+        app.push_line(LogKind::Status, "A ping is already waiting for an answer");
+        return;
+    }
+    let id = next_id();
+    app.rpc_pending.ping_id = Some(id.clone());
+    app.rpc_pending.ping_sent_at = Some(Instant::now());
+    if let Err(error) = send_request(child_stdin, &id, "ping", json!({})) {
+        app.rpc_pending.ping_id = None;
+        app.rpc_pending.ping_sent_at = None;
+        app.push_error_report("send error", error.to_string());
+        return;
+    }
+    app.push_line(LogKind::Status, "Ping sent to the runtime");
+}
+
+/// Any answer proves the runtime reads requests, including "method not found" from runtimes
+/// without `ping`.
+pub(crate) fn handle_ping_response(app: &mut AppState, sent_at: Option<Instant>) {
+    let message = match sent_at {
+        Some(sent_at) => format!(
+            "Runtime answered ping after {}ms; the run itself is still quiet",
+            sent_at.elapsed().as_millis()
+        ),
+        None => "Runtime answered ping; the run itself is still quiet".to_string(),
+    };
+    app.push_line(LogKind::Status, message);
+}
+
+fn cancel_stalled_run(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) {
+    let Some(run_id) = app.runtime_info.active_run_id.clone() else {
+        app.push_line(LogKind::Status, "No active run id to cancel yet");
+        return;
+    };
+    if app.rpc_pending.run_cancel_id.is_some() {
+        app.push_line(LogKind::Status, "Cancellation is already pending");
+        return;
+    }
+    let id = next_id();
+    app.rpc_pending.run_cancel_id = Some(id.clone());
+    if let Err(error) = send_run_cancel(child_stdin, &id, &run_id, Some("runtime stalled")) {
+        app.rpc_pending.run_cancel_id = None;
+        app.push_error_report("send error", error.to_string());
+    } else {
+        app.push_line(LogKind::Status, "Cancel requested (runtime stalled)");
+    }
+}
+
+/// `pending` is this tab's in-flight requests as `(id, method, age)`.
+fn diagnostics_snapshot(
+    app: &AppState,
+    pending: &[(String, String, Duration)],
+    now: Instant,
+) -> String {
+    let mut report = format!(
+        "codelia-tui {} runtime stall snapshot\n",
+        env!("CARGO_PKG_VERSION")
+    );
+    let info = &app.runtime_info;
+    let _ = writeln!(
+        report,
+        "run_status: {}\nactive_run_id: {}\nsilence: {}s\nruntime: {} {}\nsession_id: {}\n",
+        app.run_status.as_deref().unwrap_or("-"),
+        info.active_run_id.as_deref().unwrap_or("-"),
+        app.runtime_watchdog.silence(now).as_secs(),
+        info.server_name.as_deref().unwrap_or("-"),
+        info.server_version.as_deref().unwrap_or("-"),
+        info.session_id.as_deref().unwrap_or("-"),
+    );
+    let _ = writeln!(report, "# pending requests ({})", pending.len());
+    for (id, method, age) in pending {
+        let _ = writeln!(report, "{id} {method} {}ms", age.as_millis());
+    }
+    let _ = writeln!(report, "\n# recent runtime events (oldest first)");
+    for (age, line) in app.runtime_watchdog.recent_lines(now) {
+        let _ = writeln!(report, "-{}s {line}", age.as_secs());
+    }
+    report
+}
+
+fn save_diagnostics_snapshot(app: &mut AppState, child_stdin: &RuntimeStdin) {
+    let created_at_unix_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|value| value.as_millis())
+        .unwrap_or(0);
+    let path = std::env::temp_dir().join(format!("codelia-tui-stall-{created_at_unix_ms}.log"));
+    match std::fs::write(
+        &path,
+        diagnostics_snapshot(app, &child_stdin.in_flight_summaries(), Instant::now()),
+    ) {
+        Ok(()) => app.push_line(
+            LogKind::Status,
+            format!("Diagnostics snapshot saved to {}", path.display()),
+        ),
+        Err(error) => {
+            app.push_error_report("snapshot failed", format!("{}: {error}", path.display()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_runtime_stall, diagnostics_snapshot, RUNTIME_STALL_PICK_ID};
+    use crate::app::AppState;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn silent_run_opens_the_stall_dialog_once() {
+        let mut app = AppState::default();
+        let start = Instant::now();
+        assert!(!check_runtime_stall(&mut app, start));
+        app.run_status = Some("running".to_string());
+        app.runtime_watchdog
+            .record_line("{\"method\":\"agent.event\"}", start);
+        assert!(!check_runtime_stall(
+            &mut app,
+            start + Duration::from_secs(30)
+        ));
+
+        let stalled = start + Duration::from_secs(91);
+        assert!(check_runtime_stall(&mut app, stalled));
+        assert!(!check_runtime_stall(&mut app, stalled));
+        assert_eq!(
+            app.pick_dialog.as_ref().map(|pick| pick.id.as_str()),
+            Some(RUNTIME_STALL_PICK_ID)
+        );
+        assert!(app
+            .log
+            .iter()
+            .any(|line| line.plain_text() == "runtime appears stalled (no events for 91s)"));
+
+        let pending = vec![("7".to_string(), "ping".to_string(), Duration::from_secs(2))];
+        let snapshot = diagnostics_snapshot(&app, &pending, stalled);
+        assert!(snapshot.contains("run_status: running\n"));
+        assert!(snapshot.contains("silence: 91s\n"));
+        assert!(snapshot.contains("# pending requests (1)\n7 ping 2000ms\n"));
+        assert!(snapshot.contains("-91s {\"method\":\"agent.event\"}"));
+    }
+
+    #[test]
+    fn silence_during_a_tool_call_is_not_a_stall() {
+        let mut app = AppState::default();
+        let start = Instant::now();
+        app.run_status = Some("running".to_string());
+        app.runtime_watchdog.record_line("{}", start);
+        app.active_tool = Some(("call-1".to_string(), "shell".to_string()));
+        assert!(!check_runtime_stall(
+            &mut app,
+            start + Duration::from_secs(600)
+        ));
+
+        // The count restarts when the tool call ends.
+        app.active_tool = None;
+        let ended = start + Duration::from_secs(601);
+        assert!(!check_runtime_stall(&mut app, ended));
+        assert!(!check_runtime_stall(
+            &mut app,
+            ended + Duration::from_secs(60)
+        ));
+        assert!(check_runtime_stall(
+            &mut app,
+            ended + Duration::from_secs(91)
+        ));
+    }
+}
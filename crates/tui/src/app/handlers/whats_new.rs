@@ -0,0 +1,144 @@
+use crate::app::state::LogKind;
+use crate::app::util::settings::{load_last_run_version, save_last_run_version};
+use crate::app::util::update::changelog::{
+    entry_command, parse_changelog, sections_since, unreleased_for, ChangelogSection, CHANGELOG,
+    UNRELEASED,
+};
+use crate::app::util::update::{current_version, parse_version};
+use crate::app::{AppState, WhatsNewPanelState};
+use crossterm::event::KeyCode;
+
+/// Opens the notes of every release since the last start, then records this version. A first
+/// start only records it.
+pub(crate) fn show_whats_new_after_upgrade(app: &mut AppState) {
+    let current = current_version();
+    let last_run = load_last_run_version();
+    if last_run.as_deref() == Some(current.as_str()) {
+        return;
+    }
+    if let Err(error) = save_last_run_version(&current) {
+        // The panel would reappear on every start; say why once.
+        app.push_error_report("last-run version save failed", error);
+    }
+    let Some(last_run) = last_run else {
+        return;
+    };
+    let sections = sections_since(&parse_changelog(CHANGELOG), &last_run, &current);
+    if !sections.is_empty() {
+        open_whats_new_panel(app, &sections);
+    }
+}
+
+/// `/whatsnew`: notes of the running version (the unreleased ones until a release names it), or
+/// the newest ones for a build the changelog does not know yet.
+pub(crate) fn open_current_whats_new(app: &mut AppState) {
+    let sections = parse_changelog(CHANGELOG);
+    let current_text = current_version();
+    let current = parse_version(&current_text);
+    let section = unreleased_for(&sections, &current_text)
+        .or_else(|| {
+            sections.iter().find(|section| {
+                section.version != UNRELEASED && parse_version(&section.version) <= current
+            })
+        })
+        .or(sections.first());
+    match section {
+        Some(section) => open_whats_new_panel(app, std::slice::from_ref(section)),
+        None => app.push_line(LogKind::Status, "No release notes bundled"),
+    }
+}
+
+/// Sections are newest first; with more than one, each gets a version row.
+pub(crate) fn open_whats_new_panel(app: &mut AppState, sections: &[ChangelogSection]) {
+    let Some(newest) = sections.first() else {
+        return;
+    };
+    let mut rows = Vec::new();
+    let mut commands = Vec::new();
+    for section in sections {
+        if sections.len() > 1 {
+            rows.push(format!("{}:", section.version));
+            commands.push(None);
+        }
+        for entry in &section.entries {
+            rows.push(format!("• {entry}"));
+            commands.push(entry_command(entry));
+        }
+    }
+    let selected = commands.iter().position(Option::is_some).unwrap_or(0);
+    app.whats_new_panel = Some(WhatsNewPanelState {
+        title: if newest.version == UNRELEASED {
+            "What's new (unreleased)".to_string()
+        } else {
+            format!("What's new in {}", newest.version)
+        },
+        header: "Enter: try the linked command  Esc: dismiss".to_string(),
+        rows,
+        commands,
+        selected,
+    });
+}
+
+pub(crate) fn handle_whats_new_panel_key(app: &mut AppState, key: KeyCode) -> Option<bool> {
+    let panel = app.whats_new_panel.as_mut()?;
+    let last = panel.rows.len().saturating_sub(1);
+    match key {
+        KeyCode::Esc => app.whats_new_panel = None,
+        KeyCode::Up => panel.selected = panel.selected.saturating_sub(1),
+        KeyCode::Down => panel.selected = usize::min(panel.selected + 1, last),
+        KeyCode::PageUp => panel.selected = panel.selected.saturating_sub(5),
+        KeyCode::PageDown => panel.selected = usize::min(panel.selected + 5, last),
+        KeyCode::Enter => {
+            let Some(command) = panel.commands.get(panel.selected).cloned().flatten() else {
+                return Some(false);
+            };
+            app.whats_new_panel = None;
+            app.input.set_from(&format!("{command} "));
+        }
+        _ => return Some(false),
+    }
+    Some(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{handle_whats_new_panel_key, open_whats_new_panel};
+    use crate::app::util::update::changelog::ChangelogSection;
+    use crate::app::AppState;
+    use crossterm::event::KeyCode;
+
+    #[test]
+    fn enter_on_an_entry_puts_its_command_in_the_composer() {
+        let mut app = AppState::default();
+        let section = |version: &str, entries: &[&str]| ChangelogSection {
+            version: version.to_string(),
+            entries: entries.iter().map(|entry| entry.to_string()).collect(),
+        };
+        open_whats_new_panel(
+            &mut app,
+            &[
+                section(
+                    "0.2.0",
+                    &["Faster startup.", "`/glyphs ascii` for old fonts."],
+                ),
+                section("0.1.9", &["`/tab` opens tabs."]),
+            ],
+        );
+        let panel = app.whats_new_panel.as_ref().expect("panel");
+        assert_eq!(panel.title, "What's new in 0.2.0");
+        assert_eq!(panel.rows[0], "0.2.0:");
+        assert_eq!(panel.selected, 2);
+
+        handle_whats_new_panel_key(&mut app, KeyCode::Up);
+        assert_eq!(
+            handle_whats_new_panel_key(&mut app, KeyCode::Enter),
+            Some(false)
+        );
+        assert!(app.whats_new_panel.is_some());
+
+        handle_whats_new_panel_key(&mut app, KeyCode::Down);
+        handle_whats_new_panel_key(&mut app, KeyCode::Enter);
+        assert!(app.whats_new_panel.is_none());
+        assert_eq!(app.input.current(), "/glyphs ");
+    }
+}
@@ -0,0 +1,57 @@
+use crate::app::runtime::{send_cwd_set, RuntimeStdin};
+use crate::app::state::LogKind;
+use crate::app::AppState;
+
+/// `/cd` with no argument: report where runs currently execute.
+pub(crate) fn show_working_dir(app: &mut AppState) {
+    let message = match (
+        app.runtime_info.working_dir.as_deref(),
+        app.runtime_info.sandbox_root.as_deref(),
+    ) {
+        (Some(cwd), Some(root)) if cwd != root => {
+            format!("Working directory: {cwd} (sandbox root {root})")
+        }
+        (Some(cwd), _) => format!("Working directory: {cwd}"),
+        (None, _) => "Working directory: sandbox root (use /cd <path> to change it)".to_string(),
+    };
+    app.push_line(LogKind::Status, message);
+}
+
+/// Asks the runtime to move the working directory for subsequent runs.
+/// `prompt` is held back and started once the runtime accepts the directory.
+pub(crate) fn request_cwd_set(
+    app: &mut AppState,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+    path: &str,
+    prompt: Option<String>,
+) -> bool {
+    if !app.runtime_info.supports_cwd_set {
+        app.push_line(LogKind::Error, "Working directory change unavailable");
+        return false;
+    }
+    if app.rpc_pending.cwd_set_id.is_some() {
+        app.push_line(
+            LogKind::Status,
+            "Working directory change already in progress",
+        );
+        return false;
+    }
+    if app.is_running() || app.rpc_pending.run_start_id.is_some() {
+        app.push_line(
+            LogKind::Error,
+            "Cannot change the working directory while a run is active",
+        );
+        return false;
+    }
+    let id = next_id();
+    app.rpc_pending.cwd_set_id = Some(id.clone());
+    app.rpc_pending.cwd_set_prompt = prompt;
+    if let Err(error) = send_cwd_set(child_stdin, &id, path) {
+        app.rpc_pending.cwd_set_id = None;
+        app.rpc_pending.cwd_set_prompt = None;
+        app.push_error_report("send error", error.to_string());
+        return false;
+    }
+    true
+}
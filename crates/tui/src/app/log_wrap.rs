@@ -1,12 +1,17 @@
-use crate::app::state::{LogKind, LogLine, LogSpan, LogTone};
+use crate::app::state::{
+    find_log_matches, ImagePreview, LogKind, LogLine, LogSearchMatch, LogSpan, LogTone,
+};
 use crate::app::theme::ui_colors;
 use crate::app::util::text::{
     char_width, detect_continuation_prefix, wrap_line, wrap_line_with_continuation,
 };
 use crate::app::{AppState, WrappedLogCache};
+use ratatui::buffer::{Buffer, CellDiffOption};
+use ratatui::layout::Rect;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use std::sync::OnceLock;
+use std::num::NonZeroU16;
+use std::sync::{Arc, OnceLock};
 use std::time::Instant;
 
 static TRUECOLOR_SUPPORT: OnceLock<bool> = OnceLock::new();
@@ -91,6 +96,9 @@ fn style_for(span: &LogSpan) -> Style {
     if let Some(fg) = span.fg {
         style = style.fg(syntax_color(fg.r, fg.g, fg.b));
     }
+    if span.link.is_some() {
+        style = style.add_modifier(Modifier::UNDERLINED);
+    }
     style
 }
 
@@ -469,9 +477,16 @@ fn pad_background_line(mut line: LogLine, width: usize) -> LogLine {
     line
 }
 
-fn wrap_log_lines(lines: &[LogLine], width: usize) -> Vec<LogLine> {
+pub(crate) fn wrap_log_lines(lines: &[LogLine], width: usize) -> Vec<LogLine> {
+    wrap_log_lines_with_starts(lines, width).0
+}
+
+/// Wrapped rows plus the first row of each input line.
+fn wrap_log_lines_with_starts(lines: &[LogLine], width: usize) -> (Vec<LogLine>, Vec<usize>) {
     let mut out = Vec::new();
+    let mut starts = Vec::with_capacity(lines.len());
     for line in lines {
+        starts.push(out.len());
         if line.plain_text().is_empty() {
             out.push(line.clone());
             continue;
@@ -489,7 +504,8 @@ fn wrap_log_lines(lines: &[LogLine], width: usize) -> Vec<LogLine> {
                 .or_else(|| detect_continuation_prefix(&line.plain_text()))
         };
 
-        if line.is_single_span() {
+        // `with_text` rebuilds a plain span, so a lone link or image takes the multi-span path.
+        if line.is_single_span() && line.spans[0].link.is_none() && line.spans[0].image.is_none() {
             let wrapped_rows = if let Some(prefix) = continuation_prefix.as_deref() {
                 wrap_line_with_continuation(&line.plain_text(), wrap_width, prefix)
             } else {
@@ -524,7 +540,7 @@ fn wrap_log_lines(lines: &[LogLine], width: usize) -> Vec<LogLine> {
                 .map(|wrapped| pad_background_line(wrapped, width)),
         );
     }
-    out
+    (out, starts)
 }
 
 pub(crate) fn cached_wrap_log_lines(app: &mut AppState, width: usize) -> &[LogLine] {
@@ -537,12 +553,13 @@ pub(crate) fn cached_wrap_log_lines(app: &mut AppState, width: usize) -> &[LogLi
     );
     if !cache_hit {
         let started = Instant::now();
-        let wrapped = wrap_log_lines(&app.log, width);
+        let (wrapped, line_starts) = wrap_log_lines_with_starts(&app.log, width);
         let wrapped_total = wrapped.len();
         app.wrapped_log_cache = Some(WrappedLogCache {
             width,
             log_version: app.log_version,
             wrapped,
+            line_starts,
         });
         app.record_wrap_cache_miss(started.elapsed(), wrapped_total);
     } else if let Some(wrapped_total) = app
@@ -558,6 +575,28 @@ pub(crate) fn cached_wrap_log_lines(app: &mut AppState, width: usize) -> &[LogLi
         .unwrap_or(&[])
 }
 
+/// Whole log lines behind the wrapped rows last drawn, so links split by wrapping stay intact.
+pub(crate) fn visible_log_lines(app: &AppState) -> &[LogLine] {
+    let Some(width) = app.wrapped_log_cache.as_ref().map(|cache| cache.width) else {
+        return &[];
+    };
+    let visible = app.render_state.visible_start..app.render_state.visible_end;
+    let mut row_end = app.render_state.wrapped_total;
+    let mut range: Option<(usize, usize)> = None;
+    for (index, line) in app.log.iter().enumerate().rev() {
+        if row_end <= visible.start {
+            break;
+        }
+        let row_start =
+            row_end.saturating_sub(wrap_log_lines(std::slice::from_ref(line), width).len());
+        if row_start < visible.end {
+            range = Some((index, range.map_or(index, |(_, last)| last)));
+        }
+        row_end = row_start;
+    }
+    range.map_or(&[], |(first, last)| &app.log[first..=last])
+}
+
 pub(crate) fn log_lines_to_lines(lines: &[LogLine]) -> Vec<Line<'static>> {
     lines
         .iter()
@@ -572,6 +611,79 @@ pub(crate) fn log_lines_to_lines(lines: &[LogLine]) -> Vec<Line<'static>> {
         .collect()
 }
 
+/// Rebuilds the Ctrl+F match index when the wrap width, the log, or the query changed.
+pub(crate) fn refresh_log_search_index(app: &mut AppState, width: usize) {
+    let Some(query) = app
+        .log_search
+        .as_ref()
+        .filter(|search| !search.is_indexed(width, app.log_version))
+        .map(|search| search.query.clone())
+    else {
+        return;
+    };
+    let matches = find_log_matches(
+        cached_wrap_log_lines(app, width)
+            .iter()
+            .map(LogLine::plain_text),
+        &query,
+    );
+    let log_version = app.log_version;
+    if let Some(search) = app.log_search.as_mut() {
+        search.set_matches(width, log_version, matches);
+    }
+}
+
+fn highlight_style(style: Style, current: bool) -> Style {
+    let modifier = if current {
+        Modifier::REVERSED | Modifier::BOLD | Modifier::UNDERLINED
+    } else {
+        Modifier::REVERSED
+    };
+    style.add_modifier(modifier)
+}
+
+/// Reverses the matched chars of drawn rows (`first_row` is the wrapped row of `lines[0]`);
+/// the current match is also bold and underlined.
+pub(crate) fn highlight_search_matches(
+    lines: &mut [Line<'static>],
+    first_row: usize,
+    matches: &[LogSearchMatch],
+    current: Option<LogSearchMatch>,
+) {
+    let start = matches.partition_point(|found| found.row < first_row);
+    for found in &matches[start..] {
+        let Some(line) = lines.get_mut(found.row - first_row) else {
+            break;
+        };
+        let is_current = current == Some(*found);
+        let mut spans = Vec::with_capacity(line.spans.len() + 2);
+        let mut offset = 0;
+        for span in line.spans.drain(..) {
+            let chars = span.content.chars().collect::<Vec<_>>();
+            let span_end = offset + chars.len();
+            let hit_start = found.start.clamp(offset, span_end) - offset;
+            let hit_end = (found.start + found.len).clamp(offset, span_end) - offset;
+            if hit_start == hit_end {
+                spans.push(span);
+            } else {
+                let piece = |range: std::ops::Range<usize>| chars[range].iter().collect::<String>();
+                if hit_start > 0 {
+                    spans.push(Span::styled(piece(0..hit_start), span.style));
+                }
+                spans.push(Span::styled(
+                    piece(hit_start..hit_end),
+                    highlight_style(span.style, is_current),
+                ));
+                if hit_end < chars.len() {
+                    spans.push(Span::styled(piece(hit_end..chars.len()), span.style));
+                }
+            }
+            offset = span_end;
+        }
+        line.spans = spans;
+    }
+}
+
 pub(crate) fn wrapped_log_range_to_lines(
     app: &mut AppState,
     width: usize,
@@ -590,12 +702,189 @@ pub(crate) fn wrapped_log_range_to_lines(
     log_lines_to_lines(&wrapped[clamped_start..clamped_end])
 }
 
+/// Columns `start..end` of wrapped row `row` that link to `url`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LogLinkRun {
+    pub row: usize,
+    pub start: usize,
+    pub end: usize,
+    pub url: String,
+}
+
+fn log_link_runs(lines: &[LogLine]) -> Vec<LogLinkRun> {
+    let mut runs = Vec::new();
+    for (row, line) in lines.iter().enumerate() {
+        let mut column = 0;
+        for span in line.spans() {
+            let width = visual_width(&span.text);
+            if let Some(url) = span.link.as_ref().filter(|_| width > 0) {
+                runs.push(LogLinkRun {
+                    row,
+                    start: column,
+                    end: column + width,
+                    url: url.clone(),
+                });
+            }
+            column += width;
+        }
+    }
+    runs
+}
+
+/// Link runs of the wrapped rows `start..end`, with rows counted from `start`.
+pub(crate) fn wrapped_log_range_links(
+    app: &mut AppState,
+    width: usize,
+    start: usize,
+    end: usize,
+) -> Vec<LogLinkRun> {
+    if width == 0 || start >= end {
+        return Vec::new();
+    }
+    let wrapped = cached_wrap_log_lines(app, width);
+    let clamped_end = end.min(wrapped.len());
+    let clamped_start = start.min(clamped_end);
+    log_link_runs(&wrapped[clamped_start..clamped_end])
+}
+
+pub(crate) struct LogImageRun {
+    pub row: usize,
+    pub column: usize,
+    pub preview: Arc<ImagePreview>,
+}
+
+fn log_image_runs(lines: &[LogLine]) -> Vec<LogImageRun> {
+    let mut runs = Vec::new();
+    for (row, line) in lines.iter().enumerate() {
+        let mut column = 0;
+        for span in line.spans() {
+            if let Some(preview) = &span.image {
+                runs.push(LogImageRun {
+                    row,
+                    column,
+                    preview: Arc::clone(preview),
+                });
+            }
+            column += visual_width(&span.text);
+        }
+    }
+    runs
+}
+
+/// Image previews of the wrapped rows `start..end`, with rows counted from `start`.
+pub(crate) fn wrapped_log_range_images(
+    app: &mut AppState,
+    width: usize,
+    start: usize,
+    end: usize,
+) -> Vec<LogImageRun> {
+    if width == 0 || start >= end {
+        return Vec::new();
+    }
+    let wrapped = cached_wrap_log_lines(app, width);
+    let clamped_end = end.min(wrapped.len());
+    let clamped_start = start.min(clamped_end);
+    log_image_runs(&wrapped[clamped_start..clamped_end])
+}
+
+/// Wraps the linked cells of rendered log rows in OSC 8 escapes. The forced width keeps
+/// ratatui's diff and cursor math on the visible glyph, so terminals without hyperlink
+/// support just show the underlined text.
+pub(crate) fn apply_log_hyperlinks(buffer: &mut Buffer, area: Rect, runs: &[LogLinkRun]) {
+    for run in runs {
+        let Ok(row) = u16::try_from(run.row) else {
+            break;
+        };
+        if row >= area.height {
+            break;
+        }
+        let end = run.end.min(usize::from(area.width));
+        let mut column = run.start;
+        while column < end {
+            let position = (area.x + column as u16, area.y + row);
+            let Some(cell) = buffer.cell_mut(position) else {
+                break;
+            };
+            let symbol = cell.symbol().to_string();
+            let width = visual_width(&symbol).max(1);
+            if let Some(forced) = NonZeroU16::new(width as u16) {
+                cell.set_symbol(&format!("\x1b]8;;{}\x1b\\{symbol}\x1b]8;;\x1b\\", run.url));
+                cell.set_diff_option(CellDiffOption::ForcedWidth(forced));
+            }
+            column += width;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{log_lines_to_lines, wrap_log_lines};
+    use super::{
+        apply_log_hyperlinks, log_lines_to_lines, log_link_runs, wrap_log_lines, LogLinkRun,
+    };
     use crate::app::state::{LogColor, LogKind, LogLine, LogSpan, LogTone};
     use crate::app::theme::ui_colors;
+    use ratatui::buffer::{Buffer, CellDiffOption};
+    use ratatui::layout::Rect;
     use ratatui::style::{Color, Modifier};
+    use std::num::NonZeroU16;
+
+    #[test]
+    fn wrapped_links_keep_targets_and_become_osc8_cells() {
+        let url = "https://example.com/guide";
+        let line = LogLine::new_with_spans(vec![
+            LogSpan::new(LogKind::Assistant, LogTone::Summary, "see "),
+            LogSpan::new(LogKind::Assistant, LogTone::Summary, "guide link").with_link(url),
+        ]);
+        let wrapped = wrap_log_lines(&[line], 8);
+        let runs = log_link_runs(&wrapped);
+        assert_eq!(
+            runs,
+            vec![
+                LogLinkRun {
+                    row: 0,
+                    start: 4,
+                    end: 8,
+                    url: url.to_string()
+                },
+                LogLinkRun {
+                    row: 1,
+                    start: 0,
+                    end: 6,
+                    url: url.to_string()
+                },
+            ]
+        );
+        let rendered = log_lines_to_lines(&wrapped);
+        assert!(rendered[1].spans[0]
+            .style
+            .add_modifier
+            .contains(Modifier::UNDERLINED));
+
+        let area = Rect::new(0, 0, 8, 2);
+        let mut buffer = Buffer::empty(area);
+        buffer.set_string(0, 0, "see 漢d", ratatui::style::Style::default());
+        let runs = [LogLinkRun {
+            row: 0,
+            start: 4,
+            end: 7,
+            url: url.to_string(),
+        }];
+        apply_log_hyperlinks(&mut buffer, area, &runs);
+        assert_eq!(buffer[(3, 0)].symbol(), " ");
+        assert_eq!(
+            buffer[(4, 0)].symbol(),
+            format!("\x1b]8;;{url}\x1b\\漢\x1b]8;;\x1b\\")
+        );
+        assert_eq!(
+            buffer[(4, 0)].diff_option,
+            CellDiffOption::ForcedWidth(NonZeroU16::new(2).unwrap())
+        );
+        assert_eq!(
+            buffer[(6, 0)].symbol(),
+            format!("\x1b]8;;{url}\x1b\\d\x1b]8;;\x1b\\")
+        );
+        assert_eq!(buffer[(7, 0)].symbol(), " ");
+    }
 
     #[test]
     fn wraps_multi_span_code_lines_preserving_foreground_spans() {
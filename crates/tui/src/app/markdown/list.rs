@@ -0,0 +1,107 @@
+/// Bullet glyph per nesting depth; deeper levels reuse the last one.
+const BULLETS: [&str; 3] = ["•", "◦", "▪"];
+
+/// `- `, `* `, `+ ` → `None`; `12. ` / `3) ` → the marker with its space. Returns the marker
+/// and the rest of the line after it.
+fn parse_marker(text: &str) -> Option<(Option<&str>, &str)> {
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(rest) = text.strip_prefix(bullet) {
+            return Some((None, rest));
+        }
+    }
+    let digits = text.bytes().take_while(u8::is_ascii_digit).count();
+    if digits == 0 || digits > 9 {
+        return None;
+    }
+    let after = &text[digits..];
+    let rest = after
+        .strip_prefix(". ")
+        .or_else(|| after.strip_prefix(") "))?;
+    Some((Some(&text[..digits + 2]), rest))
+}
+
+struct OpenItem {
+    /// Indent of the item's marker in the source.
+    source_indent: usize,
+    /// Column where the item's text starts in the source and in the rendered line.
+    source_content: usize,
+    rendered_content: usize,
+}
+
+/// Tracks the open list items while rendering, so nesting is judged from the source indent
+/// and re-laid out from the parent's rendered text column: children line up under their
+/// parent's text, and ordered items keep the `N. ` width `detect_continuation_prefix` uses
+/// to indent wrapped rows.
+#[derive(Default)]
+pub(super) struct ListLayout {
+    open: Vec<OpenItem>,
+}
+
+impl ListLayout {
+    /// Rewrites a list item line; `None` for lines that are not list items.
+    pub(super) fn item(&mut self, raw: &str) -> Option<String> {
+        let indent = raw.bytes().take_while(|byte| *byte == b' ').count();
+        let (ordered, rest) = parse_marker(&raw[indent..])?;
+        while self
+            .open
+            .last()
+            .is_some_and(|item| item.source_indent > indent)
+        {
+            self.open.pop();
+        }
+        if self
+            .open
+            .last()
+            .is_some_and(|item| item.source_indent == indent)
+        {
+            self.open.pop();
+        }
+        let rendered_indent = match self.open.last() {
+            Some(parent) => parent.rendered_content,
+            // Top-level items keep their own indent, as other paragraphs do.
+            None => indent,
+        };
+        let marker = match ordered {
+            Some(marker) => marker.to_string(),
+            None => format!("{} ", BULLETS[self.open.len().min(BULLETS.len() - 1)]),
+        };
+        let marker_width = marker.chars().count();
+        let source_marker_width = ordered.map_or(2, str::len);
+        self.open.push(OpenItem {
+            source_indent: indent,
+            source_content: indent + source_marker_width,
+            rendered_content: rendered_indent + marker_width,
+        });
+        Some(format!("{}{marker}{rest}", " ".repeat(rendered_indent)))
+    }
+
+    /// Re-indents a paragraph continuing an open item (indented at least to its text);
+    /// an unindented line ends the list.
+    pub(super) fn continuation(&mut self, raw: &str) -> Option<String> {
+        let indent = raw.bytes().take_while(|byte| *byte == b' ').count();
+        if raw.trim().is_empty() {
+            return None;
+        }
+        if indent == 0 {
+            self.open.clear();
+            return None;
+        }
+        while self
+            .open
+            .last()
+            .is_some_and(|item| item.source_content > indent)
+        {
+            self.open.pop();
+        }
+        let item = self.open.last()?;
+        Some(format!(
+            "{}{}",
+            " ".repeat(item.rendered_content),
+            &raw[indent..]
+        ))
+    }
+
+    pub(super) fn reset(&mut self) {
+        self.open.clear();
+    }
+}
@@ -1,7 +1,11 @@
 mod language_aliases;
+mod list;
+mod table;
 
 use crate::app::state::{LogColor, LogKind, LogLine, LogSpan, LogTone};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::OnceLock;
+use std::time::{Duration, Instant};
 use syntect::easy::HighlightLines;
 use syntect::highlighting::{Theme, ThemeSet};
 use syntect::parsing::SyntaxSet;
@@ -10,31 +14,108 @@ use crate::app::theme::{
     inline_palette, syntect_fallback_theme_name, syntect_theme_name, InlinePalette,
 };
 use language_aliases::language_aliases;
+use list::ListLayout;
+use table::MarkdownTable;
 
 struct HighlightAssets {
     syntax_set: SyntaxSet,
-    theme: Theme,
+    /// Every bundled syntect theme by name. The active one is looked up per code block, so a
+    /// `/theme` switch recolors blocks rendered after it; lines already in the log keep theirs.
+    themes: ThemeSet,
 }
 
-// NOTE: syntect highlight assets are intentionally initialized once per process.
-// Theme changes applied at runtime update UI colors immediately, but syntect code
-// highlighting stays on the initially loaded theme until TUI restart.
+impl HighlightAssets {
+    fn theme_named(&self, name: &str) -> Option<&Theme> {
+        let themes = &self.themes.themes;
+        themes
+            .get(name)
+            .or_else(|| themes.get(syntect_fallback_theme_name()))
+            .or_else(|| themes.values().next())
+    }
+
+    fn theme(&self) -> Option<&Theme> {
+        self.theme_named(syntect_theme_name())
+    }
+}
+
+// Loaded once per process; only the theme lookup follows the active UI theme.
 static HIGHLIGHT_ASSETS: OnceLock<Option<HighlightAssets>> = OnceLock::new();
 
+// Microseconds spent deserializing the syntect dumps, for the perf panel; 0 until loaded.
+static HIGHLIGHT_LOAD_MICROS: AtomicU64 = AtomicU64::new(0);
+
+// Compiled on the preload thread too; other syntaxes compile their regexes on first use.
+const PRELOAD_LANGUAGES: &[&str] = &["rust", "js", "python", "bash"];
+
+// Cleared while effects are reduced on slow terminals; lines already rendered keep their colors.
+static SYNTAX_HIGHLIGHTING: AtomicBool = AtomicBool::new(true);
+
+pub(crate) fn set_syntax_highlighting(enabled: bool) {
+    SYNTAX_HIGHLIGHTING.store(enabled, Ordering::Relaxed);
+}
+
+// Columns available to rendered Markdown, taken from the last drawn log width; 0 (no frame
+// yet, headless) lets tables use their natural width.
+static MARKDOWN_WIDTH: AtomicUsize = AtomicUsize::new(0);
+
+// Assistant messages are indented by two columns in the log.
+const MESSAGE_INDENT_WIDTH: usize = 2;
+
+pub(crate) fn set_markdown_width(log_width: usize) {
+    MARKDOWN_WIDTH.store(
+        log_width.saturating_sub(MESSAGE_INDENT_WIDTH),
+        Ordering::Relaxed,
+    );
+}
+
+fn load_highlight_assets() -> Option<HighlightAssets> {
+    let started = Instant::now();
+    // Both come from syntect's compressed binary dumps; syntax regexes stay uncompiled
+    // until a block in that language is highlighted.
+    let syntax_set = SyntaxSet::load_defaults_newlines();
+    let themes = ThemeSet::load_defaults();
+    if themes.themes.is_empty() {
+        return None;
+    }
+    let micros = started.elapsed().as_micros().max(1) as u64;
+    HIGHLIGHT_LOAD_MICROS.store(micros, Ordering::Relaxed);
+    Some(HighlightAssets { syntax_set, themes })
+}
+
 fn highlight_assets() -> Option<&'static HighlightAssets> {
-    HIGHLIGHT_ASSETS
-        .get_or_init(|| {
-            let syntax_set = SyntaxSet::load_defaults_newlines();
-            let theme_set = ThemeSet::load_defaults();
-            let theme = theme_set
-                .themes
-                .get(syntect_theme_name())
-                .cloned()
-                .or_else(|| theme_set.themes.get(syntect_fallback_theme_name()).cloned())
-                .or_else(|| theme_set.themes.values().next().cloned())?;
-            Some(HighlightAssets { syntax_set, theme })
-        })
-        .as_ref()
+    if !SYNTAX_HIGHLIGHTING.load(Ordering::Relaxed) {
+        return None;
+    }
+    HIGHLIGHT_ASSETS.get_or_init(load_highlight_assets).as_ref()
+}
+
+/// Loads the highlight assets on a background thread, so the first code block of a session
+/// does not stall a frame. A highlight requested before the thread finishes waits for it
+/// instead of loading twice.
+pub(crate) fn preload_highlight_assets() {
+    let _ = std::thread::Builder::new()
+        .name("syntect-preload".to_string())
+        .spawn(|| {
+            let Some(assets) = HIGHLIGHT_ASSETS.get_or_init(load_highlight_assets) else {
+                return;
+            };
+            let Some(theme) = assets.theme() else {
+                return;
+            };
+            for language in PRELOAD_LANGUAGES {
+                let syntax = syntax_for_language(&assets.syntax_set, Some(language));
+                let _ =
+                    HighlightLines::new(syntax, theme).highlight_line("x\n", &assets.syntax_set);
+            }
+        });
+}
+
+/// How long loading the highlight assets took; `None` until they are loaded.
+pub(crate) fn highlight_assets_load_time() -> Option<Duration> {
+    match HIGHLIGHT_LOAD_MICROS.load(Ordering::Relaxed) {
+        0 => None,
+        micros => Some(Duration::from_micros(micros)),
+    }
 }
 
 fn push_assistant_span(spans: &mut Vec<LogSpan>, text: &str, fg: Option<LogColor>) {
@@ -89,6 +170,21 @@ fn parse_inline_markdown_spans(value: &str) -> Vec<LogSpan> {
                 }
             }
 
+            let link = markdown_link(rest).or_else(|| {
+                let after_word = value[..cursor]
+                    .chars()
+                    .next_back()
+                    .is_some_and(char::is_alphanumeric);
+                (!after_word).then(|| bare_url(rest)).flatten()
+            });
+            if let Some((label, url, len)) = link {
+                push_assistant_span(&mut spans, &value[..cursor], None);
+                spans
+                    .push(LogSpan::new(LogKind::Assistant, LogTone::Summary, label).with_link(url));
+                let consumed = &rest[len..];
+                return [spans, parse_with_palette(consumed, palette)].concat();
+            }
+
             let next_char_len = rest.chars().next().map(char::len_utf8).unwrap_or(1);
             cursor += next_char_len;
         }
@@ -100,6 +196,51 @@ fn parse_inline_markdown_spans(value: &str) -> Vec<LogSpan> {
     parse_with_palette(value, &inline_palette())
 }
 
+fn is_absolute_url(url: &str) -> bool {
+    ["https://", "http://", "mailto:", "file://"]
+        .iter()
+        .any(|scheme| url.len() > scheme.len() && url.starts_with(scheme))
+}
+
+/// `[label](url)` at the start of `rest` as `(label, url, bytes consumed)`; relative targets
+/// stay plain text because a terminal cannot open them.
+fn markdown_link(rest: &str) -> Option<(&str, &str, usize)> {
+    let after_open = rest.strip_prefix('[')?;
+    let label_end = after_open.find(']')?;
+    let label = &after_open[..label_end];
+    let target = after_open[label_end + 1..].strip_prefix('(')?;
+    let url_end = target.find(')')?;
+    let url = target[..url_end].trim();
+    if label.is_empty() || url.contains(char::is_whitespace) || !is_absolute_url(url) {
+        return None;
+    }
+    Some((label, url, 1 + label_end + 2 + url_end + 1))
+}
+
+/// An `http(s)://` URL at the start of `rest`, without trailing sentence punctuation or a
+/// closing paren it did not open.
+fn bare_url(rest: &str) -> Option<(&str, &str, usize)> {
+    if !rest.starts_with("https://") && !rest.starts_with("http://") {
+        return None;
+    }
+    let end = rest
+        .find(|ch: char| ch.is_whitespace() || matches!(ch, '<' | '>' | '"' | '`'))
+        .unwrap_or(rest.len());
+    let mut url = &rest[..end];
+    loop {
+        let trimmed = url.trim_end_matches(['.', ',', ';', ':', '!', '?', '\'', '*']);
+        let trimmed = match trimmed.strip_suffix(')') {
+            Some(inner) if inner.matches('(').count() <= inner.matches(')').count() => inner,
+            _ => trimmed,
+        };
+        if trimmed.len() == url.len() {
+            break;
+        }
+        url = trimmed;
+    }
+    is_absolute_url(url).then_some((url, url, url.len()))
+}
+
 fn apply_heading_tint(mut spans: Vec<LogSpan>) -> Vec<LogSpan> {
     let palette = inline_palette();
     for span in &mut spans {
@@ -126,7 +267,7 @@ pub(crate) fn highlight_code_line(
 ) -> Option<Vec<LogSpan>> {
     let assets = highlight_assets()?;
     let syntax = syntax_for_language(&assets.syntax_set, language);
-    let mut highlighter = HighlightLines::new(syntax, &assets.theme);
+    let mut highlighter = HighlightLines::new(syntax, assets.theme()?);
     let ranges = highlighter.highlight_line(line, &assets.syntax_set).ok()?;
 
     if ranges.is_empty() {
@@ -209,7 +350,7 @@ fn render_plain_code_lines(lines: &[String]) -> Vec<LogLine> {
 fn render_highlighted_code_lines(lines: &[String], language: Option<&str>) -> Option<Vec<LogLine>> {
     let assets = highlight_assets()?;
     let syntax = syntax_for_language(&assets.syntax_set, language);
-    let mut highlighter = HighlightLines::new(syntax, &assets.theme);
+    let mut highlighter = HighlightLines::new(syntax, assets.theme()?);
 
     let mut rendered = Vec::with_capacity(lines.len());
     for line in lines {
@@ -267,8 +408,15 @@ pub fn render_markdown_lines(value: &str) -> Vec<LogLine> {
     let mut code_block_language: Option<String> = None;
     let mut code_block_lines: Vec<String> = Vec::new();
 
-    for raw in value.split('\n') {
-        let raw = raw.trim_end_matches('\r');
+    let raw_lines = value
+        .split('\n')
+        .map(|raw| raw.trim_end_matches('\r'))
+        .collect::<Vec<_>>();
+    let mut lists = ListLayout::default();
+    let mut index = 0;
+    while index < raw_lines.len() {
+        let raw = raw_lines[index];
+        index += 1;
         let trimmed = raw.trim_start();
         if trimmed.starts_with("```") {
             if in_code_block {
@@ -290,6 +438,13 @@ pub fn render_markdown_lines(value: &str) -> Vec<LogLine> {
             continue;
         }
 
+        if let Some((table, consumed)) = MarkdownTable::parse(&raw_lines[index - 1..]) {
+            out.extend(table.render(MARKDOWN_WIDTH.load(Ordering::Relaxed)));
+            lists.reset();
+            index += consumed - 1;
+            continue;
+        }
+
         let leading_spaces_len = raw.bytes().take_while(|byte| *byte == b' ').count();
         let leading_spaces = &raw[..leading_spaces_len];
         let left_trimmed = &raw[leading_spaces_len..];
@@ -297,11 +452,10 @@ pub fn render_markdown_lines(value: &str) -> Vec<LogLine> {
         let mut is_heading = false;
         if let Some(rest) = left_trimmed.strip_prefix("> ") {
             line = format!("{leading_spaces}│ {rest}");
-        } else if let Some(rest) = left_trimmed.strip_prefix("- ") {
-            line = format!("{leading_spaces}• {rest}");
-        } else if let Some(rest) = left_trimmed.strip_prefix("* ") {
-            line = format!("{leading_spaces}• {rest}");
+        } else if let Some(item) = lists.item(raw) {
+            line = item;
         } else if left_trimmed.starts_with('#') {
+            lists.reset();
             let mut idx = 0;
             for ch in left_trimmed.chars() {
                 if ch == '#' {
@@ -313,6 +467,8 @@ pub fn render_markdown_lines(value: &str) -> Vec<LogLine> {
             let rest = left_trimmed[idx..].trim_start();
             line = format!("{leading_spaces}{rest}");
             is_heading = true;
+        } else if let Some(continued) = lists.continuation(raw) {
+            line = continued;
         }
 
         let mut spans = parse_inline_markdown_spans(&line);
@@ -338,10 +494,48 @@ pub fn render_markdown_lines(value: &str) -> Vec<LogLine> {
 mod tests {
     use super::{
         highlight_assets, highlight_code_line, inline_palette, render_markdown_lines,
-        syntax_for_language,
+        syntax_for_language, MarkdownTable,
     };
     use crate::app::state::{LogColor, LogKind};
     use crate::app::theme::inline_palette_for;
+    use crate::app::util::text::detect_continuation_prefix;
+
+    #[test]
+    fn markdown_links_and_bare_urls_carry_their_targets() {
+        let lines = render_markdown_lines(
+            "See [the docs](https://docs.rs/ratatui) or https://example.com/a_(b).\n\
+             Relative [notes](notes.md) and xhttps://nope.dev stay plain.",
+        );
+        let links = |line: usize| {
+            lines[line]
+                .spans()
+                .iter()
+                .filter_map(|span| Some((span.text.clone(), span.link.clone()?)))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            links(0),
+            vec![
+                (
+                    "the docs".to_string(),
+                    "https://docs.rs/ratatui".to_string()
+                ),
+                (
+                    "https://example.com/a_(b)".to_string(),
+                    "https://example.com/a_(b)".to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            lines[0].plain_text(),
+            "See the docs or https://example.com/a_(b)."
+        );
+        assert!(links(1).is_empty());
+        assert_eq!(
+            lines[1].plain_text(),
+            "Relative [notes](notes.md) and xhttps://nope.dev stay plain."
+        );
+    }
 
     #[test]
     fn fenced_code_block_uses_assistant_code_lines() {
@@ -397,6 +591,16 @@ mod tests {
         assert!(spans.iter().any(|span| span.fg.is_some()));
     }
 
+    #[test]
+    fn syntax_themes_are_looked_up_by_name_with_a_fallback() {
+        let assets = highlight_assets().expect("highlight assets");
+        let ocean = assets.theme_named("base16-ocean.dark").expect("ocean");
+        let solarized = assets.theme_named("Solarized (dark)").expect("solarized");
+        assert_ne!(ocean.settings.background, solarized.settings.background);
+        let unknown = assets.theme_named("no-such-theme").expect("fallback");
+        assert_eq!(unknown.settings.background, solarized.settings.background);
+    }
+
     #[test]
     fn ts_token_resolves_non_plain_syntax() {
         let assets = highlight_assets().expect("highlight assets");
@@ -522,7 +726,41 @@ mod tests {
             .map(|line| line.plain_text())
             .collect::<Vec<_>>();
 
-        assert_eq!(rendered, vec!["• parent", "  • child", "    • grandchild"]);
+        assert_eq!(rendered, vec!["• parent", "  ◦ child", "    ▪ grandchild"]);
+    }
+
+    #[test]
+    fn ordered_and_mixed_lists_nest_under_their_parent_text() {
+        let lines = render_markdown_lines(
+            "1. Install\n   run the script\n   - macOS\n     * brew\n10. Configure\n    - edit `tui.toml`\n\nDone.\n- last",
+        );
+        let rendered = lines
+            .iter()
+            .map(|line| line.plain_text())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            rendered,
+            vec![
+                "1. Install",
+                "   run the script",
+                "   ◦ macOS",
+                "     ▪ brew",
+                "10. Configure",
+                "    ◦ edit tui.toml",
+                "",
+                "Done.",
+                "• last",
+            ]
+        );
+        assert_eq!(
+            detect_continuation_prefix(&rendered[4]),
+            Some("    ".to_string())
+        );
+        assert_eq!(
+            detect_continuation_prefix(&rendered[2]),
+            Some("     ".to_string())
+        );
     }
 
     #[test]
@@ -533,6 +771,56 @@ mod tests {
         assert_eq!(lines[0].plain_text(), "  │ quoted");
     }
 
+    #[test]
+    fn tables_render_aligned_columns_without_pipes() {
+        let lines = render_markdown_lines(
+            "Results:\n| Name | Count | State |\n|:-----|------:|:-----:|\n| `a\\|b` | 3 | ok |\n| **long name** | 12 |\n\nafter",
+        );
+        let rendered = lines
+            .iter()
+            .map(|line| line.plain_text())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rendered,
+            vec![
+                "Results:",
+                "Name      │ Count │ State",
+                "──────────┼───────┼──────",
+                "a|b       │     3 │  ok",
+                "long name │    12 │",
+                "",
+                "after",
+            ]
+        );
+        assert_eq!(lines[1].spans()[0].fg, Some(inline_palette().heading));
+    }
+
+    #[test]
+    fn narrow_tables_fall_back_to_records() {
+        let lines = "| Key | Meaning |\n|---|---|\n| a | first letter |\n| b | second |"
+            .lines()
+            .collect::<Vec<_>>();
+        let (table, consumed) = MarkdownTable::parse(&lines).expect("table");
+        assert_eq!(consumed, 4);
+        assert_eq!(table.render(0).len(), 4);
+        let rendered = table
+            .render(12)
+            .iter()
+            .map(|line| line.plain_text())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rendered,
+            vec![
+                "• Key: a",
+                "  Meaning: first letter",
+                "",
+                "• Key: b",
+                "  Meaning: second",
+            ]
+        );
+        assert!(MarkdownTable::parse(&["a | b", "not a delimiter"]).is_none());
+    }
+
     #[test]
     fn rose_palette_is_available() {
         let palette = inline_palette_for("rose");
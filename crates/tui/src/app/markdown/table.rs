@@ -0,0 +1,220 @@
+use super::{apply_heading_tint, parse_inline_markdown_spans};
+use crate::app::state::{LogKind, LogLine, LogSpan, LogTone};
+use unicode_width::UnicodeWidthStr;
+
+const COLUMN_SEPARATOR: &str = " │ ";
+const HEADER_CROSSING: &str = "─┼─";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+/// `| a | b |` → `["a", "b"]`. Pipes escaped as `\|` or inside backticks stay in the cell.
+fn split_row(line: &str) -> Vec<String> {
+    let trimmed = line.trim();
+    let trimmed = trimmed.strip_prefix('|').unwrap_or(trimmed);
+    let trimmed = match trimmed.strip_suffix('|') {
+        Some(rest) if !rest.ends_with('\\') => rest,
+        _ => trimmed,
+    };
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut in_code = false;
+    let mut chars = trimmed.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' if chars.peek() == Some(&'|') => {
+                cell.push('|');
+                chars.next();
+            }
+            '`' => {
+                in_code = !in_code;
+                cell.push(ch);
+            }
+            '|' if !in_code => cells.push(std::mem::take(&mut cell).trim().to_string()),
+            _ => cell.push(ch),
+        }
+    }
+    cells.push(cell.trim().to_string());
+    cells
+}
+
+fn parse_delimiter(line: &str) -> Option<Vec<Align>> {
+    if !line.contains('-') {
+        return None;
+    }
+    split_row(line)
+        .iter()
+        .map(|cell| {
+            let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+            if dashes.is_empty() || !dashes.chars().all(|ch| ch == '-') {
+                return None;
+            }
+            Some(match (cell.starts_with(':'), cell.ends_with(':')) {
+                (true, true) => Align::Center,
+                (false, true) => Align::Right,
+                _ => Align::Left,
+            })
+        })
+        .collect()
+}
+
+/// GitHub-style table: a header row, a `---` delimiter row with one entry per header cell,
+/// then body rows until a line without a pipe.
+pub(super) struct MarkdownTable {
+    header: Vec<String>,
+    aligns: Vec<Align>,
+    rows: Vec<Vec<String>>,
+}
+
+impl MarkdownTable {
+    /// Parses a table starting at `lines[0]`; returns it with the number of lines consumed.
+    pub(super) fn parse(lines: &[&str]) -> Option<(Self, usize)> {
+        let header_line = lines.first()?;
+        if !header_line.contains('|') {
+            return None;
+        }
+        let header = split_row(header_line);
+        let aligns = parse_delimiter(lines.get(1)?)?;
+        if aligns.len() != header.len() {
+            return None;
+        }
+        let mut rows = Vec::new();
+        for line in &lines[2..] {
+            if line.trim().is_empty() || !line.contains('|') {
+                break;
+            }
+            let mut row = split_row(line);
+            row.resize(header.len(), String::new());
+            rows.push(row);
+        }
+        let consumed = 2 + rows.len();
+        Some((
+            Self {
+                header,
+                aligns,
+                rows,
+            },
+            consumed,
+        ))
+    }
+
+    fn column_widths(&self) -> Vec<usize> {
+        (0..self.header.len())
+            .map(|column| {
+                std::iter::once(&self.header)
+                    .chain(&self.rows)
+                    .map(|row| cell_width(&row[column]))
+                    .max()
+                    .unwrap_or(0)
+                    .max(1)
+            })
+            .collect()
+    }
+
+    /// Aligned columns when they fit in `width` (0 = unlimited); otherwise each body row
+    /// becomes a small `header: value` record so nothing is cut off.
+    pub(super) fn render(&self, width: usize) -> Vec<LogLine> {
+        let widths = self.column_widths();
+        let total = widths.iter().sum::<usize>()
+            + COLUMN_SEPARATOR.width() * widths.len().saturating_sub(1);
+        if width > 0 && total > width {
+            return self.render_records();
+        }
+        let mut out = vec![self.render_row(&self.header, &widths, true)];
+        let rule = widths
+            .iter()
+            .map(|width| "─".repeat(*width))
+            .collect::<Vec<_>>()
+            .join(HEADER_CROSSING);
+        out.push(LogLine::new_with_spans(vec![border_span(rule)]));
+        for row in &self.rows {
+            out.push(self.render_row(row, &widths, false));
+        }
+        out
+    }
+
+    fn render_row(&self, row: &[String], widths: &[usize], header: bool) -> LogLine {
+        let mut spans = Vec::new();
+        for (column, cell) in row.iter().enumerate() {
+            if column > 0 {
+                spans.push(border_span(COLUMN_SEPARATOR));
+            }
+            let padding = widths[column].saturating_sub(cell_width(cell));
+            let (before, after) = match self.aligns[column] {
+                Align::Left => (0, padding),
+                Align::Right => (padding, 0),
+                Align::Center => (padding / 2, padding - padding / 2),
+            };
+            push_padding(&mut spans, before);
+            spans.extend(cell_spans(cell, header));
+            push_padding(&mut spans, after);
+        }
+        while spans.last().is_some_and(|span| span.text.trim().is_empty()) {
+            spans.pop();
+        }
+        if let Some(last) = spans.last_mut() {
+            last.text.truncate(last.text.trim_end().len());
+        }
+        LogLine::new_with_spans(spans)
+    }
+
+    fn render_records(&self) -> Vec<LogLine> {
+        let mut out = Vec::new();
+        for (index, row) in self.rows.iter().enumerate() {
+            if index > 0 {
+                out.push(LogLine::new(LogKind::Assistant, ""));
+            }
+            for (column, cell) in row.iter().enumerate() {
+                let marker = if column == 0 { "• " } else { "  " };
+                let mut spans = vec![LogSpan::new(LogKind::Assistant, LogTone::Summary, marker)];
+                spans.extend(cell_spans(&self.header[column], true));
+                spans.push(LogSpan::new(LogKind::Assistant, LogTone::Summary, ": "));
+                spans.extend(cell_spans(cell, false));
+                out.push(LogLine::new_with_spans(spans));
+            }
+        }
+        if out.is_empty() {
+            let header = self.header.join(" · ");
+            out.push(LogLine::new_with_spans(cell_spans(&header, true)));
+        }
+        out
+    }
+}
+
+fn cell_spans(cell: &str, header: bool) -> Vec<LogSpan> {
+    let spans = parse_inline_markdown_spans(cell)
+        .into_iter()
+        .filter(|span| !span.text.is_empty())
+        .collect::<Vec<_>>();
+    if header {
+        apply_heading_tint(spans)
+    } else {
+        spans
+    }
+}
+
+/// Display width without the inline markers `cell_spans` strips.
+fn cell_width(cell: &str) -> usize {
+    parse_inline_markdown_spans(cell)
+        .iter()
+        .map(|span| span.text.width())
+        .sum()
+}
+
+fn border_span(text: impl Into<String>) -> LogSpan {
+    LogSpan::new(LogKind::Assistant, LogTone::Detail, text)
+}
+
+fn push_padding(spans: &mut Vec<LogSpan>, count: usize) {
+    if count > 0 {
+        spans.push(LogSpan::new(
+            LogKind::Assistant,
+            LogTone::Summary,
+            " ".repeat(count),
+        ));
+    }
+}
@@ -11,15 +11,19 @@ pub(crate) mod view;
 mod app_state;
 
 pub(crate) use crate::app::state::{
-    ConfirmDialogState, ConfirmMode, ConfirmPhase, ContextPanelState, CursorPhase, LaneListItem,
-    LaneListPanelState, ModelListMode, ModelListPanelState, ModelListSubmitAction,
-    ModelListViewMode, ModelPickerState, ModelSetScope, PendingImageAttachment, PickDialogItem,
-    PickDialogState, PromptDialogState, ProviderPickerState, ReasoningPickerState,
-    SessionListPanelState, SkillsListItemState, SkillsListPanelState, SkillsScopeFilter,
-    StatusLineMode, SyncPhase, ThemeListPanelState, WrappedLogCache,
+    CacheListItem, CacheListPanelState, CacheReadAction, ConfirmDialogState, ConfirmMode,
+    ConfirmPhase, ContextPanelState, CursorPhase, EditConflictPanelState, LaneAttachState,
+    LaneListItem, LaneListPanelState, McpListPanelState, ModelListMode, ModelListPanelState,
+    ModelListSubmitAction, ModelListViewMode, ModelPickerState, ModelSetScope,
+    PendingFileAttachment, PendingImageAttachment, PermissionsPanelState, PickDialogItem,
+    PickDialogState, PromptDialogState, ProviderPickerState, QueuePanelState, ReasoningPickerState,
+    SecretsPanelState, SessionListPanelState, SkillsListItemState, SkillsListPanelState,
+    SkillsScopeFilter, StackedPanel, StatusLineMode, SyncPhase, ThemeListPanelState,
+    ToolsPanelState, WhatsNewPanelState, WideListRows, WrappedLogCache,
 };
 pub(crate) use app_state::{
-    AppState, ErrorDetailMode, LogComponentSpan, PendingPromptRun, PendingRpcMatch,
-    PendingShellResult, PermissionPreviewRecord, PROMPT_DISPATCH_MAX_ATTEMPTS,
+    AppState, CachedOutputContext, CachedOutputTarget, ErrorDetailMode, LogComponentSpan,
+    PendingPromptRun, PendingRpcMatch, PendingShellResult, PermissionPreviewRecord,
+    QueuedAttachments, RetryableRpc, RuntimeLaunchInfo, PROMPT_DISPATCH_MAX_ATTEMPTS,
     PROMPT_DISPATCH_RETRY_BACKOFF,
 };
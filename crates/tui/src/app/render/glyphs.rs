@@ -0,0 +1,197 @@
+use crossterm::cursor::position;
+use crossterm::terminal::{Clear, ClearType};
+use crossterm::QueueableCommand;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use std::io::Write;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+/// One braille spinner frame, one box-drawing line, one check mark: each should advance the
+/// cursor by exactly one column in a font that has them.
+const PROBE_GLYPHS: &str = "⠋─✓";
+
+static GLYPH_MODE: AtomicU8 = AtomicU8::new(GlyphMode::Auto as u8);
+static ENV_LACKS_UNICODE: OnceLock<bool> = OnceLock::new();
+static PROBE_LACKS_UNICODE: OnceLock<bool> = OnceLock::new();
+
+/// `glyphs` setting: which glyph set the UI draws with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum GlyphMode {
+    /// ASCII when the environment or the startup width probe says the font lacks the glyphs.
+    #[default]
+    Auto,
+    Ascii,
+    Unicode,
+}
+
+impl GlyphMode {
+    pub(crate) const VALUES: &'static str = "auto|ascii|unicode";
+
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "auto" => Some(Self::Auto),
+            "ascii" => Some(Self::Ascii),
+            "unicode" => Some(Self::Unicode),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Ascii => "ascii",
+            Self::Unicode => "unicode",
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Ascii,
+            2 => Self::Unicode,
+            _ => Self::Auto,
+        }
+    }
+}
+
+pub(crate) fn set_glyph_mode(mode: GlyphMode) {
+    GLYPH_MODE.store(mode as u8, Ordering::Relaxed);
+}
+
+/// The Linux console and non-UTF-8 locales cannot show the UI glyphs.
+fn env_lacks_unicode(var: impl Fn(&str) -> Option<String>) -> bool {
+    let term = var("TERM").unwrap_or_default().to_ascii_lowercase();
+    if matches!(term.as_str(), "linux" | "vt100" | "vt220" | "dumb") {
+        return true;
+    }
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .into_iter()
+        .find_map(var)
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    !locale.is_empty()
+        && locale != "c"
+        && locale != "posix"
+        && !locale.contains("utf-8")
+        && !locale.contains("utf8")
+}
+
+/// Writes `PROBE_GLYPHS` at the start of the cursor line and checks how far the cursor moved,
+/// then erases it. Call once with raw mode on, before the first frame; no answer means no
+/// verdict.
+pub(crate) fn probe_glyph_widths(stdout: &mut impl Write) {
+    let mut probe = || -> std::io::Result<u16> {
+        stdout.write_all(format!("\r{PROBE_GLYPHS}").as_bytes())?;
+        stdout.flush()?;
+        let (column, _) = position()?;
+        stdout.write_all(b"\r")?;
+        stdout.queue(Clear(ClearType::UntilNewLine))?;
+        stdout.flush()?;
+        Ok(column)
+    };
+    if let Ok(column) = probe() {
+        let expected = PROBE_GLYPHS.chars().count() as u16;
+        let _ = PROBE_LACKS_UNICODE.set(column != expected);
+    }
+}
+
+/// Whether frames are drawn with the ASCII fallback glyphs.
+pub(crate) fn ascii_glyphs() -> bool {
+    match GlyphMode::from_u8(GLYPH_MODE.load(Ordering::Relaxed)) {
+        GlyphMode::Ascii => true,
+        GlyphMode::Unicode => false,
+        GlyphMode::Auto => {
+            PROBE_LACKS_UNICODE.get().copied().unwrap_or(false)
+                || *ENV_LACKS_UNICODE.get_or_init(|| {
+                    env_lacks_unicode(|name| std::env::var(name).ok().filter(|v| !v.is_empty()))
+                })
+        }
+    }
+}
+
+/// Single-column ASCII stand-in for a UI glyph; `None` leaves the symbol as it is.
+fn ascii_fallback(symbol: &str) -> Option<&'static str> {
+    let mut chars = symbol.chars();
+    let ch = chars.next()?;
+    if ch.is_ascii() || chars.next().is_some() {
+        return None;
+    }
+    let fallback = match ch {
+        '─' | '━' | '┄' | '┈' | '╌' | '═' | '⎯' | '—' | '–' => "-",
+        '│' | '┃' | '┆' | '┊' | '╎' | '║' | '▏' | '▕' => "|",
+        '╭' | '╮' | '╯' | '╰' | '┌' | '┐' | '└' | '┘' | '├' | '┤' | '┬' | '┴' | '┼' | '╔' | '╗'
+        | '╚' | '╝' | '╠' | '╣' | '╦' | '╩' | '╬' => "+",
+        '✓' | '✔' | '☑' => "v",
+        '✗' | '✘' | '✕' | '✖' | '×' | '☒' => "x",
+        '●' | '•' | '◉' | '◆' | '■' | '★' | '⚡' => "*",
+        '○' | '◦' | '◇' | '□' | '☐' | '☆' => "o",
+        '·' | '…' | '⋯' => ".",
+        '▸' | '▶' | '►' | '→' | '⟶' | '»' | '›' | '❯' => ">",
+        '◂' | '◀' | '◄' | '←' | '«' | '‹' => "<",
+        '▴' | '▲' | '↑' => "^",
+        '▾' | '▼' | '↓' => "v",
+        '⟳' | '↻' | '↺' => "@",
+        '⚠' => "!",
+        '█' | '▉' | '▊' | '▋' | '▌' | '▍' | '▓' | '▒' => "#",
+        '░' => ":",
+        '▁' | '▂' | '▃' | '▄' => "_",
+        '▅' | '▆' | '▇' | '▀' => "=",
+        '◜' | '◝' | '◞' | '◟' | '◠' | '◡' => "o",
+        '\u{2800}'..='\u{28ff}' => "*",
+        '\u{2500}'..='\u{257f}' => "+",
+        _ => return None,
+    };
+    Some(fallback)
+}
+
+/// Replaces UI glyphs in `area` with ASCII; wide glyphs keep their second (blank) cell.
+pub(crate) fn apply_ascii_glyphs(buffer: &mut Buffer, area: Rect) {
+    let area = area.intersection(buffer.area);
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = &mut buffer[(x, y)];
+            if let Some(fallback) = ascii_fallback(cell.symbol()) {
+                cell.set_symbol(fallback);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_ascii_glyphs, ascii_fallback, env_lacks_unicode, GlyphMode};
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
+    use ratatui::style::Style;
+
+    #[test]
+    fn fallback_covers_spinner_box_and_check_glyphs_only() {
+        let area = Rect::new(0, 0, 8, 1);
+        let mut buffer = Buffer::empty(area);
+        buffer.set_string(0, 0, "╭─⠋✓✗é▸", Style::default());
+        apply_ascii_glyphs(&mut buffer, area);
+        let row = (0..7)
+            .map(|x| buffer[(x, 0)].symbol().to_string())
+            .collect::<String>();
+        assert_eq!(row, "+-*vxé>");
+        assert_eq!(ascii_fallback("a"), None);
+        assert_eq!(ascii_fallback("日"), None);
+
+        let env = |pairs: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                pairs
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        assert!(env_lacks_unicode(env(&[("TERM", "linux")])));
+        assert!(env_lacks_unicode(env(&[("LANG", "en_US.ISO-8859-1")])));
+        assert!(!env_lacks_unicode(env(&[
+            ("TERM", "xterm-256color"),
+            ("LANG", "en_US.UTF-8")
+        ])));
+        assert!(!env_lacks_unicode(env(&[("TERM", "xterm-256color")])));
+        assert_eq!(GlyphMode::parse("ASCII"), Some(GlyphMode::Ascii));
+    }
+}
@@ -0,0 +1,492 @@
+use crate::app::log_wrap::LogImageRun;
+use crate::app::state::{ImagePreview, LogKind, LogLine, LogSpan, LogTone};
+use crate::app::util::settings::TuiSettings;
+use base64::Engine;
+use image::imageops::FilterType;
+use image::{ImageFormat, RgbaImage};
+use ratatui::buffer::{Buffer, CellDiffOption};
+use ratatui::layout::Rect;
+use std::io::Cursor;
+use std::num::NonZeroU16;
+use std::sync::OnceLock;
+
+const PREVIEW_MAX_ROWS: u64 = 8;
+const PREVIEW_MAX_COLUMNS: u64 = 40;
+/// Cell size assumed when the terminal does not report its pixel size.
+const DEFAULT_CELL_PIXELS: (u32, u32) = (10, 20);
+/// Kitty accepts at most 4096 bytes of base64 payload per escape.
+const KITTY_CHUNK_BYTES: usize = 4096;
+/// Levels per channel of the 6x6x6 color cube used for sixel output.
+const SIXEL_LEVELS: usize = 6;
+
+static DETECTED_PROTOCOL: OnceLock<Option<GraphicsProtocol>> = OnceLock::new();
+
+/// `image_preview` setting: which protocol to use for inline image thumbnails.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum ImagePreviewMode {
+    /// Detected from the terminal environment.
+    #[default]
+    Auto,
+    Kitty,
+    Iterm,
+    Sixel,
+    Off,
+}
+
+impl ImagePreviewMode {
+    pub(crate) const VALUES: &'static str = "auto|kitty|iterm|sixel|off";
+
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "auto" => Some(Self::Auto),
+            "kitty" => Some(Self::Kitty),
+            "iterm" => Some(Self::Iterm),
+            "sixel" => Some(Self::Sixel),
+            "off" => Some(Self::Off),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Kitty => "kitty",
+            Self::Iterm => "iterm",
+            Self::Sixel => "sixel",
+            Self::Off => "off",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum GraphicsProtocol {
+    Kitty,
+    Iterm2,
+    Sixel,
+}
+
+/// Protocol guessed from the environment. Multiplexers drop graphics escapes unless
+/// passthrough is configured, so tmux and screen get none; force a mode to override.
+fn detect_graphics_protocol(var: impl Fn(&str) -> Option<String>) -> Option<GraphicsProtocol> {
+    let term = var("TERM").unwrap_or_default().to_ascii_lowercase();
+    if var("TMUX").is_some() || term.starts_with("screen") || term.starts_with("tmux") {
+        return None;
+    }
+    let program = var("TERM_PROGRAM").unwrap_or_default().to_ascii_lowercase();
+    if var("KITTY_WINDOW_ID").is_some() || term.contains("kitty") || term.contains("ghostty") {
+        return Some(GraphicsProtocol::Kitty);
+    }
+    if program == "ghostty" {
+        return Some(GraphicsProtocol::Kitty);
+    }
+    if program == "iterm.app" || program == "wezterm" || var("WEZTERM_EXECUTABLE").is_some() {
+        return Some(GraphicsProtocol::Iterm2);
+    }
+    if term.contains("sixel") || term.starts_with("foot") || term.starts_with("mlterm") {
+        return Some(GraphicsProtocol::Sixel);
+    }
+    None
+}
+
+/// Protocol for new previews; environment detection runs once per process.
+fn graphics_protocol(mode: ImagePreviewMode) -> Option<GraphicsProtocol> {
+    match mode {
+        ImagePreviewMode::Auto => *DETECTED_PROTOCOL.get_or_init(|| {
+            detect_graphics_protocol(|name| std::env::var(name).ok().filter(|v| !v.is_empty()))
+        }),
+        ImagePreviewMode::Kitty => Some(GraphicsProtocol::Kitty),
+        ImagePreviewMode::Iterm => Some(GraphicsProtocol::Iterm2),
+        ImagePreviewMode::Sixel => Some(GraphicsProtocol::Sixel),
+        ImagePreviewMode::Off => None,
+    }
+}
+
+/// Protocol for previews under `settings`. Previews are drawn only into the scrollback, so
+/// the alternate screen gets none; `--low-bandwidth` gets none whatever `image_preview` says,
+/// since one thumbnail can outweigh a screenful of text.
+pub(crate) fn preview_protocol(
+    settings: &TuiSettings,
+    low_bandwidth: bool,
+) -> Option<GraphicsProtocol> {
+    if settings.alt_screen || low_bandwidth {
+        return None;
+    }
+    graphics_protocol(settings.image_preview)
+}
+
+fn cell_pixel_size() -> (u32, u32) {
+    crossterm::terminal::window_size()
+        .ok()
+        .filter(|size| size.columns > 0 && size.rows > 0 && size.width > 0 && size.height > 0)
+        .map(|size| {
+            (
+                u32::from(size.width / size.columns).max(1),
+                u32::from(size.height / size.rows).max(1),
+            )
+        })
+        .unwrap_or(DEFAULT_CELL_PIXELS)
+}
+
+/// Cells covered by a `width`x`height` image: its natural size, capped at
+/// `PREVIEW_MAX_COLUMNS` x `PREVIEW_MAX_ROWS` with the aspect ratio kept.
+fn preview_cells(width: u32, height: u32, cell: (u32, u32)) -> (u32, u32) {
+    let (width, height) = (u64::from(width.max(1)), u64::from(height.max(1)));
+    let (cell_width, cell_height) = (u64::from(cell.0), u64::from(cell.1));
+    let mut rows = height.div_ceil(cell_height).clamp(1, PREVIEW_MAX_ROWS);
+    let mut columns = (rows * cell_height * width).div_ceil(height * cell_width);
+    if columns > PREVIEW_MAX_COLUMNS {
+        columns = PREVIEW_MAX_COLUMNS;
+        rows = (columns * cell_width * height)
+            .div_ceil(width * cell_height)
+            .clamp(1, PREVIEW_MAX_ROWS);
+    }
+    (columns.max(1) as u32, rows as u32)
+}
+
+fn kitty_escape(png: &[u8], columns: u32, rows: u32) -> String {
+    let payload = base64::engine::general_purpose::STANDARD.encode(png);
+    let chunks = payload
+        .as_bytes()
+        .chunks(KITTY_CHUNK_BYTES)
+        .collect::<Vec<_>>();
+    let mut out = String::new();
+    for (index, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(index + 1 < chunks.len());
+        let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+        if index == 0 {
+            // C=1 keeps the cursor in place; q=2 suppresses the terminal's replies.
+            out.push_str(&format!(
+                "\x1b_Ga=T,f=100,q=2,C=1,c={columns},r={rows},m={more};{chunk}\x1b\\"
+            ));
+        } else {
+            out.push_str(&format!("\x1b_Gm={more};{chunk}\x1b\\"));
+        }
+    }
+    out
+}
+
+fn iterm_escape(png: &[u8], columns: u32, rows: u32) -> String {
+    format!(
+        "\x1b]1337;File=inline=1;size={};width={columns};height={rows};preserveAspectRatio=1;doNotMoveCursor=1:{}\x07",
+        png.len(),
+        base64::engine::general_purpose::STANDARD.encode(png)
+    )
+}
+
+fn sixel_level(value: u8) -> usize {
+    (usize::from(value) * (SIXEL_LEVELS - 1) + 127) / 255
+}
+
+fn push_sixel_run(out: &mut String, bits: u8, count: usize) {
+    let ch = char::from(0x3f + bits);
+    if count > 3 {
+        out.push_str(&format!("!{count}{ch}"));
+    } else {
+        out.extend(std::iter::repeat_n(ch, count));
+    }
+}
+
+/// Sixel image quantized to the 6x6x6 color cube; transparent pixels are left unpainted.
+fn sixel_escape(image: &RgbaImage) -> String {
+    let (width, height) = (image.width() as usize, image.height() as usize);
+    let indices = image
+        .pixels()
+        .map(|pixel| {
+            (pixel[3] >= 128).then(|| {
+                sixel_level(pixel[0]) * SIXEL_LEVELS * SIXEL_LEVELS
+                    + sixel_level(pixel[1]) * SIXEL_LEVELS
+                    + sixel_level(pixel[2])
+            })
+        })
+        .collect::<Vec<_>>();
+    let palette_size = SIXEL_LEVELS.pow(3);
+    let mut out = format!("\x1bP0;1;0q\"1;1;{width};{height}");
+    let mut used = vec![false; palette_size];
+    for index in indices.iter().flatten() {
+        used[*index] = true;
+    }
+    let percent = |level: usize| level * 100 / (SIXEL_LEVELS - 1);
+    for (index, _) in used.iter().enumerate().filter(|(_, used)| **used) {
+        let (r, g, b) = (
+            index / (SIXEL_LEVELS * SIXEL_LEVELS),
+            index / SIXEL_LEVELS % SIXEL_LEVELS,
+            index % SIXEL_LEVELS,
+        );
+        out.push_str(&format!(
+            "#{index};2;{};{};{}",
+            percent(r),
+            percent(g),
+            percent(b)
+        ));
+    }
+    for top in (0..height).step_by(6) {
+        let band = top..(top + 6).min(height);
+        let mut in_band = vec![false; palette_size];
+        for row in band.clone() {
+            for index in indices[row * width..(row + 1) * width].iter().flatten() {
+                in_band[*index] = true;
+            }
+        }
+        for (color, _) in in_band.iter().enumerate().filter(|(_, used)| **used) {
+            out.push_str(&format!("#{color}"));
+            let mut run = (0u8, 0usize);
+            for x in 0..width {
+                let bits = band.clone().fold(0u8, |bits, row| {
+                    if indices[row * width + x] == Some(color) {
+                        bits | (1 << (row - top))
+                    } else {
+                        bits
+                    }
+                });
+                if bits != run.0 && run.1 > 0 {
+                    push_sixel_run(&mut out, run.0, run.1);
+                    run.1 = 0;
+                }
+                run = (bits, run.1 + 1);
+            }
+            push_sixel_run(&mut out, run.0, run.1);
+            out.push('$');
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+/// Thumbnail escape for `protocol`, wrapped in cursor save/restore so the cursor is back on
+/// the cell ratatui drew next; `None` when the data URL does not decode.
+fn image_preview(
+    protocol: GraphicsProtocol,
+    data_url: &str,
+    cell: (u32, u32),
+) -> Option<ImagePreview> {
+    let (_, payload) = data_url.split_once(";base64,")?;
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(payload)
+        .ok()?;
+    let image = image::load_from_memory(&bytes).ok()?;
+    let (columns, rows) = preview_cells(image.width(), image.height(), cell);
+    let (max_width, max_height) = (columns * cell.0, rows * cell.1);
+    let thumbnail = if image.width() > max_width || image.height() > max_height {
+        image.resize(max_width, max_height, FilterType::Triangle)
+    } else {
+        image
+    };
+    let escape = match protocol {
+        GraphicsProtocol::Sixel => sixel_escape(&thumbnail.to_rgba8()),
+        GraphicsProtocol::Kitty | GraphicsProtocol::Iterm2 => {
+            let mut png = Vec::new();
+            thumbnail
+                .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+                .ok()?;
+            if protocol == GraphicsProtocol::Kitty {
+                kitty_escape(&png, columns, rows)
+            } else {
+                iterm_escape(&png, columns, rows)
+            }
+        }
+    };
+    Some(ImagePreview {
+        escape: format!("\x1b7{escape}\x1b8"),
+        rows: rows as usize,
+    })
+}
+
+/// Blank log rows reserving room for an inline thumbnail of `data_url`; the first carries
+/// the preview. Empty without a protocol or when the image does not decode, which leaves
+/// the caller's text line as the only placeholder.
+pub(crate) fn image_preview_lines(
+    protocol: Option<GraphicsProtocol>,
+    data_url: &str,
+    kind: LogKind,
+) -> Vec<LogLine> {
+    match protocol {
+        Some(protocol) => preview_lines(protocol, data_url, kind, cell_pixel_size()),
+        None => Vec::new(),
+    }
+}
+
+fn preview_lines(
+    protocol: GraphicsProtocol,
+    data_url: &str,
+    kind: LogKind,
+    cell: (u32, u32),
+) -> Vec<LogLine> {
+    let Some(preview) = image_preview(protocol, data_url, cell) else {
+        return Vec::new();
+    };
+    let rows = preview.rows;
+    let mut lines = vec![LogLine::new_with_spans(vec![LogSpan::new(
+        kind,
+        LogTone::Detail,
+        " ",
+    )
+    .with_image(preview)])];
+    lines.extend((1..rows).map(|_| LogLine::new_with_tone(kind, LogTone::Detail, "")));
+    lines
+}
+
+/// Prefixes the preview escapes to their cells in rows headed for the scrollback. A preview
+/// whose rows do not all fit in `area` is skipped, leaving its blank rows.
+pub(crate) fn apply_log_images(buffer: &mut Buffer, area: Rect, runs: &[LogImageRun]) {
+    for run in runs {
+        if run.row + run.preview.rows > usize::from(area.height)
+            || run.column >= usize::from(area.width)
+        {
+            continue;
+        }
+        let position = (area.x + run.column as u16, area.y + run.row as u16);
+        let Some(cell) = buffer.cell_mut(position) else {
+            continue;
+        };
+        let symbol = cell.symbol().to_string();
+        cell.set_symbol(&format!("{}{symbol}", run.preview.escape));
+        cell.set_diff_option(CellDiffOption::ForcedWidth(NonZeroU16::MIN));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        apply_log_images, detect_graphics_protocol, image_preview_lines, preview_cells,
+        preview_lines, preview_protocol, sixel_escape, GraphicsProtocol, ImagePreviewMode,
+    };
+    use crate::app::log_wrap::LogImageRun;
+    use crate::app::state::LogKind;
+    use crate::app::util::settings::TuiSettings;
+    use base64::Engine;
+    use image::{DynamicImage, ImageFormat, RgbaImage};
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
+    use std::io::Cursor;
+    use std::sync::Arc;
+
+    fn detect(vars: &[(&str, &str)]) -> Option<GraphicsProtocol> {
+        detect_graphics_protocol(|name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        })
+    }
+
+    fn png_data_url(width: u32, height: u32) -> String {
+        let image = DynamicImage::ImageRgba8(RgbaImage::from_pixel(
+            width,
+            height,
+            image::Rgba([200, 40, 40, 255]),
+        ));
+        let mut bytes = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .expect("png");
+        format!(
+            "data:image/png;base64,{}",
+            base64::engine::general_purpose::STANDARD.encode(bytes)
+        )
+    }
+
+    #[test]
+    fn protocol_is_detected_from_the_terminal_environment() {
+        assert_eq!(
+            detect(&[("TERM", "xterm-kitty")]),
+            Some(GraphicsProtocol::Kitty)
+        );
+        assert_eq!(
+            detect(&[("TERM_PROGRAM", "ghostty")]),
+            Some(GraphicsProtocol::Kitty)
+        );
+        assert_eq!(
+            detect(&[("TERM_PROGRAM", "iTerm.app")]),
+            Some(GraphicsProtocol::Iterm2)
+        );
+        assert_eq!(detect(&[("TERM", "foot")]), Some(GraphicsProtocol::Sixel));
+        assert_eq!(detect(&[("TERM", "xterm-256color")]), None);
+        assert_eq!(
+            detect(&[("TERM", "xterm-kitty"), ("TMUX", "/tmp/tmux-1/default")]),
+            None
+        );
+        assert_eq!(
+            ImagePreviewMode::parse("ITERM"),
+            Some(ImagePreviewMode::Iterm)
+        );
+        assert_eq!(ImagePreviewMode::parse("blink"), None);
+    }
+
+    #[test]
+    fn low_bandwidth_and_the_alternate_screen_get_no_previews() {
+        let mut settings = TuiSettings {
+            image_preview: ImagePreviewMode::Kitty,
+            ..TuiSettings::default()
+        };
+        assert_eq!(
+            preview_protocol(&settings, false),
+            Some(GraphicsProtocol::Kitty)
+        );
+        assert_eq!(preview_protocol(&settings, true), None);
+        settings.alt_screen = true;
+        assert_eq!(preview_protocol(&settings, false), None);
+    }
+
+    #[test]
+    fn previews_keep_the_aspect_ratio_within_the_row_and_column_caps() {
+        assert_eq!(preview_cells(1600, 1200, (10, 20)), (22, 8));
+        assert_eq!(preview_cells(4000, 500, (10, 20)), (40, 3));
+        assert_eq!(preview_cells(30, 15, (10, 20)), (4, 1));
+    }
+
+    #[test]
+    fn preview_rows_carry_the_escape_on_their_first_row() {
+        let data_url = png_data_url(100, 100);
+        let cell = (10, 20);
+        let lines = preview_lines(GraphicsProtocol::Kitty, &data_url, LogKind::Status, cell);
+        assert_eq!(lines.len(), 5);
+        let preview = lines[0].spans[0].image.clone().expect("preview");
+        assert!(preview
+            .escape
+            .starts_with("\x1b7\x1b_Ga=T,f=100,q=2,C=1,c=10,r=5,"));
+        assert!(preview.escape.ends_with("\x1b\\\x1b8"));
+        assert!(lines[1..].iter().all(|line| line.plain_text().is_empty()));
+
+        let lines = preview_lines(GraphicsProtocol::Iterm2, &data_url, LogKind::Status, cell);
+        let preview = lines[0].spans[0].image.clone().expect("preview");
+        assert!(preview
+            .escape
+            .contains("width=10;height=5;preserveAspectRatio=1"));
+        assert!(image_preview_lines(None, &data_url, LogKind::Status).is_empty());
+        let broken = "data:image/png;base64,AAAA";
+        assert!(preview_lines(GraphicsProtocol::Kitty, broken, LogKind::Status, cell).is_empty());
+
+        let area = Rect::new(0, 0, 20, 6);
+        let mut buffer = Buffer::empty(area);
+        let runs = [LogImageRun {
+            row: 1,
+            column: 0,
+            preview: Arc::clone(&preview),
+        }];
+        apply_log_images(&mut buffer, area, &runs);
+        assert!(buffer[(0, 1)].symbol().starts_with("\x1b7\x1b]1337;File="));
+        assert!(buffer[(0, 1)].symbol().ends_with("\x1b8 "));
+
+        // Five preview rows do not fit below row 2 of a six-row chunk.
+        let mut buffer = Buffer::empty(area);
+        let runs = [LogImageRun {
+            row: 2,
+            column: 0,
+            preview,
+        }];
+        apply_log_images(&mut buffer, area, &runs);
+        assert_eq!(buffer[(0, 2)].symbol(), " ");
+    }
+
+    #[test]
+    fn sixel_output_paints_each_band_per_color() {
+        let mut image = RgbaImage::from_pixel(5, 7, image::Rgba([255, 0, 0, 255]));
+        image.put_pixel(0, 6, image::Rgba([0, 0, 255, 255]));
+        image.put_pixel(4, 6, image::Rgba([0, 0, 0, 0]));
+        let sixel = sixel_escape(&image);
+        assert!(sixel.starts_with("\x1bP0;1;0q\"1;1;5;7#5;2;0;0;100#180;2;100;0;0"));
+        assert!(sixel.contains("#180!5~$-"));
+        assert!(sixel.contains("#5@!4?$#180?@@@?$-"));
+        assert!(sixel.ends_with("\x1b\\"));
+    }
+}
@@ -1,10 +1,17 @@
-use crate::app::log_wrap::wrapped_log_range_to_lines;
+use crate::app::log_wrap::{
+    apply_log_hyperlinks, wrapped_log_range_images, wrapped_log_range_links,
+    wrapped_log_range_to_lines, LogImageRun, LogLinkRun,
+};
+use crate::app::render::glyphs::{apply_ascii_glyphs, ascii_glyphs};
+use crate::app::render::graphics::apply_log_images;
+use crate::app::render::palette::{ansi16_only, apply_ansi16_colors};
 use crate::app::{AppState, CursorPhase, SyncPhase};
 use ratatui::backend::Backend;
 use ratatui::layout::Rect;
 use ratatui::text::Line;
 use ratatui::widgets::Widget;
 use ratatui::Terminal;
+use std::sync::Arc;
 
 #[derive(Default)]
 pub struct TerminalEffects {
@@ -14,6 +21,8 @@ pub struct TerminalEffects {
 fn insert_history_chunk<B: Backend>(
     terminal: &mut Terminal<B>,
     lines: &[Line<'static>],
+    links: &[LogLinkRun],
+    images: &[LogImageRun],
     viewport_width: u16,
 ) -> Result<usize, B::Error> {
     if lines.is_empty() {
@@ -27,6 +36,15 @@ fn insert_history_chunk<B: Backend>(
             line.clone()
                 .render(Rect::new(0, row as u16, width, 1), buffer);
         }
+        let area = Rect::new(0, 0, width, height);
+        if ascii_glyphs() {
+            apply_ascii_glyphs(buffer, area);
+        }
+        if ansi16_only() {
+            apply_ansi16_colors(buffer, area);
+        }
+        apply_log_hyperlinks(buffer, area, links);
+        apply_log_images(buffer, area, images);
     })?;
     Ok(lines.len())
 }
@@ -87,6 +105,8 @@ pub fn apply_terminal_effects<B: Backend>(
         viewport_width.max(1) as usize
     };
     let lines = wrapped_log_range_to_lines(app, log_width, start, overflow);
+    let links = wrapped_log_range_links(app, log_width, start, overflow);
+    let images = wrapped_log_range_images(app, log_width, start, overflow);
     if lines.is_empty() {
         app.render_state.inserted_until = overflow;
         app.render_state.sync_phase = SyncPhase::Idle;
@@ -100,8 +120,27 @@ pub fn apply_terminal_effects<B: Backend>(
         let max_lines = u16::MAX / viewport_width.max(1);
         usize::from(max_lines.max(1))
     };
-    for chunk in lines.chunks(max_lines_per_insert) {
-        let inserted = insert_history_chunk(terminal, chunk, viewport_width)?;
+    for (index, chunk) in lines.chunks(max_lines_per_insert).enumerate() {
+        let first_row = index * max_lines_per_insert;
+        let chunk_links = links
+            .iter()
+            .filter(|run| (first_row..first_row + chunk.len()).contains(&run.row))
+            .map(|run| LogLinkRun {
+                row: run.row - first_row,
+                ..run.clone()
+            })
+            .collect::<Vec<_>>();
+        let chunk_images = images
+            .iter()
+            .filter(|run| (first_row..first_row + chunk.len()).contains(&run.row))
+            .map(|run| LogImageRun {
+                row: run.row - first_row,
+                column: run.column,
+                preview: Arc::clone(&run.preview),
+            })
+            .collect::<Vec<_>>();
+        let inserted =
+            insert_history_chunk(terminal, chunk, &chunk_links, &chunk_images, viewport_width)?;
         app.render_state.inserted_until = app
             .render_state
             .inserted_until
@@ -136,7 +175,7 @@ mod tests {
         )
         .expect("terminal");
 
-        let inserted = insert_history_chunk(&mut terminal, &[], 12).expect("insert");
+        let inserted = insert_history_chunk(&mut terminal, &[], &[], &[], 12).expect("insert");
 
         assert_eq!(inserted, 0);
         terminal.backend().assert_buffer_lines([
@@ -182,6 +221,8 @@ mod tests {
         let inserted = insert_history_chunk(
             &mut terminal,
             &[Line::raw("INSERTED-001"), Line::raw("INSERTED-002")],
+            &[],
+            &[],
             12,
         )
         .expect("insert");
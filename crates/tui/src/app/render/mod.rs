@@ -1 +1,4 @@
+pub(crate) mod glyphs;
+pub(crate) mod graphics;
 pub(crate) mod inline;
+pub(crate) mod palette;
@@ -0,0 +1,154 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ANSI16_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// The 16 ANSI colors in SGR order, for `Indexed(0..16)`.
+const ANSI16: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Gray,
+    Color::DarkGray,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::White,
+];
+/// Channel spread below which a color reads as a gray.
+const GRAY_SPREAD: u8 = 48;
+/// Brightest channel from which a hue takes its `Light` variant.
+const BRIGHT_LEVEL: u8 = 200;
+
+/// `--low-bandwidth`: frames and scrollback are drawn with the 16 ANSI colors only.
+pub(crate) fn set_ansi16_only(enabled: bool) {
+    ANSI16_ONLY.store(enabled, Ordering::Relaxed);
+}
+
+pub(crate) fn ansi16_only() -> bool {
+    ANSI16_ONLY.load(Ordering::Relaxed)
+}
+
+/// 256-color cube and grayscale ramp entries; `index` is 16 or more.
+fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        16..=231 => {
+            let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+            let cube = index - 16;
+            (level(cube / 36), level(cube / 6 % 6), level(cube % 6))
+        }
+        _ => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+    }
+}
+
+/// Keeps the hue rather than the nearest point: theme colors are muted, and plain RGB distance
+/// would turn most of them gray. Channels above the midpoint of the brightest and darkest one
+/// pick the hue.
+fn nearest_ansi16((r, g, b): (u8, u8, u8)) -> Color {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    if max - min < GRAY_SPREAD {
+        return match max {
+            0..=47 => Color::Black,
+            48..=143 => Color::DarkGray,
+            144..=223 => Color::Gray,
+            _ => Color::White,
+        };
+    }
+    let mid = (u16::from(max) + u16::from(min)) / 2;
+    let on = |value: u8| u16::from(value) > mid;
+    let bright = max >= BRIGHT_LEVEL;
+    match (on(r), on(g), on(b)) {
+        (true, false, false) if bright => Color::LightRed,
+        (true, false, false) => Color::Red,
+        (false, true, false) if bright => Color::LightGreen,
+        (false, true, false) => Color::Green,
+        (true, true, false) if bright => Color::LightYellow,
+        (true, true, false) => Color::Yellow,
+        (false, false, true) if bright => Color::LightBlue,
+        (false, false, true) => Color::Blue,
+        (true, false, true) if bright => Color::LightMagenta,
+        (true, false, true) => Color::Magenta,
+        (false, true, true) if bright => Color::LightCyan,
+        _ => Color::Cyan,
+    }
+}
+
+/// Nearest of the 16 ANSI colors; named colors and `Reset` pass through.
+pub(crate) fn to_ansi16(color: Color) -> Color {
+    match color {
+        Color::Rgb(r, g, b) => nearest_ansi16((r, g, b)),
+        Color::Indexed(index) if index < 16 => ANSI16[index as usize],
+        Color::Indexed(index) => nearest_ansi16(indexed_rgb(index)),
+        color => color,
+    }
+}
+
+/// Tinted backgrounds (code blocks, diff lines) that land on black keep the terminal's own
+/// background instead of painting a black box.
+fn background_to_ansi16(color: Color) -> Color {
+    match to_ansi16(color) {
+        Color::Black if color != Color::Black => Color::Reset,
+        mapped => mapped,
+    }
+}
+
+/// Rewrites 256-color and truecolor cells in `area` to the 16 ANSI colors.
+pub(crate) fn apply_ansi16_colors(buffer: &mut Buffer, area: Rect) {
+    let area = area.intersection(buffer.area);
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = &mut buffer[(x, y)];
+            cell.fg = to_ansi16(cell.fg);
+            cell.bg = background_to_ansi16(cell.bg);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_ansi16_colors, to_ansi16};
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
+    use ratatui::style::{Color, Style};
+
+    #[test]
+    fn truecolor_and_256_colors_map_to_the_nearest_ansi_color() {
+        assert_eq!(to_ansi16(Color::Rgb(191, 97, 106)), Color::Red);
+        assert_eq!(to_ansi16(Color::Rgb(250, 250, 245)), Color::White);
+        assert_eq!(to_ansi16(Color::Rgb(124, 193, 255)), Color::LightCyan);
+        assert_eq!(to_ansi16(Color::Rgb(120, 130, 125)), Color::DarkGray);
+        assert_eq!(to_ansi16(Color::Indexed(9)), Color::LightRed);
+        assert_eq!(to_ansi16(Color::Indexed(46)), Color::LightGreen);
+        assert_eq!(to_ansi16(Color::Indexed(244)), Color::DarkGray);
+        assert_eq!(to_ansi16(Color::Cyan), Color::Cyan);
+
+        let area = Rect::new(0, 0, 2, 1);
+        let mut buffer = Buffer::empty(area);
+        buffer.set_string(
+            0,
+            0,
+            "ab",
+            Style::default()
+                .fg(Color::Rgb(163, 190, 140))
+                .bg(Color::Rgb(24, 30, 36)),
+        );
+        buffer[(1, 0)].bg = Color::Black;
+        apply_ansi16_colors(&mut buffer, area);
+        assert_eq!(buffer[(0, 0)].fg, Color::Green);
+        assert_eq!(buffer[(0, 0)].bg, Color::Reset);
+        assert_eq!(buffer[(1, 0)].bg, Color::Black);
+    }
+}
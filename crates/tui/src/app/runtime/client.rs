@@ -1,9 +1,18 @@
+use crate::app::util::local_models::LOCAL_MODEL_PROVIDER;
+use codelia_protocol::request::{
+    client_tool_error_reply, client_tool_json_reply, client_tool_text_reply, confirm_reply,
+    pick_reply, prompt_reply,
+};
+pub use codelia_protocol::ConfirmHunkDecision;
+use codelia_protocol::{ModelSet, Request, RunStart, UiContextUpdate};
 use serde_json::{json, Value};
+use std::collections::HashMap;
 use std::env;
 use std::io::{BufRead, BufReader, BufWriter, Write};
-use std::process::{Child, Command, Stdio};
+use std::process::{Child, ChildStdin, Command, Stdio};
 use std::sync::mpsc::{self, Receiver};
 use std::thread;
+use std::time::{Duration, Instant};
 
 fn split_args(value: &str) -> Vec<String> {
     match shell_words::split(value) {
@@ -24,13 +33,108 @@ fn split_args(value: &str) -> Vec<String> {
     }
 }
 
-type RuntimeSpawn = (Child, BufWriter<std::process::ChildStdin>, Receiver<String>);
+type RuntimeSpawn = (Child, RuntimeStdin, Receiver<String>);
 type RuntimeSpawnResult = Result<RuntimeSpawn, Box<dyn std::error::Error>>;
 
-fn json_line(value: Value) -> String {
+// Requests whose responses never arrive (runtime restart) must not grow this forever.
+const MAX_IN_FLIGHT_REQUESTS: usize = 512;
+
+/// An outgoing request as it was written, kept until its response arrives.
+#[derive(Debug, Clone)]
+pub(crate) struct SentRequest {
+    pub method: String,
+    pub params: Value,
+    sent_at: Instant,
+    /// Write order; timestamps can tie.
+    seq: u64,
+}
+
+impl SentRequest {
+    pub(crate) fn latency(&self) -> Duration {
+        self.sent_at.elapsed()
+    }
+}
+
+/// One runtime process's stdin plus the requests written to it that are still waiting for a
+/// response. Each tab owns its own, and a reconnect starts a fresh one, so ids from another tab
+/// or from a previous runtime process never match.
+pub(crate) struct RuntimeStdin {
+    writer: BufWriter<ChildStdin>,
+    in_flight: HashMap<String, SentRequest>,
+    sent_count: u64,
+}
+
+impl RuntimeStdin {
+    pub(crate) fn new(stdin: ChildStdin) -> Self {
+        Self {
+            writer: BufWriter::new(stdin),
+            in_flight: HashMap::new(),
+            sent_count: 0,
+        }
+    }
+
+    /// Every outgoing request passes through here, so send times and parameters are captured
+    /// in one place.
+    fn record_request_sent(&mut self, value: &Value) {
+        let (Some(id), Some(method)) = (
+            value.get("id").and_then(Value::as_str),
+            value.get("method").and_then(Value::as_str),
+        ) else {
+            return;
+        };
+        if self.in_flight.len() >= MAX_IN_FLIGHT_REQUESTS {
+            let oldest = self
+                .in_flight
+                .iter()
+                .min_by_key(|(_, sent)| sent.seq)
+                .map(|(id, _)| id.clone());
+            if let Some(oldest) = oldest {
+                self.in_flight.remove(&oldest);
+            }
+        }
+        self.sent_count += 1;
+        self.in_flight.insert(
+            id.to_string(),
+            SentRequest {
+                method: method.to_string(),
+                params: value.get("params").cloned().unwrap_or(Value::Null),
+                sent_at: Instant::now(),
+                seq: self.sent_count,
+            },
+        );
+    }
+
+    /// Returns the request sent under `id`, once, when its response arrives.
+    pub(crate) fn take_sent_request(&mut self, id: &str) -> Option<SentRequest> {
+        self.in_flight.remove(id)
+    }
+
+    /// Requests still waiting for a response as `(id, method, age)`, oldest first.
+    pub(crate) fn in_flight_summaries(&self) -> Vec<(String, String, Duration)> {
+        let mut summaries = self
+            .in_flight
+            .iter()
+            .map(|(id, sent)| (id.clone(), sent.method.clone(), sent.latency()))
+            .collect::<Vec<_>>();
+        summaries.sort_by_key(|(_, _, age)| std::cmp::Reverse(*age));
+        summaries
+    }
+}
+
+fn json_line(value: &Value) -> String {
     value.to_string() + "\n"
 }
 
+fn write_message(writer: &mut RuntimeStdin, message: Value) -> std::io::Result<()> {
+    writer.record_request_sent(&message);
+    writer.writer.write_all(json_line(&message).as_bytes())?;
+    writer.writer.flush()
+}
+
+fn send(writer: &mut RuntimeStdin, id: &str, request: Request) -> std::io::Result<()> {
+    write_message(writer, request.to_message(id))
+}
+
 fn tui_client_tools() -> Value {
     json!([
         {
@@ -283,7 +387,11 @@ fn spawn_reader<T: std::io::Read + Send + 'static>(
     });
 }
 
-pub fn spawn_runtime(enable_diagnostics: bool, approval_mode: Option<&str>) -> RuntimeSpawnResult {
+/// Program and arguments used to launch the runtime (`CODELIA_RUNTIME_CMD` / `CODELIA_RUNTIME_ARGS`).
+///
+/// Launch flags travel in argv rather than the environment so they survive
+/// wrappers such as `ssh host codelia-runtime`.
+pub fn runtime_command(approval_mode: Option<&str>, read_only: bool) -> (String, Vec<String>) {
     let runtime_cmd = env::var("CODELIA_RUNTIME_CMD").unwrap_or_else(|_| "bun".to_string());
     let mut runtime_args = env::var("CODELIA_RUNTIME_ARGS")
         .map(|value| split_args(&value))
@@ -293,7 +401,18 @@ pub fn spawn_runtime(enable_diagnostics: bool, approval_mode: Option<&str>) -> R
         runtime_args.push("--approval-mode".to_string());
         runtime_args.push(mode.to_string());
     }
+    if read_only {
+        runtime_args.push("--read-only".to_string());
+    }
+    (runtime_cmd, runtime_args)
+}
 
+pub fn spawn_runtime(
+    enable_diagnostics: bool,
+    approval_mode: Option<&str>,
+    read_only: bool,
+) -> RuntimeSpawnResult {
+    let (runtime_cmd, runtime_args) = runtime_command(approval_mode, read_only);
     let mut command = Command::new(runtime_cmd);
     command
         .args(runtime_args)
@@ -305,7 +424,7 @@ pub fn spawn_runtime(enable_diagnostics: bool, approval_mode: Option<&str>) -> R
     }
     let mut child = command.spawn()?;
 
-    let child_stdin = BufWriter::new(child.stdin.take().expect("stdin missing"));
+    let child_stdin = RuntimeStdin::new(child.stdin.take().expect("stdin missing"));
     let child_stdout = child.stdout.take().expect("stdout missing");
     let child_stderr = child.stderr.take().expect("stderr missing");
     let (tx, rx) = mpsc::channel::<String>();
@@ -315,238 +434,118 @@ pub fn spawn_runtime(enable_diagnostics: bool, approval_mode: Option<&str>) -> R
     Ok((child, child_stdin, rx))
 }
 
-pub fn send_initialize(
-    writer: &mut BufWriter<std::process::ChildStdin>,
-    id: &str,
-) -> std::io::Result<()> {
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "method": "initialize",
-        "params": {
-            "protocol_version": "0",
-            "client": { "name": "codelia-tui", "version": "0.1.0" },
-            "ui_capabilities": {
-                "supports_confirm": true,
-                "supports_prompt": true,
-                "supports_pick": true,
-                "supports_permission_preflight_events": true
-            }
-        }
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+pub fn send_initialize(writer: &mut RuntimeStdin, id: &str) -> std::io::Result<()> {
+    send(writer, id, Request::initialize("codelia-tui", "0.1.0"))
 }
 
 pub fn send_confirm_response(
-    writer: &mut BufWriter<std::process::ChildStdin>,
+    writer: &mut RuntimeStdin,
     id: &str,
     ok: bool,
     remember: bool,
     reason: Option<&str>,
+    hunks: &[ConfirmHunkDecision],
 ) -> std::io::Result<()> {
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "result": { "ok": ok, "remember": remember, "reason": reason }
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+    write_message(writer, confirm_reply(id, ok, remember, reason, hunks))
 }
 
 pub fn send_prompt_response(
-    writer: &mut BufWriter<std::process::ChildStdin>,
+    writer: &mut RuntimeStdin,
     id: &str,
     value: Option<&str>,
 ) -> std::io::Result<()> {
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "result": { "value": value }
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+    write_message(writer, prompt_reply(id, value))
 }
 
 pub fn send_pick_response(
-    writer: &mut BufWriter<std::process::ChildStdin>,
+    writer: &mut RuntimeStdin,
     id: &str,
     ids: &[String],
 ) -> std::io::Result<()> {
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "result": { "ids": ids }
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+    write_message(writer, pick_reply(id, ids))
 }
 
 pub fn send_run_start(
-    writer: &mut BufWriter<std::process::ChildStdin>,
+    writer: &mut RuntimeStdin,
     id: &str,
     session_id: Option<&str>,
     input: Value,
     force_compaction: bool,
+    dry_run: bool,
+    disabled_tools: &[String],
 ) -> std::io::Result<()> {
-    let mut params = serde_json::Map::new();
-    params.insert("input".to_string(), input);
-    if let Some(session_id) = session_id {
-        params.insert("session_id".to_string(), json!(session_id));
-    }
-    if force_compaction {
-        params.insert("force_compaction".to_string(), json!(true));
-    }
-    if should_include_tui_client_tools() {
-        params.insert("tools".to_string(), tui_client_tools());
-    }
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "method": "run.start",
-        "params": params
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+    let run = RunStart {
+        input,
+        session_id: session_id.map(str::to_string),
+        force_compaction,
+        dry_run,
+        disabled_tools: disabled_tools.to_vec(),
+        tools: should_include_tui_client_tools().then(tui_client_tools),
+    };
+    send(writer, id, Request::run_start(run))
 }
 
 pub fn send_client_tool_success(
-    writer: &mut BufWriter<std::process::ChildStdin>,
+    writer: &mut RuntimeStdin,
     id: &str,
     result: Value,
 ) -> std::io::Result<()> {
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "result": { "ok": true, "result": { "type": "json", "value": result } }
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+    write_message(writer, client_tool_json_reply(id, result))
 }
 
 pub fn send_client_tool_text_success(
-    writer: &mut BufWriter<std::process::ChildStdin>,
+    writer: &mut RuntimeStdin,
     id: &str,
     text: &str,
 ) -> std::io::Result<()> {
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "result": { "ok": true, "result": { "type": "text", "text": text } }
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+    write_message(writer, client_tool_text_reply(id, text))
 }
 
 pub fn send_client_tool_error(
-    writer: &mut BufWriter<std::process::ChildStdin>,
+    writer: &mut RuntimeStdin,
     id: &str,
     error: &str,
 ) -> std::io::Result<()> {
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "result": { "ok": false, "error": error }
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+    write_message(writer, client_tool_error_reply(id, error))
 }
 
 pub fn send_run_cancel(
-    writer: &mut BufWriter<std::process::ChildStdin>,
+    writer: &mut RuntimeStdin,
     id: &str,
     run_id: &str,
     reason: Option<&str>,
 ) -> std::io::Result<()> {
-    let mut params = serde_json::Map::new();
-    params.insert("run_id".to_string(), json!(run_id));
-    if let Some(reason) = reason {
-        params.insert("reason".to_string(), json!(reason));
-    }
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "method": "run.cancel",
-        "params": params
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+    send(writer, id, Request::run_cancel(run_id, reason))
 }
 
 pub fn send_session_list(
-    writer: &mut BufWriter<std::process::ChildStdin>,
+    writer: &mut RuntimeStdin,
     id: &str,
     limit: Option<usize>,
     show_all: bool,
 ) -> std::io::Result<()> {
-    let mut params = serde_json::Map::new();
-    if let Some(limit) = limit {
-        params.insert("limit".to_string(), json!(limit));
-    }
-    params.insert(
-        "scope".to_string(),
-        json!(if show_all { "all" } else { "current_workspace" }),
-    );
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "method": "session.list",
-        "params": params
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+    send(writer, id, Request::session_list(limit, show_all))
 }
 
 pub fn send_auth_logout(
-    writer: &mut BufWriter<std::process::ChildStdin>,
+    writer: &mut RuntimeStdin,
     id: &str,
     clear_session: bool,
 ) -> std::io::Result<()> {
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "method": "auth.logout",
-        "params": { "clear_session": clear_session }
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+    send(writer, id, Request::auth_logout(clear_session))
 }
 
 pub fn send_model_list(
-    writer: &mut BufWriter<std::process::ChildStdin>,
+    writer: &mut RuntimeStdin,
     id: &str,
     provider: Option<&str>,
     include_details: bool,
 ) -> std::io::Result<()> {
-    let mut params = serde_json::Map::new();
-    if let Some(provider) = provider {
-        params.insert("provider".to_string(), json!(provider));
-    }
-    params.insert("include_details".to_string(), json!(include_details));
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "method": "model.list",
-        "params": params
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+    send(writer, id, Request::model_list(provider, include_details))
 }
 
 pub fn send_model_set(
-    writer: &mut BufWriter<std::process::ChildStdin>,
+    writer: &mut RuntimeStdin,
     id: &str,
     provider: Option<&str>,
     model: &str,
@@ -555,283 +554,282 @@ pub fn send_model_set(
     scope: Option<&str>,
     reset: bool,
 ) -> std::io::Result<()> {
-    let mut params = serde_json::Map::new();
-    if !reset {
-        params.insert("name".to_string(), json!(model));
-    }
-    if let Some(provider) = provider {
-        params.insert("provider".to_string(), json!(provider));
-    }
-    if let Some(reasoning) = reasoning {
-        params.insert("reasoning".to_string(), json!(reasoning));
-    }
-    if let Some(fast) = fast {
-        params.insert("fast".to_string(), json!(fast));
-    }
-    if let Some(scope) = scope {
-        params.insert("scope".to_string(), json!(scope));
-    }
-    if reset {
-        params.insert("reset".to_string(), json!(true));
-    }
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "method": "model.set",
-        "params": params
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+    let model = ModelSet {
+        model: model.to_string(),
+        provider: provider.map(str::to_string),
+        reasoning: reasoning.map(str::to_string),
+        fast,
+        scope: scope.map(str::to_string),
+        reset,
+        base_url: None,
+    };
+    send(writer, id, Request::model_set(model))
 }
 
-pub fn send_theme_set(
-    writer: &mut BufWriter<std::process::ChildStdin>,
+/// Selects a model served by a detected local server and saves it to config.
+pub fn send_local_model_set(
+    writer: &mut RuntimeStdin,
     id: &str,
-    name: &str,
+    model: &str,
+    base_url: &str,
 ) -> std::io::Result<()> {
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "method": "theme.set",
-        "params": { "name": name }
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+    let model = ModelSet {
+        model: model.to_string(),
+        provider: Some(LOCAL_MODEL_PROVIDER.to_string()),
+        scope: Some("config".to_string()),
+        base_url: Some(base_url.to_string()),
+        ..ModelSet::default()
+    };
+    send(writer, id, Request::model_set(model))
+}
+
+pub fn send_cwd_set(writer: &mut RuntimeStdin, id: &str, path: &str) -> std::io::Result<()> {
+    send(writer, id, Request::cwd_set(path))
+}
+
+pub fn send_theme_set(writer: &mut RuntimeStdin, id: &str, name: &str) -> std::io::Result<()> {
+    send(writer, id, Request::theme_set(name))
+}
+
+pub fn send_cache_list(writer: &mut RuntimeStdin, id: &str) -> std::io::Result<()> {
+    send(writer, id, Request::cache_list())
+}
+
+pub fn send_cache_read(
+    writer: &mut RuntimeStdin,
+    id: &str,
+    ref_id: &str,
+    max_bytes: usize,
+) -> std::io::Result<()> {
+    send(writer, id, Request::cache_read(ref_id, max_bytes))
+}
+
+pub fn send_cache_delete(writer: &mut RuntimeStdin, id: &str, ref_id: &str) -> std::io::Result<()> {
+    send(writer, id, Request::cache_delete(ref_id))
 }
 
 pub fn send_shell_exec(
-    writer: &mut BufWriter<std::process::ChildStdin>,
+    writer: &mut RuntimeStdin,
     id: &str,
     command: &str,
     timeout_seconds: Option<u64>,
+    env: Option<&serde_json::Map<String, Value>>,
 ) -> std::io::Result<()> {
-    let mut params = serde_json::Map::new();
-    params.insert("command".to_string(), json!(command));
-    if let Some(timeout_seconds) = timeout_seconds {
-        params.insert("timeout_seconds".to_string(), json!(timeout_seconds));
-    }
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "method": "shell.exec",
-        "params": params
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+    send(
+        writer,
+        id,
+        Request::shell(false, command, timeout_seconds, env),
+    )
 }
 
 pub fn send_shell_start(
-    writer: &mut BufWriter<std::process::ChildStdin>,
+    writer: &mut RuntimeStdin,
     id: &str,
     command: &str,
     timeout_seconds: Option<u64>,
+    env: Option<&serde_json::Map<String, Value>>,
 ) -> std::io::Result<()> {
-    let mut params = serde_json::Map::new();
-    params.insert("command".to_string(), json!(command));
-    if let Some(timeout_seconds) = timeout_seconds {
-        params.insert("timeout_seconds".to_string(), json!(timeout_seconds));
-    }
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "method": "shell.start",
-        "params": params
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+    send(
+        writer,
+        id,
+        Request::shell(true, command, timeout_seconds, env),
+    )
 }
 
-pub fn send_shell_wait(
-    writer: &mut BufWriter<std::process::ChildStdin>,
-    id: &str,
-    task_id: &str,
-) -> std::io::Result<()> {
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "method": "shell.wait",
-        "params": { "task_id": task_id }
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+pub fn send_shell_wait(writer: &mut RuntimeStdin, id: &str, task_id: &str) -> std::io::Result<()> {
+    send(writer, id, Request::shell_wait(task_id))
 }
 
 pub fn send_shell_detach(
-    writer: &mut BufWriter<std::process::ChildStdin>,
+    writer: &mut RuntimeStdin,
     id: &str,
     task_id: &str,
 ) -> std::io::Result<()> {
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "method": "shell.detach",
-        "params": { "task_id": task_id }
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+    send(writer, id, Request::shell_detach(task_id))
 }
 
-pub fn send_task_list(
-    writer: &mut BufWriter<std::process::ChildStdin>,
-    id: &str,
-) -> std::io::Result<()> {
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "method": "task.list",
-        "params": {}
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+pub fn send_task_list(writer: &mut RuntimeStdin, id: &str) -> std::io::Result<()> {
+    send(writer, id, Request::task_list())
 }
 
-pub fn send_task_status(
-    writer: &mut BufWriter<std::process::ChildStdin>,
-    id: &str,
-    task_id: &str,
-) -> std::io::Result<()> {
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "method": "task.status",
-        "params": { "task_id": task_id }
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+pub fn send_task_status(writer: &mut RuntimeStdin, id: &str, task_id: &str) -> std::io::Result<()> {
+    send(writer, id, Request::task_status(task_id))
+}
+
+pub fn send_task_cancel(writer: &mut RuntimeStdin, id: &str, task_id: &str) -> std::io::Result<()> {
+    send(writer, id, Request::task_cancel(task_id))
 }
 
-pub fn send_task_cancel(
-    writer: &mut BufWriter<std::process::ChildStdin>,
+pub fn send_tool_call(
+    writer: &mut RuntimeStdin,
     id: &str,
-    task_id: &str,
+    name: &str,
+    arguments: Value,
 ) -> std::io::Result<()> {
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "method": "task.cancel",
-        "params": { "task_id": task_id }
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+    send(writer, id, Request::tool_call(name, arguments, false))
 }
 
-pub fn send_tool_call(
-    writer: &mut BufWriter<std::process::ChildStdin>,
+/// `tool.call` that goes through the session permission policy and UI confirm first
+/// (`supports_tool_call_permission`); a denial answers `ok: false` with the reason.
+pub fn send_permissioned_tool_call(
+    writer: &mut RuntimeStdin,
     id: &str,
     name: &str,
     arguments: Value,
 ) -> std::io::Result<()> {
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "method": "tool.call",
-        "params": {
-            "name": name,
-            "arguments": arguments
-        }
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+    send(writer, id, Request::tool_call(name, arguments, true))
+}
+
+/// Re-sends a request captured by `RuntimeStdin::take_sent_request` under a new id.
+pub fn send_request(
+    writer: &mut RuntimeStdin,
+    id: &str,
+    method: &str,
+    params: Value,
+) -> std::io::Result<()> {
+    send(writer, id, Request::new(method, params))
 }
 
 pub fn send_mcp_list(
-    writer: &mut BufWriter<std::process::ChildStdin>,
+    writer: &mut RuntimeStdin,
     id: &str,
     scope: Option<&str>,
 ) -> std::io::Result<()> {
-    let mut params = serde_json::Map::new();
-    if let Some(scope) = scope {
-        params.insert("scope".to_string(), json!(scope));
-    }
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "method": "mcp.list",
-        "params": params
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+    send(writer, id, Request::mcp_list(scope))
 }
 
 pub fn send_context_inspect(
-    writer: &mut BufWriter<std::process::ChildStdin>,
+    writer: &mut RuntimeStdin,
     id: &str,
     include_agents: bool,
     include_skills: bool,
 ) -> std::io::Result<()> {
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "method": "context.inspect",
-        "params": {
-            "include_agents": include_agents,
-            "include_skills": include_skills
-        }
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+    send(
+        writer,
+        id,
+        Request::context_inspect(include_agents, include_skills),
+    )
 }
 
 pub fn send_skills_list(
-    writer: &mut BufWriter<std::process::ChildStdin>,
+    writer: &mut RuntimeStdin,
     id: &str,
     force_reload: bool,
 ) -> std::io::Result<()> {
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "method": "skills.list",
-        "params": { "force_reload": force_reload }
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+    send(writer, id, Request::skills_list(force_reload))
+}
+
+pub fn send_ui_context_update(
+    writer: &mut RuntimeStdin,
+    context: &UiContextUpdate,
+) -> std::io::Result<()> {
+    write_message(writer, context.to_message())
 }
 
 pub fn send_session_history(
-    writer: &mut BufWriter<std::process::ChildStdin>,
+    writer: &mut RuntimeStdin,
     id: &str,
     session_id: &str,
     max_runs: Option<usize>,
     max_events: Option<usize>,
 ) -> std::io::Result<()> {
-    let mut params = serde_json::Map::new();
-    params.insert("session_id".to_string(), json!(session_id));
-    if let Some(max_runs) = max_runs {
-        params.insert("max_runs".to_string(), json!(max_runs));
-    }
-    if let Some(max_events) = max_events {
-        params.insert("max_events".to_string(), json!(max_events));
-    }
-    let msg = json!({
-        "jsonrpc": "2.0",
-        "id": id,
-        "method": "session.history",
-        "params": params
-    });
-    writer.write_all(json_line(msg).as_bytes())?;
-    writer.flush()?;
-    Ok(())
+    send(
+        writer,
+        id,
+        Request::session_history(session_id, max_runs, max_events),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        json_line, should_include_tui_client_tools_from_values, split_args, tui_client_tools,
+        json_line, runtime_command, should_include_tui_client_tools_from_values, split_args,
+        tui_client_tools, write_message, RuntimeStdin, MAX_IN_FLIGHT_REQUESTS,
     };
     use serde_json::json;
+    use std::process::{Command, Stdio};
+
+    #[test]
+    fn request_latency_is_tracked_for_outgoing_requests_only() {
+        let mut child = Command::new("cat")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+            .expect("spawn cat");
+        let mut writer = RuntimeStdin::new(child.stdin.take().expect("stdin"));
+
+        write_message(
+            &mut writer,
+            json!({"jsonrpc": "2.0", "id": "1", "method": "theme.set", "params": {"name": "rose"}}),
+        )
+        .expect("write");
+        write_message(
+            &mut writer,
+            json!({"jsonrpc": "2.0", "id": "2", "result": {}}),
+        )
+        .expect("write");
+        let sent = writer.take_sent_request("1").expect("tracked request");
+        assert_eq!(sent.method, "theme.set");
+        assert_eq!(sent.params, json!({"name": "rose"}));
+        assert!(writer.take_sent_request("1").is_none());
+        assert!(writer.take_sent_request("2").is_none());
+
+        // Past the cap only the oldest request is forgotten.
+        for index in 0..=MAX_IN_FLIGHT_REQUESTS {
+            write_message(
+                &mut writer,
+                json!({"jsonrpc": "2.0", "id": index.to_string(), "method": "ping"}),
+            )
+            .expect("write");
+        }
+        assert_eq!(writer.in_flight_summaries().len(), MAX_IN_FLIGHT_REQUESTS);
+        assert!(writer.take_sent_request("0").is_none());
+        assert!(writer.take_sent_request("1").is_some());
+
+        drop(writer);
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+
+    #[test]
+    fn tabs_reusing_an_id_match_their_own_requests() {
+        let spawn_cat = || {
+            Command::new("cat")
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .spawn()
+                .expect("spawn cat")
+        };
+        let mut children = [spawn_cat(), spawn_cat()];
+        let [mut tab_a, mut tab_b] = children
+            .each_mut()
+            .map(|child| RuntimeStdin::new(child.stdin.take().expect("stdin")));
+
+        write_message(
+            &mut tab_a,
+            json!({"jsonrpc": "2.0", "id": "3", "method": "model.set", "params": {"name": "a"}}),
+        )
+        .expect("write");
+        write_message(
+            &mut tab_b,
+            json!({"jsonrpc": "2.0", "id": "3", "method": "theme.set", "params": {"name": "b"}}),
+        )
+        .expect("write");
+        assert_eq!(
+            tab_b.take_sent_request("3").expect("tab b").method,
+            "theme.set"
+        );
+        assert_eq!(
+            tab_a.take_sent_request("3").expect("tab a").params,
+            json!({"name": "a"})
+        );
+
+        drop((tab_a, tab_b));
+        for child in &mut children {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
 
     #[test]
     fn split_args_supports_quoted_values() {
@@ -848,6 +846,19 @@ mod tests {
         assert_eq!(args, vec!["node", "\"unterminated"]);
     }
 
+    #[test]
+    fn runtime_command_passes_launch_flags_in_argv() {
+        let (_, args) = runtime_command(Some("minimal"), true);
+        assert!(args.ends_with(&[
+            "--approval-mode".to_string(),
+            "minimal".to_string(),
+            "--read-only".to_string(),
+        ]));
+
+        let (_, args) = runtime_command(None, false);
+        assert!(!args.iter().any(|arg| arg == "--read-only"));
+    }
+
     #[test]
     fn run_input_payload_text_shape_example() {
         let payload = json!({ "type": "text", "text": "hello" });
@@ -900,7 +911,7 @@ mod tests {
                 "arguments": {}
             }
         });
-        let line = json_line(payload);
+        let line = json_line(&payload);
         assert!(line.contains("\"method\":\"tool.call\""));
         assert!(line.contains("\"name\":\"lane_list\""));
     }
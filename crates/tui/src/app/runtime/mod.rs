@@ -1,5 +1,6 @@
 pub(crate) mod client;
 pub(crate) mod parser;
+pub(crate) mod reconnect;
 
 pub(crate) use client::*;
 pub(crate) use parser::*;
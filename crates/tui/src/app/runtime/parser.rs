@@ -1,8 +1,10 @@
 use crate::app::markdown::render_markdown_lines;
 use crate::app::state::{LogKind, LogLine, LogSpan, LogTone};
+use codelia_protocol::{classify, validate_runtime_message, AgentEvent, Incoming, RunStatus};
 use serde_json::Value;
 
 mod agents;
+mod binary;
 mod common;
 mod diff;
 mod helpers;
@@ -16,20 +18,38 @@ use self::common::{
     format_percent, format_u64_with_commas, prefix_block, summary_line, DETAIL_INDENT,
 };
 #[cfg(test)]
+use self::diff::limited_edit_diff_lines_with_hint;
 use self::diff::{
-    limited_edit_diff_lines_with_hint, DIFF_ADDED_MARKER_FG, DIFF_NUMBER_FG, DIFF_REMOVED_MARKER_FG,
+    language_from_path, large_preview_hunks, normalize_diff_fingerprint, normalize_language_hint,
+    permission_preview_lines,
 };
-use self::diff::{normalize_diff_fingerprint, permission_preview_lines};
 use self::helpers::{
-    is_legacy_permission_raw_args_message, looks_like_error, parse_runtime_log_line,
-    permission_preflight_ready_lines, prefix_rendered, summarize_tool_call,
-    summary_and_detail_line, tool_result_lines,
+    edit_call_args, is_legacy_permission_raw_args_message, looks_like_error,
+    parse_runtime_log_line, permission_preflight_ready_lines, prefix_rendered, summarize_tool_call,
+    summary_and_detail_line, tool_result_images, tool_result_lines, STALE_READ_MARKER,
 };
 pub(crate) use self::types::{
-    ClientToolRequest, ParsedOutput, PermissionPreviewUpdate, PermissionReadyUpdate, RpcResponse,
-    ToolCallResultUpdate, UiConfirmRequest, UiPickItem, UiPickRequest, UiPromptRequest,
+    ClientToolRequest, EditCallArgs, ParsedOutput, PermissionPreviewUpdate, PermissionReadyUpdate,
+    RpcResponse, RunUsage, SchemaMismatch, ToolCallResultUpdate, UiConfirmRequest, UiPickRequest,
+    UiPromptRequest,
 };
 
+/// Log lines for an assistant message: a spacer, then the Markdown rendering indented as
+/// detail. `text_delta` streams re-render through this too, so the final swap is seamless.
+pub(crate) fn assistant_message_lines(content: &str) -> Vec<LogLine> {
+    if content.trim().is_empty() {
+        return Vec::new();
+    }
+    let mut lines = vec![LogLine::new(LogKind::Space, "")];
+    lines.extend(prefix_rendered(
+        DETAIL_INDENT,
+        DETAIL_INDENT,
+        render_markdown_lines(content),
+        LogTone::Detail,
+    ));
+    lines
+}
+
 pub fn parse_runtime_output(raw: &str) -> ParsedOutput {
     let trimmed = raw.trim_end();
     if trimmed.is_empty() {
@@ -60,673 +80,439 @@ pub fn parse_runtime_output(raw: &str) -> ParsedOutput {
             };
         }
     };
+    let schema_mismatch = validate_runtime_message(&value, trimmed);
+    let mut parsed = parse_runtime_value(trimmed, value);
+    parsed.schema_mismatch = schema_mismatch;
+    parsed
+}
 
-    if let Some(method) = value.get("method").and_then(|m| m.as_str()) {
-        if method == "ui.confirm.request" {
-            let id = value
-                .get("id")
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .to_string();
-            let params = value.get("params").and_then(|v| v.as_object());
-            let title = params
-                .and_then(|p| p.get("title"))
-                .and_then(|v| v.as_str())
-                .unwrap_or("Confirm")
-                .to_string();
-            let message = params
-                .and_then(|p| p.get("message"))
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .to_string();
-            let danger_level = params
-                .and_then(|p| p.get("danger_level"))
-                .and_then(|v| v.as_str())
-                .map(|v| v.to_string());
-            let confirm_label = params
-                .and_then(|p| p.get("confirm_label"))
-                .and_then(|v| v.as_str())
-                .map(|v| v.to_string());
-            let cancel_label = params
-                .and_then(|p| p.get("cancel_label"))
-                .and_then(|v| v.as_str())
-                .map(|v| v.to_string());
-            let allow_remember = params
-                .and_then(|p| p.get("allow_remember"))
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false);
-            let allow_reason = params
-                .and_then(|p| p.get("allow_reason"))
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false);
-            return ParsedOutput {
-                confirm_request: Some(UiConfirmRequest {
-                    id,
-                    title,
-                    message,
-                    danger_level,
-                    confirm_label,
-                    cancel_label,
-                    allow_remember,
-                    allow_reason,
-                }),
-                ..ParsedOutput::empty()
-            };
-        }
+fn parse_runtime_value(trimmed: &str, value: Value) -> ParsedOutput {
+    match classify(&value) {
+        Incoming::ConfirmRequest(request) => ParsedOutput {
+            confirm_request: Some(request),
+            ..ParsedOutput::empty()
+        },
+        Incoming::PromptRequest(request) => ParsedOutput {
+            prompt_request: Some(request),
+            ..ParsedOutput::empty()
+        },
+        Incoming::PickRequest(request) => ParsedOutput {
+            pick_request: Some(request),
+            ..ParsedOutput::empty()
+        },
+        Incoming::ClientToolCall(request) => ParsedOutput {
+            client_tool_request: Some(request),
+            ..ParsedOutput::empty()
+        },
+        Incoming::AgentEvent(event) => agent_event_output(event),
+        Incoming::RunContext(context) => ParsedOutput {
+            context_left_percent: context.context_left_percent,
+            ..ParsedOutput::empty()
+        },
+        Incoming::RunUsage(usage) => ParsedOutput {
+            run_usage: Some(usage),
+            ..ParsedOutput::empty()
+        },
+        Incoming::RunDiagnostics(params) => diagnostics_output(&params),
+        Incoming::RunStatus(status) => run_status_output(status),
+        Incoming::Response(response) => ParsedOutput {
+            rpc_response: Some(response),
+            ..ParsedOutput::empty()
+        },
+        Incoming::Unknown => ParsedOutput {
+            lines: vec![LogLine::new(LogKind::Runtime, trimmed.to_string())],
+            ..ParsedOutput::empty()
+        },
+    }
+}
 
-        if method == "ui.prompt.request" {
-            let id = value
-                .get("id")
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .to_string();
-            let params = value.get("params").and_then(|v| v.as_object());
-            let title = params
-                .and_then(|p| p.get("title"))
-                .and_then(|v| v.as_str())
-                .unwrap_or("Prompt")
-                .to_string();
-            let message = params
-                .and_then(|p| p.get("message"))
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .to_string();
-            let default_value = params
-                .and_then(|p| p.get("default_value"))
-                .and_then(|v| v.as_str())
-                .map(|v| v.to_string());
-            let multiline = params
-                .and_then(|p| p.get("multiline"))
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false);
-            let secret = params
-                .and_then(|p| p.get("secret"))
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false);
-            return ParsedOutput {
-                prompt_request: Some(UiPromptRequest {
-                    id,
-                    title,
-                    message,
-                    default_value,
-                    multiline,
-                    secret,
+fn agent_event_output(event: AgentEvent) -> ParsedOutput {
+    match event {
+        AgentEvent::PermissionPreview {
+            tool,
+            tool_call_id,
+            file_path,
+            language,
+            diff,
+            summary,
+            truncated,
+        } => {
+            let (file_path, language) = (file_path.as_deref(), language.as_deref());
+            let (diff, summary) = (diff.as_deref(), summary.as_deref());
+            let diff_fingerprint = diff.and_then(normalize_diff_fingerprint);
+            let resolved_language = language
+                .and_then(normalize_language_hint)
+                .or_else(|| file_path.and_then(language_from_path));
+            let large_hunks =
+                diff.and_then(|diff| large_preview_hunks(diff, resolved_language.as_deref()));
+            ParsedOutput {
+                lines: permission_preview_lines(
+                    &tool,
+                    diff,
+                    summary,
+                    truncated,
+                    file_path,
+                    language,
+                    large_hunks.as_deref(),
+                ),
+                permission_preview_update: tool_call_id.map(|id| PermissionPreviewUpdate {
+                    tool_call_id: id,
+                    tool,
+                    target: file_path
+                        .or(summary)
+                        .and_then(|value| value.lines().next())
+                        .map(str::to_string),
+                    has_diff: diff_fingerprint.is_some(),
+                    truncated,
+                    diff_fingerprint,
+                    large_hunks,
                 }),
                 ..ParsedOutput::empty()
-            };
+            }
         }
-
-        if method == "ui.pick.request" {
-            let id = value
-                .get("id")
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .to_string();
-            let params = value.get("params").and_then(|v| v.as_object());
-            let title = params
-                .and_then(|p| p.get("title"))
-                .and_then(|v| v.as_str())
-                .unwrap_or("Pick")
-                .to_string();
-            let items = params
-                .and_then(|p| p.get("items"))
-                .and_then(|v| v.as_array())
-                .map(|items| {
-                    items
-                        .iter()
-                        .filter_map(|item| item.as_object())
-                        .map(|item| UiPickItem {
-                            id: item
-                                .get("id")
-                                .and_then(|v| v.as_str())
-                                .unwrap_or("")
-                                .to_string(),
-                            label: item
-                                .get("label")
-                                .and_then(|v| v.as_str())
-                                .unwrap_or("")
-                                .to_string(),
-                            detail: item
-                                .get("detail")
-                                .and_then(|v| v.as_str())
-                                .map(|v| v.to_string()),
-                        })
-                        .collect::<Vec<_>>()
-                })
-                .unwrap_or_default();
-            let multi = params
-                .and_then(|p| p.get("multi"))
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false);
-            return ParsedOutput {
-                pick_request: Some(UiPickRequest {
-                    id,
-                    title,
-                    items,
-                    multi,
-                }),
+        AgentEvent::PermissionReady { tool, tool_call_id } => ParsedOutput {
+            lines: permission_preflight_ready_lines(&tool),
+            permission_ready_update: tool_call_id.map(|id| PermissionReadyUpdate {
+                tool_call_id: id,
+                tool,
+            }),
+            ..ParsedOutput::empty()
+        },
+        AgentEvent::Text { content } => {
+            if is_legacy_permission_raw_args_message(&content) {
+                return ParsedOutput::empty();
+            }
+            ParsedOutput {
+                lines: assistant_message_lines(&content),
+                assistant_text: Some(content),
                 ..ParsedOutput::empty()
-            };
+            }
         }
-
-        if method == "client.tool.call" {
-            let id = value
-                .get("id")
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .to_string();
-            let params = value.get("params").and_then(|v| v.as_object());
-            let name = params
-                .and_then(|p| p.get("name"))
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .to_string();
-            let arguments = params
-                .and_then(|p| p.get("arguments"))
-                .cloned()
-                .unwrap_or(Value::Null);
-            return ParsedOutput {
-                client_tool_request: Some(ClientToolRequest {
-                    id,
-                    name,
-                    arguments,
-                }),
+        AgentEvent::TextDelta { content } => {
+            if content.is_empty() {
+                return ParsedOutput::empty();
+            }
+            ParsedOutput {
+                assistant_delta: Some(content),
                 ..ParsedOutput::empty()
-            };
+            }
         }
-
-        if method == "agent.event" {
-            let event = &value["params"]["event"];
-            let event_type = event
-                .get("type")
-                .and_then(|v| v.as_str())
-                .unwrap_or("event");
-            match event_type {
-                "permission.preview" => {
-                    let tool = event.get("tool").and_then(|v| v.as_str()).unwrap_or("tool");
-                    let tool_call_id = event
-                        .get("tool_call_id")
-                        .and_then(|v| v.as_str())
-                        .map(|v| v.to_string());
-                    let file_path = event.get("file_path").and_then(|v| v.as_str());
-                    let language = event.get("language").and_then(|v| v.as_str());
-                    let diff = event.get("diff").and_then(|v| v.as_str());
-                    let summary = event.get("summary").and_then(|v| v.as_str());
-                    let truncated = event
-                        .get("truncated")
-                        .and_then(|v| v.as_bool())
-                        .unwrap_or(false);
-                    let diff_fingerprint = diff.and_then(normalize_diff_fingerprint);
-                    return ParsedOutput {
-                        lines: permission_preview_lines(
-                            tool, diff, summary, truncated, file_path, language,
-                        ),
-                        permission_preview_update: tool_call_id.map(|id| PermissionPreviewUpdate {
-                            tool_call_id: id,
-                            has_diff: diff_fingerprint.is_some(),
-                            truncated,
-                            diff_fingerprint,
-                        }),
-                        ..ParsedOutput::empty()
-                    };
-                }
-                "permission.ready" => {
-                    let tool = event.get("tool").and_then(|v| v.as_str()).unwrap_or("tool");
-                    let tool_call_id = event
-                        .get("tool_call_id")
-                        .and_then(|v| v.as_str())
-                        .map(|v| v.to_string());
-                    return ParsedOutput {
-                        lines: permission_preflight_ready_lines(tool),
-                        permission_ready_update: tool_call_id
-                            .map(|id| PermissionReadyUpdate { tool_call_id: id }),
-                        ..ParsedOutput::empty()
-                    };
-                }
-                "text" => {
-                    let content = event.get("content").and_then(|v| v.as_str()).unwrap_or("");
-                    if is_legacy_permission_raw_args_message(content) {
-                        return ParsedOutput::empty();
-                    }
-                    let rendered = render_markdown_lines(content);
-                    let lines = if content.trim().is_empty() {
-                        Vec::new()
-                    } else {
-                        let mut lines = vec![LogLine::new(LogKind::Space, "")];
-                        let mut body = prefix_rendered(
-                            DETAIL_INDENT,
-                            DETAIL_INDENT,
-                            rendered,
-                            LogTone::Detail,
-                        );
-                        lines.append(&mut body);
-                        lines
-                    };
-                    return ParsedOutput {
-                        lines,
-                        assistant_text: Some(content.to_string()),
-                        ..ParsedOutput::empty()
-                    };
-                }
-                "reasoning" => {
-                    let content = event.get("content").and_then(|v| v.as_str()).unwrap_or("");
-                    if content.trim().is_empty() {
-                        return ParsedOutput::empty();
-                    }
-                    let mut lines = vec![LogLine::new(LogKind::Space, "")];
-                    let mut body =
-                        prefix_block("", "", LogKind::Reasoning, LogTone::Detail, content);
-                    lines.append(&mut body);
-                    return ParsedOutput {
-                        lines,
-                        ..ParsedOutput::empty()
-                    };
-                }
-                "step_start" | "step_complete" => {
-                    return ParsedOutput::empty();
-                }
-                "compaction_start" => {
-                    let lines = vec![summary_line("", "Compaction: running", LogKind::Compaction)];
-                    return ParsedOutput {
-                        lines,
-                        compaction_started: true,
-                        ..ParsedOutput::empty()
-                    };
-                }
-                "compaction_complete" => {
-                    let compacted = event
-                        .get("compacted")
-                        .and_then(|v| v.as_bool())
-                        .unwrap_or(false);
-                    let status = if compacted { "completed" } else { "skipped" };
-                    let label = format!("Compaction: {status} (compacted={compacted})");
-                    let lines = vec![summary_line("", label, LogKind::Compaction)];
-                    return ParsedOutput {
-                        lines,
-                        compaction_completed: true,
-                        ..ParsedOutput::empty()
-                    };
-                }
-                "tool_call" => {
-                    let tool = event.get("tool").and_then(|v| v.as_str()).unwrap_or("tool");
-                    let args = event.get("args").cloned().unwrap_or(Value::Null);
-                    let tool_call_id = event
-                        .get("tool_call_id")
-                        .and_then(|v| v.as_str())
-                        .map(|v| v.to_string());
-                    let summary = summarize_tool_call(tool, &args);
-                    let mut spans = vec![LogSpan::new(
-                        LogKind::ToolCall,
-                        LogTone::Summary,
-                        summary.label,
-                    )];
-                    if !summary.detail.is_empty() {
-                        spans.push(LogSpan::new(LogKind::ToolCall, LogTone::Summary, " "));
-                        spans.push(LogSpan::new(
-                            LogKind::Assistant,
-                            LogTone::Summary,
-                            summary.detail,
-                        ));
-                    }
-                    let lines = vec![LogLine::new_with_spans(spans)];
-                    return ParsedOutput {
-                        lines,
-                        tool_call_start_id: tool_call_id,
-                        ..ParsedOutput::empty()
-                    };
-                }
-                "tool_result" => {
-                    let tool = event.get("tool").and_then(|v| v.as_str()).unwrap_or("tool");
-                    let tool_call_id = event
-                        .get("tool_call_id")
-                        .and_then(|v| v.as_str())
-                        .map(|v| v.to_string());
-                    let result = event.get("result").cloned().unwrap_or(Value::Null);
-                    let is_error = event
-                        .get("is_error")
-                        .and_then(|v| v.as_bool())
-                        .unwrap_or(false);
-                    let content = if let Some(text) = result.as_str() {
-                        text.to_string()
-                    } else {
-                        result.to_string()
-                    };
-                    let mut rendered = tool_result_lines(tool, &content, is_error);
-                    let mut lines = rendered.lines;
-                    let is_error_result = is_error || looks_like_error(tool, &content, is_error);
-                    let fallback_summary = if let Some(line) = lines.first().cloned() {
-                        line
-                    } else {
-                        LogLine::new(LogKind::ToolResult, "")
-                    };
-                    let tool_call_result = tool_call_id.map(|id| ToolCallResultUpdate {
-                        tool_call_id: id,
-                        tool: tool.to_string(),
-                        is_error: is_error_result,
-                        fallback_summary,
-                        edit_diff_fingerprint: rendered.edit_diff_fingerprint.take(),
-                    });
-                    if tool_call_result.is_some() && !lines.is_empty() {
-                        lines.remove(0);
-                    }
-                    return ParsedOutput {
-                        lines,
-                        tool_call_result,
-                        ..ParsedOutput::empty()
-                    };
-                }
-                "final" => {
-                    let content = event.get("content").and_then(|v| v.as_str()).unwrap_or("");
-                    let rendered = render_markdown_lines(content);
-                    let lines = if content.trim().is_empty() {
-                        Vec::new()
-                    } else {
-                        let mut lines = vec![LogLine::new(LogKind::Space, "")];
-                        let mut body = prefix_rendered(
-                            DETAIL_INDENT,
-                            DETAIL_INDENT,
-                            rendered,
-                            LogTone::Detail,
-                        );
-                        lines.append(&mut body);
-                        lines
-                    };
-                    return ParsedOutput {
-                        lines,
-                        final_text: Some(content.to_string()),
-                        ..ParsedOutput::empty()
-                    };
-                }
-                "hidden_user_message" => {
-                    let content = event.get("content").and_then(|v| v.as_str()).unwrap_or("");
-                    let line = format!("> {}", content);
-                    let lines = vec![LogLine::new(LogKind::User, line)];
-                    return ParsedOutput {
-                        lines,
-                        ..ParsedOutput::empty()
-                    };
-                }
-                _ => {
-                    return ParsedOutput {
-                        lines: vec![LogLine::new(
-                            LogKind::Runtime,
-                            format!("event: {event_type}"),
-                        )],
-                        ..ParsedOutput::empty()
-                    };
-                }
+        AgentEvent::Reasoning { content } => {
+            if content.trim().is_empty() {
+                return ParsedOutput::empty();
+            }
+            let mut lines = vec![LogLine::new(LogKind::Space, "")];
+            let mut body = prefix_block("", "", LogKind::Reasoning, LogTone::Detail, &content);
+            lines.append(&mut body);
+            ParsedOutput {
+                lines,
+                ..ParsedOutput::empty()
             }
         }
-
-        if method == "run.context" {
-            let percent = value["params"]
-                .get("context_left_percent")
-                .and_then(|v| v.as_u64())
-                .map(|value| value.min(100) as u8);
-            return ParsedOutput {
-                context_left_percent: percent,
+        AgentEvent::StepStart | AgentEvent::StepComplete => ParsedOutput::empty(),
+        AgentEvent::CompactionStart => ParsedOutput {
+            lines: vec![summary_line("", "Compaction: running", LogKind::Compaction)],
+            compaction_started: true,
+            ..ParsedOutput::empty()
+        },
+        AgentEvent::CompactionComplete { compacted } => {
+            let status = if compacted { "completed" } else { "skipped" };
+            let label = format!("Compaction: {status} (compacted={compacted})");
+            ParsedOutput {
+                lines: vec![summary_line("", label, LogKind::Compaction)],
+                compaction_completed: true,
                 ..ParsedOutput::empty()
-            };
+            }
         }
-
-        if method == "run.diagnostics" {
-            let params = value.get("params").cloned().unwrap_or(Value::Null);
-            let kind = params
-                .get("kind")
-                .and_then(|v| v.as_str())
-                .unwrap_or("unknown");
-            if kind == "llm_call" {
-                let call = params.get("call").cloned().unwrap_or(Value::Null);
-                let seq = call.get("seq").and_then(|v| v.as_u64()).unwrap_or(0);
-                let model = call
-                    .get("model")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("unknown");
-                let provider = call.get("provider").and_then(|v| v.as_str()).unwrap_or("-");
-                let latency_ms = call.get("latency_ms").and_then(|v| v.as_u64()).unwrap_or(0);
-                let stop_reason = call
-                    .get("stop_reason")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("-");
-                let usage = call.get("usage").cloned().unwrap_or(Value::Null);
-                let input_tokens = usage
-                    .get("input_tokens")
-                    .and_then(|v| v.as_u64())
-                    .unwrap_or(0);
-                let output_tokens = usage
-                    .get("output_tokens")
-                    .and_then(|v| v.as_u64())
-                    .unwrap_or(0);
-                let total_tokens = usage
-                    .get("total_tokens")
-                    .and_then(|v| v.as_u64())
-                    .unwrap_or(0);
-                let cache = call.get("cache").cloned().unwrap_or(Value::Null);
-                let hit_state = cache
-                    .get("hit_state")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("unknown");
-                let cache_read = cache
-                    .get("cache_read_tokens")
-                    .and_then(|v| v.as_u64())
-                    .unwrap_or(0);
-                let cache_creation = cache
-                    .get("cache_creation_tokens")
-                    .and_then(|v| v.as_u64())
-                    .unwrap_or(0);
-                let cache_read_ratio = if input_tokens == 0 {
-                    0.0
-                } else {
-                    (cache_read as f64 / input_tokens as f64) * 100.0
-                };
-                let label = format!("diag llm#{seq} {model}");
-                let detail = format!(
-                    "provider={provider} latency={}ms stop={} tok(in/out/total)={}/{}/{} cache={} read={} ({}) create={}",
-                    latency_ms,
-                    stop_reason,
-                    format_u64_with_commas(input_tokens),
-                    format_u64_with_commas(output_tokens),
-                    format_u64_with_commas(total_tokens),
-                    hit_state,
-                    format_u64_with_commas(cache_read),
-                    format_percent(cache_read_ratio),
-                    format_u64_with_commas(cache_creation),
-                );
-                let detail = if let Some(provider_meta_summary) = call
-                    .get("provider_meta_summary")
-                    .and_then(|v| v.as_str())
-                    .filter(|v| !v.is_empty())
-                {
-                    format!("{detail} meta={provider_meta_summary}")
-                } else {
-                    detail
-                };
-                return ParsedOutput {
-                    lines: summary_and_detail_line(
-                        "",
-                        &label,
-                        &detail,
-                        LogKind::Status,
-                        LogKind::Status,
-                    ),
-                    ..ParsedOutput::empty()
-                };
+        AgentEvent::ToolCall {
+            tool,
+            tool_call_id,
+            args,
+        } => {
+            let summary = summarize_tool_call(&tool, &args);
+            let mut spans = vec![LogSpan::new(
+                LogKind::ToolCall,
+                LogTone::Summary,
+                summary.label,
+            )];
+            if !summary.detail.is_empty() {
+                spans.push(LogSpan::new(LogKind::ToolCall, LogTone::Summary, " "));
+                spans.push(LogSpan::new(
+                    LogKind::Assistant,
+                    LogTone::Summary,
+                    summary.detail,
+                ));
             }
-            if kind == "run_summary" {
-                let summary = params.get("summary").cloned().unwrap_or(Value::Null);
-                let total_calls = summary
-                    .get("total_calls")
-                    .and_then(|v| v.as_u64())
-                    .unwrap_or(0);
-                let total_input = summary
-                    .get("total_input_tokens")
-                    .and_then(|v| v.as_u64())
-                    .unwrap_or(0);
-                let total_output = summary
-                    .get("total_output_tokens")
-                    .and_then(|v| v.as_u64())
-                    .unwrap_or(0);
-                let total_tokens = summary
-                    .get("total_tokens")
-                    .and_then(|v| v.as_u64())
-                    .unwrap_or(0);
-                let total_cached = summary
-                    .get("total_cached_input_tokens")
-                    .and_then(|v| v.as_u64())
-                    .unwrap_or(0);
-                let total_cache_creation = summary
-                    .get("total_cache_creation_tokens")
-                    .and_then(|v| v.as_u64())
-                    .unwrap_or(0);
-                let cache_read_ratio = if total_input == 0 {
-                    0.0
-                } else {
-                    (total_cached as f64 / total_input as f64) * 100.0
-                };
-                let by_model = summary
-                    .get("by_model")
-                    .and_then(|v| v.as_object())
-                    .cloned()
-                    .unwrap_or_default();
-                let mut hit_calls = 0_u64;
-                let mut miss_calls = 0_u64;
-                let mut unknown_calls = 0_u64;
-                for model_stats in by_model.values() {
-                    let calls = model_stats
-                        .get("calls")
-                        .and_then(|v| v.as_u64())
-                        .unwrap_or(0);
-                    let cached_input_tokens = model_stats
-                        .get("cached_input_tokens")
-                        .and_then(|v| v.as_u64())
-                        .unwrap_or(0);
-                    let input_tokens = model_stats
-                        .get("input_tokens")
-                        .and_then(|v| v.as_u64())
-                        .unwrap_or(0);
-                    if calls == 0 {
-                        continue;
-                    }
-                    if cached_input_tokens > 0 {
-                        hit_calls += calls;
-                    } else if input_tokens > 0 {
-                        miss_calls += calls;
-                    } else {
-                        unknown_calls += calls;
-                    }
-                }
-                if hit_calls + miss_calls + unknown_calls < total_calls {
-                    unknown_calls += total_calls - (hit_calls + miss_calls + unknown_calls);
-                }
-                let label = "diag run total (cumulative)";
-                let detail = format!(
-                    "calls={} tok(in/out/total)={}/{}/{} cache(read/create)={}/{} ({}) calls(hit/miss/unknown)={}/{}/{}",
-                    total_calls,
-                    format_u64_with_commas(total_input),
-                    format_u64_with_commas(total_output),
-                    format_u64_with_commas(total_tokens),
-                    format_u64_with_commas(total_cached),
-                    format_u64_with_commas(total_cache_creation),
-                    format_percent(cache_read_ratio),
-                    hit_calls,
-                    miss_calls,
-                    unknown_calls,
-                );
-                return ParsedOutput {
-                    lines: summary_and_detail_line(
-                        "",
-                        label,
-                        &detail,
-                        LogKind::Status,
-                        LogKind::Status,
-                    ),
-                    ..ParsedOutput::empty()
-                };
+            ParsedOutput {
+                lines: vec![LogLine::new_with_spans(spans)],
+                tool_call_start_id: tool_call_id,
+                tool_call_edit: edit_call_args(&tool, &args),
+                tool_call_command: (tool == "shell")
+                    .then(|| args.get("command").and_then(|v| v.as_str()))
+                    .flatten()
+                    .map(|v| v.to_string()),
+                tool_call_start_tool: Some(tool),
+                ..ParsedOutput::empty()
             }
-            return ParsedOutput::empty();
         }
-
-        if method == "run.status" {
-            let status = value["params"]
-                .get("status")
-                .and_then(|v| v.as_str())
-                .unwrap_or("unknown");
-            let message = value["params"]
-                .get("message")
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-            let run_id = value["params"]
-                .get("run_id")
-                .and_then(|v| v.as_str())
-                .map(|id| id.to_string());
-            let is_error_status = status == "error";
-            let summary_kind = if is_error_status {
-                LogKind::Error
+        AgentEvent::ToolResult {
+            tool,
+            tool_call_id,
+            result,
+            is_error,
+            output_ref_id,
+        } => {
+            let content = if let Some(text) = result.as_str() {
+                text.to_string()
             } else {
-                LogKind::Runtime
+                result.to_string()
             };
-            let detail_kind = if is_error_status {
-                LogKind::Error
+            let mut rendered = tool_result_lines(&tool, &content, is_error);
+            let mut lines = rendered.lines;
+            let is_error_result = is_error || looks_like_error(&tool, &content, is_error);
+            let fallback_summary = if let Some(line) = lines.first().cloned() {
+                line
             } else {
-                LogKind::Status
+                LogLine::new(LogKind::ToolResult, "")
             };
-            let lines = if message.is_empty() {
-                vec![LogLine::new(
-                    summary_kind,
-                    format!("runtime status: {status}"),
-                )]
-            } else {
-                summary_and_detail_line(
-                    "",
-                    &format!("runtime status: {status} -"),
-                    message,
-                    summary_kind,
-                    detail_kind,
-                )
-            };
-            return ParsedOutput {
+            let tool_call_result = tool_call_id.map(|id| ToolCallResultUpdate {
+                tool_call_id: id,
+                tool,
+                is_error: is_error_result,
+                fallback_summary,
+                edit_diff_fingerprint: rendered.edit_diff_fingerprint.take(),
+                output_ref_id,
+                images: tool_result_images(&result),
+                stale_read: is_error_result && content.contains(STALE_READ_MARKER),
+            });
+            if tool_call_result.is_some() && !lines.is_empty() {
+                lines.remove(0);
+            }
+            ParsedOutput {
                 lines,
-                status: Some(status.to_string()),
-                status_run_id: run_id,
+                tool_call_result,
                 ..ParsedOutput::empty()
-            };
+            }
         }
+        AgentEvent::Final { content } => ParsedOutput {
+            lines: assistant_message_lines(&content),
+            final_text: Some(content),
+            ..ParsedOutput::empty()
+        },
+        AgentEvent::HiddenUserMessage { content } => ParsedOutput {
+            lines: vec![LogLine::new(LogKind::User, format!("> {content}"))],
+            ..ParsedOutput::empty()
+        },
+        AgentEvent::Other { event_type, .. } => ParsedOutput {
+            lines: vec![LogLine::new(
+                LogKind::Runtime,
+                format!("event: {event_type}"),
+            )],
+            ..ParsedOutput::empty()
+        },
     }
+}
 
-    if value.get("result").is_some() && value.get("id").is_some() {
-        let id = value
-            .get("id")
-            .and_then(|id| id.as_str())
-            .unwrap_or("")
-            .to_string();
+/// `run.diagnostics`: per-call LLM stats and the cumulative run summary (`--diagnostics`).
+fn diagnostics_output(params: &Value) -> ParsedOutput {
+    let kind = params
+        .get("kind")
+        .and_then(|v| v.as_str())
+        .unwrap_or("unknown");
+    if kind == "llm_call" {
+        let call = params.get("call").cloned().unwrap_or(Value::Null);
+        let seq = call.get("seq").and_then(|v| v.as_u64()).unwrap_or(0);
+        let model = call
+            .get("model")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown");
+        let provider = call.get("provider").and_then(|v| v.as_str()).unwrap_or("-");
+        let latency_ms = call.get("latency_ms").and_then(|v| v.as_u64()).unwrap_or(0);
+        let stop_reason = call
+            .get("stop_reason")
+            .and_then(|v| v.as_str())
+            .unwrap_or("-");
+        let usage = call.get("usage").cloned().unwrap_or(Value::Null);
+        let input_tokens = usage
+            .get("input_tokens")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        let output_tokens = usage
+            .get("output_tokens")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        let total_tokens = usage
+            .get("total_tokens")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        let cache = call.get("cache").cloned().unwrap_or(Value::Null);
+        let hit_state = cache
+            .get("hit_state")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown");
+        let cache_read = cache
+            .get("cache_read_tokens")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        let cache_creation = cache
+            .get("cache_creation_tokens")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        let cache_read_ratio = if input_tokens == 0 {
+            0.0
+        } else {
+            (cache_read as f64 / input_tokens as f64) * 100.0
+        };
+        let label = format!("diag llm#{seq} {model}");
+        let detail = format!(
+            "provider={provider} latency={}ms stop={} tok(in/out/total)={}/{}/{} cache={} read={} ({}) create={}",
+            latency_ms,
+            stop_reason,
+            format_u64_with_commas(input_tokens),
+            format_u64_with_commas(output_tokens),
+            format_u64_with_commas(total_tokens),
+            hit_state,
+            format_u64_with_commas(cache_read),
+            format_percent(cache_read_ratio),
+            format_u64_with_commas(cache_creation),
+        );
+        let detail = if let Some(provider_meta_summary) = call
+            .get("provider_meta_summary")
+            .and_then(|v| v.as_str())
+            .filter(|v| !v.is_empty())
+        {
+            format!("{detail} meta={provider_meta_summary}")
+        } else {
+            detail
+        };
         return ParsedOutput {
-            rpc_response: Some(RpcResponse {
-                id,
-                result: value.get("result").cloned(),
-                error: value.get("error").cloned(),
-            }),
+            lines: summary_and_detail_line("", &label, &detail, LogKind::Status, LogKind::Status),
             ..ParsedOutput::empty()
         };
     }
-
-    if value.get("error").is_some() && value.get("id").is_some() {
-        let id = value
-            .get("id")
-            .and_then(|id| id.as_str())
-            .unwrap_or("")
-            .to_string();
+    if kind == "run_summary" {
+        let summary = params.get("summary").cloned().unwrap_or(Value::Null);
+        let total_calls = summary
+            .get("total_calls")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        let total_input = summary
+            .get("total_input_tokens")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        let total_output = summary
+            .get("total_output_tokens")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        let total_tokens = summary
+            .get("total_tokens")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        let total_cached = summary
+            .get("total_cached_input_tokens")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        let total_cache_creation = summary
+            .get("total_cache_creation_tokens")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        let cache_read_ratio = if total_input == 0 {
+            0.0
+        } else {
+            (total_cached as f64 / total_input as f64) * 100.0
+        };
+        let by_model = summary
+            .get("by_model")
+            .and_then(|v| v.as_object())
+            .cloned()
+            .unwrap_or_default();
+        let mut hit_calls = 0_u64;
+        let mut miss_calls = 0_u64;
+        let mut unknown_calls = 0_u64;
+        for model_stats in by_model.values() {
+            let calls = model_stats
+                .get("calls")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0);
+            let cached_input_tokens = model_stats
+                .get("cached_input_tokens")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0);
+            let input_tokens = model_stats
+                .get("input_tokens")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0);
+            if calls == 0 {
+                continue;
+            }
+            if cached_input_tokens > 0 {
+                hit_calls += calls;
+            } else if input_tokens > 0 {
+                miss_calls += calls;
+            } else {
+                unknown_calls += calls;
+            }
+        }
+        if hit_calls + miss_calls + unknown_calls < total_calls {
+            unknown_calls += total_calls - (hit_calls + miss_calls + unknown_calls);
+        }
+        let label = "diag run total (cumulative)";
+        let detail = format!(
+            "calls={} tok(in/out/total)={}/{}/{} cache(read/create)={}/{} ({}) calls(hit/miss/unknown)={}/{}/{}",
+            total_calls,
+            format_u64_with_commas(total_input),
+            format_u64_with_commas(total_output),
+            format_u64_with_commas(total_tokens),
+            format_u64_with_commas(total_cached),
+            format_u64_with_commas(total_cache_creation),
+            format_percent(cache_read_ratio),
+            hit_calls,
+            miss_calls,
+            unknown_calls,
+        );
         return ParsedOutput {
-            rpc_response: Some(RpcResponse {
-                id,
-                result: None,
-                error: value.get("error").cloned(),
-            }),
+            lines: summary_and_detail_line("", label, &detail, LogKind::Status, LogKind::Status),
             ..ParsedOutput::empty()
         };
     }
+    ParsedOutput::empty()
+}
 
+fn run_status_output(status: RunStatus) -> ParsedOutput {
+    let RunStatus {
+        run_id,
+        status,
+        message,
+    } = status;
+    let is_error_status = status == "error";
+    let summary_kind = if is_error_status {
+        LogKind::Error
+    } else {
+        LogKind::Runtime
+    };
+    let detail_kind = if is_error_status {
+        LogKind::Error
+    } else {
+        LogKind::Status
+    };
+    let lines = if message.is_empty() {
+        vec![LogLine::new(
+            summary_kind,
+            format!("runtime status: {status}"),
+        )]
+    } else {
+        summary_and_detail_line(
+            "",
+            &format!("runtime status: {status} -"),
+            &message,
+            summary_kind,
+            detail_kind,
+        )
+    };
     ParsedOutput {
-        lines: vec![LogLine::new(LogKind::Runtime, trimmed.to_string())],
+        lines,
+        status: Some(status),
+        status_run_id: run_id,
         ..ParsedOutput::empty()
     }
 }
@@ -734,6 +520,8 @@ pub fn parse_runtime_output(raw: &str) -> ParsedOutput {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::app::state::set_ansi_color_passthrough;
+    use crate::app::theme::diff_palette;
     use serde_json::json;
 
     #[test]
@@ -747,6 +535,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_text_delta_carries_the_chunk_without_log_lines() {
+        let payload = json!({
+            "jsonrpc": "2.0",
+            "method": "agent.event",
+            "params": {"event": {"type": "text_delta", "content": "**bo"}}
+        })
+        .to_string();
+        let parsed = parse_runtime_output(&payload);
+        assert!(parsed.lines.is_empty());
+        assert_eq!(parsed.assistant_delta.as_deref(), Some("**bo"));
+        assert!(parsed.assistant_text.is_none());
+    }
+
     #[test]
     fn parse_runtime_output_keeps_non_error_runtime_lines_as_runtime() {
         let parsed = parse_runtime_output("[runtime] runtime started");
@@ -1168,6 +970,80 @@ mod tests {
         );
     }
 
+    #[test]
+    fn tool_result_images_are_collected_as_data_urls() {
+        let payload = json!({
+            "jsonrpc": "2.0",
+            "method": "agent.event",
+            "params": {
+                "event": {
+                    "type": "tool_result",
+                    "tool": "screenshot",
+                    "tool_call_id": "shot-1",
+                    "is_error": false,
+                    "result": [
+                        { "type": "text", "text": "captured" },
+                        { "type": "image_url", "image_url": { "url": "data:image/png;base64,AAAA" } },
+                        { "type": "image", "data": "BBBB", "mimeType": "image/jpeg" },
+                        { "type": "image_url", "image_url": { "url": "https://example.com/a.png" } }
+                    ]
+                }
+            }
+        })
+        .to_string();
+        let update = parse_runtime_output(&payload)
+            .tool_call_result
+            .expect("tool result update");
+        assert_eq!(
+            update.images,
+            vec![
+                "data:image/png;base64,AAAA".to_string(),
+                "data:image/jpeg;base64,BBBB".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn edit_calls_and_stale_read_failures_are_reported() {
+        let event = |event: serde_json::Value| {
+            json!({ "jsonrpc": "2.0", "method": "agent.event", "params": { "event": event } })
+                .to_string()
+        };
+        let parsed = parse_runtime_output(&event(json!({
+            "type": "tool_call",
+            "tool": "edit",
+            "tool_call_id": "edit-1",
+            "args": { "file_path": "src/a.rs", "old_string": "a\n", "new_string": "b\n" }
+        })));
+        let edit = parsed.tool_call_edit.expect("edit args");
+        assert_eq!(
+            (
+                edit.file_path.as_str(),
+                edit.old_string.as_str(),
+                edit.new_string.as_str()
+            ),
+            ("src/a.rs", "a\n", "b\n")
+        );
+        let parsed = parse_runtime_output(&event(json!({
+            "type": "tool_call",
+            "tool": "edit",
+            "tool_call_id": "edit-2",
+            "args": { "file_path": "src/a.rs", "old_string": "a", "new_string": "b", "replace_all": true }
+        })));
+        assert!(parsed.tool_call_edit.is_none());
+
+        let update = parse_runtime_output(&event(json!({
+            "type": "tool_result",
+            "tool": "edit",
+            "tool_call_id": "edit-1",
+            "is_error": true,
+            "result": "Hash mismatch for src/a.rs. The file changed since it was read; read it again and retry with the new content_sha256."
+        })))
+        .tool_call_result
+        .expect("tool result update");
+        assert!(update.is_error && update.stale_read);
+    }
+
     #[test]
     fn webfetch_tool_result_uses_single_summary_line() {
         let payload = json!({
@@ -1967,6 +1843,44 @@ mod tests {
             .all(|line| line.kind() == LogKind::Shell));
     }
 
+    #[test]
+    fn shell_result_output_keeps_sgr_colors_only_when_passthrough_is_on() {
+        let payload = json!({
+            "jsonrpc": "2.0",
+            "method": "agent.event",
+            "params": {
+                "event": {
+                    "type": "tool_result",
+                    "tool": "shell_result",
+                    "tool_call_id": "shell-ansi-1",
+                    "is_error": false,
+                    "result": {
+                        "key": "shell-ansi-1",
+                        "command": "cargo test",
+                        "state": "completed",
+                        "exit_code": 0,
+                        "stdout": "test a ... \u{1b}[32mok\u{1b}[0m\u{1b}[1A"
+                    }
+                }
+            }
+        })
+        .to_string();
+        let stripped = parse_runtime_output(&payload);
+        assert_eq!(stripped.lines[0].spans().len(), 1);
+        assert_eq!(stripped.lines[0].plain_text(), "  test a ... ok");
+
+        set_ansi_color_passthrough(true);
+        let colored = parse_runtime_output(&payload);
+        set_ansi_color_passthrough(false);
+        let spans = colored.lines[0].spans();
+        assert_eq!(colored.lines[0].plain_text(), "  test a ... ok");
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].fg, None);
+        assert_eq!(spans[1].text, "ok");
+        assert!(spans[1].fg.is_some());
+        assert!(spans.iter().all(|span| span.kind == LogKind::Shell));
+    }
+
     #[test]
     fn shell_result_tool_result_preserves_failed_state() {
         let payload = json!({
@@ -2181,6 +2095,75 @@ mod tests {
             .any(|line| line.plain_text().contains("+ world")));
     }
 
+    #[test]
+    fn parse_runtime_output_summarizes_binary_image_write() {
+        let raw = r#"{"method":"agent.event","params":{"event":{"type":"tool_result","tool":"write","result":{"summary":"Wrote 13 bytes to dot.gif","diff":"--- /dev/null\n+++ dot.gif\n@@ -0,0 +1 @@\n+GIF89a\u0003\u0000\u0002\u0000\u0000\u0000;","file_path":"dot.gif"}}}}"#;
+        let parsed = parse_runtime_output(raw);
+        let texts = parsed
+            .lines
+            .iter()
+            .map(|line| line.plain_text())
+            .collect::<Vec<_>>();
+
+        assert_eq!(texts[1], "  binary file dot.gif (GIF image): added");
+        assert_eq!(texts[2], "  old: (none)");
+        assert!(texts[3].starts_with("  new: 13 B · 3×2 · #"));
+        assert_eq!(texts.len(), 4);
+    }
+
+    #[test]
+    fn permission_preview_summarizes_large_diff_by_hunk() {
+        let mut diff = String::from("--- a/big.rs\n+++ b/big.rs\n");
+        for hunk in 0..3 {
+            diff.push_str(&format!("@@ -{0},80 +{0},80 @@\n", hunk * 100 + 1));
+            for line in 0..80 {
+                diff.push_str(&format!("-old {hunk} {line}\n+new {hunk} {line}\n"));
+            }
+        }
+        let raw = serde_json::json!({
+            "method": "agent.event",
+            "params": {"event": {
+                "type": "permission.preview",
+                "tool": "edit",
+                "tool_call_id": "call-big",
+                "file_path": "big.rs",
+                "diff": diff,
+            }},
+        })
+        .to_string();
+        let parsed = parse_runtime_output(&raw);
+        let texts = parsed
+            .lines
+            .iter()
+            .map(|line| line.plain_text())
+            .collect::<Vec<_>>();
+
+        assert!(texts[2].starts_with("  Large diff: 3 hunks, +240 -240 lines"));
+        assert_eq!(texts[3], "  #1 big.rs @@ -1,80 +1,80 @@  +80 -80");
+        assert_eq!(texts.len(), 6);
+        let hunks = parsed
+            .permission_preview_update
+            .and_then(|update| update.large_hunks)
+            .expect("hunks");
+        assert_eq!(hunks.len(), 3);
+        assert_eq!(hunks[1].lines.len(), 160);
+    }
+
+    #[test]
+    fn permission_preview_summarizes_git_binary_diff() {
+        let raw = r#"{"method":"agent.event","params":{"event":{"type":"permission.preview","tool":"apply_patch","diff":"diff --git a/logo.png b/logo.png\nindex 1a2b3c4..5d6e7f8 100644\nBinary files a/logo.png and b/logo.png differ"}}}"#;
+        let parsed = parse_runtime_output(raw);
+        let texts = parsed
+            .lines
+            .iter()
+            .map(|line| line.plain_text())
+            .collect::<Vec<_>>();
+
+        assert!(texts.contains(&"  binary file logo.png: content changed".to_string()));
+        assert!(texts.contains(&"  old: #1a2b3c4".to_string()));
+        assert!(texts.contains(&"  new: #5d6e7f8".to_string()));
+    }
+
     #[test]
     fn parse_runtime_output_formats_apply_patch_tool_result_with_diff_body() {
         let raw = r#"{"method":"agent.event","params":{"event":{"type":"tool_result","tool":"apply_patch","result":{"summary":"Applied patch to 1 file(s)","diff":"--- a/demo.txt\n+++ b/demo.txt\n@@ -1 +1 @@\n-old line\n+new line"}}}}"#;
@@ -2464,10 +2447,11 @@ mod tests {
             .find(|line| line.plain_text().contains("+ new line"))
             .expect("added line");
 
-        assert_eq!(removed_line.spans()[1].fg, Some(DIFF_NUMBER_FG));
-        assert_eq!(added_line.spans()[1].fg, Some(DIFF_NUMBER_FG));
-        assert_eq!(removed_line.spans()[2].fg, Some(DIFF_REMOVED_MARKER_FG));
-        assert_eq!(added_line.spans()[2].fg, Some(DIFF_ADDED_MARKER_FG));
+        let palette = diff_palette();
+        assert_eq!(removed_line.spans()[1].fg, Some(palette.line_number));
+        assert_eq!(added_line.spans()[1].fg, Some(palette.line_number));
+        assert_eq!(removed_line.spans()[2].fg, Some(palette.removed_marker));
+        assert_eq!(added_line.spans()[2].fg, Some(palette.added_marker));
     }
 
     #[test]
@@ -2477,4 +2461,39 @@ mod tests {
         assert!(truncated);
         assert_eq!(lines.len(), 1);
     }
+
+    #[test]
+    fn schema_check_reports_missing_and_mistyped_fields() {
+        let raw = r#"{"method":"agent.event","params":{"event":{"type":"tool_call","tool":7,"args":{}}}}"#;
+        let mismatch = parse_runtime_output(raw)
+            .schema_mismatch
+            .expect("tool_call mismatch");
+        assert_eq!(mismatch.method, "agent.event/tool_call");
+        assert_eq!(
+            mismatch.problems,
+            vec![
+                "unexpected type for `params.event.tool`: expected string, got number",
+                "missing field `params.event.tool_call_id` (string)",
+            ]
+        );
+        assert_eq!(mismatch.raw, raw);
+
+        let parsed = parse_runtime_output(
+            r#"{"method":"run.context","params":{"context_left_percent":"40"}}"#,
+        );
+        assert_eq!(parsed.context_left_percent, None);
+        assert_eq!(
+            parsed.schema_mismatch.expect("run.context mismatch").problems,
+            vec!["unexpected type for `params.context_left_percent`: expected non-negative integer, got string"]
+        );
+
+        let well_formed =
+            r#"{"method":"run.status","params":{"run_id":"r1","status":"running","extra":1}}"#;
+        assert!(parse_runtime_output(well_formed).schema_mismatch.is_none());
+        assert!(
+            parse_runtime_output(r#"{"method":"future.notify","params":{}}"#)
+                .schema_mismatch
+                .is_none()
+        );
+    }
 }
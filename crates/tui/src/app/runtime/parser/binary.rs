@@ -0,0 +1,221 @@
+use crate::app::state::{LogKind, LogLine};
+
+use super::common::{detail_line, relative_or_basename, split_lines, DETAIL_INDENT};
+
+// Runtime diffs are built from UTF-8 decoded file content, so binary bytes arrive as U+FFFD or raw
+// control characters. Any of these in a content line means the diff is not worth showing as text.
+fn is_binary_text(text: &str) -> bool {
+    text.chars()
+        .any(|ch| ch == '\u{FFFD}' || (ch.is_control() && !matches!(ch, '\t' | '\r')))
+}
+
+#[derive(Default)]
+struct BinarySide {
+    path: Option<String>,
+    absent: bool,
+    bytes: Vec<u8>,
+    has_content: bool,
+    lossy: bool,
+    index_hash: Option<String>,
+}
+
+impl BinarySide {
+    fn push_content(&mut self, text: &str) {
+        if self.has_content {
+            self.bytes.push(b'\n');
+        }
+        self.bytes.extend_from_slice(text.as_bytes());
+        self.has_content = true;
+        self.lossy |= text.contains('\u{FFFD}');
+    }
+}
+
+fn header_path(raw: &str) -> (Option<String>, bool) {
+    let path = raw
+        .split('\t')
+        .next()
+        .unwrap_or(raw)
+        .trim()
+        .trim_matches('"');
+    if path == "/dev/null" {
+        return (None, true);
+    }
+    let path = path
+        .strip_prefix("a/")
+        .or_else(|| path.strip_prefix("b/"))
+        .unwrap_or(path);
+    (Some(path.to_string()), false)
+}
+
+/// `Binary files a/x and b/y differ` as printed by git and diff(1).
+fn parse_binary_files_line(line: &str) -> Option<(&str, &str)> {
+    let rest = line
+        .strip_prefix("Binary files ")?
+        .strip_suffix(" differ")?;
+    rest.split_once(" and ")
+}
+
+struct BinaryDiff {
+    old: BinarySide,
+    new: BinarySide,
+}
+
+fn parse_binary_diff(diff: &str) -> Option<BinaryDiff> {
+    let mut old = BinarySide::default();
+    let mut new = BinarySide::default();
+    let mut binary = false;
+    for line in split_lines(diff) {
+        if let Some(raw) = line.strip_prefix("--- ") {
+            (old.path, old.absent) = header_path(raw);
+        } else if let Some(raw) = line.strip_prefix("+++ ") {
+            (new.path, new.absent) = header_path(raw);
+        } else if let Some((old_raw, new_raw)) = parse_binary_files_line(&line) {
+            (old.path, old.absent) = header_path(old_raw);
+            (new.path, new.absent) = header_path(new_raw);
+            binary = true;
+        } else if line == "GIT binary patch" {
+            binary = true;
+        } else if let Some(range) = line.strip_prefix("index ") {
+            let range = range.split_whitespace().next().unwrap_or_default();
+            if let Some((from, to)) = range.split_once("..") {
+                old.index_hash = Some(from.to_string());
+                new.index_hash = Some(to.to_string());
+            }
+        } else if line.starts_with("@@") {
+            continue;
+        } else if let Some(text) = line.strip_prefix('-') {
+            binary |= is_binary_text(text);
+            old.push_content(text);
+        } else if let Some(text) = line.strip_prefix('+') {
+            binary |= is_binary_text(text);
+            new.push_content(text);
+        } else if let Some(text) = line.strip_prefix(' ') {
+            binary |= is_binary_text(text);
+            old.push_content(text);
+            new.push_content(text);
+        }
+    }
+    binary.then_some(BinaryDiff { old, new })
+}
+
+/// Sniffs the format from magic bytes; the leading non-ASCII byte of PNG/JPEG is often lost to
+/// U+FFFD, so those are matched by the ASCII tag that follows.
+fn sniff_format(bytes: &[u8]) -> Option<&'static str> {
+    let head = &bytes[..bytes.len().min(32)];
+    let contains = |tag: &[u8]| head.windows(tag.len()).any(|window| window == tag);
+    if head.starts_with(b"GIF87a") || head.starts_with(b"GIF89a") {
+        Some("GIF image")
+    } else if contains(b"PNG\r\n") || contains(b"IHDR") {
+        Some("PNG image")
+    } else if head.starts_with(b"RIFF") && contains(b"WEBP") {
+        Some("WebP image")
+    } else if contains(b"JFIF") || contains(b"Exif") {
+        Some("JPEG image")
+    } else if head.starts_with(b"BM") {
+        Some("BMP image")
+    } else if head.starts_with(b"%PDF") {
+        Some("PDF document")
+    } else if head.starts_with(b"PK\x03\x04") {
+        Some("zip archive")
+    } else {
+        None
+    }
+}
+
+/// Width and height from PNG/GIF headers; `None` when the header bytes did not survive decoding.
+fn image_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        let header = bytes.get(6..10)?;
+        let width = u16::from_le_bytes([header[0], header[1]]);
+        let height = u16::from_le_bytes([header[2], header[3]]);
+        return Some((u32::from(width), u32::from(height)));
+    }
+    let ihdr = bytes.windows(4).position(|window| window == b"IHDR")?;
+    let header = bytes.get(ihdr + 4..ihdr + 12)?;
+    let width = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+    let height = u32::from_be_bytes([header[4], header[5], header[6], header[7]]);
+    Some((width, height))
+}
+
+/// FNV-1a: stable across runs, which is all a "did it change" marker needs.
+fn content_hash(bytes: &[u8]) -> String {
+    let hash = bytes.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:08x}", hash >> 32)
+}
+
+fn format_size(bytes: usize) -> String {
+    if bytes < 1024 {
+        format!("{bytes} B")
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+fn side_hash(side: &BinarySide) -> Option<String> {
+    side.index_hash
+        .clone()
+        .or_else(|| side.has_content.then(|| content_hash(&side.bytes)))
+}
+
+fn side_line(label: &str, side: &BinarySide) -> LogLine {
+    if side.absent {
+        return detail_line(LogKind::DiffMeta, format!("{DETAIL_INDENT}{label}: (none)"));
+    }
+    let mut parts = Vec::new();
+    if side.has_content {
+        let approx = if side.lossy { "≈" } else { "" };
+        parts.push(format!("{approx}{}", format_size(side.bytes.len())));
+        if let Some((width, height)) = image_dimensions(&side.bytes) {
+            parts.push(format!("{width}×{height}"));
+        }
+    }
+    if let Some(hash) = side_hash(side) {
+        parts.push(format!("#{hash}"));
+    }
+    let facts = if parts.is_empty() {
+        "-".to_string()
+    } else {
+        parts.join(" · ")
+    };
+    let kind = if label == "old" {
+        LogKind::DiffRemoved
+    } else {
+        LogKind::DiffAdded
+    };
+    detail_line(kind, format!("{DETAIL_INDENT}{label}: {facts}"))
+}
+
+/// Replaces a binary or image diff with path, format, per-side size/dimensions, and hash change.
+pub(super) fn binary_diff_summary_lines(
+    diff: &str,
+    file_path: Option<&str>,
+) -> Option<Vec<LogLine>> {
+    let BinaryDiff { old, new } = parse_binary_diff(diff)?;
+    let path = file_path
+        .map(str::to_string)
+        .or_else(|| new.path.clone())
+        .or_else(|| old.path.clone())
+        .map(|path| relative_or_basename(&path))
+        .unwrap_or_else(|| "(unknown file)".to_string());
+    let format = sniff_format(&new.bytes).or_else(|| sniff_format(&old.bytes));
+    let header = match format {
+        Some(format) => format!("{DETAIL_INDENT}binary file {path} ({format})"),
+        None => format!("{DETAIL_INDENT}binary file {path}"),
+    };
+    let change = match (side_hash(&old), side_hash(&new)) {
+        _ if old.absent => "added",
+        _ if new.absent => "deleted",
+        (Some(old_hash), Some(new_hash)) if old_hash == new_hash => "content unchanged",
+        (Some(_), Some(_)) => "content changed",
+        _ => "content differs",
+    };
+    Some(vec![
+        detail_line(LogKind::DiffMeta, format!("{header}: {change}")),
+        side_line("old", &old),
+        side_line("new", &new),
+    ])
+}
@@ -1,4 +1,4 @@
-use crate::app::state::{LogKind, LogLine, LogTone};
+use crate::app::state::{ansi_color_passthrough, LogKind, LogLine, LogTone};
 use std::path::Path;
 
 pub(super) const DETAIL_INDENT: &str = "  ";
@@ -125,6 +125,7 @@ pub(super) fn prefix_block(
             prefix.trim_end().to_string(),
         )];
     }
+    let keep_colors = ansi_color_passthrough();
     let mut out = Vec::new();
     for (idx, line) in lines.into_iter().enumerate() {
         let full = if idx == 0 {
@@ -132,7 +133,11 @@ pub(super) fn prefix_block(
         } else {
             format!("{indent}{line}")
         };
-        out.push(LogLine::new_with_tone(kind, tone, full));
+        out.push(if keep_colors {
+            LogLine::new_with_ansi_colors(kind, tone, &full)
+        } else {
+            LogLine::new_with_tone(kind, tone, full)
+        });
     }
     out
 }
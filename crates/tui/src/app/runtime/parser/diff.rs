@@ -1,15 +1,15 @@
 use crate::app::markdown::highlight_code_line;
-use crate::app::state::{LogColor, LogKind, LogLine, LogSpan, LogTone};
+use crate::app::state::{LogKind, LogLine, LogSpan, LogTone, ReviewHunk};
+use crate::app::theme::diff_palette;
 use similar::{ChangeTag, TextDiff};
 use std::path::Path;
 
+use super::binary::binary_diff_summary_lines;
 use super::common::{detail_line, prefix_block, split_lines, summary_line, DETAIL_INDENT};
 
 pub(super) const MAX_DIFF_LINES: usize = 200;
-
-pub(super) const DIFF_NUMBER_FG: LogColor = LogColor::rgb(143, 161, 179);
-pub(super) const DIFF_ADDED_MARKER_FG: LogColor = LogColor::rgb(163, 190, 140);
-pub(super) const DIFF_REMOVED_MARKER_FG: LogColor = LogColor::rgb(191, 97, 106);
+const LARGE_PREVIEW_DIFF_BYTES: usize = 64 * 1024;
+const MAX_LISTED_HUNKS: usize = 40;
 
 fn env_flag(name: &str) -> bool {
     matches!(
@@ -211,9 +211,10 @@ fn diff_content_line(
     } else {
         format!("{marker} ")
     };
+    let palette = diff_palette();
     let marker_fg = match kind {
-        LogKind::DiffAdded => Some(DIFF_ADDED_MARKER_FG),
-        LogKind::DiffRemoved => Some(DIFF_REMOVED_MARKER_FG),
+        LogKind::DiffAdded => Some(palette.added_marker),
+        LogKind::DiffRemoved => Some(palette.removed_marker),
         _ => None,
     };
 
@@ -228,7 +229,7 @@ fn diff_content_line(
                 number_kind,
                 LogTone::Detail,
                 number_prefix,
-                Some(DIFF_NUMBER_FG),
+                Some(palette.line_number),
             ),
             LogSpan::new_with_fg(kind, LogTone::Detail, marker_text, marker_fg),
             LogSpan::new(kind, LogTone::Detail, text),
@@ -245,7 +246,7 @@ fn diff_content_line(
             row_kind,
             LogTone::Detail,
             number_prefix,
-            Some(DIFF_NUMBER_FG),
+            Some(palette.line_number),
         ),
         LogSpan::new_with_fg(row_kind, LogTone::Detail, marker_text, marker_fg),
     ];
@@ -528,6 +529,124 @@ pub(super) fn limited_edit_diff_lines_with_hint(
     (limited, true)
 }
 
+fn diff_header_path(line: &str) -> Option<String> {
+    let raw = line.get(4..)?.split('\t').next()?.trim();
+    let path = raw.strip_prefix("b/").unwrap_or(raw);
+    (path != "/dev/null").then(|| path.to_string())
+}
+
+/// Splits a unified diff into hunks; each keeps its file headers so it renders on its own.
+fn split_diff_hunks(diff: &str, fallback_language: Option<&str>) -> Vec<ReviewHunk> {
+    let mut hunks = Vec::new();
+    let mut file_headers: Vec<String> = Vec::new();
+    let mut file: Option<String> = None;
+    let mut current: Option<(String, Vec<String>, usize, usize)> = None;
+    let finish = |current: &mut Option<(String, Vec<String>, usize, usize)>,
+                  hunks: &mut Vec<ReviewHunk>| {
+        if let Some((label, body, added, removed)) = current.take() {
+            hunks.push(ReviewHunk {
+                label,
+                added,
+                removed,
+                lines: render_edit_diff_lines(&body.join("\n"), fallback_language),
+                decision: None,
+            });
+        }
+    };
+    let lines = split_lines(diff);
+    for (index, line) in lines.iter().cloned().enumerate() {
+        // `--- x` directly followed by `+++ y` starts a new file; otherwise it is a removed row.
+        let next_is_new_header = lines
+            .get(index + 1)
+            .is_some_and(|next| next.starts_with("+++ "));
+        if line.starts_with("--- ") && next_is_new_header {
+            finish(&mut current, &mut hunks);
+            file_headers = vec![line];
+            file = None;
+            continue;
+        }
+        if line.starts_with("+++ ") && current.is_none() {
+            file = diff_header_path(&line);
+            file_headers.push(line);
+            continue;
+        }
+        if line.starts_with("@@") {
+            finish(&mut current, &mut hunks);
+            let label = match file.as_deref() {
+                Some(file) => format!("{file} {line}"),
+                None => line.clone(),
+            };
+            let mut body = file_headers.clone();
+            body.push(line);
+            current = Some((label, body, 0, 0));
+            continue;
+        }
+        let Some((_, body, added, removed)) = current.as_mut() else {
+            continue;
+        };
+        if line.starts_with('+') {
+            *added += 1;
+        } else if line.starts_with('-') {
+            *removed += 1;
+        }
+        body.push(line);
+    }
+    finish(&mut current, &mut hunks);
+    hunks
+}
+
+/// Hunks for a permission preview too large to show inline (`MAX_DIFF_LINES` rows or 64 KiB).
+pub(super) fn large_preview_hunks(
+    diff: &str,
+    fallback_language: Option<&str>,
+) -> Option<Vec<ReviewHunk>> {
+    if !looks_like_unified_diff(diff) {
+        return None;
+    }
+    let large = diff.len() > LARGE_PREVIEW_DIFF_BYTES
+        || render_edit_diff_lines(diff, fallback_language).len() > MAX_DIFF_LINES;
+    if !large {
+        return None;
+    }
+    let hunks = split_diff_hunks(diff, fallback_language);
+    (!hunks.is_empty()).then_some(hunks)
+}
+
+fn hunk_summary_lines(hunks: &[ReviewHunk], diff_bytes: usize) -> Vec<LogLine> {
+    let added = hunks.iter().map(|hunk| hunk.added).sum::<usize>();
+    let removed = hunks.iter().map(|hunk| hunk.removed).sum::<usize>();
+    let mut lines = vec![detail_line(
+        LogKind::DiffMeta,
+        format!(
+            "{DETAIL_INDENT}Large diff: {} hunks, +{added} -{removed} lines ({:.1} KiB); press H in the confirm dialog to review hunks",
+            hunks.len(),
+            diff_bytes as f64 / 1024.0
+        ),
+    )];
+    for (index, hunk) in hunks.iter().take(MAX_LISTED_HUNKS).enumerate() {
+        lines.push(detail_line(
+            LogKind::DiffMeta,
+            format!(
+                "{DETAIL_INDENT}#{} {}  +{} -{}",
+                index + 1,
+                hunk.label,
+                hunk.added,
+                hunk.removed
+            ),
+        ));
+    }
+    if hunks.len() > MAX_LISTED_HUNKS {
+        lines.push(detail_line(
+            LogKind::DiffMeta,
+            format!(
+                "{DETAIL_INDENT}... {} more hunks",
+                hunks.len() - MAX_LISTED_HUNKS
+            ),
+        ));
+    }
+    lines
+}
+
 pub(super) fn looks_like_unified_diff(value: &str) -> bool {
     let mut has_old_header = false;
     let mut has_new_header = false;
@@ -551,6 +670,7 @@ pub(super) fn permission_preview_lines(
     truncated_hint: bool,
     file_path: Option<&str>,
     language: Option<&str>,
+    large_hunks: Option<&[ReviewHunk]>,
 ) -> Vec<LogLine> {
     let mut lines = vec![
         LogLine::new(LogKind::Space, ""),
@@ -570,9 +690,23 @@ pub(super) fn permission_preview_lines(
         append_permission_preview_debug_line(&mut lines, None, file_path);
         return lines;
     }
+    if let Some(mut binary_lines) = binary_diff_summary_lines(diff_text, file_path) {
+        lines.append(&mut binary_lines);
+        return lines;
+    }
     let resolved_language = language
         .and_then(normalize_language_hint)
         .or_else(|| file_path.and_then(language_from_path));
+    if let Some(hunks) = large_hunks {
+        lines.extend(hunk_summary_lines(hunks, diff_text.len()));
+        if truncated_hint {
+            lines.push(detail_line(
+                LogKind::DiffMeta,
+                format!("{DETAIL_INDENT}..."),
+            ));
+        }
+        return lines;
+    }
     if !diff_text.trim().is_empty() && looks_like_unified_diff(diff_text) {
         let (mut diff_lines, truncated) = limited_edit_diff_lines_with_hint(
             diff_text,
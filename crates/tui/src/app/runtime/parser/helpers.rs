@@ -2,6 +2,7 @@ use crate::app::state::{LogKind, LogLine, LogSpan, LogTone};
 use serde_json::Value;
 
 use super::agents::tool_result_lines as agents_resolve_tool_result_lines;
+use super::binary::binary_diff_summary_lines;
 use super::common::{
     detail_line, prefix_block, redact_ref_markers, relative_or_basename, short_id, split_lines,
     summary_line, truncate_line, ToolCallSummary, DETAIL_INDENT,
@@ -19,6 +20,7 @@ use super::todo::{
     is_todo_mutation_tool, result_is_error as todo_result_is_error,
     tool_result_lines as todo_tool_result_lines,
 };
+use super::types::EditCallArgs;
 use super::web::{
     web_search_queries_from_value, web_search_summary_detail, web_search_summary_from_result,
     webfetch_summary_detail, webfetch_summary_from_result,
@@ -31,6 +33,7 @@ const DEFAULT_PREVIEW_LINES: usize = 3;
 const MAX_WRITE_DIFF_LINES: usize = 30;
 const MAX_ARG_LENGTH: usize = 160;
 const MAX_HEADER_LENGTH: usize = 200;
+const MAX_RESULT_IMAGES: usize = 4;
 fn limit_lines(lines: Vec<String>, max: usize) -> (Vec<String>, bool) {
     if lines.len() <= max {
         return (lines, false);
@@ -96,6 +99,7 @@ fn is_builtin_tool(tool: &str) -> bool {
             | "lane_create"
             | "lane_list"
             | "lane_status"
+            | "lane_tail"
             | "lane_close"
             | "lane_gc"
             | "search"
@@ -126,6 +130,7 @@ fn tool_display_name(tool: &str) -> String {
             "lane_create" => "LaneCreate".to_string(),
             "lane_list" => "LaneList".to_string(),
             "lane_status" => "LaneStatus".to_string(),
+            "lane_tail" => "LaneTail".to_string(),
             "lane_close" => "LaneClose".to_string(),
             "lane_gc" => "LaneGc".to_string(),
             _ => {
@@ -245,6 +250,16 @@ pub(super) fn summarize_tool_call(tool: &str, args: &Value) -> ToolCallSummary {
             detail: format!("lane={}", short_id(lane_id)),
         };
     }
+    if tool == "lane_tail" {
+        let lane_id = obj
+            .and_then(|value| value.get("lane_id"))
+            .and_then(|value| value.as_str())
+            .unwrap_or("");
+        return ToolCallSummary {
+            label: "LaneTail:".to_string(),
+            detail: format!("lane={}", short_id(lane_id)),
+        };
+    }
     if tool == "lane_close" {
         let lane_id = obj
             .and_then(|value| value.get("lane_id"))
@@ -422,6 +437,69 @@ pub(super) fn looks_like_error(tool: &str, text: &str, is_error: bool) -> bool {
     false
 }
 
+fn collect_result_images(value: &Value, out: &mut Vec<String>) {
+    if out.len() >= MAX_RESULT_IMAGES {
+        return;
+    }
+    match value {
+        Value::Array(items) => items
+            .iter()
+            .for_each(|item| collect_result_images(item, out)),
+        Value::Object(map) => match map.get("type").and_then(Value::as_str) {
+            Some("image_url") => {
+                if let Some(url) = map
+                    .get("image_url")
+                    .and_then(|image| image.get("url"))
+                    .and_then(Value::as_str)
+                    .filter(|url| url.starts_with("data:image/"))
+                {
+                    out.push(url.to_string());
+                }
+            }
+            Some("image") => {
+                let media_type = map
+                    .get("mimeType")
+                    .or_else(|| map.get("media_type"))
+                    .and_then(Value::as_str)
+                    .unwrap_or("image/png");
+                if let Some(data) = map.get("data").and_then(Value::as_str) {
+                    out.push(format!("data:{media_type};base64,{data}"));
+                }
+            }
+            _ => map
+                .values()
+                .for_each(|item| collect_result_images(item, out)),
+        },
+        _ => {}
+    }
+}
+
+/// Substring of the runtime's content-hash mismatch error for edits of a changed file.
+pub(super) const STALE_READ_MARKER: &str = "changed since it was read";
+
+/// File path and strings of a single-occurrence `edit` call; `replace_all` edits and file
+/// creations (empty `old_string`) cannot be merged region by region.
+pub(super) fn edit_call_args(tool: &str, args: &Value) -> Option<EditCallArgs> {
+    if tool != "edit" || args.get("replace_all").and_then(|v| v.as_bool()) == Some(true) {
+        return None;
+    }
+    let field = |key: &str| args.get(key).and_then(|v| v.as_str()).map(str::to_string);
+    let old_string = field("old_string").filter(|value| !value.is_empty())?;
+    Some(EditCallArgs {
+        file_path: field("file_path")?,
+        old_string,
+        new_string: field("new_string").unwrap_or_default(),
+    })
+}
+
+/// Inline images of a tool result as data URLs: `image_url` content parts or MCP-style
+/// `{type: "image", data, mimeType}` blocks, first few only.
+pub(super) fn tool_result_images(result: &Value) -> Vec<String> {
+    let mut images = Vec::new();
+    collect_result_images(result, &mut images);
+    images
+}
+
 pub(super) struct ToolResultRender {
     pub(super) lines: Vec<LogLine>,
     pub(super) edit_diff_fingerprint: Option<String>,
@@ -491,7 +569,10 @@ pub(super) fn tool_result_lines(tool: &str, raw: &str, is_error: bool) -> ToolRe
                     .and_then(normalize_language_hint)
                     .or_else(|| file_path.and_then(language_from_path));
                 if let Some(diff_text) = diff_fingerprint.as_deref() {
-                    if looks_like_unified_diff(diff_text) {
+                    if let Some(mut binary_lines) = binary_diff_summary_lines(diff_text, file_path)
+                    {
+                        lines.append(&mut binary_lines);
+                    } else if looks_like_unified_diff(diff_text) {
                         let max_diff_lines = if tool == "write" {
                             MAX_WRITE_DIFF_LINES
                         } else {
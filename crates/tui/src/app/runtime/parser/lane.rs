@@ -82,7 +82,7 @@ pub(super) fn tool_result_lines(
 ) -> Option<Vec<LogLine>> {
     if !matches!(
         tool,
-        "lane_create" | "lane_status" | "lane_close" | "lane_list" | "lane_gc"
+        "lane_create" | "lane_status" | "lane_tail" | "lane_close" | "lane_list" | "lane_gc"
     ) {
         return None;
     }
@@ -118,6 +118,19 @@ pub(super) fn tool_result_lines(
                 lines.push(detail_line(kind, format!("{DETAIL_INDENT}{detail}")));
             }
         }
+        "lane_tail" => {
+            let lane = parsed.get("lane")?;
+            let count = parsed
+                .get("lines")
+                .and_then(|value| value.as_array())
+                .map_or(0, Vec::len);
+            let state = lane_summary_status(lane);
+            lines.push(summary_line(
+                icon,
+                format!("lane tail: {count} lines ({state})"),
+                kind,
+            ));
+        }
         "lane_close" => {
             let lane = parsed.get("lane")?;
             lines.push(summary_line(icon, "lane closed", kind));
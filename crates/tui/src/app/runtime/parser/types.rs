@@ -1,12 +1,19 @@
-use crate::app::state::LogLine;
-use serde_json::Value;
+use crate::app::state::{LogLine, ReviewHunk};
+pub use codelia_protocol::{
+    ClientToolRequest, RpcResponse, RunUsage, SchemaMismatch, UiConfirmRequest, UiPickRequest,
+    UiPromptRequest,
+};
 
 pub struct ParsedOutput {
     pub lines: Vec<LogLine>,
     pub status: Option<String>,
     pub status_run_id: Option<String>,
     pub context_left_percent: Option<u8>,
+    /// `run.usage` of one LLM call.
+    pub run_usage: Option<RunUsage>,
     pub assistant_text: Option<String>,
+    /// `text_delta` chunk of the assistant message still being streamed.
+    pub assistant_delta: Option<String>,
     pub final_text: Option<String>,
     pub rpc_response: Option<RpcResponse>,
     pub confirm_request: Option<UiConfirmRequest>,
@@ -14,11 +21,18 @@ pub struct ParsedOutput {
     pub pick_request: Option<UiPickRequest>,
     pub client_tool_request: Option<ClientToolRequest>,
     pub tool_call_start_id: Option<String>,
+    /// Tool name of the `tool_call` event, used to apply per-tool styles.
+    pub tool_call_start_tool: Option<String>,
+    /// Arguments of an `edit` call, kept to offer a merge if it fails on a stale read.
+    pub tool_call_edit: Option<EditCallArgs>,
+    /// `command` of a `shell` call, for the split pane's live output.
+    pub tool_call_command: Option<String>,
     pub tool_call_result: Option<ToolCallResultUpdate>,
     pub compaction_started: bool,
     pub compaction_completed: bool,
     pub permission_preview_update: Option<PermissionPreviewUpdate>,
     pub permission_ready_update: Option<PermissionReadyUpdate>,
+    pub schema_mismatch: Option<SchemaMismatch>,
 }
 
 impl ParsedOutput {
@@ -28,7 +42,9 @@ impl ParsedOutput {
             status: None,
             status_run_id: None,
             context_left_percent: None,
+            run_usage: None,
             assistant_text: None,
+            assistant_delta: None,
             final_text: None,
             rpc_response: None,
             confirm_request: None,
@@ -36,11 +52,15 @@ impl ParsedOutput {
             pick_request: None,
             client_tool_request: None,
             tool_call_start_id: None,
+            tool_call_start_tool: None,
+            tool_call_edit: None,
+            tool_call_command: None,
             tool_call_result: None,
             compaction_started: false,
             compaction_completed: false,
             permission_preview_update: None,
             permission_ready_update: None,
+            schema_mismatch: None,
         }
     }
 }
@@ -51,60 +71,34 @@ pub struct ToolCallResultUpdate {
     pub is_error: bool,
     pub fallback_summary: LogLine,
     pub edit_diff_fingerprint: Option<String>,
+    /// Tool output cache ref of the full result, when the runtime persisted it.
+    pub output_ref_id: Option<String>,
+    /// Data URLs of images in the result, previewed under it.
+    pub images: Vec<String>,
+    /// The file changed on disk after the tool read it (content hash mismatch).
+    pub stale_read: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EditCallArgs {
+    pub file_path: String,
+    pub old_string: String,
+    pub new_string: String,
 }
 
 pub struct PermissionPreviewUpdate {
     pub tool_call_id: String,
+    pub tool: String,
+    /// File path or one-line summary, used to list dry-run plan steps.
+    pub target: Option<String>,
     pub has_diff: bool,
     pub truncated: bool,
     pub diff_fingerprint: Option<String>,
+    /// Set when the diff was summarized by hunk instead of shown inline.
+    pub large_hunks: Option<Vec<ReviewHunk>>,
 }
 
 pub struct PermissionReadyUpdate {
     pub tool_call_id: String,
-}
-
-pub struct RpcResponse {
-    pub id: String,
-    pub result: Option<Value>,
-    pub error: Option<Value>,
-}
-
-pub struct UiConfirmRequest {
-    pub id: String,
-    pub title: String,
-    pub message: String,
-    pub danger_level: Option<String>,
-    pub confirm_label: Option<String>,
-    pub cancel_label: Option<String>,
-    pub allow_remember: bool,
-    pub allow_reason: bool,
-}
-
-pub struct UiPromptRequest {
-    pub id: String,
-    pub title: String,
-    pub message: String,
-    pub default_value: Option<String>,
-    pub multiline: bool,
-    pub secret: bool,
-}
-
-pub struct UiPickItem {
-    pub id: String,
-    pub label: String,
-    pub detail: Option<String>,
-}
-
-pub struct UiPickRequest {
-    pub id: String,
-    pub title: String,
-    pub items: Vec<UiPickItem>,
-    pub multi: bool,
-}
-
-pub struct ClientToolRequest {
-    pub id: String,
-    pub name: String,
-    pub arguments: Value,
+    pub tool: String,
 }
@@ -0,0 +1,219 @@
+use crate::app::state::LogKind;
+use crate::app::AppState;
+use std::process::ExitStatus;
+use std::time::{Duration, Instant};
+
+/// Restarts allowed before the TUI gives up on a crashing runtime.
+pub(crate) const MAX_RECONNECT_ATTEMPTS: u32 = 5;
+const FIRST_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+/// A runtime that stays up this long resets the attempt count.
+const STABLE_RUNTIME_AFTER: Duration = Duration::from_secs(60);
+
+/// Restart bookkeeping for the runtime child process of one tab.
+#[derive(Debug, Default)]
+pub struct RuntimeReconnect {
+    /// Restarts since the runtime last stayed up for `STABLE_RUNTIME_AFTER`.
+    pub attempt: u32,
+    /// Set while the runtime is down and a restart is scheduled.
+    pub next_attempt_at: Option<Instant>,
+    /// When the current runtime was (re)started by this manager.
+    pub connected_at: Option<Instant>,
+}
+
+/// 1s, 2s, 4s, … capped at 30s.
+pub(crate) fn reconnect_delay(attempt: u32) -> Duration {
+    FIRST_RECONNECT_DELAY
+        .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+        .min(MAX_RECONNECT_DELAY)
+}
+
+/// Exit code 0 is a deliberate shutdown; anything else (including signals) is a crash.
+pub(crate) fn is_abnormal_exit(status: &ExitStatus) -> bool {
+    !status.success()
+}
+
+impl RuntimeReconnect {
+    pub(crate) fn reconnecting(&self) -> bool {
+        self.next_attempt_at.is_some()
+    }
+
+    pub(crate) fn due(&self, now: Instant) -> bool {
+        self.next_attempt_at.is_some_and(|at| now >= at)
+    }
+
+    /// Schedules the next restart; `None` once `MAX_RECONNECT_ATTEMPTS` are used up.
+    pub(crate) fn schedule(&mut self, now: Instant) -> Option<Duration> {
+        if self
+            .connected_at
+            .is_some_and(|at| now.saturating_duration_since(at) >= STABLE_RUNTIME_AFTER)
+        {
+            self.attempt = 0;
+        }
+        self.connected_at = None;
+        if self.attempt >= MAX_RECONNECT_ATTEMPTS {
+            self.next_attempt_at = None;
+            return None;
+        }
+        self.attempt += 1;
+        let delay = reconnect_delay(self.attempt);
+        self.next_attempt_at = Some(now + delay);
+        Some(delay)
+    }
+
+    pub(crate) fn mark_connected(&mut self, now: Instant) {
+        self.next_attempt_at = None;
+        self.connected_at = Some(now);
+    }
+
+    pub(crate) fn status_segment(&self) -> Option<String> {
+        self.reconnecting().then(|| {
+            format!(
+                "⟳ runtime reconnecting ({}/{MAX_RECONNECT_ATTEMPTS})",
+                self.attempt
+            )
+        })
+    }
+}
+
+/// After an abnormal exit: drops state tied to the dead process (active run, confirm
+/// dialogs, pending RPC ids), puts an undelivered prompt back at the front of the queue,
+/// and schedules a restart. False when the attempts are used up.
+pub(crate) fn begin_runtime_reconnect(app: &mut AppState, status: &str, now: Instant) -> bool {
+    if app.is_running() {
+        app.update_run_status("error".to_string());
+    }
+    app.runtime_info.active_run_id = None;
+    app.active_tool = None;
+    app.confirm_dialog = None;
+    app.pending_confirm_dialog = None;
+    app.rpc_pending = Default::default();
+    app.connection_health = Default::default();
+    if let Some(prompt) = app.dispatching_prompt.take() {
+        app.pending_prompt_queue.push_front(prompt);
+    }
+    let remote = app
+        .runtime_info
+        .launch
+        .as_ref()
+        .and_then(|launch| launch.ssh_host())
+        .map(str::to_string);
+    let Some(delay) = app.runtime_reconnect.schedule(now) else {
+        let message = match remote {
+            Some(host) => format!(
+                "remote connection to {host} lost ({status}); giving up after {MAX_RECONNECT_ATTEMPTS} reconnects"
+            ),
+            None => format!(
+                "runtime exited unexpectedly ({status}); giving up after {MAX_RECONNECT_ATTEMPTS} restarts"
+            ),
+        };
+        app.push_line(LogKind::Error, message);
+        return false;
+    };
+    let attempt = app.runtime_reconnect.attempt;
+    let message = match remote {
+        Some(host) => format!(
+            "remote connection to {host} lost ({status}) — attempting reconnect in {}s (attempt {attempt}/{MAX_RECONNECT_ATTEMPTS})",
+            delay.as_secs()
+        ),
+        None => format!(
+            "runtime exited unexpectedly ({status}); restarting in {}s (attempt {attempt}/{MAX_RECONNECT_ATTEMPTS})",
+            delay.as_secs()
+        ),
+    };
+    app.push_line(LogKind::Error, message);
+    true
+}
+
+/// Log line for a successful restart: the session and queue carry over.
+pub(crate) fn finish_runtime_reconnect(app: &mut AppState, pid: u32, now: Instant) {
+    app.runtime_reconnect.mark_connected(now);
+    let session = app
+        .runtime_info
+        .session_id
+        .as_deref()
+        .map(|id| {
+            format!(
+                "; session {} continues with the next prompt",
+                id.chars().take(8).collect::<String>()
+            )
+        })
+        .unwrap_or_default();
+    let queued = match app.pending_prompt_queue.len() {
+        0 => String::new(),
+        count => format!("; replaying {count} queued prompt(s)"),
+    };
+    app.push_line(
+        LogKind::Runtime,
+        format!("Runtime restarted (pid {pid}){session}{queued}"),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        begin_runtime_reconnect, reconnect_delay, RuntimeReconnect, MAX_RECONNECT_ATTEMPTS,
+    };
+    use crate::app::{AppState, PendingPromptRun, QueuedAttachments};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn restarts_back_off_and_give_up_unless_the_runtime_stabilizes() {
+        assert_eq!(reconnect_delay(1), Duration::from_secs(1));
+        assert_eq!(reconnect_delay(3), Duration::from_secs(4));
+        assert_eq!(reconnect_delay(9), Duration::from_secs(30));
+
+        let now = Instant::now();
+        let mut reconnect = RuntimeReconnect::default();
+        for attempt in 1..=MAX_RECONNECT_ATTEMPTS {
+            assert_eq!(reconnect.schedule(now), Some(reconnect_delay(attempt)));
+            assert!(!reconnect.due(now));
+            reconnect.mark_connected(now);
+        }
+        assert_eq!(reconnect.schedule(now), None);
+        assert!(!reconnect.reconnecting());
+
+        reconnect.mark_connected(now);
+        let later = now + Duration::from_secs(61);
+        assert_eq!(reconnect.schedule(later), Some(Duration::from_secs(1)));
+        assert!(reconnect.due(later + Duration::from_secs(1)));
+        assert_eq!(
+            reconnect.status_segment().as_deref(),
+            Some("⟳ runtime reconnecting (1/5)")
+        );
+    }
+
+    #[test]
+    fn crash_requeues_the_undelivered_prompt_and_clears_the_run() {
+        let mut app = AppState::default();
+        app.update_run_status("running".to_string());
+        app.runtime_info.active_run_id = Some("run-1".to_string());
+        app.dispatching_prompt = Some(PendingPromptRun {
+            queue_id: "q7".to_string(),
+            queued_at: Instant::now(),
+            preview: "hello".to_string(),
+            user_text: "hello".to_string(),
+            input_payload: serde_json::json!({ "type": "text", "text": "hello" }),
+            attachment_count: 0,
+            shell_result_count: 0,
+            dispatch_attempts: 1,
+            dry_run: false,
+            json_request: None,
+            attachments: QueuedAttachments::default(),
+        });
+        assert!(begin_runtime_reconnect(
+            &mut app,
+            "signal: 9",
+            Instant::now()
+        ));
+        assert!(!app.is_running());
+        assert!(app.runtime_info.active_run_id.is_none());
+        assert_eq!(
+            app.pending_prompt_queue
+                .front()
+                .map(|prompt| prompt.queue_id.as_str()),
+            Some("q7")
+        );
+        assert!(app.runtime_reconnect.reconnecting());
+    }
+}
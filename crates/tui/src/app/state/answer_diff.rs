@@ -0,0 +1,120 @@
+use similar::{capture_diff_slices, Algorithm, DiffOp};
+use unicode_width::UnicodeWidthChar;
+
+/// `/regen`: the answer being replaced and, once the rerun finishes, its alternative.
+#[derive(Debug, Default)]
+pub(crate) struct RegenCandidates {
+    pub original: String,
+    pub alternative: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AnswerDiffLayout {
+    Unified,
+    SideBySide,
+}
+
+fn pad_to_width(text: &str, width: usize) -> String {
+    let mut out = String::new();
+    let mut used = 0;
+    for ch in text.chars() {
+        let ch_width = ch.width().unwrap_or(0);
+        if used + ch_width > width {
+            break;
+        }
+        used += ch_width;
+        out.push(ch);
+    }
+    out.push_str(&" ".repeat(width - used));
+    out
+}
+
+/// Line-level diff of two answers as panel rows. Side-by-side rows fit `width` columns,
+/// truncating long lines; unified rows are left for the panel to wrap.
+pub(crate) fn answer_diff_rows(
+    original: &str,
+    alternative: &str,
+    layout: AnswerDiffLayout,
+    width: usize,
+) -> Vec<String> {
+    let old = original.lines().collect::<Vec<_>>();
+    let new = alternative.lines().collect::<Vec<_>>();
+    let ops = capture_diff_slices(Algorithm::Myers, &old, &new);
+    let mut rows = Vec::new();
+    match layout {
+        AnswerDiffLayout::Unified => {
+            rows.push("--- answer 1".to_string());
+            rows.push("+++ answer 2".to_string());
+            for op in ops {
+                let (_, old_range, new_range) = op.as_tag_tuple();
+                let equal = matches!(op, DiffOp::Equal { .. });
+                for line in &old[old_range] {
+                    rows.push(format!("{}{line}", if equal { "  " } else { "- " }));
+                }
+                if !equal {
+                    for line in &new[new_range] {
+                        rows.push(format!("+ {line}"));
+                    }
+                }
+            }
+        }
+        AnswerDiffLayout::SideBySide => {
+            let column = width.saturating_sub(3).max(20) / 2;
+            let row = |marker: char, left: &str, right: &str| {
+                format!(
+                    "{} {marker}{}",
+                    pad_to_width(left, column),
+                    pad_to_width(right, column).trim_end()
+                )
+            };
+            rows.push(row('│', "answer 1", "answer 2"));
+            for op in ops {
+                let (_, old_range, new_range) = op.as_tag_tuple();
+                let marker = if matches!(op, DiffOp::Equal { .. }) {
+                    '│'
+                } else {
+                    '┃'
+                };
+                let left = &old[old_range];
+                let right = &new[new_range];
+                for index in 0..left.len().max(right.len()) {
+                    rows.push(row(
+                        marker,
+                        left.get(index).copied().unwrap_or(""),
+                        right.get(index).copied().unwrap_or(""),
+                    ));
+                }
+            }
+        }
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{answer_diff_rows, AnswerDiffLayout};
+
+    #[test]
+    fn unified_and_side_by_side_rows_mark_changed_lines() {
+        let original = "Use a mutex.\nIt is simple.\nDone.";
+        let alternative = "Use a channel.\nIt is simple.\nDone.\nAlso add a test.";
+        assert_eq!(
+            answer_diff_rows(original, alternative, AnswerDiffLayout::Unified, 80),
+            vec![
+                "--- answer 1",
+                "+++ answer 2",
+                "- Use a mutex.",
+                "+ Use a channel.",
+                "  It is simple.",
+                "  Done.",
+                "+ Also add a test.",
+            ]
+        );
+
+        let rows = answer_diff_rows(original, alternative, AnswerDiffLayout::SideBySide, 43);
+        assert_eq!(rows[0], "answer 1             │answer 2");
+        assert_eq!(rows[1], "Use a mutex.         ┃Use a channel.");
+        assert_eq!(rows[2], "It is simple.        │It is simple.");
+        assert_eq!(rows[4], "                     ┃Also add a test.");
+    }
+}
@@ -0,0 +1,100 @@
+use super::file_artifacts::fence_marker;
+use std::collections::VecDeque;
+
+const MAX_ASSISTANT_MESSAGES: usize = 50;
+const PREVIEW_CHARS: usize = 70;
+
+/// Bodies of fenced code blocks in `markdown`, in order; an unclosed fence runs to the end.
+pub(crate) fn code_blocks(markdown: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut lines = markdown.lines();
+    while let Some(line) = lines.next() {
+        let Some((fence, _)) = fence_marker(line) else {
+            continue;
+        };
+        let mut body = Vec::new();
+        for line in lines.by_ref() {
+            if fence_marker(line)
+                .is_some_and(|(close, rest)| close.starts_with(fence) && rest.trim().is_empty())
+            {
+                break;
+            }
+            body.push(line);
+        }
+        blocks.push(body.join("\n"));
+    }
+    blocks
+}
+
+/// Raw markdown of this tab's recent assistant messages, oldest first, for `/copy pick`.
+#[derive(Debug, Default)]
+pub(crate) struct AssistantMessages {
+    messages: VecDeque<String>,
+}
+
+impl AssistantMessages {
+    /// A message repeated by the run's `final` event is kept once.
+    pub(crate) fn record(&mut self, text: &str) {
+        if text.trim().is_empty() || self.messages.back().is_some_and(|last| last == text) {
+            return;
+        }
+        if self.messages.len() == MAX_ASSISTANT_MESSAGES {
+            self.messages.pop_front();
+        }
+        self.messages.push_back(text.to_string());
+    }
+
+    /// `age` 0 is the newest message.
+    pub(crate) fn get(&self, age: usize) -> Option<&str> {
+        let index = self.messages.len().checked_sub(age + 1)?;
+        self.messages.get(index).map(String::as_str)
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+/// First non-blank line, shortened for a picker row.
+pub(crate) fn message_preview(text: &str) -> String {
+    let line = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    if line.chars().count() <= PREVIEW_CHARS {
+        return line.to_string();
+    }
+    let mut preview: String = line.chars().take(PREVIEW_CHARS - 1).collect();
+    preview.push('…');
+    preview
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{code_blocks, message_preview, AssistantMessages};
+
+    #[test]
+    fn messages_are_kept_newest_last_and_code_blocks_extracted() {
+        let mut messages = AssistantMessages::default();
+        messages.record("First answer");
+        messages.record("Second\n```rust\nfn main() {}\n```\nthen\n~~~\nls -la\n~~~");
+        messages.record("Second\n```rust\nfn main() {}\n```\nthen\n~~~\nls -la\n~~~");
+        messages.record("  ");
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages.get(1), Some("First answer"));
+        assert_eq!(messages.get(2), None);
+
+        let newest = messages.get(0).expect("newest");
+        assert_eq!(code_blocks(newest), vec!["fn main() {}", "ls -la"]);
+        assert_eq!(code_blocks("```\nunclosed\nrest"), vec!["unclosed\nrest"]);
+        assert!(code_blocks("no fences").is_empty());
+
+        assert_eq!(message_preview("\n  Done.\nMore"), "Done.");
+        assert_eq!(message_preview(&"x".repeat(80)).chars().count(), 70);
+    }
+}
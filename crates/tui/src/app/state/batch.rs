@@ -0,0 +1,82 @@
+/// Splits a prompt file into prompts: blocks separated by one or more blank lines.
+pub(crate) fn split_prompt_blocks(text: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                blocks.push(current.join("\n"));
+                current.clear();
+            }
+        } else {
+            current.push(line.trim_end());
+        }
+    }
+    if !current.is_empty() {
+        blocks.push(current.join("\n"));
+    }
+    blocks
+}
+
+/// Prompts from `/batch` or `--prompt-file`, run one after another through the prompt queue.
+#[derive(Debug, Default)]
+pub(crate) struct PromptBatch {
+    /// File name shown in progress and completion lines.
+    pub(crate) label: String,
+    /// Prompts loaded at startup and held until the runtime can take runs.
+    pub(crate) unstarted: Vec<String>,
+    /// Queue ids of the enqueued prompts, in file order.
+    pub(crate) queue_ids: Vec<String>,
+}
+
+impl PromptBatch {
+    pub(crate) fn total(&self) -> usize {
+        self.unstarted.len() + self.queue_ids.len()
+    }
+
+    /// Batch items that have not been dispatched yet, given the ids still waiting to run.
+    pub(crate) fn waiting<'a>(&self, waiting_ids: impl IntoIterator<Item = &'a str>) -> usize {
+        let waiting = waiting_ids
+            .into_iter()
+            .filter(|id| self.queue_ids.iter().any(|queued| queued == id))
+            .count();
+        waiting + self.unstarted.len()
+    }
+
+    /// Status-line segment such as `batch: 3/10` (the item running or about to run).
+    pub(crate) fn status_segment(&self, waiting: usize) -> String {
+        let total = self.total();
+        format!("batch: {}/{total}", total.saturating_sub(waiting))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split_prompt_blocks, PromptBatch};
+
+    #[test]
+    fn prompt_files_split_on_blank_lines_and_progress_counts_dispatched_items() {
+        let blocks = split_prompt_blocks(
+            "\n  first prompt\nstill first  \n\n\n\t\nsecond prompt\r\n\r\nthird\n",
+        );
+        assert_eq!(
+            blocks,
+            vec![
+                "  first prompt\nstill first".to_string(),
+                "second prompt".to_string(),
+                "third".to_string(),
+            ]
+        );
+        assert!(split_prompt_blocks(" \n\n").is_empty());
+
+        let batch = PromptBatch {
+            label: "evals.txt".to_string(),
+            unstarted: Vec::new(),
+            queue_ids: vec!["q1".to_string(), "q2".to_string(), "q3".to_string()],
+        };
+        let waiting = batch.waiting(["q2", "q3", "q9"]);
+        assert_eq!(waiting, 2);
+        assert_eq!(batch.status_segment(waiting), "batch: 1/3");
+        assert_eq!(batch.status_segment(0), "batch: 3/3");
+    }
+}
@@ -0,0 +1,155 @@
+use std::time::{Duration, Instant};
+
+/// How long a started chord waits for its next key before the keys are typed as text.
+pub(crate) const CHORD_TIMEOUT: Duration = Duration::from_millis(800);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ChordAction {
+    ScrollTop,
+    ScrollBottom,
+    QuickJump,
+    PinAnswer,
+    Scratchpad,
+    ExternalEditor,
+}
+
+/// Multi-key bindings, written as the characters typed (Ctrl+letter is its ASCII control
+/// character: `\u{18}` is Ctrl+X). Every binding starts with a Ctrl leader, so a prompt that
+/// begins with a letter or a space is never held back.
+pub(crate) const CHORD_BINDINGS: &[(&str, ChordAction)] = &[
+    ("\u{18}g", ChordAction::ScrollTop),
+    ("\u{18}G", ChordAction::ScrollBottom),
+    ("\u{18}f", ChordAction::QuickJump),
+    ("\u{18}p", ChordAction::PinAnswer),
+    ("\u{18}s", ChordAction::Scratchpad),
+    ("\u{18}\u{5}", ChordAction::ExternalEditor),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ChordStep {
+    /// The keys so far prefix a binding; wait for more.
+    Pending,
+    Matched(ChordAction),
+    /// No binding matches; these keys should be handled as ordinary input.
+    Unmatched(Vec<char>),
+}
+
+fn key_label(ch: char) -> String {
+    match ch {
+        ch if ch.is_ascii_control() => format!("Ctrl+{}", (ch as u8 | 0x40) as char),
+        ch => ch.to_string(),
+    }
+}
+
+/// Keys typed toward a chord, held back from the composer until they match or time out.
+#[derive(Debug, Default)]
+pub(crate) struct ChordBuffer {
+    keys: Vec<char>,
+    started_at: Option<Instant>,
+}
+
+impl ChordBuffer {
+    pub(crate) fn is_pending(&self) -> bool {
+        !self.keys.is_empty()
+    }
+
+    /// Only a Ctrl leader starts a chord; printable keys always reach the composer.
+    pub(crate) fn starts_chord(ch: char) -> bool {
+        ch.is_ascii_control() && CHORD_BINDINGS.iter().any(|(keys, _)| keys.starts_with(ch))
+    }
+
+    pub(crate) fn feed(&mut self, ch: char, now: Instant) -> ChordStep {
+        if self.keys.is_empty() {
+            self.started_at = Some(now);
+        }
+        self.keys.push(ch);
+        let typed = self.keys.iter().collect::<String>();
+        if let Some((_, action)) = CHORD_BINDINGS.iter().find(|(keys, _)| *keys == typed) {
+            self.clear();
+            return ChordStep::Matched(*action);
+        }
+        if CHORD_BINDINGS
+            .iter()
+            .any(|(keys, _)| keys.starts_with(typed.as_str()))
+        {
+            return ChordStep::Pending;
+        }
+        ChordStep::Unmatched(self.flush())
+    }
+
+    /// Pending keys once [`CHORD_TIMEOUT`] has passed, so the caller can type them.
+    pub(crate) fn take_expired(&mut self, now: Instant) -> Option<Vec<char>> {
+        let started_at = self.started_at?;
+        (now.duration_since(started_at) >= CHORD_TIMEOUT).then(|| self.flush())
+    }
+
+    pub(crate) fn flush(&mut self) -> Vec<char> {
+        self.started_at = None;
+        std::mem::take(&mut self.keys)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.keys.clear();
+        self.started_at = None;
+    }
+
+    /// Status-line hint such as `Ctrl+X …`.
+    pub(crate) fn pending_label(&self) -> Option<String> {
+        if self.keys.is_empty() {
+            return None;
+        }
+        let keys = self
+            .keys
+            .iter()
+            .map(|ch| key_label(*ch))
+            .collect::<Vec<_>>()
+            .join(" ");
+        Some(format!("{keys} …"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChordAction, ChordBuffer, ChordStep, CHORD_BINDINGS, CHORD_TIMEOUT};
+    use std::time::Instant;
+
+    const CTRL_X: char = '\u{18}';
+    const CTRL_E: char = '\u{5}';
+
+    #[test]
+    fn chords_match_fall_through_and_time_out() {
+        let now = Instant::now();
+        let mut chord = ChordBuffer::default();
+        assert!(CHORD_BINDINGS
+            .iter()
+            .all(|(keys, _)| keys.starts_with(|ch: char| ch.is_ascii_control())));
+        assert!(ChordBuffer::starts_chord(CTRL_X));
+        assert!(!ChordBuffer::starts_chord('g'));
+        assert!(!ChordBuffer::starts_chord(' '));
+
+        assert_eq!(chord.feed(CTRL_X, now), ChordStep::Pending);
+        assert_eq!(chord.pending_label().as_deref(), Some("Ctrl+X …"));
+        assert_eq!(
+            chord.feed('g', now),
+            ChordStep::Matched(ChordAction::ScrollTop)
+        );
+        assert!(!chord.is_pending());
+
+        assert_eq!(chord.feed(CTRL_X, now), ChordStep::Pending);
+        assert_eq!(
+            chord.feed('x', now),
+            ChordStep::Unmatched(vec![CTRL_X, 'x'])
+        );
+
+        assert_eq!(chord.feed(CTRL_X, now), ChordStep::Pending);
+        assert_eq!(chord.take_expired(now), None);
+        assert_eq!(chord.take_expired(now + CHORD_TIMEOUT), Some(vec![CTRL_X]));
+        assert_eq!(chord.pending_label(), None);
+
+        assert_eq!(chord.feed(CTRL_X, now), ChordStep::Pending);
+        assert_eq!(
+            chord.feed(CTRL_E, now),
+            ChordStep::Matched(ChordAction::ExternalEditor)
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use std::time::{Duration, Instant};
+
+/// Time between keepalive pings to a runtime reached over ssh.
+pub(crate) const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+/// An unanswered keepalive this old means the connection is gone.
+pub(crate) const KEEPALIVE_TIMEOUT: Duration = Duration::from_secs(45);
+/// Waiting this long for a keepalive answer shows in the status line.
+const KEEPALIVE_SLOW_AFTER: Duration = Duration::from_secs(5);
+
+/// Keepalive pings and the last measured round trip for an ssh runtime.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ConnectionHealth {
+    last_sent_at: Option<Instant>,
+    /// Set while a keepalive waits for its answer.
+    pending_since: Option<Instant>,
+    pub(crate) latency: Option<Duration>,
+}
+
+impl ConnectionHealth {
+    pub(crate) fn due(&self, now: Instant) -> bool {
+        self.pending_since.is_none()
+            && self
+                .last_sent_at
+                .is_none_or(|at| now.saturating_duration_since(at) >= KEEPALIVE_INTERVAL)
+    }
+
+    pub(crate) fn sent(&mut self, now: Instant) {
+        self.last_sent_at = Some(now);
+        self.pending_since = Some(now);
+    }
+
+    pub(crate) fn answered(&mut self, now: Instant) {
+        if let Some(sent_at) = self.pending_since.take() {
+            self.latency = Some(now.saturating_duration_since(sent_at));
+        }
+    }
+
+    /// How long the pending keepalive has waited, once that reaches `KEEPALIVE_TIMEOUT`.
+    pub(crate) fn overdue(&self, now: Instant) -> Option<Duration> {
+        let waited = now.saturating_duration_since(self.pending_since?);
+        (waited >= KEEPALIVE_TIMEOUT).then_some(waited)
+    }
+
+    /// `ssh: host 42ms`, or how long the current keepalive has gone unanswered.
+    pub(crate) fn status_segment(&self, host: &str, now: Instant) -> String {
+        let waited = self
+            .pending_since
+            .map(|at| now.saturating_duration_since(at))
+            .filter(|waited| *waited >= KEEPALIVE_SLOW_AFTER);
+        match (waited, self.latency) {
+            (Some(waited), _) => format!("ssh: {host} no reply {}s", waited.as_secs()),
+            (None, Some(latency)) => format!("ssh: {host} {}ms", latency.as_millis()),
+            (None, None) => format!("ssh: {host}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConnectionHealth, KEEPALIVE_INTERVAL, KEEPALIVE_TIMEOUT};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn keepalive_measures_latency_and_flags_silent_connections() {
+        let now = Instant::now();
+        let mut health = ConnectionHealth::default();
+        assert!(health.due(now));
+        assert_eq!(health.status_segment("dev@box", now), "ssh: dev@box");
+
+        health.sent(now);
+        assert!(!health.due(now + KEEPALIVE_INTERVAL));
+        health.answered(now + Duration::from_millis(42));
+        assert_eq!(health.status_segment("dev@box", now), "ssh: dev@box 42ms");
+        assert!(!health.due(now + Duration::from_secs(1)));
+
+        let next = now + KEEPALIVE_INTERVAL;
+        assert!(health.due(next));
+        health.sent(next);
+        assert_eq!(
+            health.status_segment("dev@box", next + Duration::from_secs(6)),
+            "ssh: dev@box no reply 6s"
+        );
+        assert_eq!(health.overdue(next + Duration::from_secs(44)), None);
+        assert_eq!(
+            health.overdue(next + KEEPALIVE_TIMEOUT),
+            Some(KEEPALIVE_TIMEOUT)
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::VecDeque;
+
+const MAX_CONTEXT_SAMPLES: usize = 64;
+const MAX_CONTEXT_RUNS: usize = 50;
+const SPARKLINE_SAMPLES: usize = 12;
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Context usage of one run, in percent of the window still free.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ContextRunUsage {
+    pub number: usize,
+    pub run_id: Option<String>,
+    /// Free context when the run began (last value seen before it), if known.
+    pub start_left: Option<u8>,
+    pub end_left: u8,
+}
+
+impl ContextRunUsage {
+    /// Percentage points this run consumed; negative after a compaction freed space.
+    pub(crate) fn consumed(&self) -> i16 {
+        self.start_left
+            .map(|start| i16::from(start) - i16::from(self.end_left))
+            .unwrap_or(0)
+    }
+}
+
+/// `context_left_percent` samples from run status updates, grouped per run.
+#[derive(Debug, Default)]
+pub(crate) struct ContextHistory {
+    samples: VecDeque<u8>,
+    runs: VecDeque<ContextRunUsage>,
+    next_run_number: usize,
+    last_left: Option<u8>,
+}
+
+impl ContextHistory {
+    pub(crate) fn record(&mut self, run_id: Option<&str>, left: u8) {
+        let left = left.min(100);
+        if self.samples.len() == MAX_CONTEXT_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(left);
+
+        let same_run = self
+            .runs
+            .back()
+            .is_some_and(|run| run.run_id.as_deref() == run_id);
+        if same_run {
+            if let Some(run) = self.runs.back_mut() {
+                run.end_left = left;
+            }
+        } else {
+            self.next_run_number += 1;
+            if self.runs.len() == MAX_CONTEXT_RUNS {
+                self.runs.pop_front();
+            }
+            self.runs.push_back(ContextRunUsage {
+                number: self.next_run_number,
+                run_id: run_id.map(str::to_string),
+                start_left: self.last_left,
+                end_left: left,
+            });
+        }
+        self.last_left = Some(left);
+    }
+
+    pub(crate) fn runs(&self) -> impl DoubleEndedIterator<Item = &ContextRunUsage> {
+        self.runs.iter()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+
+    /// Used-context sparkline of the latest samples (taller = fuller); `None` until two samples exist.
+    pub(crate) fn sparkline(&self) -> Option<String> {
+        if self.samples.len() < 2 {
+            return None;
+        }
+        let skip = self.samples.len().saturating_sub(SPARKLINE_SAMPLES);
+        Some(
+            self.samples
+                .iter()
+                .skip(skip)
+                .map(|left| {
+                    let used = usize::from(100 - left);
+                    SPARK_LEVELS[(used * SPARK_LEVELS.len() / 101).min(SPARK_LEVELS.len() - 1)]
+                })
+                .collect(),
+        )
+    }
+}
+
+/// `width`-cell bar: `█` used before the run, `▓` used by the run, `░` still free.
+pub(crate) fn context_usage_bar(run: &ContextRunUsage, width: usize) -> String {
+    let cells = |percent: u8| usize::from(100 - percent.min(100)) * width / 100;
+    let used_after = cells(run.end_left);
+    let used_before = run
+        .start_left
+        .map(cells)
+        .unwrap_or(used_after)
+        .min(used_after);
+    let mut bar = String::with_capacity(width * 3);
+    bar.extend(std::iter::repeat_n('█', used_before));
+    bar.extend(std::iter::repeat_n('▓', used_after - used_before));
+    bar.extend(std::iter::repeat_n('░', width - used_after));
+    bar
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{context_usage_bar, ContextHistory};
+
+    #[test]
+    fn context_history_groups_samples_by_run() {
+        let mut history = ContextHistory::default();
+        assert_eq!(history.sparkline(), None);
+        history.record(Some("run-1"), 100);
+        history.record(Some("run-1"), 90);
+        history.record(Some("run-2"), 80);
+        history.record(Some("run-2"), 40);
+        history.record(Some("run-3"), 70);
+
+        let runs = history.runs().collect::<Vec<_>>();
+        assert_eq!(runs.len(), 3);
+        assert_eq!((runs[0].start_left, runs[0].end_left), (None, 90));
+        assert_eq!(runs[1].consumed(), 50);
+        assert_eq!(runs[2].consumed(), -30, "compaction frees space");
+        assert_eq!(history.sparkline().as_deref(), Some("▁▁▂▅▃"));
+        assert_eq!(context_usage_bar(runs[1], 10), "█▓▓▓▓▓░░░░");
+        assert_eq!(context_usage_bar(runs[2], 10), "███░░░░░░░");
+    }
+}
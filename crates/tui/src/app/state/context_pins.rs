@@ -0,0 +1,188 @@
+use serde_json::json;
+
+pub(crate) const DEFAULT_PIN_TURNS: usize = 3;
+const MAX_CONTEXT_PINS: usize = 8;
+/// Longer captures keep their tail, where errors usually end up.
+const MAX_PIN_CHARS: usize = 8_000;
+
+/// Log text sent ahead of the next `remaining` prompts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ContextPin {
+    pub name: String,
+    pub text: String,
+    pub remaining: usize,
+}
+
+/// Named log snippets captured with `/pin`, plus the optional `/pin mark` start line.
+#[derive(Debug, Default)]
+pub(crate) struct ContextPins {
+    pins: Vec<ContextPin>,
+    mark: Option<usize>,
+}
+
+fn keep_tail(text: &str, max_chars: usize) -> String {
+    let count = text.chars().count();
+    if count <= max_chars {
+        return text.to_string();
+    }
+    let tail = text.chars().skip(count - max_chars).collect::<String>();
+    format!("…{tail}")
+}
+
+impl ContextPins {
+    /// Replaces a pin with the same name; returns false for blank text or zero turns.
+    pub(crate) fn add(&mut self, name: &str, text: &str, turns: usize) -> bool {
+        let text = text.trim_matches('\n').trim_end();
+        if text.trim().is_empty() || turns == 0 {
+            return false;
+        }
+        self.pins.retain(|pin| pin.name != name);
+        if self.pins.len() == MAX_CONTEXT_PINS {
+            self.pins.remove(0);
+        }
+        self.pins.push(ContextPin {
+            name: name.to_string(),
+            text: keep_tail(text, MAX_PIN_CHARS),
+            remaining: turns,
+        });
+        true
+    }
+
+    pub(crate) fn remove(&mut self, name: &str) -> Option<ContextPin> {
+        let index = self.pins.iter().position(|pin| pin.name == name)?;
+        Some(self.pins.remove(index))
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.pins.clear();
+    }
+
+    pub(crate) fn pins(&self) -> &[ContextPin] {
+        &self.pins
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pins.is_empty()
+    }
+
+    /// `pin-N` with the smallest N not already taken.
+    pub(crate) fn next_default_name(&self) -> String {
+        (1..)
+            .map(|index| format!("pin-{index}"))
+            .find(|name| self.pins.iter().all(|pin| &pin.name != name))
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn set_mark(&mut self, log_len: usize) {
+        self.mark = Some(log_len);
+    }
+
+    pub(crate) fn take_mark(&mut self) -> Option<usize> {
+        self.mark.take()
+    }
+
+    /// Follows the log after its first `count` lines were dropped.
+    pub(crate) fn shift_mark(&mut self, count: usize) {
+        if let Some(mark) = self.mark.as_mut() {
+            *mark = mark.saturating_sub(count);
+        }
+    }
+
+    /// Follows the log after `count` lines were inserted before line `index`.
+    pub(crate) fn shift_mark_for_insert(&mut self, index: usize, count: usize) {
+        if let Some(mark) = self.mark.as_mut().filter(|mark| **mark >= index) {
+            *mark += count;
+        }
+    }
+
+    pub(crate) fn mark(&self) -> Option<usize> {
+        self.mark
+    }
+
+    /// `<pinned_context>` blocks; `<`/`>` are JSON-escaped so log text cannot close the tag.
+    pub(crate) fn prefix(&self) -> Option<String> {
+        if self.pins.is_empty() {
+            return None;
+        }
+        let blocks = self
+            .pins
+            .iter()
+            .map(|pin| {
+                let payload = json!({ "name": pin.name, "content": pin.text });
+                let json_text = payload
+                    .to_string()
+                    .replace('<', "\\u003c")
+                    .replace('>', "\\u003e");
+                format!("<pinned_context>\n{json_text}\n</pinned_context>")
+            })
+            .collect::<Vec<_>>();
+        Some(blocks.join("\n"))
+    }
+
+    /// Counts one prompt against every pin and returns the names that just expired.
+    pub(crate) fn consume_turn(&mut self) -> Vec<String> {
+        let mut expired = Vec::new();
+        self.pins.retain_mut(|pin| {
+            pin.remaining = pin.remaining.saturating_sub(1);
+            if pin.remaining == 0 {
+                expired.push(pin.name.clone());
+            }
+            pin.remaining > 0
+        });
+        expired
+    }
+
+    /// A `name (N prompts left)` heading per pin followed by its text lines.
+    pub(crate) fn panel_rows(&self) -> Vec<String> {
+        let mut rows = Vec::new();
+        for (index, pin) in self.pins.iter().enumerate() {
+            if index > 0 {
+                rows.push(String::new());
+            }
+            let plural = if pin.remaining == 1 { "" } else { "s" };
+            rows.push(format!(
+                "{} ({} prompt{plural} left)",
+                pin.name, pin.remaining
+            ));
+            rows.extend(pin.text.lines().map(|line| format!("  {line}")));
+        }
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ContextPins;
+
+    #[test]
+    fn pins_expire_after_their_turns_and_replace_by_name() {
+        let mut pins = ContextPins::default();
+        assert!(!pins.add("blank", "  \n", 2));
+        assert!(pins.add("err", "panic: </pinned_context> boom", 2));
+        assert!(pins.add("log", "first", 1));
+        assert!(pins.add("log", "second", 1));
+        assert_eq!(pins.pins().len(), 2);
+        assert_eq!(pins.pins()[1].text, "second");
+        assert_eq!(pins.next_default_name(), "pin-1");
+
+        let prefix = pins.prefix().expect("prefix");
+        assert!(prefix.contains("\\u003c/pinned_context\\u003e boom"));
+        assert_eq!(prefix.matches("</pinned_context>").count(), 2);
+
+        assert_eq!(pins.consume_turn(), vec!["log".to_string()]);
+        assert_eq!(pins.panel_rows()[0], "err (1 prompt left)");
+        assert_eq!(pins.consume_turn(), vec!["err".to_string()]);
+        assert!(pins.prefix().is_none());
+    }
+
+    #[test]
+    fn long_captures_keep_their_tail() {
+        let mut pins = ContextPins::default();
+        let text = format!("{}END", "x".repeat(super::MAX_PIN_CHARS));
+        assert!(pins.add("big", &text, 1));
+        let stored = &pins.pins()[0].text;
+        assert!(stored.starts_with('…'));
+        assert!(stored.ends_with("END"));
+        assert_eq!(stored.chars().count(), super::MAX_PIN_CHARS + 1);
+    }
+}
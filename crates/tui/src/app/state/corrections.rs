@@ -0,0 +1,208 @@
+use std::collections::BTreeSet;
+
+/// Same-convention corrections needed before AGENTS.md is suggested.
+const REPEAT_THRESHOLD: usize = 2;
+const MAX_TRACKED_CORRECTIONS: usize = 20;
+/// Longer prompts are new requests, not feedback.
+const MAX_CORRECTION_CHARS: usize = 280;
+
+const LEAD_MARKERS: &[&str] = &[
+    "no",
+    "nope",
+    "not",
+    "don't",
+    "dont",
+    "do not",
+    "stop",
+    "never",
+    "always",
+    "again",
+    "wrong",
+    "instead",
+    "i said",
+    "i told you",
+    "that's not",
+    "thats not",
+    "please don't",
+    "please do not",
+    "please stop",
+];
+const PHRASE_MARKERS: &[&str] = &[
+    "i said",
+    "i told you",
+    "i asked",
+    "again",
+    "instead of",
+    "don't",
+    "do not",
+    "stop ",
+    "never ",
+    "always ",
+    "as i said",
+];
+/// Stripped from the front of a correction to get the instruction itself.
+const DISTILL_PREFIXES: &[&str] = &[
+    "no",
+    "nope",
+    "again",
+    "i said",
+    "i told you",
+    "like i said",
+    "as i said",
+    "please",
+    "remember",
+    "wrong",
+];
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "again", "always", "are", "as", "asked", "be", "but", "do", "don't", "dont",
+    "for", "i", "in", "instead", "is", "it", "me", "my", "never", "no", "nope", "not", "of", "on",
+    "or", "please", "said", "should", "stop", "that", "that's", "the", "this", "to", "told", "use",
+    "using", "we", "with", "wrong", "you", "your",
+];
+
+fn keywords(text: &str) -> BTreeSet<String> {
+    text.to_lowercase()
+        .split(|ch: char| !(ch.is_alphanumeric() || matches!(ch, '\'' | '-' | '_' | '.')))
+        .map(|word| word.trim_matches(|ch| matches!(ch, '\'' | '.' | '-')))
+        .filter(|word| word.len() > 1 && !STOPWORDS.contains(word))
+        .map(str::to_string)
+        .collect()
+}
+
+/// `text` after a leading `marker` word (ASCII case-insensitive).
+fn strip_word<'a>(text: &'a str, marker: &str) -> Option<&'a str> {
+    let head = text.get(..marker.len())?;
+    let rest = &text[marker.len()..];
+    (head.eq_ignore_ascii_case(marker) && !rest.starts_with(char::is_alphanumeric)).then_some(rest)
+}
+
+/// Short feedback that pushes back on what the agent did.
+pub(crate) fn is_correction(text: &str) -> bool {
+    let text = text.trim().to_lowercase().replace('’', "'");
+    if text.is_empty() || text.chars().count() > MAX_CORRECTION_CHARS || text.contains('\n') {
+        return false;
+    }
+    LEAD_MARKERS
+        .iter()
+        .any(|marker| strip_word(&text, marker).is_some())
+        || PHRASE_MARKERS.iter().any(|marker| text.contains(marker))
+}
+
+/// The correction as an AGENTS.md rule: leading "no," / "I said" and the like are dropped.
+pub(crate) fn distill_instruction(text: &str) -> String {
+    let mut rest = text.trim().replace('’', "'");
+    while let Some(stripped) = DISTILL_PREFIXES
+        .iter()
+        .find_map(|prefix| strip_word(&rest, prefix))
+    {
+        let stripped = stripped.trim_start_matches(|ch: char| {
+            ch.is_whitespace() || matches!(ch, ',' | '!' | ':' | '-')
+        });
+        if stripped.is_empty() {
+            break;
+        }
+        rest = stripped.to_string();
+    }
+    let mut chars = rest.chars();
+    let mut instruction = chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+        .unwrap_or_default();
+    let instruction_end = instruction.trim_end_matches(['!', ' ']).len();
+    instruction.truncate(instruction_end);
+    if !instruction.ends_with(['.', '?']) {
+        instruction.push('.');
+    }
+    instruction
+}
+
+/// At least two shared keywords covering most of the shorter correction.
+fn similar(left: &BTreeSet<String>, right: &BTreeSet<String>) -> bool {
+    let shared = left.intersection(right).count();
+    let smaller = left.len().min(right.len());
+    shared >= 2 && shared * 5 >= smaller * 3
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AgentsSuggestion {
+    /// The line appended to AGENTS.md (without the `- ` bullet).
+    pub instruction: String,
+    pub count: usize,
+}
+
+/// Recent corrections of this tab, grouped by keyword overlap.
+#[derive(Debug, Default)]
+pub(crate) struct CorrectionTracker {
+    seen: Vec<BTreeSet<String>>,
+    /// Keywords of conventions already suggested; they are not offered twice.
+    offered: Vec<BTreeSet<String>>,
+    /// Waiting for `Alt+A`.
+    pub suggestion: Option<AgentsSuggestion>,
+}
+
+impl CorrectionTracker {
+    /// Records a sent prompt; returns the new suggestion when it repeats an earlier correction.
+    pub(crate) fn observe(&mut self, text: &str) -> Option<&AgentsSuggestion> {
+        if !is_correction(text) {
+            return None;
+        }
+        let words = keywords(text);
+        if words.len() < 2 || self.offered.iter().any(|offered| similar(offered, &words)) {
+            return None;
+        }
+        let count = 1 + self
+            .seen
+            .iter()
+            .filter(|earlier| similar(earlier, &words))
+            .count();
+        if count < REPEAT_THRESHOLD {
+            if self.seen.len() == MAX_TRACKED_CORRECTIONS {
+                self.seen.remove(0);
+            }
+            self.seen.push(words);
+            return None;
+        }
+        self.seen.retain(|earlier| !similar(earlier, &words));
+        self.offered.push(words);
+        self.suggestion = Some(AgentsSuggestion {
+            instruction: distill_instruction(text),
+            count,
+        });
+        self.suggestion.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{distill_instruction, is_correction, CorrectionTracker};
+
+    #[test]
+    fn repeated_corrections_on_one_convention_become_a_suggestion() {
+        assert!(is_correction("no, use pnpm not npm"));
+        assert!(is_correction("Don’t touch the lockfile"));
+        assert!(!is_correction("add a --json flag to the export command"));
+        assert!(!is_correction("nothing else to do"));
+        assert_eq!(
+            distill_instruction("No, I said: use pnpm for installs, not npm!"),
+            "Use pnpm for installs, not npm."
+        );
+
+        let mut tracker = CorrectionTracker::default();
+        assert!(tracker.observe("no, run pnpm install, not npm").is_none());
+        assert!(tracker.observe("don't reformat the lockfile").is_none());
+        assert!(tracker.observe("fix the failing test").is_none());
+        let suggestion = tracker
+            .observe("again, pnpm install — never npm")
+            .expect("suggestion")
+            .clone();
+        assert_eq!(suggestion.instruction, "Pnpm install — never npm.");
+        assert_eq!(suggestion.count, 2);
+        // The convention is not offered twice.
+        assert!(tracker.observe("I said pnpm install, not npm").is_none());
+        let lockfile = tracker.observe("No, don't reformat the lockfile");
+        assert_eq!(
+            lockfile.map(|suggestion| suggestion.instruction.as_str()),
+            Some("Don't reformat the lockfile.")
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use std::time::{Duration, Instant};
+
+/// How long a first Esc keeps the run-cancel step armed under the `layered` policy.
+pub(crate) const ESC_CANCEL_WINDOW: Duration = Duration::from_millis(1_500);
+
+/// `esc_policy` setting: how far a main-screen Esc may escalate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum EscPolicy {
+    /// One layer per press; cancelling a run takes a second Esc within the window.
+    #[default]
+    Layered,
+    /// Cancels the run as soon as nothing else is left to close.
+    Immediate,
+    /// Never cancels a run; Ctrl+C still does.
+    Never,
+}
+
+impl EscPolicy {
+    pub(crate) const VALUES: &'static str = "layered|immediate|never";
+
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "layered" => Some(Self::Layered),
+            "immediate" => Some(Self::Immediate),
+            "never" => Some(Self::Never),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Layered => "layered",
+            Self::Immediate => "immediate",
+            Self::Never => "never",
+        }
+    }
+}
+
+/// What the next Esc on the main screen does, outermost layer first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EscAction {
+    CloseSearch,
+    LeaveScrollback,
+    LeaveShellMode,
+    ClearComposer,
+    ArmCancel,
+    CancelRun,
+    DismissMotd,
+}
+
+impl EscAction {
+    /// Status line hint, shown as `Esc: <hint>`.
+    pub(crate) fn hint(self) -> &'static str {
+        match self {
+            Self::CloseSearch => "close search",
+            Self::LeaveScrollback => "back to bottom",
+            Self::LeaveShellMode => "leave ! mode",
+            Self::ClearComposer => "clear input",
+            Self::ArmCancel => "cancel run (press twice)",
+            Self::CancelRun => "again to cancel run",
+            Self::DismissMotd => "dismiss tip",
+        }
+    }
+}
+
+/// Snapshot of the state the Esc layers look at.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct EscContext {
+    /// Ctrl+F search is open.
+    pub search_open: bool,
+    pub scrolled_back: bool,
+    /// `!` mode with nothing typed or attached.
+    pub empty_shell_mode: bool,
+    pub composer_has_content: bool,
+    /// A run is active and no cancel request is in flight.
+    pub cancellable_run: bool,
+    pub cancel_armed_at: Option<Instant>,
+    pub motd_visible: bool,
+}
+
+pub(crate) fn next_esc_action(
+    policy: EscPolicy,
+    context: &EscContext,
+    now: Instant,
+) -> Option<EscAction> {
+    if context.search_open {
+        return Some(EscAction::CloseSearch);
+    }
+    if context.scrolled_back {
+        return Some(EscAction::LeaveScrollback);
+    }
+    if context.empty_shell_mode {
+        return Some(EscAction::LeaveShellMode);
+    }
+    if context.composer_has_content {
+        return Some(EscAction::ClearComposer);
+    }
+    if context.cancellable_run {
+        let armed = context
+            .cancel_armed_at
+            .is_some_and(|armed_at| now.saturating_duration_since(armed_at) < ESC_CANCEL_WINDOW);
+        match policy {
+            EscPolicy::Layered if armed => return Some(EscAction::CancelRun),
+            EscPolicy::Layered => return Some(EscAction::ArmCancel),
+            EscPolicy::Immediate => return Some(EscAction::CancelRun),
+            EscPolicy::Never => {}
+        }
+    }
+    context.motd_visible.then_some(EscAction::DismissMotd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{next_esc_action, EscAction, EscContext, EscPolicy, ESC_CANCEL_WINDOW};
+    use std::time::Instant;
+
+    #[test]
+    fn layers_peel_one_per_press_and_cancel_needs_a_second_esc() {
+        let now = Instant::now();
+        let mut context = EscContext {
+            search_open: true,
+            scrolled_back: true,
+            composer_has_content: true,
+            cancellable_run: true,
+            ..EscContext::default()
+        };
+        let next = |context: &EscContext, policy| next_esc_action(policy, context, now);
+        assert_eq!(
+            next(&context, EscPolicy::Never),
+            Some(EscAction::CloseSearch)
+        );
+        context.search_open = false;
+        assert_eq!(
+            next(&context, EscPolicy::Layered),
+            Some(EscAction::LeaveScrollback)
+        );
+        context.scrolled_back = false;
+        assert_eq!(
+            next(&context, EscPolicy::Layered),
+            Some(EscAction::ClearComposer)
+        );
+        context.composer_has_content = false;
+        assert_eq!(
+            next(&context, EscPolicy::Layered),
+            Some(EscAction::ArmCancel)
+        );
+        assert_eq!(
+            next(&context, EscPolicy::Immediate),
+            Some(EscAction::CancelRun)
+        );
+        assert_eq!(next(&context, EscPolicy::Never), None);
+
+        context.cancel_armed_at = Some(now);
+        assert_eq!(
+            next(&context, EscPolicy::Layered),
+            Some(EscAction::CancelRun)
+        );
+        context.cancel_armed_at = now.checked_sub(ESC_CANCEL_WINDOW);
+        assert_eq!(
+            next(&context, EscPolicy::Layered),
+            Some(EscAction::ArmCancel)
+        );
+
+        context.cancellable_run = false;
+        context.motd_visible = true;
+        assert_eq!(
+            next(&context, EscPolicy::Never),
+            Some(EscAction::DismissMotd)
+        );
+    }
+}
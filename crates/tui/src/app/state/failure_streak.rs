@@ -0,0 +1,61 @@
+/// Consecutive failed tool calls or runs before error details are shown automatically.
+pub(crate) const FAILURE_STREAK_THRESHOLD: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FailureStreakChange {
+    /// The streak just reached the threshold; carries the failure count.
+    Escalated(usize),
+    /// A success ended a streak that had switched error details on.
+    Recovered,
+}
+
+/// Counts consecutive failures; escalation is reported once per streak.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FailureStreak {
+    count: usize,
+    escalated: bool,
+    /// Error details were switched on by the streak and should go back to summary.
+    pub(crate) restore_summary: bool,
+}
+
+impl FailureStreak {
+    pub(crate) fn record_failure(&mut self) -> Option<FailureStreakChange> {
+        self.count += 1;
+        if self.escalated || self.count < FAILURE_STREAK_THRESHOLD {
+            return None;
+        }
+        self.escalated = true;
+        Some(FailureStreakChange::Escalated(self.count))
+    }
+
+    pub(crate) fn record_success(&mut self) -> Option<FailureStreakChange> {
+        let escalated = self.escalated;
+        self.count = 0;
+        self.escalated = false;
+        escalated.then_some(FailureStreakChange::Recovered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FailureStreak, FailureStreakChange};
+
+    #[test]
+    fn streak_escalates_once_and_recovers_on_success() {
+        let mut streak = FailureStreak::default();
+        assert_eq!(streak.record_failure(), None);
+        assert_eq!(streak.record_success(), None);
+        assert_eq!(streak.record_failure(), None);
+        assert_eq!(streak.record_failure(), None);
+        assert_eq!(
+            streak.record_failure(),
+            Some(FailureStreakChange::Escalated(3))
+        );
+        assert_eq!(streak.record_failure(), None);
+        assert_eq!(
+            streak.record_success(),
+            Some(FailureStreakChange::Recovered)
+        );
+        assert_eq!(streak.record_failure(), None);
+    }
+}
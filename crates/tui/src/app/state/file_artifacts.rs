@@ -0,0 +1,226 @@
+/// A complete file in an assistant answer: a closed fenced block whose info string or
+/// preceding line names a workspace-relative path. Offered as `[s]ave as <path>` (Alt+S).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FileArtifact {
+    pub path: String,
+    pub content: String,
+}
+
+impl FileArtifact {
+    pub(crate) fn line_count(&self) -> usize {
+        self.content.lines().count()
+    }
+}
+
+/// Quick-pick digits 1-9 address every entry.
+const MAX_FILE_ARTIFACTS: usize = 9;
+
+/// Fences that show changes or sessions rather than a file's contents.
+const NON_FILE_LANGUAGES: &[&str] = &["diff", "patch", "console", "shell-session", "terminal"];
+const PATH_PREFIXES: &[&str] = &["file:", "filename:", "path:"];
+const BARE_FILE_NAMES: &[&str] = &["Makefile", "Dockerfile", "Justfile", "Gemfile", "Procfile"];
+
+fn is_relative_file_path(value: &str) -> bool {
+    if value.is_empty()
+        || value.len() > 200
+        || value.starts_with(['/', '~', '-'])
+        || value.contains("://")
+        || value
+            .split('/')
+            .any(|segment| segment == ".." || segment.is_empty())
+    {
+        return false;
+    }
+    if !value
+        .chars()
+        .all(|ch| ch.is_alphanumeric() || matches!(ch, '/' | '.' | '_' | '-' | '@' | '+'))
+    {
+        return false;
+    }
+    let name = value.rsplit('/').next().unwrap_or(value);
+    BARE_FILE_NAMES.contains(&name)
+        || name.rsplit_once('.').is_some_and(|(_, ext)| {
+            (1..=10).contains(&ext.len()) && ext.chars().any(|ch| ch.is_ascii_alphabetic())
+        })
+}
+
+/// `rust:src/main.rs`, `rust title="src/main.rs"`, `file=src/main.rs` or a bare `src/main.rs`.
+fn path_from_info(info: &str) -> Option<String> {
+    info.split_whitespace()
+        .flat_map(|token| {
+            let value = token
+                .split_once('=')
+                .map(|(key, value)| match key {
+                    "title" | "file" | "filename" | "path" => value,
+                    _ => "",
+                })
+                .unwrap_or(token);
+            [value, value.split_once(':').map_or("", |(_, path)| path)]
+        })
+        .map(|value| value.trim_matches(['"', '\'']))
+        .find(|value| is_relative_file_path(value))
+        .map(str::to_string)
+}
+
+/// `src/main.rs`, `**src/main.rs**`, `### File: src/main.rs` or `Create `src/main.rs`:`.
+fn path_from_heading(line: &str) -> Option<String> {
+    let line = line
+        .trim()
+        .trim_start_matches(['#', '-', '>'])
+        .trim()
+        .trim_end_matches(':')
+        .trim_matches(['*', '_'])
+        .trim_end_matches(':');
+    let lower = line.to_ascii_lowercase();
+    let line = PATH_PREFIXES
+        .iter()
+        .find_map(|prefix| {
+            lower
+                .starts_with(prefix)
+                .then(|| line[prefix.len()..].trim())
+        })
+        .unwrap_or(line);
+    let whole = line.trim_matches(['`', '*', '_']).trim_end_matches(':');
+    if is_relative_file_path(whole) {
+        return Some(whole.to_string());
+    }
+    // Prose ending in a colon: its single code span is the file name.
+    let spans = line.split('`').skip(1).step_by(2).collect::<Vec<_>>();
+    match spans.as_slice() {
+        [path] if is_relative_file_path(path) => Some(path.to_string()),
+        _ => None,
+    }
+}
+
+/// `(fence, info string)` for a fence line with at most three spaces of indent.
+pub(crate) fn fence_marker(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim_start();
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let ch = trimmed
+        .chars()
+        .next()
+        .filter(|ch| matches!(ch, '`' | '~'))?;
+    let count = trimmed.chars().take_while(|c| *c == ch).count();
+    (count >= 3).then(|| trimmed.split_at(count))
+}
+
+/// Files in `markdown`, last occurrence of each path winning, capped at `MAX_FILE_ARTIFACTS`.
+pub(crate) fn detect_file_artifacts(markdown: &str) -> Vec<FileArtifact> {
+    let mut artifacts: Vec<FileArtifact> = Vec::new();
+    let mut previous: Option<&str> = None;
+    let mut lines = markdown.lines();
+    while let Some(line) = lines.next() {
+        let Some((fence, info)) = fence_marker(line) else {
+            if !line.trim().is_empty() {
+                previous = Some(line);
+            }
+            continue;
+        };
+        let language = info.split([' ', ':', '{']).next().unwrap_or_default();
+        let path = path_from_info(info).or_else(|| previous.and_then(path_from_heading));
+        let mut body = Vec::new();
+        let mut closed = false;
+        for line in lines.by_ref() {
+            if fence_marker(line)
+                .is_some_and(|(close, rest)| close.starts_with(fence) && rest.trim().is_empty())
+            {
+                closed = true;
+                break;
+            }
+            body.push(line);
+        }
+        previous = None;
+        let Some(path) = path else {
+            continue;
+        };
+        if !closed
+            || body.iter().all(|line| line.trim().is_empty())
+            || NON_FILE_LANGUAGES.contains(&language.to_ascii_lowercase().as_str())
+        {
+            continue;
+        }
+        artifacts.retain(|artifact| artifact.path != path);
+        artifacts.push(FileArtifact {
+            path,
+            content: format!("{}\n", body.join("\n")),
+        });
+    }
+    let excess = artifacts.len().saturating_sub(MAX_FILE_ARTIFACTS);
+    artifacts.drain(..excess);
+    artifacts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::detect_file_artifacts;
+
+    #[test]
+    fn fenced_blocks_named_by_heading_or_info_string_are_artifacts() {
+        let answer = "\
+Here is the layout.
+
+**`src/main.rs`**
+
+```rust
+fn main() {
+    println!(\"hi\");
+}
+```
+
+Then create `Cargo.toml`:
+```toml
+[package]
+name = \"demo\"
+```
+
+```rust title=\"src/lib.rs\"
+pub fn lib() {}
+```
+
+Run it like this:
+```sh
+cargo run
+```
+
+### src/fix.diff
+```diff
+-a
++b
+```
+
+Unfinished `notes.md`:
+```md
+never closed";
+        let artifacts = detect_file_artifacts(answer);
+        let paths = artifacts
+            .iter()
+            .map(|artifact| artifact.path.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(paths, vec!["src/main.rs", "Cargo.toml", "src/lib.rs"]);
+        assert_eq!(
+            artifacts[0].content,
+            "fn main() {\n    println!(\"hi\");\n}\n"
+        );
+        assert_eq!(artifacts[0].line_count(), 3);
+    }
+
+    #[test]
+    fn unsafe_or_prose_names_are_not_paths() {
+        for heading in [
+            "/etc/passwd:",
+            "../outside.rs:",
+            "Use `a.rs` and `b.rs`:",
+            "https://example.com/x.js",
+            "Example output",
+        ] {
+            let answer = format!("{heading}\n```\nbody\n```\n");
+            assert!(detect_file_artifacts(&answer).is_empty(), "{heading}");
+        }
+        let repeated = "a.txt\n```\none\n```\na.txt\n```\ntwo\n```\n";
+        let artifacts = detect_file_artifacts(repeated);
+        assert_eq!(artifacts.len(), 1);
+        assert_eq!(artifacts[0].content, "two\n");
+    }
+}
@@ -1,3 +1,52 @@
+/// `composer_wrap` setting: how a logical line wider than the composer is shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum ComposerWrap {
+    /// Soft-wrap onto continuation rows.
+    #[default]
+    Wrap,
+    /// One row per logical line, scrolled sideways to keep the cursor visible.
+    Scroll,
+}
+
+impl ComposerWrap {
+    pub(crate) const VALUES: &'static str = "wrap|scroll";
+
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "wrap" => Some(Self::Wrap),
+            "scroll" => Some(Self::Scroll),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Wrap => "wrap",
+            Self::Scroll => "scroll",
+        }
+    }
+}
+
+/// Undo steps kept per composer, and their total chars (large pastes); the oldest go first.
+const MAX_UNDO_STEPS: usize = 200;
+const MAX_UNDO_CHARS: usize = 2_000_000;
+
+/// Edits that merge with the one before them into a single undo step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditGroup {
+    /// Typed characters, one step per word (a new step starts at each word's first char).
+    Typing,
+    /// Single-char `Backspace`/`Delete` runs.
+    Deleting,
+}
+
+/// Buffer and cursor before an edit.
+#[derive(Debug, Clone)]
+struct UndoStep {
+    buffer: Vec<char>,
+    cursor: usize,
+}
+
 #[derive(Default)]
 pub struct InputState {
     pub buffer: Vec<char>,
@@ -6,6 +55,12 @@ pub struct InputState {
     pub history_index: Option<usize>,
     pub history_saved: Option<String>,
     preferred_column: Option<usize>,
+    /// Where a Shift+movement selection started; the cursor is its other end.
+    selection_anchor: Option<usize>,
+    undo_steps: Vec<UndoStep>,
+    redo_steps: Vec<UndoStep>,
+    /// The open coalescing group and the cursor it ended at; typing elsewhere starts a new step.
+    open_group: Option<(EditGroup, usize)>,
 }
 
 impl InputState {
@@ -21,81 +76,289 @@ impl InputState {
             .map(|ch| if *ch == '\n' { '\n' } else { mask })
             .collect();
         clone.cursor = self.cursor.min(clone.buffer.len());
+        clone.selection_anchor = self.selection_anchor;
         clone
     }
 
+    pub fn selection_anchor(&self) -> Option<usize> {
+        self.selection_anchor
+    }
+
+    pub fn set_selection_anchor(&mut self, anchor: Option<usize>) {
+        self.selection_anchor = anchor;
+    }
+
+    /// The selected chars, or `None` when nothing is selected.
+    pub fn selection(&self) -> Option<std::ops::Range<usize>> {
+        let len = self.buffer.len();
+        let anchor = self.selection_anchor?.min(len);
+        let cursor = self.cursor.min(len);
+        (anchor != cursor).then(|| anchor.min(cursor)..anchor.max(cursor))
+    }
+
+    /// Runs a cursor movement while keeping (or starting) the selection at the old cursor.
+    pub fn extend_selection(&mut self, movement: impl FnOnce(&mut Self)) {
+        let anchor = self.selection_anchor.unwrap_or(self.cursor);
+        movement(self);
+        self.selection_anchor = Some(anchor);
+    }
+
+    /// Removes the selected chars and puts the cursor where they were; false without a selection.
+    fn delete_selection(&mut self) -> bool {
+        let selection = self.selection();
+        self.selection_anchor = None;
+        let Some(range) = selection else {
+            return false;
+        };
+        self.cursor = range.start;
+        self.buffer.drain(range);
+        self.preferred_column = None;
+        self.reset_history_nav();
+        true
+    }
+
+    /// Runs an edit and records the buffer before it as an undo step, unless the edit
+    /// continues the open `group`. Edits that leave the buffer unchanged record nothing.
+    fn record_edit(&mut self, group: Option<EditGroup>, edit: impl FnOnce(&mut Self)) {
+        let before = UndoStep {
+            buffer: self.buffer.clone(),
+            cursor: self.cursor,
+        };
+        let continues =
+            group.is_some() && self.open_group == group.map(|group| (group, self.cursor));
+        edit(self);
+        if self.buffer == before.buffer {
+            return;
+        }
+        if !continues {
+            self.undo_steps.push(before);
+            let mut chars = self
+                .undo_steps
+                .iter()
+                .map(|step| step.buffer.len())
+                .sum::<usize>();
+            while self.undo_steps.len() > MAX_UNDO_STEPS
+                || (chars > MAX_UNDO_CHARS && self.undo_steps.len() > 1)
+            {
+                chars -= self.undo_steps.remove(0).buffer.len();
+            }
+        }
+        self.redo_steps.clear();
+        self.open_group = group.map(|group| (group, self.cursor));
+    }
+
+    fn restore_step(&mut self, step: UndoStep) {
+        self.buffer = step.buffer;
+        self.cursor = step.cursor.min(self.buffer.len());
+        self.open_group = None;
+        self.preferred_column = None;
+        self.selection_anchor = None;
+        self.reset_history_nav();
+    }
+
+    /// Reverts the last edit step; false when there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(step) = self.undo_steps.pop() else {
+            return false;
+        };
+        self.redo_steps.push(UndoStep {
+            buffer: std::mem::take(&mut self.buffer),
+            cursor: self.cursor,
+        });
+        self.restore_step(step);
+        true
+    }
+
+    /// Re-applies the last undone step; false when there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(step) = self.redo_steps.pop() else {
+            return false;
+        };
+        self.undo_steps.push(UndoStep {
+            buffer: std::mem::take(&mut self.buffer),
+            cursor: self.cursor,
+        });
+        self.restore_step(step);
+        true
+    }
+
     pub fn set_from(&mut self, value: &str) {
-        self.buffer = value.chars().collect();
+        self.record_edit(None, |input| {
+            input.buffer = value.chars().collect();
+        });
         self.cursor = self.buffer.len();
+        self.open_group = None;
         self.preferred_column = None;
+        self.selection_anchor = None;
     }
 
+    /// Deletes every occurrence of `needle` as one undo step, keeping the cursor on the same
+    /// surrounding text; false when there was none.
+    pub fn remove_occurrences(&mut self, needle: &str) -> bool {
+        let needle = needle.chars().collect::<Vec<_>>();
+        if needle.is_empty() {
+            return false;
+        }
+        let before = self.buffer.len();
+        self.record_edit(None, |input| {
+            let mut kept = Vec::with_capacity(input.buffer.len());
+            let mut cursor = input.cursor;
+            let mut index = 0;
+            while index < input.buffer.len() {
+                if input.buffer[index..].starts_with(&needle) {
+                    if index < input.cursor {
+                        cursor -= input.cursor.min(index + needle.len()) - index;
+                    }
+                    index += needle.len();
+                } else {
+                    kept.push(input.buffer[index]);
+                    index += 1;
+                }
+            }
+            input.buffer = kept;
+            input.cursor = cursor;
+        });
+        let removed = self.buffer.len() != before;
+        if removed {
+            self.open_group = None;
+            self.preferred_column = None;
+            self.selection_anchor = None;
+        }
+        removed
+    }
+
+    /// Empties the composer; the cleared text stays one undo step away.
     pub fn clear(&mut self) {
-        self.buffer.clear();
+        self.record_edit(None, |input| input.buffer.clear());
         self.cursor = 0;
         self.history_index = None;
         self.history_saved = None;
         self.preferred_column = None;
+        self.selection_anchor = None;
     }
 
+    /// Typing over a selection replaces it.
     pub fn insert_char(&mut self, ch: char) {
-        self.buffer.insert(self.cursor, ch);
-        self.cursor += 1;
+        // A word's first char opens a new step, so undo takes back one word at a time.
+        let starts_word = !ch.is_whitespace()
+            && self.cursor > 0
+            && self
+                .buffer
+                .get(self.cursor - 1)
+                .is_some_and(|prev| prev.is_whitespace());
+        let group = (self.selection().is_none() && !starts_word).then_some(EditGroup::Typing);
+        self.record_edit(group, |input| {
+            input.delete_selection();
+            input.buffer.insert(input.cursor, ch);
+            input.cursor += 1;
+        });
+        self.open_group = Some((EditGroup::Typing, self.cursor));
         self.preferred_column = None;
         self.reset_history_nav();
     }
 
+    /// Pastes and other multi-char inserts are one undo step each.
     pub fn insert_str(&mut self, value: &str) {
-        for ch in value.chars() {
-            self.buffer.insert(self.cursor, ch);
-            self.cursor += 1;
-        }
+        self.record_edit(None, |input| {
+            input.delete_selection();
+            for ch in value.chars() {
+                input.buffer.insert(input.cursor, ch);
+                input.cursor += 1;
+            }
+        });
         self.preferred_column = None;
         self.reset_history_nav();
     }
 
     pub fn backspace(&mut self) {
-        if self.cursor == 0 {
-            return;
-        }
-        self.cursor -= 1;
-        self.buffer.remove(self.cursor);
-        self.preferred_column = None;
-        self.reset_history_nav();
+        let group = self.selection().is_none().then_some(EditGroup::Deleting);
+        self.record_edit(group, |input| {
+            if input.delete_selection() || input.cursor == 0 {
+                return;
+            }
+            input.cursor -= 1;
+            input.buffer.remove(input.cursor);
+            input.preferred_column = None;
+            input.reset_history_nav();
+        });
     }
 
     pub fn delete(&mut self) {
-        if self.cursor >= self.buffer.len() {
-            return;
-        }
-        self.buffer.remove(self.cursor);
-        self.preferred_column = None;
-        self.reset_history_nav();
+        let group = self.selection().is_none().then_some(EditGroup::Deleting);
+        self.record_edit(group, |input| {
+            if input.delete_selection() || input.cursor >= input.buffer.len() {
+                return;
+            }
+            input.buffer.remove(input.cursor);
+            input.preferred_column = None;
+            input.reset_history_nav();
+        });
     }
 
     pub fn move_left(&mut self) {
+        self.selection_anchor = None;
         self.cursor = self.cursor.saturating_sub(1);
         self.preferred_column = None;
     }
 
     pub fn move_right(&mut self) {
+        self.selection_anchor = None;
         if self.cursor < self.buffer.len() {
             self.cursor += 1;
         }
         self.preferred_column = None;
     }
 
+    /// Start of the current logical line; from there, start of the whole input.
     pub fn move_home(&mut self) {
-        self.cursor = 0;
+        self.selection_anchor = None;
+        let cursor = self.cursor.min(self.buffer.len());
+        let start = self.line_start(cursor);
+        self.cursor = if cursor == start { 0 } else { start };
         self.preferred_column = None;
     }
 
+    /// End of the current logical line; from there, end of the whole input.
     pub fn move_end(&mut self) {
-        self.cursor = self.buffer.len();
+        self.selection_anchor = None;
+        let len = self.buffer.len();
+        let cursor = self.cursor.min(len);
+        let end = self.find_next_newline(cursor).unwrap_or(len);
+        self.cursor = if cursor == end { len } else { end };
+        self.preferred_column = None;
+    }
+
+    /// Back to the start of the previous whitespace-separated word.
+    pub fn move_word_left(&mut self) {
+        self.selection_anchor = None;
+        let mut cursor = self.cursor.min(self.buffer.len());
+        while cursor > 0 && self.buffer[cursor - 1].is_whitespace() {
+            cursor -= 1;
+        }
+        while cursor > 0 && !self.buffer[cursor - 1].is_whitespace() {
+            cursor -= 1;
+        }
+        self.cursor = cursor;
+        self.preferred_column = None;
+    }
+
+    /// Forward to the end of the next whitespace-separated word.
+    pub fn move_word_right(&mut self) {
+        self.selection_anchor = None;
+        let len = self.buffer.len();
+        let mut cursor = self.cursor.min(len);
+        while cursor < len && self.buffer[cursor].is_whitespace() {
+            cursor += 1;
+        }
+        while cursor < len && !self.buffer[cursor].is_whitespace() {
+            cursor += 1;
+        }
+        self.cursor = cursor;
         self.preferred_column = None;
     }
 
     pub fn move_up(&mut self) -> bool {
+        self.selection_anchor = None;
         let cursor = self.cursor.min(self.buffer.len());
         let current_start = self.line_start(cursor);
         if current_start == 0 {
@@ -115,6 +378,7 @@ impl InputState {
     }
 
     pub fn move_down(&mut self) -> bool {
+        self.selection_anchor = None;
         let len = self.buffer.len();
         let cursor = self.cursor.min(len);
         let current_start = self.line_start(cursor);
@@ -139,27 +403,46 @@ impl InputState {
     }
 
     pub fn kill_to_end(&mut self) {
-        if self.cursor < self.buffer.len() {
-            self.buffer.truncate(self.cursor);
-        }
+        self.selection_anchor = None;
+        self.record_edit(None, |input| {
+            if input.cursor < input.buffer.len() {
+                input.buffer.truncate(input.cursor);
+            }
+        });
         self.preferred_column = None;
         self.reset_history_nav();
     }
 
     pub fn delete_word_back(&mut self) {
-        if self.cursor == 0 {
-            return;
-        }
-        while self.cursor > 0 && self.buffer[self.cursor - 1].is_whitespace() {
-            self.cursor -= 1;
-            self.buffer.remove(self.cursor);
-        }
-        while self.cursor > 0 && !self.buffer[self.cursor - 1].is_whitespace() {
-            self.cursor -= 1;
-            self.buffer.remove(self.cursor);
-        }
-        self.preferred_column = None;
-        self.reset_history_nav();
+        self.record_edit(None, |input| {
+            if input.delete_selection() || input.cursor == 0 {
+                return;
+            }
+            while input.cursor > 0 && input.buffer[input.cursor - 1].is_whitespace() {
+                input.cursor -= 1;
+                input.buffer.remove(input.cursor);
+            }
+            while input.cursor > 0 && !input.buffer[input.cursor - 1].is_whitespace() {
+                input.cursor -= 1;
+                input.buffer.remove(input.cursor);
+            }
+            input.preferred_column = None;
+            input.reset_history_nav();
+        });
+    }
+
+    /// Deletes up to the end of the next whitespace-separated word, like `move_word_right`.
+    pub fn delete_word_forward(&mut self) {
+        self.record_edit(None, |input| {
+            if input.delete_selection() || input.cursor >= input.buffer.len() {
+                return;
+            }
+            let start = input.cursor;
+            input.move_word_right();
+            input.buffer.drain(start..input.cursor);
+            input.cursor = start;
+            input.reset_history_nav();
+        });
     }
 
     fn reset_history_nav(&mut self) {
@@ -249,6 +532,99 @@ mod tests {
         assert_eq!(input.cursor, 13);
     }
 
+    #[test]
+    fn home_end_and_word_moves_follow_logical_lines() {
+        let mut input = InputState::default();
+        input.set_from("first line\n  second  word");
+        input.move_home();
+        assert_eq!(input.cursor, 11);
+        input.move_home();
+        assert_eq!(input.cursor, 0);
+        input.move_end();
+        assert_eq!(input.cursor, 10);
+        input.move_end();
+        assert_eq!(input.buffer.len(), input.cursor);
+
+        input.move_word_left();
+        assert_eq!(input.cursor, 21);
+        input.move_word_left();
+        assert_eq!(input.cursor, 13);
+        input.move_word_left();
+        assert_eq!(input.cursor, 6);
+        input.move_word_right();
+        assert_eq!(input.cursor, 10);
+        input.move_word_right();
+        assert_eq!(input.cursor, 19);
+    }
+
+    #[test]
+    fn shift_selection_is_replaced_or_deleted_by_edits() {
+        let mut input = InputState::default();
+        input.set_from("alpha beta gamma");
+        input.cursor = 6;
+        input.extend_selection(InputState::move_word_right);
+        input.extend_selection(InputState::move_right);
+        assert_eq!(input.selection(), Some(6..11));
+
+        input.insert_char('B');
+        assert_eq!(input.current(), "alpha Bgamma");
+        assert_eq!((input.cursor, input.selection()), (7, None));
+
+        input.extend_selection(InputState::move_home);
+        input.backspace();
+        assert_eq!(input.current(), "gamma");
+
+        input.extend_selection(InputState::move_end);
+        input.move_left();
+        assert_eq!(input.selection(), None);
+
+        input.set_from("one two  three");
+        input.cursor = 3;
+        input.delete_word_forward();
+        assert_eq!(input.current(), "one  three");
+        input.delete_word_forward();
+        assert_eq!((input.current(), input.cursor), ("one".to_string(), 3));
+    }
+
+    #[test]
+    fn undo_takes_back_words_pastes_and_kills_one_step_at_a_time() {
+        let mut input = InputState::default();
+        for ch in "fix the bug".chars() {
+            input.insert_char(ch);
+        }
+        input.insert_str(" in parser.rs");
+        input.backspace();
+        input.backspace();
+        input.delete_word_back();
+        assert_eq!(input.current(), "fix the bug in ");
+
+        assert!(input.undo());
+        assert_eq!(input.current(), "fix the bug in parser.");
+        assert!(input.undo());
+        assert_eq!(input.current(), "fix the bug in parser.rs");
+        assert!(input.undo());
+        assert_eq!(
+            (input.current(), input.cursor),
+            ("fix the bug".to_string(), 11)
+        );
+        assert!(input.undo());
+        assert_eq!(input.current(), "fix the ");
+        assert!(input.redo());
+        assert!(input.redo());
+        assert_eq!(input.current(), "fix the bug in parser.rs");
+
+        // A new edit drops the redo steps; clearing the composer is one step too.
+        input.insert_char('!');
+        assert!(!input.redo());
+        input.clear();
+        assert!(input.undo());
+        assert_eq!(input.current(), "fix the bug in parser.rs!");
+        while input.undo() {}
+        assert_eq!(input.current(), "");
+        input.backspace();
+        assert!(!input.undo());
+    }
+
     #[test]
     fn move_up_down_returns_false_at_boundaries() {
         let mut input = InputState::default();
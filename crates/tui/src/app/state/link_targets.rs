@@ -0,0 +1,127 @@
+/// Something in the log the quick-jump menu (Alt+O) can open.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum LinkTarget {
+    Url(String),
+    Path { path: String, line: Option<usize> },
+}
+
+impl LinkTarget {
+    pub(crate) fn label(&self) -> String {
+        match self {
+            LinkTarget::Url(url) => url.clone(),
+            LinkTarget::Path {
+                path,
+                line: Some(line),
+            } => format!("{path}:{line}"),
+            LinkTarget::Path { path, line: None } => path.clone(),
+        }
+    }
+}
+
+/// Quick-pick digits 1-9 address every entry.
+pub(crate) const MAX_LINK_TARGETS: usize = 9;
+
+const EDGE_PUNCTUATION: &[char] = &[
+    '"', '\'', '`', '(', ')', '[', ']', '<', '>', '{', '}', ',', ';', '.', ':', '!', '?', '*',
+];
+
+fn url_target(token: &str) -> Option<LinkTarget> {
+    let start = token.find("https://").or_else(|| token.find("http://"))?;
+    let url = token[start..].trim_end_matches(EDGE_PUNCTUATION);
+    // A closing paren that belongs to markdown `[text](url)` rather than to the URL.
+    let url = if url.ends_with(')') && !url.contains('(') {
+        url.trim_end_matches(')')
+    } else {
+        url
+    };
+    url.split_once("://")
+        .is_some_and(|(_, host)| !host.is_empty())
+        .then(|| LinkTarget::Url(url.to_string()))
+}
+
+fn looks_like_path(value: &str) -> bool {
+    if value.is_empty() || value.starts_with('-') || value.contains("://") {
+        return false;
+    }
+    if !value
+        .chars()
+        .all(|ch| ch.is_alphanumeric() || matches!(ch, '/' | '.' | '_' | '-' | '~' | '@' | '+'))
+    {
+        return false;
+    }
+    if value.contains('/') {
+        return value.chars().any(char::is_alphanumeric);
+    }
+    // Bare names need an extension (`main.rs`) so plain words and versions are skipped.
+    value.rsplit_once('.').is_some_and(|(stem, ext)| {
+        !stem.is_empty()
+            && (1..=8).contains(&ext.len())
+            && ext.chars().all(|ch| ch.is_ascii_alphanumeric())
+            && ext.chars().any(|ch| ch.is_ascii_alphabetic())
+    })
+}
+
+/// `src/main.rs:42:7` style tokens; the line (if any) is kept, the column dropped.
+fn path_target(token: &str) -> Option<LinkTarget> {
+    let token = token.trim_matches(EDGE_PUNCTUATION);
+    let mut parts = token.split(':');
+    let path = parts.next()?;
+    let line = parts.next().and_then(|value| value.parse::<usize>().ok());
+    looks_like_path(path).then(|| LinkTarget::Path {
+        path: path.to_string(),
+        line: line.filter(|line| *line > 0),
+    })
+}
+
+/// URLs and path-like tokens in `lines`, newest (last) line first, without duplicates.
+pub(crate) fn extract_link_targets<S: AsRef<str>>(lines: &[S]) -> Vec<LinkTarget> {
+    let mut targets = Vec::new();
+    for line in lines.iter().rev() {
+        for token in line.as_ref().split_whitespace() {
+            let Some(target) = url_target(token).or_else(|| path_target(token)) else {
+                continue;
+            };
+            if !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+    }
+    targets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{extract_link_targets, LinkTarget};
+
+    #[test]
+    fn extracts_urls_and_paths_newest_first() {
+        let lines = [
+            "See [docs](https://example.com/guide#setup).",
+            "error at src/app/mod.rs:42:7 while reading Cargo.toml, v1.2",
+            "plain words - and --flags",
+            "https://example.com/guide#setup again",
+        ];
+        assert_eq!(
+            extract_link_targets(&lines),
+            vec![
+                LinkTarget::Url("https://example.com/guide#setup".to_string()),
+                LinkTarget::Path {
+                    path: "src/app/mod.rs".to_string(),
+                    line: Some(42),
+                },
+                LinkTarget::Path {
+                    path: "Cargo.toml".to_string(),
+                    line: None,
+                },
+            ]
+        );
+        assert_eq!(
+            LinkTarget::Path {
+                path: "a.rs".to_string(),
+                line: Some(3)
+            }
+            .label(),
+            "a.rs:3"
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+/// Last-row announcement for the `accessible` setting. Screen readers follow the cursor,
+/// so a new announcement parks it at the end of the row until the next key.
+#[derive(Debug, Default)]
+pub(crate) struct LiveRegion {
+    text: Option<String>,
+    /// Times the same text was announced in a row; shown so the row changes and is read again.
+    repeats: usize,
+    pending: bool,
+}
+
+impl LiveRegion {
+    pub(crate) fn announce(&mut self, text: impl Into<String>) {
+        let text = text.into();
+        if self.text.as_deref() == Some(text.as_str()) {
+            self.repeats += 1;
+        } else {
+            self.text = Some(text);
+            self.repeats = 1;
+        }
+        self.pending = true;
+    }
+
+    /// The row as drawn, e.g. `Run started` or `Run started (2)` for a repeat.
+    pub(crate) fn row(&self) -> Option<String> {
+        let text = self.text.as_deref()?;
+        if self.repeats > 1 {
+            return Some(format!("{text} ({})", self.repeats));
+        }
+        Some(text.to_string())
+    }
+
+    /// An announcement has not been followed by a key yet.
+    pub(crate) fn holds_cursor(&self) -> bool {
+        self.pending && self.text.is_some()
+    }
+
+    /// A key was pressed: the cursor goes back to the composer.
+    pub(crate) fn settle(&mut self) {
+        self.pending = false;
+    }
+}
+
+/// Spelled out for speech: `42 seconds`, `3 minutes 5 seconds`.
+fn spoken_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let unit =
+        |count: u64, name: &str| format!("{count} {name}{}", if count == 1 { "" } else { "s" });
+    if secs < 60 {
+        return unit(secs, "second");
+    }
+    let minutes = unit(secs / 60, "minute");
+    match secs % 60 {
+        0 => minutes,
+        rest => format!("{minutes} {}", unit(rest, "second")),
+    }
+}
+
+/// What a run status change says aloud; `None` for changes inside a run.
+pub(crate) fn run_status_announcement(
+    previous: Option<&str>,
+    status: &str,
+    elapsed: Option<Duration>,
+) -> Option<String> {
+    let active =
+        |status: Option<&str>| matches!(status, Some("starting" | "running" | "awaiting_ui"));
+    let took = || {
+        elapsed
+            .map(|elapsed| format!(" after {}", spoken_duration(elapsed)))
+            .unwrap_or_default()
+    };
+    match status {
+        "starting" | "running" if !active(previous) => Some("Run started".to_string()),
+        "completed" => Some(format!("Run completed{}", took())),
+        "error" => Some(format!("Run failed{}", took())),
+        "cancelled" => Some("Run cancelled".to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_status_announcement, LiveRegion};
+    use std::time::Duration;
+
+    #[test]
+    fn run_transitions_are_announced_and_repeats_change_the_row() {
+        assert_eq!(
+            run_status_announcement(None, "starting", None).as_deref(),
+            Some("Run started")
+        );
+        assert_eq!(
+            run_status_announcement(Some("starting"), "running", None),
+            None
+        );
+        assert_eq!(
+            run_status_announcement(Some("awaiting_ui"), "running", None),
+            None
+        );
+        assert_eq!(
+            run_status_announcement(Some("running"), "completed", Some(Duration::from_secs(185)))
+                .as_deref(),
+            Some("Run completed after 3 minutes 5 seconds")
+        );
+        assert_eq!(
+            run_status_announcement(Some("running"), "error", Some(Duration::from_secs(1)))
+                .as_deref(),
+            Some("Run failed after 1 second")
+        );
+
+        let mut region = LiveRegion::default();
+        assert!(!region.holds_cursor());
+        region.announce("Run started");
+        assert!(region.holds_cursor());
+        region.settle();
+        assert!(!region.holds_cursor());
+        region.announce("Run started");
+        assert_eq!(region.row().as_deref(), Some("Run started (2)"));
+        region.announce("Run completed");
+        assert_eq!(region.row().as_deref(), Some("Run completed"));
+    }
+}
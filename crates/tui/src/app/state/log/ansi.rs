@@ -0,0 +1,162 @@
+use super::LogColor;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Off by default: tool output is stripped of every escape sequence unless `ansi_colors` is on.
+static ANSI_COLOR_PASSTHROUGH: AtomicBool = AtomicBool::new(false);
+
+pub(crate) fn set_ansi_color_passthrough(enabled: bool) {
+    ANSI_COLOR_PASSTHROUGH.store(enabled, Ordering::Relaxed);
+}
+
+pub(crate) fn ansi_color_passthrough() -> bool {
+    ANSI_COLOR_PASSTHROUGH.load(Ordering::Relaxed)
+}
+
+const BASIC_COLORS: [LogColor; 8] = [
+    // Black would vanish on dark themes; render it as a dim gray instead.
+    LogColor::rgb(0x5c, 0x63, 0x70),
+    LogColor::rgb(0xe0, 0x6c, 0x75),
+    LogColor::rgb(0x98, 0xc3, 0x79),
+    LogColor::rgb(0xe5, 0xc0, 0x7b),
+    LogColor::rgb(0x61, 0xaf, 0xef),
+    LogColor::rgb(0xc6, 0x78, 0xdd),
+    LogColor::rgb(0x56, 0xb6, 0xc2),
+    LogColor::rgb(0xdc, 0xdf, 0xe4),
+];
+
+const BRIGHT_COLORS: [LogColor; 8] = [
+    LogColor::rgb(0x7f, 0x84, 0x8e),
+    LogColor::rgb(0xff, 0x7b, 0x86),
+    LogColor::rgb(0xb5, 0xe8, 0x90),
+    LogColor::rgb(0xff, 0xd6, 0x8a),
+    LogColor::rgb(0x7d, 0xc4, 0xff),
+    LogColor::rgb(0xde, 0x8e, 0xf5),
+    LogColor::rgb(0x6c, 0xd0, 0xdc),
+    LogColor::rgb(0xff, 0xff, 0xff),
+];
+
+/// xterm 256-color palette: 16 named colors, a 6x6x6 cube, then a 24-step gray ramp.
+fn palette_color(index: u16) -> Option<LogColor> {
+    let index = u8::try_from(index).ok()?;
+    let color = match index {
+        0..=7 => BASIC_COLORS[usize::from(index)],
+        8..=15 => BRIGHT_COLORS[usize::from(index - 8)],
+        16..=231 => {
+            let cube = index - 16;
+            let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+            LogColor::rgb(level(cube / 36), level((cube / 6) % 6), level(cube % 6))
+        }
+        _ => {
+            let gray = 8 + (index - 232) * 10;
+            LogColor::rgb(gray, gray, gray)
+        }
+    };
+    Some(color)
+}
+
+/// Applies one SGR parameter list to `fg`; attributes and backgrounds are ignored.
+fn apply_sgr(params: &str, fg: &mut Option<LogColor>) {
+    let mut codes = params
+        .split([';', ':'])
+        .map(|code| code.parse::<u16>().unwrap_or(0));
+    while let Some(code) = codes.next() {
+        match code {
+            0 | 39 => *fg = None,
+            30..=37 => *fg = Some(BASIC_COLORS[usize::from(code - 30)]),
+            90..=97 => *fg = Some(BRIGHT_COLORS[usize::from(code - 90)]),
+            38 | 48 => {
+                let color = match codes.next() {
+                    Some(5) => codes.next().and_then(palette_color),
+                    Some(2) => {
+                        let mut channel =
+                            || codes.next().and_then(|value| u8::try_from(value).ok());
+                        match (channel(), channel(), channel()) {
+                            (Some(r), Some(g), Some(b)) => Some(LogColor::rgb(r, g, b)),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                };
+                if code == 38 && color.is_some() {
+                    *fg = color;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Splits `text` into runs colored by its SGR sequences.
+///
+/// Other CSI sequences (cursor movement, erase) are dropped; anything else is left for
+/// `sanitize_for_tui` to neutralize when the runs become spans.
+pub(crate) fn sgr_color_runs(text: &str) -> Vec<(String, Option<LogColor>)> {
+    let mut runs = Vec::new();
+    let mut current = String::new();
+    let mut fg = None;
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch != '\x1b' || chars.peek() != Some(&'[') {
+            current.push(ch);
+            continue;
+        }
+        chars.next();
+        let mut params = String::new();
+        let mut final_byte = None;
+        for next in chars.by_ref() {
+            if (0x40..=0x7e).contains(&(next as u32)) {
+                final_byte = Some(next);
+                break;
+            }
+            params.push(next);
+        }
+        if final_byte != Some('m') {
+            continue;
+        }
+        let mut next_fg = fg;
+        apply_sgr(&params, &mut next_fg);
+        if next_fg != fg {
+            if !current.is_empty() {
+                runs.push((std::mem::take(&mut current), fg));
+            }
+            fg = next_fg;
+        }
+    }
+    if !current.is_empty() || runs.is_empty() {
+        runs.push((current, fg));
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sgr_color_runs, BASIC_COLORS, BRIGHT_COLORS};
+    use crate::app::state::LogColor;
+
+    #[test]
+    fn sgr_runs_keep_colors_and_drop_cursor_movement() {
+        let runs =
+            sgr_color_runs("\x1b[1;32mok\x1b[0m 3 \x1b[91mfailed\x1b[39m\x1b[2K\x1b[1A done");
+        assert_eq!(
+            runs,
+            vec![
+                ("ok".to_string(), Some(BASIC_COLORS[2])),
+                (" 3 ".to_string(), None),
+                ("failed".to_string(), Some(BRIGHT_COLORS[1])),
+                (" done".to_string(), None),
+            ]
+        );
+        assert_eq!(
+            sgr_color_runs("\x1b[38;2;1;2;3mrgb\x1b[48;5;4m bg\x1b[38;5;196mcube"),
+            vec![
+                ("rgb bg".to_string(), Some(LogColor::rgb(1, 2, 3))),
+                ("cube".to_string(), Some(LogColor::rgb(255, 0, 0))),
+            ]
+        );
+        assert_eq!(sgr_color_runs("plain"), vec![("plain".to_string(), None)]);
+        assert_eq!(
+            sgr_color_runs("\x1b[31m"),
+            vec![(String::new(), Some(BASIC_COLORS[1]))]
+        );
+    }
+}
@@ -1,4 +1,9 @@
+mod ansi;
+
 use crate::app::util::text::sanitize_for_tui;
+use std::sync::Arc;
+
+pub(crate) use ansi::{ansi_color_passthrough, set_ansi_color_passthrough};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum LogKind {
@@ -51,6 +56,18 @@ pub struct LogSpan {
     pub tone: LogTone,
     pub text: String,
     pub fg: Option<LogColor>,
+    /// Target of a Markdown link or bare URL; rendered underlined and emitted as OSC 8.
+    pub link: Option<String>,
+    /// Inline image drawn from this cell when the row is written to the terminal scrollback.
+    pub image: Option<Arc<ImagePreview>>,
+}
+
+/// Terminal graphics escape for a thumbnail that covers `rows` log rows, starting at the row
+/// of the span that carries it; built by `render::graphics`.
+#[derive(Debug)]
+pub struct ImagePreview {
+    pub escape: String,
+    pub rows: usize,
 }
 
 impl LogSpan {
@@ -70,22 +87,55 @@ impl LogSpan {
             tone,
             text: sanitize_for_tui(&raw),
             fg,
+            link: None,
+            image: None,
         }
     }
 
+    /// Control chars are dropped from `url` so it cannot end the OSC 8 sequence early.
+    pub fn with_link(mut self, url: &str) -> Self {
+        self.link = Some(url.chars().filter(|ch| !ch.is_control()).collect());
+        self
+    }
+
+    pub fn with_image(mut self, preview: ImagePreview) -> Self {
+        self.image = Some(Arc::new(preview));
+        self
+    }
+
     pub fn with_tone(&self, tone: LogTone) -> Self {
         Self {
             kind: self.kind,
             tone,
             text: self.text.clone(),
             fg: self.fg,
+            link: self.link.clone(),
+            image: self.image.clone(),
         }
     }
 }
 
+/// Where a log line came from; stamped by `AppState` when the line enters the log.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LogMeta {
+    pub run_id: Option<String>,
+    pub tool_call_id: Option<String>,
+    /// 1-based prompt number; `None` before the first prompt.
+    pub turn: Option<u32>,
+    /// Unix time in milliseconds; 0 until stamped.
+    pub at_unix_ms: u64,
+}
+
+impl LogMeta {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct LogLine {
     pub spans: Vec<LogSpan>,
+    pub meta: LogMeta,
 }
 
 impl LogLine {
@@ -94,13 +144,27 @@ impl LogLine {
     }
 
     pub fn new_with_tone(kind: LogKind, tone: LogTone, text: impl Into<String>) -> Self {
-        Self {
-            spans: vec![LogSpan::new(kind, tone, text)],
+        Self::new_with_spans(vec![LogSpan::new(kind, tone, text)])
+    }
+
+    /// Like `new_with_tone`, but SGR colors in `text` become span colors instead of being stripped.
+    pub fn new_with_ansi_colors(kind: LogKind, tone: LogTone, text: &str) -> Self {
+        if !text.contains("\x1b[") {
+            return Self::new_with_tone(kind, tone, text);
         }
+        Self::new_with_spans(
+            ansi::sgr_color_runs(text)
+                .into_iter()
+                .map(|(text, fg)| LogSpan::new_with_fg(kind, tone, text, fg))
+                .collect(),
+        )
     }
 
     pub fn new_with_spans(spans: Vec<LogSpan>) -> Self {
-        Self { spans }
+        Self {
+            spans,
+            meta: LogMeta::default(),
+        }
     }
 
     pub fn spans(&self) -> &[LogSpan] {
@@ -0,0 +1,201 @@
+/// One query hit inside a wrapped log row; `start` and `len` count chars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct LogSearchMatch {
+    pub row: usize,
+    pub start: usize,
+    pub len: usize,
+}
+
+/// Ctrl+F search over the wrapped log. Matches are indexed per (wrap width, log version,
+/// query) and rebuilt only when one of those changes.
+#[derive(Debug, Default)]
+pub(crate) struct LogSearch {
+    pub query: String,
+    /// Keys edit the query; after Enter they move between matches instead.
+    pub editing: bool,
+    matches: Vec<LogSearchMatch>,
+    current: Option<usize>,
+    indexed_for: Option<(usize, u64, String)>,
+}
+
+impl LogSearch {
+    pub(crate) fn open() -> Self {
+        Self {
+            editing: true,
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn is_indexed(&self, width: usize, log_version: u64) -> bool {
+        self.indexed_for
+            .as_ref()
+            .is_some_and(|(w, v, q)| *w == width && *v == log_version && *q == self.query)
+    }
+
+    /// Replaces the index; the current match stays on the same row when it still matches,
+    /// otherwise selection restarts from the newest match on the next step.
+    pub(crate) fn set_matches(
+        &mut self,
+        width: usize,
+        log_version: u64,
+        matches: Vec<LogSearchMatch>,
+    ) {
+        let query_changed = self
+            .indexed_for
+            .as_ref()
+            .is_none_or(|(_, _, query)| *query != self.query);
+        let current_row = self.current_match().map(|found| found.row);
+        self.current = match current_row {
+            Some(row) if !query_changed => matches.iter().position(|found| found.row >= row),
+            _ => None,
+        };
+        self.matches = matches;
+        self.indexed_for = Some((width, log_version, self.query.clone()));
+    }
+
+    pub(crate) fn matches(&self) -> &[LogSearchMatch] {
+        &self.matches
+    }
+
+    pub(crate) fn current_match(&self) -> Option<LogSearchMatch> {
+        self.current
+            .and_then(|index| self.matches.get(index).copied())
+    }
+
+    /// Moves to the next match up (`older`) or down, wrapping around, among rows at or below
+    /// `first_row`; with nothing selected both directions start from the newest match.
+    pub(crate) fn step(&mut self, older: bool, first_row: usize) -> Option<LogSearchMatch> {
+        let reachable = self.matches.partition_point(|found| found.row < first_row);
+        if reachable == self.matches.len() {
+            self.current = None;
+            return None;
+        }
+        let last = self.matches.len() - 1;
+        let next = match self.current.filter(|index| *index >= reachable) {
+            None => last,
+            Some(index) if older => {
+                if index == reachable {
+                    last
+                } else {
+                    index - 1
+                }
+            }
+            Some(index) => {
+                if index == last {
+                    reachable
+                } else {
+                    index + 1
+                }
+            }
+        };
+        self.current = Some(next);
+        self.current_match()
+    }
+
+    /// `3/12`, with hits already pushed to the terminal's own scrollback counted separately.
+    pub(crate) fn position_label(&self, first_row: usize) -> String {
+        if self.query.is_empty() {
+            return String::new();
+        }
+        let hidden = self.matches.partition_point(|found| found.row < first_row);
+        let reachable = self.matches.len() - hidden;
+        let mut label = match self.current.filter(|index| *index >= hidden) {
+            Some(index) => format!("{}/{reachable}", index - hidden + 1),
+            None if reachable == 0 => "no matches".to_string(),
+            None => format!("{reachable} matches"),
+        };
+        if hidden > 0 {
+            label.push_str(&format!(" (+{hidden} in terminal scrollback)"));
+        }
+        label
+    }
+}
+
+/// Case-insensitive unless the query has an uppercase letter.
+pub(crate) fn find_log_matches(
+    rows: impl IntoIterator<Item = impl AsRef<str>>,
+    query: &str,
+) -> Vec<LogSearchMatch> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let case_sensitive = query.chars().any(char::is_uppercase);
+    let fold = |text: &str| -> Vec<char> {
+        if case_sensitive {
+            text.chars().collect()
+        } else {
+            text.chars().flat_map(char::to_lowercase).collect()
+        }
+    };
+    let needle = fold(query);
+    let mut matches = Vec::new();
+    for (row, text) in rows.into_iter().enumerate() {
+        let text = text.as_ref();
+        let hay = fold(text);
+        // Lowercasing can change the char count (e.g. `İ`); skip such rows rather than
+        // highlight the wrong columns.
+        if hay.len() != text.chars().count() || hay.len() < needle.len() {
+            continue;
+        }
+        let mut start = 0;
+        while start + needle.len() <= hay.len() {
+            if hay[start..start + needle.len()] == needle[..] {
+                matches.push(LogSearchMatch {
+                    row,
+                    start,
+                    len: needle.len(),
+                });
+                start += needle.len();
+            } else {
+                start += 1;
+            }
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_log_matches, LogSearch, LogSearchMatch};
+
+    #[test]
+    fn finds_smart_case_matches_and_steps_from_newest() {
+        let rows = ["Error: disk full", "retrying", "error again, ERROR"];
+        let found = find_log_matches(rows, "error");
+        assert_eq!(
+            found,
+            vec![
+                LogSearchMatch {
+                    row: 0,
+                    start: 0,
+                    len: 5
+                },
+                LogSearchMatch {
+                    row: 2,
+                    start: 0,
+                    len: 5
+                },
+                LogSearchMatch {
+                    row: 2,
+                    start: 13,
+                    len: 5
+                },
+            ]
+        );
+        assert_eq!(find_log_matches(rows, "ERROR").len(), 1);
+
+        let mut search = LogSearch::open();
+        search.query = "error".to_string();
+        search.set_matches(80, 1, found);
+        assert!(search.is_indexed(80, 1));
+        assert_eq!(search.position_label(0), "3 matches");
+        assert_eq!(search.step(true, 0).map(|found| found.start), Some(13));
+        assert_eq!(search.step(true, 0).map(|found| found.row), Some(2));
+        assert_eq!(search.step(true, 0).map(|found| found.row), Some(0));
+        assert_eq!(search.position_label(0), "1/3");
+        // Wraps around, and rows before `first_row` are out of reach.
+        assert_eq!(search.step(true, 1).map(|found| found.start), Some(13));
+        assert_eq!(search.step(false, 1).map(|found| found.start), Some(0));
+        assert_eq!(search.position_label(1), "1/2 (+1 in terminal scrollback)");
+    }
+}
@@ -0,0 +1,98 @@
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// When log lines appeared, kept as `(log length, elapsed)` marks so `/export cast`
+/// can replay the session with its real pacing.
+#[derive(Debug, Clone)]
+pub(crate) struct LogTimeline {
+    started: Instant,
+    started_unix_secs: u64,
+    marks: Vec<(usize, Duration)>,
+}
+
+impl Default for LogTimeline {
+    fn default() -> Self {
+        Self::starting_at(Instant::now())
+    }
+}
+
+impl LogTimeline {
+    fn starting_at(started: Instant) -> Self {
+        Self {
+            started,
+            started_unix_secs: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|value| value.as_secs())
+                .unwrap_or(0),
+            marks: Vec::new(),
+        }
+    }
+
+    /// Notes that the log now holds `len` lines; shrinking logs are ignored.
+    pub(crate) fn record(&mut self, len: usize, now: Instant) {
+        if self.marks.last().is_some_and(|(last, _)| *last >= len) {
+            return;
+        }
+        self.marks
+            .push((len, now.saturating_duration_since(self.started)));
+    }
+
+    /// Follows the log after its first `count` lines were dropped.
+    pub(crate) fn drop_front(&mut self, count: usize) {
+        let kept = self.marks.partition_point(|(len, _)| *len <= count);
+        self.marks.drain(..kept);
+        for (len, _) in &mut self.marks {
+            *len -= count;
+        }
+    }
+
+    /// Follows the log after `count` lines were inserted before line `index`.
+    pub(crate) fn insert_at(&mut self, index: usize, count: usize) {
+        for (len, _) in &mut self.marks {
+            if *len > index {
+                *len += count;
+            }
+        }
+    }
+
+    pub(crate) fn reset(&mut self, now: Instant) {
+        *self = Self::starting_at(now);
+    }
+
+    pub(crate) fn started_unix_secs(&self) -> u64 {
+        self.started_unix_secs
+    }
+
+    /// Elapsed time at which line `index` first appeared (zero if unknown).
+    pub(crate) fn offset_of(&self, index: usize) -> Duration {
+        let position = self.marks.partition_point(|(len, _)| *len <= index);
+        self.marks
+            .get(position)
+            .map(|(_, offset)| *offset)
+            .unwrap_or_else(|| self.marks.last().map(|(_, at)| *at).unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LogTimeline;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn timeline_maps_line_indices_to_first_appearance() {
+        let start = Instant::now();
+        let mut timeline = LogTimeline::starting_at(start);
+        timeline.record(2, start + Duration::from_millis(100));
+        timeline.record(2, start + Duration::from_millis(200));
+        timeline.record(5, start + Duration::from_millis(900));
+
+        assert_eq!(timeline.offset_of(0), Duration::from_millis(100));
+        assert_eq!(timeline.offset_of(1), Duration::from_millis(100));
+        assert_eq!(timeline.offset_of(2), Duration::from_millis(900));
+        assert_eq!(timeline.offset_of(4), Duration::from_millis(900));
+        assert_eq!(timeline.offset_of(9), Duration::from_millis(900));
+
+        timeline.drop_front(3);
+        assert_eq!(timeline.offset_of(0), Duration::from_millis(900));
+        assert_eq!(timeline.offset_of(1), Duration::from_millis(900));
+    }
+}
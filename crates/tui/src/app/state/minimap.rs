@@ -0,0 +1,85 @@
+use super::log::{LogKind, LogLine};
+
+/// What one minimap cell summarizes; later variants win when a cell covers several lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum MinimapMark {
+    Empty,
+    Text,
+    Tool,
+    User,
+    Error,
+}
+
+impl MinimapMark {
+    fn for_kind(kind: LogKind) -> Self {
+        match kind {
+            LogKind::Space => MinimapMark::Empty,
+            LogKind::Error => MinimapMark::Error,
+            LogKind::User => MinimapMark::User,
+            LogKind::ToolCall | LogKind::ToolResult | LogKind::Shell => MinimapMark::Tool,
+            _ => MinimapMark::Text,
+        }
+    }
+}
+
+/// Marks for a `height`-row minimap over every wrapped log line, keyed so redraws reuse them.
+#[derive(Debug, Default)]
+pub(crate) struct MinimapCache {
+    pub log_version: u64,
+    pub width: usize,
+    pub height: usize,
+    pub marks: Vec<MinimapMark>,
+}
+
+/// Splits `lines` into `height` equal buckets and keeps the strongest mark of each.
+pub(crate) fn minimap_marks(lines: &[LogLine], height: usize) -> Vec<MinimapMark> {
+    let mut marks = vec![MinimapMark::Empty; height];
+    if height == 0 || lines.is_empty() {
+        return marks;
+    }
+    for (index, line) in lines.iter().enumerate() {
+        let row = index * height / lines.len();
+        marks[row] = marks[row].max(MinimapMark::for_kind(line.kind()));
+    }
+    marks
+}
+
+/// Minimap rows covering wrapped lines `start..end` of `total`.
+pub(crate) fn minimap_viewport_rows(
+    total: usize,
+    height: usize,
+    start: usize,
+    end: usize,
+) -> std::ops::Range<usize> {
+    if total == 0 || height == 0 {
+        return 0..0;
+    }
+    let first = start.min(total - 1) * height / total;
+    let last = end.saturating_sub(1).clamp(start, total - 1) * height / total;
+    first..last + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{minimap_marks, minimap_viewport_rows, MinimapMark};
+    use crate::app::state::{LogKind, LogLine};
+
+    #[test]
+    fn minimap_keeps_strongest_mark_per_bucket() {
+        let lines = [
+            LogLine::new(LogKind::User, "hi"),
+            LogLine::new(LogKind::Assistant, "hello"),
+            LogLine::new(LogKind::ToolCall, "Bash: ls"),
+            LogLine::new(LogKind::Error, "boom"),
+            LogLine::new(LogKind::Space, ""),
+            LogLine::new(LogKind::Space, ""),
+        ];
+        assert_eq!(
+            minimap_marks(&lines, 3),
+            vec![MinimapMark::User, MinimapMark::Error, MinimapMark::Empty]
+        );
+        assert_eq!(minimap_marks(&lines, 6)[2], MinimapMark::Tool);
+        assert_eq!(minimap_viewport_rows(600, 10, 540, 600), 9..10);
+        assert_eq!(minimap_viewport_rows(6, 12, 0, 6), 0..11);
+    }
+}
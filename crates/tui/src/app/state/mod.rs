@@ -1,20 +1,86 @@
+pub(crate) mod answer_diff;
+pub(crate) mod assistant_messages;
+pub(crate) mod batch;
+pub(crate) mod chord;
+pub(crate) mod connection_health;
+pub(crate) mod context_history;
+pub(crate) mod context_pins;
+pub(crate) mod corrections;
+pub(crate) mod esc_policy;
+pub(crate) mod failure_streak;
+pub(crate) mod file_artifacts;
 pub(crate) mod input;
+pub(crate) mod link_targets;
+pub(crate) mod live_region;
 pub(crate) mod log;
+pub(crate) mod log_search;
+pub(crate) mod log_timeline;
+pub(crate) mod minimap;
+pub(crate) mod permalink;
+pub(crate) mod provider_stats;
 pub(crate) mod render;
+pub(crate) mod run_trace;
+pub(crate) mod scratchpad;
+pub(crate) mod session_notes;
+pub(crate) mod streaming;
+pub(crate) mod tabs;
+pub(crate) mod terminal_title;
+pub(crate) mod tool_pane;
 pub(crate) mod ui;
+pub(crate) mod usage;
+pub(crate) mod watchdog;
+pub(crate) mod working_dir;
 
-pub(crate) use input::InputState;
-pub(crate) use log::{LogColor, LogKind, LogLine, LogSpan, LogTone};
+pub(crate) use answer_diff::{answer_diff_rows, AnswerDiffLayout, RegenCandidates};
+pub(crate) use assistant_messages::{code_blocks, message_preview, AssistantMessages};
+pub(crate) use batch::{split_prompt_blocks, PromptBatch};
+pub(crate) use chord::{ChordAction, ChordBuffer, ChordStep};
+pub(crate) use connection_health::ConnectionHealth;
+pub(crate) use context_history::{context_usage_bar, ContextHistory};
+pub(crate) use context_pins::{ContextPins, DEFAULT_PIN_TURNS};
+pub(crate) use corrections::CorrectionTracker;
+pub(crate) use esc_policy::{next_esc_action, EscAction, EscContext, EscPolicy};
+pub(crate) use failure_streak::{FailureStreak, FailureStreakChange};
+pub(crate) use file_artifacts::{detect_file_artifacts, FileArtifact};
+pub(crate) use input::{ComposerWrap, InputState};
+pub(crate) use link_targets::{extract_link_targets, LinkTarget, MAX_LINK_TARGETS};
+pub(crate) use live_region::{run_status_announcement, LiveRegion};
+pub(crate) use log::{
+    ansi_color_passthrough, set_ansi_color_passthrough, ImagePreview, LogColor, LogKind, LogLine,
+    LogSpan, LogTone,
+};
+pub(crate) use log_search::{find_log_matches, LogSearch, LogSearchMatch};
+pub(crate) use log_timeline::LogTimeline;
+pub(crate) use minimap::{minimap_marks, minimap_viewport_rows, MinimapCache, MinimapMark};
+pub(crate) use permalink::{
+    is_prompt_line, parse_session_permalink, session_permalink, turn_anchor, turn_starts, TurnStart,
+};
+pub(crate) use provider_stats::{ProviderStat, ProviderStats};
 pub(crate) use render::{
-    ConfirmPhase, CursorPhase, PerfDebugStats, RenderState, SyncPhase, WrappedLogCache,
+    ConfirmPhase, CursorPhase, HeldMemory, PerfDebugStats, RenderState, ScrollAnchor,
+    SlowTerminalMonitor, SyncPhase, WrappedLogCache, SLOW_FRAME_BUDGET_MS,
 };
+pub(crate) use run_trace::{run_trace_rows, RunTraces};
+pub(crate) use scratchpad::Scratchpad;
+pub(crate) use session_notes::{parse_note_ttl, NoteExpiry, SessionNote, SessionNotes};
+pub(crate) use streaming::StreamingAssistant;
+pub(crate) use tabs::{TabIndicator, TabRequest, TabStrip};
+pub(crate) use terminal_title::{terminal_title, TerminalTitleFields};
+pub(crate) use tool_pane::ToolPane;
 pub(crate) use ui::{
-    active_skill_mention_token, command_suggestion_rows, complete_skill_mention,
-    complete_slash_command, is_known_command, parse_theme_name, skill_suggestion_rows,
-    theme_options, unknown_command_message, ConfirmDialogState, ConfirmMode, ContextPanelState,
-    LaneListItem, LaneListPanelState, ModelListMode, ModelListPanelState, ModelListSubmitAction,
-    ModelListViewMode, ModelPickerState, ModelSetScope, PendingImageAttachment, PickDialogItem,
-    PickDialogState, PromptDialogState, ProviderPickerState, ReasoningPickerState,
-    SessionListPanelState, SkillsListItemState, SkillsListPanelState, SkillsScopeFilter,
-    StatusLineMode, ThemeListPanelState, ThemeName,
+    active_dir_mention_token, active_file_mention_token, active_skill_mention_token,
+    command_suggestion_rows, complete_skill_mention, complete_slash_command, is_known_command,
+    parse_theme_name, skill_suggestion_rows, theme_options, unknown_command_message, CacheListItem,
+    CacheListPanelState, CacheReadAction, ConfirmAccelerators, ConfirmDialogState, ConfirmMode,
+    ContextPanelState, EditConflictPanelState, HunkReviewState, LaneAttachState, LaneListItem,
+    LaneListPanelState, McpListPanelState, ModelListMode, ModelListPanelState,
+    ModelListSubmitAction, ModelListViewMode, ModelPickerState, ModelSetScope, PanelStack,
+    PendingFileAttachment, PendingImageAttachment, PermissionsPanelState, PickDialogItem,
+    PickDialogState, PromptDialogState, ProviderPickerState, QueuePanelState, ReasoningPickerState,
+    ReviewHunk, SecretsPanelState, SessionListPanelState, SkillsListItemState,
+    SkillsListPanelState, SkillsScopeFilter, StackedPanel, StatusLineMode, ThemeListPanelState,
+    ThemeName, ToolsPanelState, WhatsNewPanelState, WideListRows,
 };
+pub(crate) use usage::{format_token_count, format_usd, model_key, ModelPricing, UsageTracker};
+pub(crate) use watchdog::RuntimeWatchdog;
+pub(crate) use working_dir::{split_cwd_modifier, working_dir_label};
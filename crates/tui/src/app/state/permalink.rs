@@ -0,0 +1,126 @@
+use super::log::{LogKind, LogLine};
+
+const PERMALINK_PREFIX: &str = "codelia://session/";
+
+/// First log line of a prompt turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TurnStart {
+    /// 1-based `LogMeta::turn`.
+    pub turn: usize,
+    pub index: usize,
+}
+
+/// A `> ` prompt line; local slash commands are not prompts.
+pub(crate) fn is_prompt_line(line: &LogLine) -> bool {
+    line.kind() == LogKind::User
+        && line
+            .plain_text()
+            .strip_prefix("> ")
+            .is_some_and(|body| !body.starts_with('/'))
+}
+
+/// The first prompt line of each stamped turn, oldest first. Turns are numbered by
+/// `LogMeta::turn` (replayed history stamps its prompts too), not by position, so the numbers
+/// stay the same after `drop_log_front` trims the oldest turns. A `> ` line stamped with the
+/// turn of the previous start (a runtime-injected message) does not open another turn.
+pub(crate) fn turn_starts(log: &[LogLine]) -> Vec<TurnStart> {
+    let mut starts = Vec::new();
+    let mut last_turn = None;
+    for (index, line) in log.iter().enumerate() {
+        let Some(turn) = line.meta.turn else {
+            continue;
+        };
+        if last_turn == Some(turn) || !is_prompt_line(line) {
+            continue;
+        }
+        last_turn = Some(turn);
+        starts.push(TurnStart {
+            turn: turn as usize,
+            index,
+        });
+    }
+    starts
+}
+
+/// Anchor id shared by the HTML/Markdown exports and the `#turn-<n>` permalink fragment.
+pub(crate) fn turn_anchor(turn: usize) -> String {
+    format!("turn-{turn}")
+}
+
+pub(crate) fn session_permalink(session_id: &str, turn: usize) -> String {
+    format!("{PERMALINK_PREFIX}{session_id}#{}", turn_anchor(turn))
+}
+
+/// `codelia://session/<id>[#turn-<n>]` → session id and 1-based turn; anything else is `None`.
+pub(crate) fn parse_session_permalink(value: &str) -> Option<(String, Option<usize>)> {
+    let rest = value.trim().strip_prefix(PERMALINK_PREFIX)?;
+    let (session_id, fragment) = match rest.split_once('#') {
+        Some((session_id, fragment)) => (session_id, Some(fragment)),
+        None => (rest, None),
+    };
+    let session_id = session_id.trim_end_matches('/');
+    if session_id.is_empty() {
+        return None;
+    }
+    let turn = match fragment {
+        Some(fragment) => Some(
+            fragment
+                .strip_prefix("turn-")?
+                .parse::<usize>()
+                .ok()
+                .filter(|turn| *turn > 0)?,
+        ),
+        None => None,
+    };
+    Some((session_id.to_string(), turn))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_session_permalink, session_permalink, turn_starts, TurnStart};
+    use crate::app::state::{LogKind, LogLine};
+
+    fn line(kind: LogKind, text: &str, turn: Option<u32>) -> LogLine {
+        let mut line = LogLine::new(kind, text);
+        line.meta.turn = turn;
+        line
+    }
+
+    #[test]
+    fn turns_skip_commands_and_links_round_trip() {
+        let log = vec![
+            line(LogKind::User, "> unstamped", None),
+            line(LogKind::User, " ", Some(3)),
+            line(LogKind::User, "> /compact", Some(3)),
+            line(LogKind::User, "> fix the build", Some(3)),
+            line(LogKind::User, "  and the tests", Some(3)),
+            line(LogKind::Assistant, "> quoted reply", Some(3)),
+            line(LogKind::User, "> injected", Some(3)),
+            line(LogKind::User, "> thanks", Some(4)),
+        ];
+        // Turns 1-2 were trimmed from the front; the rest keep their numbers.
+        assert_eq!(
+            turn_starts(&log),
+            vec![
+                TurnStart { turn: 3, index: 3 },
+                TurnStart { turn: 4, index: 7 },
+            ]
+        );
+
+        let link = session_permalink("abc-123", 2);
+        assert_eq!(link, "codelia://session/abc-123#turn-2");
+        assert_eq!(
+            parse_session_permalink(&link),
+            Some(("abc-123".to_string(), Some(2)))
+        );
+        assert_eq!(
+            parse_session_permalink("codelia://session/abc-123"),
+            Some(("abc-123".to_string(), None))
+        );
+        assert_eq!(
+            parse_session_permalink("codelia://session/abc#turn-0"),
+            None
+        );
+        assert_eq!(parse_session_permalink("abc-123"), None);
+    }
+}
@@ -0,0 +1,129 @@
+use super::usage::TokenTotals;
+use codelia_protocol::RunUsage;
+use std::time::Duration;
+
+/// Runs, outcomes and tokens of one `provider/model` in this tab.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ProviderStat {
+    pub runs: u64,
+    pub failures: u64,
+    pub cancelled: u64,
+    /// Summed over completed runs only, so cancelled and failed runs don't skew the average.
+    total_latency: Duration,
+    completed: u64,
+    pub tokens: TokenTotals,
+}
+
+impl ProviderStat {
+    pub(crate) fn average_latency(&self) -> Option<Duration> {
+        (self.completed > 0).then(|| self.total_latency / self.completed as u32)
+    }
+}
+
+/// Per-`provider/model` routing statistics for `/providers stats`, in order of first use.
+/// Runs are keyed by the model selected when they start; tokens by the model each call reports.
+#[derive(Debug, Default)]
+pub(crate) struct ProviderStats {
+    pub(crate) entries: Vec<(String, ProviderStat)>,
+    active: Option<String>,
+}
+
+impl ProviderStats {
+    fn entry(&mut self, key: &str) -> &mut ProviderStat {
+        let index = match self
+            .entries
+            .iter()
+            .position(|(candidate, _)| candidate == key)
+        {
+            Some(index) => index,
+            None => {
+                self.entries
+                    .push((key.to_string(), ProviderStat::default()));
+                self.entries.len() - 1
+            }
+        };
+        &mut self.entries[index].1
+    }
+
+    pub(crate) fn start_run(&mut self, key: &str) {
+        self.entry(key).runs += 1;
+        self.active = Some(key.to_string());
+    }
+
+    /// Closes the run started last; a terminal status without a started run is ignored.
+    pub(crate) fn finish_run(&mut self, status: &str, elapsed: Option<Duration>) {
+        let Some(key) = self.active.take() else {
+            return;
+        };
+        let stat = self.entry(&key);
+        match status {
+            "completed" => {
+                if let Some(elapsed) = elapsed {
+                    stat.total_latency += elapsed;
+                    stat.completed += 1;
+                }
+            }
+            "error" => stat.failures += 1,
+            "cancelled" => stat.cancelled += 1,
+            _ => {}
+        }
+    }
+
+    pub(crate) fn record_usage(&mut self, key: &str, usage: &RunUsage) {
+        self.entry(key).tokens.add(usage);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entries with the most runs first; ties keep first-use order.
+    pub(crate) fn ranked(&self) -> Vec<&(String, ProviderStat)> {
+        let mut ranked: Vec<_> = self.entries.iter().collect();
+        ranked.sort_by_key(|(_, stat)| std::cmp::Reverse(stat.runs));
+        ranked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProviderStats;
+    use codelia_protocol::RunUsage;
+    use std::time::Duration;
+
+    #[test]
+    fn runs_are_attributed_to_the_model_they_started_on() {
+        let mut stats = ProviderStats::default();
+        stats.start_run("openai/gpt-5");
+        stats.finish_run("completed", Some(Duration::from_secs(40)));
+        stats.start_run("anthropic/claude-sonnet");
+        stats.finish_run("error", Some(Duration::from_secs(3)));
+        stats.start_run("anthropic/claude-sonnet");
+        stats.finish_run("completed", Some(Duration::from_secs(20)));
+        stats.start_run("anthropic/claude-sonnet");
+        stats.finish_run("cancelled", None);
+        stats.finish_run("completed", Some(Duration::from_secs(99)));
+        stats.record_usage(
+            "openai/gpt-5",
+            &RunUsage {
+                run_id: None,
+                provider: None,
+                model: "gpt-5".to_string(),
+                input_tokens: 1_000,
+                output_tokens: 200,
+                cached_input_tokens: 0,
+                cache_creation_tokens: 0,
+            },
+        );
+
+        let ranked = stats.ranked();
+        assert_eq!(ranked[0].0, "anthropic/claude-sonnet");
+        let sonnet = &ranked[0].1;
+        assert_eq!((sonnet.runs, sonnet.failures, sonnet.cancelled), (3, 1, 1));
+        assert_eq!(sonnet.average_latency(), Some(Duration::from_secs(20)));
+        let gpt = &ranked[1].1;
+        assert_eq!(gpt.runs, 1);
+        assert_eq!(gpt.tokens.input_tokens, 1_000);
+        assert_eq!(gpt.average_latency(), Some(Duration::from_secs(40)));
+    }
+}
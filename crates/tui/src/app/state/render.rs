@@ -1,4 +1,5 @@
 use crate::app::state::log::LogLine;
+use std::collections::{BTreeMap, VecDeque};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SyncPhase {
@@ -48,6 +49,59 @@ pub struct WrappedLogCache {
     pub width: usize,
     pub log_version: u64,
     pub wrapped: Vec<LogLine>,
+    /// First wrapped row of each log line.
+    pub line_starts: Vec<usize>,
+}
+
+impl WrappedLogCache {
+    fn line_rows(&self, log_index: usize) -> usize {
+        let start = self.line_starts[log_index];
+        let end = self
+            .line_starts
+            .get(log_index + 1)
+            .copied()
+            .unwrap_or(self.wrapped.len());
+        end.saturating_sub(start)
+    }
+
+    /// Anchor for a wrapped row; `None` when the log is empty.
+    pub fn anchor_at(&self, row: usize, scroll_from_bottom: usize) -> Option<ScrollAnchor> {
+        let log_index = self
+            .line_starts
+            .partition_point(|start| *start <= row)
+            .checked_sub(1)?;
+        Some(ScrollAnchor {
+            log_index,
+            row_offset: row.saturating_sub(self.line_starts[log_index]),
+            line_rows: self.line_rows(log_index).max(1),
+            scroll_from_bottom,
+        })
+    }
+
+    /// Wrapped row of `anchor` at this width; the intra-line offset is rescaled when the line
+    /// now wraps to a different number of rows.
+    pub fn resolve_anchor(&self, anchor: &ScrollAnchor) -> Option<usize> {
+        let start = *self.line_starts.get(anchor.log_index)?;
+        let rows = self.line_rows(anchor.log_index);
+        let offset = if rows == anchor.line_rows {
+            anchor.row_offset
+        } else {
+            anchor.row_offset * rows / anchor.line_rows.max(1)
+        };
+        Some(start + offset.min(rows.saturating_sub(1)))
+    }
+}
+
+/// Top visible log row while scrolled back, kept as a log line plus the wrapped row inside
+/// it so resizes and re-wraps keep the same content on screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScrollAnchor {
+    pub log_index: usize,
+    pub row_offset: usize,
+    /// Rows the anchored line wrapped to when the anchor was taken.
+    pub line_rows: usize,
+    /// `scroll_from_bottom` the anchor matches; any other value means the user scrolled since.
+    pub scroll_from_bottom: usize,
 }
 
 #[derive(Default)]
@@ -61,4 +115,120 @@ pub struct PerfDebugStats {
     pub wrapped_total: usize,
     pub tui_rss_bytes: Option<u64>,
     pub runtime_rss_bytes: Option<u64>,
+    pub rpc_latency: RpcLatencyStats,
+    pub held_memory: HeldMemory,
+}
+
+/// Bytes held by attachments, `!` shell results and the log at the last memory-cap check,
+/// plus how many items the caps have dropped this session.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HeldMemory {
+    pub image_bytes: usize,
+    pub shell_bytes: usize,
+    pub log_bytes: usize,
+    pub evicted: usize,
+}
+
+const RPC_LATENCY_WINDOW: usize = 20;
+// A couple of slow model listings should not flag the link as slow.
+const RPC_LATENCY_MIN_SAMPLES: usize = 5;
+pub const RPC_SLOW_MEDIAN_MS: f64 = 1500.0;
+
+/// Rolling send→response latencies, per method and across all methods.
+#[derive(Default)]
+pub struct RpcLatencyStats {
+    by_method: BTreeMap<String, VecDeque<f64>>,
+    recent: VecDeque<f64>,
+}
+
+fn median(values: &VecDeque<f64>) -> f64 {
+    let mut sorted = values.iter().copied().collect::<Vec<_>>();
+    sorted.sort_by(f64::total_cmp);
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 0 {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    }
+}
+
+fn push_window(window: &mut VecDeque<f64>, value: f64) {
+    if window.len() == RPC_LATENCY_WINDOW {
+        window.pop_front();
+    }
+    window.push_back(value);
+}
+
+impl RpcLatencyStats {
+    pub fn record(&mut self, method: &str, latency_ms: f64) {
+        push_window(
+            self.by_method.entry(method.to_string()).or_default(),
+            latency_ms,
+        );
+        push_window(&mut self.recent, latency_ms);
+    }
+
+    /// `(method, average ms, samples)`, slowest first.
+    pub fn method_averages(&self) -> Vec<(&str, f64, usize)> {
+        let mut rows = self
+            .by_method
+            .iter()
+            .map(|(method, window)| {
+                let average = window.iter().sum::<f64>() / window.len() as f64;
+                (method.as_str(), average, window.len())
+            })
+            .collect::<Vec<_>>();
+        rows.sort_by(|a, b| b.1.total_cmp(&a.1));
+        rows
+    }
+
+    pub fn median_ms(&self) -> Option<f64> {
+        if self.recent.len() < RPC_LATENCY_MIN_SAMPLES {
+            return None;
+        }
+        Some(median(&self.recent))
+    }
+
+    /// Median latency when it exceeds the slow-runtime threshold.
+    pub fn slow_median_ms(&self) -> Option<f64> {
+        self.median_ms()
+            .filter(|median| *median > RPC_SLOW_MEDIAN_MS)
+    }
+}
+
+const SLOW_FRAME_WINDOW: usize = 20;
+pub const SLOW_FRAME_BUDGET_MS: f64 = 40.0;
+// Recovery needs clearly fast frames so the mode does not flap around the budget.
+const FAST_FRAME_MS: f64 = 10.0;
+
+/// Draw+flush time of recent frames, used to detect slow links (mosh, serial consoles).
+#[derive(Default)]
+pub struct SlowTerminalMonitor {
+    frame_ms: VecDeque<f64>,
+    pub detected: bool,
+}
+
+impl SlowTerminalMonitor {
+    /// Returns the median frame time when detection flips; each verdict needs a full window.
+    pub fn record(&mut self, frame_ms: f64) -> Option<f64> {
+        if self.frame_ms.len() == SLOW_FRAME_WINDOW {
+            self.frame_ms.pop_front();
+        }
+        self.frame_ms.push_back(frame_ms);
+        if self.frame_ms.len() < SLOW_FRAME_WINDOW {
+            return None;
+        }
+        let median_ms = median(&self.frame_ms);
+        let flipped = if self.detected {
+            median_ms < FAST_FRAME_MS
+        } else {
+            median_ms > SLOW_FRAME_BUDGET_MS
+        };
+        if !flipped {
+            return None;
+        }
+        self.detected = !self.detected;
+        self.frame_ms.clear();
+        Some(median_ms)
+    }
 }
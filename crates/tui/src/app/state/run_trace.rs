@@ -0,0 +1,337 @@
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TraceKind {
+    /// `run.start` sent, accepted, or the run's terminal status.
+    Run,
+    Retry,
+    /// One LLM call, closed by its `run.usage`; it started when the run or the previous tool,
+    /// pause or call ended.
+    Model,
+    Tool,
+    AwaitingUi,
+    Compaction,
+}
+
+impl TraceKind {
+    fn marker(self) -> &'static str {
+        match self {
+            Self::Run => "●",
+            Self::Retry => "↻",
+            Self::Model => "◆",
+            Self::Tool => "▸",
+            Self::AwaitingUi => "⏸",
+            Self::Compaction => "≋",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TraceSpan {
+    pub kind: TraceKind,
+    pub label: String,
+    /// Since the trace began.
+    pub start: Duration,
+    /// `None` for instants, and for spans still open.
+    pub duration: Option<Duration>,
+    pub failed: bool,
+    /// `tool_call_id` of a tool span, to close it.
+    key: Option<String>,
+}
+
+/// Timeline of one prompt, from its `run.start` to the terminal `run.status`, stamped with
+/// the time each message reached the TUI.
+#[derive(Debug, Clone)]
+pub(crate) struct RunTrace {
+    pub queue_id: String,
+    pub run_id: Option<String>,
+    began: Instant,
+    pub spans: Vec<TraceSpan>,
+    /// Where the next model span starts.
+    model_from: Duration,
+    pub finished: Option<Duration>,
+}
+
+impl RunTrace {
+    fn push(&mut self, kind: TraceKind, label: impl Into<String>, now: Instant) -> &mut TraceSpan {
+        let start = now.saturating_duration_since(self.began);
+        self.spans.push(TraceSpan {
+            kind,
+            label: label.into(),
+            start,
+            duration: None,
+            failed: false,
+            key: None,
+        });
+        self.spans.last_mut().expect("just pushed")
+    }
+
+    /// Closes the newest open span of `kind` (and `key`); returns whether one was open.
+    fn close(&mut self, kind: TraceKind, key: Option<&str>, failed: bool, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.began);
+        let open = self.spans.iter_mut().rev().find(|span| {
+            span.kind == kind
+                && span.duration.is_none()
+                && (key.is_none() || span.key.as_deref() == key)
+        });
+        let Some(span) = open else {
+            return false;
+        };
+        span.duration = Some(elapsed.saturating_sub(span.start));
+        span.failed = failed;
+        self.model_from = elapsed;
+        true
+    }
+
+    /// Time spent in closed spans of `kind`.
+    pub(crate) fn total(&self, kind: TraceKind) -> Duration {
+        self.spans
+            .iter()
+            .filter(|span| span.kind == kind)
+            .filter_map(|span| span.duration)
+            .sum()
+    }
+}
+
+/// The trace of the run in flight and of the last finished one, for `/trace`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RunTraces {
+    pub current: Option<RunTrace>,
+    pub last: Option<RunTrace>,
+}
+
+impl RunTraces {
+    /// `run.start` was sent for `queue_id`; a resend of the same prompt stays in its trace.
+    pub(crate) fn dispatched(&mut self, queue_id: &str, now: Instant) {
+        if let Some(trace) = self.current.as_mut() {
+            if trace.queue_id == queue_id && trace.run_id.is_none() {
+                trace.push(TraceKind::Run, "run.start resent", now);
+                return;
+            }
+        }
+        let mut trace = RunTrace {
+            queue_id: queue_id.to_string(),
+            run_id: None,
+            began: now,
+            spans: Vec::new(),
+            model_from: Duration::ZERO,
+            finished: None,
+        };
+        trace.push(TraceKind::Run, format!("run.start sent ({queue_id})"), now);
+        self.current = Some(trace);
+    }
+
+    pub(crate) fn retry(&mut self, reason: &str, now: Instant) {
+        if let Some(trace) = self.current.as_mut() {
+            trace.push(TraceKind::Retry, format!("dispatch retry: {reason}"), now);
+        }
+    }
+
+    pub(crate) fn accepted(&mut self, run_id: &str, now: Instant) {
+        if let Some(trace) = self.current.as_mut() {
+            trace.run_id = Some(run_id.to_string());
+            trace.push(TraceKind::Run, format!("run {run_id} started"), now);
+            trace.model_from = now.saturating_duration_since(trace.began);
+        }
+    }
+
+    pub(crate) fn model_call(&mut self, label: String, now: Instant) {
+        let Some(trace) = self.current.as_mut() else {
+            return;
+        };
+        let end = now.saturating_duration_since(trace.began);
+        let start = trace.model_from.min(end);
+        trace.spans.push(TraceSpan {
+            kind: TraceKind::Model,
+            label,
+            start,
+            duration: Some(end - start),
+            failed: false,
+            key: None,
+        });
+        trace.model_from = end;
+    }
+
+    pub(crate) fn tool_started(&mut self, tool_call_id: Option<&str>, label: String, now: Instant) {
+        if let Some(trace) = self.current.as_mut() {
+            trace.push(TraceKind::Tool, label, now).key = tool_call_id.map(str::to_string);
+        }
+    }
+
+    pub(crate) fn tool_finished(&mut self, tool_call_id: &str, failed: bool, now: Instant) {
+        if let Some(trace) = self.current.as_mut() {
+            trace.close(TraceKind::Tool, Some(tool_call_id), failed, now);
+        }
+    }
+
+    pub(crate) fn compaction(&mut self, started: bool, now: Instant) {
+        let Some(trace) = self.current.as_mut() else {
+            return;
+        };
+        if started {
+            trace.push(TraceKind::Compaction, "compaction", now);
+        } else {
+            trace.close(TraceKind::Compaction, None, false, now);
+        }
+    }
+
+    /// Follows `run.status`: `awaiting_ui` opens a pause, anything else closes it, and a
+    /// terminal status ends the trace.
+    pub(crate) fn status(&mut self, status: &str, now: Instant) {
+        let Some(trace) = self.current.as_mut() else {
+            return;
+        };
+        if status == "awaiting_ui" {
+            trace.push(TraceKind::AwaitingUi, "awaiting ui", now);
+            return;
+        }
+        trace.close(TraceKind::AwaitingUi, None, false, now);
+        if !matches!(status, "completed" | "error" | "cancelled") {
+            return;
+        }
+        let Some(mut trace) = self.current.take() else {
+            return;
+        };
+        let end = now.saturating_duration_since(trace.began);
+        for span in trace
+            .spans
+            .iter_mut()
+            .filter(|span| span.duration.is_none())
+        {
+            if span.kind != TraceKind::Run && span.kind != TraceKind::Retry {
+                span.duration = Some(end.saturating_sub(span.start));
+                span.failed = true;
+            }
+        }
+        trace.push(TraceKind::Run, status, now).failed = status != "completed";
+        trace.finished = Some(end);
+        self.last = Some(trace);
+    }
+
+    /// The run in flight, else the last one.
+    pub(crate) fn latest(&self) -> Option<&RunTrace> {
+        self.current.as_ref().or(self.last.as_ref())
+    }
+}
+
+fn format_offset(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    if secs >= 60.0 {
+        format!("+{}m{:04.1}s", duration.as_secs() / 60, secs % 60.0)
+    } else {
+        format!("+{secs:.2}s")
+    }
+}
+
+fn format_span_duration(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    if secs >= 60.0 {
+        format!(
+            "{}m{:02}s",
+            duration.as_secs() / 60,
+            duration.as_secs() % 60
+        )
+    } else if secs >= 1.0 {
+        format!("{secs:.1}s")
+    } else {
+        format!("{}ms", duration.as_millis())
+    }
+}
+
+/// One summary line, then a row per span: offset, marker, label, duration.
+pub(crate) fn run_trace_rows(trace: &RunTrace, now: Instant) -> Vec<String> {
+    let elapsed = trace
+        .finished
+        .unwrap_or_else(|| now.saturating_duration_since(trace.began));
+    let summary = format!(
+        "{} {} · model {} · tools {} · waiting {}",
+        if trace.finished.is_some() {
+            "total"
+        } else {
+            "running"
+        },
+        format_span_duration(elapsed),
+        format_span_duration(trace.total(TraceKind::Model)),
+        format_span_duration(trace.total(TraceKind::Tool)),
+        format_span_duration(trace.total(TraceKind::AwaitingUi)),
+    );
+    let label_width = trace
+        .spans
+        .iter()
+        .map(|span| span.label.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(48);
+    let mut rows = vec![summary, String::new()];
+    for span in &trace.spans {
+        let label = if span.label.chars().count() > label_width {
+            let cut: String = span.label.chars().take(label_width - 1).collect();
+            format!("{cut}…")
+        } else {
+            span.label.clone()
+        };
+        let duration = match (span.kind, span.duration) {
+            (TraceKind::Run | TraceKind::Retry, _) => String::new(),
+            (_, Some(duration)) => format_span_duration(duration),
+            (_, None) => "…".to_string(),
+        };
+        let failed = if span.failed { " ✗" } else { "" };
+        rows.push(
+            format!(
+                "{:>9} {} {label:<label_width$}  {duration}{failed}",
+                format_offset(span.start),
+                span.kind.marker(),
+            )
+            .trim_end()
+            .to_string(),
+        );
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_trace_rows, RunTraces, TraceKind};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn trace_times_model_calls_tools_and_pauses() {
+        let t0 = Instant::now();
+        let at = |ms: u64| t0 + Duration::from_millis(ms);
+        let mut traces = RunTraces::default();
+        traces.dispatched("q1", at(0));
+        traces.retry("runtime busy", at(100));
+        traces.dispatched("q1", at(600));
+        traces.accepted("r1", at(700));
+        traces.model_call("gpt-5 1.2k in / 80 out".to_string(), at(2_700));
+        traces.tool_started(Some("c1"), "shell cargo test".to_string(), at(2_710));
+        traces.status("awaiting_ui", at(2_800));
+        traces.status("running", at(5_800));
+        traces.tool_finished("c1", true, at(9_000));
+        traces.model_call("gpt-5 1.5k in / 20 out".to_string(), at(10_000));
+        traces.tool_started(Some("c2"), "read".to_string(), at(10_010));
+        traces.status("cancelled", at(10_500));
+
+        assert!(traces.current.is_none());
+        let trace = traces.latest().expect("last trace");
+        assert_eq!(trace.run_id.as_deref(), Some("r1"));
+        assert_eq!(trace.finished, Some(Duration::from_millis(10_500)));
+        assert_eq!(trace.total(TraceKind::Model), Duration::from_millis(3_000));
+        assert_eq!(trace.total(TraceKind::AwaitingUi), Duration::from_secs(3));
+        // The run ended with the read still open; it is closed as failed.
+        assert_eq!(trace.total(TraceKind::Tool), Duration::from_millis(6_780));
+
+        let rows = run_trace_rows(trace, at(20_000));
+        assert_eq!(
+            rows[0],
+            "total 10.5s · model 3.0s · tools 6.8s · waiting 3.0s"
+        );
+        assert_eq!(rows[3], "   +0.10s ↻ dispatch retry: runtime busy");
+        assert_eq!(rows[6], "   +0.70s ◆ gpt-5 1.2k in / 80 out        2.0s");
+        assert_eq!(rows[7], "   +2.71s ▸ shell cargo test              6.3s ✗");
+        assert_eq!(
+            rows.last().map(String::as_str),
+            Some("  +10.50s ● cancelled                      ✗")
+        );
+    }
+}
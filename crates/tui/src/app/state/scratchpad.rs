@@ -0,0 +1,117 @@
+const MAX_SCRATCHPAD_ENTRIES: usize = 32;
+
+/// One pinned snippet: the last assistant answer or text added by hand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ScratchpadEntry {
+    pub label: String,
+    pub text: String,
+}
+
+/// Reference material pinned for the lifetime of the TUI; survives `/clear` and new sessions.
+#[derive(Debug, Default)]
+pub(crate) struct Scratchpad {
+    entries: Vec<ScratchpadEntry>,
+}
+
+impl Scratchpad {
+    /// Returns false when `text` is blank or identical to the newest entry.
+    pub(crate) fn pin(&mut self, label: impl Into<String>, text: &str) -> bool {
+        let text = text.trim_end();
+        if text.trim().is_empty() || self.entries.last().is_some_and(|entry| entry.text == text) {
+            return false;
+        }
+        if self.entries.len() == MAX_SCRATCHPAD_ENTRIES {
+            self.entries.remove(0);
+        }
+        self.entries.push(ScratchpadEntry {
+            label: label.into(),
+            text: text.to_string(),
+        });
+        true
+    }
+
+    /// Removes the 1-based entry `number`.
+    pub(crate) fn remove(&mut self, number: usize) -> Option<ScratchpadEntry> {
+        let index = number.checked_sub(1)?;
+        (index < self.entries.len()).then(|| self.entries.remove(index))
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub(crate) fn entries(&self) -> &[ScratchpadEntry] {
+        &self.entries
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Panel rows: a `#n label` heading per entry followed by its text lines.
+    pub(crate) fn panel_rows(&self) -> Vec<String> {
+        let mut rows = Vec::new();
+        for (index, entry) in self.entries.iter().enumerate() {
+            if index > 0 {
+                rows.push(String::new());
+            }
+            rows.push(format!("#{} {}", index + 1, entry.label));
+            rows.extend(entry.text.lines().map(|line| format!("  {line}")));
+        }
+        rows
+    }
+
+    pub(crate) fn to_markdown(&self) -> String {
+        let mut out = String::from("# Scratchpad\n");
+        for (index, entry) in self.entries.iter().enumerate() {
+            out.push_str(&format!(
+                "\n## {}. {}\n\n{}\n",
+                index + 1,
+                entry.label,
+                entry.text
+            ));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Scratchpad;
+
+    #[test]
+    fn scratchpad_pins_removes_and_exports_entries() {
+        let mut pad = Scratchpad::default();
+        assert!(pad.pin("answer", "fn api(a: u32)\n- [ ] test\n"));
+        assert!(
+            !pad.pin("answer", "fn api(a: u32)\n- [ ] test"),
+            "duplicate"
+        );
+        assert!(!pad.pin("note", "   "), "blank");
+        assert!(pad.pin("note", "remember the flag"));
+
+        assert_eq!(
+            pad.panel_rows(),
+            vec![
+                "#1 answer",
+                "  fn api(a: u32)",
+                "  - [ ] test",
+                "",
+                "#2 note",
+                "  remember the flag",
+            ]
+        );
+        assert_eq!(
+            pad.to_markdown(),
+            "# Scratchpad\n\n## 1. answer\n\nfn api(a: u32)\n- [ ] test\n\n## 2. note\n\nremember the flag\n"
+        );
+        assert!(pad.remove(0).is_none());
+        assert_eq!(
+            pad.remove(1).map(|entry| entry.label),
+            Some("answer".into())
+        );
+        assert_eq!(pad.entries().len(), 1);
+        pad.clear();
+        assert!(pad.is_empty());
+    }
+}
@@ -0,0 +1,204 @@
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+const MAX_SESSION_NOTES: usize = 16;
+const MAX_NOTE_CHARS: usize = 500;
+
+/// When a note stops being sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NoteExpiry {
+    /// Until dropped or the TUI exits.
+    Never,
+    /// Prompts left, counted like pin turns.
+    Turns(usize),
+    At(Instant),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SessionNote {
+    /// Stable while the note lives; shown as `#id` and used by `/note drop`.
+    pub id: usize,
+    pub text: String,
+    pub expiry: NoteExpiry,
+}
+
+/// Short user notes from `/note`, sent with every prompt until they expire.
+#[derive(Debug, Default)]
+pub(crate) struct SessionNotes {
+    notes: Vec<SessionNote>,
+    last_id: usize,
+}
+
+/// `30s`, `15m`, `2h` or `1d`.
+pub(crate) fn parse_note_ttl(value: &str) -> Option<Duration> {
+    let unit = value.chars().last()?;
+    let count = value[..value.len() - unit.len_utf8()].parse::<u64>().ok()?;
+    let seconds = match unit {
+        's' => count,
+        'm' => count.checked_mul(60)?,
+        'h' => count.checked_mul(3600)?,
+        'd' => count.checked_mul(86_400)?,
+        _ => return None,
+    };
+    (seconds > 0).then(|| Duration::from_secs(seconds))
+}
+
+fn expiry_label(expiry: NoteExpiry, now: Instant) -> String {
+    match expiry {
+        NoteExpiry::Never => "session".to_string(),
+        NoteExpiry::Turns(turns) => {
+            format!("{turns} prompt{} left", if turns == 1 { "" } else { "s" })
+        }
+        NoteExpiry::At(at) => {
+            let left = at.saturating_duration_since(now).as_secs();
+            if left >= 3600 {
+                format!("{}h{:02}m left", left / 3600, left % 3600 / 60)
+            } else if left >= 60 {
+                format!("{}m left", left / 60)
+            } else {
+                format!("{left}s left")
+            }
+        }
+    }
+}
+
+impl SessionNotes {
+    /// Returns the new note's id, or `None` for blank text. The oldest note makes room.
+    pub(crate) fn add(&mut self, text: &str, expiry: NoteExpiry) -> Option<usize> {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if text.is_empty() || expiry == NoteExpiry::Turns(0) {
+            return None;
+        }
+        if self.notes.len() == MAX_SESSION_NOTES {
+            self.notes.remove(0);
+        }
+        self.last_id += 1;
+        self.notes.push(SessionNote {
+            id: self.last_id,
+            text: text.chars().take(MAX_NOTE_CHARS).collect(),
+            expiry,
+        });
+        Some(self.last_id)
+    }
+
+    pub(crate) fn remove(&mut self, id: usize) -> Option<SessionNote> {
+        let index = self.notes.iter().position(|note| note.id == id)?;
+        Some(self.notes.remove(index))
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.notes.clear();
+    }
+
+    pub(crate) fn notes(&self) -> &[SessionNote] {
+        &self.notes
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.notes.is_empty()
+    }
+
+    /// Drops notes whose time is up and returns them.
+    pub(crate) fn expire(&mut self, now: Instant) -> Vec<SessionNote> {
+        let (expired, live) = std::mem::take(&mut self.notes)
+            .into_iter()
+            .partition(|note| matches!(note.expiry, NoteExpiry::At(at) if at <= now));
+        self.notes = live;
+        expired
+    }
+
+    /// Counts one prompt against turn-limited notes and returns those that just ran out.
+    pub(crate) fn consume_turn(&mut self) -> Vec<SessionNote> {
+        for note in &mut self.notes {
+            if let NoteExpiry::Turns(turns) = &mut note.expiry {
+                *turns = turns.saturating_sub(1);
+            }
+        }
+        let (expired, live) = std::mem::take(&mut self.notes)
+            .into_iter()
+            .partition(|note| note.expiry == NoteExpiry::Turns(0));
+        self.notes = live;
+        expired
+    }
+
+    /// One `<session_notes>` block holding a JSON list of the notes still live at `now`;
+    /// `<`/`>` are escaped like pins.
+    pub(crate) fn prefix(&self, now: Instant) -> Option<String> {
+        let notes = self
+            .notes
+            .iter()
+            .filter(|note| !matches!(note.expiry, NoteExpiry::At(at) if at <= now))
+            .map(|note| note.text.as_str())
+            .collect::<Vec<_>>();
+        if notes.is_empty() {
+            return None;
+        }
+        let json_text = json!({ "notes": notes })
+            .to_string()
+            .replace('<', "\\u003c")
+            .replace('>', "\\u003e");
+        Some(format!("<session_notes>\n{json_text}\n</session_notes>"))
+    }
+
+    /// `#id text (expiry)` per note.
+    pub(crate) fn rows(&self, now: Instant) -> Vec<String> {
+        self.notes
+            .iter()
+            .map(|note| {
+                format!(
+                    "#{} {} ({})",
+                    note.id,
+                    note.text,
+                    expiry_label(note.expiry, now)
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_note_ttl, NoteExpiry, SessionNotes};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn notes_expire_by_turns_or_time_and_keep_ids() {
+        let now = Instant::now();
+        let mut notes = SessionNotes::default();
+        assert_eq!(notes.add("  \n", NoteExpiry::Never), None);
+        assert_eq!(notes.add("use  pnpm,\nnot npm", NoteExpiry::Never), Some(1));
+        assert_eq!(notes.add("staging is down", NoteExpiry::Turns(1)), Some(2));
+        let ttl = parse_note_ttl("15m").expect("ttl");
+        assert_eq!(
+            notes.add("</session_notes> tricky", NoteExpiry::At(now + ttl)),
+            Some(3)
+        );
+
+        assert!(notes
+            .prefix(now + ttl)
+            .is_some_and(|prefix| !prefix.contains("tricky")));
+        let prefix = notes.prefix(now).expect("prefix");
+        assert!(prefix.contains("\"use pnpm, not npm\""));
+        assert!(prefix.contains("\\u003c/session_notes\\u003e tricky"));
+        assert_eq!(prefix.matches("</session_notes>").count(), 1);
+        assert_eq!(
+            notes.rows(now + Duration::from_secs(60)),
+            vec![
+                "#1 use pnpm, not npm (session)",
+                "#2 staging is down (1 prompt left)",
+                "#3 </session_notes> tricky (14m left)",
+            ]
+        );
+
+        assert_eq!(notes.consume_turn()[0].id, 2);
+        assert!(notes.expire(now + Duration::from_secs(60)).is_empty());
+        assert_eq!(notes.expire(now + ttl)[0].id, 3);
+        assert_eq!(notes.remove(1).map(|note| note.id), Some(1));
+        assert!(notes.prefix(now).is_none());
+        assert_eq!(notes.add("next", NoteExpiry::Never), Some(4));
+
+        assert_eq!(parse_note_ttl("2h"), Some(Duration::from_secs(7200)));
+        assert_eq!(parse_note_ttl("0m"), None);
+        assert_eq!(parse_note_ttl("10x"), None);
+    }
+}
@@ -0,0 +1,37 @@
+/// Assistant message arriving as `text_delta` chunks. The live segment (its log lines run
+/// from `segment_start` to the end of the log) is re-rendered as Markdown on every chunk;
+/// text before `segment_from` is frozen once other lines follow it or it scrolls into the
+/// terminal's own scrollback, where it can no longer be replaced.
+#[derive(Debug, Default)]
+pub(crate) struct StreamingAssistant {
+    pub text: String,
+    /// Byte offset in `text` where the live segment starts.
+    pub segment_from: usize,
+    pub segment_start: usize,
+    pub segment_len: usize,
+}
+
+impl StreamingAssistant {
+    pub(crate) fn new(log_len: usize) -> Self {
+        Self {
+            segment_start: log_len,
+            ..Self::default()
+        }
+    }
+
+    /// The live segment is still the tail of a log that is `log_len` lines long.
+    pub(crate) fn is_tail(&self, log_len: usize) -> bool {
+        self.segment_start + self.segment_len == log_len
+    }
+
+    /// Keeps the shown lines as they are and starts a new segment after them.
+    pub(crate) fn freeze(&mut self, log_len: usize) {
+        self.segment_from = self.text.len();
+        self.segment_start = log_len;
+        self.segment_len = 0;
+    }
+
+    pub(crate) fn live_text(&self) -> &str {
+        &self.text[self.segment_from..]
+    }
+}
@@ -0,0 +1,124 @@
+/// A `/tab` command or `Ctrl+Tab`, applied by the run loop, which owns the tabs' runtimes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TabRequest {
+    New,
+    Next,
+    Previous,
+    /// 1-based, as shown in the status line.
+    Switch(usize),
+    /// `None` closes the active tab.
+    Close(Option<usize>),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct TabIndicator {
+    /// First 8 characters of the session id, or empty before the first run.
+    pub session: String,
+    pub running: bool,
+    /// A confirm/prompt/pick dialog is waiting for an answer.
+    pub waiting: bool,
+}
+
+impl TabIndicator {
+    fn marker(&self) -> &'static str {
+        if self.waiting {
+            "?"
+        } else if self.running {
+            "●"
+        } else {
+            ""
+        }
+    }
+
+    fn state_label(&self) -> &'static str {
+        if self.waiting {
+            "waiting for you"
+        } else if self.running {
+            "running"
+        } else {
+            "idle"
+        }
+    }
+}
+
+/// All tabs as seen from the active one; refreshed by the run loop before each draw.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct TabStrip {
+    pub tabs: Vec<TabIndicator>,
+    pub active: usize,
+}
+
+impl TabStrip {
+    /// `tabs: 1● [2] 3?`; `None` with a single tab, so the status line is unchanged.
+    pub(crate) fn status_segment(&self) -> Option<String> {
+        if self.tabs.len() < 2 {
+            return None;
+        }
+        let items = self
+            .tabs
+            .iter()
+            .enumerate()
+            .map(|(index, tab)| {
+                let label = format!("{}{}", index + 1, tab.marker());
+                if index == self.active {
+                    format!("[{label}]")
+                } else {
+                    label
+                }
+            })
+            .collect::<Vec<_>>();
+        Some(format!("tabs: {}", items.join(" ")))
+    }
+
+    /// Rows for a bare `/tab`.
+    pub(crate) fn list_rows(&self) -> Vec<String> {
+        self.tabs
+            .iter()
+            .enumerate()
+            .map(|(index, tab)| {
+                let active = if index == self.active { "▸" } else { " " };
+                let session = if tab.session.is_empty() {
+                    "new session"
+                } else {
+                    tab.session.as_str()
+                };
+                format!("{active} {}  {session}  {}", index + 1, tab.state_label())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TabIndicator, TabStrip};
+
+    #[test]
+    fn strip_marks_active_running_and_waiting_tabs() {
+        let mut strip = TabStrip {
+            tabs: vec![TabIndicator::default()],
+            active: 0,
+        };
+        assert_eq!(strip.status_segment(), None);
+
+        strip.tabs.push(TabIndicator {
+            session: "1a2b3c4d".to_string(),
+            running: true,
+            waiting: false,
+        });
+        strip.tabs.push(TabIndicator {
+            session: "9f8e7d6c".to_string(),
+            running: true,
+            waiting: true,
+        });
+        strip.active = 1;
+        assert_eq!(strip.status_segment().as_deref(), Some("tabs: 1 [2●] 3?"));
+        assert_eq!(
+            strip.list_rows(),
+            vec![
+                "  1  new session  idle",
+                "▸ 2  1a2b3c4d  running",
+                "  3  9f8e7d6c  waiting for you",
+            ]
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use std::path::Path;
+use std::time::Duration;
+
+const TITLE_SEPARATOR: &str = " ▸ ";
+const SESSION_LABEL_CHARS: usize = 8;
+
+/// Inputs for the terminal/tmux pane title; `None` parts are left out.
+pub(crate) struct TerminalTitleFields<'a> {
+    pub working_dir: Option<&'a str>,
+    pub session_id: Option<&'a str>,
+    pub run_status: Option<&'a str>,
+    /// Only shown while the run is active.
+    pub elapsed: Option<Duration>,
+}
+
+/// Whole minutes only, so the title changes at most once a minute during a run.
+fn coarse_elapsed(elapsed: Duration) -> Option<String> {
+    let minutes = elapsed.as_secs() / 60;
+    match minutes {
+        0 => None,
+        1..=59 => Some(format!("{minutes}m")),
+        _ => Some(format!("{}h{:02}m", minutes / 60, minutes % 60)),
+    }
+}
+
+fn run_state_label(status: Option<&str>) -> &'static str {
+    match status {
+        Some("starting") | Some("running") => "running",
+        Some("awaiting_ui") => "waiting for you",
+        Some("error") => "error",
+        _ => "idle",
+    }
+}
+
+/// `codelia ▸ <project> ▸ <session> ▸ <state>`, with control characters removed so a
+/// directory name cannot end the OSC sequence early.
+pub(crate) fn terminal_title(fields: &TerminalTitleFields<'_>) -> String {
+    let mut parts = vec!["codelia".to_string()];
+    if let Some(project) = fields
+        .working_dir
+        .and_then(|dir| Path::new(dir).file_name())
+        .map(|name| name.to_string_lossy().into_owned())
+    {
+        parts.push(project);
+    }
+    if let Some(session) = fields.session_id.filter(|id| !id.is_empty()) {
+        parts.push(session.chars().take(SESSION_LABEL_CHARS).collect());
+    }
+    let label = run_state_label(fields.run_status);
+    let active = matches!(label, "running" | "waiting for you");
+    let mut state = label.to_string();
+    if let Some(elapsed) = fields.elapsed.filter(|_| active).and_then(coarse_elapsed) {
+        state = format!("{state} {elapsed}");
+    }
+    parts.push(state);
+    parts
+        .join(TITLE_SEPARATOR)
+        .chars()
+        .filter(|ch| !ch.is_control())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{terminal_title, TerminalTitleFields};
+    use std::time::Duration;
+
+    #[test]
+    fn title_shows_project_session_and_coarse_run_time() {
+        let mut fields = TerminalTitleFields {
+            working_dir: Some("/home/me/myrepo"),
+            session_id: Some("3f9a2c7e-41aa"),
+            run_status: Some("running"),
+            elapsed: Some(Duration::from_secs(150)),
+        };
+        assert_eq!(
+            terminal_title(&fields),
+            "codelia ▸ myrepo ▸ 3f9a2c7e ▸ running 2m"
+        );
+
+        fields.elapsed = Some(Duration::from_secs(3_900));
+        fields.run_status = Some("awaiting_ui");
+        assert_eq!(
+            terminal_title(&fields),
+            "codelia ▸ myrepo ▸ 3f9a2c7e ▸ waiting for you 1h05m"
+        );
+
+        fields.run_status = Some("completed");
+        fields.session_id = None;
+        fields.working_dir = Some("/tmp/evil\u{7}name");
+        assert_eq!(terminal_title(&fields), "codelia ▸ evilname ▸ idle");
+    }
+}
@@ -0,0 +1,162 @@
+use super::{LogKind, LogLine, LogTone};
+use crate::app::util::text::sanitize_for_tui;
+use std::time::Instant;
+
+/// Right pane of the split layout: the running (or last) tool call and what it printed so far.
+#[derive(Debug, Default)]
+pub(crate) struct ToolPane {
+    /// Empty until the first tool call of the session.
+    pub(crate) tool_call_id: String,
+    pub(crate) tool: String,
+    /// `command` argument of a `shell` call; picks its task out of `shell_list`.
+    pub(crate) command: Option<String>,
+    pub(crate) running: bool,
+    pub(crate) failed: bool,
+    /// Tool call line and permission previews (edit diffs).
+    pub(crate) call_lines: Vec<LogLine>,
+    /// Live tails polled with `shell_logs` while a shell call runs.
+    pub(crate) stdout: Vec<String>,
+    pub(crate) stderr: Vec<String>,
+    pub(crate) result_lines: Vec<LogLine>,
+    /// Task key of the running shell call once `shell_list` found it.
+    pub(crate) shell_key: Option<String>,
+    /// The next `shell_logs` poll reads stderr; polls alternate between the streams.
+    pub(crate) poll_stderr: bool,
+    pub(crate) last_poll_at: Option<Instant>,
+}
+
+impl ToolPane {
+    pub(crate) fn start(
+        &mut self,
+        tool_call_id: &str,
+        tool: &str,
+        command: Option<String>,
+        lines: &[LogLine],
+    ) {
+        *self = Self {
+            tool_call_id: tool_call_id.to_string(),
+            tool: tool.to_string(),
+            command,
+            running: true,
+            call_lines: lines.to_vec(),
+            ..Self::default()
+        };
+    }
+
+    /// Lines of a later event of the shown call, such as a permission preview.
+    pub(crate) fn append(&mut self, tool_call_id: &str, lines: &[LogLine]) {
+        if self.tool_call_id == tool_call_id {
+            self.call_lines.extend_from_slice(lines);
+        }
+    }
+
+    pub(crate) fn finish(&mut self, tool_call_id: &str, is_error: bool, lines: &[LogLine]) {
+        if self.tool_call_id != tool_call_id {
+            return;
+        }
+        self.running = false;
+        self.failed = is_error;
+        self.result_lines = lines.to_vec();
+    }
+
+    /// A cancelled or failed run sends no result for the call in flight.
+    pub(crate) fn stop(&mut self) {
+        self.running = false;
+    }
+
+    pub(crate) fn set_shell_output(&mut self, stderr: bool, content: &str) {
+        let lines = content.lines().map(str::to_string).collect();
+        if stderr {
+            self.stderr = lines;
+        } else {
+            self.stdout = lines;
+        }
+    }
+
+    pub(crate) fn title(&self) -> String {
+        if self.tool_call_id.is_empty() {
+            return "Tool output".to_string();
+        }
+        let state = match (self.running, self.failed) {
+            (true, _) => "running",
+            (false, true) => "failed",
+            (false, false) => "done",
+        };
+        format!("{} · {state}", self.tool)
+    }
+
+    /// Body rows, oldest first: the call, live output, then the result.
+    pub(crate) fn body_lines(&self) -> Vec<LogLine> {
+        if self.tool_call_id.is_empty() {
+            return vec![LogLine::new_with_tone(
+                LogKind::Status,
+                LogTone::Detail,
+                "No tool has run yet",
+            )];
+        }
+        let output = |text: &str| {
+            LogLine::new_with_ansi_colors(
+                LogKind::ToolResult,
+                LogTone::Detail,
+                &sanitize_for_tui(text),
+            )
+        };
+        let mut lines = self.call_lines.clone();
+        lines.extend(self.stdout.iter().map(|line| output(line)));
+        if !self.stderr.is_empty() {
+            lines.push(LogLine::new_with_tone(
+                LogKind::Error,
+                LogTone::Detail,
+                "stderr:",
+            ));
+            lines.extend(self.stderr.iter().map(|line| output(line)));
+        }
+        lines.extend_from_slice(&self.result_lines);
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ToolPane;
+    use crate::app::state::{LogKind, LogLine};
+
+    #[test]
+    fn pane_follows_the_latest_call_and_ignores_others() {
+        let mut pane = ToolPane::default();
+        assert_eq!(pane.title(), "Tool output");
+        let call = LogLine::new(LogKind::ToolCall, "Bash cargo test");
+        pane.start("call-1", "shell", Some("cargo test".to_string()), &[call]);
+        pane.set_shell_output(false, "running 3 tests\ntest a ... ok");
+        pane.set_shell_output(true, "warning: unused");
+        pane.append("call-0", &[LogLine::new(LogKind::Status, "stale")]);
+        pane.finish("call-0", true, &[]);
+        assert_eq!(pane.title(), "shell · running");
+
+        pane.finish(
+            "call-1",
+            false,
+            &[LogLine::new(LogKind::ToolResult, "✔ Bash finished")],
+        );
+        assert_eq!(pane.title(), "shell · done");
+        let body = pane
+            .body_lines()
+            .iter()
+            .map(LogLine::plain_text)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            body,
+            vec![
+                "Bash cargo test",
+                "running 3 tests",
+                "test a ... ok",
+                "stderr:",
+                "warning: unused",
+                "✔ Bash finished",
+            ]
+        );
+
+        pane.start("call-2", "edit", None, &[]);
+        assert!(pane.stdout.is_empty() && pane.result_lines.is_empty());
+    }
+}
@@ -5,3 +5,13 @@ pub struct PendingImageAttachment {
     pub height: u32,
     pub encoded_bytes: usize,
 }
+
+/// A workspace file mentioned as `@path` in the composer; sent as a text part before the prompt.
+#[derive(Clone, Debug)]
+pub struct PendingFileAttachment {
+    pub path: String,
+    pub content: String,
+    /// Size on disk; larger than `content` when `truncated`.
+    pub bytes: usize,
+    pub truncated: bool,
+}
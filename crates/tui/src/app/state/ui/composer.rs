@@ -1,4 +1,5 @@
 use crate::app::state::InputState;
+use crate::app::util::dir_tree::DIR_MENTION_PREFIX;
 use std::collections::BTreeSet;
 
 use super::skills::SkillsListItemState;
@@ -38,8 +39,8 @@ const SLASH_COMMANDS: &[SlashCommandSpec] = &[
     },
     SlashCommandSpec {
         command: "/context",
-        usage: "/context [brief]",
-        summary: "Show context snapshot",
+        usage: "/context [brief|graph]",
+        summary: "Show context snapshot or per-run usage graph",
     },
     SlashCommandSpec {
         command: "/skills",
@@ -73,14 +74,194 @@ const SLASH_COMMANDS: &[SlashCommandSpec] = &[
     },
     SlashCommandSpec {
         command: "/queue",
-        usage: "/queue [cancel [id|index]|clear]",
-        summary: "Inspect/cancel/clear queued prompts",
+        usage: "/queue [list|cancel [id|index]|clear]",
+        summary: "Reorder, edit or cancel queued prompts",
     },
     SlashCommandSpec {
         command: "/tasks",
         usage: "/tasks [list|show <task_id>|cancel <task_id>]",
         summary: "List background tasks and inspect/cancel one",
     },
+    SlashCommandSpec {
+        command: "/bundle",
+        usage: "/bundle [dir]",
+        summary: "Export transcript, diffs, shell outputs, and images",
+    },
+    SlashCommandSpec {
+        command: "/issue",
+        usage: "/issue <number|url>",
+        summary: "Attach an issue/PR body as context for the next prompt",
+    },
+    SlashCommandSpec {
+        command: "/pr",
+        usage: "/pr [--draft] [title...]|confirm|cancel",
+        summary: "Preview, then push and open a pull request",
+    },
+    SlashCommandSpec {
+        command: "/ci",
+        usage: "/ci [watch|stop|logs]",
+        summary: "Show branch CI checks; attach failing logs",
+    },
+    SlashCommandSpec {
+        command: "/plan",
+        usage: "/plan <prompt>|run|discard",
+        summary: "Dry run: preview edits/commands, then run for real",
+    },
+    SlashCommandSpec {
+        command: "/secrets",
+        usage: "/secrets [set <NAME> [--keychain]|rm|on|off <NAME>]",
+        summary: "Manage env values injected into ! commands (redacted)",
+    },
+    SlashCommandSpec {
+        command: "/permissions",
+        usage: "/permissions [add|rm <pattern>]",
+        summary: "Shell commands auto-approved in this project",
+    },
+    SlashCommandSpec {
+        command: "/cache",
+        usage: "/cache",
+        summary: "Browse tool outputs cached in this session",
+    },
+    SlashCommandSpec {
+        command: "/page",
+        usage: "/page [n]",
+        summary: "Page through the full output of a read result",
+    },
+    SlashCommandSpec {
+        command: "/audit",
+        usage: "/audit",
+        summary: "Review recorded permission decisions (hash-chained log)",
+    },
+    SlashCommandSpec {
+        command: "/debug-keys",
+        usage: "/debug-keys [file]",
+        summary: "Export recorded input events for a bug report",
+    },
+    SlashCommandSpec {
+        command: "/batch",
+        usage: "/batch <file>",
+        summary: "Queue blank-line-separated prompts from a file and run them in order",
+    },
+    SlashCommandSpec {
+        command: "/json",
+        usage: "/json <schema|description>|off|show|save [file]",
+        summary: "Ask for JSON output on the next prompt and validate it",
+    },
+    SlashCommandSpec {
+        command: "/translate",
+        usage: "/translate [<lang>|off|show [<turn>]]",
+        summary: "Mirror each answer into another language below it",
+    },
+    SlashCommandSpec {
+        command: "/cd",
+        usage: "/cd [path]",
+        summary: "Show or change the runtime working directory for next runs",
+    },
+    SlashCommandSpec {
+        command: "/runtime",
+        usage: "/runtime [copy]",
+        summary: "Show runtime version, transport, directories, tools, and capabilities",
+    },
+    SlashCommandSpec {
+        command: "/export",
+        usage: "/export <ansi|cast|md|html> [--run[=<id>]] [file]",
+        summary: "Export the log as ANSI text, an asciinema recording, Markdown, or HTML",
+    },
+    SlashCommandSpec {
+        command: "/tab",
+        usage: "/tab [new|next|prev|close [n]|<n>]",
+        summary: "List, open, switch, or close session tabs (each runs its own runtime)",
+    },
+    SlashCommandSpec {
+        command: "/tee",
+        usage: "/tee <file>|off",
+        summary: "Append this run's assistant text and tool summaries to a file as they arrive",
+    },
+    SlashCommandSpec {
+        command: "/local-models",
+        usage: "/local-models",
+        summary: "Find a local Ollama/llama.cpp server and list its models",
+    },
+    SlashCommandSpec {
+        command: "/regen",
+        usage: "/regen [diff [split]|pick <1|2>]",
+        summary: "Rerun the last prompt and compare or pick between the two answers",
+    },
+    SlashCommandSpec {
+        command: "/link",
+        usage: "/link [turn]",
+        summary: "Copy a codelia:// deep link to a turn (default: latest) for --resume",
+    },
+    SlashCommandSpec {
+        command: "/edit",
+        usage: "/edit",
+        summary: "Write the prompt in $EDITOR (Ctrl+X Ctrl+E edits the current composer text)",
+    },
+    SlashCommandSpec {
+        command: "/copy",
+        usage: "/copy [code|pick]",
+        summary: "Copy the last answer, its last code block, or a picked answer (Alt+C)",
+    },
+    SlashCommandSpec {
+        command: "/tools",
+        usage: "/tools [enable|disable <name>]",
+        summary: "Enable or disable runtime tools for this session",
+    },
+    SlashCommandSpec {
+        command: "/pin",
+        usage: "/pin [add [name] [--turns N]|mark|drop <name>|clear]",
+        summary: "Keep the visible or marked log rows as context for the next prompts",
+    },
+    SlashCommandSpec {
+        command: "/scratch",
+        usage: "/scratch [pin|add <text>|drop <n>|clear|export [file]]",
+        summary: "Pin answers/snippets to a scratchpad and export it",
+    },
+    SlashCommandSpec {
+        command: "/notify",
+        usage: "/notify [on|off|threshold <secs>]",
+        summary: "Desktop notification when a run finishes unfocused or after a threshold",
+    },
+    SlashCommandSpec {
+        command: "/glyphs",
+        usage: "/glyphs [ascii|unicode|auto]",
+        summary: "Draw spinner, box and check glyphs as ASCII when the font lacks them",
+    },
+    SlashCommandSpec {
+        command: "/whatsnew",
+        usage: "/whatsnew",
+        summary: "Show release notes for this version",
+    },
+    SlashCommandSpec {
+        command: "/usage",
+        usage: "/usage",
+        summary: "Show token usage and cost estimate for this run and session",
+    },
+    SlashCommandSpec {
+        command: "/providers",
+        usage: "/providers [stats]",
+        summary: "Compare runs, failures, latency and cost per provider/model",
+    },
+    SlashCommandSpec {
+        command: "/trace",
+        usage: "/trace",
+        summary: "Show a timeline of the last run: model calls, tools, waits and retries",
+    },
+    SlashCommandSpec {
+        command: "/note",
+        usage: "/note [--turns N|--for <30m|2h|1d>] <text> | /note [drop <id>|clear]",
+        summary: "Keep a short note in every prompt of this session (lightweight memory)",
+    },
+    SlashCommandSpec {
+        command: "/draft",
+        usage: "/draft [restore|discard]",
+        summary: "Show, restore or discard the autosaved composer draft",
+    },
+    SlashCommandSpec {
+        command: "/set",
+        usage: "/set [<key> [value]]",
+        summary: "Show or change persistent UI settings (tui.toml)",
+    },
 ];
 
 fn find_command(command: &str) -> Option<&'static SlashCommandSpec> {
@@ -173,6 +354,22 @@ pub(crate) fn active_skill_mention_token(value: &str) -> Option<String> {
     Some(token.to_string())
 }
 
+/// The trailing `@query` word being typed, without the `@`; `@cwd:` modifiers and `@dir:`
+/// mentions are not file mentions.
+pub(crate) fn active_file_mention_token(value: &str) -> Option<&str> {
+    let (start, end) = trailing_token_range(value)?;
+    let query = value[start..end].strip_prefix('@')?;
+    (!query.starts_with("cwd:") && !query.starts_with(DIR_MENTION_PREFIX)).then_some(query)
+}
+
+/// The trailing `@dir:query` word being typed, without the `@dir:`.
+pub(crate) fn active_dir_mention_token(value: &str) -> Option<&str> {
+    let (start, end) = trailing_token_range(value)?;
+    value[start..end]
+        .strip_prefix('@')?
+        .strip_prefix(DIR_MENTION_PREFIX)
+}
+
 fn unique_enabled_skill_names(skills: &[SkillsListItemState]) -> Vec<String> {
     let mut names = BTreeSet::new();
     for skill in skills {
@@ -407,8 +604,8 @@ mod tests {
 
     #[test]
     fn tab_completion_common_prefix() {
-        let completed = complete_command_text("/c").expect("completion");
-        assert_eq!(completed, "/co");
+        let completed = complete_command_text("/mo").expect("completion");
+        assert_eq!(completed, "/model");
     }
 
     #[test]
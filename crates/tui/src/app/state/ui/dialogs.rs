@@ -1,5 +1,9 @@
+use crate::app::state::LogLine;
+
 pub struct ConfirmDialogState {
     pub id: String,
+    /// Tool behind a permission confirm, for the audit log.
+    pub tool: Option<String>,
     pub title: String,
     pub message: String,
     pub danger_level: Option<String>,
@@ -10,6 +14,81 @@ pub struct ConfirmDialogState {
     pub command_view: bool,
     pub selected: usize,
     pub mode: ConfirmMode,
+    pub accelerators: ConfirmAccelerators,
+    /// Per-hunk review for a permission preview whose diff was too large to show inline.
+    pub hunk_review: Option<HunkReviewState>,
+}
+
+/// Single-letter keys for the confirm options, underlined in the rendered dialog.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConfirmAccelerators {
+    pub allow: Option<char>,
+    pub deny: Option<char>,
+    /// Toggles between the plain allow and the allow-and-remember option.
+    pub remember: Option<char>,
+}
+
+impl ConfirmAccelerators {
+    /// Keymap letters win; the rest take the first free letter of their label (`remember`
+    /// uses the word itself). `reserved` keys keep their meaning, and `y`/`n` never map to
+    /// the opposite answer.
+    pub fn derive(
+        confirm_label: &str,
+        cancel_label: &str,
+        allow_remember: bool,
+        reserved: &[char],
+        keymap: ConfirmAccelerators,
+    ) -> Self {
+        let mut taken = reserved.to_vec();
+        taken.extend(
+            [keymap.allow, keymap.deny, keymap.remember]
+                .into_iter()
+                .flatten(),
+        );
+        let mut pick = |preferred: Option<char>, label: &str, avoid: char| {
+            if preferred.is_some() {
+                return preferred;
+            }
+            let letter = label
+                .chars()
+                .filter(char::is_ascii_alphabetic)
+                .map(|ch| ch.to_ascii_lowercase())
+                .find(|ch| *ch != avoid && !taken.contains(ch))?;
+            taken.push(letter);
+            Some(letter)
+        };
+        let allow = pick(keymap.allow, confirm_label, 'n');
+        let deny = pick(keymap.deny, cancel_label, 'y');
+        let remember = if allow_remember {
+            pick(keymap.remember, "remember", 'n')
+        } else {
+            None
+        };
+        Self {
+            allow,
+            deny,
+            remember,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ReviewHunk {
+    pub label: String,
+    pub added: usize,
+    pub removed: usize,
+    /// Rendered diff rows, appended to the log when the hunk is expanded.
+    pub lines: Vec<LogLine>,
+    /// `Some(true)` accepted, `Some(false)` rejected, `None` not reviewed.
+    pub decision: Option<bool>,
+}
+
+#[derive(Clone, Default)]
+pub struct HunkReviewState {
+    pub hunks: Vec<ReviewHunk>,
+    pub selected: usize,
+    /// The dialog shows the hunk list instead of the Allow/Deny options.
+    pub active: bool,
 }
 
 pub struct PromptDialogState {
@@ -41,3 +120,36 @@ pub enum ConfirmMode {
     Select,
     Reason,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ConfirmAccelerators;
+
+    #[test]
+    fn accelerators_follow_labels_and_skip_reserved_keys() {
+        let keys = ConfirmAccelerators::derive(
+            "Allow",
+            "Deny",
+            true,
+            &['d', 'h'],
+            ConfirmAccelerators::default(),
+        );
+        assert_eq!(keys.allow, Some('a'));
+        assert_eq!(keys.deny, Some('e'));
+        assert_eq!(keys.remember, Some('r'));
+
+        let keys = ConfirmAccelerators::derive(
+            "Yes",
+            "No",
+            false,
+            &[],
+            ConfirmAccelerators {
+                deny: Some('x'),
+                ..ConfirmAccelerators::default()
+            },
+        );
+        assert_eq!(keys.allow, Some('y'));
+        assert_eq!(keys.deny, Some('x'));
+        assert_eq!(keys.remember, None);
+    }
+}
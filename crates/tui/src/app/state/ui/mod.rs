@@ -7,20 +7,25 @@ mod skills;
 mod status;
 mod theme;
 
-pub use attachments::PendingImageAttachment;
+pub use attachments::{PendingFileAttachment, PendingImageAttachment};
 pub(crate) use composer::{
-    active_skill_mention_token, command_suggestion_rows, complete_skill_mention,
-    complete_slash_command, is_known_command, skill_suggestion_rows, unknown_command_message,
+    active_dir_mention_token, active_file_mention_token, active_skill_mention_token,
+    command_suggestion_rows, complete_skill_mention, complete_slash_command, is_known_command,
+    skill_suggestion_rows, unknown_command_message,
 };
 pub use dialogs::{
-    ConfirmDialogState, ConfirmMode, PickDialogItem, PickDialogState, PromptDialogState,
+    ConfirmAccelerators, ConfirmDialogState, ConfirmMode, HunkReviewState, PickDialogItem,
+    PickDialogState, PromptDialogState, ReviewHunk,
 };
 pub use model::{
     ModelListMode, ModelListPanelState, ModelListSubmitAction, ModelListViewMode, ModelPickerState,
     ModelSetScope, ProviderPickerState, ReasoningPickerState,
 };
 pub use panels::{
-    ContextPanelState, LaneListItem, LaneListPanelState, SessionListPanelState, ThemeListPanelState,
+    CacheListItem, CacheListPanelState, CacheReadAction, ContextPanelState, EditConflictPanelState,
+    LaneAttachState, LaneListItem, LaneListPanelState, McpListPanelState, PanelStack,
+    PermissionsPanelState, QueuePanelState, SecretsPanelState, SessionListPanelState, StackedPanel,
+    ThemeListPanelState, ToolsPanelState, WhatsNewPanelState, WideListRows,
 };
 pub use skills::{SkillsListItemState, SkillsListPanelState, SkillsScopeFilter};
 pub use status::StatusLineMode;
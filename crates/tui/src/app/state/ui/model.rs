@@ -1,3 +1,5 @@
+use super::panels::WideListRows;
+
 pub struct ModelPickerState {
     pub models: Vec<String>,
     pub selected: usize,
@@ -63,6 +65,10 @@ pub enum ModelListSubmitAction {
         request_id: String,
         item_ids: Vec<String>,
     },
+    /// `/local-models`: registers the row's server with the runtime on select.
+    LocalModelSet {
+        base_urls: Vec<String>,
+    },
 }
 
 pub struct ProviderPickerState {
@@ -78,6 +84,8 @@ pub struct ModelListPanelState {
     pub rows_limits: Vec<String>,
     pub header_cost: String,
     pub rows_cost: Vec<String>,
+    /// Limits and costs side by side with the provider, for wide terminals.
+    pub wide: Option<WideListRows>,
     pub model_ids: Vec<String>,
     pub selected: usize,
     pub view_mode: ModelListViewMode,
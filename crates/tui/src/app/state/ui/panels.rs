@@ -1,7 +1,19 @@
+use crate::app::util::merge3::ThreeWayMerge;
+use std::time::Instant;
+
+/// Extra-metadata header and rows a list panel shows on wide terminals; rows stay index-aligned
+/// with the narrow rows so selection is shared.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WideListRows {
+    pub header: String,
+    pub rows: Vec<String>,
+}
+
 pub struct SessionListPanelState {
     pub title: String,
     pub header: String,
     pub rows: Vec<String>,
+    pub wide: Option<WideListRows>,
     pub session_ids: Vec<String>,
     pub selected: usize,
     pub show_all: bool,
@@ -27,6 +39,104 @@ pub struct LaneListPanelState {
     pub selected: usize,
 }
 
+/// Lane attach mode: the lane's pane output, refreshed by polling `lane_tail`.
+pub struct LaneAttachState {
+    pub lane_id: String,
+    pub lines: Vec<String>,
+    /// Output captured while scrolled back; shown once follow mode is back on.
+    pub incoming: Option<Vec<String>>,
+    /// Lane state from the last poll (`running`, `finished`, …).
+    pub state: String,
+    pub backend_alive: bool,
+    /// Keep the newest output in view; scrolling back turns it off.
+    pub follow: bool,
+    /// Lines scrolled back from the bottom.
+    pub scroll: usize,
+    pub last_poll_at: Option<Instant>,
+    pub error: Option<String>,
+}
+
+/// What's-new panel: changelog entries; `commands` is index-aligned with `rows` and holds the
+/// `/command` an entry links to.
+pub struct WhatsNewPanelState {
+    pub title: String,
+    pub header: String,
+    pub rows: Vec<String>,
+    pub commands: Vec<Option<String>>,
+    pub selected: usize,
+}
+
+/// `/mcp`: loaded servers; `server_ids` and `details` are index-aligned with `rows`.
+pub struct McpListPanelState {
+    pub title: String,
+    pub header: String,
+    pub rows: Vec<String>,
+    pub server_ids: Vec<String>,
+    pub details: Vec<Vec<String>>,
+    pub selected: usize,
+}
+
+/// A list panel set aside while a detail panel opened from it is shown.
+pub enum StackedPanel {
+    LaneList(LaneListPanelState),
+    McpList(McpListPanelState),
+}
+
+impl StackedPanel {
+    pub fn title(&self) -> &str {
+        match self {
+            Self::LaneList(panel) => &panel.title,
+            Self::McpList(panel) => &panel.title,
+        }
+    }
+}
+
+/// Parents of the open detail (context) panel, outermost first. Each entry remembers the
+/// detail title it opened, so a panel opened some other way never pops a stale parent.
+#[derive(Default)]
+pub struct PanelStack {
+    entries: Vec<(StackedPanel, String)>,
+}
+
+impl PanelStack {
+    pub fn push(&mut self, parent: StackedPanel, child_title: &str) {
+        self.entries.push((parent, child_title.to_string()));
+    }
+
+    fn is_child(&self, title: &str) -> bool {
+        self.entries
+            .last()
+            .is_some_and(|(_, child_title)| child_title == title)
+    }
+
+    /// Parent to go back to from the detail titled `title`; anything else clears the stack.
+    pub fn pop_for(&mut self, title: &str) -> Option<StackedPanel> {
+        if !self.is_child(title) {
+            self.entries.clear();
+            return None;
+        }
+        self.entries.pop().map(|(parent, _)| parent)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// `Lanes › Lane 7f3a` for a detail opened from a list; other titles are returned as-is.
+    pub fn breadcrumb(&self, title: &str) -> String {
+        if !self.is_child(title) {
+            return title.to_string();
+        }
+        let mut parts = self
+            .entries
+            .iter()
+            .map(|(parent, _)| parent.title())
+            .collect::<Vec<_>>();
+        parts.push(title);
+        parts.join(" › ")
+    }
+}
+
 pub struct ThemeListPanelState {
     pub title: String,
     pub header: String,
@@ -34,3 +144,75 @@ pub struct ThemeListPanelState {
     pub theme_ids: Vec<String>,
     pub selected: usize,
 }
+
+pub struct SecretsPanelState {
+    pub title: String,
+    pub header: String,
+    pub rows: Vec<String>,
+    pub names: Vec<String>,
+    pub selected: usize,
+}
+
+/// Three-way merge of an `edit` that failed because the file changed after it was read.
+pub struct EditConflictPanelState {
+    /// As the agent wrote it, echoed back in the resolution.
+    pub file_path: String,
+    /// The edited region as it is on disk now (the user's version).
+    pub current: String,
+    /// SHA-256 of the whole file the merge was built against, sent as `expected_hash`.
+    pub expected_hash: String,
+    /// The failed call; its `AppState::edit_calls` entry lives until the merge is resolved.
+    pub tool_call_id: String,
+    pub merge: ThreeWayMerge,
+    pub selected: usize,
+}
+
+/// `/permissions`: auto-approved shell command patterns of the current project.
+pub struct PermissionsPanelState {
+    pub title: String,
+    pub header: String,
+    pub rows: Vec<String>,
+    pub selected: usize,
+}
+
+/// `/tools`: runtime tools with per-session enable toggles; `names` is index-aligned with `rows`.
+pub struct ToolsPanelState {
+    pub title: String,
+    pub header: String,
+    pub rows: Vec<String>,
+    pub names: Vec<String>,
+    pub selected: usize,
+}
+
+/// `/queue`: queued prompts; `queue_ids` is index-aligned with `rows`.
+pub struct QueuePanelState {
+    pub title: String,
+    pub header: String,
+    pub rows: Vec<String>,
+    pub queue_ids: Vec<String>,
+    pub selected: usize,
+    /// Opened by a finished run to ask about each prompt in turn; rows above `selected` are kept.
+    pub review: bool,
+}
+
+#[derive(Clone)]
+pub struct CacheListItem {
+    pub ref_id: String,
+    pub tool: String,
+}
+
+/// What to do with a cached tool output once `cache.read` returns its content.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CacheReadAction {
+    Preview,
+    Attach,
+    Save,
+}
+
+pub struct CacheListPanelState {
+    pub title: String,
+    pub header: String,
+    pub rows: Vec<String>,
+    pub items: Vec<CacheListItem>,
+    pub selected: usize,
+}
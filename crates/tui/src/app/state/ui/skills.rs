@@ -1,3 +1,6 @@
+use super::panels::WideListRows;
+use crate::app::util::text::truncate_start;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SkillsScopeFilter {
     All,
@@ -31,6 +34,8 @@ impl SkillsScopeFilter {
     }
 }
 
+const MAX_WIDE_SKILL_PATH_CHARS: usize = 48;
+
 #[derive(Clone)]
 pub struct SkillsListItemState {
     pub name: String,
@@ -44,26 +49,41 @@ pub struct SkillsListPanelState {
     pub title: String,
     pub header: String,
     pub rows: Vec<String>,
+    /// Adds the skill path and untruncated description on wide terminals.
+    pub wide: Option<WideListRows>,
     pub filtered_indices: Vec<usize>,
     pub items: Vec<SkillsListItemState>,
     pub selected: usize,
     pub search_query: String,
     pub scope_filter: SkillsScopeFilter,
+    /// Scope and lowercased query behind `filtered_indices`; a query that only grows narrows
+    /// the previous matches instead of rescanning every item.
+    pub applied_filter: Option<(SkillsScopeFilter, String)>,
 }
 
 impl SkillsListPanelState {
     pub fn rebuild(&mut self) {
         let query = self.search_query.trim().to_lowercase();
-        self.filtered_indices = self
-            .items
-            .iter()
-            .enumerate()
-            .filter_map(|(index, item)| {
+        let narrowing = self
+            .applied_filter
+            .as_ref()
+            .is_some_and(|(scope, previous)| {
+                *scope == self.scope_filter && query.starts_with(previous.as_str())
+            });
+        let candidates = if narrowing {
+            std::mem::take(&mut self.filtered_indices)
+        } else {
+            (0..self.items.len()).collect()
+        };
+        self.filtered_indices = candidates
+            .into_iter()
+            .filter(|index| {
+                let item = &self.items[*index];
                 if !self.scope_filter.matches(&item.scope) {
-                    return None;
+                    return false;
                 }
                 if query.is_empty() {
-                    return Some(index);
+                    return true;
                 }
                 let haystack = format!(
                     "{} {} {}",
@@ -71,13 +91,15 @@ impl SkillsListPanelState {
                     item.description.to_lowercase(),
                     item.path.to_lowercase()
                 );
-                haystack.contains(&query).then_some(index)
+                haystack.contains(&query)
             })
             .collect();
+        self.applied_filter = Some((self.scope_filter, query));
 
         if self.filtered_indices.is_empty() {
             self.selected = 0;
             self.rows = vec!["(no skills matched)".to_string()];
+            self.wide = None;
         } else {
             self.selected = self
                 .selected
@@ -94,6 +116,37 @@ impl SkillsListPanelState {
                     )
                 })
                 .collect();
+            let name_width = self
+                .filtered_indices
+                .iter()
+                .map(|index| self.items[*index].name.chars().count())
+                .max()
+                .unwrap_or(0)
+                .max("name".len());
+            let path_width = self
+                .filtered_indices
+                .iter()
+                .map(|index| self.items[*index].path.chars().count())
+                .max()
+                .unwrap_or(0)
+                .clamp("path".len(), MAX_WIDE_SKILL_PATH_CHARS);
+            let rows = self
+                .filtered_indices
+                .iter()
+                .map(|index| {
+                    let item = &self.items[*index];
+                    let marker = if item.enabled { "*" } else { "x" };
+                    let path = truncate_start(&item.path, path_width);
+                    format!(
+                        "{marker} [{:<4}] {:<name_width$} | {path:<path_width$} | {}",
+                        item.scope, item.name, item.description
+                    )
+                })
+                .collect();
+            self.wide = Some(WideListRows {
+                header: String::new(),
+                rows,
+            });
         }
 
         let enabled_count = self.items.iter().filter(|item| item.enabled).count();
@@ -104,6 +157,9 @@ impl SkillsListPanelState {
             enabled_count,
             self.items.len()
         );
+        if let Some(wide) = self.wide.as_mut() {
+            wide.header = self.header.clone();
+        }
     }
 
     pub fn selected_item_index(&self) -> Option<usize> {
@@ -0,0 +1,190 @@
+use codelia_protocol::RunUsage;
+use std::collections::{HashMap, HashSet};
+
+/// List prices in USD per 1M tokens, from `model.list` details.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ModelPricing {
+    pub input: f64,
+    pub output: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct TokenTotals {
+    pub calls: u64,
+    /// Includes `cached_input_tokens`.
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cached_input_tokens: u64,
+}
+
+impl TokenTotals {
+    pub(crate) fn add(&mut self, usage: &RunUsage) {
+        self.calls += 1;
+        self.input_tokens += usage.input_tokens;
+        self.output_tokens += usage.output_tokens;
+        self.cached_input_tokens += usage.cached_input_tokens;
+    }
+
+    fn cost(&self, pricing: ModelPricing) -> f64 {
+        (self.input_tokens as f64 * pricing.input + self.output_tokens as f64 * pricing.output)
+            / 1_000_000.0
+    }
+}
+
+/// Token totals per `provider/model`, in order of first use.
+pub(crate) type ModelTotals = Vec<(String, TokenTotals)>;
+
+/// `run.usage` totals for the latest run and the session, priced with `model.list` costs.
+#[derive(Debug, Default)]
+pub(crate) struct UsageTracker {
+    session_id: Option<String>,
+    run_id: Option<String>,
+    pub(crate) run: ModelTotals,
+    pub(crate) session: ModelTotals,
+    pricing: HashMap<String, ModelPricing>,
+    /// Providers whose prices were asked for, so a provider without details is asked once.
+    pub(crate) pricing_requested: HashSet<String>,
+}
+
+pub(crate) fn model_key(provider: &str, model: &str) -> String {
+    format!("{provider}/{model}")
+}
+
+fn add_to(totals: &mut ModelTotals, key: &str, usage: &RunUsage) {
+    match totals.iter_mut().find(|(candidate, _)| candidate == key) {
+        Some((_, entry)) => entry.add(usage),
+        None => {
+            let mut entry = TokenTotals::default();
+            entry.add(usage);
+            totals.push((key.to_string(), entry));
+        }
+    }
+}
+
+impl UsageTracker {
+    /// A call under another session id than the last one starts new session totals.
+    pub(crate) fn record(&mut self, session_id: Option<&str>, provider: &str, usage: &RunUsage) {
+        if session_id.is_some() && self.session_id.as_deref() != session_id {
+            if self.session_id.is_some() {
+                self.session.clear();
+            }
+            self.session_id = session_id.map(str::to_string);
+        }
+        if self.run_id != usage.run_id {
+            self.run.clear();
+            self.run_id = usage.run_id.clone();
+        }
+        let key = model_key(provider, &usage.model);
+        add_to(&mut self.run, &key, usage);
+        add_to(&mut self.session, &key, usage);
+    }
+
+    pub(crate) fn set_pricing(&mut self, provider: &str, model: &str, pricing: ModelPricing) {
+        self.pricing.insert(model_key(provider, model), pricing);
+    }
+
+    pub(crate) fn has_pricing(&self, provider: &str, model: &str) -> bool {
+        self.pricing.contains_key(&model_key(provider, model))
+    }
+
+    pub(crate) fn cost_of(&self, key: &str, totals: &TokenTotals) -> Option<f64> {
+        self.pricing.get(key).map(|pricing| totals.cost(*pricing))
+    }
+
+    /// Sum over the models with known prices; `None` when none of them has one.
+    pub(crate) fn total_cost(&self, totals: &ModelTotals) -> Option<f64> {
+        totals
+            .iter()
+            .filter_map(|(key, entry)| self.cost_of(key, entry))
+            .reduce(|sum, cost| sum + cost)
+    }
+
+    /// Compact session summary for the status line, once a call reported usage.
+    pub(crate) fn status_segment(&self) -> Option<String> {
+        if self.session.is_empty() {
+            return None;
+        }
+        let tokens = self
+            .session
+            .iter()
+            .map(|(_, entry)| entry.input_tokens + entry.output_tokens)
+            .sum();
+        let tokens = format_token_count(tokens);
+        Some(match self.total_cost(&self.session) {
+            Some(cost) => format!("usage: {tokens} tok ~{}", format_usd(cost)),
+            None => format!("usage: {tokens} tok"),
+        })
+    }
+}
+
+/// `950`, `12.3k`, `1.2M`.
+pub(crate) fn format_token_count(count: u64) -> String {
+    match count {
+        0..=999 => count.to_string(),
+        1_000..=999_999 => format!("{:.1}k", count as f64 / 1_000.0),
+        _ => format!("{:.1}M", count as f64 / 1_000_000.0),
+    }
+}
+
+/// Sub-dollar amounts keep three decimals so cheap runs don't all read `$0.00`.
+pub(crate) fn format_usd(cost: f64) -> String {
+    if cost < 1.0 {
+        format!("${cost:.3}")
+    } else {
+        format!("${cost:.2}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_token_count, ModelPricing, UsageTracker};
+    use codelia_protocol::RunUsage;
+
+    fn usage(run_id: &str, model: &str, input: u64, output: u64) -> RunUsage {
+        RunUsage {
+            run_id: Some(run_id.to_string()),
+            provider: None,
+            model: model.to_string(),
+            input_tokens: input,
+            output_tokens: output,
+            cached_input_tokens: 0,
+            cache_creation_tokens: 0,
+        }
+    }
+
+    #[test]
+    fn totals_split_by_run_and_session_and_price_known_models() {
+        let mut tracker = UsageTracker::default();
+        assert_eq!(tracker.status_segment(), None);
+        tracker.record(Some("s1"), "openai", &usage("r1", "gpt-5", 10_000, 1_000));
+        tracker.record(Some("s1"), "openai", &usage("r2", "gpt-5", 20_000, 2_000));
+        tracker.record(Some("s1"), "openai", &usage("r2", "gpt-5-mini", 1_000, 500));
+        assert_eq!(tracker.run.len(), 2);
+        assert_eq!(tracker.run[0].1.calls, 1);
+        assert_eq!(tracker.session[0].1.input_tokens, 30_000);
+        assert_eq!(
+            tracker.status_segment().as_deref(),
+            Some("usage: 34.5k tok")
+        );
+
+        tracker.set_pricing(
+            "openai",
+            "gpt-5",
+            ModelPricing {
+                input: 1.25,
+                output: 10.0,
+            },
+        );
+        let cost = tracker.total_cost(&tracker.session).expect("priced");
+        assert!((cost - 0.0675).abs() < 1e-9, "{cost}");
+        assert_eq!(
+            tracker.status_segment().as_deref(),
+            Some("usage: 34.5k tok ~$0.068")
+        );
+
+        tracker.record(Some("s2"), "openai", &usage("r3", "gpt-5", 100, 10));
+        assert_eq!(tracker.session.len(), 1);
+        assert_eq!(tracker.session[0].1.calls, 1);
+        assert_eq!(format_token_count(1_300_000), "1.3M");
+    }
+}
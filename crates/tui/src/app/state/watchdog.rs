@@ -0,0 +1,107 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Raw runtime lines kept for the diagnostics snapshot.
+const RECENT_EVENT_LIMIT: usize = 50;
+const RECENT_EVENT_MAX_CHARS: usize = 500;
+
+/// Notices when a run stops producing runtime output; warns once per silence.
+#[derive(Debug, Clone)]
+pub(crate) struct RuntimeWatchdog {
+    last_activity: Instant,
+    warned: bool,
+    recent: VecDeque<(Instant, String)>,
+}
+
+impl Default for RuntimeWatchdog {
+    fn default() -> Self {
+        Self {
+            last_activity: Instant::now(),
+            warned: false,
+            recent: VecDeque::new(),
+        }
+    }
+}
+
+impl RuntimeWatchdog {
+    /// Any line from the runtime (event, notification or RPC response) counts as a sign of life.
+    pub(crate) fn record_line(&mut self, line: &str, now: Instant) {
+        self.touch(now);
+        let mut line = line.trim_end().to_string();
+        if let Some((cut, _)) = line.char_indices().nth(RECENT_EVENT_MAX_CHARS) {
+            line.truncate(cut);
+            line.push('…');
+        }
+        if self.recent.len() == RECENT_EVENT_LIMIT {
+            self.recent.pop_front();
+        }
+        self.recent.push_back((now, line));
+    }
+
+    fn touch(&mut self, now: Instant) {
+        self.last_activity = now;
+        self.warned = false;
+    }
+
+    /// Returns the silence length the first time it reaches `timeout` during a run; idle time
+    /// between runs never counts.
+    pub(crate) fn check(
+        &mut self,
+        now: Instant,
+        timeout: Duration,
+        running: bool,
+    ) -> Option<Duration> {
+        if !running {
+            self.touch(now);
+            return None;
+        }
+        if timeout.is_zero() || self.warned {
+            return None;
+        }
+        let silence = now.saturating_duration_since(self.last_activity);
+        if silence < timeout {
+            return None;
+        }
+        self.warned = true;
+        Some(silence)
+    }
+
+    pub(crate) fn silence(&self, now: Instant) -> Duration {
+        now.saturating_duration_since(self.last_activity)
+    }
+
+    /// Recent runtime lines with their age, oldest first.
+    pub(crate) fn recent_lines(&self, now: Instant) -> impl Iterator<Item = (Duration, &str)> {
+        self.recent
+            .iter()
+            .map(move |(at, line)| (now.saturating_duration_since(*at), line.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RuntimeWatchdog;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn warns_once_per_silence_during_a_run() {
+        let start = Instant::now();
+        let timeout = Duration::from_secs(90);
+        let mut watchdog = RuntimeWatchdog::default();
+        assert_eq!(watchdog.check(start, timeout, false), None);
+        let later = start + Duration::from_secs(95);
+        assert_eq!(watchdog.check(later, Duration::ZERO, true), None);
+        assert_eq!(
+            watchdog.check(later, timeout, true),
+            Some(Duration::from_secs(95))
+        );
+        assert_eq!(watchdog.check(later, timeout, true), None);
+
+        watchdog.record_line(&"x".repeat(600), later);
+        assert_eq!(watchdog.check(later + timeout / 2, timeout, true), None);
+        let (age, line) = watchdog.recent_lines(later).next().expect("line");
+        assert_eq!(age, Duration::ZERO);
+        assert_eq!(line.chars().count(), 501);
+        assert!(watchdog.check(later + timeout, timeout, true).is_some());
+    }
+}
@@ -0,0 +1,57 @@
+use std::path::Path;
+
+const CWD_MODIFIER_PREFIX: &str = "@cwd:";
+
+/// Splits a leading `@cwd:<path>` modifier off a prompt; returns the path and the rest.
+pub(crate) fn split_cwd_modifier(input: &str) -> Option<(&str, &str)> {
+    let rest = input.trim_start().strip_prefix(CWD_MODIFIER_PREFIX)?;
+    let (path, prompt) = match rest.find(char::is_whitespace) {
+        Some(index) => (&rest[..index], &rest[index..]),
+        None => (rest, ""),
+    };
+    if path.is_empty() {
+        return None;
+    }
+    Some((path, prompt.trim()))
+}
+
+/// Status label for a working directory that differs from the sandbox root.
+/// Paths inside the root are shown relative to it.
+pub(crate) fn working_dir_label(cwd: &str, sandbox_root: Option<&str>) -> Option<String> {
+    let Some(root) = sandbox_root else {
+        return Some(cwd.to_string());
+    };
+    if Path::new(cwd) == Path::new(root) {
+        return None;
+    }
+    match Path::new(cwd).strip_prefix(root) {
+        Ok(relative) => Some(relative.display().to_string()),
+        Err(_) => Some(cwd.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split_cwd_modifier, working_dir_label};
+
+    #[test]
+    fn cwd_modifier_and_label_parsing() {
+        assert_eq!(
+            split_cwd_modifier("@cwd:packages/api fix the failing test"),
+            Some(("packages/api", "fix the failing test"))
+        );
+        assert_eq!(split_cwd_modifier("  @cwd:../web"), Some(("../web", "")));
+        assert_eq!(split_cwd_modifier("@cwd: fix it"), None);
+        assert_eq!(split_cwd_modifier("see @cwd:foo"), None);
+
+        assert_eq!(working_dir_label("/repo", Some("/repo")), None);
+        assert_eq!(
+            working_dir_label("/repo/packages/api", Some("/repo")).as_deref(),
+            Some("packages/api")
+        );
+        assert_eq!(
+            working_dir_label("/elsewhere", Some("/repo")).as_deref(),
+            Some("/elsewhere")
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::app::state::{parse_theme_name, LogColor, ThemeName};
+use crate::app::util::settings::{find_custom_theme, resolve_theme_id, CustomTheme, ThemeColor};
 use ratatui::style::{Color, Modifier};
 use std::sync::{Mutex, OnceLock};
 
@@ -9,6 +10,30 @@ pub(crate) struct InlinePalette {
     pub(crate) inline_code: LogColor,
 }
 
+/// Colors of rendered diffs (permission previews and edit results). The gutter and
+/// marker colors are baked into spans by the parser, like `InlinePalette`; the row surfaces
+/// reach `log_wrap` through `UiColors::diff_*_bg`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct DiffPalette {
+    pub(crate) line_number: LogColor,
+    pub(crate) added_marker: LogColor,
+    pub(crate) removed_marker: LogColor,
+    pub(crate) added_bg: LogColor,
+    pub(crate) removed_bg: LogColor,
+    /// Context rows of a syntax-highlighted diff.
+    pub(crate) code_bg: LogColor,
+}
+
+/// Built-in themes share it: diff rows are painted dark surfaces on any terminal background.
+const DEFAULT_DIFF_PALETTE: DiffPalette = DiffPalette {
+    line_number: LogColor::rgb(143, 161, 179),
+    added_marker: LogColor::rgb(163, 190, 140),
+    removed_marker: LogColor::rgb(191, 97, 106),
+    added_bg: LogColor::rgb(21, 45, 33),
+    removed_bg: LogColor::rgb(53, 28, 31),
+    code_bg: LogColor::rgb(24, 30, 36),
+};
+
 #[derive(Clone, Copy)]
 pub(crate) struct UiColors {
     pub(crate) input_bg: Color,
@@ -38,6 +63,7 @@ pub(crate) struct UiColors {
 #[derive(Clone, Copy)]
 struct ThemeDefinition {
     inline_palette: InlinePalette,
+    diff_palette: DiffPalette,
     syntect_theme_name: &'static str,
     ui: UiColors,
 }
@@ -57,7 +83,8 @@ enum ThemeKind {
 }
 
 static THEME_DEFINITION: OnceLock<Mutex<ThemeDefinition>> = OnceLock::new();
-static CURRENT_THEME_NAME: OnceLock<Mutex<ThemeName>> = OnceLock::new();
+/// Built-in name or custom theme file name.
+static CURRENT_THEME_ID: OnceLock<Mutex<String>> = OnceLock::new();
 
 fn theme_kind_for_name(name: ThemeName) -> ThemeKind {
     match name {
@@ -74,33 +101,36 @@ fn theme_kind_for_name(name: ThemeName) -> ThemeKind {
     }
 }
 
-fn selected_theme_name_from_env() -> ThemeName {
-    if let Ok(selected) = std::env::var("CODELIA_TUI_THEME") {
-        if let Some(parsed) = parse_theme_name(&selected) {
-            return parsed;
-        }
-    }
-    if let Ok(selected) = std::env::var("CODELIA_TUI_MARKDOWN_THEME") {
-        if let Some(parsed) = parse_theme_name(&selected) {
-            return parsed;
+fn selected_theme_id_from_env() -> String {
+    for name in ["CODELIA_TUI_THEME", "CODELIA_TUI_MARKDOWN_THEME"] {
+        if let Some(id) = std::env::var(name)
+            .ok()
+            .and_then(|value| resolve_theme_id(&value))
+        {
+            return id;
         }
     }
-    ThemeName::Codelia
+    ThemeName::Codelia.as_str().to_string()
 }
 
-fn current_theme_name() -> ThemeName {
-    let lock = CURRENT_THEME_NAME.get_or_init(|| Mutex::new(selected_theme_name_from_env()));
-    lock.lock()
-        .map(|value| *value)
-        .unwrap_or(ThemeName::Codelia)
+fn current_theme_id_mutex() -> &'static Mutex<String> {
+    CURRENT_THEME_ID.get_or_init(|| Mutex::new(selected_theme_id_from_env()))
 }
 
-pub(crate) fn active_theme_name() -> ThemeName {
-    current_theme_name()
+pub(crate) fn active_theme_id() -> String {
+    current_theme_id_mutex()
+        .lock()
+        .map(|value| value.clone())
+        .unwrap_or_else(|_| ThemeName::Codelia.as_str().to_string())
 }
 
+/// Custom themes fall back to their base palette.
 fn selected_theme_kind() -> ThemeKind {
-    theme_kind_for_name(current_theme_name())
+    let id = active_theme_id();
+    let name = parse_theme_name(&id)
+        .or_else(|| find_custom_theme(&id).map(|theme| theme.base))
+        .unwrap_or(ThemeName::Codelia);
+    theme_kind_for_name(name)
 }
 
 const fn inline_palette_for_kind(kind: ThemeKind) -> InlinePalette {
@@ -222,7 +252,7 @@ fn log_color_to_color(value: LogColor) -> Color {
     Color::Rgb(value.r, value.g, value.b)
 }
 
-fn adaptive_ui_colors(palette: InlinePalette) -> UiColors {
+fn adaptive_ui_colors(palette: InlinePalette, diff: DiffPalette) -> UiColors {
     let accent_heading = log_color_to_color(palette.heading);
     let accent_bold = log_color_to_color(palette.bold);
     let accent_inline = log_color_to_color(palette.inline_code);
@@ -232,9 +262,9 @@ fn adaptive_ui_colors(palette: InlinePalette) -> UiColors {
     UiColors {
         input_bg: Color::Rgb(40, 40, 40),
         code_block_bg: Color::Rgb(36, 44, 52),
-        diff_code_block_bg: Color::Rgb(24, 30, 36),
-        diff_added_bg: Color::Rgb(21, 45, 33),
-        diff_removed_bg: Color::Rgb(53, 28, 31),
+        diff_code_block_bg: log_color_to_color(diff.code_bg),
+        diff_added_bg: log_color_to_color(diff.added_bg),
+        diff_removed_bg: log_color_to_color(diff.removed_bg),
         surface_fg: Color::Rgb(238, 238, 238),
         log_primary_fg: Color::Reset,
         log_muted_fg: muted,
@@ -273,26 +303,115 @@ fn syntect_theme_name_for_kind(kind: ThemeKind) -> &'static str {
 fn build_theme_definition(name: ThemeName) -> ThemeDefinition {
     let kind = theme_kind_for_name(name);
     let inline_palette = adaptive_inline_palette(kind);
+    let diff_palette = DEFAULT_DIFF_PALETTE;
     ThemeDefinition {
         inline_palette,
+        diff_palette,
         syntect_theme_name: syntect_theme_name_for_kind(kind),
-        ui: adaptive_ui_colors(inline_palette),
+        ui: adaptive_ui_colors(inline_palette, diff_palette),
+    }
+}
+
+fn color_or(color: Option<ThemeColor>, fallback: LogColor) -> LogColor {
+    match color {
+        Some(ThemeColor::Rgb(color)) => color,
+        _ => fallback,
+    }
+}
+
+/// Custom accents are used as written: the file author picked them for their own terminal, so
+/// they skip the contrast adjustment the built-ins get.
+fn build_custom_theme_definition(theme: &CustomTheme) -> ThemeDefinition {
+    let base = build_theme_definition(theme.base);
+    let inline_palette = InlinePalette {
+        heading: color_or(theme.color("inline.heading"), base.inline_palette.heading),
+        bold: color_or(theme.color("inline.bold"), base.inline_palette.bold),
+        inline_code: color_or(
+            theme.color("inline.inline_code"),
+            base.inline_palette.inline_code,
+        ),
+    };
+    let diff = base.diff_palette;
+    let diff_palette = DiffPalette {
+        line_number: color_or(theme.color("diff.line_number"), diff.line_number),
+        added_marker: color_or(theme.color("diff.added_marker"), diff.added_marker),
+        removed_marker: color_or(theme.color("diff.removed_marker"), diff.removed_marker),
+        added_bg: color_or(theme.color("diff.added_bg"), diff.added_bg),
+        removed_bg: color_or(theme.color("diff.removed_bg"), diff.removed_bg),
+        code_bg: color_or(theme.color("diff.code_bg"), diff.code_bg),
+    };
+    let mut ui = adaptive_ui_colors(inline_palette, diff_palette);
+    let slot = |key: &str, fallback: Color| match theme.color(key) {
+        Some(ThemeColor::Default) => Color::Reset,
+        Some(ThemeColor::Rgb(color)) => log_color_to_color(color),
+        None => fallback,
+    };
+    ui.surface_fg = slot("log.text", ui.surface_fg);
+    ui.log_primary_fg = slot("log.primary", ui.log_primary_fg);
+    ui.log_muted_fg = slot("log.muted", ui.log_muted_fg);
+    ui.log_system_fg = slot("log.system", ui.log_system_fg);
+    ui.log_tool_call_fg = slot("log.tool_call", ui.log_tool_call_fg);
+    ui.log_tool_result_fg = slot("log.tool_result", ui.log_tool_result_fg);
+    ui.log_status_fg = slot("log.status", ui.log_status_fg);
+    ui.log_space_fg = slot("log.space", ui.log_space_fg);
+    ui.log_error_fg = slot("log.error", ui.log_error_fg);
+    ui.input_bg = slot("log.user_bg", ui.input_bg);
+    ui.code_block_bg = slot("log.code_bg", ui.code_block_bg);
+    ui.panel_divider_fg = slot("log.divider", ui.panel_divider_fg);
+    ui.run_ready_fg = slot("status.ready", ui.run_ready_fg);
+    ui.run_completed_fg = slot("status.completed", ui.run_completed_fg);
+    ui.run_cancelled_fg = slot("status.cancelled", ui.run_cancelled_fg);
+    ui.run_error_fg = slot("status.error", ui.run_error_fg);
+    ui.bang_prefix_fg = slot("status.badge", ui.bang_prefix_fg);
+    ui.debug_perf_fg = slot("status.perf", ui.debug_perf_fg);
+    if let Some(dim) = theme.dim_details {
+        ui.low_emphasis_modifier = if dim {
+            Modifier::DIM
+        } else {
+            Modifier::empty()
+        };
+    }
+    ThemeDefinition {
+        inline_palette,
+        diff_palette,
+        syntect_theme_name: theme.syntax.unwrap_or(base.syntect_theme_name),
+        ui,
+    }
+}
+
+/// Unknown ids (a custom theme whose file was removed) fall back to the default theme.
+fn build_theme_definition_for_id(id: &str) -> ThemeDefinition {
+    match parse_theme_name(id) {
+        Some(name) => build_theme_definition(name),
+        None => find_custom_theme(id)
+            .map(|theme| build_custom_theme_definition(&theme))
+            .unwrap_or_else(|| build_theme_definition(ThemeName::Codelia)),
     }
 }
 
 fn theme_definition_mutex() -> &'static Mutex<ThemeDefinition> {
-    THEME_DEFINITION.get_or_init(|| Mutex::new(build_theme_definition(current_theme_name())))
+    THEME_DEFINITION.get_or_init(|| Mutex::new(build_theme_definition_for_id(&active_theme_id())))
 }
 
-pub(crate) fn apply_theme_name(name: ThemeName) {
-    if let Ok(mut current) = CURRENT_THEME_NAME
-        .get_or_init(|| Mutex::new(selected_theme_name_from_env()))
-        .lock()
-    {
-        *current = name;
+/// Applies a built-in (aliases included) or loaded custom theme; false for unknown names.
+pub(crate) fn apply_theme_id(value: &str) -> bool {
+    let Some(id) = resolve_theme_id(value) else {
+        return false;
+    };
+    if let Ok(mut definition) = theme_definition_mutex().lock() {
+        *definition = build_theme_definition_for_id(&id);
+    }
+    if let Ok(mut current) = current_theme_id_mutex().lock() {
+        *current = id;
     }
+    true
+}
+
+/// Rebuilds the active theme after the custom theme files were rescanned.
+pub(crate) fn reload_active_theme() {
+    let id = active_theme_id();
     if let Ok(mut definition) = theme_definition_mutex().lock() {
-        *definition = build_theme_definition(name);
+        *definition = build_theme_definition_for_id(&id);
     }
 }
 
@@ -303,11 +422,23 @@ pub(crate) fn inline_palette() -> InlinePalette {
         .unwrap_or_else(|_| adaptive_inline_palette(selected_theme_kind()))
 }
 
+pub(crate) fn diff_palette() -> DiffPalette {
+    theme_definition_mutex()
+        .lock()
+        .map(|theme| theme.diff_palette)
+        .unwrap_or(DEFAULT_DIFF_PALETTE)
+}
+
 pub(crate) fn ui_colors() -> UiColors {
     theme_definition_mutex()
         .lock()
         .map(|theme| theme.ui)
-        .unwrap_or_else(|_| adaptive_ui_colors(adaptive_inline_palette(selected_theme_kind())))
+        .unwrap_or_else(|_| {
+            adaptive_ui_colors(
+                adaptive_inline_palette(selected_theme_kind()),
+                DEFAULT_DIFF_PALETTE,
+            )
+        })
 }
 
 pub(crate) fn syntect_theme_name() -> &'static str {
@@ -334,7 +465,7 @@ mod tests {
     #[test]
     fn adaptive_palette_uses_terminal_defaults_and_explicit_dark_surfaces() {
         let palette = adaptive_inline_palette(ThemeKind::Codelia);
-        let colors = adaptive_ui_colors(palette);
+        let colors = adaptive_ui_colors(palette, DEFAULT_DIFF_PALETTE);
 
         assert_eq!(colors.input_bg, Color::Rgb(40, 40, 40));
         assert_eq!(colors.surface_fg, Color::Rgb(238, 238, 238));
@@ -368,6 +499,58 @@ mod tests {
         }
     }
 
+    #[test]
+    fn diff_markers_stand_out_on_their_row_surfaces() {
+        let diff = DEFAULT_DIFF_PALETTE;
+        let contrast = |fg: LogColor, bg: LogColor| {
+            (relative_luminance(fg) + 0.05) / (relative_luminance(bg) + 0.05)
+        };
+        assert!(contrast(diff.added_marker, diff.added_bg) >= 4.5);
+        assert!(contrast(diff.removed_marker, diff.removed_bg) >= 3.0);
+        assert!(contrast(diff.line_number, diff.code_bg) >= 4.5);
+        let colors = adaptive_ui_colors(adaptive_inline_palette(ThemeKind::Codelia), diff);
+        assert_eq!(colors.diff_added_bg, Color::Rgb(21, 45, 33));
+    }
+
+    #[test]
+    fn custom_theme_overrides_slots_and_keeps_its_base_for_the_rest() {
+        let theme = CustomTheme {
+            name: "nord".to_string(),
+            description: String::new(),
+            base: ThemeName::Ocean,
+            syntax: Some("base16-mocha.dark"),
+            dim_details: Some(true),
+            colors: vec![
+                (
+                    "inline.heading",
+                    ThemeColor::Rgb(LogColor::rgb(136, 192, 208)),
+                ),
+                ("diff.added_bg", ThemeColor::Rgb(LogColor::rgb(30, 60, 40))),
+                ("log.user_bg", ThemeColor::Default),
+                (
+                    "status.completed",
+                    ThemeColor::Rgb(LogColor::rgb(163, 190, 140)),
+                ),
+            ],
+        };
+        let definition = build_custom_theme_definition(&theme);
+        let ocean = build_theme_definition(ThemeName::Ocean);
+
+        assert_eq!(
+            definition.inline_palette.heading,
+            LogColor::rgb(136, 192, 208)
+        );
+        assert_eq!(definition.inline_palette.bold, ocean.inline_palette.bold);
+        // Accents derived from the inline palette follow the override.
+        assert_eq!(definition.ui.log_system_fg, Color::Rgb(136, 192, 208));
+        assert_eq!(definition.ui.diff_added_bg, Color::Rgb(30, 60, 40));
+        assert_eq!(definition.ui.input_bg, Color::Reset);
+        assert_eq!(definition.ui.run_completed_fg, Color::Rgb(163, 190, 140));
+        assert_eq!(definition.ui.run_error_fg, ocean.ui.run_error_fg);
+        assert_eq!(definition.ui.low_emphasis_modifier, Modifier::DIM);
+        assert_eq!(definition.syntect_theme_name, "base16-mocha.dark");
+    }
+
     #[test]
     fn painted_code_surfaces_use_dark_syntax_themes() {
         assert_eq!(
@@ -0,0 +1,135 @@
+use super::transcode::{max_source_bytes, transcode_image_bytes};
+use crate::app::util::ClipboardImageError;
+use crate::app::PendingImageAttachment;
+use image::ImageFormat;
+use std::path::{Path, PathBuf};
+
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "webp", "gif"];
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let hex = bytes
+            .get(index + 1..index + 3)
+            .and_then(|pair| std::str::from_utf8(pair).ok())
+            .and_then(|pair| u8::from_str_radix(pair, 16).ok());
+        match (bytes[index], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                index += 3;
+            }
+            (byte, _) => {
+                out.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn has_image_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            IMAGE_EXTENSIONS
+                .iter()
+                .any(|known| extension.eq_ignore_ascii_case(known))
+        })
+}
+
+/// Image paths in pasted text, as terminals insert them when files are dragged in: one or
+/// more shell-quoted or backslash-escaped paths, or `file://` URIs. `None` unless every word
+/// is an existing PNG/JPEG/WebP/GIF file, so ordinary text that mentions a path stays text.
+pub(crate) fn dropped_image_paths(text: &str) -> Option<Vec<PathBuf>> {
+    let words = shell_words::split(text.trim()).ok()?;
+    if words.is_empty() {
+        return None;
+    }
+    words
+        .into_iter()
+        .map(|word| {
+            let path = match word.strip_prefix("file://") {
+                Some(rest) => PathBuf::from(percent_decode(rest)),
+                None => PathBuf::from(word),
+            };
+            (has_image_extension(&path) && path.is_file()).then_some(path)
+        })
+        .collect()
+}
+
+/// Loads an image file as an attachment through `transcode_image_bytes`; a file over its
+/// format's source cap (by extension) is refused before being read.
+pub(crate) fn read_image_file_attachment(
+    path: &Path,
+    max_bytes: usize,
+) -> Result<PendingImageAttachment, ClipboardImageError> {
+    let read_error = |error: std::io::Error| {
+        ClipboardImageError::Clipboard(format!("{}: {error}", path.display()))
+    };
+    let size = std::fs::metadata(path).map_err(read_error)?.len() as usize;
+    let max_source = ImageFormat::from_path(path)
+        .ok()
+        .and_then(max_source_bytes)
+        .unwrap_or(usize::MAX);
+    if size > max_source {
+        return Err(ClipboardImageError::TooLarge {
+            bytes: size,
+            max_bytes: max_source,
+        });
+    }
+    let bytes = std::fs::read(path).map_err(read_error)?;
+    transcode_image_bytes(&bytes, max_bytes).map_err(|error| match error {
+        ClipboardImageError::Encode(error) => {
+            ClipboardImageError::Encode(format!("{}: {error}", path.display()))
+        }
+        error => error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dropped_image_paths, read_image_file_attachment};
+    use crate::app::util::ClipboardImageError;
+    use image::{DynamicImage, ImageFormat, RgbImage};
+
+    #[test]
+    fn dropped_paths_load_images_with_their_size() {
+        let dir = std::env::temp_dir().join(format!("codelia drop {}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("dir");
+        let png = dir.join("shot.png");
+        let jpeg = dir.join("photo.JPG");
+        let gif = dir.join("clip.gif");
+        let image = DynamicImage::ImageRgb8(RgbImage::from_pixel(40, 30, image::Rgb([9, 99, 199])));
+        image.save_with_format(&png, ImageFormat::Png).expect("png");
+        image
+            .save_with_format(&jpeg, ImageFormat::Jpeg)
+            .expect("jpeg");
+        image.save_with_format(&gif, ImageFormat::Gif).expect("gif");
+
+        let escaped = format!("{} ", png.display().to_string().replace(' ', "\\ "));
+        assert_eq!(dropped_image_paths(&escaped), Some(vec![png.clone()]));
+        let uri = format!("file://{}", jpeg.display().to_string().replace(' ', "%20"));
+        let quoted = format!("'{}' {uri} '{}'", png.display(), gif.display());
+        assert_eq!(
+            dropped_image_paths(&quoted),
+            Some(vec![png.clone(), jpeg.clone(), gif.clone()])
+        );
+        assert_eq!(dropped_image_paths(&format!("see {}", png.display())), None);
+
+        let attached = read_image_file_attachment(&png, 64 * 1024).expect("png attachment");
+        assert_eq!((attached.width, attached.height), (40, 30));
+        assert!(attached.data_url.starts_with("data:image/png;base64,"));
+        let attached = read_image_file_attachment(&jpeg, 64 * 1024).expect("jpeg attachment");
+        assert!(attached.data_url.starts_with("data:image/jpeg;base64,"));
+        let attached = read_image_file_attachment(&gif, 64 * 1024).expect("gif attachment");
+        assert_eq!((attached.width, attached.height), (40, 30));
+        assert!(attached.data_url.starts_with("data:image/png;base64,"));
+        assert!(matches!(
+            read_image_file_attachment(&png, 8),
+            Err(ClipboardImageError::TooLarge { max_bytes: 8, .. })
+        ));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
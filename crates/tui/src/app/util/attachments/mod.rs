@@ -3,6 +3,12 @@ use crate::app::PendingImageAttachment;
 use serde_json::{json, Value};
 use std::collections::{HashMap, HashSet};
 
+mod image_file;
+mod transcode;
+
+pub(crate) use image_file::{dropped_image_paths, read_image_file_attachment};
+pub(crate) use transcode::{encode_rgba_image, transcode_image_bytes};
+
 const TOKEN_PREFIX: &str = "[[codelia-img:";
 const TOKEN_SUFFIX: &str = "]]";
 
@@ -104,6 +110,109 @@ pub fn referenced_attachment_ids(
         .collect()
 }
 
+/// Mismatches between attachment tokens in the text and the pending attachments.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct AttachmentTokenCheck {
+    /// Token-like text (`[[codelia-img:…`) that resolves to no pending attachment.
+    pub orphaned_tokens: Vec<String>,
+    /// Pending attachments no token in the text refers to.
+    pub unreferenced_ids: Vec<String>,
+}
+
+impl AttachmentTokenCheck {
+    pub fn is_clean(&self) -> bool {
+        self.orphaned_tokens.is_empty() && self.unreferenced_ids.is_empty()
+    }
+}
+
+/// Char ranges of token-like text that does not resolve; a damaged token runs to its
+/// `]]` or, without one, to the next whitespace.
+fn orphaned_token_ranges(
+    chars: &[char],
+    nonce: &str,
+    attachments: &HashMap<String, PendingImageAttachment>,
+) -> Vec<(usize, usize)> {
+    let valid = parse_token_matches(chars, nonce)
+        .into_iter()
+        .filter(|token| attachments.contains_key(&token.attachment_id))
+        .map(|token| (token.start, token.end))
+        .collect::<HashMap<_, _>>();
+    let prefix_len = TOKEN_PREFIX.chars().count();
+    let mut ranges = Vec::new();
+    let mut idx = 0;
+    while idx < chars.len() {
+        if let Some(end) = valid.get(&idx) {
+            idx = *end;
+            continue;
+        }
+        if !chars_match_literal(chars, idx, TOKEN_PREFIX) {
+            idx += 1;
+            continue;
+        }
+        let mut end = idx + prefix_len;
+        while end < chars.len() && !chars[end].is_whitespace() {
+            if chars_match_literal(chars, end, TOKEN_SUFFIX) {
+                end += TOKEN_SUFFIX.chars().count();
+                break;
+            }
+            end += 1;
+        }
+        ranges.push((idx, end));
+        idx = end;
+    }
+    ranges
+}
+
+pub fn check_attachment_tokens(
+    input: &str,
+    nonce: &str,
+    attachments: &HashMap<String, PendingImageAttachment>,
+) -> AttachmentTokenCheck {
+    let chars = input.chars().collect::<Vec<_>>();
+    let orphaned_tokens = orphaned_token_ranges(&chars, nonce, attachments)
+        .into_iter()
+        .map(|(start, end)| chars[start..end].iter().collect())
+        .collect();
+    let referenced = referenced_attachment_ids(input, nonce, attachments)
+        .into_iter()
+        .collect::<HashSet<_>>();
+    let mut unreferenced_ids = attachments
+        .keys()
+        .filter(|attachment_id| !referenced.contains(*attachment_id))
+        .cloned()
+        .collect::<Vec<_>>();
+    unreferenced_ids.sort();
+    AttachmentTokenCheck {
+        orphaned_tokens,
+        unreferenced_ids,
+    }
+}
+
+/// `input` without the orphaned token text found by [`check_attachment_tokens`].
+pub fn strip_orphaned_attachment_tokens(
+    input: &str,
+    nonce: &str,
+    attachments: &HashMap<String, PendingImageAttachment>,
+) -> String {
+    let chars = input.chars().collect::<Vec<_>>();
+    let mut output = String::new();
+    let mut src_index = 0;
+    for (start, end) in orphaned_token_ranges(&chars, nonce, attachments) {
+        output.extend(chars[src_index..start].iter());
+        src_index = end;
+    }
+    output.extend(chars[src_index..].iter());
+    output
+}
+
+/// Media type from the `data:<type>;base64,` prefix; PNG when it cannot be read.
+pub(crate) fn image_media_type(data_url: &str) -> &str {
+    data_url
+        .strip_prefix("data:")
+        .and_then(|rest| rest.split_once(';'))
+        .map_or("image/png", |(media_type, _)| media_type)
+}
+
 pub fn build_run_input_payload(
     input: &str,
     nonce: &str,
@@ -131,7 +240,7 @@ pub fn build_run_input_payload(
                     "type": "image_url",
                     "image_url": {
                         "url": image.data_url,
-                        "media_type": "image/png",
+                        "media_type": image_media_type(&image.data_url),
                         "detail": "auto"
                     }
                 }));
@@ -161,10 +270,53 @@ pub fn build_run_input_payload(
     json!({ "type": "parts", "parts": parts })
 }
 
+/// `payload` from [`build_run_input_payload`] with `texts` as leading text parts.
+pub fn prepend_text_parts(payload: Value, texts: Vec<String>) -> Value {
+    if texts.is_empty() {
+        return payload;
+    }
+    let mut parts = texts
+        .into_iter()
+        .map(|text| json!({ "type": "text", "text": text }))
+        .collect::<Vec<_>>();
+    match payload.get("type").and_then(Value::as_str) {
+        Some("parts") => {
+            if let Some(existing) = payload.get("parts").and_then(Value::as_array) {
+                parts.extend(existing.iter().cloned());
+            }
+        }
+        _ => {
+            let text = payload.get("text").and_then(Value::as_str).unwrap_or("");
+            if !text.is_empty() {
+                parts.push(json!({ "type": "text", "text": text }));
+            }
+        }
+    }
+    json!({ "type": "parts", "parts": parts })
+}
+
+/// The composer as drawn: attachment tokens become `[Image N]` labels, with the cursor and any
+/// selection anchor mapped onto the labelled text.
 pub fn render_input_with_attachment_labels(
     input: &InputState,
     nonce: &str,
     attachments: &HashMap<String, PendingImageAttachment>,
+) -> InputState {
+    let mut display = label_attachment_tokens(input, nonce, attachments);
+    if let Some(anchor) = input.selection_anchor() {
+        let mut at_anchor = InputState::default();
+        at_anchor.buffer = input.buffer.clone();
+        at_anchor.cursor = anchor;
+        let anchor_display = label_attachment_tokens(&at_anchor, nonce, attachments).cursor;
+        display.set_selection_anchor(Some(anchor_display));
+    }
+    display
+}
+
+fn label_attachment_tokens(
+    input: &InputState,
+    nonce: &str,
+    attachments: &HashMap<String, PendingImageAttachment>,
 ) -> InputState {
     let chars = &input.buffer;
     let token_matches = parse_token_matches(chars, nonce);
@@ -267,7 +419,8 @@ pub fn render_input_text_with_attachment_labels(
 #[cfg(test)]
 mod tests {
     use super::{
-        build_run_input_payload, make_attachment_token, render_input_with_attachment_labels,
+        build_run_input_payload, check_attachment_tokens, make_attachment_token,
+        prepend_text_parts, render_input_with_attachment_labels, strip_orphaned_attachment_tokens,
     };
     use crate::app::state::InputState;
     use crate::app::PendingImageAttachment;
@@ -312,6 +465,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn file_texts_lead_the_prompt_parts() {
+        let payload = build_run_input_payload("explain @a.rs", "abc", &HashMap::new());
+        assert_eq!(prepend_text_parts(payload.clone(), Vec::new()), payload);
+        assert_eq!(
+            prepend_text_parts(payload, vec!["<file path=\"a.rs\">\n</file>".to_string()]),
+            json!({
+                "type": "parts",
+                "parts": [
+                    { "type": "text", "text": "<file path=\"a.rs\">\n</file>" },
+                    { "type": "text", "text": "explain @a.rs" }
+                ]
+            })
+        );
+    }
+
     #[test]
     fn payload_only_uses_first_duplicate_token_occurrence() {
         let nonce = "abc";
@@ -353,4 +522,30 @@ mod tests {
         let rendered = render_input_with_attachment_labels(&input, nonce, &attachments);
         assert_eq!(rendered.current(), "hello [Image 1]");
     }
+
+    #[test]
+    fn token_check_reports_orphans_and_unreferenced_attachments() {
+        let nonce = "abc";
+        let mut attachments = HashMap::new();
+        attachments.insert("img1".to_string(), sample_image());
+        attachments.insert("img2".to_string(), sample_image());
+        let live = make_attachment_token(nonce, "img1");
+        let stale = make_attachment_token("old", "img1");
+        let input = format!("see {live} and {stale} plus [[codelia-img:abc:img9 end");
+
+        let check = check_attachment_tokens(&input, nonce, &attachments);
+        assert_eq!(
+            check.orphaned_tokens,
+            vec![stale.clone(), "[[codelia-img:abc:img9".to_string()]
+        );
+        assert_eq!(check.unreferenced_ids, vec!["img2".to_string()]);
+        assert!(!check.is_clean());
+        assert_eq!(
+            strip_orphaned_attachment_tokens(&input, nonce, &attachments),
+            format!("see {live} and  plus  end")
+        );
+
+        attachments.remove("img2");
+        assert!(check_attachment_tokens(&format!("ok {live}"), nonce, &attachments).is_clean());
+    }
 }
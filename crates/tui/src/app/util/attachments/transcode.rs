@@ -0,0 +1,220 @@
+use crate::app::util::ClipboardImageError;
+use crate::app::PendingImageAttachment;
+use base64::Engine;
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat, ImageReader, RgbaImage};
+use std::io::Cursor;
+
+/// Largest source accepted per format, checked before decoding; animated GIFs carry every
+/// frame even though only the first is sent.
+const MAX_SOURCE_BYTES: &[(ImageFormat, usize)] = &[
+    (ImageFormat::Png, 32 * 1024 * 1024),
+    (ImageFormat::Jpeg, 32 * 1024 * 1024),
+    (ImageFormat::WebP, 32 * 1024 * 1024),
+    (ImageFormat::Gif, 16 * 1024 * 1024),
+];
+/// Downscaling stops once the longer side would drop below this.
+const MIN_DOWNSCALED_SIDE: u32 = 256;
+const JPEG_QUALITY: u8 = 85;
+
+pub(crate) fn max_source_bytes(format: ImageFormat) -> Option<usize> {
+    MAX_SOURCE_BYTES
+        .iter()
+        .find(|(known, _)| *known == format)
+        .map(|(_, max_bytes)| *max_bytes)
+}
+
+fn encode_error(error: impl std::fmt::Display) -> ClipboardImageError {
+    ClipboardImageError::Encode(error.to_string())
+}
+
+fn attachment(media_type: &str, bytes: &[u8], width: u32, height: u32) -> PendingImageAttachment {
+    PendingImageAttachment {
+        data_url: format!(
+            "data:{media_type};base64,{}",
+            base64::engine::general_purpose::STANDARD.encode(bytes)
+        ),
+        width,
+        height,
+        encoded_bytes: bytes.len(),
+    }
+}
+
+fn encode_png(image: &DynamicImage) -> Result<Vec<u8>, ClipboardImageError> {
+    let mut bytes = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+        .map_err(encode_error)?;
+    Ok(bytes)
+}
+
+fn encode_jpeg(image: &DynamicImage) -> Result<Vec<u8>, ClipboardImageError> {
+    let mut bytes = Vec::new();
+    JpegEncoder::new_with_quality(&mut bytes, JPEG_QUALITY)
+        .encode_image(&DynamicImage::ImageRgb8(image.to_rgb8()))
+        .map_err(encode_error)?;
+    Ok(bytes)
+}
+
+/// PNG when it fits (keeps transparency), otherwise JPEG, shrinking by a quarter per step
+/// until it fits under `max_bytes`.
+fn fit_image(
+    image: DynamicImage,
+    max_bytes: usize,
+) -> Result<PendingImageAttachment, ClipboardImageError> {
+    let png = encode_png(&image)?;
+    if png.len() <= max_bytes {
+        return Ok(attachment("image/png", &png, image.width(), image.height()));
+    }
+    let mut image = image;
+    loop {
+        let jpeg = encode_jpeg(&image)?;
+        if jpeg.len() <= max_bytes {
+            return Ok(attachment(
+                "image/jpeg",
+                &jpeg,
+                image.width(),
+                image.height(),
+            ));
+        }
+        let (width, height) = (image.width() * 3 / 4, image.height() * 3 / 4);
+        if width.max(height) < MIN_DOWNSCALED_SIDE || width == 0 || height == 0 {
+            return Err(ClipboardImageError::TooLarge {
+                bytes: jpeg.len(),
+                max_bytes,
+            });
+        }
+        image = image.resize_exact(width, height, FilterType::Triangle);
+    }
+}
+
+/// Attachment from encoded image bytes. PNG and JPEG under `max_bytes` are sent unchanged;
+/// WebP, GIF (first frame) and oversized images are re-encoded and downscaled as needed.
+pub(crate) fn transcode_image_bytes(
+    bytes: &[u8],
+    max_bytes: usize,
+) -> Result<PendingImageAttachment, ClipboardImageError> {
+    let format = image::guess_format(bytes).map_err(encode_error)?;
+    let max_source = max_source_bytes(format).ok_or_else(|| {
+        ClipboardImageError::Encode(format!(
+            "unsupported image format {format:?} (expected PNG, JPEG, WebP or GIF)"
+        ))
+    })?;
+    if bytes.len() > max_source {
+        return Err(ClipboardImageError::TooLarge {
+            bytes: bytes.len(),
+            max_bytes: max_source,
+        });
+    }
+    let reader = || ImageReader::with_format(Cursor::new(bytes), format);
+    if bytes.len() <= max_bytes {
+        let media_type = match format {
+            ImageFormat::Png => Some("image/png"),
+            ImageFormat::Jpeg => Some("image/jpeg"),
+            _ => None,
+        };
+        if let Some(media_type) = media_type {
+            let (width, height) = reader().into_dimensions().map_err(encode_error)?;
+            return Ok(attachment(media_type, bytes, width, height));
+        }
+    }
+    fit_image(reader().decode().map_err(encode_error)?, max_bytes)
+}
+
+/// Attachment from raw RGBA pixels (native clipboard images).
+pub(crate) fn encode_rgba_image(
+    width: usize,
+    height: usize,
+    rgba: Vec<u8>,
+    max_bytes: usize,
+) -> Result<PendingImageAttachment, ClipboardImageError> {
+    let (Ok(width), Ok(height)) = (u32::try_from(width), u32::try_from(height)) else {
+        return Err(ClipboardImageError::Encode(
+            "clipboard image is too large".to_string(),
+        ));
+    };
+    let image = RgbaImage::from_raw(width, height, rgba).ok_or_else(|| {
+        ClipboardImageError::Encode("clipboard image size does not match its pixels".to_string())
+    })?;
+    fit_image(DynamicImage::ImageRgba8(image), max_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode_rgba_image, transcode_image_bytes};
+    use crate::app::util::ClipboardImageError;
+    use image::{DynamicImage, ImageFormat, RgbaImage};
+    use std::io::Cursor;
+
+    /// Busy enough that PNG stays large, smooth enough for JPEG to shrink it.
+    fn pattern(width: u32, height: u32) -> DynamicImage {
+        DynamicImage::ImageRgba8(RgbaImage::from_fn(width, height, |x, y| {
+            image::Rgba([
+                (x * y % 251) as u8,
+                (x ^ y) as u8,
+                (x * 7 + y * 13) as u8,
+                255,
+            ])
+        }))
+    }
+
+    fn encoded(image: &DynamicImage, format: ImageFormat) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut bytes), format)
+            .expect("encode");
+        bytes
+    }
+
+    #[test]
+    fn formats_are_passed_through_or_transcoded_to_fit() {
+        let small = pattern(16, 8);
+        let png = encoded(&small, ImageFormat::Png);
+        let image = transcode_image_bytes(&png, 1024 * 1024).expect("png");
+        assert_eq!(image.encoded_bytes, png.len());
+        assert_eq!((image.width, image.height), (16, 8));
+
+        let jpeg = encoded(&DynamicImage::ImageRgb8(small.to_rgb8()), ImageFormat::Jpeg);
+        let image = transcode_image_bytes(&jpeg, 1024 * 1024).expect("jpeg");
+        assert!(image.data_url.starts_with("data:image/jpeg;base64,"));
+        assert_eq!(image.encoded_bytes, jpeg.len());
+
+        for format in [ImageFormat::Gif, ImageFormat::WebP] {
+            let source = encoded(&small, format);
+            let image = transcode_image_bytes(&source, 1024 * 1024).expect("transcoded");
+            assert!(image.data_url.starts_with("data:image/png;base64,"));
+            assert_eq!((image.width, image.height), (16, 8));
+        }
+
+        let large = encoded(&pattern(800, 600), ImageFormat::Png);
+        let image = transcode_image_bytes(&large, 200 * 1024).expect("downscaled");
+        assert!(image.data_url.starts_with("data:image/jpeg;base64,"));
+        assert!(image.encoded_bytes <= 200 * 1024);
+        assert_eq!((image.width, image.height), (600, 450));
+
+        assert!(matches!(
+            transcode_image_bytes(&large, 1024),
+            Err(ClipboardImageError::TooLarge {
+                max_bytes: 1024,
+                ..
+            })
+        ));
+        assert!(matches!(
+            transcode_image_bytes(b"not an image", 1024),
+            Err(ClipboardImageError::Encode(_))
+        ));
+    }
+
+    #[test]
+    fn clipboard_pixels_become_png_or_shrink_to_jpeg() {
+        let image = pattern(300, 300);
+        let pixels = image.to_rgba8().into_raw();
+        let attachment = encode_rgba_image(300, 300, pixels.clone(), 1024 * 1024).expect("png");
+        assert!(attachment.data_url.starts_with("data:image/png;base64,"));
+        let attachment = encode_rgba_image(300, 300, pixels, 120 * 1024).expect("jpeg");
+        assert!(attachment.data_url.starts_with("data:image/jpeg;base64,"));
+        assert!(attachment.encoded_bytes <= 120 * 1024);
+        assert!(encode_rgba_image(10, 10, vec![0; 3], 1024).is_err());
+    }
+}
@@ -0,0 +1,272 @@
+mod sha256;
+
+use serde_json::{json, Value};
+use std::fs;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+pub(crate) use sha256::sha256_hex;
+
+const AUDIT_FILE: &str = "audit.jsonl";
+const AUDIT_SUMMARY_MAX_CHARS: usize = 200;
+/// `prev` of the first record in a fresh file.
+const GENESIS_HASH: &str = "0";
+
+/// One confirm-dialog decision as stored in `audit.jsonl`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AuditEntry {
+    pub ts_unix_ms: u64,
+    /// Tool of the `permission.ready` with the confirm's `tool_call_id`; `None` for other confirms.
+    pub tool: Option<String>,
+    pub title: String,
+    pub summary: String,
+    pub allowed: bool,
+    pub remember: bool,
+    pub reason: Option<String>,
+}
+
+impl AuditEntry {
+    /// First line of the dialog message, shortened for the log.
+    pub(crate) fn summarize(message: &str) -> String {
+        let first = message.lines().next().unwrap_or_default().trim();
+        if first.chars().count() <= AUDIT_SUMMARY_MAX_CHARS {
+            return first.to_string();
+        }
+        let mut summary = first
+            .chars()
+            .take(AUDIT_SUMMARY_MAX_CHARS)
+            .collect::<String>();
+        summary.push('…');
+        summary
+    }
+
+    pub(crate) fn decision_label(&self) -> &'static str {
+        match (self.allowed, self.remember) {
+            (true, true) => "allow+remember",
+            (true, false) => "allow",
+            (false, _) => "deny",
+        }
+    }
+
+    fn to_record(&self, prev: &str) -> Value {
+        json!({
+            "ts_unix_ms": self.ts_unix_ms,
+            "tool": self.tool,
+            "title": self.title,
+            "summary": self.summary,
+            "decision": if self.allowed { "allow" } else { "deny" },
+            "remember": self.remember,
+            "reason": self.reason,
+            "prev": prev,
+        })
+    }
+
+    fn from_record(record: &Value) -> Option<Self> {
+        let text = |key: &str| record.get(key).and_then(Value::as_str).map(str::to_string);
+        Some(Self {
+            ts_unix_ms: record.get("ts_unix_ms")?.as_u64()?,
+            tool: text("tool"),
+            title: text("title")?,
+            summary: text("summary").unwrap_or_default(),
+            allowed: record.get("decision")?.as_str()? == "allow",
+            remember: record
+                .get("remember")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+            reason: text("reason"),
+        })
+    }
+}
+
+/// Each record hashes its predecessor's hash plus its own fields (keys sorted by serde_json),
+/// so editing, dropping, or reordering a line breaks every later link.
+fn record_hash(record: &Value) -> String {
+    let prev = record
+        .get("prev")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    sha256_hex(format!("{prev}\n{record}").as_bytes())
+}
+
+/// Parsed audit file; `broken_at` is the 1-based record where chain verification first failed.
+#[derive(Debug, Default)]
+pub(crate) struct AuditLog {
+    pub entries: Vec<AuditEntry>,
+    pub broken_at: Option<usize>,
+}
+
+pub(crate) fn audit_log_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("codelia").join(AUDIT_FILE))
+}
+
+/// Last non-blank line, read backwards from the end in chunks so appending never rereads the
+/// whole file.
+fn last_line(file: &mut fs::File) -> std::io::Result<Option<String>> {
+    const CHUNK: u64 = 4096;
+    let mut end = file.seek(SeekFrom::End(0))?;
+    let mut tail = Vec::new();
+    while end > 0 {
+        let start = end.saturating_sub(CHUNK);
+        let mut chunk = vec![0; (end - start) as usize];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(&mut chunk)?;
+        chunk.extend_from_slice(&tail);
+        tail = chunk;
+        end = start;
+        let trimmed = tail.trim_ascii_end();
+        if let Some(newline) = trimmed.iter().rposition(|byte| *byte == b'\n') {
+            tail = trimmed[newline + 1..].to_vec();
+            break;
+        }
+    }
+    let line = String::from_utf8_lossy(&tail).trim().to_string();
+    Ok((!line.is_empty()).then_some(line))
+}
+
+fn last_hash(path: &Path) -> Result<String, String> {
+    let mut file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(GENESIS_HASH.to_string())
+        }
+        Err(error) => return Err(error.to_string()),
+    };
+    let Some(line) = last_line(&mut file).map_err(|error| error.to_string())? else {
+        return Ok(GENESIS_HASH.to_string());
+    };
+    serde_json::from_str::<Value>(&line)
+        .ok()
+        .and_then(|record| record.get("hash")?.as_str().map(str::to_string))
+        .ok_or_else(|| format!("{}: last record is not valid audit JSON", path.display()))
+}
+
+/// Appends `entry` chained to the file's last record; the file is only ever appended to.
+pub(crate) fn append_audit_entry(path: &Path, entry: &AuditEntry) -> Result<(), String> {
+    let prev = last_hash(path)?;
+    let mut record = entry.to_record(&prev);
+    let hash = record_hash(&record);
+    record["hash"] = Value::String(hash);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|error| error.to_string())?;
+    }
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|error| error.to_string())?;
+    writeln!(file, "{record}").map_err(|error| error.to_string())
+}
+
+/// Reads and verifies the whole chain; a missing file is an empty log.
+pub(crate) fn load_audit_log(path: &Path) -> Result<AuditLog, String> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(AuditLog::default())
+        }
+        Err(error) => return Err(error.to_string()),
+    };
+    let mut log = AuditLog::default();
+    let mut expected_prev = GENESIS_HASH.to_string();
+    for line in text.lines().filter(|line| !line.trim().is_empty()) {
+        let number = log.entries.len() + 1;
+        let mut record = serde_json::from_str::<Value>(line).unwrap_or(Value::Null);
+        let stored_hash = record
+            .as_object_mut()
+            .and_then(|object| object.remove("hash"))
+            .and_then(|hash| hash.as_str().map(str::to_string));
+        let prev = record.get("prev").and_then(Value::as_str);
+        let valid = stored_hash.as_deref() == Some(record_hash(&record).as_str())
+            && prev == Some(expected_prev.as_str());
+        if !valid && log.broken_at.is_none() {
+            log.broken_at = Some(number);
+        }
+        expected_prev = stored_hash.unwrap_or_default();
+        match AuditEntry::from_record(&record) {
+            Some(entry) => log.entries.push(entry),
+            None => log.entries.push(AuditEntry {
+                ts_unix_ms: 0,
+                tool: None,
+                title: "(unreadable record)".to_string(),
+                summary: String::new(),
+                allowed: false,
+                remember: false,
+                reason: None,
+            }),
+        }
+    }
+    Ok(log)
+}
+
+/// `YYYY-MM-DD HH:MM:SSZ` from Unix milliseconds (proleptic Gregorian, UTC).
+pub(crate) fn format_utc_timestamp(ts_unix_ms: u64) -> String {
+    let secs = ts_unix_ms / 1000;
+    let days = (secs / 86_400) as i64;
+    let time = secs % 86_400;
+    // Howard Hinnant's days-to-civil conversion.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}Z",
+        time / 3600,
+        time / 60 % 60,
+        time % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{append_audit_entry, format_utc_timestamp, load_audit_log, AuditEntry};
+
+    fn entry(tool: &str, allowed: bool) -> AuditEntry {
+        AuditEntry {
+            ts_unix_ms: 1_700_000_000_000,
+            tool: Some(tool.to_string()),
+            title: "Run command?".to_string(),
+            summary: AuditEntry::summarize("git push origin main\nremember: bash git push"),
+            allowed,
+            remember: false,
+            reason: (!allowed).then(|| "not on prod".to_string()),
+        }
+    }
+
+    #[test]
+    fn audit_chain_round_trips_and_detects_edits() {
+        let path = std::env::temp_dir().join(format!("codelia-audit-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        append_audit_entry(&path, &entry("bash", true)).expect("append");
+        append_audit_entry(&path, &entry("bash", false)).expect("append");
+        // A record longer than one tail-read chunk still chains to the next.
+        let mut long = entry("bash", false);
+        long.reason = Some("x".repeat(10_000));
+        append_audit_entry(&path, &long).expect("append");
+        append_audit_entry(&path, &entry("write", true)).expect("append");
+
+        let log = load_audit_log(&path).expect("load");
+        assert_eq!(log.broken_at, None);
+        assert_eq!(log.entries.len(), 4);
+        assert_eq!(log.entries[0].summary, "git push origin main");
+        assert_eq!(log.entries[1].decision_label(), "deny");
+        assert_eq!(log.entries[1].reason.as_deref(), Some("not on prod"));
+
+        let text = std::fs::read_to_string(&path).expect("read");
+        std::fs::write(&path, text.replacen("\"deny\"", "\"allow\"", 1)).expect("tamper");
+        assert_eq!(load_audit_log(&path).expect("load").broken_at, Some(2));
+
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(
+            format_utc_timestamp(1_700_000_000_000),
+            "2023-11-14 22:13:20Z"
+        );
+    }
+}
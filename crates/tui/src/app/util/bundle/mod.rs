@@ -0,0 +1,305 @@
+use crate::app::state::PendingImageAttachment;
+use crate::app::PendingShellResult;
+use base64::Engine;
+use serde_json::{json, Value};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub(crate) const BUNDLE_MANIFEST_FILE: &str = "manifest.json";
+const BUNDLE_TRANSCRIPT_FILE: &str = "transcript.txt";
+const BUNDLE_DIFF_FILE: &str = "changes.diff";
+const BUNDLE_SHELL_DIR: &str = "shell";
+const BUNDLE_ATTACHMENTS_DIR: &str = "attachments";
+
+pub(crate) struct RunBundleInput<'a> {
+    pub transcript: &'a [String],
+    pub diffs: &'a [String],
+    pub shell_results: &'a [PendingShellResult],
+    pub attachments: &'a [(String, PendingImageAttachment)],
+    pub session_id: Option<&'a str>,
+    pub model: Option<&'a str>,
+    pub created_at_unix_ms: u128,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct RunBundleSummary {
+    pub file_count: usize,
+    pub diff_count: usize,
+    pub shell_result_count: usize,
+    pub attachment_count: usize,
+}
+
+pub(crate) fn default_bundle_dir_name(created_at_unix_ms: u128) -> String {
+    format!("codelia-bundle-{created_at_unix_ms}")
+}
+
+/// Cache ids come from the runtime; keep only characters that are safe in a file name.
+fn sanitize_file_stem(value: &str) -> String {
+    let sanitized = value
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.') {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    let trimmed = sanitized.trim_matches('.');
+    if trimmed.is_empty() {
+        "output".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+fn decode_data_url(data_url: &str) -> Option<(&str, Vec<u8>)> {
+    let rest = data_url.strip_prefix("data:")?;
+    let (meta, payload) = rest.split_once(',')?;
+    let mime = meta.strip_suffix(";base64")?;
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(payload)
+        .ok()?;
+    Some((mime, bytes))
+}
+
+fn extension_for_mime(mime: &str) -> &'static str {
+    match mime {
+        "image/png" => "png",
+        "image/jpeg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        _ => "bin",
+    }
+}
+
+fn write_shell_stream(
+    dir: &Path,
+    result: &PendingShellResult,
+    stream: &str,
+    text: Option<&str>,
+    cache_id: Option<&str>,
+    file_count: &mut usize,
+) -> io::Result<Option<String>> {
+    let Some(text) = text else {
+        return Ok(None);
+    };
+    let stem = sanitize_file_stem(cache_id.unwrap_or(&result.id));
+    let relative = format!("{BUNDLE_SHELL_DIR}/{stem}.{stream}.txt");
+    fs::write(dir.join(&relative), text)?;
+    *file_count += 1;
+    Ok(Some(relative))
+}
+
+pub(crate) fn write_run_bundle(
+    dir: &Path,
+    input: &RunBundleInput<'_>,
+) -> io::Result<RunBundleSummary> {
+    fs::create_dir_all(dir)?;
+    let mut summary = RunBundleSummary::default();
+
+    let mut transcript = input.transcript.join("\n");
+    transcript.push('\n');
+    fs::write(dir.join(BUNDLE_TRANSCRIPT_FILE), transcript)?;
+    summary.file_count += 1;
+
+    let diff_file = if input.diffs.is_empty() {
+        None
+    } else {
+        let mut diff = input.diffs.join("\n");
+        diff.push('\n');
+        fs::write(dir.join(BUNDLE_DIFF_FILE), diff)?;
+        summary.file_count += 1;
+        summary.diff_count = input.diffs.len();
+        Some(BUNDLE_DIFF_FILE)
+    };
+
+    let mut shell_entries = Vec::new();
+    if !input.shell_results.is_empty() {
+        fs::create_dir_all(dir.join(BUNDLE_SHELL_DIR))?;
+    }
+    for result in input.shell_results {
+        let stdout_file = write_shell_stream(
+            dir,
+            result,
+            "stdout",
+            result
+                .stdout
+                .as_deref()
+                .or(result.stdout_excerpt.as_deref()),
+            result.stdout_cache_id.as_deref(),
+            &mut summary.file_count,
+        )?;
+        let stderr_file = write_shell_stream(
+            dir,
+            result,
+            "stderr",
+            result
+                .stderr
+                .as_deref()
+                .or(result.stderr_excerpt.as_deref()),
+            result.stderr_cache_id.as_deref(),
+            &mut summary.file_count,
+        )?;
+        shell_entries.push(json!({
+            "id": result.id,
+            "command": result.command_preview,
+            "exit_code": result.exit_code,
+            "signal": result.signal,
+            "duration_ms": result.duration_ms,
+            "stdout_file": stdout_file,
+            "stderr_file": stderr_file,
+            "stdout_cache_id": result.stdout_cache_id,
+            "stderr_cache_id": result.stderr_cache_id,
+            "truncated": {
+                "stdout": result.truncated_stdout,
+                "stderr": result.truncated_stderr,
+                "combined": result.truncated_combined,
+            },
+        }));
+    }
+    summary.shell_result_count = shell_entries.len();
+
+    let mut attachment_entries = Vec::new();
+    for (attachment_id, image) in input.attachments {
+        let Some((mime, bytes)) = decode_data_url(&image.data_url) else {
+            continue;
+        };
+        if attachment_entries.is_empty() {
+            fs::create_dir_all(dir.join(BUNDLE_ATTACHMENTS_DIR))?;
+        }
+        let relative = format!(
+            "{BUNDLE_ATTACHMENTS_DIR}/{}.{}",
+            sanitize_file_stem(attachment_id),
+            extension_for_mime(mime)
+        );
+        fs::write(dir.join(&relative), bytes)?;
+        summary.file_count += 1;
+        attachment_entries.push(json!({
+            "id": attachment_id,
+            "file": relative,
+            "mime": mime,
+            "width": image.width,
+            "height": image.height,
+            "bytes": image.encoded_bytes,
+        }));
+    }
+    summary.attachment_count = attachment_entries.len();
+
+    let manifest: Value = json!({
+        "version": 1,
+        "created_at_unix_ms": input.created_at_unix_ms as u64,
+        "session_id": input.session_id,
+        "model": input.model,
+        "transcript_file": BUNDLE_TRANSCRIPT_FILE,
+        "diff_file": diff_file,
+        "diff_count": summary.diff_count,
+        "shell_results": shell_entries,
+        "attachments": attachment_entries,
+    });
+    let manifest_text = serde_json::to_string_pretty(&manifest).map_err(io::Error::other)?;
+    fs::write(dir.join(BUNDLE_MANIFEST_FILE), manifest_text)?;
+    summary.file_count += 1;
+
+    Ok(summary)
+}
+
+pub(crate) fn resolve_bundle_dir(target: Option<&str>, created_at_unix_ms: u128) -> PathBuf {
+    match target.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => PathBuf::from(value),
+        None => PathBuf::from(default_bundle_dir_name(created_at_unix_ms)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        decode_data_url, sanitize_file_stem, write_run_bundle, RunBundleInput, BUNDLE_MANIFEST_FILE,
+    };
+    use crate::app::state::PendingImageAttachment;
+    use crate::app::PendingShellResult;
+    use serde_json::Value;
+
+    fn shell_result() -> PendingShellResult {
+        PendingShellResult {
+            id: "shell_1".to_string(),
+            command_preview: "cargo test".to_string(),
+            exit_code: Some(1),
+            signal: None,
+            duration_ms: 42,
+            stdout: None,
+            stderr: Some("boom".to_string()),
+            stdout_excerpt: Some("head...".to_string()),
+            stderr_excerpt: None,
+            stdout_cache_id: Some("cache/abc".to_string()),
+            stderr_cache_id: None,
+            truncated_stdout: true,
+            truncated_stderr: false,
+            truncated_combined: false,
+        }
+    }
+
+    #[test]
+    fn sanitize_file_stem_replaces_path_separators() {
+        assert_eq!(sanitize_file_stem("cache/abc"), "cache_abc");
+        assert_eq!(sanitize_file_stem(".."), "output");
+    }
+
+    #[test]
+    fn decode_data_url_reads_mime_and_payload() {
+        let (mime, bytes) = decode_data_url("data:image/png;base64,aGk=").expect("decode");
+        assert_eq!(mime, "image/png");
+        assert_eq!(bytes, b"hi");
+        assert!(decode_data_url("image/png;base64,aGk=").is_none());
+    }
+
+    #[test]
+    fn write_run_bundle_writes_manifest_and_artifacts() {
+        let dir = std::env::temp_dir().join(format!("codelia-bundle-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let transcript = vec!["> hello".to_string(), "hi there".to_string()];
+        let diffs = vec!["--- a/x\n+++ b/x\n@@ -1 +1 @@\n-a\n+b".to_string()];
+        let shell_results = vec![shell_result()];
+        let attachments = vec![(
+            "img1".to_string(),
+            PendingImageAttachment {
+                data_url: "data:image/png;base64,aGk=".to_string(),
+                width: 1,
+                height: 1,
+                encoded_bytes: 2,
+            },
+        )];
+        let summary = write_run_bundle(
+            &dir,
+            &RunBundleInput {
+                transcript: &transcript,
+                diffs: &diffs,
+                shell_results: &shell_results,
+                attachments: &attachments,
+                session_id: Some("session-1"),
+                model: Some("openai/gpt-5"),
+                created_at_unix_ms: 1,
+            },
+        )
+        .expect("write bundle");
+
+        assert_eq!(summary.file_count, 6);
+        assert_eq!(summary.shell_result_count, 1);
+        assert_eq!(summary.attachment_count, 1);
+        assert!(dir.join("shell/cache_abc.stdout.txt").is_file());
+        assert!(dir.join("shell/shell_1.stderr.txt").is_file());
+        assert_eq!(
+            std::fs::read(dir.join("attachments/img1.png")).expect("attachment"),
+            b"hi"
+        );
+        let manifest: Value = serde_json::from_str(
+            &std::fs::read_to_string(dir.join(BUNDLE_MANIFEST_FILE)).expect("manifest"),
+        )
+        .expect("manifest json");
+        assert_eq!(manifest["diff_file"], "changes.diff");
+        assert_eq!(manifest["session_id"], "session-1");
+        assert_eq!(manifest["shell_results"][0]["stdout_cache_id"], "cache/abc");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::app::util::attachments::{encode_rgba_image, transcode_image_bytes};
 use crate::app::PendingImageAttachment;
 use arboard::{Clipboard, Error as ClipboardError};
 use base64::Engine;
@@ -5,6 +6,7 @@ use serde_json::Value;
 use std::env;
 use std::fs;
 use std::process::Command;
+use std::sync::Mutex;
 
 #[derive(Debug)]
 pub enum ClipboardImageError {
@@ -23,33 +25,7 @@ $obj = @{ width = $img.Width; height = $img.Height; base64 = [Convert]::ToBase64
 $obj | ConvertTo-Json -Compress
 "#;
 
-fn encode_png_rgba(
-    width: usize,
-    height: usize,
-    rgba: &[u8],
-) -> Result<Vec<u8>, ClipboardImageError> {
-    let width_u32 = u32::try_from(width).map_err(|_| {
-        ClipboardImageError::Encode("clipboard image width is too large".to_string())
-    })?;
-    let height_u32 = u32::try_from(height).map_err(|_| {
-        ClipboardImageError::Encode("clipboard image height is too large".to_string())
-    })?;
-    let mut bytes = Vec::new();
-    {
-        let mut encoder = png::Encoder::new(&mut bytes, width_u32, height_u32);
-        encoder.set_color(png::ColorType::Rgba);
-        encoder.set_depth(png::BitDepth::Eight);
-        let mut writer = encoder.write_header().map_err(|error| {
-            ClipboardImageError::Encode(format!("failed to write PNG header: {error}"))
-        })?;
-        writer.write_image_data(rgba).map_err(|error| {
-            ClipboardImageError::Encode(format!("failed to encode PNG bytes: {error}"))
-        })?;
-    }
-    Ok(bytes)
-}
-
-fn is_wsl_environment() -> bool {
+pub(crate) fn is_wsl_environment() -> bool {
     if env::var_os("WSL_DISTRO_NAME").is_some() || env::var_os("WSL_INTEROP").is_some() {
         return true;
     }
@@ -90,10 +66,7 @@ fn parse_windows_clipboard_image_json(
             ))
         })?;
     if encoded.len() > max_bytes {
-        return Err(ClipboardImageError::TooLarge {
-            bytes: encoded.len(),
-            max_bytes,
-        });
+        return transcode_image_bytes(&encoded, max_bytes);
     }
     Ok(PendingImageAttachment {
         data_url: format!("data:image/png;base64,{base64_data}"),
@@ -144,6 +117,7 @@ fn read_windows_clipboard_image_attachment(
     parse_windows_clipboard_image_json(&stdout, max_bytes)
 }
 
+/// Clipboard image as PNG, or as a downscaled JPEG when the PNG would exceed `max_bytes`.
 pub fn read_clipboard_image_attachment(
     max_bytes: usize,
 ) -> Result<PendingImageAttachment, ClipboardImageError> {
@@ -157,29 +131,12 @@ pub fn read_clipboard_image_attachment(
             }
             Err(error) => return Err(ClipboardImageError::Clipboard(error.to_string())),
         };
-        let encoded = encode_png_rgba(image.width, image.height, image.bytes.as_ref())?;
-        if encoded.len() > max_bytes {
-            return Err(ClipboardImageError::TooLarge {
-                bytes: encoded.len(),
-                max_bytes,
-            });
-        }
-        let data_url = format!(
-            "data:image/png;base64,{}",
-            base64::engine::general_purpose::STANDARD.encode(encoded.as_slice())
-        );
-        let width = u32::try_from(image.width).map_err(|_| {
-            ClipboardImageError::Encode("clipboard image width is too large".to_string())
-        })?;
-        let height = u32::try_from(image.height).map_err(|_| {
-            ClipboardImageError::Encode("clipboard image height is too large".to_string())
-        })?;
-        Ok(PendingImageAttachment {
-            data_url,
-            width,
-            height,
-            encoded_bytes: encoded.len(),
-        })
+        encode_rgba_image(
+            image.width,
+            image.height,
+            image.bytes.into_owned(),
+            max_bytes,
+        )
     })();
 
     match native_result {
@@ -200,9 +157,29 @@ pub fn read_clipboard_image_attachment(
     }
 }
 
+/// Clipboard handle kept alive for the process: on X11/Wayland the copied text is
+/// only served while its owner exists.
+static TEXT_CLIPBOARD: Mutex<Option<Clipboard>> = Mutex::new(None);
+
+pub(crate) fn write_clipboard_text(text: &str) -> Result<(), String> {
+    let mut guard = TEXT_CLIPBOARD
+        .lock()
+        .map_err(|_| "clipboard lock poisoned".to_string())?;
+    if guard.is_none() {
+        *guard = Some(Clipboard::new().map_err(|error| error.to_string())?);
+    }
+    let Some(clipboard) = guard.as_mut() else {
+        return Err("clipboard unavailable".to_string());
+    };
+    clipboard
+        .set_text(text.to_string())
+        .map_err(|error| error.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::parse_windows_clipboard_image_json;
+    use base64::Engine;
 
     #[test]
     fn parse_windows_clipboard_image_payload_success() {
@@ -218,18 +195,28 @@ mod tests {
     }
 
     #[test]
-    fn parse_windows_clipboard_image_payload_checks_limit() {
+    fn parse_windows_clipboard_image_payload_transcodes_over_limit() {
         let error = parse_windows_clipboard_image_json(
             r#"{"width":10,"height":20,"base64":"iVBORw0KGgo="}"#,
             1,
         )
-        .expect_err("expected size error");
-        match error {
-            super::ClipboardImageError::TooLarge { bytes, max_bytes } => {
-                assert_eq!(bytes, 8);
-                assert_eq!(max_bytes, 1);
-            }
-            other => panic!("unexpected error: {other:?}"),
-        }
+        .expect_err("expected decode error");
+        assert!(matches!(error, super::ClipboardImageError::Encode(_)));
+
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgb8(image::RgbImage::from_fn(300, 300, |x, y| {
+            image::Rgb([(x * y) as u8, (x ^ y) as u8, (x * 7 + y * 13) as u8])
+        }))
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .expect("png");
+        let payload = serde_json::json!({
+            "width": 300,
+            "height": 300,
+            "base64": base64::engine::general_purpose::STANDARD.encode(&png),
+        });
+        let attachment = parse_windows_clipboard_image_json(&payload.to_string(), png.len() - 1)
+            .expect("expected transcoded attachment");
+        assert!(attachment.data_url.starts_with("data:image/jpeg;base64,"));
+        assert!(attachment.encoded_bytes < png.len());
     }
 }
@@ -0,0 +1,144 @@
+use serde_json::{json, Map, Value};
+use std::path::{Path, PathBuf};
+
+/// Tool names the runtime treats as shell for `command_glob` rules (`bash` is the legacy one).
+const SHELL_TOOLS: &[&str] = &["shell", "bash"];
+
+/// The runtime's project config, whose `permissions.allow` rules the runtime matches against
+/// every command segment it is asked to run.
+pub(crate) fn project_config_path(workspace: &Path) -> PathBuf {
+    workspace.join(".codelia").join("config.json")
+}
+
+fn read_config(path: &Path) -> Result<Map<String, Value>, String> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Map::new()),
+        Err(error) => return Err(error.to_string()),
+    };
+    match serde_json::from_str::<Value>(&text) {
+        Ok(Value::Object(config)) => Ok(config),
+        Ok(_) => Err(format!("{}: not a JSON object", path.display())),
+        Err(error) => Err(format!("{}: {error}", path.display())),
+    }
+}
+
+fn write_config(path: &Path, config: Map<String, Value>) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|error| error.to_string())?;
+    }
+    let text = serde_json::to_string_pretty(&Value::Object(config)).map_err(|e| e.to_string())?;
+    std::fs::write(path, format!("{text}\n")).map_err(|error| error.to_string())
+}
+
+/// The `command_glob` of a shell allow rule that has no other constraint.
+fn shell_glob(rule: &Value) -> Option<&str> {
+    let tool = rule.get("tool").and_then(Value::as_str)?;
+    if !SHELL_TOOLS.contains(&tool) || rule.get("command").is_some() {
+        return None;
+    }
+    rule.get("command_glob").and_then(Value::as_str)
+}
+
+/// `permissions.allow`, created when missing; a malformed one is an error, never replaced.
+fn allow_rules(config: &mut Map<String, Value>) -> Result<&mut Vec<Value>, String> {
+    config
+        .entry("permissions")
+        .or_insert_with(|| json!({}))
+        .as_object_mut()
+        .ok_or("permissions is not an object")?
+        .entry("allow")
+        .or_insert_with(|| json!([]))
+        .as_array_mut()
+        .ok_or_else(|| "permissions.allow is not a list".to_string())
+}
+
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Shell `command_glob` allow rules of the project config; a missing file has none.
+pub(crate) fn load_command_rules(path: &Path) -> Result<Vec<String>, String> {
+    let mut config = read_config(path)?;
+    Ok(allow_rules(&mut config)?
+        .iter()
+        .filter_map(shell_glob)
+        .map(str::to_string)
+        .collect())
+}
+
+/// Appends `{ "tool": "shell", "command_glob": pattern }`; false when the pattern is blank or
+/// already allowed. Other config keys and rules are kept.
+pub(crate) fn add_command_rule(path: &Path, pattern: &str) -> Result<bool, String> {
+    let pattern = normalize_whitespace(pattern);
+    if pattern.is_empty() {
+        return Ok(false);
+    }
+    let mut config = read_config(path)?;
+    let rules = allow_rules(&mut config)?;
+    if rules.iter().any(|rule| shell_glob(rule) == Some(&pattern)) {
+        return Ok(false);
+    }
+    rules.push(json!({ "tool": "shell", "command_glob": pattern }));
+    config.entry("version").or_insert(json!(1));
+    write_config(path, config).map(|()| true)
+}
+
+/// Drops the shell rules with this `command_glob`; false when there were none.
+pub(crate) fn remove_command_rule(path: &Path, pattern: &str) -> Result<bool, String> {
+    let pattern = normalize_whitespace(pattern);
+    let mut config = read_config(path)?;
+    let rules = allow_rules(&mut config)?;
+    let before = rules.len();
+    rules.retain(|rule| shell_glob(rule) != Some(&pattern));
+    if rules.len() == before {
+        return Ok(false);
+    }
+    write_config(path, config).map(|()| true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{add_command_rule, load_command_rules, project_config_path, remove_command_rule};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn rules_are_written_into_the_project_permissions_config() {
+        let workspace =
+            std::env::temp_dir().join(format!("codelia-allow-rules-{}", std::process::id()));
+        let path = project_config_path(&workspace);
+        assert_eq!(
+            load_command_rules(&path).expect("missing file"),
+            Vec::<String>::new()
+        );
+
+        std::fs::create_dir_all(path.parent().expect("dir")).expect("dir");
+        std::fs::write(
+            &path,
+            r#"{"version":1,"model":{"name":"m"},"permissions":{"allow":[{"tool":"read"},{"tool":"bash","command":"git"}]}}"#,
+        )
+        .expect("write");
+        assert!(add_command_rule(&path, "cargo  test*").expect("add"));
+        assert!(!add_command_rule(&path, "cargo test*").expect("duplicate"));
+        assert!(add_command_rule(&path, "npm run lint").expect("add"));
+        assert_eq!(
+            load_command_rules(&path).expect("load"),
+            vec!["cargo test*", "npm run lint"]
+        );
+
+        assert!(remove_command_rule(&path, "npm run lint").expect("remove"));
+        assert!(!remove_command_rule(&path, "npm run lint").expect("gone"));
+        let config: Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).expect("read")).expect("json");
+        assert_eq!(config["model"], json!({ "name": "m" }));
+        assert_eq!(
+            config["permissions"]["allow"],
+            json!([
+                { "tool": "read" },
+                { "tool": "bash", "command": "git" },
+                { "tool": "shell", "command_glob": "cargo test*" },
+            ])
+        );
+        let _ = std::fs::remove_dir_all(workspace);
+    }
+}
@@ -0,0 +1,263 @@
+use crate::app::PendingFileAttachment;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+pub(crate) const DIR_MENTION_PREFIX: &str = "dir:";
+/// Directory levels listed under the mentioned directory; deeper ones are summarized.
+const MAX_TREE_DEPTH: usize = 3;
+/// Entry rows per tree; the walk stops there and the attachment is marked truncated.
+const MAX_TREE_ENTRIES: usize = 400;
+/// Files larger than this are listed with their size only.
+const MAX_LINE_COUNT_BYTES: u64 = 1024 * 1024;
+
+/// The workspace file index as nested directories, built once per index.
+#[derive(Debug, Default)]
+pub(crate) struct TreeNode {
+    dirs: BTreeMap<String, TreeNode>,
+    files: Vec<String>,
+}
+
+impl TreeNode {
+    pub(crate) fn from_files(files: &[String]) -> Self {
+        let mut root = Self::default();
+        for file in files {
+            root.insert(file);
+        }
+        root
+    }
+
+    fn subtree(&self, dir: &str) -> Option<&Self> {
+        dir.split('/')
+            .try_fold(self, |node, name| node.dirs.get(name))
+    }
+
+    fn insert(&mut self, relative: &str) {
+        match relative.split_once('/') {
+            Some((dir, rest)) => self.dirs.entry(dir.to_string()).or_default().insert(rest),
+            None => self.files.push(relative.to_string()),
+        }
+    }
+
+    fn file_count(&self) -> usize {
+        self.files.len() + self.dirs.values().map(TreeNode::file_count).sum::<usize>()
+    }
+
+    fn dir_count(&self) -> usize {
+        self.dirs.len() + self.dirs.values().map(TreeNode::dir_count).sum::<usize>()
+    }
+}
+
+/// What the walk listed; files past the row cap are never stat'ed or read.
+#[derive(Default)]
+struct TreeTotals {
+    listed_files: usize,
+    lines: usize,
+    bytes: u64,
+    truncated: bool,
+}
+
+/// Directories containing workspace files, for `@dir:` completion.
+pub(crate) fn workspace_dirs(files: &[String]) -> Vec<String> {
+    let mut dirs = BTreeSet::new();
+    for file in files {
+        let mut end = 0;
+        while let Some(index) = file[end..].find('/') {
+            end += index;
+            dirs.insert(file[..end].to_string());
+            end += 1;
+        }
+    }
+    dirs.into_iter().collect()
+}
+
+/// Lines of a text file; `None` for binaries and files too large to count cheaply.
+fn count_lines(path: &Path, bytes: u64) -> Option<usize> {
+    if bytes > MAX_LINE_COUNT_BYTES {
+        return None;
+    }
+    let content = std::fs::read(path).ok()?;
+    if content.iter().take(8192).any(|byte| *byte == 0) {
+        return None;
+    }
+    let newlines = content.iter().filter(|byte| **byte == b'\n').count();
+    Some(newlines + usize::from(!content.is_empty() && !content.ends_with(b"\n")))
+}
+
+fn size_label(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{bytes}B")
+    } else {
+        format!("{}KB", bytes.div_ceil(1024))
+    }
+}
+
+fn render_node(
+    node: &TreeNode,
+    disk_dir: &Path,
+    indent: &str,
+    depth: usize,
+    rows: &mut Vec<String>,
+    totals: &mut TreeTotals,
+) {
+    let count = node.dirs.len() + node.files.len();
+    let entries = node
+        .dirs
+        .keys()
+        .map(|name| (name, true))
+        .chain(node.files.iter().map(|name| (name, false)));
+    for (index, (name, is_dir)) in entries.enumerate() {
+        if rows.len() >= MAX_TREE_ENTRIES {
+            totals.truncated = true;
+            return;
+        }
+        let last = index + 1 == count;
+        let branch = if last { "└── " } else { "├── " };
+        if is_dir {
+            let child = &node.dirs[name];
+            if depth >= MAX_TREE_DEPTH {
+                let files = child.file_count();
+                totals.listed_files += files;
+                rows.push(format!("{indent}{branch}{name}/ ({files} files)"));
+                continue;
+            }
+            rows.push(format!("{indent}{branch}{name}/"));
+            let child_indent = format!("{indent}{}", if last { "    " } else { "│   " });
+            render_node(
+                child,
+                &disk_dir.join(name),
+                &child_indent,
+                depth + 1,
+                rows,
+                totals,
+            );
+        } else {
+            totals.listed_files += 1;
+            let path = disk_dir.join(name);
+            let bytes = std::fs::metadata(&path).map_or(0, |meta| meta.len());
+            totals.bytes += bytes;
+            let detail = match count_lines(&path, bytes) {
+                Some(lines) => {
+                    totals.lines += lines;
+                    format!("{lines} lines, {}", size_label(bytes))
+                }
+                None => size_label(bytes),
+            };
+            rows.push(format!("{indent}{branch}{name}  {detail}"));
+        }
+    }
+}
+
+/// Tree listing of `dir` from the workspace file index (so ignored files stay out), with
+/// per-file line counts and sizes; sent instead of file contents for `@dir:` mentions. Past
+/// `MAX_TREE_ENTRIES` rows the counts in the header still cover the whole directory, while
+/// lines and sizes cover the listed files only.
+pub(crate) fn read_dir_mention(
+    dir: &str,
+    tree: &TreeNode,
+) -> Result<PendingFileAttachment, String> {
+    let dir = dir.trim_end_matches('/');
+    if !Path::new(dir).is_dir() {
+        return Err(format!("{dir}: not a directory"));
+    }
+    let Some(root) = tree
+        .subtree(dir)
+        .filter(|root| !root.files.is_empty() || !root.dirs.is_empty())
+    else {
+        return Err(format!("{dir}: no workspace files"));
+    };
+    let mut rows = Vec::new();
+    let mut totals = TreeTotals::default();
+    render_node(root, Path::new(dir), "", 1, &mut rows, &mut totals);
+    let files = root.file_count();
+    let listed = if totals.truncated {
+        " in listed files"
+    } else {
+        ""
+    };
+    let mut content = format!(
+        "{dir}/ ({files} files, {} dirs, {} lines, {}{listed})\n",
+        root.dir_count(),
+        totals.lines,
+        size_label(totals.bytes)
+    );
+    for row in rows {
+        content.push_str(&row);
+        content.push('\n');
+    }
+    if totals.truncated {
+        content.push_str(&format!(
+            "… {} more files not listed\n",
+            files - totals.listed_files
+        ));
+    }
+    Ok(PendingFileAttachment {
+        path: format!("{DIR_MENTION_PREFIX}{dir}"),
+        bytes: content.len(),
+        content,
+        truncated: totals.truncated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_dir_mention, workspace_dirs, TreeNode};
+
+    #[test]
+    fn tree_lists_indexed_files_with_counts_and_summarizes_deep_dirs() {
+        let dir = std::env::temp_dir().join(format!("codelia-dir-tree-{}", std::process::id()));
+        let root = dir.display().to_string();
+        let deep = dir.join("a/b/c");
+        std::fs::create_dir_all(&deep).expect("dir");
+        std::fs::write(dir.join("mod.rs"), "fn a() {}\nfn b() {}\n").expect("write");
+        std::fs::write(dir.join("a/x.rs"), "one").expect("write");
+        std::fs::write(deep.join("y.rs"), "").expect("write");
+        std::fs::write(dir.join("ignored.log"), "not indexed\n").expect("write");
+        let files = ["mod.rs", "a/x.rs", "a/b/c/y.rs"]
+            .iter()
+            .map(|file| format!("{root}/{file}"))
+            .collect::<Vec<_>>();
+
+        assert!(workspace_dirs(&files).contains(&format!("{root}/a/b")));
+        let index = TreeNode::from_files(&files);
+        let tree = read_dir_mention(&root, &index).expect("tree");
+        assert_eq!(tree.path, format!("dir:{root}"));
+        assert!(!tree.truncated);
+        let rows = tree.content.lines().skip(1).collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            vec![
+                "├── a/",
+                "│   ├── b/",
+                "│   │   └── c/ (1 files)",
+                "│   └── x.rs  1 lines, 3B",
+                "└── mod.rs  2 lines, 20B",
+            ]
+        );
+        assert!(tree
+            .content
+            .starts_with(&format!("{root}/ (3 files, 3 dirs, 3 lines, 23B)")));
+        assert!(read_dir_mention(&format!("{root}/mod.rs"), &index).is_err());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn tree_walk_stops_at_the_row_cap() {
+        let dir = std::env::temp_dir().join(format!("codelia-dir-cap-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("dir");
+        let root = dir.display().to_string();
+        let files = (0..450)
+            .map(|index| format!("{root}/f{index:03}.txt"))
+            .collect::<Vec<_>>();
+
+        let tree = read_dir_mention(&root, &TreeNode::from_files(&files)).expect("tree");
+        assert!(tree.truncated);
+        let lines = tree.content.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 1 + 400 + 1);
+        assert_eq!(
+            lines[0],
+            format!("{root}/ (450 files, 0 dirs, 0 lines, 0B in listed files)")
+        );
+        assert_eq!(lines[401], "… 50 more files not listed");
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
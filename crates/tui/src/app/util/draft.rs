@@ -0,0 +1,271 @@
+use crate::app::PendingImageAttachment;
+use serde_json::{json, Value};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Instant, SystemTime};
+
+use super::settings::config_dir;
+
+const DRAFT_FILE: &str = "tui-draft.json";
+
+/// Unsent composer content. Images are kept whole since they have no other source (pasted
+/// screenshots); `@file` attachments are kept as paths and read again on restore.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ComposerDraft {
+    pub text: String,
+    /// Nonce of the image attachment tokens in `text`.
+    pub composer_nonce: String,
+    pub images: Vec<(String, PendingImageAttachment)>,
+    pub files: Vec<String>,
+    pub cwd: Option<String>,
+    pub saved_at_unix_ms: u64,
+}
+
+impl ComposerDraft {
+    fn to_json(&self) -> Value {
+        let images = self
+            .images
+            .iter()
+            .map(|(id, image)| {
+                json!({
+                    "id": id,
+                    "data_url": image.data_url,
+                    "width": image.width,
+                    "height": image.height,
+                    "encoded_bytes": image.encoded_bytes,
+                })
+            })
+            .collect::<Vec<_>>();
+        json!({
+            "version": 1,
+            "text": self.text,
+            "composer_nonce": self.composer_nonce,
+            "images": images,
+            "files": self.files,
+            "cwd": self.cwd,
+            "saved_at_unix_ms": self.saved_at_unix_ms,
+        })
+    }
+
+    fn from_json(value: &Value) -> Option<Self> {
+        let text = value.get("text")?.as_str()?.to_string();
+        let str_field = |key| value.get(key).and_then(Value::as_str).map(str::to_string);
+        let images = value
+            .get("images")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|image| {
+                let number = |key| image.get(key).and_then(Value::as_u64);
+                Some((
+                    image.get("id")?.as_str()?.to_string(),
+                    PendingImageAttachment {
+                        data_url: image.get("data_url")?.as_str()?.to_string(),
+                        width: number("width")? as u32,
+                        height: number("height")? as u32,
+                        encoded_bytes: number("encoded_bytes")? as usize,
+                    },
+                ))
+            })
+            .collect();
+        let files = value
+            .get("files")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|path| path.as_str().map(str::to_string))
+            .collect();
+        Some(Self {
+            text,
+            composer_nonce: str_field("composer_nonce").unwrap_or_default(),
+            images,
+            files,
+            cwd: str_field("cwd"),
+            saved_at_unix_ms: value
+                .get("saved_at_unix_ms")
+                .and_then(Value::as_u64)
+                .unwrap_or(0),
+        })
+    }
+}
+
+pub(crate) fn draft_path() -> Option<PathBuf> {
+    Some(config_dir()?.join(DRAFT_FILE))
+}
+
+/// A missing file is no draft.
+pub(crate) fn load_draft(path: &Path) -> Result<Option<ComposerDraft>, String> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(error.to_string()),
+    };
+    let value = serde_json::from_str::<Value>(&text)
+        .map_err(|error| format!("invalid {DRAFT_FILE}: {error}"))?;
+    ComposerDraft::from_json(&value)
+        .map(Some)
+        .ok_or_else(|| format!("invalid {DRAFT_FILE}: missing text"))
+}
+
+/// Written owner-only (`0600`, it may hold pasted screenshots) next to the target and
+/// renamed, so a crash mid-write keeps the previous draft.
+pub(crate) fn save_draft(path: &Path, draft: &ComposerDraft) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|error| error.to_string())?;
+    }
+    let text = serde_json::to_string(&draft.to_json()).map_err(|error| error.to_string())?;
+    let partial = path.with_extension("json.partial");
+    // A leftover partial may carry other permissions; the new one is always created fresh.
+    remove_draft(&partial)?;
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&partial).map_err(|error| error.to_string())?;
+    file.write_all(text.as_bytes())
+        .map_err(|error| error.to_string())?;
+    drop(file);
+    std::fs::rename(&partial, path).map_err(|error| error.to_string())
+}
+
+/// Modification time and size of the draft file, to tell whether another tab rewrote it.
+pub(crate) fn draft_file_stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// The draft file as this tab last wrote or restored it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DraftOnDisk {
+    pub composer_nonce: String,
+    pub stamp: Option<(SystemTime, u64)>,
+}
+
+pub(crate) fn remove_draft(path: &Path) -> Result<(), String> {
+    match std::fs::remove_file(path) {
+        Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(error.to_string()),
+        _ => Ok(()),
+    }
+}
+
+/// Debounced composer autosave: a change is written once the text has been still for a
+/// moment. An emptied composer never overwrites the file, so `Esc` keeps the last draft.
+#[derive(Debug, Default)]
+pub(crate) struct DraftAutosave {
+    /// `None` disables autosave (line mode, headless, no config dir).
+    pub path: Option<PathBuf>,
+    /// Text of the draft in the file, as this tab last wrote or restored it.
+    pub saved_text: Option<String>,
+    /// Which composer that file holds, so sending a prompt can drop it without reading it.
+    pub on_disk: Option<DraftOnDisk>,
+    seen_text: String,
+    seen_at: Option<Instant>,
+}
+
+impl DraftAutosave {
+    pub(crate) fn new(path: Option<PathBuf>) -> Self {
+        Self {
+            path,
+            ..Self::default()
+        }
+    }
+
+    /// The composer text to write now, if any.
+    pub(crate) fn due(
+        &mut self,
+        text: &str,
+        now: Instant,
+        debounce: std::time::Duration,
+    ) -> Option<String> {
+        self.path.as_ref()?;
+        if text != self.seen_text {
+            self.seen_text = text.to_string();
+            self.seen_at = Some(now);
+            return None;
+        }
+        let still_since = self.seen_at?;
+        if text.trim().is_empty()
+            || self.saved_text.as_deref() == Some(text)
+            || now.saturating_duration_since(still_since) < debounce
+        {
+            return None;
+        }
+        Some(text.to_string())
+    }
+
+    /// The composer now shows `text` (restored or just written); it is not due again.
+    pub(crate) fn mark_saved(&mut self, text: &str) {
+        self.saved_text = Some(text.to_string());
+        self.seen_text = text.to_string();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load_draft, remove_draft, save_draft, ComposerDraft, DraftAutosave};
+    use crate::app::PendingImageAttachment;
+    use std::path::PathBuf;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn autosave_waits_for_a_pause_and_round_trips_the_draft() {
+        let path = std::env::temp_dir()
+            .join(format!("codelia-draft-{}", std::process::id()))
+            .join("tui-draft.json");
+        let debounce = Duration::from_secs(1);
+        let t0 = Instant::now();
+        let at = |ms: u64| t0 + Duration::from_millis(ms);
+        let mut autosave = DraftAutosave::new(Some(path.clone()));
+        assert_eq!(autosave.due("fix th", at(0), debounce), None);
+        assert_eq!(autosave.due("fix the bug", at(400), debounce), None);
+        assert_eq!(autosave.due("fix the bug", at(1_000), debounce), None);
+        assert_eq!(
+            autosave.due("fix the bug", at(1_400), debounce).as_deref(),
+            Some("fix the bug")
+        );
+        autosave.mark_saved("fix the bug");
+        assert_eq!(autosave.due("fix the bug", at(3_000), debounce), None);
+        // Esc: the empty composer is never written.
+        assert_eq!(autosave.due("", at(3_100), debounce), None);
+        assert_eq!(autosave.due("", at(9_000), debounce), None);
+        assert_eq!(DraftAutosave::default().due("x", at(9_000), debounce), None);
+
+        assert!(load_draft(&path).expect("missing file").is_none());
+        let draft = ComposerDraft {
+            text: "look at [image] and @src/lib.rs".to_string(),
+            composer_nonce: "n1".to_string(),
+            images: vec![(
+                "img1".to_string(),
+                PendingImageAttachment {
+                    data_url: "data:image/png;base64,AA==".to_string(),
+                    width: 2,
+                    height: 3,
+                    encoded_bytes: 4,
+                },
+            )],
+            files: vec!["src/lib.rs".to_string()],
+            cwd: Some("/work".to_string()),
+            saved_at_unix_ms: 42,
+        };
+        save_draft(&path, &draft).expect("save");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).expect("meta").permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        let loaded = load_draft(&path).expect("load").expect("draft");
+        assert_eq!(loaded.text, draft.text);
+        assert_eq!(loaded.composer_nonce, "n1");
+        assert_eq!(loaded.images[0].0, "img1");
+        assert_eq!(loaded.images[0].1.height, 3);
+        assert_eq!(loaded.files, vec!["src/lib.rs"]);
+        assert_eq!(loaded.cwd.as_deref(), Some("/work"));
+        remove_draft(&path).expect("remove");
+        remove_draft(&path).expect("already gone");
+        let _ = std::fs::remove_dir_all(path.parent().map(PathBuf::from).expect("dir"));
+    }
+}
@@ -0,0 +1,373 @@
+//! `--editor-socket`: a local Unix socket that editor plugins (Neovim, VS Code) use to drive the
+//! TUI. One JSON request per line, one JSON answer per line:
+//!
+//! - `{"id":1,"method":"prompt","params":{"text":"…","active_file":…,"selection":…}}`
+//! - `{"id":2,"method":"context","params":{"active_file":…,"selection":…}}`
+//! - `{"id":3,"method":"last_answer"}`
+//! - `{"id":4,"method":"open","params":{"path":"src/main.rs","line":12}}`
+//!
+//! `active_file` / `selection` have the `ui.context.update` shape. Answers are
+//! `{"id":…,"result":…}` or `{"id":…,"error":{"message":…}}`.
+
+use codelia_protocol::UiContextUpdate;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, Sender};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum EditorCall {
+    /// Queue a prompt; the context is pushed to the runtime first.
+    Prompt {
+        text: String,
+        context: UiContextUpdate,
+    },
+    /// The file or selection in the editor changed.
+    Context(UiContextUpdate),
+    LastAnswer,
+    /// Preview `path` in the TUI with `line` (1-based) selected.
+    Open {
+        path: String,
+        line: Option<usize>,
+    },
+}
+
+/// Editors only report what they show; the working directory stays the TUI's (`/cd`).
+fn editor_context(params: &Value) -> Result<UiContextUpdate, String> {
+    let mut context: UiContextUpdate = serde_json::from_value(params.clone())
+        .map_err(|error| format!("invalid context: {error}"))?;
+    context.cwd = None;
+    context.workspace_root = None;
+    Ok(context)
+}
+
+pub(crate) fn parse_editor_call(request: &Value) -> Result<EditorCall, String> {
+    let params = request.get("params").cloned().unwrap_or_else(|| json!({}));
+    match request.get("method").and_then(Value::as_str) {
+        Some("prompt") => {
+            let text = params
+                .get("text")
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|text| !text.is_empty())
+                .ok_or("prompt needs a non-empty `text`")?
+                .to_string();
+            Ok(EditorCall::Prompt {
+                text,
+                context: editor_context(&params)?,
+            })
+        }
+        Some("context") => Ok(EditorCall::Context(editor_context(&params)?)),
+        Some("last_answer") => Ok(EditorCall::LastAnswer),
+        Some("open") => {
+            let path = params
+                .get("path")
+                .and_then(Value::as_str)
+                .filter(|path| !path.is_empty())
+                .ok_or("open needs a `path`")?
+                .to_string();
+            let line = params
+                .get("line")
+                .and_then(Value::as_u64)
+                .map(|line| line as usize);
+            Ok(EditorCall::Open { path, line })
+        }
+        Some(method) => Err(format!("unknown method: {method}")),
+        None => Err("missing `method`".to_string()),
+    }
+}
+
+/// The prompt as sent to the model: the selected text, when the editor sent it, goes first.
+pub(crate) fn editor_prompt_text(text: &str, context: &UiContextUpdate) -> String {
+    let Some(selection) = context.selection.as_ref() else {
+        return match context.active_file.as_ref() {
+            Some(file) => format!("(Active file in my editor: {})\n\n{text}", file.path),
+            None => text.to_string(),
+        };
+    };
+    let Some(selected) = selection
+        .selected_text
+        .as_deref()
+        .filter(|selected| !selected.is_empty())
+    else {
+        return format!(
+            "(Selected in my editor: {}:{}-{})\n\n{text}",
+            selection.path,
+            selection.range.start.line + 1,
+            selection.range.end.line + 1
+        );
+    };
+    let language = context
+        .active_file
+        .as_ref()
+        .filter(|file| file.path == selection.path)
+        .and_then(|file| file.language_id.as_deref())
+        .unwrap_or("");
+    format!(
+        "Selected in my editor ({}:{}-{}):\n```{language}\n{}\n```\n\n{text}",
+        selection.path,
+        selection.range.start.line + 1,
+        selection.range.end.line + 1,
+        selected.trim_end_matches('\n')
+    )
+}
+
+fn answer_line(id: &Value, answer: Result<Value, String>) -> String {
+    let message = match answer {
+        Ok(result) => json!({ "id": id, "result": result }),
+        Err(message) => json!({ "id": id, "error": { "message": message } }),
+    };
+    message.to_string() + "\n"
+}
+
+/// A parsed request waiting for the run loop.
+pub(crate) struct EditorRequest {
+    pub call: EditorCall,
+    pub reply: EditorReply,
+}
+
+/// Dropping it without `send` closes the editor's connection.
+pub(crate) struct EditorReply {
+    id: Value,
+    sender: Sender<String>,
+}
+
+impl EditorReply {
+    pub(crate) fn send(self, answer: Result<Value, String>) {
+        let _ = self.sender.send(answer_line(&self.id, answer));
+    }
+}
+
+/// The listening socket; the file, and the private directory `start` made for it, are removed
+/// again on drop.
+pub(crate) struct EditorBridge {
+    path: PathBuf,
+    owned_dir: Option<PathBuf>,
+    requests: Receiver<EditorRequest>,
+}
+
+fn unique_suffix() -> String {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.subsec_nanos())
+        .unwrap_or(0);
+    format!("{}-{nanos}", std::process::id())
+}
+
+/// The socket file name inside the default private directory.
+const DEFAULT_SOCKET_NAME: &str = "editor.sock";
+
+/// `$XDG_RUNTIME_DIR/codelia-tui-<pid>.sock` (that directory is already private to the user),
+/// else `editor.sock` in a `codelia-tui-<pid>-<nanos>` directory of the temp dir that `start`
+/// creates with mode 0700.
+pub(crate) fn default_editor_socket_path() -> PathBuf {
+    match std::env::var_os("XDG_RUNTIME_DIR").filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir).join(format!("codelia-tui-{}.sock", std::process::id())),
+        None => std::env::temp_dir()
+            .join(format!("codelia-tui-{}", unique_suffix()))
+            .join(DEFAULT_SOCKET_NAME),
+    }
+}
+
+/// A new directory only this user can enter; fails when the name is taken.
+#[cfg(unix)]
+fn create_private_dir(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::DirBuilderExt;
+    std::fs::DirBuilder::new().mode(0o700).create(path)
+}
+
+impl EditorBridge {
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Requests that arrived since the last call.
+    pub(crate) fn drain(&self) -> Vec<EditorRequest> {
+        self.requests.try_iter().collect()
+    }
+
+    /// Binds inside a fresh 0700 directory next to `path`, restricts the socket to 0600 there,
+    /// and only then moves it into place, so nobody else can connect in between. A missing
+    /// parent directory (the default temp-dir path) is created private, too.
+    #[cfg(unix)]
+    pub(crate) fn start(path: PathBuf) -> std::io::Result<Self> {
+        use std::os::unix::fs::PermissionsExt;
+        use std::os::unix::net::{UnixListener, UnixStream};
+
+        let parent = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or(Path::new("."))
+            .to_path_buf();
+        let owned_dir = if !parent.exists() {
+            create_private_dir(&parent)?;
+            Some(parent.clone())
+        } else {
+            None
+        };
+        if path.exists() {
+            if UnixStream::connect(&path).is_ok() {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::AddrInUse,
+                    "another codelia-tui is listening there",
+                ));
+            }
+            // Left behind by a TUI that did not exit cleanly.
+            std::fs::remove_file(&path)?;
+        }
+        // Anyone who can connect can run prompts; keep it to this user.
+        let staging = parent.join(format!(".codelia-tui-bind-{}", unique_suffix()));
+        create_private_dir(&staging)?;
+        let staged = staging.join(DEFAULT_SOCKET_NAME);
+        let bound = UnixListener::bind(&staged).and_then(|listener| {
+            std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o600))?;
+            std::fs::rename(&staged, &path)?;
+            Ok(listener)
+        });
+        let _ = std::fs::remove_file(&staged);
+        let _ = std::fs::remove_dir(&staging);
+        let listener = match bound {
+            Ok(listener) => listener,
+            Err(error) => {
+                if let Some(dir) = &owned_dir {
+                    let _ = std::fs::remove_dir(dir);
+                }
+                return Err(error);
+            }
+        };
+        let (sender, requests) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { continue };
+                let sender = sender.clone();
+                std::thread::spawn(move || serve_connection(stream, sender));
+            }
+        });
+        Ok(Self {
+            path,
+            owned_dir,
+            requests,
+        })
+    }
+
+    #[cfg(not(unix))]
+    pub(crate) fn start(_path: PathBuf) -> std::io::Result<Self> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "the editor socket needs Unix domain sockets",
+        ))
+    }
+}
+
+impl Drop for EditorBridge {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+        if let Some(dir) = &self.owned_dir {
+            let _ = std::fs::remove_dir(dir);
+        }
+    }
+}
+
+/// Requests on one connection are answered in order; the next line is read after the answer.
+#[cfg(unix)]
+fn serve_connection(stream: std::os::unix::net::UnixStream, requests: Sender<EditorRequest>) {
+    use std::io::{BufRead, BufReader, Write};
+
+    let Ok(mut writer) = stream.try_clone() else {
+        return;
+    };
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else { return };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let answer = match serde_json::from_str::<Value>(&line) {
+            Err(error) => answer_line(&Value::Null, Err(format!("invalid JSON: {error}"))),
+            Ok(request) => {
+                let id = request.get("id").cloned().unwrap_or(Value::Null);
+                match parse_editor_call(&request) {
+                    Err(message) => answer_line(&id, Err(message)),
+                    Ok(call) => {
+                        let (sender, answer) = std::sync::mpsc::channel();
+                        let reply = EditorReply { id, sender };
+                        if requests.send(EditorRequest { call, reply }).is_err() {
+                            return;
+                        }
+                        let Ok(answer) = answer.recv() else { return };
+                        answer
+                    }
+                }
+            }
+        };
+        if writer.write_all(answer.as_bytes()).is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{editor_prompt_text, parse_editor_call, EditorCall};
+    use serde_json::json;
+
+    #[cfg(unix)]
+    #[test]
+    fn socket_is_owner_only_in_a_private_directory_removed_on_drop() {
+        use super::EditorBridge;
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("codelia-bridge-{}", std::process::id()));
+        let path = dir.join("editor.sock");
+        let bridge = EditorBridge::start(path.clone()).expect("start");
+        let mode = |path: &std::path::Path| {
+            std::fs::metadata(path).expect("meta").permissions().mode() & 0o777
+        };
+        assert_eq!(mode(&dir), 0o700);
+        assert_eq!(mode(&path), 0o600);
+        assert_eq!(std::fs::read_dir(&dir).expect("dir").count(), 1);
+        std::os::unix::net::UnixStream::connect(&path).expect("connect");
+        drop(bridge);
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn parses_calls_and_puts_the_selection_before_the_prompt() {
+        let call = parse_editor_call(&json!({
+            "id": 1,
+            "method": "prompt",
+            "params": {
+                "text": " explain this ",
+                "cwd": "/elsewhere",
+                "active_file": { "path": "src/lib.rs", "language_id": "rust" },
+                "selection": {
+                    "path": "src/lib.rs",
+                    "range": { "start": { "line": 9, "column": 0 }, "end": { "line": 11, "column": 0 } },
+                    "selected_text": "fn add(a: u8) -> u8 {\n    a + 1\n}\n"
+                }
+            }
+        }))
+        .expect("prompt");
+        let EditorCall::Prompt { text, context } = call else {
+            panic!("not a prompt");
+        };
+        assert_eq!(context.cwd, None);
+        assert_eq!(
+            editor_prompt_text(&text, &context),
+            "Selected in my editor (src/lib.rs:10-12):\n```rust\nfn add(a: u8) -> u8 {\n    a + 1\n}\n```\n\nexplain this"
+        );
+
+        assert_eq!(
+            parse_editor_call(
+                &json!({ "method": "open", "params": { "path": "a.rs", "line": 3 } })
+            ),
+            Ok(EditorCall::Open {
+                path: "a.rs".to_string(),
+                line: Some(3)
+            })
+        );
+        assert_eq!(
+            parse_editor_call(&json!({ "method": "prompt", "params": { "text": "  " } })),
+            Err("prompt needs a non-empty `text`".to_string())
+        );
+        assert!(parse_editor_call(&json!({ "method": "shutdown" })).is_err());
+    }
+}
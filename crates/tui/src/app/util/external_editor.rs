@@ -0,0 +1,144 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const DEFAULT_EDITOR: &str = if cfg!(target_os = "windows") {
+    "notepad"
+} else {
+    "vi"
+};
+
+/// `$VISUAL`, then `$EDITOR`, then the platform default; blank values are skipped.
+pub(crate) fn resolve_editor(visual: Option<&str>, editor: Option<&str>) -> String {
+    [visual, editor]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|value| !value.is_empty())
+        .unwrap_or(DEFAULT_EDITOR)
+        .to_string()
+}
+
+pub(crate) fn editor_from_env() -> String {
+    resolve_editor(
+        std::env::var("VISUAL").ok().as_deref(),
+        std::env::var("EDITOR").ok().as_deref(),
+    )
+}
+
+/// The editor runs through the shell so values like `code --wait` keep their arguments.
+fn editor_command(editor: &str, path: &Path) -> Command {
+    if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        command.args(["/C", &format!("{editor} \"{}\"", path.display())]);
+        command
+    } else {
+        let mut command = Command::new("sh");
+        command
+            .arg("-c")
+            .arg(format!("{editor} \"$1\""))
+            .arg("sh")
+            .arg(path);
+        command
+    }
+}
+
+/// Tries before giving up when every candidate name already exists.
+const PROMPT_FILE_ATTEMPTS: u32 = 16;
+
+/// Creates a fresh owner-only temp file holding `text`. `create_new` refuses a file or symlink
+/// planted under the name, and another name is tried instead.
+fn create_prompt_file(text: &str) -> Result<PathBuf, String> {
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos())
+        .unwrap_or(0);
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    for attempt in 0..PROMPT_FILE_ATTEMPTS {
+        let path = std::env::temp_dir().join(format!(
+            "codelia-prompt-{}-{stamp}-{attempt}.md",
+            std::process::id()
+        ));
+        match options.open(&path) {
+            Ok(mut file) => {
+                return file
+                    .write_all(text.as_bytes())
+                    .map(|()| path.clone())
+                    .map_err(|error| {
+                        let _ = std::fs::remove_file(&path);
+                        format!("{}: {error}", path.display())
+                    });
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(error) => return Err(format!("{}: {error}", path.display())),
+        }
+    }
+    Err("could not create a temporary prompt file".to_string())
+}
+
+/// Editors end the file with a newline the composer should not get.
+pub(crate) fn strip_editor_newline(text: &str) -> &str {
+    text.strip_suffix("\r\n")
+        .or_else(|| text.strip_suffix('\n'))
+        .unwrap_or(text)
+}
+
+/// Writes `text` to a temporary `.md` file, waits for `editor` on it, and returns the saved
+/// text. The terminal must already be out of raw mode; the file is removed either way.
+pub(crate) fn edit_in_external_editor(editor: &str, text: &str) -> Result<String, String> {
+    let path = create_prompt_file(text)?;
+    let status = editor_command(editor, &path).status();
+    let edited = std::fs::read_to_string(&path);
+    let _ = std::fs::remove_file(&path);
+    let status = status.map_err(|error| format!("{editor}: {error}"))?;
+    if !status.success() {
+        return Err(format!("{editor} exited with {status}"));
+    }
+    let edited = edited.map_err(|error| format!("{}: {error}", path.display()))?;
+    Ok(strip_editor_newline(&edited).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{create_prompt_file, resolve_editor, strip_editor_newline, DEFAULT_EDITOR};
+
+    #[test]
+    fn visual_wins_over_editor_and_blank_values_fall_through() {
+        assert_eq!(resolve_editor(Some("nvim"), Some("nano")), "nvim");
+        assert_eq!(
+            resolve_editor(Some("  "), Some("code --wait")),
+            "code --wait"
+        );
+        assert_eq!(resolve_editor(None, None), DEFAULT_EDITOR);
+        assert_eq!(strip_editor_newline("fix it\n"), "fix it");
+        assert_eq!(strip_editor_newline("fix it\r\n"), "fix it");
+        assert_eq!(strip_editor_newline("a\n\n"), "a\n");
+    }
+
+    #[test]
+    fn prompt_files_are_new_and_owner_only() {
+        let first = create_prompt_file("draft").expect("first");
+        let second = create_prompt_file("draft").expect("second");
+        assert_ne!(first, second);
+        assert_eq!(std::fs::read_to_string(&first).expect("read"), "draft");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&first)
+                .expect("meta")
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        let _ = std::fs::remove_file(first);
+        let _ = std::fs::remove_file(second);
+    }
+}
@@ -0,0 +1,351 @@
+use crate::app::util::dir_tree::{workspace_dirs, TreeNode, DIR_MENTION_PREFIX};
+use crate::app::PendingFileAttachment;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver};
+
+/// Upper bound on indexed workspace files, so a huge checkout cannot stall the composer.
+const MAX_INDEXED_FILES: usize = 20_000;
+/// Content sent per `@file`; longer files are cut at a line boundary below this size.
+pub(crate) const MAX_FILE_MENTION_BYTES: usize = 64 * 1024;
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "dist", "build"];
+
+fn git_workspace_files() -> Option<Vec<String>> {
+    let output = Command::new("git")
+        .args([
+            "ls-files",
+            "-z",
+            "--cached",
+            "--others",
+            "--exclude-standard",
+        ])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(
+        output
+            .stdout
+            .split(|byte| *byte == 0)
+            .filter(|path| !path.is_empty())
+            .map(|path| String::from_utf8_lossy(path).into_owned())
+            .collect(),
+    )
+}
+
+fn walk_workspace_files(dir: &Path, prefix: &str, files: &mut Vec<String>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries = entries.flatten().collect::<Vec<_>>();
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        if files.len() >= MAX_INDEXED_FILES {
+            return;
+        }
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with('.') {
+            continue;
+        }
+        let path = format!("{prefix}{name}");
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            if !SKIPPED_DIRS.contains(&name.as_str()) {
+                walk_workspace_files(&entry.path(), &format!("{path}/"), files);
+            }
+        } else if file_type.is_file() {
+            files.push(path);
+        }
+    }
+}
+
+/// Files under the current directory for `@` mentions: `git ls-files` (tracked plus
+/// untracked, minus ignored) when available, otherwise a walk skipping dot and build dirs.
+pub(crate) fn workspace_files() -> Vec<String> {
+    let mut files = git_workspace_files().unwrap_or_else(|| {
+        let mut files = Vec::new();
+        walk_workspace_files(Path::new("."), "", &mut files);
+        files
+    });
+    files.sort();
+    files.truncate(MAX_INDEXED_FILES);
+    files
+}
+
+/// Workspace files plus what `@dir:` needs from them, derived once per index so drawing the
+/// suggestions never rescans the list; dropped (and rebuilt on next use) when a run ends.
+#[derive(Debug, Default)]
+pub(crate) struct WorkspaceIndex {
+    pub files: Vec<String>,
+    pub dirs: Vec<String>,
+    pub tree: TreeNode,
+}
+
+impl WorkspaceIndex {
+    pub(crate) fn from_files(files: Vec<String>) -> Self {
+        Self {
+            dirs: workspace_dirs(&files),
+            tree: TreeNode::from_files(&files),
+            files,
+        }
+    }
+
+    pub(crate) fn load() -> Self {
+        Self::from_files(workspace_files())
+    }
+}
+
+/// One background index build at a time, so `git ls-files` on a large checkout never blocks
+/// typing; the run loop polls it like the local model probe.
+#[derive(Default)]
+pub(crate) struct WorkspaceIndexLoad {
+    receiver: Option<Receiver<WorkspaceIndex>>,
+}
+
+impl WorkspaceIndexLoad {
+    /// False when a build is already running.
+    pub(crate) fn spawn(&mut self) -> bool {
+        if self.receiver.is_some() {
+            return false;
+        }
+        let (sender, receiver) = mpsc::channel();
+        self.receiver = Some(receiver);
+        std::thread::spawn(move || {
+            let _ = sender.send(WorkspaceIndex::load());
+        });
+        true
+    }
+
+    pub(crate) fn is_running(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    pub(crate) fn poll(&mut self) -> Option<WorkspaceIndex> {
+        let index = self.receiver.as_ref()?.try_recv().ok()?;
+        self.receiver = None;
+        Some(index)
+    }
+}
+
+/// Ranked candidates for the `@` word being typed, scored when the query changes rather than
+/// on every draw; `selected` is the one Tab attaches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MentionMatches {
+    pub dir: bool,
+    pub query: String,
+    pub paths: Vec<String>,
+    pub selected: usize,
+}
+
+/// Subsequence match of `query` in `path`, case-insensitive; higher is better. Consecutive
+/// characters and matches at the start of a path segment or in the file name score more.
+fn fuzzy_score(query: &str, path: &str) -> Option<i64> {
+    let file_name_start = path.rfind('/').map_or(0, |index| index + 1);
+    let mut score = 0i64;
+    let mut previous_end: Option<usize> = None;
+    let mut chars = path.char_indices();
+    let mut previous_char = None;
+    for wanted in query.chars().map(|ch| ch.to_ascii_lowercase()) {
+        loop {
+            let (index, ch) = chars.next()?;
+            let at_segment_start = matches!(previous_char, None | Some('/' | '_' | '-' | '.'));
+            previous_char = Some(ch);
+            if ch.to_ascii_lowercase() != wanted {
+                continue;
+            }
+            score += 1;
+            if previous_end == Some(index) {
+                score += 4;
+            }
+            if at_segment_start {
+                score += 3;
+            }
+            if index >= file_name_start {
+                score += 2;
+            }
+            previous_end = Some(index + ch.len_utf8());
+            break;
+        }
+    }
+    // Shorter paths win ties, so `src/main.rs` ranks above `src/bin/main_old.rs`.
+    Some(score * 1000 - path.len() as i64)
+}
+
+pub(crate) fn fuzzy_file_matches<'a>(
+    query: &str,
+    files: &'a [String],
+    limit: usize,
+) -> Vec<&'a str> {
+    let mut scored = files
+        .iter()
+        .filter_map(|path| fuzzy_score(query, path).map(|score| (score, path.as_str())))
+        .collect::<Vec<_>>();
+    scored.sort_by(|left, right| right.0.cmp(&left.0).then(left.1.cmp(right.1)));
+    scored.truncate(limit);
+    scored.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Reads a mentioned file, refusing binaries and keeping at most `MAX_FILE_MENTION_BYTES`.
+pub(crate) fn read_file_mention(path: &str) -> Result<PendingFileAttachment, String> {
+    let bytes = std::fs::read(path).map_err(|error| format!("{path}: {error}"))?;
+    if bytes.iter().take(8192).any(|byte| *byte == 0) {
+        return Err(format!("{path} looks like a binary file"));
+    }
+    let text = String::from_utf8_lossy(&bytes);
+    let truncated = text.len() > MAX_FILE_MENTION_BYTES;
+    let content = if truncated {
+        let mut cut = MAX_FILE_MENTION_BYTES;
+        while !text.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        let cut = text[..cut].rfind('\n').map_or(cut, |index| index + 1);
+        text[..cut].to_string()
+    } else {
+        text.into_owned()
+    };
+    Ok(PendingFileAttachment {
+        path: path.to_string(),
+        content,
+        bytes: bytes.len(),
+        truncated,
+    })
+}
+
+/// The composer word for an attached path: `@path`, or `@"path"` when it has whitespace so the
+/// mention stays one word.
+pub(crate) fn mention_word(path: &str) -> String {
+    if path.contains(char::is_whitespace) {
+        format!("@\"{path}\"")
+    } else {
+        format!("@{path}")
+    }
+}
+
+fn escape_attribute(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// The text part sent for a mentioned file (or `@dir:` tree listing), ahead of the prompt.
+pub(crate) fn file_mention_text(file: &PendingFileAttachment) -> String {
+    if let Some(dir) = file.path.strip_prefix(DIR_MENTION_PREFIX) {
+        let note = if file.truncated {
+            " truncated=\"entries\""
+        } else {
+            ""
+        };
+        return format!(
+            "<directory_tree path=\"{}\"{note}>\n{}</directory_tree>",
+            escape_attribute(dir),
+            file.content
+        );
+    }
+    let note = if file.truncated {
+        format!(
+            " truncated=\"first {} of {} bytes\"",
+            file.content.len(),
+            file.bytes
+        )
+    } else {
+        String::new()
+    };
+    let newline = if file.content.ends_with('\n') {
+        ""
+    } else {
+        "\n"
+    };
+    format!(
+        "<file path=\"{}\"{note}>\n{}{newline}</file>",
+        escape_attribute(&file.path),
+        file.content
+    )
+}
+
+/// Whether the `mention_word` of `path` still appears as a whitespace-separated word of `text`.
+pub(crate) fn mentions_file(text: &str, path: &str) -> bool {
+    let word = mention_word(path);
+    text.match_indices(&word).any(|(start, _)| {
+        let end = start + word.len();
+        text[..start]
+            .chars()
+            .next_back()
+            .is_none_or(char::is_whitespace)
+            && text[end..].chars().next().is_none_or(char::is_whitespace)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        file_mention_text, fuzzy_file_matches, mention_word, mentions_file, read_file_mention,
+    };
+    use crate::app::PendingFileAttachment;
+
+    #[test]
+    fn fuzzy_matches_rank_file_names_and_mentions_are_read() {
+        let files = [
+            "crates/tui/src/main.rs",
+            "crates/tui/src/bin/main_old.rs",
+            "packages/runtime/src/index.ts",
+            "README.md",
+        ]
+        .map(str::to_string);
+        assert_eq!(
+            fuzzy_file_matches("main", &files, 5),
+            vec!["crates/tui/src/main.rs", "crates/tui/src/bin/main_old.rs"]
+        );
+        assert_eq!(
+            fuzzy_file_matches("rtidx", &files, 5),
+            vec!["packages/runtime/src/index.ts"]
+        );
+        assert!(fuzzy_file_matches("zzz", &files, 5).is_empty());
+
+        assert!(mentions_file("explain @README.md please", "README.md"));
+        assert!(!mentions_file("explain README.md", "README.md"));
+        assert!(!mentions_file("explain @README.md.bak", "README.md"));
+
+        let spaced = "docs/release notes.md";
+        assert_eq!(mention_word(spaced), "@\"docs/release notes.md\"");
+        assert!(mentions_file("read @\"docs/release notes.md\" now", spaced));
+        assert!(!mentions_file("read @docs/release notes.md now", spaced));
+        let file = PendingFileAttachment {
+            path: "a \"b\" & <c>.txt".to_string(),
+            content: "x\n".to_string(),
+            bytes: 2,
+            truncated: false,
+        };
+        assert_eq!(
+            file_mention_text(&file),
+            "<file path=\"a &quot;b&quot; &amp; &lt;c&gt;.txt\">\nx\n</file>"
+        );
+
+        let dir = std::env::temp_dir().join(format!("codelia-mention-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("dir");
+        let path = dir.join("notes.txt");
+        std::fs::write(&path, "alpha\nbeta").expect("write");
+        let path = path.display().to_string();
+        let file = read_file_mention(&path).expect("read");
+        assert!(!file.truncated);
+        assert_eq!(
+            file_mention_text(&file),
+            format!("<file path=\"{path}\">\nalpha\nbeta\n</file>")
+        );
+        std::fs::write(dir.join("blob.bin"), [0u8, 1, 2]).expect("write");
+        assert!(read_file_mention(&dir.join("blob.bin").display().to_string()).is_err());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
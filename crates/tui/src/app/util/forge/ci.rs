@@ -0,0 +1,437 @@
+use super::{forge_api_get, truncate_chars, ForgeKind, ForgeRepo};
+use serde_json::{json, Value};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::{Duration, Instant};
+
+pub(crate) const CI_POLL_INTERVAL: Duration = Duration::from_secs(30);
+const CI_LOG_TAIL_LINES: usize = 200;
+const CI_LOG_MAX_CHARS: usize = 24_000;
+const CI_LOG_FETCH_LIMIT: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CiStatus {
+    Passed,
+    Failed,
+    Running,
+    Pending,
+    Skipped,
+}
+
+impl CiStatus {
+    pub(crate) fn icon(self) -> &'static str {
+        match self {
+            Self::Passed => "✔",
+            Self::Failed => "✖",
+            Self::Running => "…",
+            Self::Pending => "○",
+            Self::Skipped => "-",
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Passed => "passed",
+            Self::Failed => "failed",
+            Self::Running => "running",
+            Self::Pending => "pending",
+            Self::Skipped => "skipped",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CiCheck {
+    pub name: String,
+    pub status: CiStatus,
+    pub job_id: Option<u64>,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CiSummary {
+    pub branch: String,
+    pub checks: Vec<CiCheck>,
+}
+
+impl CiSummary {
+    fn count(&self, status: CiStatus) -> usize {
+        self.checks
+            .iter()
+            .filter(|check| check.status == status)
+            .count()
+    }
+
+    pub(crate) fn overall(&self) -> Option<CiStatus> {
+        if self.checks.is_empty() {
+            return None;
+        }
+        [CiStatus::Failed, CiStatus::Running, CiStatus::Pending]
+            .into_iter()
+            .find(|status| self.count(*status) > 0)
+            .or(Some(CiStatus::Passed))
+    }
+
+    pub(crate) fn failed_checks(&self) -> impl Iterator<Item = &CiCheck> {
+        self.checks
+            .iter()
+            .filter(|check| check.status == CiStatus::Failed)
+    }
+
+    /// Compact status-line text, e.g. `ci: ✖ 1 failed (4/5)`.
+    pub(crate) fn status_segment(&self) -> String {
+        let Some(overall) = self.overall() else {
+            return "ci: no checks".to_string();
+        };
+        let counted = self.checks.len() - self.count(CiStatus::Skipped);
+        let passed = self.count(CiStatus::Passed);
+        match overall {
+            CiStatus::Passed => format!("ci: ✔ {passed}/{counted}"),
+            CiStatus::Failed => format!(
+                "ci: ✖ {} failed ({passed}/{counted})",
+                self.count(CiStatus::Failed)
+            ),
+            status => format!(
+                "ci: {} {} {} ({passed}/{counted})",
+                status.icon(),
+                self.count(status),
+                status.label()
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CiJobLog {
+    pub name: String,
+    pub url: Option<String>,
+    pub text: String,
+}
+
+fn github_check_status(status: &str, conclusion: Option<&str>) -> CiStatus {
+    match status {
+        "queued" | "waiting" | "requested" | "pending" => CiStatus::Pending,
+        "in_progress" => CiStatus::Running,
+        _ => match conclusion.unwrap_or_default() {
+            "success" | "neutral" => CiStatus::Passed,
+            "skipped" => CiStatus::Skipped,
+            _ => CiStatus::Failed,
+        },
+    }
+}
+
+fn gitlab_job_status(status: &str) -> CiStatus {
+    match status {
+        "success" => CiStatus::Passed,
+        "running" => CiStatus::Running,
+        "skipped" | "manual" => CiStatus::Skipped,
+        "failed" | "canceled" => CiStatus::Failed,
+        _ => CiStatus::Pending,
+    }
+}
+
+pub(crate) fn parse_github_check_runs(value: &Value) -> Vec<CiCheck> {
+    value
+        .get("check_runs")
+        .and_then(Value::as_array)
+        .map(|runs| {
+            runs.iter()
+                .filter_map(|run| {
+                    Some(CiCheck {
+                        name: run.get("name").and_then(Value::as_str)?.to_string(),
+                        status: github_check_status(
+                            run.get("status").and_then(Value::as_str).unwrap_or(""),
+                            run.get("conclusion").and_then(Value::as_str),
+                        ),
+                        job_id: run.get("id").and_then(Value::as_u64),
+                        url: run
+                            .get("html_url")
+                            .and_then(Value::as_str)
+                            .map(str::to_string),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub(crate) fn parse_gitlab_jobs(value: &Value) -> Vec<CiCheck> {
+    value
+        .as_array()
+        .map(|jobs| {
+            jobs.iter()
+                .filter_map(|job| {
+                    Some(CiCheck {
+                        name: job.get("name").and_then(Value::as_str)?.to_string(),
+                        status: gitlab_job_status(
+                            job.get("status").and_then(Value::as_str).unwrap_or(""),
+                        ),
+                        job_id: job.get("id").and_then(Value::as_u64),
+                        url: job
+                            .get("web_url")
+                            .and_then(Value::as_str)
+                            .map(str::to_string),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn get_json(repo: &ForgeRepo, token: &str, url: &str) -> Result<Value, String> {
+    let body = forge_api_get(repo.kind, token, url)?;
+    serde_json::from_slice(&body).map_err(|error| format!("invalid forge response: {error}"))
+}
+
+pub(crate) fn current_branch() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!branch.is_empty() && branch != "HEAD").then_some(branch)
+}
+
+fn fetch_ci_summary(repo: &ForgeRepo, token: &str) -> Result<CiSummary, String> {
+    let branch = current_branch().ok_or_else(|| "no current git branch".to_string())?;
+    let base = repo.api_base();
+    let checks = match repo.kind {
+        ForgeKind::GitHub => {
+            let url = format!("{base}/commits/{branch}/check-runs?per_page=100");
+            parse_github_check_runs(&get_json(repo, token, &url)?)
+        }
+        ForgeKind::GitLab => {
+            let url = format!("{base}/pipelines?ref={branch}&per_page=1");
+            let pipelines = get_json(repo, token, &url)?;
+            match pipelines
+                .get(0)
+                .and_then(|pipeline| pipeline.get("id"))
+                .and_then(Value::as_u64)
+            {
+                Some(pipeline_id) => {
+                    let url = format!("{base}/pipelines/{pipeline_id}/jobs?per_page=100");
+                    parse_gitlab_jobs(&get_json(repo, token, &url)?)
+                }
+                None => Vec::new(),
+            }
+        }
+    };
+    Ok(CiSummary { branch, checks })
+}
+
+pub(crate) fn tail_log_text(text: &str) -> String {
+    let lines = text.lines().collect::<Vec<_>>();
+    let start = lines.len().saturating_sub(CI_LOG_TAIL_LINES);
+    let tail = lines[start..].join("\n");
+    let char_count = tail.chars().count();
+    if char_count <= CI_LOG_MAX_CHARS {
+        return tail;
+    }
+    tail.chars().skip(char_count - CI_LOG_MAX_CHARS).collect()
+}
+
+fn fetch_job_log(repo: &ForgeRepo, token: &str, check: &CiCheck) -> Result<CiJobLog, String> {
+    let job_id = check
+        .job_id
+        .ok_or_else(|| format!("{}: no job id", check.name))?;
+    let base = repo.api_base();
+    let url = match repo.kind {
+        ForgeKind::GitHub => format!("{base}/actions/jobs/{job_id}/logs"),
+        ForgeKind::GitLab => format!("{base}/jobs/{job_id}/trace"),
+    };
+    let body = forge_api_get(repo.kind, token, &url)
+        .map_err(|error| format!("{}: {error}", check.name))?;
+    Ok(CiJobLog {
+        name: check.name.clone(),
+        url: check.url.clone(),
+        text: tail_log_text(&String::from_utf8_lossy(&body)),
+    })
+}
+
+pub(crate) fn build_ci_log_prefix(logs: &[CiJobLog]) -> Option<String> {
+    if logs.is_empty() {
+        return None;
+    }
+    let blocks = logs
+        .iter()
+        .map(|log| {
+            let payload = json!({
+                "job": log.name,
+                "url": log.url,
+                "log_tail": truncate_chars(&log.text, CI_LOG_MAX_CHARS),
+            });
+            let json_text = payload
+                .to_string()
+                .replace('<', "\\u003c")
+                .replace('>', "\\u003e");
+            format!("<ci_log>\n{json_text}\n</ci_log>")
+        })
+        .collect::<Vec<_>>();
+    Some(blocks.join("\n"))
+}
+
+pub(crate) enum CiFetchResult {
+    Summary {
+        result: Result<CiSummary, String>,
+        announce: bool,
+    },
+    Logs(Vec<Result<CiJobLog, String>>),
+}
+
+/// `/ci` polling state; worker threads report back through the channel.
+pub(crate) struct CiWatchState {
+    pub watching: bool,
+    pub summary: Option<CiSummary>,
+    pub next_poll_at: Option<Instant>,
+    pub in_flight: usize,
+    pub pending_logs: Vec<CiJobLog>,
+    sender: Sender<CiFetchResult>,
+    receiver: Receiver<CiFetchResult>,
+}
+
+impl Default for CiWatchState {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            watching: false,
+            summary: None,
+            next_poll_at: None,
+            in_flight: 0,
+            pending_logs: Vec::new(),
+            sender,
+            receiver,
+        }
+    }
+}
+
+impl CiWatchState {
+    pub(crate) fn spawn_summary_fetch(&mut self, repo: ForgeRepo, token: String, announce: bool) {
+        let sender = self.sender.clone();
+        self.in_flight = self.in_flight.saturating_add(1);
+        std::thread::spawn(move || {
+            let result = fetch_ci_summary(&repo, &token);
+            let _ = sender.send(CiFetchResult::Summary { result, announce });
+        });
+    }
+
+    pub(crate) fn spawn_log_fetch(&mut self, repo: ForgeRepo, token: String, checks: Vec<CiCheck>) {
+        let sender = self.sender.clone();
+        self.in_flight = self.in_flight.saturating_add(1);
+        std::thread::spawn(move || {
+            let logs = checks
+                .iter()
+                .take(CI_LOG_FETCH_LIMIT)
+                .map(|check| fetch_job_log(&repo, &token, check))
+                .collect();
+            let _ = sender.send(CiFetchResult::Logs(logs));
+        });
+    }
+
+    pub(crate) fn poll_due(&self, now: Instant) -> bool {
+        self.watching
+            && self.in_flight == 0
+            && self.next_poll_at.is_none_or(|deadline| now >= deadline)
+    }
+
+    pub(crate) fn drain_results(&mut self) -> Vec<CiFetchResult> {
+        if self.in_flight == 0 {
+            return Vec::new();
+        }
+        let results = self.receiver.try_iter().collect::<Vec<_>>();
+        self.in_flight = self.in_flight.saturating_sub(results.len());
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        build_ci_log_prefix, parse_github_check_runs, parse_gitlab_jobs, tail_log_text, CiJobLog,
+        CiStatus, CiSummary, CiWatchState,
+    };
+    use serde_json::json;
+    use std::time::Instant;
+
+    #[test]
+    fn parse_github_check_runs_maps_status_and_conclusion() {
+        let checks = parse_github_check_runs(&json!({
+            "check_runs": [
+                {"id": 1, "name": "build", "status": "completed", "conclusion": "success"},
+                {"id": 2, "name": "test", "status": "completed", "conclusion": "failure",
+                 "html_url": "https://github.com/o/r/runs/2"},
+                {"id": 3, "name": "lint", "status": "in_progress", "conclusion": null},
+                {"id": 4, "name": "docs", "status": "completed", "conclusion": "skipped"}
+            ]
+        }));
+        let statuses = checks.iter().map(|check| check.status).collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            vec![
+                CiStatus::Passed,
+                CiStatus::Failed,
+                CiStatus::Running,
+                CiStatus::Skipped
+            ]
+        );
+        let summary = CiSummary {
+            branch: "main".to_string(),
+            checks,
+        };
+        assert_eq!(summary.overall(), Some(CiStatus::Failed));
+        assert_eq!(summary.status_segment(), "ci: ✖ 1 failed (1/3)");
+        assert_eq!(summary.failed_checks().count(), 1);
+    }
+
+    #[test]
+    fn parse_gitlab_jobs_reports_running_pipeline() {
+        let checks = parse_gitlab_jobs(&json!([
+            {"id": 10, "name": "build", "status": "success"},
+            {"id": 11, "name": "test", "status": "running"},
+            {"id": 12, "name": "deploy", "status": "created"}
+        ]));
+        let summary = CiSummary {
+            branch: "feature".to_string(),
+            checks,
+        };
+        assert_eq!(summary.status_segment(), "ci: … 1 running (1/3)");
+        let empty = CiSummary {
+            branch: "feature".to_string(),
+            checks: Vec::new(),
+        };
+        assert_eq!(empty.overall(), None);
+        assert_eq!(empty.status_segment(), "ci: no checks");
+    }
+
+    #[test]
+    fn tail_log_text_keeps_last_lines_and_prefix_escapes_tags() {
+        let text = (1..=250)
+            .map(|index| format!("line {index}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let tail = tail_log_text(&text);
+        assert!(tail.starts_with("line 51\n"));
+        assert!(tail.ends_with("line 250"));
+
+        let prefix = build_ci_log_prefix(&[CiJobLog {
+            name: "test".to_string(),
+            url: None,
+            text: "<error>".to_string(),
+        }])
+        .expect("prefix");
+        assert!(prefix.starts_with("<ci_log>"));
+        assert!(prefix.contains("\\u003cerror\\u003e"));
+    }
+
+    #[test]
+    fn poll_due_requires_watch_mode() {
+        let mut state = CiWatchState::default();
+        assert!(!state.poll_due(Instant::now()));
+        state.watching = true;
+        assert!(state.poll_due(Instant::now()));
+    }
+}
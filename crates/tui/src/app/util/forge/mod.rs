@@ -0,0 +1,806 @@
+pub(crate) mod ci;
+
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender};
+
+const FORGE_FETCH_TIMEOUT_SECS: u64 = 10;
+const FORGE_TITLE_MAX_CHARS: usize = 96;
+const FORGE_REFS_PER_TEXT_LIMIT: usize = 5;
+const FORGE_CONTEXT_BODY_MAX_CHARS: usize = 16_000;
+/// Hosts a token may be sent to besides the host of the `origin` remote.
+const TRUSTED_FORGE_HOSTS: &[&str] = &["github.com", "gitlab.com"];
+const FORGE_MAX_REDIRECTS: usize = 5;
+/// Separates the response body from curl's `--write-out` status line.
+const CURL_STATUS_MARKER: &str = "\n<codelia-curl-status>";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ForgeKind {
+    GitHub,
+    GitLab,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ForgeRepo {
+    pub kind: ForgeKind,
+    pub host: String,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ForgeRef {
+    pub repo: ForgeRepo,
+    pub number: u64,
+    /// True when the reference explicitly targets a PR/MR (URL form).
+    pub merge_request: bool,
+}
+
+impl ForgeRef {
+    pub(crate) fn key(&self) -> String {
+        format!("{}/{}#{}", self.repo.host, self.repo.path, self.number)
+    }
+
+    pub(crate) fn label(&self) -> String {
+        format!("{}#{}", self.repo.path, self.number)
+    }
+
+    fn api_url(&self) -> String {
+        let base = self.repo.api_base();
+        match self.repo.kind {
+            // The issues endpoint serves both issues and pull requests.
+            ForgeKind::GitHub => format!("{base}/issues/{}", self.number),
+            ForgeKind::GitLab => {
+                let resource = if self.merge_request {
+                    "merge_requests"
+                } else {
+                    "issues"
+                };
+                format!("{base}/{resource}/{}", self.number)
+            }
+        }
+    }
+}
+
+impl ForgeRepo {
+    /// Repository/project root of the REST API (`.../repos/o/r` or `.../projects/<id>`).
+    pub(crate) fn api_base(&self) -> String {
+        match self.kind {
+            ForgeKind::GitHub => {
+                let root = if self.host == "github.com" {
+                    "https://api.github.com".to_string()
+                } else {
+                    format!("https://{}/api/v3", self.host)
+                };
+                format!("{root}/repos/{}", self.path)
+            }
+            ForgeKind::GitLab => format!(
+                "https://{}/api/v4/projects/{}",
+                self.host,
+                self.path.replace('/', "%2F")
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ForgeRefInfo {
+    pub key: String,
+    pub label: String,
+    pub title: String,
+    pub state: String,
+    pub url: String,
+    pub is_pull: bool,
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ForgeConfig {
+    pub github_token: Option<String>,
+    pub gitlab_token: Option<String>,
+    pub default_repo: Option<ForgeRepo>,
+}
+
+impl ForgeConfig {
+    pub(crate) fn from_env() -> Self {
+        let read = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        let default_repo = read("CODELIA_FORGE_REPO")
+            .and_then(|value| parse_remote_url(&value))
+            .or_else(|| git_origin_url().and_then(|value| parse_remote_url(&value)));
+        Self {
+            github_token: read("GITHUB_TOKEN").or_else(|| read("GH_TOKEN")),
+            gitlab_token: read("GITLAB_TOKEN"),
+            default_repo,
+        }
+    }
+
+    /// Whether a token may be sent to `host`: github.com, gitlab.com, or the host of the default
+    /// repo (the `origin` remote or `CODELIA_FORGE_REPO`). Hosts that merely look like a forge,
+    /// e.g. from a link in assistant output, never get one.
+    pub(crate) fn trusts_host(&self, host: &str) -> bool {
+        TRUSTED_FORGE_HOSTS.contains(&host)
+            || self
+                .default_repo
+                .as_ref()
+                .is_some_and(|repo| repo.host == host)
+    }
+
+    /// The token for `repo`'s forge, only when its host is trusted.
+    pub(crate) fn token_for(&self, repo: &ForgeRepo) -> Option<&str> {
+        if !self.trusts_host(&repo.host) {
+            return None;
+        }
+        match repo.kind {
+            ForgeKind::GitHub => self.github_token.as_deref(),
+            ForgeKind::GitLab => self.gitlab_token.as_deref(),
+        }
+    }
+
+    pub(crate) fn has_any_token(&self) -> bool {
+        self.github_token.is_some() || self.gitlab_token.is_some()
+    }
+}
+
+fn git_origin_url() -> Option<String> {
+    let output = Command::new("git")
+        .args(["remote", "get-url", "origin"])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!url.is_empty()).then_some(url)
+}
+
+fn forge_kind_for_host(host: &str) -> Option<ForgeKind> {
+    if host.contains("github") {
+        Some(ForgeKind::GitHub)
+    } else if host.contains("gitlab") {
+        Some(ForgeKind::GitLab)
+    } else {
+        None
+    }
+}
+
+/// Accepts `git@host:owner/repo(.git)`, `ssh://git@host/owner/repo`, and `https://host/owner/repo`.
+pub(crate) fn parse_remote_url(url: &str) -> Option<ForgeRepo> {
+    let trimmed = url.trim();
+    let (host, path) = if let Some(rest) = trimmed
+        .strip_prefix("https://")
+        .or_else(|| trimmed.strip_prefix("http://"))
+        .or_else(|| trimmed.strip_prefix("ssh://"))
+    {
+        let rest = rest.rsplit_once('@').map_or(rest, |(_, tail)| tail);
+        rest.split_once('/')?
+    } else if let Some(rest) = trimmed.strip_prefix("git@") {
+        rest.split_once(':')?
+    } else {
+        return None;
+    };
+    let host = host.split(':').next().unwrap_or(host).to_ascii_lowercase();
+    let path = path.trim_end_matches('/').trim_end_matches(".git");
+    let path = path.split("/-/").next().unwrap_or(path);
+    if path.split('/').filter(|part| !part.is_empty()).count() < 2 {
+        return None;
+    }
+    Some(ForgeRepo {
+        kind: forge_kind_for_host(&host)?,
+        host,
+        path: path.to_string(),
+    })
+}
+
+fn parse_ref_url(token: &str) -> Option<ForgeRef> {
+    let url = token.trim_end_matches(|ch: char| !ch.is_ascii_alphanumeric());
+    let rest = url.strip_prefix("https://")?;
+    let (host, path) = rest.split_once('/')?;
+    let host = host.to_ascii_lowercase();
+    let kind = forge_kind_for_host(&host)?;
+    let segments = path.split('/').collect::<Vec<_>>();
+    let (repo_path, resource, number) = match kind {
+        ForgeKind::GitHub => {
+            if segments.len() < 4 {
+                return None;
+            }
+            (segments[..2].join("/"), segments[2], segments[3])
+        }
+        ForgeKind::GitLab => {
+            let marker = segments.iter().position(|segment| *segment == "-")?;
+            if marker < 2 || segments.len() < marker + 3 {
+                return None;
+            }
+            (
+                segments[..marker].join("/"),
+                segments[marker + 1],
+                segments[marker + 2],
+            )
+        }
+    };
+    let merge_request = match resource {
+        "pull" | "merge_requests" => true,
+        "issues" => false,
+        _ => return None,
+    };
+    Some(ForgeRef {
+        repo: ForgeRepo {
+            kind,
+            host,
+            path: repo_path,
+        },
+        number: number.parse().ok()?,
+        merge_request,
+    })
+}
+
+fn parse_bare_ref(token: &str, default_repo: &ForgeRepo) -> Option<ForgeRef> {
+    let digits = token
+        .trim_start_matches(['(', '['])
+        .strip_prefix('#')?
+        .trim_end_matches(['.', ',', ';', ':', ')', ']', '?', '!']);
+    if digits.is_empty() || digits.len() > 9 || !digits.chars().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    Some(ForgeRef {
+        repo: default_repo.clone(),
+        number: digits.parse().ok()?,
+        merge_request: false,
+    })
+}
+
+/// Finds `#1234` (resolved against the default repo) and issue/PR URLs in free text.
+pub(crate) fn extract_forge_refs(text: &str, default_repo: Option<&ForgeRepo>) -> Vec<ForgeRef> {
+    let mut seen = HashSet::new();
+    let mut refs = Vec::new();
+    for token in text.split_whitespace() {
+        let parsed = if token.contains("://") {
+            token
+                .find("https://")
+                .and_then(|start| parse_ref_url(&token[start..]))
+        } else {
+            default_repo.and_then(|repo| parse_bare_ref(token, repo))
+        };
+        if let Some(reference) = parsed {
+            if seen.insert(reference.key()) {
+                refs.push(reference);
+            }
+        }
+        if refs.len() >= FORGE_REFS_PER_TEXT_LIMIT {
+            break;
+        }
+    }
+    refs
+}
+
+/// Parses `/issue` arguments: a bare number, `#number`, or an issue/PR URL.
+pub(crate) fn parse_issue_argument(
+    value: &str,
+    default_repo: Option<&ForgeRepo>,
+) -> Option<ForgeRef> {
+    if value.contains("://") {
+        return parse_ref_url(value);
+    }
+    let normalized = if value.starts_with('#') {
+        value.to_string()
+    } else {
+        format!("#{value}")
+    };
+    default_repo.and_then(|repo| parse_bare_ref(&normalized, repo))
+}
+
+pub(crate) fn truncate_chars(value: &str, max: usize) -> String {
+    if value.chars().count() <= max {
+        return value.to_string();
+    }
+    let truncated: String = value.chars().take(max.saturating_sub(3)).collect();
+    format!("{truncated}...")
+}
+
+pub(crate) fn parse_forge_response(reference: &ForgeRef, value: &Value) -> Option<ForgeRefInfo> {
+    let title = value.get("title").and_then(Value::as_str)?;
+    let merged = value
+        .get("merged_at")
+        .is_some_and(|merged| !merged.is_null())
+        || value
+            .get("pull_request")
+            .and_then(|pull| pull.get("merged_at"))
+            .is_some_and(|merged| !merged.is_null());
+    let state = value
+        .get("state")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown");
+    let state = if merged { "merged" } else { state };
+    let url = value
+        .get("html_url")
+        .or_else(|| value.get("web_url"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .unwrap_or_default();
+    let body = value
+        .get("body")
+        .or_else(|| value.get("description"))
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    Some(ForgeRefInfo {
+        key: reference.key(),
+        label: reference.label(),
+        title: title.trim().to_string(),
+        state: state.to_string(),
+        url,
+        is_pull: reference.merge_request || value.get("pull_request").is_some(),
+        body,
+    })
+}
+
+pub(crate) fn format_forge_ref_line(info: &ForgeRefInfo) -> String {
+    let kind = if info.is_pull { "PR" } else { "issue" };
+    let mut line = format!(
+        "  ↳ {} {kind} [{}] {}",
+        info.label,
+        info.state,
+        truncate_chars(&info.title, FORGE_TITLE_MAX_CHARS)
+    );
+    if !info.url.is_empty() {
+        line.push_str(&format!(" — {}", info.url));
+    }
+    line
+}
+
+pub(crate) fn build_issue_context_prefix(contexts: &[ForgeRefInfo]) -> Option<String> {
+    if contexts.is_empty() {
+        return None;
+    }
+    let blocks = contexts
+        .iter()
+        .map(|info| {
+            let payload = json!({
+                "reference": info.label,
+                "kind": if info.is_pull { "pull_request" } else { "issue" },
+                "state": info.state,
+                "title": info.title,
+                "url": info.url,
+                "body": info
+                    .body
+                    .as_deref()
+                    .map(|body| truncate_chars(body, FORGE_CONTEXT_BODY_MAX_CHARS)),
+            });
+            let json_text = payload
+                .to_string()
+                .replace('<', "\\u003c")
+                .replace('>', "\\u003e");
+            format!("<issue_context>\n{json_text}\n</issue_context>")
+        })
+        .collect::<Vec<_>>();
+    Some(blocks.join("\n"))
+}
+
+const PR_TITLE_MAX_CHARS: usize = 72;
+const PR_BODY_MAX_CHARS: usize = 8_000;
+const PR_PUSH_COMMAND: &str = "git push -u origin HEAD";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PullRequestDraft {
+    pub kind: ForgeKind,
+    pub title: String,
+    pub body: String,
+    pub draft: bool,
+}
+
+impl PullRequestDraft {
+    pub(crate) fn new(kind: ForgeKind, title: &str, body: &str, draft: bool) -> Self {
+        Self {
+            kind,
+            title: truncate_chars(title.trim(), PR_TITLE_MAX_CHARS),
+            body: truncate_chars(body.trim(), PR_BODY_MAX_CHARS),
+            draft,
+        }
+    }
+
+    /// Push the current branch, then create the PR/MR through the forge CLI.
+    pub(crate) fn shell_command(&self) -> String {
+        let mut args = match self.kind {
+            ForgeKind::GitHub => vec![
+                "gh".to_string(),
+                "pr".to_string(),
+                "create".to_string(),
+                "--title".to_string(),
+                self.title.clone(),
+                "--body".to_string(),
+                self.body.clone(),
+            ],
+            ForgeKind::GitLab => vec![
+                "glab".to_string(),
+                "mr".to_string(),
+                "create".to_string(),
+                "--yes".to_string(),
+                "--title".to_string(),
+                self.title.clone(),
+                "--description".to_string(),
+                self.body.clone(),
+            ],
+        };
+        if self.draft {
+            args.push("--draft".to_string());
+        }
+        format!("{PR_PUSH_COMMAND} && {}", shell_words::join(args))
+    }
+}
+
+/// First line of the prompt that started the run, used when `/pr` gets no explicit title.
+pub(crate) fn default_pull_request_title(prompt: &str) -> Option<String> {
+    prompt
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+pub(crate) fn extract_pull_request_url(output: &str) -> Option<String> {
+    output
+        .split_whitespace()
+        .filter(|token| token.starts_with("https://"))
+        .find(|token| token.contains("/pull/") || token.contains("/merge_requests/"))
+        .map(|token| token.trim_end_matches(['.', ',', ')']).to_string())
+}
+
+/// Host (with port) of an `https://` URL, lowercased; `None` for any other scheme.
+fn https_host(url: &str) -> Option<String> {
+    let rest = url.strip_prefix("https://")?;
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    (!host.is_empty()).then(|| host.to_ascii_lowercase())
+}
+
+/// Splits curl output into the body and the `<status> <redirect url>` written after it.
+fn split_curl_status(stdout: &[u8]) -> Option<(&[u8], u16, String)> {
+    let marker = CURL_STATUS_MARKER.as_bytes();
+    let start = stdout
+        .windows(marker.len())
+        .rposition(|window| window == marker)?;
+    let status = String::from_utf8_lossy(&stdout[start + marker.len()..]).into_owned();
+    let (code, location) = status.split_once(' ').unwrap_or((&status, ""));
+    Some((
+        &stdout[..start],
+        code.trim().parse().ok()?,
+        location.trim().to_string(),
+    ))
+}
+
+/// One GET without following redirects; the credential header, when given, goes on stdin so
+/// the token never appears in the process list.
+fn curl_get(url: &str, auth_header: Option<&str>) -> Result<(Vec<u8>, u16, String), String> {
+    let timeout = FORGE_FETCH_TIMEOUT_SECS.to_string();
+    let write_out = format!("{CURL_STATUS_MARKER}%{{http_code}} %{{redirect_url}}");
+    let mut args = vec!["-sS", "--max-time", &timeout, "-w", &write_out];
+    if auth_header.is_some() {
+        args.extend(["-H", "@-"]);
+    }
+    args.extend([
+        "-H",
+        "Accept: application/json",
+        "-H",
+        "User-Agent: codelia-tui",
+        url,
+    ]);
+    let mut child = Command::new("curl")
+        .args(&args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("failed to run curl: {error}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        use std::io::Write;
+        if let Some(header) = auth_header {
+            let _ = writeln!(stdin, "{header}");
+        }
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|error| format!("curl failed: {error}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(if stderr.is_empty() {
+            format!("curl exited with {}", output.status)
+        } else {
+            stderr
+        });
+    }
+    let (body, status, location) = split_curl_status(&output.stdout)
+        .ok_or_else(|| "curl did not report a status".to_string())?;
+    Ok((body.to_vec(), status, location))
+}
+
+/// Authenticated GET against a forge API through `curl`. Redirects are followed by hand so the
+/// host is checked on every hop: the token only goes to the host of `url` (built from a trusted
+/// repo), a redirect elsewhere (e.g. CI logs on blob storage) is fetched without it, and a
+/// redirect off `https://` is refused.
+pub(crate) fn forge_api_get(kind: ForgeKind, token: &str, url: &str) -> Result<Vec<u8>, String> {
+    let auth_header = match kind {
+        ForgeKind::GitHub => format!("Authorization: Bearer {token}"),
+        ForgeKind::GitLab => format!("PRIVATE-TOKEN: {token}"),
+    };
+    let credential_host = https_host(url).ok_or_else(|| format!("not an https URL: {url}"))?;
+    let mut url = url.to_string();
+    for _ in 0..=FORGE_MAX_REDIRECTS {
+        let host = https_host(&url).ok_or_else(|| format!("refusing redirect to {url}"))?;
+        let auth = (host == credential_host).then_some(auth_header.as_str());
+        let (body, status, location) = curl_get(&url, auth)?;
+        match status {
+            200..=299 => return Ok(body),
+            300..=399 if !location.is_empty() => url = location,
+            _ => return Err(format!("HTTP {status} from {host}")),
+        }
+    }
+    Err(format!("too many redirects (last: {url})"))
+}
+
+fn fetch_forge_ref(token: &str, reference: &ForgeRef) -> Result<ForgeRefInfo, String> {
+    let body = forge_api_get(reference.repo.kind, token, &reference.api_url())?;
+    let value: Value = serde_json::from_slice(&body)
+        .map_err(|error| format!("invalid forge response: {error}"))?;
+    parse_forge_response(reference, &value)
+        .ok_or_else(|| "forge response did not include a title".to_string())
+}
+
+pub(crate) struct ForgeFetchResult {
+    pub reference: ForgeRef,
+    pub attach: bool,
+    pub result: Result<ForgeRefInfo, String>,
+}
+
+/// Background fetch bookkeeping; results are drained once per tick by the run loop.
+pub(crate) struct ForgeRefState {
+    config: Option<ForgeConfig>,
+    pub requested: HashSet<String>,
+    pub in_flight: usize,
+    pub pending_contexts: Vec<ForgeRefInfo>,
+    sender: Sender<ForgeFetchResult>,
+    receiver: Receiver<ForgeFetchResult>,
+}
+
+impl Default for ForgeRefState {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            config: None,
+            requested: HashSet::new(),
+            in_flight: 0,
+            pending_contexts: Vec::new(),
+            sender,
+            receiver,
+        }
+    }
+}
+
+impl ForgeRefState {
+    /// Resolves env tokens and the git origin once per process.
+    pub(crate) fn config(&mut self) -> &ForgeConfig {
+        self.config.get_or_insert_with(ForgeConfig::from_env)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_config(&mut self, config: ForgeConfig) {
+        self.config = Some(config);
+    }
+
+    pub(crate) fn spawn_fetch(&mut self, reference: ForgeRef, attach: bool) -> bool {
+        let Some(token) = self.config().token_for(&reference.repo).map(str::to_string) else {
+            return false;
+        };
+        let sender = self.sender.clone();
+        self.in_flight = self.in_flight.saturating_add(1);
+        std::thread::spawn(move || {
+            let result = fetch_forge_ref(&token, &reference);
+            let _ = sender.send(ForgeFetchResult {
+                reference,
+                attach,
+                result,
+            });
+        });
+        true
+    }
+
+    pub(crate) fn drain_results(&mut self) -> Vec<ForgeFetchResult> {
+        if self.in_flight == 0 {
+            return Vec::new();
+        }
+        let results = self.receiver.try_iter().collect::<Vec<_>>();
+        self.in_flight = self.in_flight.saturating_sub(results.len());
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        build_issue_context_prefix, default_pull_request_title, extract_forge_refs,
+        extract_pull_request_url, format_forge_ref_line, https_host, parse_forge_response,
+        parse_issue_argument, parse_remote_url, split_curl_status, ForgeConfig, ForgeKind,
+        ForgeRef, ForgeRefState, ForgeRepo, PullRequestDraft, CURL_STATUS_MARKER,
+    };
+    use serde_json::json;
+
+    fn github_repo() -> ForgeRepo {
+        ForgeRepo {
+            kind: ForgeKind::GitHub,
+            host: "github.com".to_string(),
+            path: "kousw/codelia".to_string(),
+        }
+    }
+
+    #[test]
+    fn parse_remote_url_supports_ssh_and_https() {
+        assert_eq!(
+            parse_remote_url("git@github.com:kousw/codelia.git"),
+            Some(github_repo())
+        );
+        assert_eq!(
+            parse_remote_url("https://token@github.com/kousw/codelia"),
+            Some(github_repo())
+        );
+        let gitlab = parse_remote_url("ssh://git@gitlab.example.com:2222/group/sub/proj.git")
+            .expect("gitlab remote");
+        assert_eq!(gitlab.kind, ForgeKind::GitLab);
+        assert_eq!(gitlab.host, "gitlab.example.com");
+        assert_eq!(gitlab.path, "group/sub/proj");
+        assert!(parse_remote_url("/tmp/local-repo").is_none());
+    }
+
+    #[test]
+    fn extract_forge_refs_finds_bare_numbers_and_urls() {
+        let repo = github_repo();
+        let refs = extract_forge_refs(
+            "fix #12, see (#12) and https://github.com/other/repo/pull/7. a#3 #x",
+            Some(&repo),
+        );
+        assert_eq!(refs.len(), 2);
+        assert_eq!(refs[0].key(), "github.com/kousw/codelia#12");
+        assert_eq!(refs[1].label(), "other/repo#7");
+        assert!(refs[1].merge_request);
+    }
+
+    #[test]
+    fn extract_forge_refs_ignores_bare_numbers_without_repo() {
+        let refs = extract_forge_refs("#12 https://gitlab.com/group/proj/-/merge_requests/5", None);
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].repo.kind, ForgeKind::GitLab);
+        assert_eq!(refs[0].repo.path, "group/proj");
+        assert_eq!(refs[0].number, 5);
+    }
+
+    #[test]
+    fn parse_issue_argument_accepts_number_and_hash() {
+        let repo = github_repo();
+        assert_eq!(
+            parse_issue_argument("1234", Some(&repo)).map(|reference| reference.number),
+            Some(1234)
+        );
+        assert_eq!(
+            parse_issue_argument("#5", Some(&repo)).map(|reference| reference.number),
+            Some(5)
+        );
+        assert!(parse_issue_argument("12", None).is_none());
+    }
+
+    #[test]
+    fn parse_forge_response_marks_merged_pull_requests() {
+        let reference = ForgeRef {
+            repo: github_repo(),
+            number: 9,
+            merge_request: false,
+        };
+        let info = parse_forge_response(
+            &reference,
+            &json!({
+                "title": "Add bundle export",
+                "state": "closed",
+                "html_url": "https://github.com/kousw/codelia/pull/9",
+                "pull_request": { "merged_at": "2026-01-01T00:00:00Z" },
+                "body": "details"
+            }),
+        )
+        .expect("info");
+        assert_eq!(info.state, "merged");
+        assert!(info.is_pull);
+        assert_eq!(
+            format_forge_ref_line(&info),
+            "  ↳ kousw/codelia#9 PR [merged] Add bundle export — https://github.com/kousw/codelia/pull/9"
+        );
+        let prefix = build_issue_context_prefix(&[info]).expect("prefix");
+        assert!(prefix.starts_with("<issue_context>"));
+        assert!(prefix.contains("\"body\":\"details\""));
+    }
+
+    #[test]
+    fn forge_state_skips_fetch_without_matching_token() {
+        let mut state = ForgeRefState::default();
+        state.set_config(ForgeConfig {
+            github_token: None,
+            gitlab_token: Some("token".to_string()),
+            default_repo: Some(github_repo()),
+        });
+        let reference = ForgeRef {
+            repo: github_repo(),
+            number: 1,
+            merge_request: false,
+        };
+        assert!(!state.spawn_fetch(reference, false));
+        assert_eq!(state.in_flight, 0);
+        assert!(state.drain_results().is_empty());
+    }
+
+    #[test]
+    fn tokens_only_go_to_allowlisted_hosts_and_the_origin_host() {
+        let config = ForgeConfig {
+            github_token: Some("gh".to_string()),
+            gitlab_token: Some("gl".to_string()),
+            default_repo: parse_remote_url("git@gitlab.example.com:group/proj.git"),
+        };
+        let lookalike = extract_forge_refs("https://github.attacker.io/o/r/issues/1", None);
+        assert_eq!(lookalike[0].repo.host, "github.attacker.io");
+        assert_eq!(config.token_for(&lookalike[0].repo), None);
+        assert_eq!(config.token_for(&github_repo()), Some("gh"));
+        let origin = config.default_repo.clone().expect("origin");
+        assert_eq!(config.token_for(&origin), Some("gl"));
+        assert!(!config.trusts_host("gitlab.example.com.attacker.io"));
+    }
+
+    #[test]
+    fn curl_status_and_hosts_are_parsed_for_redirect_checks() {
+        assert_eq!(
+            https_host("https://user@API.github.com:443/repos/o/r?x=1").as_deref(),
+            Some("api.github.com:443")
+        );
+        assert_eq!(https_host("http://api.github.com/repos"), None);
+        let stdout = format!("{{\"a\":1}}{CURL_STATUS_MARKER}302 https://blob.example/log");
+        let (body, status, location) = split_curl_status(stdout.as_bytes()).expect("status");
+        assert_eq!(body, b"{\"a\":1}");
+        assert_eq!(status, 302);
+        assert_eq!(location, "https://blob.example/log");
+        let stdout = format!("ok{CURL_STATUS_MARKER}200 ");
+        assert_eq!(
+            split_curl_status(stdout.as_bytes()),
+            Some((&b"ok"[..], 200, String::new()))
+        );
+    }
+
+    #[test]
+    fn pull_request_draft_builds_quoted_cli_command() {
+        let draft = PullRequestDraft::new(ForgeKind::GitHub, "Fix it's bug", "Summary\nline", true);
+        assert_eq!(
+            draft.shell_command(),
+            "git push -u origin HEAD && gh pr create --title 'Fix it'\\''s bug' --body 'Summary\nline' --draft"
+        );
+        let gitlab = PullRequestDraft::new(ForgeKind::GitLab, "t", "b", false);
+        assert!(gitlab
+            .shell_command()
+            .ends_with("glab mr create --yes --title t --description b"));
+    }
+
+    #[test]
+    fn extract_pull_request_url_finds_created_url() {
+        assert_eq!(
+            extract_pull_request_url("Creating pull request\nhttps://github.com/o/r/pull/3\n"),
+            Some("https://github.com/o/r/pull/3".to_string())
+        );
+        assert_eq!(
+            extract_pull_request_url("see https://gitlab.com/g/p/-/merge_requests/8."),
+            Some("https://gitlab.com/g/p/-/merge_requests/8".to_string())
+        );
+        assert!(extract_pull_request_url("https://github.com/o/r").is_none());
+        assert_eq!(
+            default_pull_request_title("\n  Fix login\nmore"),
+            Some("Fix login".to_string())
+        );
+    }
+}
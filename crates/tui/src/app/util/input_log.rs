@@ -0,0 +1,244 @@
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseEvent};
+use std::collections::VecDeque;
+use std::fs;
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Events kept by the `record_input` ring buffer.
+pub(crate) const MAX_RECORDED_INPUT_EVENTS: usize = 200;
+
+/// Off by default: nothing about typed input is kept unless `record_input` is on.
+static INPUT_RECORDING: AtomicBool = AtomicBool::new(false);
+static INPUT_EVENTS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+static RECORDING_EPOCH: OnceLock<Instant> = OnceLock::new();
+
+pub(crate) fn set_input_recording(enabled: bool) {
+    INPUT_RECORDING.store(enabled, Ordering::Relaxed);
+    if !enabled {
+        if let Ok(mut events) = INPUT_EVENTS.lock() {
+            events.clear();
+        }
+    }
+}
+
+pub(crate) fn input_recording() -> bool {
+    INPUT_RECORDING.load(Ordering::Relaxed)
+}
+
+fn describe_modifiers(modifiers: KeyModifiers) -> String {
+    let names = [
+        (KeyModifiers::CONTROL, "ctrl"),
+        (KeyModifiers::ALT, "alt"),
+        (KeyModifiers::SHIFT, "shift"),
+        (KeyModifiers::SUPER, "super"),
+        (KeyModifiers::HYPER, "hyper"),
+        (KeyModifiers::META, "meta"),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| modifiers.contains(*flag))
+    .map(|(_, name)| name)
+    .collect::<Vec<_>>();
+    if names.is_empty() {
+        "none".to_string()
+    } else {
+        names.join("+")
+    }
+}
+
+/// Typed text is reduced to its character class; shortcuts and punctuation stay exact.
+fn describe_key_code(code: KeyCode, modifiers: KeyModifiers) -> String {
+    let KeyCode::Char(ch) = code else {
+        return format!("{code:?}");
+    };
+    if modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) || ch.is_ascii_punctuation()
+    {
+        return format!("{code:?}");
+    }
+    let class = match ch {
+        ' ' => "' '",
+        ch if ch.is_uppercase() => "A",
+        ch if ch.is_alphabetic() => "a",
+        ch if ch.is_numeric() => "0",
+        _ => "·",
+    };
+    format!("Char({class})")
+}
+
+fn describe_key(key: &KeyEvent) -> String {
+    format!(
+        "key {} mods={} kind={:?}",
+        describe_key_code(key.code, key.modifiers),
+        describe_modifiers(key.modifiers),
+        key.kind
+    )
+}
+
+fn describe_mouse(mouse: &MouseEvent) -> String {
+    format!(
+        "mouse {:?} at {},{} mods={}",
+        mouse.kind,
+        mouse.column,
+        mouse.row,
+        describe_modifiers(mouse.modifiers)
+    )
+}
+
+pub(crate) fn describe_input_event(event: &Event) -> String {
+    match event {
+        Event::Key(key) => describe_key(key),
+        Event::Mouse(mouse) => describe_mouse(mouse),
+        Event::Paste(text) => format!(
+            "paste {} chars, {} lines",
+            text.chars().count(),
+            text.lines().count().max(1)
+        ),
+        Event::Resize(width, height) => format!("resize {width}x{height}"),
+        Event::FocusGained => "focus gained".to_string(),
+        Event::FocusLost => "focus lost".to_string(),
+    }
+}
+
+/// Appends `event` to the ring buffer when recording is on.
+pub(crate) fn record_input_event(event: &Event) {
+    if !input_recording() {
+        return;
+    }
+    let epoch = *RECORDING_EPOCH.get_or_init(Instant::now);
+    let line = format!(
+        "+{:.3}s {}",
+        epoch.elapsed().as_secs_f64(),
+        describe_input_event(event)
+    );
+    if let Ok(mut events) = INPUT_EVENTS.lock() {
+        if events.len() == MAX_RECORDED_INPUT_EVENTS {
+            events.pop_front();
+        }
+        events.push_back(line);
+    }
+}
+
+pub(crate) fn recorded_input_events() -> Vec<String> {
+    INPUT_EVENTS
+        .lock()
+        .map(|events| events.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+fn unix_ms() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|value| value.as_millis())
+        .unwrap_or(0)
+}
+
+fn events_section(events: &[String]) -> String {
+    let mut text = format!(
+        "# last {} input events (typed letters/digits shown as a/A/0)\n",
+        events.len()
+    );
+    for event in events {
+        text.push_str(event);
+        text.push('\n');
+    }
+    text
+}
+
+/// Writes the recorded events for `/debug-keys`; the default file goes to the working directory.
+pub(crate) fn export_input_events(target: Option<&Path>) -> Result<(PathBuf, usize), String> {
+    let path = target
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from(format!("codelia-keys-{}.log", unix_ms())));
+    let events = recorded_input_events();
+    fs::write(&path, events_section(&events))
+        .map_err(|error| format!("{}: {error}", path.display()))?;
+    Ok((path, events.len()))
+}
+
+fn write_crash_report(info: &PanicHookInfo<'_>) -> std::io::Result<PathBuf> {
+    let path = std::env::temp_dir().join(format!("codelia-tui-crash-{}.log", unix_ms()));
+    let mut report = format!(
+        "codelia-tui {} crashed\n{info}\n\n",
+        env!("CARGO_PKG_VERSION")
+    );
+    // The panicking thread may hold the buffer lock; never block inside the hook.
+    match INPUT_EVENTS.try_lock() {
+        Ok(events) if input_recording() => {
+            report.push_str(&events_section(&events.iter().cloned().collect::<Vec<_>>()));
+        }
+        Ok(_) => report.push_str("# input recording was off (`/set record_input on`)\n"),
+        Err(_) => report.push_str("# input events unavailable (buffer busy)\n"),
+    }
+    fs::write(&path, report)?;
+    Ok(path)
+}
+
+/// Chains a panic hook that writes a crash report (with recorded input) to the temp directory.
+pub(crate) fn install_crash_report_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let report = write_crash_report(info);
+        previous(info);
+        if let Ok(path) = report {
+            eprintln!("crash report: {}", path.display());
+        }
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::describe_input_event;
+    use crossterm::event::{
+        Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent, MouseEventKind,
+    };
+
+    #[test]
+    fn input_events_hide_typed_text_but_keep_shortcuts() {
+        let key = |code, modifiers| {
+            describe_input_event(&Event::Key(KeyEvent::new_with_kind(
+                code,
+                modifiers,
+                KeyEventKind::Press,
+            )))
+        };
+        assert_eq!(
+            key(KeyCode::Char('p'), KeyModifiers::NONE),
+            "key Char(a) mods=none kind=Press"
+        );
+        assert_eq!(
+            key(KeyCode::Char('P'), KeyModifiers::SHIFT),
+            "key Char(A) mods=shift kind=Press"
+        );
+        assert_eq!(
+            key(KeyCode::Char('7'), KeyModifiers::NONE),
+            "key Char(0) mods=none kind=Press"
+        );
+        assert_eq!(
+            key(KeyCode::Char('\\'), KeyModifiers::NONE),
+            "key Char('\\\\') mods=none kind=Press"
+        );
+        assert_eq!(
+            key(KeyCode::Char('j'), KeyModifiers::CONTROL),
+            "key Char('j') mods=ctrl kind=Press"
+        );
+        assert_eq!(
+            key(KeyCode::Enter, KeyModifiers::SHIFT),
+            "key Enter mods=shift kind=Press"
+        );
+        assert_eq!(
+            describe_input_event(&Event::Paste("secret\ntoken".to_string())),
+            "paste 12 chars, 2 lines"
+        );
+        assert_eq!(
+            describe_input_event(&Event::Mouse(MouseEvent {
+                kind: MouseEventKind::ScrollUp,
+                column: 4,
+                row: 9,
+                modifiers: KeyModifiers::NONE,
+            })),
+            "mouse ScrollUp at 4,9 mods=none"
+        );
+    }
+}
@@ -0,0 +1,356 @@
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Containers nested deeper than this are folded to a one-line summary in the log.
+pub(crate) const JSON_FOLD_DEPTH: usize = 3;
+/// Children shown per object/array before the rest are folded.
+pub(crate) const JSON_FOLD_ITEMS: usize = 20;
+
+/// Structured-output request attached to the next prompt by `/json`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum JsonOutputRequest {
+    /// A JSON Schema object; the answer is validated against it.
+    Schema(Value),
+    /// Free-form shape description; the answer only has to parse.
+    Description(String),
+}
+
+impl JsonOutputRequest {
+    /// Text starting with `{` must be a JSON Schema object; anything else is a description.
+    pub(crate) fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err("missing schema or description".to_string());
+        }
+        if !text.starts_with('{') {
+            return Ok(Self::Description(text.to_string()));
+        }
+        match serde_json::from_str::<Value>(text) {
+            Ok(schema @ Value::Object(_)) => Ok(Self::Schema(schema)),
+            Ok(_) => Err("schema must be a JSON object".to_string()),
+            Err(error) => Err(format!("invalid JSON schema: {error}")),
+        }
+    }
+
+    pub(crate) fn label(&self) -> String {
+        match self {
+            Self::Schema(schema) => match schema.get("properties").and_then(Value::as_object) {
+                Some(properties) => format!("schema ({} properties)", properties.len()),
+                None => "schema".to_string(),
+            },
+            Self::Description(description) => format!("\"{description}\""),
+        }
+    }
+
+    /// Appended to the prompt text sent to the runtime.
+    pub(crate) fn instruction(&self) -> String {
+        match self {
+            Self::Schema(schema) => format!(
+                "Reply with a single JSON value that conforms to this JSON Schema. Output only the JSON: no prose and no Markdown fences.\n\nJSON Schema:\n{}",
+                serde_json::to_string_pretty(schema).unwrap_or_else(|_| schema.to_string())
+            ),
+            Self::Description(description) => format!(
+                "Reply with a single JSON value matching this description: {description}\nOutput only the JSON: no prose and no Markdown fences."
+            ),
+        }
+    }
+}
+
+fn fenced_body(text: &str) -> Option<&str> {
+    let start = text.find("```")?;
+    let after_fence = &text[start + 3..];
+    let body_start = after_fence.find('\n')? + 1;
+    let body = &after_fence[body_start..];
+    let end = body.find("```")?;
+    Some(&body[..end])
+}
+
+/// Parses the answer as JSON, falling back to its first fenced code block.
+pub(crate) fn extract_json_answer(text: &str) -> Result<Value, String> {
+    let trimmed = text.trim();
+    match serde_json::from_str::<Value>(trimmed) {
+        Ok(value) => Ok(value),
+        Err(error) => fenced_body(trimmed)
+            .and_then(|body| serde_json::from_str::<Value>(body.trim()).ok())
+            .ok_or_else(|| error.to_string()),
+    }
+}
+
+fn json_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(number) if number.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn type_matches(value: &Value, expected: &str) -> bool {
+    match expected {
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64()
+                || value.is_u64()
+                || value.as_f64().is_some_and(|number| number.fract() == 0.0)
+        }
+        other => json_type_name(value) == other,
+    }
+}
+
+/// Checks the common JSON Schema keywords (type, enum, const, properties, required,
+/// additionalProperties, items, min/max bounds); unknown keywords are ignored.
+pub(crate) fn validate_json(value: &Value, schema: &Value) -> Vec<String> {
+    let mut errors = Vec::new();
+    validate_at(value, schema, "$", &mut errors);
+    errors
+}
+
+fn validate_at(value: &Value, schema: &Value, path: &str, errors: &mut Vec<String>) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+    let expected_types = match schema.get("type") {
+        Some(Value::String(name)) => vec![name.as_str()],
+        Some(Value::Array(names)) => names.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    if !expected_types.is_empty() && !expected_types.iter().any(|name| type_matches(value, name)) {
+        errors.push(format!(
+            "{path}: expected {}, got {}",
+            expected_types.join(" or "),
+            json_type_name(value)
+        ));
+        return;
+    }
+    if let Some(Value::Array(options)) = schema.get("enum") {
+        if !options.contains(value) {
+            errors.push(format!("{path}: {value} is not one of the allowed values"));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            errors.push(format!("{path}: expected {expected}"));
+        }
+    }
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::Array(required)) = schema.get("required") {
+                for name in required.iter().filter_map(Value::as_str) {
+                    if !map.contains_key(name) {
+                        errors.push(format!("{path}: missing required property `{name}`"));
+                    }
+                }
+            }
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for (key, child) in map {
+                let child_path = format!("{path}.{key}");
+                match properties.and_then(|properties| properties.get(key)) {
+                    Some(child_schema) => validate_at(child, child_schema, &child_path, errors),
+                    None => match schema.get("additionalProperties") {
+                        Some(Value::Bool(false)) => {
+                            errors.push(format!("{path}: unexpected property `{key}`"));
+                        }
+                        Some(extra_schema) => {
+                            validate_at(child, extra_schema, &child_path, errors);
+                        }
+                        None => {}
+                    },
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    validate_at(item, item_schema, &format!("{path}[{index}]"), errors);
+                }
+            }
+            check_bounds(
+                items.len(),
+                schema,
+                "minItems",
+                "maxItems",
+                "items",
+                path,
+                errors,
+            );
+        }
+        Value::String(text) => {
+            let length = text.chars().count();
+            check_bounds(
+                length,
+                schema,
+                "minLength",
+                "maxLength",
+                "characters",
+                path,
+                errors,
+            );
+        }
+        Value::Number(number) => {
+            let number = number.as_f64().unwrap_or_default();
+            if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64) {
+                if number < minimum {
+                    errors.push(format!("{path}: {number} is below the minimum {minimum}"));
+                }
+            }
+            if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64) {
+                if number > maximum {
+                    errors.push(format!("{path}: {number} is above the maximum {maximum}"));
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn check_bounds(
+    count: usize,
+    schema: &serde_json::Map<String, Value>,
+    min_key: &str,
+    max_key: &str,
+    unit: &str,
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    if let Some(min) = schema.get(min_key).and_then(Value::as_u64) {
+        if (count as u64) < min {
+            errors.push(format!("{path}: {count} {unit}, expected at least {min}"));
+        }
+    }
+    if let Some(max) = schema.get(max_key).and_then(Value::as_u64) {
+        if (count as u64) > max {
+            errors.push(format!("{path}: {count} {unit}, expected at most {max}"));
+        }
+    }
+}
+
+/// Pretty-printed lines with deep or long containers folded to summaries such as `{… 4 keys}`.
+pub(crate) fn folded_json_lines(value: &Value) -> Vec<String> {
+    let mut lines = Vec::new();
+    push_folded(value, 0, None, true, &mut lines);
+    lines
+}
+
+fn push_folded(
+    value: &Value,
+    depth: usize,
+    key: Option<&str>,
+    last: bool,
+    lines: &mut Vec<String>,
+) {
+    let indent = "  ".repeat(depth);
+    let head = match key {
+        Some(key) => format!("{indent}{}: ", Value::String(key.to_string())),
+        None => indent.clone(),
+    };
+    let comma = if last { "" } else { "," };
+    let (open, close, len) = match value {
+        Value::Object(map) if !map.is_empty() => ('{', '}', map.len()),
+        Value::Array(items) if !items.is_empty() => ('[', ']', items.len()),
+        scalar => {
+            lines.push(format!("{head}{scalar}{comma}"));
+            return;
+        }
+    };
+    if depth >= JSON_FOLD_DEPTH {
+        let unit = match (open, len) {
+            ('{', 1) => "key",
+            ('{', _) => "keys",
+            (_, 1) => "item",
+            _ => "items",
+        };
+        lines.push(format!("{head}{open}… {len} {unit}{close}{comma}"));
+        return;
+    }
+    lines.push(format!("{head}{open}"));
+    let shown = len.min(JSON_FOLD_ITEMS);
+    let folded_rest = len > shown;
+    match value {
+        Value::Object(map) => {
+            for (index, (child_key, child)) in map.iter().take(shown).enumerate() {
+                let child_last = index + 1 == shown && !folded_rest;
+                push_folded(child, depth + 1, Some(child_key), child_last, lines);
+            }
+        }
+        Value::Array(items) => {
+            for (index, child) in items.iter().take(shown).enumerate() {
+                let child_last = index + 1 == shown && !folded_rest;
+                push_folded(child, depth + 1, None, child_last, lines);
+            }
+        }
+        _ => {}
+    }
+    if folded_rest {
+        lines.push(format!("{indent}  … {} more", len - shown));
+    }
+    lines.push(format!("{indent}{close}{comma}"));
+}
+
+/// Writes pretty JSON for `/json save`; the default file goes to the working directory.
+pub(crate) fn save_json_output(value: &Value, target: Option<&Path>) -> Result<PathBuf, String> {
+    let path = target.map(Path::to_path_buf).unwrap_or_else(|| {
+        let created_at_unix_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|value| value.as_millis())
+            .unwrap_or(0);
+        PathBuf::from(format!("codelia-output-{created_at_unix_ms}.json"))
+    });
+    let text = serde_json::to_string_pretty(value).map_err(|error| error.to_string())?;
+    std::fs::write(&path, format!("{text}\n"))
+        .map_err(|error| format!("{}: {error}", path.display()))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{extract_json_answer, folded_json_lines, validate_json, JsonOutputRequest};
+    use serde_json::json;
+
+    #[test]
+    fn json_answers_parse_validate_and_fold() {
+        let request = JsonOutputRequest::parse(
+            r#"{"type":"object","required":["name","tags"],"additionalProperties":false,
+                "properties":{"name":{"type":"string"},"tags":{"type":"array","items":{"type":"string"},"maxItems":2},
+                "count":{"type":"integer","minimum":0}}}"#,
+        )
+        .expect("schema");
+        assert_eq!(request.label(), "schema (3 properties)");
+        assert!(JsonOutputRequest::parse("{not json").is_err());
+        assert_eq!(
+            JsonOutputRequest::parse("a list of file names").unwrap(),
+            JsonOutputRequest::Description("a list of file names".to_string())
+        );
+        let JsonOutputRequest::Schema(schema) = request else {
+            panic!("expected schema");
+        };
+
+        let answer =
+            "Here you go:\n```json\n{\"name\": \"x\", \"tags\": [\"a\"], \"count\": 2}\n```";
+        let value = extract_json_answer(answer).expect("fenced json");
+        assert!(validate_json(&value, &schema).is_empty());
+        assert!(extract_json_answer("no json here").is_err());
+
+        let bad = json!({"tags": ["a", 1, "c"], "count": -1.5, "extra": true});
+        assert_eq!(
+            validate_json(&bad, &schema),
+            vec![
+                "$: missing required property `name`",
+                "$.count: expected integer, got number",
+                "$: unexpected property `extra`",
+                "$.tags[1]: expected string, got integer",
+                "$.tags: 3 items, expected at most 2",
+            ]
+        );
+
+        let nested = json!({"a": {"b": {"c": {"d": 1}}}, "list": (0..25).collect::<Vec<_>>()});
+        let lines = folded_json_lines(&nested);
+        assert_eq!(lines[0], "{");
+        assert_eq!(lines[3], "      \"c\": {… 1 key}");
+        assert!(lines.contains(&"    … 5 more".to_string()));
+        assert_eq!(lines.last().map(String::as_str), Some("}"));
+    }
+}
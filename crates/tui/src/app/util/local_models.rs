@@ -0,0 +1,240 @@
+use serde_json::Value;
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver};
+
+/// Local servers answer instantly or not at all; keep `/local-models` snappy.
+const PROBE_TIMEOUT_SECS: u64 = 2;
+
+/// Runtime provider id for models served by a local Ollama/llama.cpp server.
+pub(crate) const LOCAL_MODEL_PROVIDER: &str = "local";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LocalServerKind {
+    Ollama,
+    LlamaCpp,
+}
+
+impl LocalServerKind {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Ollama => "Ollama",
+            Self::LlamaCpp => "llama.cpp",
+        }
+    }
+
+    fn models_path(self) -> &'static str {
+        match self {
+            Self::Ollama => "/api/tags",
+            Self::LlamaCpp => "/v1/models",
+        }
+    }
+}
+
+/// Default ports, probed in order; the first server answering on a port wins it.
+const LOCAL_SERVER_PROBES: &[(LocalServerKind, u16)] = &[
+    (LocalServerKind::Ollama, 11434),
+    (LocalServerKind::LlamaCpp, 8080),
+    (LocalServerKind::LlamaCpp, 8000),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LocalModel {
+    pub name: String,
+    pub size_bytes: Option<u64>,
+    pub quantization: Option<String>,
+    pub parameters: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LocalServer {
+    pub kind: LocalServerKind,
+    pub base_url: String,
+    pub models: Vec<LocalModel>,
+}
+
+/// Ollama `GET /api/tags`.
+pub(crate) fn parse_ollama_models(value: &Value) -> Vec<LocalModel> {
+    value
+        .get("models")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|model| {
+            let name = model
+                .get("name")
+                .or_else(|| model.get("model"))
+                .and_then(Value::as_str)?;
+            let details = model.get("details");
+            let detail = |key: &str| {
+                details
+                    .and_then(|details| details.get(key))
+                    .and_then(Value::as_str)
+                    .filter(|value| !value.is_empty())
+                    .map(str::to_string)
+            };
+            Some(LocalModel {
+                name: name.to_string(),
+                size_bytes: model.get("size").and_then(Value::as_u64),
+                quantization: detail("quantization_level"),
+                parameters: detail("parameter_size"),
+            })
+        })
+        .collect()
+}
+
+/// OpenAI-compatible `GET /v1/models` as served by llama.cpp; size and parameter count come
+/// from its `meta` block, and the quantization is guessed from a GGUF file name.
+pub(crate) fn parse_openai_compatible_models(value: &Value) -> Vec<LocalModel> {
+    value
+        .get("data")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|model| {
+            let name = model.get("id").and_then(Value::as_str)?;
+            let meta = model.get("meta");
+            let meta_u64 = |key: &str| meta.and_then(|meta| meta.get(key)).and_then(Value::as_u64);
+            Some(LocalModel {
+                name: name.to_string(),
+                size_bytes: meta_u64("size"),
+                quantization: gguf_quantization(name),
+                parameters: meta_u64("n_params").map(format_parameter_count),
+            })
+        })
+        .collect()
+}
+
+/// `qwen2.5-7b-instruct-Q4_K_M.gguf` → `Q4_K_M`.
+fn gguf_quantization(name: &str) -> Option<String> {
+    let file = name.rsplit(['/', '\\']).next()?;
+    let stem = file.strip_suffix(".gguf").unwrap_or(file);
+    let tag = stem.rsplit(['-', '.']).next()?.to_ascii_uppercase();
+    if matches!(tag.as_str(), "F16" | "BF16" | "F32") {
+        return Some(tag);
+    }
+    let rest = tag.strip_prefix("IQ").or_else(|| tag.strip_prefix('Q'))?;
+    rest.starts_with(|ch: char| ch.is_ascii_digit())
+        .then_some(tag.clone())
+}
+
+fn format_parameter_count(count: u64) -> String {
+    if count >= 1_000_000_000 {
+        format!("{:.1}B", count as f64 / 1e9)
+    } else {
+        format!("{}M", count / 1_000_000)
+    }
+}
+
+/// `4.7 GB`; local models are sized in decimal units like their download pages.
+pub(crate) fn format_model_size(bytes: u64) -> String {
+    if bytes >= 1_000_000_000 {
+        format!("{:.1} GB", bytes as f64 / 1e9)
+    } else {
+        format!("{} MB", bytes / 1_000_000)
+    }
+}
+
+fn fetch_json(url: &str) -> Option<Value> {
+    let output = Command::new("curl")
+        .args([
+            "-sSf",
+            "--max-time",
+            &PROBE_TIMEOUT_SECS.to_string(),
+            "-H",
+            "Accept: application/json",
+            url,
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    serde_json::from_slice(&output.stdout).ok()
+}
+
+fn probe_local_servers() -> Vec<LocalServer> {
+    let mut servers: Vec<LocalServer> = Vec::new();
+    for (kind, port) in LOCAL_SERVER_PROBES {
+        let base_url = format!("http://127.0.0.1:{port}");
+        if servers.iter().any(|server| server.base_url == base_url) {
+            continue;
+        }
+        let Some(value) = fetch_json(&format!("{base_url}{}", kind.models_path())) else {
+            continue;
+        };
+        let models = match kind {
+            LocalServerKind::Ollama => parse_ollama_models(&value),
+            LocalServerKind::LlamaCpp => parse_openai_compatible_models(&value),
+        };
+        servers.push(LocalServer {
+            kind: *kind,
+            base_url,
+            models,
+        });
+    }
+    servers
+}
+
+/// One background probe at a time; the run loop polls it like the update check.
+#[derive(Default)]
+pub(crate) struct LocalModelProbeState {
+    receiver: Option<Receiver<Vec<LocalServer>>>,
+}
+
+impl LocalModelProbeState {
+    /// False when a probe is already running.
+    pub(crate) fn spawn(&mut self) -> bool {
+        if self.receiver.is_some() {
+            return false;
+        }
+        let (sender, receiver) = mpsc::channel();
+        self.receiver = Some(receiver);
+        std::thread::spawn(move || {
+            let _ = sender.send(probe_local_servers());
+        });
+        true
+    }
+
+    pub(crate) fn poll(&mut self) -> Option<Vec<LocalServer>> {
+        let result = self.receiver.as_ref()?.try_recv().ok()?;
+        self.receiver = None;
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        format_model_size, gguf_quantization, parse_ollama_models, parse_openai_compatible_models,
+        LocalModel,
+    };
+    use serde_json::json;
+
+    #[test]
+    fn parses_ollama_and_llama_cpp_model_lists() {
+        let tags = json!({"models": [{
+            "name": "llama3.1:8b",
+            "size": 4_920_753_328u64,
+            "details": {"parameter_size": "8.0B", "quantization_level": "Q4_K_M"}
+        }]});
+        assert_eq!(
+            parse_ollama_models(&tags),
+            vec![LocalModel {
+                name: "llama3.1:8b".to_string(),
+                size_bytes: Some(4_920_753_328),
+                quantization: Some("Q4_K_M".to_string()),
+                parameters: Some("8.0B".to_string()),
+            }]
+        );
+        assert_eq!(format_model_size(4_920_753_328), "4.9 GB");
+
+        let models = json!({"data": [{
+            "id": "models/qwen2.5-7b-instruct-Q5_K_M.gguf",
+            "meta": {"n_params": 7_615_616_512u64, "size": 5_444_000_000u64}
+        }]});
+        let parsed = parse_openai_compatible_models(&models);
+        assert_eq!(parsed[0].quantization.as_deref(), Some("Q5_K_M"));
+        assert_eq!(parsed[0].parameters.as_deref(), Some("7.6B"));
+        assert_eq!(gguf_quantization("mistral-7b.gguf"), None);
+    }
+}
@@ -0,0 +1,403 @@
+use crate::app::state::{turn_anchor, TurnStart};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use serde_json::json;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Pauses longer than this are shortened on replay (asciinema `idle_time_limit`).
+const CAST_IDLE_TIME_LIMIT_SECS: f64 = 2.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum LogExportFormat {
+    /// Raw text with SGR colors, for `cat`/`less -R`.
+    Ansi,
+    /// asciinema v2 recording replaying the log with its original pacing.
+    Cast,
+    /// Plain text per turn under `turn-<n>` anchors, for pasting into docs or issues.
+    Markdown,
+    /// Standalone page with theme colors and linkable `#turn-<n>` sections.
+    Html,
+}
+
+impl LogExportFormat {
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        match value {
+            "ansi" => Some(Self::Ansi),
+            "cast" | "asciinema" => Some(Self::Cast),
+            "md" | "markdown" => Some(Self::Markdown),
+            "html" => Some(Self::Html),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn extension(self) -> &'static str {
+        match self {
+            Self::Ansi => "ansi",
+            Self::Cast => "cast",
+            Self::Markdown => "md",
+            Self::Html => "html",
+        }
+    }
+}
+
+fn color_sgr(color: Color, background: bool) -> String {
+    let base = if background { 40 } else { 30 };
+    let bright = if background { 100 } else { 90 };
+    match color {
+        Color::Reset => (base + 9).to_string(),
+        Color::Black => base.to_string(),
+        Color::Red => (base + 1).to_string(),
+        Color::Green => (base + 2).to_string(),
+        Color::Yellow => (base + 3).to_string(),
+        Color::Blue => (base + 4).to_string(),
+        Color::Magenta => (base + 5).to_string(),
+        Color::Cyan => (base + 6).to_string(),
+        Color::Gray => (base + 7).to_string(),
+        Color::DarkGray => bright.to_string(),
+        Color::LightRed => (bright + 1).to_string(),
+        Color::LightGreen => (bright + 2).to_string(),
+        Color::LightYellow => (bright + 3).to_string(),
+        Color::LightBlue => (bright + 4).to_string(),
+        Color::LightMagenta => (bright + 5).to_string(),
+        Color::LightCyan => (bright + 6).to_string(),
+        Color::White => (bright + 7).to_string(),
+        Color::Indexed(index) => format!("{};5;{index}", base + 8),
+        Color::Rgb(r, g, b) => format!("{};2;{r};{g};{b}", base + 8),
+    }
+}
+
+fn style_sgr(style: Style) -> Option<String> {
+    let mut params = Vec::new();
+    let modifiers = [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::REVERSED, "7"),
+        (Modifier::CROSSED_OUT, "9"),
+    ];
+    for (modifier, code) in modifiers {
+        if style.add_modifier.contains(modifier) {
+            params.push(code.to_string());
+        }
+    }
+    if let Some(fg) = style.fg {
+        params.push(color_sgr(fg, false));
+    }
+    if let Some(bg) = style.bg {
+        params.push(color_sgr(bg, true));
+    }
+    if params.is_empty() {
+        return None;
+    }
+    Some(format!("\x1b[{}m", params.join(";")))
+}
+
+/// One styled log line as text with SGR escapes; styles never leak past the line.
+pub(crate) fn line_to_ansi(line: &Line<'_>) -> String {
+    let mut out = String::new();
+    for span in &line.spans {
+        match style_sgr(line.style.patch(span.style)) {
+            Some(sgr) => {
+                out.push_str(&sgr);
+                out.push_str(&span.content);
+                out.push_str("\x1b[0m");
+            }
+            None => out.push_str(&span.content),
+        }
+    }
+    out
+}
+
+pub(crate) fn lines_to_ansi(lines: &[Line<'_>]) -> String {
+    let mut out = String::new();
+    for line in lines {
+        out.push_str(&line_to_ansi(line));
+        out.push('\n');
+    }
+    out
+}
+
+/// asciinema v2 cast: a JSON header line, then one `[seconds, "o", data]` event per
+/// batch of lines that appeared together. `offsets[i]` is when `lines[i]` appeared.
+pub(crate) fn lines_to_cast(
+    lines: &[Line<'_>],
+    offsets: &[Duration],
+    size: (u16, u16),
+    started_unix_secs: u64,
+    title: &str,
+) -> String {
+    let header = json!({
+        "version": 2,
+        "width": size.0,
+        "height": size.1,
+        "timestamp": started_unix_secs,
+        "idle_time_limit": CAST_IDLE_TIME_LIMIT_SECS,
+        "title": title,
+        "env": {
+            "TERM": std::env::var("TERM").unwrap_or_else(|_| "xterm-256color".to_string()),
+        },
+    });
+    let mut out = format!("{header}\n");
+    let mut pending: Option<(Duration, String)> = None;
+    for (index, line) in lines.iter().enumerate() {
+        let at = offsets.get(index).copied().unwrap_or_default();
+        let text = format!("{}\r\n", line_to_ansi(line));
+        match pending.as_mut() {
+            Some((pending_at, data)) if *pending_at == at => data.push_str(&text),
+            _ => {
+                if let Some((pending_at, data)) = pending.take() {
+                    out.push_str(&cast_event(pending_at, &data));
+                }
+                pending = Some((at, text));
+            }
+        }
+    }
+    if let Some((at, data)) = pending {
+        out.push_str(&cast_event(at, &data));
+    }
+    out
+}
+
+fn cast_event(at: Duration, data: &str) -> String {
+    format!("{}\n", json!([at.as_secs_f64(), "o", data]))
+}
+
+/// Splits `0..len` at each turn start; the part before the first turn has no turn number.
+fn turn_sections(len: usize, turn_starts: &[TurnStart]) -> Vec<(Option<usize>, Range<usize>)> {
+    let mut sections = Vec::new();
+    let first = turn_starts
+        .first()
+        .map_or(len, |start| start.index)
+        .min(len);
+    if first > 0 {
+        sections.push((None, 0..first));
+    }
+    for (position, start) in turn_starts.iter().enumerate() {
+        let end = turn_starts
+            .get(position + 1)
+            .map_or(len, |next| next.index)
+            .min(len);
+        if start.index < end {
+            sections.push((Some(start.turn), start.index..end));
+        }
+    }
+    sections
+}
+
+/// Markdown with an `<a id="turn-<n>">` anchor per turn. Rows go in indented code blocks so
+/// backticks and `#` in the log never turn into markup.
+pub(crate) fn lines_to_markdown(
+    texts: &[String],
+    turn_starts: &[TurnStart],
+    title: &str,
+) -> String {
+    let mut out = format!("# {title}\n");
+    for (turn, range) in turn_sections(texts.len(), turn_starts) {
+        out.push('\n');
+        if let Some(turn) = turn {
+            let anchor = turn_anchor(turn);
+            out.push_str(&format!(
+                "<a id=\"{anchor}\"></a>\n\n## [Turn {turn}](#{anchor})\n\n"
+            ));
+        }
+        for text in &texts[range] {
+            if text.trim().is_empty() {
+                out.push('\n');
+            } else {
+                out.push_str(&format!("    {text}\n"));
+            }
+        }
+    }
+    out
+}
+
+fn html_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// CSS for the colors a theme produces; indexed colors fall back to the page default.
+fn color_css(color: Color) -> Option<String> {
+    let name = match color {
+        Color::Rgb(r, g, b) => return Some(format!("#{r:02x}{g:02x}{b:02x}")),
+        Color::Reset | Color::Indexed(_) => return None,
+        Color::Black => "black",
+        Color::Red | Color::LightRed => "red",
+        Color::Green | Color::LightGreen => "green",
+        Color::Yellow | Color::LightYellow => "olive",
+        Color::Blue | Color::LightBlue => "blue",
+        Color::Magenta | Color::LightMagenta => "purple",
+        Color::Cyan | Color::LightCyan => "teal",
+        Color::Gray => "silver",
+        Color::DarkGray => "gray",
+        Color::White => "white",
+    };
+    Some(name.to_string())
+}
+
+fn line_to_html(line: &Line<'_>) -> String {
+    let mut out = String::new();
+    for span in &line.spans {
+        let style = line.style.patch(span.style);
+        let mut css = Vec::new();
+        if let Some(fg) = style.fg.and_then(color_css) {
+            css.push(format!("color:{fg}"));
+        }
+        if let Some(bg) = style.bg.and_then(color_css) {
+            css.push(format!("background:{bg}"));
+        }
+        if style.add_modifier.contains(Modifier::BOLD) {
+            css.push("font-weight:bold".to_string());
+        }
+        if style.add_modifier.contains(Modifier::ITALIC) {
+            css.push("font-style:italic".to_string());
+        }
+        let text = html_escape(&span.content);
+        if css.is_empty() {
+            out.push_str(&text);
+        } else {
+            out.push_str(&format!("<span style=\"{}\">{text}</span>", css.join(";")));
+        }
+    }
+    out
+}
+
+/// Standalone HTML page; each turn is a `<section id="turn-<n>">` with a self-link heading.
+pub(crate) fn lines_to_html(lines: &[Line<'_>], turn_starts: &[TurnStart], title: &str) -> String {
+    let title = html_escape(title);
+    let mut out = format!(
+        "<!doctype html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+         <style>body{{background:#1e1e1e;color:#d4d4d4;font-family:sans-serif}}\
+         pre{{font-family:monospace;white-space:pre-wrap}}h2 a{{color:inherit}}</style>\n\
+         </head>\n<body>\n<h1>{title}</h1>\n"
+    );
+    for (turn, range) in turn_sections(lines.len(), turn_starts) {
+        match turn {
+            Some(turn) => {
+                let anchor = turn_anchor(turn);
+                out.push_str(&format!(
+                    "<section id=\"{anchor}\">\n<h2><a href=\"#{anchor}\">Turn {turn}</a></h2>\n"
+                ));
+            }
+            None => out.push_str("<section>\n"),
+        }
+        out.push_str("<pre>");
+        for line in &lines[range] {
+            out.push_str(&line_to_html(line));
+            out.push('\n');
+        }
+        out.push_str("</pre>\n</section>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+pub(crate) fn default_log_export_path(format: LogExportFormat) -> PathBuf {
+    let created_at_unix_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|value| value.as_millis())
+        .unwrap_or(0);
+    PathBuf::from(format!(
+        "codelia-log-{created_at_unix_ms}.{}",
+        format.extension()
+    ))
+}
+
+pub(crate) fn write_log_export(path: &Path, contents: &str) -> Result<(), String> {
+    std::fs::write(path, contents).map_err(|error| format!("{}: {error}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lines_to_ansi, lines_to_cast, lines_to_html, lines_to_markdown, TurnStart};
+    use ratatui::style::{Color, Modifier, Style};
+    use ratatui::text::{Line, Span};
+    use serde_json::Value;
+    use std::time::Duration;
+
+    #[test]
+    fn ansi_and_cast_exports_keep_styles_and_timing() {
+        let lines = vec![
+            Line::from(vec![
+                Span::styled("> ", Style::default().fg(Color::Rgb(1, 2, 3))),
+                Span::raw("hello"),
+            ]),
+            Line::from(Span::styled(
+                "error",
+                Style::default()
+                    .fg(Color::Red)
+                    .bg(Color::Indexed(236))
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from("done"),
+        ];
+        assert_eq!(
+            lines_to_ansi(&lines),
+            "\x1b[38;2;1;2;3m> \x1b[0mhello\n\x1b[1;31;48;5;236merror\x1b[0m\ndone\n"
+        );
+
+        let offsets = [
+            Duration::from_millis(0),
+            Duration::from_millis(0),
+            Duration::from_millis(1500),
+        ];
+        let cast = lines_to_cast(&lines, &offsets, (80, 24), 1_700_000_000, "codelia");
+        let rows = cast.lines().collect::<Vec<_>>();
+        assert_eq!(rows.len(), 3);
+        let header: Value = serde_json::from_str(rows[0]).expect("header");
+        assert_eq!(header["version"], 2);
+        assert_eq!(header["width"], 80);
+        assert_eq!(header["timestamp"], 1_700_000_000);
+        let first: Value = serde_json::from_str(rows[1]).expect("event");
+        assert_eq!(first[0], 0.0);
+        assert_eq!(first[1], "o");
+        assert!(first[2]
+            .as_str()
+            .unwrap_or_default()
+            .ends_with("error\x1b[0m\r\n"));
+        let second: Value = serde_json::from_str(rows[2]).expect("event");
+        assert_eq!(second[0], 1.5);
+        assert_eq!(second[2], "done\r\n");
+    }
+
+    #[test]
+    fn markdown_and_html_exports_anchor_each_turn() {
+        let texts = ["codelia", "> hi", "hello <b>", "> again", "done"]
+            .map(str::to_string)
+            .to_vec();
+        let starts = [
+            TurnStart { turn: 1, index: 1 },
+            TurnStart { turn: 2, index: 3 },
+        ];
+        let markdown = lines_to_markdown(&texts, &starts, "codelia session s1");
+        assert_eq!(
+            markdown,
+            "# codelia session s1\n\n    codelia\n\n<a id=\"turn-1\"></a>\n\n## [Turn 1](#turn-1)\n\n    > hi\n    hello <b>\n\n<a id=\"turn-2\"></a>\n\n## [Turn 2](#turn-2)\n\n    > again\n    done\n"
+        );
+
+        let lines = vec![
+            Line::from("> hi"),
+            Line::from(Span::styled(
+                "a<b",
+                Style::default().fg(Color::Rgb(255, 0, 16)),
+            )),
+        ];
+        // A log trimmed from the front keeps the turn's own number.
+        let html = lines_to_html(&lines, &[TurnStart { turn: 7, index: 0 }], "s1");
+        assert!(html.contains(
+            "<section id=\"turn-7\">\n<h2><a href=\"#turn-7\">Turn 7</a></h2>\n<pre>&gt; hi\n<span style=\"color:#ff0010\">a&lt;b</span>\n</pre>"
+        ));
+    }
+}
@@ -0,0 +1,306 @@
+use similar::{capture_diff_slices, Algorithm, DiffTag};
+use std::ops::Range;
+
+/// A located region may span at most this many lines per base line (plus slack) before it
+/// is treated as a false anchor match.
+const MAX_REGION_GROWTH: usize = 4;
+const REGION_SLACK_LINES: usize = 20;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum MergeHunkKind {
+    /// Only the agent changed these lines.
+    Agent,
+    /// Only the user changed these lines.
+    User,
+    /// Both made the same change.
+    Same,
+    /// Both changed the lines differently; needs a choice.
+    Conflict,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum MergeChoice {
+    Base,
+    Agent,
+    User,
+    /// Agent lines followed by user lines.
+    Both,
+}
+
+impl MergeChoice {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Base => "base",
+            Self::Agent => "agent",
+            Self::User => "user",
+            Self::Both => "both",
+        }
+    }
+}
+
+/// Lines keep their `\n`, so joining them gives back the exact text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct MergeHunk {
+    pub kind: MergeHunkKind,
+    /// 0-based line of the hunk in the base text.
+    pub base_line: usize,
+    pub base: Vec<String>,
+    pub agent: Vec<String>,
+    pub user: Vec<String>,
+    /// Preset for one-sided hunks; `None` until a conflict is decided.
+    pub choice: Option<MergeChoice>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum MergeSegment {
+    Stable(Vec<String>),
+    Hunk(usize),
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct ThreeWayMerge {
+    pub segments: Vec<MergeSegment>,
+    pub hunks: Vec<MergeHunk>,
+}
+
+struct Change {
+    base: Range<usize>,
+    side: Range<usize>,
+}
+
+fn split_lines(text: &str) -> Vec<&str> {
+    text.split_inclusive('\n').collect()
+}
+
+fn changes(base: &[&str], side: &[&str]) -> Vec<Change> {
+    capture_diff_slices(Algorithm::Myers, base, side)
+        .iter()
+        .map(|op| op.as_tag_tuple())
+        .filter(|(tag, _, _)| *tag != DiffTag::Equal)
+        .map(|(_, base, side)| Change { base, side })
+        .collect()
+}
+
+fn owned(lines: &[&str]) -> Vec<String> {
+    lines.iter().map(|line| line.to_string()).collect()
+}
+
+/// Lines of one side over the base region `region`, given that side's changes inside it.
+fn side_lines(
+    base: &[&str],
+    side: &[&str],
+    region: &Range<usize>,
+    changes: &[Change],
+) -> Vec<String> {
+    let (Some(first), Some(last)) = (changes.first(), changes.last()) else {
+        return owned(&base[region.clone()]);
+    };
+    let start = first.side.start - (first.base.start - region.start);
+    let end = last.side.end + (region.end - last.base.end);
+    owned(&side[start..end])
+}
+
+/// diff3-style merge of `agent` and `user`, both derived from `base`. Changes of the two
+/// sides that overlap or touch form one hunk.
+pub(crate) fn three_way_merge(base: &str, agent: &str, user: &str) -> ThreeWayMerge {
+    let (base, agent, user) = (split_lines(base), split_lines(agent), split_lines(user));
+    let (agent_changes, user_changes) = (changes(&base, &agent), changes(&base, &user));
+    let (mut next_agent, mut next_user, mut pos) = (0, 0, 0);
+    let mut merge = ThreeWayMerge::default();
+    loop {
+        let start = match (agent_changes.get(next_agent), user_changes.get(next_user)) {
+            (Some(a), Some(u)) => a.base.start.min(u.base.start),
+            (Some(a), None) => a.base.start,
+            (None, Some(u)) => u.base.start,
+            (None, None) => break,
+        };
+        let (agent_from, user_from) = (next_agent, next_user);
+        let mut end = start;
+        loop {
+            if let Some(change) = agent_changes
+                .get(next_agent)
+                .filter(|change| change.base.start <= end)
+            {
+                end = end.max(change.base.end);
+                next_agent += 1;
+            } else if let Some(change) = user_changes
+                .get(next_user)
+                .filter(|change| change.base.start <= end)
+            {
+                end = end.max(change.base.end);
+                next_user += 1;
+            } else {
+                break;
+            }
+        }
+        if pos < start {
+            merge
+                .segments
+                .push(MergeSegment::Stable(owned(&base[pos..start])));
+        }
+        let region = start..end;
+        let agent_side = side_lines(
+            &base,
+            &agent,
+            &region,
+            &agent_changes[agent_from..next_agent],
+        );
+        let user_side = side_lines(&base, &user, &region, &user_changes[user_from..next_user]);
+        let (kind, choice) = match (next_agent > agent_from, next_user > user_from) {
+            (true, false) => (MergeHunkKind::Agent, Some(MergeChoice::Agent)),
+            (false, _) => (MergeHunkKind::User, Some(MergeChoice::User)),
+            (true, true) if agent_side == user_side => {
+                (MergeHunkKind::Same, Some(MergeChoice::Agent))
+            }
+            (true, true) => (MergeHunkKind::Conflict, None),
+        };
+        merge.segments.push(MergeSegment::Hunk(merge.hunks.len()));
+        merge.hunks.push(MergeHunk {
+            kind,
+            base_line: start,
+            base: owned(&base[region]),
+            agent: agent_side,
+            user: user_side,
+            choice,
+        });
+        pos = end;
+    }
+    if pos < base.len() {
+        merge
+            .segments
+            .push(MergeSegment::Stable(owned(&base[pos..])));
+    }
+    merge
+}
+
+impl ThreeWayMerge {
+    pub(crate) fn undecided(&self) -> usize {
+        self.hunks
+            .iter()
+            .filter(|hunk| hunk.choice.is_none())
+            .count()
+    }
+
+    /// Merged text, or `None` while a conflict is undecided.
+    pub(crate) fn merged_text(&self) -> Option<String> {
+        let mut out = String::new();
+        for segment in &self.segments {
+            match segment {
+                MergeSegment::Stable(lines) => out.extend(lines.iter().map(String::as_str)),
+                MergeSegment::Hunk(index) => {
+                    let hunk = &self.hunks[*index];
+                    let parts: &[&Vec<String>] = match hunk.choice? {
+                        MergeChoice::Base => &[&hunk.base],
+                        MergeChoice::Agent => &[&hunk.agent],
+                        MergeChoice::User => &[&hunk.user],
+                        MergeChoice::Both => &[&hunk.agent, &hunk.user],
+                    };
+                    for lines in parts {
+                        out.extend(lines.iter().map(String::as_str));
+                    }
+                }
+            }
+        }
+        Some(out)
+    }
+}
+
+/// Byte range in `current` that holds what used to be `base`: the exact text when it is
+/// still there, else the lines from the first to the last non-blank line of `base`, matched
+/// ignoring surrounding whitespace.
+pub(crate) fn locate_edited_region(current: &str, base: &str) -> Option<Range<usize>> {
+    if base.is_empty() {
+        return None;
+    }
+    if let Some(start) = current.find(base) {
+        return Some(start..start + base.len());
+    }
+    let anchors = base
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+    let (first, last) = (*anchors.first()?, *anchors.last()?);
+    if anchors.len() < 2 {
+        return None;
+    }
+    let mut offsets = Vec::new();
+    let mut at = 0;
+    for line in current.split_inclusive('\n') {
+        offsets.push((at, line));
+        at += line.len();
+    }
+    let begin = offsets.iter().position(|(_, line)| line.trim() == first)?;
+    let end = begin
+        + 1
+        + offsets[begin + 1..]
+            .iter()
+            .position(|(_, line)| line.trim() == last)?;
+    if end - begin > base.lines().count() * MAX_REGION_GROWTH + REGION_SLACK_LINES {
+        return None;
+    }
+    let (end_at, end_line) = offsets[end];
+    let mut end_byte = end_at + end_line.len();
+    if !base.ends_with('\n') && end_line.ends_with('\n') {
+        end_byte -= 1;
+    }
+    Some(offsets[begin].0..end_byte)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{locate_edited_region, three_way_merge, MergeChoice, MergeHunkKind};
+
+    #[test]
+    fn one_sided_changes_merge_and_overlaps_need_a_choice() {
+        let base = "a\nb\nc\nd\ne\n";
+        let agent = "a\nB\nc\nd\nE\n";
+        let user = "A\nb\nc\nd\nuser e\n";
+        let mut merge = three_way_merge(base, agent, user);
+        let kinds = merge.hunks.iter().map(|hunk| hunk.kind).collect::<Vec<_>>();
+        // Line 1 (user) touches line 2 (agent), so they form one conflict.
+        assert_eq!(
+            kinds,
+            vec![MergeHunkKind::Conflict, MergeHunkKind::Conflict]
+        );
+        assert_eq!(merge.hunks[0].agent, vec!["a\n", "B\n"]);
+        assert_eq!(merge.hunks[0].user, vec!["A\n", "b\n"]);
+        assert_eq!(merge.undecided(), 2);
+        assert_eq!(merge.merged_text(), None);
+
+        merge.hunks[0].choice = Some(MergeChoice::User);
+        merge.hunks[1].choice = Some(MergeChoice::Both);
+        assert_eq!(
+            merge.merged_text().as_deref(),
+            Some("A\nb\nc\nd\nE\nuser e\n")
+        );
+
+        let merge = three_way_merge(base, "a\nB\nc\nd\ne\n", "a\nb\nc\nd\nE\n");
+        assert_eq!(merge.hunks.len(), 2);
+        assert_eq!(merge.hunks[0].kind, MergeHunkKind::Agent);
+        assert_eq!(merge.hunks[1].kind, MergeHunkKind::User);
+        assert_eq!(merge.hunks[1].base_line, 4);
+        assert_eq!(merge.merged_text().as_deref(), Some("a\nB\nc\nd\nE\n"));
+
+        let merge = three_way_merge(base, "a\nX\nc\nd\ne\n", "a\nX\nc\nd\ne\n");
+        assert_eq!(merge.hunks[0].kind, MergeHunkKind::Same);
+        assert_eq!(merge.merged_text().as_deref(), Some("a\nX\nc\nd\ne\n"));
+    }
+
+    #[test]
+    fn edited_region_is_found_exactly_or_by_anchor_lines() {
+        let base = "fn main() {\n    run();\n}";
+        let current = "// header\nfn main() {\n    run();\n}\n";
+        let range = locate_edited_region(current, base).expect("exact");
+        assert_eq!(&current[range], base);
+
+        let current = "// header\nfn main() {\n    setup();\n    run(true);\n}\n// tail\n";
+        let range = locate_edited_region(current, base).expect("anchored");
+        assert_eq!(
+            &current[range],
+            "fn main() {\n    setup();\n    run(true);\n}"
+        );
+        assert_eq!(locate_edited_region("fn other() {}\n", base), None);
+        assert_eq!(locate_edited_region(current, "single line"), None);
+    }
+}
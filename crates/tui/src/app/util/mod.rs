@@ -1,7 +1,30 @@
 pub(crate) mod attachments;
+pub(crate) mod audit;
+pub(crate) mod bundle;
 pub(crate) mod clipboard;
+pub(crate) mod command_allowlist;
+pub(crate) mod dir_tree;
+pub(crate) mod draft;
+pub(crate) mod editor_bridge;
+pub(crate) mod external_editor;
+pub(crate) mod file_mentions;
+pub(crate) mod forge;
+pub(crate) mod input_log;
+pub(crate) mod json_output;
+pub(crate) mod local_models;
+pub(crate) mod log_export;
+pub(crate) mod merge3;
+pub(crate) mod notify;
+pub(crate) mod open;
+pub(crate) mod paste_sample;
 pub(crate) mod perf;
+pub(crate) mod run_tee;
+pub(crate) mod secrets;
+pub(crate) mod settings;
+pub(crate) mod stack_trace;
 pub(crate) mod text;
+pub(crate) mod translate;
+pub(crate) mod update;
 
 pub(crate) use attachments::make_attachment_token;
 pub(crate) use clipboard::{read_clipboard_image_attachment, ClipboardImageError};
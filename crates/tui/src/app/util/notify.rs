@@ -0,0 +1,96 @@
+use crate::app::util::settings::TuiSettings;
+use std::io;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// Terminal focus from focus-change reports; terminals that never report stay "focused".
+static TERMINAL_FOCUSED: AtomicBool = AtomicBool::new(true);
+
+pub(crate) fn set_terminal_focused(focused: bool) {
+    TERMINAL_FOCUSED.store(focused, Ordering::Relaxed);
+}
+
+pub(crate) fn terminal_focused() -> bool {
+    TERMINAL_FOCUSED.load(Ordering::Relaxed)
+}
+
+/// A finished run is worth a notification when the terminal is in the background, or when it
+/// ran at least `notify_threshold` seconds (0 disables the duration rule).
+pub(crate) fn should_notify_run_finished(
+    settings: &TuiSettings,
+    focused: bool,
+    duration: Option<Duration>,
+) -> bool {
+    if !settings.notifications {
+        return false;
+    }
+    let long_run = settings.notify_threshold > 0
+        && duration.is_some_and(|duration| duration.as_secs() >= settings.notify_threshold);
+    !focused || long_run
+}
+
+/// AppleScript string literal: quotes and backslashes escaped.
+fn applescript_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn notification_command(title: &str, body: &str) -> Option<Command> {
+    if cfg!(target_os = "macos") {
+        let mut command = Command::new("osascript");
+        command.arg("-e").arg(format!(
+            "display notification {} with title {}",
+            applescript_string(body),
+            applescript_string(title)
+        ));
+        Some(command)
+    } else if cfg!(target_os = "windows") {
+        None
+    } else {
+        let mut command = Command::new("notify-send");
+        command.args(["--app-name=codelia", title, body]);
+        Some(command)
+    }
+}
+
+/// Starts `notify-send`/`osascript` without waiting; an error means the caller should fall
+/// back to the terminal bell.
+pub(crate) fn send_desktop_notification(title: &str, body: &str) -> io::Result<()> {
+    let mut command = notification_command(title, body)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::Unsupported, "no notifier"))?;
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{applescript_string, should_notify_run_finished};
+    use crate::app::util::settings::TuiSettings;
+    use std::time::Duration;
+
+    #[test]
+    fn notifies_when_unfocused_or_after_the_threshold() {
+        let mut settings = TuiSettings::default();
+        let short = Some(Duration::from_secs(5));
+        let long = Some(Duration::from_secs(45));
+        assert!(!should_notify_run_finished(&settings, false, long));
+
+        settings.notifications = true;
+        settings.notify_threshold = 30;
+        assert!(should_notify_run_finished(&settings, false, short));
+        assert!(!should_notify_run_finished(&settings, true, short));
+        assert!(should_notify_run_finished(&settings, true, long));
+        assert!(!should_notify_run_finished(&settings, true, None));
+
+        settings.notify_threshold = 0;
+        assert!(!should_notify_run_finished(&settings, true, long));
+        assert_eq!(applescript_string("say \"hi\""), "\"say \\\"hi\\\"\"");
+    }
+}
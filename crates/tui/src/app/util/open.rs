@@ -0,0 +1,33 @@
+use crate::app::util::clipboard::is_wsl_environment;
+use std::io;
+use std::process::{Command, Stdio};
+
+/// Platform opener for URLs; WSL hands off to the Windows browser when `wslview` exists.
+fn opener_command(url: &str) -> Command {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else if is_wsl_environment() {
+        Command::new("wslview")
+    } else {
+        Command::new("xdg-open")
+    };
+    command.arg(url);
+    command
+}
+
+/// Starts the browser without waiting for it; the child is reaped on a background thread.
+pub(crate) fn open_url(url: &str) -> io::Result<()> {
+    let mut child = opener_command(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(())
+}
@@ -0,0 +1,114 @@
+/// Pastes longer than this many lines get the sampling offer.
+pub(crate) const LONG_PASTE_LINES: usize = 300;
+/// Error lines kept from the omitted middle of a sampled paste.
+const MAX_SAMPLED_ERROR_LINES: usize = 40;
+const ERROR_MARKERS: &[&str] = &[
+    "error",
+    "fail",
+    "panic",
+    "exception",
+    "traceback",
+    "fatal",
+    "denied",
+    "not found",
+];
+
+/// How much of a long paste to keep at each end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PasteSampleSize {
+    pub head: usize,
+    pub tail: usize,
+}
+
+pub(crate) const COMPACT_PASTE_SAMPLE: PasteSampleSize = PasteSampleSize { head: 40, tail: 40 };
+pub(crate) const WIDE_PASTE_SAMPLE: PasteSampleSize = PasteSampleSize {
+    head: 120,
+    tail: 120,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PasteSample {
+    pub text: String,
+    pub kept_errors: usize,
+    pub omitted: usize,
+}
+
+fn looks_like_error_line(line: &str) -> bool {
+    let lower = line.to_ascii_lowercase();
+    ERROR_MARKERS.iter().any(|marker| lower.contains(marker))
+}
+
+fn omitted_marker(count: usize) -> String {
+    let plural = if count == 1 { "" } else { "s" };
+    format!("[… {count} line{plural} omitted …]")
+}
+
+/// Head + tail of `text`, plus the error-looking lines of the middle (first ones first, up to
+/// a cap), with a marker counting each omitted run. `None` when nothing would be dropped.
+pub(crate) fn sample_long_paste(text: &str, size: PasteSampleSize) -> Option<PasteSample> {
+    let lines = text.lines().collect::<Vec<_>>();
+    if lines.len() <= size.head + size.tail {
+        return None;
+    }
+    let middle = size.head..lines.len() - size.tail;
+    let errors = middle
+        .clone()
+        .filter(|index| looks_like_error_line(lines[*index]))
+        .take(MAX_SAMPLED_ERROR_LINES)
+        .collect::<Vec<_>>();
+
+    let mut out = lines[..size.head]
+        .iter()
+        .map(|line| line.to_string())
+        .collect::<Vec<_>>();
+    let mut cursor = middle.start;
+    for index in errors.iter().copied().chain(std::iter::once(middle.end)) {
+        if index > cursor {
+            out.push(omitted_marker(index - cursor));
+        }
+        if index < middle.end {
+            out.push(lines[index].to_string());
+        }
+        cursor = index + 1;
+    }
+    out.extend(lines[middle.end..].iter().map(|line| line.to_string()));
+    Some(PasteSample {
+        text: out.join("\n"),
+        kept_errors: errors.len(),
+        omitted: middle.len() - errors.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sample_long_paste, PasteSampleSize};
+
+    #[test]
+    fn keeps_head_tail_and_error_lines_with_omitted_counts() {
+        let mut lines = (1..=20).map(|n| format!("line {n}")).collect::<Vec<_>>();
+        lines[8] = "ERROR: disk full".to_string();
+        lines[9] = "test foo ... FAILED".to_string();
+        let text = lines.join("\n");
+        let size = PasteSampleSize { head: 3, tail: 2 };
+
+        let sample = sample_long_paste(&text, size).expect("sample");
+        assert_eq!(
+            sample.text.lines().collect::<Vec<_>>(),
+            vec![
+                "line 1",
+                "line 2",
+                "line 3",
+                "[… 5 lines omitted …]",
+                "ERROR: disk full",
+                "test foo ... FAILED",
+                "[… 8 lines omitted …]",
+                "line 19",
+                "line 20",
+            ]
+        );
+        assert_eq!(sample.kept_errors, 2);
+        assert_eq!(sample.omitted, 13);
+
+        assert!(sample_long_paste("a\nb\nc", size).is_none());
+    }
+}
@@ -0,0 +1,106 @@
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// `/tee <path>`: plain-text copy of a run's assistant text and tool summaries, appended as
+/// they arrive. Each write is flushed so `tail -f` sees the stream live.
+pub(crate) struct RunTee {
+    path: PathBuf,
+    file: File,
+    bytes: u64,
+    /// A `text_delta` stream is mid-message; the complete message only ends its line.
+    streaming: bool,
+    at_line_start: bool,
+}
+
+impl RunTee {
+    pub(crate) fn open(path: &Path) -> Result<Self, String> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|error| format!("{}: {error}", path.display()))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            bytes: 0,
+            streaming: false,
+            at_line_start: true,
+        })
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub(crate) fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    fn write(&mut self, text: &str) -> Result<(), String> {
+        if text.is_empty() {
+            return Ok(());
+        }
+        self.file
+            .write_all(text.as_bytes())
+            .and_then(|()| self.file.flush())
+            .map_err(|error| format!("{}: {error}", self.path.display()))?;
+        self.bytes += text.len() as u64;
+        self.at_line_start = text.ends_with('\n');
+        Ok(())
+    }
+
+    pub(crate) fn append_delta(&mut self, delta: &str) -> Result<(), String> {
+        self.streaming = true;
+        self.write(delta)
+    }
+
+    /// A complete assistant message; after streamed deltas only the line is ended.
+    pub(crate) fn append_message(&mut self, text: &str) -> Result<(), String> {
+        if !std::mem::take(&mut self.streaming) {
+            self.write(text)?;
+        }
+        self.end_line()
+    }
+
+    /// One line of its own, such as a tool summary.
+    pub(crate) fn append_line(&mut self, line: &str) -> Result<(), String> {
+        self.end_line()?;
+        self.write(line)?;
+        self.end_line()
+    }
+
+    fn end_line(&mut self) -> Result<(), String> {
+        if self.at_line_start {
+            Ok(())
+        } else {
+            self.write("\n")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RunTee;
+
+    #[test]
+    fn streamed_and_whole_messages_are_not_duplicated() {
+        let path = std::env::temp_dir().join(format!("codelia-tee-{}.txt", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        std::fs::write(&path, "earlier\n").expect("seed");
+
+        let mut tee = RunTee::open(&path).expect("open");
+        tee.append_delta("Building").expect("delta");
+        tee.append_delta("…").expect("delta");
+        tee.append_line("✔ Bash finished").expect("line");
+        tee.append_message("Building…").expect("message");
+        tee.append_message("Done.").expect("message");
+        let written = tee.bytes();
+        drop(tee);
+
+        let contents = std::fs::read_to_string(&path).expect("read");
+        assert_eq!(contents, "earlier\nBuilding…\n✔ Bash finished\nDone.\n");
+        assert_eq!(written as usize, contents.len() - "earlier\n".len());
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,360 @@
+use crate::app::util::settings::config_dir;
+use serde_json::{json, Map, Value};
+use std::borrow::Cow;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+const KEYCHAIN_SERVICE: &str = "codelia-tui";
+const SECRETS_INDEX_FILE: &str = "tui-secrets.json";
+/// Very short values would redact ordinary words; keep them injectable but not redacted.
+const MIN_REDACT_CHARS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SecretSource {
+    Session,
+    Keychain,
+}
+
+impl SecretSource {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Session => "session",
+            Self::Keychain => "keychain",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub(crate) struct SecretEntry {
+    pub name: String,
+    value: String,
+    pub inject: bool,
+    pub source: SecretSource,
+}
+
+impl std::fmt::Debug for SecretEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecretEntry")
+            .field("name", &self.name)
+            .field("value", &"<redacted>")
+            .field("inject", &self.inject)
+            .field("source", &self.source)
+            .finish()
+    }
+}
+
+pub(crate) fn is_valid_secret_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(first) if first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
+/// Named environment values for bang/shell commands. Values never leave this struct except
+/// through `injected_env` (sent to the runtime) and keychain writes.
+#[derive(Debug, Default)]
+pub(crate) struct SecretStore {
+    entries: Vec<SecretEntry>,
+}
+
+impl SecretStore {
+    pub(crate) fn entries(&self) -> &[SecretEntry] {
+        &self.entries
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub(crate) fn set(&mut self, name: &str, value: String, source: SecretSource) {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.name == name) {
+            entry.value = value;
+            entry.source = source;
+            return;
+        }
+        self.entries.push(SecretEntry {
+            name: name.to_string(),
+            value,
+            inject: true,
+            source,
+        });
+        self.entries.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    pub(crate) fn remove(&mut self, name: &str) -> Option<SecretEntry> {
+        let index = self.entries.iter().position(|entry| entry.name == name)?;
+        Some(self.entries.remove(index))
+    }
+
+    pub(crate) fn set_inject(&mut self, name: &str, inject: bool) -> bool {
+        match self.entries.iter_mut().find(|entry| entry.name == name) {
+            Some(entry) => {
+                entry.inject = inject;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn injected_count(&self) -> usize {
+        self.entries.iter().filter(|entry| entry.inject).count()
+    }
+
+    pub(crate) fn injected_env(&self) -> Option<Map<String, Value>> {
+        let env = self
+            .entries
+            .iter()
+            .filter(|entry| entry.inject)
+            .map(|entry| (entry.name.clone(), Value::String(entry.value.clone())))
+            .collect::<Map<_, _>>();
+        (!env.is_empty()).then_some(env)
+    }
+
+    pub(crate) fn redact<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut redacted = Cow::Borrowed(text);
+        for entry in &self.entries {
+            if entry.value.chars().count() < MIN_REDACT_CHARS {
+                continue;
+            }
+            if redacted.contains(entry.value.as_str()) {
+                redacted = Cow::Owned(
+                    redacted.replace(entry.value.as_str(), &format!("[secret:{}]", entry.name)),
+                );
+            }
+        }
+        redacted
+    }
+
+    pub(crate) fn index_json(&self) -> Value {
+        let names = self
+            .entries
+            .iter()
+            .filter(|entry| entry.source == SecretSource::Keychain)
+            .map(|entry| json!({ "name": entry.name, "inject": entry.inject }))
+            .collect::<Vec<_>>();
+        json!({ "version": 1, "secrets": names })
+    }
+}
+
+fn secrets_index_path() -> Option<PathBuf> {
+    Some(config_dir()?.join(SECRETS_INDEX_FILE))
+}
+
+/// The index stores names only; values stay in the OS keychain.
+pub(crate) fn save_secrets_index(store: &SecretStore) -> Result<(), String> {
+    let path = secrets_index_path().ok_or_else(|| "no config directory".to_string())?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|error| error.to_string())?;
+    }
+    let text = serde_json::to_string_pretty(&store.index_json()).map_err(|e| e.to_string())?;
+    std::fs::write(&path, text).map_err(|error| error.to_string())
+}
+
+pub(crate) fn load_secrets_from_keychain() -> (SecretStore, Vec<String>) {
+    let mut store = SecretStore::default();
+    let mut errors = Vec::new();
+    let Some(path) = secrets_index_path() else {
+        return (store, errors);
+    };
+    let Ok(text) = std::fs::read_to_string(path) else {
+        return (store, errors);
+    };
+    let Ok(index) = serde_json::from_str::<Value>(&text) else {
+        errors.push(format!("invalid {SECRETS_INDEX_FILE}"));
+        return (store, errors);
+    };
+    for item in index
+        .get("secrets")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let Some(name) = item.get("name").and_then(Value::as_str) else {
+            continue;
+        };
+        if !is_valid_secret_name(name) {
+            continue;
+        }
+        match keychain_load(name) {
+            Ok(value) => {
+                store.set(name, value, SecretSource::Keychain);
+                let inject = item.get("inject").and_then(Value::as_bool).unwrap_or(true);
+                store.set_inject(name, inject);
+            }
+            Err(error) => errors.push(format!("{name}: {error}")),
+        }
+    }
+    (store, errors)
+}
+
+fn run_keychain_command(mut command: Command, stdin_value: Option<&str>) -> Result<String, String> {
+    let mut child = command
+        .stdin(if stdin_value.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("keychain unavailable: {error}"))?;
+    if let (Some(value), Some(mut stdin)) = (stdin_value, child.stdin.take()) {
+        let _ = stdin.write_all(value.as_bytes());
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|error| error.to_string())?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(if stderr.is_empty() {
+            format!("keychain command exited with {}", output.status)
+        } else {
+            stderr
+        });
+    }
+    let mut value = String::from_utf8_lossy(&output.stdout).to_string();
+    if value.ends_with('\n') {
+        value.pop();
+    }
+    Ok(value)
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) fn keychain_store(name: &str, value: &str) -> Result<(), String> {
+    // A trailing `-w` without a value makes `security` prompt for the password (and its
+    // confirmation) on stdin, so the secret never shows in the process list.
+    let mut command = Command::new("security");
+    command.args([
+        "add-generic-password",
+        "-U",
+        "-s",
+        KEYCHAIN_SERVICE,
+        "-a",
+        name,
+        "-w",
+    ]);
+    run_keychain_command(command, Some(&format!("{value}\n{value}\n"))).map(|_| ())
+}
+
+#[cfg(target_os = "macos")]
+fn keychain_load(name: &str) -> Result<String, String> {
+    let mut command = Command::new("security");
+    command.args([
+        "find-generic-password",
+        "-s",
+        KEYCHAIN_SERVICE,
+        "-a",
+        name,
+        "-w",
+    ]);
+    run_keychain_command(command, None)
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) fn keychain_delete(name: &str) -> Result<(), String> {
+    let mut command = Command::new("security");
+    command.args([
+        "delete-generic-password",
+        "-s",
+        KEYCHAIN_SERVICE,
+        "-a",
+        name,
+    ]);
+    run_keychain_command(command, None).map(|_| ())
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+pub(crate) fn keychain_store(name: &str, value: &str) -> Result<(), String> {
+    let mut command = Command::new("secret-tool");
+    command.args([
+        "store",
+        &format!("--label=codelia {name}"),
+        "service",
+        KEYCHAIN_SERVICE,
+        "name",
+        name,
+    ]);
+    run_keychain_command(command, Some(value)).map(|_| ())
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn keychain_load(name: &str) -> Result<String, String> {
+    let mut command = Command::new("secret-tool");
+    command.args(["lookup", "service", KEYCHAIN_SERVICE, "name", name]);
+    run_keychain_command(command, None)
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+pub(crate) fn keychain_delete(name: &str) -> Result<(), String> {
+    let mut command = Command::new("secret-tool");
+    command.args(["clear", "service", KEYCHAIN_SERVICE, "name", name]);
+    run_keychain_command(command, None).map(|_| ())
+}
+
+#[cfg(not(unix))]
+pub(crate) fn keychain_store(_name: &str, _value: &str) -> Result<(), String> {
+    Err("OS keychain is not supported on this platform".to_string())
+}
+
+#[cfg(not(unix))]
+fn keychain_load(_name: &str) -> Result<String, String> {
+    Err("OS keychain is not supported on this platform".to_string())
+}
+
+#[cfg(not(unix))]
+pub(crate) fn keychain_delete(_name: &str) -> Result<(), String> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_valid_secret_name, SecretSource, SecretStore};
+    use serde_json::json;
+
+    #[test]
+    fn secret_names_follow_env_var_rules() {
+        assert!(is_valid_secret_name("API_TOKEN"));
+        assert!(is_valid_secret_name("_x1"));
+        assert!(!is_valid_secret_name("1TOKEN"));
+        assert!(!is_valid_secret_name("MY-TOKEN"));
+        assert!(!is_valid_secret_name(""));
+    }
+
+    #[test]
+    fn redact_replaces_values_with_names() {
+        let mut store = SecretStore::default();
+        store.set(
+            "API_TOKEN",
+            "s3cr3t-value".to_string(),
+            SecretSource::Session,
+        );
+        store.set("PIN", "12".to_string(), SecretSource::Session);
+        assert_eq!(
+            store.redact("auth=s3cr3t-value 12"),
+            "auth=[secret:API_TOKEN] 12"
+        );
+        assert!(matches!(
+            store.redact("nothing here"),
+            std::borrow::Cow::Borrowed(_)
+        ));
+        assert!(!format!("{:?}", store).contains("s3cr3t"));
+    }
+
+    #[test]
+    fn injected_env_respects_toggle_and_index_omits_values() {
+        let mut store = SecretStore::default();
+        store.set("A", "alpha".to_string(), SecretSource::Keychain);
+        store.set("B", "beta".to_string(), SecretSource::Session);
+        assert!(store.set_inject("B", false));
+        let env = store.injected_env().expect("env");
+        assert_eq!(env.get("A"), Some(&json!("alpha")));
+        assert!(env.get("B").is_none());
+        assert_eq!(store.injected_count(), 1);
+        assert_eq!(
+            store.index_json(),
+            json!({"version": 1, "secrets": [{"name": "A", "inject": true}]})
+        );
+        store.remove("A");
+        assert!(store.injected_env().is_none());
+    }
+}
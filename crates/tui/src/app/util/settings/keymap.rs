@@ -0,0 +1,31 @@
+use crate::app::state::ConfirmAccelerators;
+
+/// Whether a table header is the confirm keymap (`[keys.confirm]` / `[tui.keys.confirm]`).
+pub(super) fn is_confirm_keymap_table(table: &str) -> bool {
+    matches!(table, "keys.confirm" | "tui.keys.confirm")
+}
+
+/// Stores one `allow` / `deny` / `remember` letter of the `[keys.confirm]` table.
+pub(super) fn set_confirm_key(
+    keys: &mut ConfirmAccelerators,
+    key: &str,
+    value: &str,
+) -> Result<(), String> {
+    let mut chars = value.chars();
+    let letter = match (chars.next(), chars.next()) {
+        (Some(ch), None) if ch.is_ascii_alphabetic() => ch.to_ascii_lowercase(),
+        _ => {
+            return Err(format!(
+                "invalid key for keys.confirm.{key}: {value} (expected one letter)"
+            ))
+        }
+    };
+    let slot = match key {
+        "allow" => &mut keys.allow,
+        "deny" => &mut keys.deny,
+        "remember" => &mut keys.remember,
+        _ => return Err(format!("unknown key in keys.confirm: {key}")),
+    };
+    *slot = Some(letter);
+    Ok(())
+}
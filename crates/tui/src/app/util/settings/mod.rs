@@ -0,0 +1,1022 @@
+use crate::app::render::glyphs::GlyphMode;
+use crate::app::render::graphics::ImagePreviewMode;
+use crate::app::state::{ComposerWrap, ConfirmAccelerators, EscPolicy};
+use crate::app::util::text::sanitize_for_tui;
+use crate::app::ErrorDetailMode;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+mod keymap;
+mod spinner;
+mod theme_file;
+mod tool_styles;
+
+pub(crate) use spinner::{render_run_line, RunLineFields, SpinnerStyle, StatusVerbs};
+pub(crate) use theme_file::{
+    custom_themes, find_custom_theme, load_custom_themes, resolve_theme_id, themes_dir,
+    CustomTheme, ThemeColor,
+};
+pub(crate) use tool_styles::{ToolStyle, ToolStyles};
+
+use keymap::{is_confirm_keymap_table, set_confirm_key};
+use spinner::{validate_status_format, DEFAULT_STATUS_FORMAT};
+
+const SETTINGS_FILE: &str = "tui.toml";
+const MOTD_DISMISSED_FILE: &str = "tui-motd-dismissed";
+const LAST_RUN_VERSION_FILE: &str = "tui-last-version";
+const MAX_BANNER_LINES: usize = 24;
+const MAX_FPS_CAP: u16 = 240;
+const MAX_NOTIFY_THRESHOLD_SECS: u64 = 86_400;
+const DEFAULT_NOTIFY_THRESHOLD_SECS: u64 = 30;
+const MAX_STALL_TIMEOUT_SECS: u64 = 3_600;
+const DEFAULT_STALL_TIMEOUT_SECS: u64 = 90;
+const MAX_MEMORY_CAP_MB: u64 = 4_096;
+const DEFAULT_IMAGE_MEMORY_MB: u64 = 32;
+const DEFAULT_SHELL_MEMORY_MB: u64 = 32;
+const DEFAULT_LOG_MEMORY_MB: u64 = 128;
+const SETTINGS_WATCH_INTERVAL: Duration = Duration::from_secs(1);
+const SETTINGS_FILE_HEADER: &str =
+    "# Codelia TUI settings (edit here or use `/set <key> <value>`).\n";
+
+pub(crate) struct SettingSpec {
+    pub key: &'static str,
+    pub values: &'static str,
+    pub summary: &'static str,
+}
+
+pub(crate) const SETTING_SPECS: &[SettingSpec] = &[
+    SettingSpec {
+        key: "theme",
+        values: "<theme>|default",
+        summary: "UI and markdown theme",
+    },
+    SettingSpec {
+        key: "verbosity",
+        values: "summary|detail",
+        summary: "error detail level (same as /errors)",
+    },
+    SettingSpec {
+        key: "mouse_capture",
+        values: "on|off",
+        summary: "capture the mouse at startup (F2 toggles)",
+    },
+    SettingSpec {
+        key: "timestamps",
+        values: "on|off",
+        summary: "prefix prompts with the UTC time",
+    },
+    SettingSpec {
+        key: "fps_cap",
+        values: "0-240",
+        summary: "max redraws per second (0 = uncapped)",
+    },
+    SettingSpec {
+        key: "alt_screen",
+        values: "on|off",
+        summary: "use the alternate screen (applies on restart)",
+    },
+    SettingSpec {
+        key: "notifications",
+        values: "on|off",
+        summary: "desktop notification (bell fallback) when a run finishes unfocused or long",
+    },
+    SettingSpec {
+        key: "notify_threshold",
+        values: "0-86400",
+        summary: "seconds after which a finished run notifies even when focused (0 = never)",
+    },
+    SettingSpec {
+        key: "stall_timeout",
+        values: "0-3600",
+        summary: "warn when a run gets no runtime output for this many seconds (0 = off)",
+    },
+    SettingSpec {
+        key: "image_memory_mb",
+        values: "0-4096",
+        summary: "cap on held image attachments; oldest are dropped first (0 = no cap)",
+    },
+    SettingSpec {
+        key: "shell_memory_mb",
+        values: "0-4096",
+        summary: "cap on held ! shell results; oldest are dropped first (0 = no cap)",
+    },
+    SettingSpec {
+        key: "log_memory_mb",
+        values: "0-4096",
+        summary: "cap on the in-memory log; oldest scrolled-off lines are dropped (0 = no cap)",
+    },
+    SettingSpec {
+        key: "banner",
+        values: "default|none|<file>",
+        summary: "startup banner art (applies on restart)",
+    },
+    SettingSpec {
+        key: "update_check",
+        values: "on|off",
+        summary: "check npm for a newer release at startup (network; opt-in)",
+    },
+    SettingSpec {
+        key: "forge_refs",
+        values: "on|off",
+        summary: "look up #N and issue/PR links in prompts and answers (network, token; opt-in)",
+    },
+    SettingSpec {
+        key: "reduce_effects",
+        values: "auto|on|off",
+        summary: "plain code blocks, slow spinner, batched redraws (auto = slow terminals)",
+    },
+    SettingSpec {
+        key: "spinner",
+        values: SpinnerStyle::VALUES,
+        summary: "run spinner frames (none = no animation)",
+    },
+    SettingSpec {
+        key: "status_verbs",
+        values: StatusVerbs::VALUES,
+        summary: "wording of run statuses on the run line",
+    },
+    SettingSpec {
+        key: "status_format",
+        values: "<text>|default",
+        summary: "run line with {status} {spinner} {tool} {elapsed}",
+    },
+    SettingSpec {
+        key: "esc_policy",
+        values: EscPolicy::VALUES,
+        summary: "what Esc may cancel (layered = press twice to cancel a run)",
+    },
+    SettingSpec {
+        key: "minimap",
+        values: "on|off",
+        summary: "one-column scrollback minimap at the right edge of the log",
+    },
+    SettingSpec {
+        key: "split_pane",
+        values: "on|off",
+        summary: "live tool output in a right pane (Alt+T; needs 120+ columns)",
+    },
+    SettingSpec {
+        key: "ansi_colors",
+        values: "on|off",
+        summary: "keep SGR colors in tool output (cursor moves still stripped)",
+    },
+    SettingSpec {
+        key: "terminal_title",
+        values: "on|off",
+        summary: "show project, session and run state in the terminal/tmux title",
+    },
+    SettingSpec {
+        key: "composer_wrap",
+        values: ComposerWrap::VALUES,
+        summary: "long composer lines: soft-wrap, or scroll sideways with « » markers",
+    },
+    SettingSpec {
+        key: "record_input",
+        values: "on|off",
+        summary: "keep the last 200 input events for /debug-keys and crash reports",
+    },
+    SettingSpec {
+        key: "image_preview",
+        values: ImagePreviewMode::VALUES,
+        summary: "inline image thumbnails in the scrollback (auto = detect terminal)",
+    },
+    SettingSpec {
+        key: "glyphs",
+        values: GlyphMode::VALUES,
+        summary: "ASCII stand-ins for spinner, box and check glyphs (auto = probe font)",
+    },
+    SettingSpec {
+        key: "accessible",
+        values: "on|off",
+        summary: "announce run start, approvals and results on the last row (screen readers)",
+    },
+    SettingSpec {
+        key: "queue_review",
+        values: "on|off",
+        summary: "offer to review queued prompts when a run completes (dispatch continues)",
+    },
+    SettingSpec {
+        key: "translate_endpoint",
+        values: "none|<url>",
+        summary: "LibreTranslate-compatible endpoint used by /translate (network)",
+    },
+];
+
+pub(crate) fn find_setting(key: &str) -> Option<&'static SettingSpec> {
+    SETTING_SPECS.iter().find(|spec| spec.key == key)
+}
+
+/// Partial key being typed in `/set <key>`, before any value.
+pub(crate) fn active_setting_key_prefix(value: &str) -> Option<&str> {
+    let rest = value.trim_start().strip_prefix("/set ")?.trim_start();
+    (!rest.chars().any(char::is_whitespace)).then_some(rest)
+}
+
+pub(crate) fn setting_suggestion_rows(prefix: &str, settings: &TuiSettings) -> Vec<String> {
+    SETTING_SPECS
+        .iter()
+        .filter(|spec| spec.key.starts_with(prefix))
+        .map(|spec| {
+            format!(
+                "{:<14} {:<18} {} (now: {})",
+                spec.key,
+                spec.values,
+                spec.summary,
+                settings.get(spec.key).unwrap_or_default()
+            )
+        })
+        .collect()
+}
+
+/// Tab completion for `/set <key>`; completes a unique key and adds the trailing space.
+pub(crate) fn complete_setting_key_text(value: &str) -> Option<String> {
+    let prefix = active_setting_key_prefix(value)?;
+    let mut matches = SETTING_SPECS
+        .iter()
+        .filter(|spec| spec.key.starts_with(prefix));
+    let first = matches.next()?;
+    if matches.next().is_some() || first.key == prefix {
+        return None;
+    }
+    let head = &value[..value.len() - prefix.len()];
+    Some(format!("{head}{} ", first.key))
+}
+
+/// Persistent UI preferences from `~/.config/codelia/tui.toml`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TuiSettings {
+    /// Built-in or custom theme id; `None` keeps the theme reported by the runtime config.
+    pub theme: Option<String>,
+    pub verbosity: ErrorDetailMode,
+    pub mouse_capture: bool,
+    pub timestamps: bool,
+    /// 0 disables the cap.
+    pub fps_cap: u16,
+    pub alt_screen: bool,
+    pub notifications: bool,
+    /// Seconds; 0 limits notifications to an unfocused terminal.
+    pub notify_threshold: u64,
+    /// Seconds without runtime output before a run is reported as stalled; 0 disables it.
+    pub stall_timeout: u64,
+    /// Memory caps in MiB enforced by `handlers::memory_caps`; 0 disables one.
+    pub image_memory_mb: u64,
+    pub shell_memory_mb: u64,
+    pub log_memory_mb: u64,
+    /// `default`, `none`, or a path to a text file with custom banner art.
+    pub banner: String,
+    /// The only TUI-initiated network check that is not tied to a user command.
+    pub update_check: bool,
+    /// Passive issue/PR lookups for references in prompts and answers; `/issue` works either way.
+    pub forge_refs: bool,
+    /// `None` follows slow-terminal detection.
+    pub reduce_effects: Option<bool>,
+    pub spinner: SpinnerStyle,
+    pub status_verbs: StatusVerbs,
+    /// Run line template; see `render_run_line` for the placeholders.
+    pub status_format: String,
+    pub esc_policy: EscPolicy,
+    pub minimap: bool,
+    pub split_pane: bool,
+    pub ansi_colors: bool,
+    pub terminal_title: bool,
+    pub record_input: bool,
+    pub composer_wrap: ComposerWrap,
+    /// Graphics protocol for image thumbnails; inline mode only.
+    pub image_preview: ImagePreviewMode,
+    /// `ascii` swaps UI glyphs for ASCII when the font lacks them; `auto` detects that.
+    pub glyphs: GlyphMode,
+    /// Screen reader announcements on the last row; see `LiveRegion`.
+    pub accessible: bool,
+    /// Review queued prompts after a completed run; see `open_queue_review`.
+    pub queue_review: bool,
+    /// `/translate` posts finished answers here; `None` leaves the command unavailable.
+    pub translate_endpoint: Option<String>,
+    /// `[tools.<name>]` tables; edited in the file only, not through `/set`.
+    pub tool_styles: ToolStyles,
+    /// `[keys.confirm]` letters; unset ones are derived from the dialog labels.
+    pub confirm_keys: ConfirmAccelerators,
+}
+
+impl Default for TuiSettings {
+    fn default() -> Self {
+        Self {
+            theme: None,
+            verbosity: ErrorDetailMode::Summary,
+            mouse_capture: false,
+            timestamps: false,
+            fps_cap: 0,
+            alt_screen: false,
+            notifications: false,
+            notify_threshold: DEFAULT_NOTIFY_THRESHOLD_SECS,
+            stall_timeout: DEFAULT_STALL_TIMEOUT_SECS,
+            image_memory_mb: DEFAULT_IMAGE_MEMORY_MB,
+            shell_memory_mb: DEFAULT_SHELL_MEMORY_MB,
+            log_memory_mb: DEFAULT_LOG_MEMORY_MB,
+            banner: "default".to_string(),
+            update_check: false,
+            forge_refs: false,
+            reduce_effects: None,
+            spinner: SpinnerStyle::default(),
+            status_verbs: StatusVerbs::default(),
+            status_format: DEFAULT_STATUS_FORMAT.to_string(),
+            esc_policy: EscPolicy::default(),
+            minimap: false,
+            split_pane: false,
+            ansi_colors: false,
+            terminal_title: true,
+            record_input: false,
+            composer_wrap: ComposerWrap::default(),
+            image_preview: ImagePreviewMode::default(),
+            glyphs: GlyphMode::default(),
+            accessible: false,
+            queue_review: false,
+            translate_endpoint: None,
+            tool_styles: ToolStyles::default(),
+            confirm_keys: ConfirmAccelerators::default(),
+        }
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "on" | "true" | "yes" | "1" => Some(true),
+        "off" | "false" | "no" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+fn on_off(value: bool) -> &'static str {
+    if value {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+impl TuiSettings {
+    /// Validates and stores one setting; `value` uses `/set` syntax (on/off, bare words).
+    pub(crate) fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let spec = find_setting(key).ok_or_else(|| format!("unknown setting: {key}"))?;
+        let invalid = || {
+            format!(
+                "invalid value for {key}: {value} (expected {})",
+                spec.values
+            )
+        };
+        match key {
+            "theme" => {
+                self.theme = if value.eq_ignore_ascii_case("default") {
+                    None
+                } else {
+                    Some(resolve_theme_id(value).ok_or_else(invalid)?)
+                };
+            }
+            "verbosity" => {
+                self.verbosity = match value.to_ascii_lowercase().as_str() {
+                    "summary" => ErrorDetailMode::Summary,
+                    "detail" => ErrorDetailMode::Detail,
+                    _ => return Err(invalid()),
+                };
+            }
+            "translate_endpoint" => {
+                self.translate_endpoint = if value.eq_ignore_ascii_case("none") {
+                    None
+                } else if value.starts_with("http://") || value.starts_with("https://") {
+                    Some(value.to_string())
+                } else {
+                    return Err(invalid());
+                };
+            }
+            "banner" => {
+                if value != "default" && value != "none" && !expand_home(value).is_file() {
+                    return Err(format!("banner file not found: {value}"));
+                }
+                self.banner = value.to_string();
+            }
+            "reduce_effects" => {
+                self.reduce_effects = if value.eq_ignore_ascii_case("auto") {
+                    None
+                } else {
+                    Some(parse_bool(value).ok_or_else(invalid)?)
+                };
+            }
+            "spinner" => {
+                self.spinner = SpinnerStyle::parse(value).ok_or_else(invalid)?;
+            }
+            "status_verbs" => {
+                self.status_verbs = StatusVerbs::parse(value).ok_or_else(invalid)?;
+            }
+            "esc_policy" => {
+                self.esc_policy = EscPolicy::parse(value).ok_or_else(invalid)?;
+            }
+            "composer_wrap" => {
+                self.composer_wrap = ComposerWrap::parse(value).ok_or_else(invalid)?;
+            }
+            "image_preview" => {
+                self.image_preview = ImagePreviewMode::parse(value).ok_or_else(invalid)?;
+            }
+            "glyphs" => {
+                self.glyphs = GlyphMode::parse(value).ok_or_else(invalid)?;
+            }
+            "status_format" => {
+                if value.trim().is_empty() || value.eq_ignore_ascii_case("default") {
+                    self.status_format = DEFAULT_STATUS_FORMAT.to_string();
+                } else {
+                    validate_status_format(value)?;
+                    self.status_format = value.to_string();
+                }
+            }
+            "fps_cap" => {
+                self.fps_cap = value
+                    .parse::<u16>()
+                    .ok()
+                    .filter(|fps| *fps <= MAX_FPS_CAP)
+                    .ok_or_else(invalid)?;
+            }
+            "notify_threshold" => {
+                self.notify_threshold = value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|secs| *secs <= MAX_NOTIFY_THRESHOLD_SECS)
+                    .ok_or_else(invalid)?;
+            }
+            "stall_timeout" => {
+                self.stall_timeout = value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|secs| *secs <= MAX_STALL_TIMEOUT_SECS)
+                    .ok_or_else(invalid)?;
+            }
+            "image_memory_mb" | "shell_memory_mb" | "log_memory_mb" => {
+                let megabytes = value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|megabytes| *megabytes <= MAX_MEMORY_CAP_MB)
+                    .ok_or_else(invalid)?;
+                match key {
+                    "image_memory_mb" => self.image_memory_mb = megabytes,
+                    "shell_memory_mb" => self.shell_memory_mb = megabytes,
+                    _ => self.log_memory_mb = megabytes,
+                }
+            }
+            _ => {
+                let parsed = parse_bool(value).ok_or_else(invalid)?;
+                match key {
+                    "mouse_capture" => self.mouse_capture = parsed,
+                    "timestamps" => self.timestamps = parsed,
+                    "alt_screen" => self.alt_screen = parsed,
+                    "update_check" => self.update_check = parsed,
+                    "forge_refs" => self.forge_refs = parsed,
+                    "minimap" => self.minimap = parsed,
+                    "split_pane" => self.split_pane = parsed,
+                    "ansi_colors" => self.ansi_colors = parsed,
+                    "terminal_title" => self.terminal_title = parsed,
+                    "record_input" => self.record_input = parsed,
+                    "accessible" => self.accessible = parsed,
+                    "queue_review" => self.queue_review = parsed,
+                    _ => self.notifications = parsed,
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Display value in `/set` syntax.
+    pub(crate) fn get(&self, key: &str) -> Option<String> {
+        let value = match key {
+            "theme" => self.theme.clone().unwrap_or_else(|| "default".to_string()),
+            "verbosity" => match self.verbosity {
+                ErrorDetailMode::Summary => "summary".to_string(),
+                ErrorDetailMode::Detail => "detail".to_string(),
+            },
+            "mouse_capture" => on_off(self.mouse_capture).to_string(),
+            "timestamps" => on_off(self.timestamps).to_string(),
+            "fps_cap" => self.fps_cap.to_string(),
+            "alt_screen" => on_off(self.alt_screen).to_string(),
+            "notifications" => on_off(self.notifications).to_string(),
+            "notify_threshold" => self.notify_threshold.to_string(),
+            "stall_timeout" => self.stall_timeout.to_string(),
+            "image_memory_mb" => self.image_memory_mb.to_string(),
+            "shell_memory_mb" => self.shell_memory_mb.to_string(),
+            "log_memory_mb" => self.log_memory_mb.to_string(),
+            "banner" => self.banner.clone(),
+            "update_check" => on_off(self.update_check).to_string(),
+            "forge_refs" => on_off(self.forge_refs).to_string(),
+            "spinner" => self.spinner.as_str().to_string(),
+            "status_verbs" => self.status_verbs.as_str().to_string(),
+            "status_format" => self.status_format.clone(),
+            "esc_policy" => self.esc_policy.as_str().to_string(),
+            "minimap" => on_off(self.minimap).to_string(),
+            "split_pane" => on_off(self.split_pane).to_string(),
+            "ansi_colors" => on_off(self.ansi_colors).to_string(),
+            "terminal_title" => on_off(self.terminal_title).to_string(),
+            "record_input" => on_off(self.record_input).to_string(),
+            "composer_wrap" => self.composer_wrap.as_str().to_string(),
+            "image_preview" => self.image_preview.as_str().to_string(),
+            "glyphs" => self.glyphs.as_str().to_string(),
+            "accessible" => on_off(self.accessible).to_string(),
+            "queue_review" => on_off(self.queue_review).to_string(),
+            "translate_endpoint" => self
+                .translate_endpoint
+                .clone()
+                .unwrap_or_else(|| "none".to_string()),
+            "reduce_effects" => self
+                .reduce_effects
+                .map(|reduce| on_off(reduce).to_string())
+                .unwrap_or_else(|| "auto".to_string()),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// TOML literal for the file; `None` means the key should be removed.
+    fn toml_literal(&self, key: &str) -> Option<String> {
+        match key {
+            "theme" => self.theme.as_ref().map(|theme| format!("\"{theme}\"")),
+            "translate_endpoint" => self
+                .translate_endpoint
+                .as_ref()
+                .map(|endpoint| format!("\"{endpoint}\"")),
+            "verbosity" | "banner" | "reduce_effects" | "spinner" | "status_verbs"
+            | "status_format" | "esc_policy" | "composer_wrap" | "image_preview" | "glyphs" => {
+                self.get(key).map(|value| format!("\"{value}\""))
+            }
+            "fps_cap" | "notify_threshold" | "stall_timeout" | "image_memory_mb"
+            | "shell_memory_mb" | "log_memory_mb" => self.get(key),
+            _ => self.get(key).map(|value| (value == "on").to_string()),
+        }
+    }
+
+    /// Minimum time between redraws; zero when uncapped.
+    pub(crate) fn frame_interval(&self) -> Duration {
+        if self.fps_cap == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_millis(1000 / u64::from(self.fps_cap))
+        }
+    }
+
+    pub(crate) fn changed_keys(&self, other: &Self) -> Vec<&'static str> {
+        let mut changed = SETTING_SPECS
+            .iter()
+            .map(|spec| spec.key)
+            .filter(|key| self.get(key) != other.get(key))
+            .collect::<Vec<_>>();
+        if self.tool_styles != other.tool_styles {
+            changed.push("tools");
+        }
+        if self.confirm_keys != other.confirm_keys {
+            changed.push("keys");
+        }
+        changed
+    }
+
+    /// Legacy env vars still win over the file so existing launch scripts keep working.
+    pub(crate) fn apply_env_overrides(&mut self) {
+        for name in ["CODELIA_TUI_THEME", "CODELIA_TUI_MARKDOWN_THEME"] {
+            if let Some(theme) = std::env::var(name)
+                .ok()
+                .and_then(|value| resolve_theme_id(&value))
+            {
+                self.theme = Some(theme);
+                break;
+            }
+        }
+    }
+}
+
+/// `HH:MM:SS` wall-clock label for the `timestamps` setting (UTC; no tz database here).
+pub(crate) fn utc_clock_label(now: SystemTime) -> String {
+    let secs = now
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+        % 86_400;
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+fn strip_toml_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (index, ch) in line.char_indices() {
+        match ch {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..index],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn split_toml_entry(line: &str) -> Option<(&str, &str)> {
+    let (key, value) = strip_toml_comment(line).split_once('=')?;
+    Some((key.trim(), value.trim()))
+}
+
+fn toml_value_to_setting(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+/// Tool name of a `[tools.<name>]` / `[tui.tools.<name>]` header; the name may be quoted.
+fn tool_style_table(table: &str) -> Option<&str> {
+    let name = table
+        .strip_prefix("tools.")
+        .or_else(|| table.strip_prefix("tui.tools."))?
+        .trim();
+    let name = toml_value_to_setting(name);
+    (!name.is_empty()).then_some(name)
+}
+
+/// Reads the flat `key = value` subset of TOML used by tui.toml; returns warnings for bad lines.
+pub(crate) fn parse_settings_toml(text: &str) -> (TuiSettings, Vec<String>) {
+    let mut settings = TuiSettings::default();
+    let mut warnings = Vec::new();
+    let mut in_tui_table = true;
+    let mut tool_table: Option<String> = None;
+    let mut in_confirm_keys = false;
+    for (index, raw_line) in text.lines().enumerate() {
+        let line = strip_toml_comment(raw_line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(table) = line
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            in_tui_table = table.trim() == "tui";
+            tool_table = tool_style_table(table.trim()).map(str::to_string);
+            in_confirm_keys = is_confirm_keymap_table(table.trim());
+            continue;
+        }
+        if !in_tui_table && tool_table.is_none() && !in_confirm_keys {
+            continue;
+        }
+        let Some((key, value)) = split_toml_entry(line) else {
+            warnings.push(format!(
+                "{SETTINGS_FILE}:{}: expected key = value",
+                index + 1
+            ));
+            continue;
+        };
+        let value = toml_value_to_setting(value);
+        let result = match tool_table.as_deref() {
+            Some(tool) => settings.tool_styles.set(tool, key, value),
+            None if in_confirm_keys => set_confirm_key(&mut settings.confirm_keys, key, value),
+            None => settings.set(key, value),
+        };
+        if let Err(error) = result {
+            warnings.push(format!("{SETTINGS_FILE}:{}: {error}", index + 1));
+        }
+    }
+    (settings, warnings)
+}
+
+/// Rewrites one key in place so comments and ordering in a hand-edited file survive.
+pub(crate) fn update_settings_toml(text: &str, key: &str, literal: Option<&str>) -> String {
+    let mut out = Vec::new();
+    let mut replaced = false;
+    let mut in_tui_table = true;
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if let Some(table) = trimmed
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            in_tui_table = table.trim() == "tui";
+        } else if in_tui_table
+            && split_toml_entry(trimmed).is_some_and(|(entry_key, _)| entry_key == key)
+        {
+            if let Some(literal) = literal.filter(|_| !replaced) {
+                out.push(format!("{key} = {literal}"));
+            }
+            replaced = true;
+            continue;
+        }
+        out.push(line.to_string());
+    }
+    let mut text = if out.is_empty() {
+        SETTINGS_FILE_HEADER.to_string()
+    } else {
+        format!("{}\n", out.join("\n"))
+    };
+    if let Some(literal) = literal.filter(|_| !replaced) {
+        // Appending after a foreign table would move the key out of the top level.
+        let has_tables = out.iter().any(|line| line.trim_start().starts_with('['));
+        if has_tables {
+            text = format!("{key} = {literal}\n{text}");
+        } else {
+            text.push_str(&format!("{key} = {literal}\n"));
+        }
+    }
+    text
+}
+
+pub(crate) fn config_dir() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("codelia"))
+}
+
+/// Config directory of the storage layout the runtime uses: `~/.codelia`, or `$XDG_CONFIG_HOME/codelia`
+/// with `CODELIA_LAYOUT=xdg`.
+pub(crate) fn storage_config_dir() -> Option<PathBuf> {
+    let xdg =
+        std::env::var("CODELIA_LAYOUT").is_ok_and(|layout| layout.eq_ignore_ascii_case("xdg"));
+    if xdg {
+        return config_dir();
+    }
+    Some(PathBuf::from(std::env::var_os("HOME")?).join(".codelia"))
+}
+
+pub(crate) fn settings_path() -> Option<PathBuf> {
+    Some(config_dir()?.join(SETTINGS_FILE))
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Custom banner art for `banner = "<file>"`; `None` keeps the built-in logo.
+pub(crate) fn load_banner_lines(banner: &str) -> Result<Option<Vec<String>>, String> {
+    match banner {
+        "default" => Ok(None),
+        "none" => Ok(Some(Vec::new())),
+        path => {
+            let text = std::fs::read_to_string(expand_home(path))
+                .map_err(|error| format!("banner {path}: {error}"))?;
+            Ok(Some(
+                text.lines()
+                    .take(MAX_BANNER_LINES)
+                    .map(sanitize_for_tui)
+                    .collect(),
+            ))
+        }
+    }
+}
+
+fn motd_fingerprint(text: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// A dismissed MOTD stays hidden until the runtime sends different text.
+pub(crate) fn is_motd_dismissed(text: &str) -> bool {
+    config_dir()
+        .and_then(|dir| std::fs::read_to_string(dir.join(MOTD_DISMISSED_FILE)).ok())
+        .is_some_and(|saved| saved.trim() == motd_fingerprint(text))
+}
+
+pub(crate) fn save_motd_dismissed(text: &str) -> Result<(), String> {
+    let dir = config_dir().ok_or_else(|| "no config directory".to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|error| error.to_string())?;
+    std::fs::write(dir.join(MOTD_DISMISSED_FILE), motd_fingerprint(text))
+        .map_err(|error| error.to_string())
+}
+
+/// Version of the previous TUI start; `None` on a first run (or an unreadable config dir).
+pub(crate) fn load_last_run_version() -> Option<String> {
+    let text = std::fs::read_to_string(config_dir()?.join(LAST_RUN_VERSION_FILE)).ok()?;
+    Some(text.trim().to_string()).filter(|version| !version.is_empty())
+}
+
+pub(crate) fn save_last_run_version(version: &str) -> Result<(), String> {
+    let dir = config_dir().ok_or_else(|| "no config directory".to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|error| error.to_string())?;
+    std::fs::write(dir.join(LAST_RUN_VERSION_FILE), version).map_err(|error| error.to_string())
+}
+
+/// Missing file means defaults; env overrides are applied on top.
+fn read_settings_file(path: Option<&Path>) -> (TuiSettings, Vec<String>) {
+    let (mut settings, warnings) = path
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|text| parse_settings_toml(&text))
+        .unwrap_or_default();
+    settings.apply_env_overrides();
+    (settings, warnings)
+}
+
+pub(crate) fn load_settings() -> (TuiSettings, Vec<String>) {
+    read_settings_file(settings_path().as_deref())
+}
+
+/// Polls the settings file mtime; there is no fs-notify dependency, and a 1s stat is cheap.
+#[derive(Debug, Default)]
+pub(crate) struct SettingsWatcher {
+    path: Option<PathBuf>,
+    last_modified: Option<SystemTime>,
+    next_check_at: Option<Instant>,
+}
+
+fn file_modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
+impl SettingsWatcher {
+    pub(crate) fn new(path: Option<PathBuf>) -> Self {
+        let last_modified = path.as_deref().and_then(file_modified);
+        Self {
+            path,
+            last_modified,
+            next_check_at: None,
+        }
+    }
+
+    /// Records our own write so `/set` does not read back as an external edit.
+    pub(crate) fn mark_current(&mut self) {
+        self.last_modified = self.path.as_deref().and_then(file_modified);
+    }
+
+    /// Returns freshly parsed settings when the file changed (or was removed) since last check.
+    pub(crate) fn poll(&mut self, now: Instant) -> Option<(TuiSettings, Vec<String>)> {
+        let path = self.path.as_ref()?;
+        if self.next_check_at.is_some_and(|at| now < at) {
+            return None;
+        }
+        self.next_check_at = Some(now + SETTINGS_WATCH_INTERVAL);
+        let modified = file_modified(path);
+        if modified == self.last_modified {
+            return None;
+        }
+        self.last_modified = modified;
+        Some(read_settings_file(Some(path)))
+    }
+}
+
+pub(crate) fn save_setting(settings: &TuiSettings, key: &str) -> Result<PathBuf, String> {
+    let path = settings_path().ok_or_else(|| "no config directory".to_string())?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|error| error.to_string())?;
+    }
+    let existing = std::fs::read_to_string(&path).unwrap_or_default();
+    let literal = settings.toml_literal(key);
+    let text = update_settings_toml(&existing, key, literal.as_deref());
+    std::fs::write(&path, text).map_err(|error| error.to_string())?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        complete_setting_key_text, load_banner_lines, parse_settings_toml, setting_suggestion_rows,
+        update_settings_toml, utc_clock_label, SettingsWatcher, TuiSettings,
+    };
+    use crate::app::state::LogColor;
+    use crate::app::ErrorDetailMode;
+    use std::time::{Duration, Instant, UNIX_EPOCH};
+
+    #[test]
+    fn parse_reads_flat_and_tui_table_keys_and_reports_bad_lines() {
+        let text = "# prefs\ntheme = \"ocean\" # comment\nfps_cap = 30\n[other]\ntimestamps = true\n[tui]\nnotifications = true\nverbosity = \"loud\"\nbogus\n";
+        let (settings, warnings) = parse_settings_toml(text);
+        assert_eq!(settings.theme.as_deref(), Some("ocean"));
+        assert_eq!(settings.fps_cap, 30);
+        assert!(!settings.timestamps);
+        assert!(settings.notifications);
+        assert_eq!(settings.verbosity, ErrorDetailMode::Summary);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains(":8: invalid value for verbosity"));
+        assert!(warnings[1].contains(":9: expected key = value"));
+    }
+
+    #[test]
+    fn parse_reads_tool_style_tables() {
+        let text = "fps_cap = 30\n[tools.bash]\nicon = \"$\"\ncolor = \"#ff8800\"\n[tui.tools.\"mcp_*\"]\ncolor = \"teal\"\n[tui]\ntimestamps = true\n";
+        let (settings, warnings) = parse_settings_toml(text);
+        assert_eq!(settings.fps_cap, 30);
+        assert!(settings.timestamps);
+        let bash = settings.tool_styles.resolve("bash").expect("bash style");
+        assert_eq!(bash.icon.as_deref(), Some("$"));
+        assert_eq!(bash.color, Some(LogColor::rgb(0xff, 0x88, 0x00)));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains(":6: invalid color for tools.mcp_*: teal"));
+        assert_eq!(
+            TuiSettings::default().changed_keys(&settings),
+            vec!["timestamps", "fps_cap", "tools"]
+        );
+    }
+
+    #[test]
+    fn parse_reads_confirm_keymap() {
+        let text = "[keys.confirm]\ndeny = \"X\"\nremember = \"rr\"\n";
+        let (settings, warnings) = parse_settings_toml(text);
+        assert_eq!(settings.confirm_keys.deny, Some('x'));
+        assert_eq!(settings.confirm_keys.remember, None);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains(":3: invalid key for keys.confirm.remember: rr"));
+        assert_eq!(TuiSettings::default().changed_keys(&settings), vec!["keys"]);
+    }
+
+    #[test]
+    fn set_validates_values() {
+        let mut settings = TuiSettings::default();
+        assert!(settings.set("timestamps", "on").is_ok());
+        assert_eq!(settings.get("timestamps").as_deref(), Some("on"));
+        assert!(settings.set("fps_cap", "999").is_err());
+        assert!(settings.set("theme", "nope").is_err());
+        assert!(settings.set("colour", "red").is_err());
+        assert!(settings.set("theme", "default").is_ok());
+        assert_eq!(settings.theme, None);
+    }
+
+    #[test]
+    fn update_preserves_comments_and_replaces_in_place() {
+        let text = "# keep me\ntimestamps = false\nfps_cap = 10\n";
+        assert_eq!(
+            update_settings_toml(text, "timestamps", Some("true")),
+            "# keep me\ntimestamps = true\nfps_cap = 10\n"
+        );
+        assert_eq!(
+            update_settings_toml(text, "theme", Some("\"rose\"")),
+            "# keep me\ntimestamps = false\nfps_cap = 10\ntheme = \"rose\"\n"
+        );
+        assert_eq!(
+            update_settings_toml(text, "fps_cap", None),
+            "# keep me\ntimestamps = false\n"
+        );
+        assert!(update_settings_toml("", "fps_cap", Some("30")).ends_with("\nfps_cap = 30\n"));
+    }
+
+    #[test]
+    fn watcher_reports_external_edits_once() {
+        let dir = std::env::temp_dir().join(format!("codelia-settings-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        let path = dir.join("tui.toml");
+        let _ = std::fs::remove_file(&path);
+        let mut watcher = SettingsWatcher::new(Some(path.clone()));
+        let start = Instant::now();
+        assert!(watcher.poll(start).is_none());
+
+        std::fs::write(&path, "fps_cap = 30\n").expect("write");
+        assert!(
+            watcher.poll(start).is_none(),
+            "throttled until the next check"
+        );
+        let later = start + Duration::from_secs(2);
+        let (settings, warnings) = watcher.poll(later).expect("changed");
+        assert_eq!(settings.fps_cap, 30);
+        assert!(warnings.is_empty());
+        assert_eq!(
+            TuiSettings::default().changed_keys(&settings),
+            vec!["fps_cap"]
+        );
+        assert!(watcher.poll(later + Duration::from_secs(2)).is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn banner_setting_accepts_keywords_and_existing_files() {
+        let path = std::env::temp_dir().join(format!("codelia-banner-{}.txt", std::process::id()));
+        std::fs::write(&path, "/\\ art\n\x1b[31mred\x1b[0m\n").expect("write");
+        let mut settings = TuiSettings::default();
+        assert!(settings.set("banner", "none").is_ok());
+        assert!(settings.set("banner", "/no/such/banner.txt").is_err());
+        assert_eq!(settings.banner, "none");
+        assert_eq!(load_banner_lines("none"), Ok(Some(Vec::new())));
+        assert_eq!(load_banner_lines("default"), Ok(None));
+        let file = path.display().to_string();
+        assert!(settings.set("banner", &file).is_ok());
+        assert_eq!(
+            load_banner_lines(&file),
+            Ok(Some(vec!["/\\ art".to_string(), "red".to_string()]))
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn utc_clock_label_wraps_days() {
+        let time = UNIX_EPOCH + Duration::from_secs(86_400 * 3 + 3600 * 13 + 60 * 5 + 9);
+        assert_eq!(utc_clock_label(time), "13:05:09");
+        assert_eq!(TuiSettings::default().frame_interval(), Duration::ZERO);
+    }
+
+    #[test]
+    fn set_key_completion_and_suggestions() {
+        assert_eq!(
+            complete_setting_key_text("/set time").as_deref(),
+            Some("/set timestamps ")
+        );
+        assert_eq!(complete_setting_key_text("/set timestamps on"), None);
+        assert_eq!(complete_setting_key_text("/set zzz"), None);
+        let rows = setting_suggestion_rows("", &TuiSettings::default());
+        assert_eq!(rows.len(), 31);
+        assert!(rows[4].starts_with("fps_cap") && rows[4].ends_with("(now: 0)"));
+        assert!(rows[11].starts_with("log_memory_mb") && rows[11].ends_with("(now: 128)"));
+        assert!(rows[14].starts_with("forge_refs") && rows[14].ends_with("(now: off)"));
+        assert!(rows[15].starts_with("reduce_effects") && rows[15].ends_with("(now: auto)"));
+        assert!(
+            rows[18].starts_with("status_format")
+                && rows[18].ends_with("(now: ● {status} {spinner})")
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use std::time::Duration;
+
+pub(crate) const DEFAULT_STATUS_FORMAT: &str = "● {status} {spinner}";
+const MAX_STATUS_FORMAT_CHARS: usize = 80;
+
+/// Frame set of the run spinner (`spinner` in tui.toml); `none` stops the animation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum SpinnerStyle {
+    #[default]
+    Dots,
+    Line,
+    Arc,
+    Bounce,
+    Pulse,
+    None,
+}
+
+impl SpinnerStyle {
+    pub(crate) const VALUES: &'static str = "dots|line|arc|bounce|pulse|none";
+
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "dots" => Some(Self::Dots),
+            "line" => Some(Self::Line),
+            "arc" => Some(Self::Arc),
+            "bounce" => Some(Self::Bounce),
+            "pulse" => Some(Self::Pulse),
+            "none" | "off" => Some(Self::None),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Dots => "dots",
+            Self::Line => "line",
+            Self::Arc => "arc",
+            Self::Bounce => "bounce",
+            Self::Pulse => "pulse",
+            Self::None => "none",
+        }
+    }
+
+    pub(crate) fn frames(self) -> &'static [&'static str] {
+        match self {
+            Self::Dots => &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧"],
+            Self::Line => &["-", "\\", "|", "/"],
+            Self::Arc => &["◜", "◠", "◝", "◞", "◡", "◟"],
+            Self::Bounce => &["⠁", "⠂", "⠄", "⠂"],
+            Self::Pulse => &["·", "•", "●", "•"],
+            Self::None => &[""],
+        }
+    }
+
+    pub(crate) fn frame(self, index: usize) -> &'static str {
+        let frames = self.frames();
+        frames[index % frames.len()]
+    }
+
+    /// Redraw interval; without frames the line still ticks once a second for `{elapsed}`.
+    pub(crate) fn interval(self, reduced: bool) -> Duration {
+        match (self, reduced) {
+            (Self::None, _) => Duration::from_secs(1),
+            (_, true) => Duration::from_millis(480),
+            (_, false) => Duration::from_millis(120),
+        }
+    }
+}
+
+/// Wording of run statuses on the run line (`status_verbs` in tui.toml).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum StatusVerbs {
+    /// The runtime's status names (`running`, `awaiting_ui`, ...).
+    #[default]
+    Plain,
+    Friendly,
+    Terse,
+}
+
+impl StatusVerbs {
+    pub(crate) const VALUES: &'static str = "plain|friendly|terse";
+
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "plain" | "default" => Some(Self::Plain),
+            "friendly" => Some(Self::Friendly),
+            "terse" => Some(Self::Terse),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Plain => "plain",
+            Self::Friendly => "friendly",
+            Self::Terse => "terse",
+        }
+    }
+
+    pub(crate) fn verb(self, status: &str) -> String {
+        let phrase = match (self, status) {
+            (Self::Plain, _) => None,
+            (Self::Friendly, "starting") => Some("Starting…"),
+            (Self::Friendly, "running") => Some("Working…"),
+            (Self::Friendly, "awaiting_ui") => Some("Waiting for you"),
+            (Self::Friendly, "completed") => Some("Done"),
+            (Self::Friendly, "cancelled") => Some("Cancelled"),
+            (Self::Friendly, "error") => Some("Failed"),
+            (Self::Friendly, "idle") => Some("Ready"),
+            (Self::Terse, "starting") => Some("start"),
+            (Self::Terse, "running") => Some("run"),
+            (Self::Terse, "awaiting_ui") => Some("wait"),
+            (Self::Terse, "completed") => Some("ok"),
+            (Self::Terse, "cancelled") => Some("stop"),
+            (Self::Terse, "error") => Some("err"),
+            _ => None,
+        };
+        phrase.unwrap_or(status).to_string()
+    }
+}
+
+/// `status_format` must stay a one-line TOML string.
+pub(crate) fn validate_status_format(value: &str) -> Result<(), String> {
+    if value.contains('"') || value.contains('\\') {
+        return Err("status_format cannot contain quotes or backslashes".to_string());
+    }
+    if value.chars().count() > MAX_STATUS_FORMAT_CHARS {
+        return Err(format!(
+            "status_format is limited to {MAX_STATUS_FORMAT_CHARS} characters"
+        ));
+    }
+    Ok(())
+}
+
+/// Values for the `status_format` placeholders; empty ones drop out of the line.
+pub(crate) struct RunLineFields<'a> {
+    pub status: &'a str,
+    pub spinner: &'a str,
+    pub tool: &'a str,
+    pub elapsed: Option<Duration>,
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs >= 60 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{secs}s")
+    }
+}
+
+/// Expands `{status}`, `{spinner}`, `{tool}`, and `{elapsed}`; unknown braces stay literal
+/// and the blanks left by empty placeholders collapse to single spaces.
+pub(crate) fn render_run_line(format: &str, fields: &RunLineFields<'_>) -> String {
+    let elapsed = fields.elapsed.map(format_elapsed).unwrap_or_default();
+    let expanded = format
+        .replace("{status}", fields.status)
+        .replace("{spinner}", fields.spinner)
+        .replace("{tool}", fields.tool)
+        .replace("{elapsed}", &elapsed);
+    expanded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render_run_line, RunLineFields, SpinnerStyle, StatusVerbs};
+    use std::time::Duration;
+
+    #[test]
+    fn run_line_placeholders_styles_and_verbs() {
+        let fields = RunLineFields {
+            status: &StatusVerbs::Friendly.verb("running"),
+            spinner: SpinnerStyle::Line.frame(5),
+            tool: "bash",
+            elapsed: Some(Duration::from_secs(75)),
+        };
+        assert_eq!(
+            render_run_line("{spinner} {status} {tool} {elapsed} {cwd}", &fields),
+            "\\ Working… bash 1m15s {cwd}"
+        );
+
+        let idle = RunLineFields {
+            status: &StatusVerbs::Terse.verb("completed"),
+            spinner: SpinnerStyle::None.frame(3),
+            tool: "",
+            elapsed: None,
+        };
+        assert_eq!(
+            render_run_line("● {status} {spinner} {tool}", &idle),
+            "● ok"
+        );
+        assert_eq!(StatusVerbs::Plain.verb("awaiting_ui"), "awaiting_ui");
+        assert_eq!(SpinnerStyle::parse("off"), Some(SpinnerStyle::None));
+    }
+}
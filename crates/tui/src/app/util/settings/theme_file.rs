@@ -0,0 +1,324 @@
+use super::tool_styles::parse_tool_color;
+use super::{split_toml_entry, storage_config_dir, strip_toml_comment, toml_value_to_setting};
+use crate::app::state::{parse_theme_name, LogColor, ThemeName};
+use crate::app::util::text::sanitize_for_tui;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+const THEMES_DIR: &str = "themes";
+const MAX_CUSTOM_THEMES: usize = 64;
+const MAX_DESCRIPTION_CHARS: usize = 60;
+
+/// Themes bundled with syntect's defaults; `syntax = "<name>"` must name one of them.
+pub(crate) const SYNTAX_THEMES: &[&str] = &[
+    "base16-ocean.dark",
+    "base16-eighties.dark",
+    "base16-mocha.dark",
+    "base16-ocean.light",
+    "InspiredGitHub",
+    "Solarized (dark)",
+    "Solarized (light)",
+];
+
+/// Color slots a theme file may set, as `<table>.<key>`. `inline` and `diff` colors are baked
+/// into spans and need a real color; `log` and `status` slots also accept `default`.
+pub(crate) const THEME_COLOR_KEYS: &[&str] = &[
+    "inline.heading",
+    "inline.bold",
+    "inline.inline_code",
+    "diff.line_number",
+    "diff.added_marker",
+    "diff.removed_marker",
+    "diff.added_bg",
+    "diff.removed_bg",
+    "diff.code_bg",
+    "log.text",
+    "log.primary",
+    "log.muted",
+    "log.system",
+    "log.tool_call",
+    "log.tool_result",
+    "log.status",
+    "log.space",
+    "log.error",
+    "log.user_bg",
+    "log.code_bg",
+    "log.divider",
+    "status.ready",
+    "status.completed",
+    "status.cancelled",
+    "status.error",
+    "status.badge",
+    "status.perf",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ThemeColor {
+    /// The terminal's own foreground or background.
+    Default,
+    Rgb(LogColor),
+}
+
+/// One `themes/<name>.toml` file; slots it leaves out come from `base`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct CustomTheme {
+    pub name: String,
+    pub description: String,
+    pub base: ThemeName,
+    pub syntax: Option<&'static str>,
+    /// Dims detail rows and muted status text when set; built-ins leave them undimmed.
+    pub dim_details: Option<bool>,
+    pub colors: Vec<(&'static str, ThemeColor)>,
+}
+
+impl CustomTheme {
+    pub(crate) fn color(&self, slot: &str) -> Option<ThemeColor> {
+        self.colors
+            .iter()
+            .find(|(candidate, _)| *candidate == slot)
+            .map(|(_, color)| *color)
+    }
+}
+
+static CUSTOM_THEMES: OnceLock<Mutex<Vec<CustomTheme>>> = OnceLock::new();
+
+fn custom_themes_mutex() -> &'static Mutex<Vec<CustomTheme>> {
+    CUSTOM_THEMES.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+pub(crate) fn install_custom_themes(themes: Vec<CustomTheme>) {
+    if let Ok(mut current) = custom_themes_mutex().lock() {
+        *current = themes;
+    }
+}
+
+pub(crate) fn custom_themes() -> Vec<CustomTheme> {
+    custom_themes_mutex()
+        .lock()
+        .map(|themes| themes.clone())
+        .unwrap_or_default()
+}
+
+pub(crate) fn find_custom_theme(name: &str) -> Option<CustomTheme> {
+    custom_themes_mutex()
+        .lock()
+        .ok()?
+        .iter()
+        .find(|theme| theme.name == name)
+        .cloned()
+}
+
+/// Canonical id of a built-in theme (aliases included) or a loaded custom theme.
+pub(crate) fn resolve_theme_id(value: &str) -> Option<String> {
+    if let Some(name) = parse_theme_name(value) {
+        return Some(name.as_str().to_string());
+    }
+    let name = value.trim().to_ascii_lowercase();
+    find_custom_theme(&name).map(|theme| theme.name)
+}
+
+pub(crate) fn themes_dir() -> Option<PathBuf> {
+    Some(storage_config_dir()?.join(THEMES_DIR))
+}
+
+fn valid_theme_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-' || ch == '_')
+}
+
+fn parse_theme_color(slot: &str, value: &str) -> Result<ThemeColor, String> {
+    if value.eq_ignore_ascii_case("default") {
+        if slot.starts_with("log.") || slot.starts_with("status.") {
+            return Ok(ThemeColor::Default);
+        }
+        return Err(format!("{slot} needs a color, not default"));
+    }
+    parse_tool_color(value).map(ThemeColor::Rgb).ok_or_else(|| {
+        format!("invalid color for {slot}: {value} (expected #rrggbb or a color name)")
+    })
+}
+
+fn set_top_level(theme: &mut CustomTheme, key: &str, value: &str) -> Result<(), String> {
+    match key {
+        "description" => {
+            theme.description = sanitize_for_tui(value)
+                .trim()
+                .chars()
+                .take(MAX_DESCRIPTION_CHARS)
+                .collect();
+        }
+        "base" => {
+            theme.base =
+                parse_theme_name(value).ok_or_else(|| format!("unknown base theme: {value}"))?;
+        }
+        "syntax" => {
+            theme.syntax = Some(
+                SYNTAX_THEMES
+                    .iter()
+                    .copied()
+                    .find(|name| name.eq_ignore_ascii_case(value))
+                    .ok_or_else(|| {
+                        format!(
+                            "unknown syntax theme: {value} (expected one of {})",
+                            SYNTAX_THEMES.join(", ")
+                        )
+                    })?,
+            );
+        }
+        "dim_details" => {
+            theme.dim_details = Some(match value {
+                "true" => true,
+                "false" => false,
+                _ => return Err(format!("invalid value for dim_details: {value}")),
+            });
+        }
+        _ => return Err(format!("unknown key: {key}")),
+    }
+    Ok(())
+}
+
+/// Reads one theme file in the same flat TOML subset as tui.toml; returns warnings for bad lines.
+pub(crate) fn parse_theme_toml(name: &str, text: &str) -> (CustomTheme, Vec<String>) {
+    let mut theme = CustomTheme {
+        name: name.to_string(),
+        description: String::new(),
+        base: ThemeName::Codelia,
+        syntax: None,
+        dim_details: None,
+        colors: Vec::new(),
+    };
+    let mut warnings = Vec::new();
+    let mut table: Option<String> = None;
+    for (index, raw_line) in text.lines().enumerate() {
+        let line = strip_toml_comment(raw_line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        let mut warn = |message: String| {
+            warnings.push(format!("{THEMES_DIR}/{name}.toml:{}: {message}", index + 1));
+        };
+        if let Some(header) = line
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            let header = header.trim();
+            if !matches!(header, "inline" | "diff" | "log" | "status") {
+                warn(format!("unknown table [{header}]"));
+            }
+            table = Some(header.to_string());
+            continue;
+        }
+        let Some((key, value)) = split_toml_entry(line) else {
+            warn("expected key = value".to_string());
+            continue;
+        };
+        let value = toml_value_to_setting(value);
+        let result = match table.as_deref() {
+            None => set_top_level(&mut theme, key, value),
+            Some(table) => {
+                let slot = format!("{table}.{key}");
+                match THEME_COLOR_KEYS
+                    .iter()
+                    .find(|candidate| **candidate == slot)
+                {
+                    Some(slot) => parse_theme_color(slot, value).map(|color| {
+                        theme.colors.retain(|(candidate, _)| candidate != slot);
+                        theme.colors.push((slot, color));
+                    }),
+                    // Already reported at the table header.
+                    None if !matches!(table, "inline" | "diff" | "log" | "status") => Ok(()),
+                    None => Err(format!("unknown key: {slot}")),
+                }
+            }
+        };
+        if let Err(error) = result {
+            warn(error);
+        }
+    }
+    (theme, warnings)
+}
+
+fn read_custom_themes(dir: &Path) -> (Vec<CustomTheme>, Vec<String>) {
+    let mut paths = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    paths.sort();
+    let mut themes = Vec::new();
+    let mut warnings = Vec::new();
+    for path in paths.into_iter().take(MAX_CUSTOM_THEMES) {
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let name = name.to_ascii_lowercase();
+        if !valid_theme_name(&name) {
+            warnings.push(format!(
+                "{THEMES_DIR}/{name}.toml: theme names use a-z, 0-9, - and _"
+            ));
+            continue;
+        }
+        if parse_theme_name(&name).is_some() {
+            warnings.push(format!(
+                "{THEMES_DIR}/{name}.toml: name clashes with a built-in theme"
+            ));
+            continue;
+        }
+        match std::fs::read_to_string(&path) {
+            Ok(text) => {
+                let (theme, theme_warnings) = parse_theme_toml(&name, &text);
+                warnings.extend(theme_warnings);
+                themes.push(theme);
+            }
+            Err(error) => warnings.push(format!("{THEMES_DIR}/{name}.toml: {error}")),
+        }
+    }
+    (themes, warnings)
+}
+
+/// Rescans the themes directory into the registry; a missing directory means no custom themes.
+pub(crate) fn load_custom_themes() -> Vec<String> {
+    let (themes, warnings) = themes_dir()
+        .map(|dir| read_custom_themes(&dir))
+        .unwrap_or_default();
+    install_custom_themes(themes);
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_theme_toml, ThemeColor};
+    use crate::app::state::{LogColor, ThemeName};
+
+    #[test]
+    fn theme_file_overrides_slots_on_top_of_a_base_theme() {
+        let text = "description = \"Nord-ish\"\nbase = \"ocean\"\nsyntax = \"base16-mocha.dark\"\ndim_details = true\n\n[inline]\nheading = \"#88c0d0\"\n\n[log]\nprimary = \"default\"\nerror = \"red\"\nbogus = \"#000000\"\n\n[diff]\nadded_bg = \"default\"\n\n[status]\ncompleted = \"#a3be8c\"\n[extra]\nkey = 1\n";
+        let (theme, warnings) = parse_theme_toml("nord", text);
+
+        assert_eq!(theme.name, "nord");
+        assert_eq!(theme.description, "Nord-ish");
+        assert_eq!(theme.base, ThemeName::Ocean);
+        assert_eq!(theme.syntax, Some("base16-mocha.dark"));
+        assert_eq!(theme.dim_details, Some(true));
+        assert_eq!(
+            theme.color("inline.heading"),
+            Some(ThemeColor::Rgb(LogColor::rgb(0x88, 0xc0, 0xd0)))
+        );
+        assert_eq!(theme.color("log.primary"), Some(ThemeColor::Default));
+        assert!(matches!(theme.color("log.error"), Some(ThemeColor::Rgb(_))));
+        assert_eq!(theme.color("diff.added_bg"), None);
+        assert_eq!(
+            warnings,
+            vec![
+                "themes/nord.toml:12: unknown key: log.bogus".to_string(),
+                "themes/nord.toml:15: diff.added_bg needs a color, not default".to_string(),
+                "themes/nord.toml:19: unknown table [extra]".to_string(),
+            ]
+        );
+    }
+}
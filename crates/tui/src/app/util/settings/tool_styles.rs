@@ -0,0 +1,131 @@
+use crate::app::state::LogColor;
+use crate::app::util::text::sanitize_for_tui;
+
+const MAX_TOOL_ICON_CHARS: usize = 4;
+
+/// Icon and accent color for one `[tools.<name>]` table in tui.toml.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ToolStyle {
+    pub icon: Option<String>,
+    pub color: Option<LogColor>,
+}
+
+/// Per-tool styles keyed by tool name; a trailing `*` matches a prefix (`mcp_github_*`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ToolStyles {
+    entries: Vec<(String, ToolStyle)>,
+}
+
+fn named_color(name: &str) -> Option<LogColor> {
+    let color = match name {
+        "red" => LogColor::rgb(0xe0, 0x6c, 0x75),
+        "green" => LogColor::rgb(0x98, 0xc3, 0x79),
+        "yellow" => LogColor::rgb(0xe5, 0xc0, 0x7b),
+        "blue" => LogColor::rgb(0x61, 0xaf, 0xef),
+        "magenta" => LogColor::rgb(0xc6, 0x78, 0xdd),
+        "cyan" => LogColor::rgb(0x56, 0xb6, 0xc2),
+        "orange" => LogColor::rgb(0xd1, 0x9a, 0x66),
+        "white" => LogColor::rgb(0xdc, 0xdf, 0xe4),
+        "gray" | "grey" => LogColor::rgb(0x8b, 0x92, 0x9e),
+        _ => return None,
+    };
+    Some(color)
+}
+
+/// Accepts `#rrggbb` or one of the named accents.
+pub(crate) fn parse_tool_color(value: &str) -> Option<LogColor> {
+    let value = value.trim().to_ascii_lowercase();
+    let Some(hex) = value.strip_prefix('#') else {
+        return named_color(&value);
+    };
+    if hex.len() != 6 || !hex.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).ok();
+    Some(LogColor::rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
+impl ToolStyles {
+    /// Stores one `icon` / `color` key of the `[tools.<pattern>]` table.
+    pub(crate) fn set(&mut self, pattern: &str, key: &str, value: &str) -> Result<(), String> {
+        let index = match self.entries.iter().position(|(name, _)| name == pattern) {
+            Some(index) => index,
+            None => {
+                self.entries
+                    .push((pattern.to_string(), ToolStyle::default()));
+                self.entries.len() - 1
+            }
+        };
+        let style = &mut self.entries[index].1;
+        match key {
+            "icon" => {
+                let icon = sanitize_for_tui(value).trim().to_string();
+                if icon.is_empty() || icon.chars().count() > MAX_TOOL_ICON_CHARS {
+                    return Err(format!(
+                        "invalid icon for tools.{pattern}: {value} (expected 1-{MAX_TOOL_ICON_CHARS} characters)"
+                    ));
+                }
+                style.icon = Some(icon);
+            }
+            "color" => {
+                style.color = Some(parse_tool_color(value).ok_or_else(|| {
+                    format!("invalid color for tools.{pattern}: {value} (expected #rrggbb or a color name)")
+                })?);
+            }
+            _ => return Err(format!("unknown key in tools.{pattern}: {key}")),
+        }
+        Ok(())
+    }
+
+    /// Exact names win over prefix patterns; among patterns the longest prefix wins.
+    pub(crate) fn resolve(&self, tool: &str) -> Option<&ToolStyle> {
+        if let Some((_, style)) = self.entries.iter().find(|(name, _)| name == tool) {
+            return Some(style);
+        }
+        self.entries
+            .iter()
+            .filter_map(|(name, style)| {
+                let prefix = name.strip_suffix('*')?;
+                tool.starts_with(prefix).then_some((prefix.len(), style))
+            })
+            .max_by_key(|(len, _)| *len)
+            .map(|(_, style)| style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_tool_color, ToolStyles};
+    use crate::app::state::LogColor;
+
+    #[test]
+    fn tool_styles_resolve_exact_names_before_prefix_patterns() {
+        let mut styles = ToolStyles::default();
+        styles.set("mcp_*", "icon", "🔌").expect("icon");
+        styles
+            .set("mcp_github_*", "color", "#112233")
+            .expect("color");
+        styles.set("bash", "color", "orange").expect("color");
+        assert!(styles.set("bash", "color", "#12345").is_err());
+        assert!(styles.set("bash", "icon", "toolong").is_err());
+        assert!(styles.set("bash", "shape", "x").is_err());
+
+        assert_eq!(
+            styles.resolve("bash").and_then(|style| style.color),
+            parse_tool_color("orange")
+        );
+        assert_eq!(
+            styles
+                .resolve("mcp_github_search")
+                .and_then(|style| style.color),
+            Some(LogColor::rgb(0x11, 0x22, 0x33))
+        );
+        assert_eq!(
+            styles
+                .resolve("mcp_slack_post")
+                .and_then(|style| style.icon.as_deref()),
+            Some("🔌")
+        );
+        assert!(styles.resolve("read").is_none());
+    }
+}
@@ -0,0 +1,292 @@
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const MAX_STACK_FRAMES: usize = 20;
+const MAX_FRAME_EXCERPTS: usize = 8;
+const EXCERPT_CONTEXT_LINES: usize = 2;
+const EXCERPT_MAX_FILE_BYTES: u64 = 1024 * 1024;
+const EXCERPT_LINE_MAX_CHARS: usize = 160;
+const TRACE_MARKERS: &[&str] = &[
+    "Traceback (most recent call last)",
+    "panicked at",
+    "stack backtrace:",
+    "Exception",
+    "Error:",
+    "goroutine ",
+    "    at ",
+];
+
+/// One `file:line[:column]` reference found in a pasted trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct StackFrame {
+    pub path: String,
+    pub line: usize,
+    pub column: Option<usize>,
+}
+
+impl StackFrame {
+    pub(crate) fn location(&self) -> String {
+        match self.column {
+            Some(column) => format!("{}:{}:{column}", self.path, self.line),
+            None => format!("{}:{}", self.path, self.line),
+        }
+    }
+}
+
+/// A frame checked against the workspace; `excerpt` is only read for files inside it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ResolvedFrame {
+    pub frame: StackFrame,
+    pub resolved: Option<PathBuf>,
+    pub in_workspace: bool,
+    pub excerpt: Option<String>,
+}
+
+fn parse_number(text: &str) -> Option<usize> {
+    (!text.is_empty() && text.bytes().all(|byte| byte.is_ascii_digit()))
+        .then(|| text.parse().ok())
+        .flatten()
+}
+
+fn has_source_extension(path: &str) -> bool {
+    let name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    let Some((stem, extension)) = name.rsplit_once('.') else {
+        return false;
+    };
+    !stem.is_empty()
+        && (1..=6).contains(&extension.len())
+        && extension.bytes().all(|byte| byte.is_ascii_alphanumeric())
+        && extension.bytes().any(|byte| byte.is_ascii_alphabetic())
+}
+
+/// `path:line[:column]`, tolerating `file://`, Windows drive letters, and wrapping punctuation.
+fn parse_location(token: &str) -> Option<StackFrame> {
+    let token = token
+        .trim_matches(|ch: char| matches!(ch, '(' | ')' | '[' | ']' | '"' | '\'' | ',' | '`'))
+        .trim_end_matches(':');
+    let token = token.strip_prefix("file://").unwrap_or(token);
+    if token.contains("://") {
+        return None;
+    }
+    let (rest, last) = token.rsplit_once(':')?;
+    let last = parse_number(last)?;
+    let (path, line, column) = match rest.rsplit_once(':') {
+        Some((path, line)) if parse_number(line).is_some() => {
+            (path, parse_number(line)?, Some(last))
+        }
+        _ => (rest, last, None),
+    };
+    (line > 0 && has_source_extension(path)).then(|| StackFrame {
+        path: path.to_string(),
+        line,
+        column,
+    })
+}
+
+/// Python's `File "app.py", line 12, in main`.
+fn parse_python_frame(line: &str) -> Option<StackFrame> {
+    let rest = line.trim_start().strip_prefix("File \"")?;
+    let (path, rest) = rest.split_once('"')?;
+    let number = rest
+        .trim_start_matches(',')
+        .trim_start()
+        .strip_prefix("line ")?;
+    let number = number.split(|ch: char| !ch.is_ascii_digit()).next()?;
+    Some(StackFrame {
+        path: path.to_string(),
+        line: parse_number(number)?,
+        column: None,
+    })
+}
+
+/// Frames referenced by `text`, in order and deduplicated; empty unless it reads like a trace
+/// (two or more frames, or one frame next to a panic/exception marker).
+pub(crate) fn detect_stack_frames(text: &str) -> Vec<StackFrame> {
+    if text.lines().filter(|line| !line.trim().is_empty()).count() < 2 {
+        return Vec::new();
+    }
+    let mut frames: Vec<StackFrame> = Vec::new();
+    for line in text.lines() {
+        let found = parse_python_frame(line)
+            .map(|frame| vec![frame])
+            .unwrap_or_else(|| {
+                line.split(|ch: char| ch.is_whitespace() || ch == '(' || ch == ')')
+                    .filter_map(parse_location)
+                    .collect()
+            });
+        for frame in found {
+            if frames.len() == MAX_STACK_FRAMES {
+                break;
+            }
+            if !frames
+                .iter()
+                .any(|seen| seen.path == frame.path && seen.line == frame.line)
+            {
+                frames.push(frame);
+            }
+        }
+    }
+    let has_marker = TRACE_MARKERS.iter().any(|marker| text.contains(marker));
+    if frames.len() >= 2 || (has_marker && !frames.is_empty()) {
+        frames
+    } else {
+        Vec::new()
+    }
+}
+
+fn excerpt_around(path: &Path, line: usize) -> Option<String> {
+    if fs::metadata(path).ok()?.len() > EXCERPT_MAX_FILE_BYTES {
+        return None;
+    }
+    let content = fs::read_to_string(path).ok()?;
+    let lines = content.lines().collect::<Vec<_>>();
+    if line > lines.len() {
+        return None;
+    }
+    let start = line.saturating_sub(EXCERPT_CONTEXT_LINES).max(1);
+    let end = line.saturating_add(EXCERPT_CONTEXT_LINES).min(lines.len());
+    let width = end.to_string().len();
+    let rows = (start..=end)
+        .map(|number| {
+            let marker = if number == line { ">" } else { " " };
+            let text = lines[number - 1]
+                .chars()
+                .take(EXCERPT_LINE_MAX_CHARS)
+                .collect::<String>();
+            format!("{marker} {number:>width$} | {text}")
+        })
+        .collect::<Vec<_>>();
+    Some(rows.join("\n"))
+}
+
+/// Checks each frame under `root`; only the first few in-workspace frames get excerpts.
+pub(crate) fn resolve_stack_frames(frames: &[StackFrame], root: &Path) -> Vec<ResolvedFrame> {
+    let root = fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+    let mut excerpts = 0;
+    frames
+        .iter()
+        .map(|frame| {
+            let path = Path::new(&frame.path);
+            let candidate = if path.is_absolute() {
+                path.to_path_buf()
+            } else {
+                root.join(path)
+            };
+            let resolved = fs::canonicalize(&candidate)
+                .ok()
+                .filter(|resolved| resolved.is_file());
+            let in_workspace = resolved
+                .as_ref()
+                .is_some_and(|resolved| resolved.starts_with(&root));
+            let excerpt = match &resolved {
+                Some(resolved) if in_workspace && excerpts < MAX_FRAME_EXCERPTS => {
+                    let excerpt = excerpt_around(resolved, frame.line);
+                    excerpts += usize::from(excerpt.is_some());
+                    excerpt
+                }
+                _ => None,
+            };
+            ResolvedFrame {
+                frame: frame.clone(),
+                resolved: resolved.map(|resolved| {
+                    resolved
+                        .strip_prefix(&root)
+                        .map(Path::to_path_buf)
+                        .unwrap_or(resolved)
+                }),
+                in_workspace,
+                excerpt,
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn build_stack_trace_prefix(traces: &[Vec<ResolvedFrame>]) -> Option<String> {
+    if traces.is_empty() {
+        return None;
+    }
+    let blocks = traces
+        .iter()
+        .map(|frames| {
+            let frames = frames
+                .iter()
+                .map(|frame| {
+                    json!({
+                        "location": frame.frame.location(),
+                        "exists": frame.resolved.is_some(),
+                        "in_workspace": frame.in_workspace,
+                        "path": frame.resolved.as_ref().map(|path| path.display().to_string()),
+                        "excerpt": frame.excerpt,
+                    })
+                })
+                .collect::<Vec<_>>();
+            let json_text = json!({ "frames": frames })
+                .to_string()
+                .replace('<', "\\u003c")
+                .replace('>', "\\u003e");
+            format!("<stack_trace_context>\n{json_text}\n</stack_trace_context>")
+        })
+        .collect::<Vec<_>>();
+    Some(blocks.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_stack_trace_prefix, detect_stack_frames, resolve_stack_frames};
+
+    fn locations(text: &str) -> Vec<String> {
+        detect_stack_frames(text)
+            .iter()
+            .map(|frame| frame.location())
+            .collect()
+    }
+
+    #[test]
+    fn detects_frames_across_common_trace_formats() {
+        let rust =
+            "thread 'main' panicked at src/main.rs:12:5:\nboom\nnote: run with RUST_BACKTRACE=1";
+        assert_eq!(locations(rust), vec!["src/main.rs:12:5"]);
+
+        let python = "Traceback (most recent call last):\n  File \"app/cli.py\", line 40, in main\n    run()\n  File \"/usr/lib/python3.12/json/__init__.py\", line 7, in loads\nValueError: bad";
+        assert_eq!(
+            locations(python),
+            vec!["app/cli.py:40", "/usr/lib/python3.12/json/__init__.py:7"]
+        );
+
+        let node = "TypeError: x is undefined\n    at run (file:///repo/src/index.ts:3:14)\n    at main (src/index.ts:9:1)\n    at src/index.ts:9:1";
+        assert_eq!(
+            locations(node),
+            vec!["/repo/src/index.ts:3:14", "src/index.ts:9:1"]
+        );
+
+        let java = "java.lang.IllegalStateException: no\n\tat com.acme.App.run(App.java:21)";
+        assert_eq!(locations(java), vec!["App.java:21"]);
+
+        assert!(locations("see https://example.com/a.js:3:1\nand version 1.2:3").is_empty());
+        assert!(locations("src/main.rs:1:1").is_empty());
+    }
+
+    #[test]
+    fn resolves_workspace_frames_with_excerpts() {
+        let root = std::env::temp_dir().join(format!("codelia-trace-test-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("src")).expect("mkdir");
+        std::fs::write(root.join("src/lib.rs"), "a\nb\nc\nd\ne\nf\n").expect("write");
+
+        let frames = detect_stack_frames("Error: x\n  at src/lib.rs:3:1\n  at src/gone.rs:1");
+        let resolved = resolve_stack_frames(&frames, &root);
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert!(resolved[0].in_workspace);
+        assert_eq!(
+            resolved[0].excerpt.as_deref(),
+            Some("  1 | a\n  2 | b\n> 3 | c\n  4 | d\n  5 | e")
+        );
+        assert!(resolved[1].resolved.is_none());
+
+        let prefix = build_stack_trace_prefix(&[resolved]).expect("prefix");
+        assert!(prefix.starts_with("<stack_trace_context>\n{\"frames\":["));
+        assert!(prefix.contains("\"path\":\"src/lib.rs\""));
+        assert!(prefix.contains("\\u003e 3 | c"));
+    }
+}
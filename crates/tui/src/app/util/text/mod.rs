@@ -79,7 +79,7 @@ pub fn detect_continuation_prefix(line: &str) -> Option<String> {
 
     let mut rest_chars = rest.chars();
     if let (Some(marker), Some(space)) = (rest_chars.next(), rest_chars.next()) {
-        if matches!(marker, '-' | '*' | '+') && space == ' ' {
+        if matches!(marker, '-' | '*' | '+' | '•' | '◦' | '▪') && space == ' ' {
             let continuation = " ".repeat(text_width(&format!("{marker}{space}")));
             return Some(format!("{indent}{continuation}"));
         }
@@ -180,6 +180,19 @@ pub fn wrap_line_with_continuation(
     out
 }
 
+/// Keeps the last `max` chars behind a leading `…`; the tail of a path is the part that tells entries apart.
+pub fn truncate_start(value: &str, max: usize) -> String {
+    let count = value.chars().count();
+    if count <= max {
+        return value.to_string();
+    }
+    let tail = value
+        .chars()
+        .skip(count + 1 - max.max(1))
+        .collect::<String>();
+    format!("…{tail}")
+}
+
 pub fn sanitize_paste(value: &str) -> String {
     let mut out = String::new();
     let mut chars = value.chars().peekable();
@@ -280,7 +293,15 @@ pub fn sanitize_for_tui(value: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{detect_continuation_prefix, sanitize_paste, wrap_line_with_continuation};
+    use super::{
+        detect_continuation_prefix, sanitize_paste, truncate_start, wrap_line_with_continuation,
+    };
+
+    #[test]
+    fn truncate_start_keeps_the_tail() {
+        assert_eq!(truncate_start("/home/me/repo", 20), "/home/me/repo");
+        assert_eq!(truncate_start("/home/me/repo", 6), "…/repo");
+    }
 
     #[test]
     fn detect_continuation_prefix_for_unordered_list() {
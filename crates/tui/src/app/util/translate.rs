@@ -0,0 +1,211 @@
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, VecDeque};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+const TRANSLATE_TIMEOUT_SECS: u64 = 30;
+const API_KEY_ENV: &str = "CODELIA_TRANSLATE_API_KEY";
+
+/// `ja`, `pt-BR`, `zh-Hans`: a 2-3 letter language code with an optional region or script.
+pub(crate) fn parse_language(value: &str) -> Option<String> {
+    let value = value.trim();
+    let (language, subtag) = match value.split_once('-') {
+        Some((language, subtag)) => (language, Some(subtag)),
+        None => (value, None),
+    };
+    let language_ok =
+        (2..=3).contains(&language.len()) && language.chars().all(|ch| ch.is_ascii_alphabetic());
+    let subtag_ok = subtag.is_none_or(|subtag| {
+        (2..=4).contains(&subtag.len()) && subtag.chars().all(|ch| ch.is_ascii_alphanumeric())
+    });
+    (language_ok && subtag_ok).then(|| value.to_string())
+}
+
+/// LibreTranslate's `/translate` request; other services can sit behind a small adapter.
+pub(crate) fn translation_request_body(text: &str, target: &str, api_key: Option<&str>) -> Value {
+    let mut body = json!({
+        "q": text,
+        "source": "auto",
+        "target": target,
+        "format": "text",
+    });
+    if let Some(api_key) = api_key {
+        body["api_key"] = json!(api_key);
+    }
+    body
+}
+
+/// `translatedText` (LibreTranslate) or a plain `translation` field.
+pub(crate) fn parse_translation_response(value: &Value) -> Result<String, String> {
+    if let Some(error) = value.get("error").and_then(Value::as_str) {
+        return Err(error.to_string());
+    }
+    value
+        .get("translatedText")
+        .or_else(|| value.get("translation"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| "response has no translatedText".to_string())
+}
+
+fn post_translation(endpoint: &str, text: &str, target: &str) -> Result<String, String> {
+    let api_key = std::env::var(API_KEY_ENV)
+        .ok()
+        .filter(|key| !key.trim().is_empty());
+    let body = translation_request_body(text, target, api_key.as_deref());
+    // The body goes through stdin so neither the answer nor the key shows in the process list.
+    let mut child = Command::new("curl")
+        .args([
+            "-sSf",
+            "--max-time",
+            &TRANSLATE_TIMEOUT_SECS.to_string(),
+            "-H",
+            "Content-Type: application/json",
+            "-H",
+            "Accept: application/json",
+            "-H",
+            "User-Agent: codelia-tui",
+            "--data-binary",
+            "@-",
+            endpoint,
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("failed to run curl: {error}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        use std::io::Write;
+        let _ = stdin.write_all(body.to_string().as_bytes());
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|error| format!("curl failed: {error}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(if stderr.is_empty() {
+            format!("curl exited with {}", output.status)
+        } else {
+            stderr
+        });
+    }
+    let value: Value = serde_json::from_slice(&output.stdout)
+        .map_err(|error| format!("invalid translation response: {error}"))?;
+    parse_translation_response(&value)
+}
+
+/// Translations kept for `/translate show <turn>`; older ones are dropped first.
+const KEPT_TRANSLATIONS: usize = 20;
+
+pub(crate) struct TranslationResult {
+    pub language: String,
+    /// `LogMeta::turn` of the translated answer.
+    pub turn: u32,
+    pub result: Result<String, String>,
+}
+
+struct TranslationJob {
+    language: String,
+    turn: u32,
+    receiver: Receiver<Result<String, String>>,
+}
+
+/// `/translate <lang>` mode plus the answers being translated, drained in the order they were
+/// sent; each result carries the turn of its answer so the block can be placed under it.
+#[derive(Default)]
+pub(crate) struct TranslationState {
+    pub target: Option<String>,
+    jobs: VecDeque<TranslationJob>,
+    /// Language and text of each finished translation by answer turn, for `/translate show`.
+    texts: BTreeMap<u32, (String, String)>,
+}
+
+impl TranslationState {
+    pub(crate) fn spawn(&mut self, endpoint: &str, text: &str, language: &str, turn: u32) {
+        let (sender, receiver) = mpsc::channel();
+        let endpoint = endpoint.to_string();
+        let text = text.to_string();
+        let target = language.to_string();
+        std::thread::spawn(move || {
+            let _ = sender.send(post_translation(&endpoint, &text, &target));
+        });
+        self.jobs.push_back(TranslationJob {
+            language: language.to_string(),
+            turn,
+            receiver,
+        });
+    }
+
+    #[cfg(test)]
+    pub(crate) fn push_finished(&mut self, language: &str, turn: u32, result: Result<&str, &str>) {
+        let (sender, receiver) = mpsc::channel();
+        let _ = sender.send(result.map(str::to_string).map_err(str::to_string));
+        self.jobs.push_back(TranslationJob {
+            language: language.to_string(),
+            turn,
+            receiver,
+        });
+    }
+
+    pub(crate) fn remember(&mut self, turn: u32, language: String, text: String) {
+        self.texts.insert(turn, (language, text));
+        while self.texts.len() > KEPT_TRANSLATIONS {
+            self.texts.pop_first();
+        }
+    }
+
+    /// The translation of `turn`'s answer, or the latest one when `turn` is `None`.
+    pub(crate) fn text_for(&self, turn: Option<u32>) -> Option<(u32, &str, &str)> {
+        let (turn, (language, text)) = match turn {
+            Some(turn) => self.texts.get_key_value(&turn)?,
+            None => self.texts.last_key_value()?,
+        };
+        Some((*turn, language.as_str(), text.as_str()))
+    }
+
+    pub(crate) fn pending(&self) -> usize {
+        self.jobs.len()
+    }
+
+    pub(crate) fn poll(&mut self) -> Option<TranslationResult> {
+        let result = match self.jobs.front()?.receiver.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => Err("translation worker stopped".to_string()),
+        };
+        let job = self.jobs.pop_front()?;
+        Some(TranslationResult {
+            language: job.language,
+            turn: job.turn,
+            result,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_language, parse_translation_response, translation_request_body};
+    use serde_json::json;
+
+    #[test]
+    fn libretranslate_requests_and_responses() {
+        assert_eq!(parse_language("ja").as_deref(), Some("ja"));
+        assert_eq!(parse_language("pt-BR").as_deref(), Some("pt-BR"));
+        assert_eq!(parse_language("zh-Hans").as_deref(), Some("zh-Hans"));
+        assert_eq!(parse_language("japanese"), None);
+        assert_eq!(parse_language("en-"), None);
+
+        assert_eq!(
+            translation_request_body("Done.", "ja", Some("k")),
+            json!({"q": "Done.", "source": "auto", "target": "ja", "format": "text", "api_key": "k"})
+        );
+        assert_eq!(
+            parse_translation_response(&json!({"translatedText": "完了しました。"})),
+            Ok("完了しました。".to_string())
+        );
+        assert_eq!(
+            parse_translation_response(&json!({"error": "ja is not supported"})),
+            Err("ja is not supported".to_string())
+        );
+    }
+}
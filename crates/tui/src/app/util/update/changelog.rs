@@ -0,0 +1,150 @@
+use super::parse_version;
+
+/// Release notes shipped in the binary; see the header of the file for the format.
+pub(crate) const CHANGELOG: &str = include_str!("../../../../CHANGELOG.md");
+
+/// Heading of the notes not released yet.
+pub(crate) const UNRELEASED: &str = "Unreleased";
+
+/// One `## <version>` (or `## Unreleased`) section of the changelog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ChangelogSection {
+    pub(crate) version: String,
+    pub(crate) entries: Vec<String>,
+}
+
+/// `- ` bullets under `## <version>` and `## Unreleased` headings, in file order (newest first).
+/// Text before the first heading and other headings are skipped.
+pub(crate) fn parse_changelog(text: &str) -> Vec<ChangelogSection> {
+    let mut sections: Vec<ChangelogSection> = Vec::new();
+    let mut in_version = false;
+    for line in text.lines() {
+        if let Some(heading) = line.strip_prefix("## ") {
+            let version = heading.trim();
+            in_version = version == UNRELEASED || parse_version(version).is_some();
+            if in_version {
+                sections.push(ChangelogSection {
+                    version: version.trim_start_matches('v').to_string(),
+                    entries: Vec::new(),
+                });
+            }
+            continue;
+        }
+        let Some(section) = sections.last_mut().filter(|_| in_version) else {
+            continue;
+        };
+        if let Some(entry) = line.strip_prefix("- ") {
+            section.entries.push(entry.trim().to_string());
+        } else if line.starts_with("  ") && !line.trim().is_empty() {
+            // Continuation of a wrapped bullet.
+            if let Some(entry) = section.entries.last_mut() {
+                entry.push(' ');
+                entry.push_str(line.trim());
+            }
+        }
+    }
+    sections
+}
+
+/// The `## Unreleased` notes when they describe `current`: no listed version has reached it
+/// yet, so the release that will be renamed from them is this build.
+pub(crate) fn unreleased_for<'a>(
+    sections: &'a [ChangelogSection],
+    current: &str,
+) -> Option<&'a ChangelogSection> {
+    let current = parse_version(current)?;
+    let released_up_to_current = sections
+        .iter()
+        .any(|section| parse_version(&section.version).is_some_and(|version| version >= current));
+    if released_up_to_current {
+        return None;
+    }
+    sections
+        .iter()
+        .find(|section| section.version == UNRELEASED)
+}
+
+/// Sections released after `last_run` up to and including `current`, led by the unreleased
+/// notes when they belong to `current`.
+pub(crate) fn sections_since(
+    sections: &[ChangelogSection],
+    last_run: &str,
+    current: &str,
+) -> Vec<ChangelogSection> {
+    let (Some(last_run_version), Some(current_version)) =
+        (parse_version(last_run), parse_version(current))
+    else {
+        return Vec::new();
+    };
+    if last_run_version >= current_version {
+        return Vec::new();
+    }
+    unreleased_for(sections, current)
+        .into_iter()
+        .chain(sections.iter().filter(|section| {
+            parse_version(&section.version)
+                .is_some_and(|version| version > last_run_version && version <= current_version)
+        }))
+        .cloned()
+        .collect()
+}
+
+/// First backticked `/command` in an entry, without its arguments: the entry's deep link.
+pub(crate) fn entry_command(entry: &str) -> Option<String> {
+    entry
+        .split('`')
+        .skip(1)
+        .step_by(2)
+        .find(|code| code.starts_with('/') && code.len() > 1)
+        .and_then(|code| code.split_whitespace().next())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{entry_command, parse_changelog, sections_since, unreleased_for, CHANGELOG};
+
+    #[test]
+    fn sections_between_versions_and_their_command_links() {
+        let text = "# notes\n\n- ignored\n\n## Unreleased\n\n- `/next` command\n\n## 0.2.0\n\n- `/glyphs [ascii]` picks glyphs\n  for old fonts.\n- Faster startup.\n\n## Other\n\n- skipped\n\n## 0.1.9\n\n- `@dir:` trees\n";
+        let mut sections = parse_changelog(text);
+        assert_eq!(sections.len(), 3);
+        assert_eq!(
+            sections_since(&sections, "0.2.0", "0.2.1")
+                .iter()
+                .map(|section| section.version.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Unreleased"]
+        );
+        assert!(unreleased_for(&sections, "0.2.0").is_none());
+        sections.remove(0);
+        assert_eq!(
+            sections[0].entries,
+            vec![
+                "`/glyphs [ascii]` picks glyphs for old fonts.",
+                "Faster startup."
+            ]
+        );
+        assert_eq!(
+            sections_since(&sections, "0.1.9", "0.2.0")
+                .iter()
+                .map(|section| section.version.as_str())
+                .collect::<Vec<_>>(),
+            vec!["0.2.0"]
+        );
+        assert_eq!(sections_since(&sections, "0.1.0", "0.1.9").len(), 1);
+        assert!(sections_since(&sections, "0.2.0", "0.2.0").is_empty());
+        assert!(sections_since(&sections, "dev", "0.2.0").is_empty());
+
+        assert_eq!(
+            entry_command(&sections[0].entries[0]).as_deref(),
+            Some("/glyphs")
+        );
+        assert_eq!(entry_command("`@dir:` trees"), None);
+        assert_eq!(
+            entry_command("`/` alone and `/tab` later").as_deref(),
+            Some("/tab")
+        );
+        assert!(!parse_changelog(CHANGELOG).is_empty());
+    }
+}
@@ -0,0 +1,105 @@
+pub(crate) mod changelog;
+
+use serde_json::Value;
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver};
+
+const LATEST_RELEASE_URL: &str = "https://registry.npmjs.org/@codelia/cli/latest";
+const UPDATE_FETCH_TIMEOUT_SECS: u64 = 5;
+pub(crate) const UPGRADE_COMMAND: &str = "npm i -g @codelia/cli";
+
+/// `major.minor.patch` with an optional `v` prefix; pre-release/build suffixes are ignored.
+pub(crate) fn parse_version(value: &str) -> Option<(u64, u64, u64)> {
+    let core = value
+        .trim()
+        .trim_start_matches('v')
+        .split(['-', '+'])
+        .next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+    let version = (parts.next()??, parts.next()??, parts.next()??);
+    parts.next().is_none().then_some(version)
+}
+
+pub(crate) fn is_newer_version(latest: &str, current: &str) -> bool {
+    match (parse_version(latest), parse_version(current)) {
+        (Some(latest), Some(current)) => latest > current,
+        _ => false,
+    }
+}
+
+/// The CLI wrapper exports its package version; a bare TUI binary falls back to the crate version.
+pub(crate) fn current_version() -> String {
+    std::env::var("CODELIA_CLI_VERSION")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| env!("CARGO_PKG_VERSION").to_string())
+}
+
+fn fetch_latest_version() -> Result<String, String> {
+    let output = Command::new("curl")
+        .args([
+            "-sSfL",
+            "--max-time",
+            &UPDATE_FETCH_TIMEOUT_SECS.to_string(),
+            "-H",
+            "Accept: application/json",
+            "-H",
+            "User-Agent: codelia-tui",
+            LATEST_RELEASE_URL,
+        ])
+        .output()
+        .map_err(|error| format!("failed to run curl: {error}"))?;
+    if !output.status.success() {
+        return Err(format!("curl exited with {}", output.status));
+    }
+    let value: Value = serde_json::from_slice(&output.stdout)
+        .map_err(|error| format!("invalid registry response: {error}"))?;
+    value
+        .get("version")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| "registry response has no version".to_string())
+}
+
+/// One background lookup per process; the run loop polls it like the forge fetches.
+#[derive(Default)]
+pub(crate) struct UpdateCheckState {
+    receiver: Option<Receiver<Result<String, String>>>,
+}
+
+impl UpdateCheckState {
+    pub(crate) fn spawn(&mut self) {
+        if self.receiver.is_some() {
+            return;
+        }
+        let (sender, receiver) = mpsc::channel();
+        self.receiver = Some(receiver);
+        std::thread::spawn(move || {
+            let _ = sender.send(fetch_latest_version());
+        });
+    }
+
+    pub(crate) fn poll(&mut self) -> Option<Result<String, String>> {
+        let result = self.receiver.as_ref()?.try_recv().ok()?;
+        self.receiver = None;
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_newer_version, parse_version};
+
+    #[test]
+    fn version_comparison_is_numeric_and_ignores_suffixes() {
+        assert_eq!(parse_version("v0.1.75"), Some((0, 1, 75)));
+        assert_eq!(parse_version("1.2.3-beta.1"), Some((1, 2, 3)));
+        assert_eq!(parse_version("1.2"), None);
+        assert_eq!(parse_version("1.2.3.4"), None);
+        assert!(is_newer_version("0.1.100", "0.1.75"));
+        assert!(is_newer_version("0.2.0", "0.1.75"));
+        assert!(!is_newer_version("0.1.75", "0.1.75"));
+        assert!(!is_newer_version("garbage", "0.1.75"));
+    }
+}
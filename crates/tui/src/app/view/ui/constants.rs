@@ -6,7 +6,15 @@ pub(super) const MAX_INPUT_HEIGHT: u16 = 6;
 pub(super) const INPUT_PADDING_X: u16 = 2;
 pub(super) const INPUT_PADDING_Y: u16 = 1;
 pub(super) const PANEL_GAP: u16 = 1;
-pub(super) const DEBUG_PANEL_HEIGHT: u16 = 3;
+pub(super) const DEBUG_PANEL_HEIGHT: u16 = 5;
+/// List panels with wide rows switch to them at this content width.
+pub(super) const WIDE_LIST_MIN_WIDTH: usize = 140;
+/// Below this the minimap column is dropped rather than squeezing the log further.
+pub(super) const MINIMAP_MIN_TERMINAL_WIDTH: u16 = 40;
+/// Below this the split layout collapses back to the log alone.
+pub(super) const SPLIT_PANE_MIN_TERMINAL_WIDTH: u16 = 120;
+/// Share of the terminal width the tool pane takes, in percent.
+pub(super) const SPLIT_PANE_PERCENT: u16 = 40;
 
 pub(super) fn input_bg() -> Color {
     ui_colors().input_bg
@@ -1,11 +1,12 @@
-use crate::app::state::InputState;
+use crate::app::state::{ComposerWrap, InputState};
 use crate::app::util::attachments::render_input_with_attachment_labels;
 use crate::app::util::text::{char_width, detect_continuation_prefix};
 use crate::app::AppState;
 use ratatui::layout::Rect;
-use ratatui::style::Style;
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::Paragraph;
+use std::ops::Range;
 
 use super::super::theme::ui_colors;
 use super::constants::input_bg;
@@ -14,6 +15,17 @@ pub(super) struct InputLayout {
     pub(super) lines: Vec<String>,
     pub(super) cursor_x: u16,
     pub(super) cursor_y: u16,
+    /// Selected columns per row, drawn reversed.
+    pub(super) selected: Vec<(usize, Range<usize>)>,
+}
+
+fn mark_selected(selected: &mut Vec<(usize, Range<usize>)>, row: usize, columns: Range<usize>) {
+    match selected.last_mut() {
+        Some((last_row, last)) if *last_row == row && last.end == columns.start => {
+            last.end = columns.end;
+        }
+        _ => selected.push((row, columns)),
+    }
 }
 
 fn text_width(text: &str) -> usize {
@@ -32,18 +44,29 @@ fn input_prefix(line_index: usize, bang_mode: bool) -> &'static str {
     }
 }
 
+/// Narrowest text area (after the `> ` prefix) that still scrolls sideways; below it the
+/// markers and the cursor would not fit, so the layout soft-wraps instead.
+const MIN_SCROLL_TEXT_WIDTH: usize = 4;
+
 pub(super) fn compute_input_layout(
     width: usize,
     input: &InputState,
     bang_mode: bool,
+    wrap: ComposerWrap,
 ) -> InputLayout {
     if width == 0 {
         return InputLayout {
             lines: vec![String::new()],
             cursor_x: 0,
             cursor_y: 0,
+            selected: Vec::new(),
         };
     }
+    if wrap == ComposerWrap::Scroll
+        && width.saturating_sub(text_width(input_prefix(0, bang_mode))) >= MIN_SCROLL_TEXT_WIDTH
+    {
+        return compute_scrolled_input_layout(width, input, bang_mode);
+    }
 
     let logical_text: String = input.buffer.iter().collect();
     let logical_continuation_prefixes: Vec<String> = logical_text
@@ -63,6 +86,8 @@ pub(super) fn compute_input_layout(
     let mut cursor_x = col;
     let mut cursor_y = 0_usize;
     let mut cursor_set = cursor == 0;
+    let selection = input.selection().unwrap_or_default();
+    let mut selected = Vec::new();
 
     for (idx, &ch) in input.buffer.iter().enumerate() {
         if idx == cursor && !cursor_set {
@@ -101,6 +126,9 @@ pub(super) fn compute_input_layout(
             line_prefix_width = col;
         }
 
+        if selection.contains(&idx) {
+            mark_selected(&mut selected, line_index, col..col + ch_width);
+        }
         line.push(ch);
         col += ch_width;
     }
@@ -120,6 +148,104 @@ pub(super) fn compute_input_layout(
         lines,
         cursor_x,
         cursor_y,
+        selected,
+    }
+}
+
+/// Columns `[offset, offset + width)` of `line`; a wide char cut by either edge becomes a space.
+fn column_window(line: &[char], offset: usize, width: usize) -> String {
+    let mut out = String::new();
+    let mut col = 0;
+    let mut used = 0;
+    for &ch in line {
+        let ch_width = char_width(ch);
+        let start = col;
+        col += ch_width;
+        if col <= offset {
+            continue;
+        }
+        if start < offset {
+            out.push_str(&" ".repeat(col - offset));
+            used += col - offset;
+            continue;
+        }
+        if used + ch_width > width {
+            break;
+        }
+        out.push(ch);
+        used += ch_width;
+    }
+    out
+}
+
+/// `composer_wrap = scroll`: one row per logical line, all shifted by the same horizontal
+/// offset so the cursor stays one column short of the right edge. `«` marks text hidden on the
+/// left, `»` text continuing past the right edge.
+fn compute_scrolled_input_layout(width: usize, input: &InputState, bang_mode: bool) -> InputLayout {
+    let text_area = width - text_width(input_prefix(0, bang_mode));
+    let cursor = input.cursor.min(input.buffer.len());
+    let logical_lines = input.buffer.split(|ch| *ch == '\n').collect::<Vec<_>>();
+    let before_cursor = &input.buffer[..cursor];
+    let cursor_y = before_cursor.iter().filter(|ch| **ch == '\n').count();
+    let line_start = before_cursor
+        .iter()
+        .rposition(|ch| *ch == '\n')
+        .map_or(0, |index| index + 1);
+    let cursor_col = input.buffer[line_start..cursor]
+        .iter()
+        .copied()
+        .map(char_width)
+        .sum::<usize>();
+    let offset = (cursor_col + 2).saturating_sub(text_area);
+    let selection = input.selection().unwrap_or_default();
+    let mut selected = Vec::new();
+    let mut line_start_index = 0;
+
+    let lines = logical_lines
+        .iter()
+        .enumerate()
+        .map(|(index, line)| {
+            let prefix = input_prefix(index, bang_mode);
+            let line_width = line.iter().copied().map(char_width).sum::<usize>();
+            let hidden_left = offset > 0 && line_width > 0;
+            let hidden_right = line_width > offset + text_area;
+            let inner_offset = offset + usize::from(hidden_left);
+            let inner_width = text_area - usize::from(hidden_left) - usize::from(hidden_right);
+            let visible = column_window(line, inner_offset, inner_width);
+            let text_start = text_width(prefix) + usize::from(hidden_left);
+            let mut col = 0;
+            for (char_index, &ch) in line.iter().enumerate() {
+                let ch_width = char_width(ch);
+                if selection.contains(&(line_start_index + char_index))
+                    && col >= inner_offset
+                    && col + ch_width <= inner_offset + inner_width
+                {
+                    let start = text_start + col - inner_offset;
+                    mark_selected(&mut selected, index, start..start + ch_width);
+                }
+                col += ch_width;
+            }
+            line_start_index += line.len() + 1;
+            let mut row = String::from(prefix);
+            if hidden_left {
+                row.push('«');
+            }
+            row.push_str(&visible);
+            if hidden_right {
+                let pad = inner_width.saturating_sub(text_width(&visible));
+                row.push_str(&" ".repeat(pad));
+                row.push('»');
+            }
+            row
+        })
+        .collect::<Vec<_>>();
+
+    let cursor_x = text_width(input_prefix(cursor_y, bang_mode)) + cursor_col - offset;
+    InputLayout {
+        lines,
+        cursor_x: cursor_x.min(width.saturating_sub(1)) as u16,
+        cursor_y: cursor_y as u16,
+        selected,
     }
 }
 
@@ -171,6 +297,22 @@ pub(super) fn render_input(
             .style(Style::default().fg(ui_colors().surface_fg).bg(input_bg())),
         area,
     );
+    for (row, columns) in &layout.selected {
+        if *row < start || *row >= end {
+            continue;
+        }
+        let x = columns.start.min(area.width as usize) as u16;
+        let selected_area = Rect {
+            x: area.x + x,
+            y: area.y + (row - start) as u16,
+            width: (columns.end.min(area.width as usize) as u16).saturating_sub(x),
+            height: 1,
+        };
+        f.buffer_mut().set_style(
+            selected_area,
+            Style::default().add_modifier(Modifier::REVERSED),
+        );
+    }
 
     let cursor_visible_y = cursor_y.saturating_sub(start).min(height.saturating_sub(1));
     f.set_cursor_position((area.x + layout.cursor_x, area.y + cursor_visible_y as u16));
@@ -215,14 +357,14 @@ pub(super) fn active_input_for_layout<'a>(
 #[cfg(test)]
 mod tests {
     use super::compute_input_layout;
-    use crate::app::state::InputState;
+    use crate::app::state::{ComposerWrap, InputState};
 
     #[test]
     fn input_wrap_keeps_task_list_continuation_indent() {
         let mut input = InputState::default();
         input.set_from("- [x] continuation alignment stays visible");
 
-        let layout = compute_input_layout(16, &input, false);
+        let layout = compute_input_layout(16, &input, false, ComposerWrap::Wrap);
         assert!(layout.lines.len() >= 2);
         assert!(layout.lines[1].starts_with("        "));
     }
@@ -232,7 +374,7 @@ mod tests {
         let mut input = InputState::default();
         input.set_from("    const value = someVeryLongIdentifier");
 
-        let layout = compute_input_layout(16, &input, false);
+        let layout = compute_input_layout(16, &input, false, ComposerWrap::Wrap);
         assert!(layout.lines.len() >= 2);
         assert!(layout.lines[1].starts_with("      "));
     }
@@ -242,8 +384,43 @@ mod tests {
         let mut input = InputState::default();
         input.set_from("- [x] abcdefghij");
 
-        let layout = compute_input_layout(8, &input, false);
+        let layout = compute_input_layout(8, &input, false, ComposerWrap::Wrap);
         assert!(layout.lines.len() >= 2);
         assert_eq!(layout.lines[1], "  abcdef");
     }
+
+    #[test]
+    fn selection_marks_wrapped_and_scrolled_columns() {
+        let mut input = InputState::default();
+        input.set_from("abcdefghij\nxy");
+        input.cursor = 4;
+        input.set_selection_anchor(Some(12));
+
+        let layout = compute_input_layout(8, &input, false, ComposerWrap::Wrap);
+        assert_eq!(layout.lines, vec!["> abcdef", "  ghij", "  xy"]);
+        assert_eq!(layout.selected, vec![(0, 6..8), (1, 2..6), (2, 2..3)]);
+
+        let layout = compute_input_layout(8, &input, false, ComposerWrap::Scroll);
+        assert_eq!(layout.lines, vec!["> abcde»", "  xy"]);
+        assert_eq!(layout.selected, vec![(0, 6..7), (1, 2..3)]);
+    }
+
+    #[test]
+    fn scroll_mode_keeps_one_row_per_line_and_follows_the_cursor() {
+        let mut input = InputState::default();
+        input.set_from("abcdefghijklmnop\nxy");
+        input.cursor = 16;
+
+        let layout = compute_input_layout(12, &input, false, ComposerWrap::Scroll);
+        assert_eq!(layout.lines, vec!["> «jklmnop", "  «"]);
+        assert_eq!((layout.cursor_x, layout.cursor_y), (10, 0));
+
+        input.cursor = 3;
+        let layout = compute_input_layout(12, &input, false, ComposerWrap::Scroll);
+        assert_eq!(layout.lines, vec!["> abcdefghi»", "  xy"]);
+        assert_eq!((layout.cursor_x, layout.cursor_y), (5, 0));
+
+        let wrapped = compute_input_layout(5, &input, false, ComposerWrap::Scroll);
+        assert!(wrapped.lines.len() > 2);
+    }
 }
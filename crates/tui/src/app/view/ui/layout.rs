@@ -1,7 +1,10 @@
 use crate::app::log_wrap::cached_wrap_log_lines;
 use crate::app::AppState;
 
-use super::constants::{DEBUG_PANEL_HEIGHT, INPUT_PADDING_Y, MAX_INPUT_HEIGHT, PANEL_GAP};
+use super::constants::{
+    DEBUG_PANEL_HEIGHT, INPUT_PADDING_Y, MAX_INPUT_HEIGHT, MINIMAP_MIN_TERMINAL_WIDTH, PANEL_GAP,
+    SPLIT_PANE_MIN_TERMINAL_WIDTH, SPLIT_PANE_PERCENT,
+};
 use super::input::{
     active_input_for_layout, compute_input_layout, masked_prompt_input, rendered_main_input,
 };
@@ -22,6 +25,35 @@ pub(super) fn layout_heights(app: &AppState) -> (u16, u16, u16) {
     (run_height, status_height, debug_height)
 }
 
+/// The `accessible` announcement row; kept while a dialog is open, since that is when
+/// "awaiting approval" is read out.
+pub(super) fn announcement_height(app: &AppState) -> u16 {
+    u16::from(app.settings.accessible)
+}
+
+/// Columns the split layout's tool pane takes at the right edge; 0 when the `split_pane`
+/// setting is off or the terminal is too narrow for two panes.
+pub(super) fn tool_pane_width(app: &AppState, width: u16) -> u16 {
+    if app.settings.split_pane && width >= SPLIT_PANE_MIN_TERMINAL_WIDTH {
+        width * SPLIT_PANE_PERCENT / 100
+    } else {
+        0
+    }
+}
+
+/// Log wrap width and whether the minimap takes the last column of the log (enabled and wide
+/// enough). The tool pane, when shown, is already taken off.
+pub(super) fn log_wrap_width(app: &AppState, width: u16) -> (usize, bool) {
+    let width = width - tool_pane_width(app, width);
+    let show_minimap = app.settings.minimap && width >= MINIMAP_MIN_TERMINAL_WIDTH;
+    let log_width = if show_minimap {
+        width.saturating_sub(1)
+    } else {
+        width
+    };
+    (log_width as usize, show_minimap)
+}
+
 pub(crate) fn desired_height(app: &mut AppState, width: u16, height: u16) -> u16 {
     if width == 0 || height == 0 {
         return 0;
@@ -30,13 +62,20 @@ pub(crate) fn desired_height(app: &mut AppState, width: u16, height: u16) -> u16
     let remaining_height = height;
 
     let (run_height, status_height, debug_height) = layout_heights(app);
-    let footer_height = status_height.saturating_add(debug_height);
+    let footer_height = status_height
+        .saturating_add(debug_height)
+        .saturating_add(announcement_height(app));
     let input_width =
         width.saturating_sub(super::constants::INPUT_PADDING_X.saturating_mul(2)) as usize;
     let masked_prompt = masked_prompt_input(app);
     let rendered_main = rendered_main_input(app);
     let active_input = active_input_for_layout(app, &masked_prompt, &rendered_main);
-    let input_layout = compute_input_layout(input_width.max(1), active_input, app.bang_input_mode);
+    let input_layout = compute_input_layout(
+        input_width.max(1),
+        active_input,
+        app.bang_input_mode,
+        app.settings.composer_wrap,
+    );
     let max_input_height = remaining_height
         .saturating_sub(footer_height + INPUT_PADDING_Y.saturating_mul(2))
         .clamp(1, MAX_INPUT_HEIGHT);
@@ -72,11 +111,15 @@ pub(crate) fn desired_height(app: &mut AppState, width: u16, height: u16) -> u16
     }
 
     let max_log_height = remaining_height.saturating_sub(reserved_height);
-    let wrapped_total = cached_wrap_log_lines(app, width as usize).len();
+    let (log_width, _) = log_wrap_width(app, width);
+    let wrapped_total = cached_wrap_log_lines(app, log_width).len();
     let mut desired_log_height = (wrapped_total as u16).min(max_log_height);
     if desired_log_height == 0 && max_log_height > 0 && wrapped_total > 0 {
         desired_log_height = 1;
     }
+    if tool_pane_width(app, width) > 0 {
+        desired_log_height = max_log_height;
+    }
 
     let total = desired_log_height
         .saturating_add(run_height)
@@ -0,0 +1,66 @@
+use crate::app::log_wrap::cached_wrap_log_lines;
+use crate::app::state::{minimap_marks, minimap_viewport_rows, MinimapCache, MinimapMark};
+use crate::app::AppState;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::Paragraph;
+
+use super::super::theme::ui_colors;
+
+fn refresh_minimap_cache(app: &mut AppState, log_width: usize, height: usize) {
+    let fresh = app.minimap_cache.as_ref().is_some_and(|cache| {
+        cache.log_version == app.log_version && cache.width == log_width && cache.height == height
+    });
+    if fresh {
+        return;
+    }
+    let marks = minimap_marks(cached_wrap_log_lines(app, log_width), height);
+    app.minimap_cache = Some(MinimapCache {
+        log_version: app.log_version,
+        width: log_width,
+        height,
+        marks,
+    });
+}
+
+/// One column at the right edge: marks for the whole history, viewport rows drawn as a thumb.
+pub(super) fn render_minimap(
+    f: &mut ratatui::Frame<'_>,
+    app: &mut AppState,
+    area: Rect,
+    log_width: usize,
+) {
+    let height = area.height as usize;
+    refresh_minimap_cache(app, log_width, height);
+    let Some(cache) = app.minimap_cache.as_ref() else {
+        return;
+    };
+    let viewport = minimap_viewport_rows(
+        app.render_state.wrapped_total,
+        height,
+        app.render_state.visible_start,
+        app.render_state.visible_end,
+    );
+    let theme = ui_colors();
+    let lines = cache
+        .marks
+        .iter()
+        .enumerate()
+        .map(|(row, mark)| {
+            let (symbol, fg) = match mark {
+                MinimapMark::Error => ("●", theme.log_error_fg),
+                MinimapMark::User => ("▸", theme.log_primary_fg),
+                MinimapMark::Tool => ("•", theme.log_tool_call_fg),
+                MinimapMark::Text => ("·", theme.log_muted_fg),
+                MinimapMark::Empty => (" ", theme.log_muted_fg),
+            };
+            let mut style = Style::default().fg(fg);
+            if viewport.contains(&row) {
+                style = style.bg(theme.panel_divider_fg);
+            }
+            Line::from(Span::styled(symbol, style))
+        })
+        .collect::<Vec<_>>();
+    f.render_widget(Paragraph::new(Text::from(lines)), area);
+}
@@ -1,11 +1,17 @@
 mod constants;
 mod input;
 mod layout;
+mod minimap;
 mod panels;
 mod status;
 mod text;
+mod tool_pane;
 
-use crate::app::log_wrap::cached_wrap_log_lines;
+use crate::app::log_wrap::{
+    apply_log_hyperlinks, cached_wrap_log_lines, highlight_search_matches,
+    refresh_log_search_index, wrapped_log_range_links,
+};
+use crate::app::state::ScrollAnchor;
 use crate::app::{AppState, SyncPhase};
 use ratatui::layout::Rect;
 use ratatui::text::{Line, Text};
@@ -15,9 +21,13 @@ use self::constants::{INPUT_PADDING_X, INPUT_PADDING_Y, MAX_INPUT_HEIGHT, PANEL_
 use self::input::{
     active_input_for_layout, compute_input_layout, masked_prompt_input, rendered_main_input,
 };
-use self::layout::layout_heights;
+use self::layout::{announcement_height, layout_heights, log_wrap_width, tool_pane_width};
+use self::minimap::render_minimap;
 use self::panels::{build_panel_render, build_panel_view, render_input_panel};
-use self::status::{build_debug_perf_lines, build_run_line, build_status_line};
+use self::status::{
+    build_announcement_line, build_debug_perf_lines, build_run_line, build_status_line,
+};
+use self::tool_pane::render_tool_pane;
 
 pub(crate) use crate::app::log_wrap::wrapped_log_range_to_lines;
 pub(crate) use layout::desired_height;
@@ -34,6 +44,33 @@ fn reconcile_insertion_boundary_for_wrap_change(app: &mut AppState, wrap_width_c
     }
 }
 
+/// While scrolled back, keeps the anchored content at the top of the log view across
+/// appends, resizes, and re-wraps. A `scroll_from_bottom` that no longer matches the anchor
+/// means the user scrolled, and that offset wins.
+fn restore_scroll_anchor(app: &mut AppState, wrapped_total: usize, log_height: usize) {
+    let Some(anchor) = app.scroll_anchor else {
+        return;
+    };
+    if anchor.scroll_from_bottom != app.scroll_from_bottom {
+        return;
+    }
+    let Some(top) = app
+        .wrapped_log_cache
+        .as_ref()
+        .and_then(|cache| cache.resolve_anchor(&anchor))
+    else {
+        return;
+    };
+    app.scroll_from_bottom = wrapped_total
+        .saturating_sub(log_height)
+        .saturating_sub(top)
+        .max(1);
+    app.scroll_anchor = Some(ScrollAnchor {
+        scroll_from_bottom: app.scroll_from_bottom,
+        ..anchor
+    });
+}
+
 fn update_render_visible_range(
     app: &mut AppState,
     wrapped_total: usize,
@@ -75,13 +112,23 @@ pub fn draw_ui(f: &mut ratatui::Frame<'_>, app: &mut AppState) {
     }
 
     let (run_height, status_height, debug_height) = layout_heights(app);
-    let footer_height = status_height.saturating_add(debug_height);
-    let log_width = size.width as usize;
+    let announcement_height = announcement_height(app);
+    let footer_height = status_height
+        .saturating_add(debug_height)
+        .saturating_add(announcement_height);
+    let (log_width, show_minimap) = log_wrap_width(app, size.width);
+    let pane_width = tool_pane_width(app, size.width);
+    app.tool_pane_shown = pane_width > 0;
     let input_width = size.width.saturating_sub(INPUT_PADDING_X.saturating_mul(2)) as usize;
     let masked_prompt = masked_prompt_input(app);
     let rendered_main = rendered_main_input(app);
     let active_input = active_input_for_layout(app, &masked_prompt, &rendered_main);
-    let input_layout = compute_input_layout(input_width.max(1), active_input, app.bang_input_mode);
+    let input_layout = compute_input_layout(
+        input_width.max(1),
+        active_input,
+        app.bang_input_mode,
+        app.settings.composer_wrap,
+    );
     let max_input_height = remaining_height
         .saturating_sub(footer_height + INPUT_PADDING_Y.saturating_mul(2))
         .clamp(1, MAX_INPUT_HEIGHT);
@@ -124,16 +171,16 @@ pub fn draw_ui(f: &mut ratatui::Frame<'_>, app: &mut AppState) {
     if desired_log_height == 0 && max_log_height > 0 && wrapped_total > 0 {
         desired_log_height = 1;
     }
-
-    // Keep scroll stable while the user is in scrollback mode.
-    if app.log_changed && app.scroll_from_bottom > 0 && app.last_wrap_width == log_width {
-        let added = wrapped_total.saturating_sub(app.last_wrapped_total);
-        app.scroll_from_bottom = app.scroll_from_bottom.saturating_add(added);
+    // The pane gets the full log height so output has room before the conversation fills it.
+    if pane_width > 0 {
+        desired_log_height = max_log_height;
     }
+
     app.log_changed = false;
     app.last_log_viewport_height = max_log_height as usize;
 
     let log_height = desired_log_height as usize;
+    restore_scroll_anchor(app, wrapped_total, log_height);
     let max_scroll = wrapped_total.saturating_sub(log_height);
     if app.scroll_from_bottom > max_scroll {
         app.scroll_from_bottom = max_scroll;
@@ -142,11 +189,17 @@ pub fn draw_ui(f: &mut ratatui::Frame<'_>, app: &mut AppState) {
     let log_area = Rect {
         x: size.x,
         y: size.y,
-        width: size.width,
+        width: log_width as u16,
         height: desired_log_height,
     };
     let raw_visible_start =
         wrapped_total.saturating_sub(log_height.saturating_add(app.scroll_from_bottom));
+    app.scroll_anchor = match app.wrapped_log_cache.as_ref() {
+        Some(cache) if app.scroll_from_bottom > 0 => {
+            cache.anchor_at(raw_visible_start, app.scroll_from_bottom)
+        }
+        _ => None,
+    };
     let wrap_width_changed = app.last_wrap_width != 0 && app.last_wrap_width != log_width;
     reconcile_insertion_boundary_for_wrap_change(app, wrap_width_changed);
     if app.render_state.inserted_until > wrapped_total {
@@ -159,9 +212,36 @@ pub fn draw_ui(f: &mut ratatui::Frame<'_>, app: &mut AppState) {
     update_render_visible_range(app, wrapped_total, visible_start, visible_end);
 
     if log_area.height > 0 {
-        let visible: Vec<Line> =
+        refresh_log_search_index(app, log_width);
+        let mut visible: Vec<Line> =
             wrapped_log_range_to_lines(app, log_width, visible_start, visible_end);
+        if let Some(search) = app.log_search.as_ref() {
+            highlight_search_matches(
+                &mut visible,
+                visible_start,
+                search.matches(),
+                search.current_match(),
+            );
+        }
         f.render_widget(Paragraph::new(Text::from(visible)), log_area);
+        let links = wrapped_log_range_links(app, log_width, visible_start, visible_end);
+        apply_log_hyperlinks(f.buffer_mut(), log_area, &links);
+        if show_minimap {
+            let minimap_area = Rect {
+                x: size.x + log_area.width,
+                width: 1,
+                ..log_area
+            };
+            render_minimap(f, app, minimap_area, log_width);
+        }
+    }
+    if pane_width > 0 {
+        let pane_area = Rect {
+            x: size.x + size.width - pane_width,
+            width: pane_width,
+            ..log_area
+        };
+        render_tool_pane(f, &app.tool_pane, pane_area);
     }
 
     let input_area = Rect {
@@ -217,7 +297,22 @@ pub fn draw_ui(f: &mut ratatui::Frame<'_>, app: &mut AppState) {
         }
     }
 
-    app.last_wrapped_total = wrapped_total;
+    let announcement_area = Rect {
+        x: size.x,
+        y: debug_area.y + debug_height,
+        width: size.width,
+        height: announcement_height,
+    };
+    if announcement_area.height > 0 {
+        let line = build_announcement_line(app);
+        let end = (line.width() as u16).min(announcement_area.width.saturating_sub(1));
+        f.render_widget(Paragraph::new(Text::from(vec![line])), announcement_area);
+        // Overrides the composer cursor until the next key.
+        if app.live_region.holds_cursor() {
+            f.set_cursor_position((announcement_area.x + end, announcement_area.y));
+        }
+    }
+
     app.last_wrap_width = log_width;
     app.render_state.cursor_phase = crate::app::CursorPhase::VisibleAtComposer;
     app.assert_render_invariants();
@@ -225,7 +320,12 @@ pub fn draw_ui(f: &mut ratatui::Frame<'_>, app: &mut AppState) {
 
 #[cfg(test)]
 mod tests {
-    use super::{reconcile_insertion_boundary_for_wrap_change, update_render_visible_range};
+    use super::{
+        reconcile_insertion_boundary_for_wrap_change, restore_scroll_anchor,
+        update_render_visible_range,
+    };
+    use crate::app::log_wrap::cached_wrap_log_lines;
+    use crate::app::state::LogKind;
     use crate::app::{AppState, SyncPhase};
 
     #[test]
@@ -274,4 +374,42 @@ mod tests {
         assert_eq!(app.render_state.inserted_until, 12);
         assert_eq!(app.render_state.sync_phase, SyncPhase::Idle);
     }
+
+    #[test]
+    fn scroll_anchor_keeps_top_line_across_resize_and_appends() {
+        let mut app = AppState::default();
+        for index in 0..30 {
+            app.push_line(
+                LogKind::Assistant,
+                format!("line {index} {}", "word ".repeat(8)),
+            );
+        }
+        let log_height = 5;
+        let wrapped_total = cached_wrap_log_lines(&mut app, 80).len();
+        let top = 12;
+        app.scroll_from_bottom = wrapped_total - log_height - top;
+        app.scroll_anchor = app
+            .wrapped_log_cache
+            .as_ref()
+            .and_then(|cache| cache.anchor_at(top, app.scroll_from_bottom));
+
+        // Narrower: every line wraps to more rows, so the row offset from the bottom grows.
+        let wrapped_total = cached_wrap_log_lines(&mut app, 20).len();
+        restore_scroll_anchor(&mut app, wrapped_total, log_height);
+        let top = wrapped_total - log_height - app.scroll_from_bottom;
+        let cache = app.wrapped_log_cache.as_ref().expect("cache");
+        assert_eq!(cache.line_starts[12], top);
+
+        // New output below does not move the view, and scrolling replaces the anchor.
+        let anchored = app.scroll_from_bottom;
+        app.push_line(LogKind::Assistant, "more output");
+        let wrapped_total = cached_wrap_log_lines(&mut app, 20).len();
+        restore_scroll_anchor(&mut app, wrapped_total, log_height);
+        assert_eq!(app.scroll_from_bottom, anchored + 1);
+
+        app.scroll_down(3);
+        let scrolled = app.scroll_from_bottom;
+        restore_scroll_anchor(&mut app, wrapped_total, log_height);
+        assert_eq!(app.scroll_from_bottom, scrolled);
+    }
 }
@@ -1,5 +1,7 @@
+use crate::app::state::HunkReviewState;
+use crate::app::util::merge3::{MergeHunk, MergeHunkKind};
 use crate::app::util::text::wrap_line;
-use crate::app::{ConfirmDialogState, PickDialogState, PromptDialogState};
+use crate::app::{ConfirmDialogState, EditConflictPanelState, PickDialogState, PromptDialogState};
 
 use super::types::PanelView;
 
@@ -8,6 +10,7 @@ const MAX_COMPACT_CONFIRM_LINE_CHARS: usize = 72;
 const TRUNCATION_SUFFIX: &str = "...";
 const COMMAND_PREFIX: &str = "Command: ";
 const REMEMBER_HEADER: &str = "Remember (don't ask again):";
+const MAX_MERGE_SIDE_LINES: usize = 6;
 
 fn split_confirm_message_sections(message: &str) -> (Vec<String>, Vec<String>) {
     let mut lines = message
@@ -106,6 +109,137 @@ fn build_command_review_lines(message: &str) -> Vec<String> {
     lines
 }
 
+fn build_hunk_review_view(review: &HunkReviewState, title: String) -> PanelView {
+    let mut lines = vec![
+        "Hunk review: A accept, X reject, Space expand into log, H/Esc return".to_string(),
+        String::new(),
+    ];
+    let header_len = lines.len();
+    for (index, hunk) in review.hunks.iter().enumerate() {
+        let mark = match hunk.decision {
+            Some(true) => "[✓]",
+            Some(false) => "[✗]",
+            None => "[ ]",
+        };
+        lines.push(format!(
+            "{mark} #{} {}  +{} -{}",
+            index + 1,
+            hunk.label,
+            hunk.added,
+            hunk.removed
+        ));
+    }
+    PanelView {
+        title: Some(title),
+        lines,
+        header_index: None,
+        selected: Some(header_len + review.selected),
+        wrap_lines: false,
+        tail_pinned_from: None,
+        wide_lines: None,
+        underlined: Vec::new(),
+    }
+}
+
+fn push_merge_side(lines: &mut Vec<String>, label: &str, side: &[String]) {
+    lines.push(format!("  {label} ({} lines)", side.len()));
+    for line in side.iter().take(MAX_MERGE_SIDE_LINES) {
+        lines.push(format!("  │ {}", line.trim_end_matches('\n')));
+    }
+    if side.len() > MAX_MERGE_SIDE_LINES {
+        lines.push(format!("  │ … {} more", side.len() - MAX_MERGE_SIDE_LINES));
+    }
+}
+
+fn merge_hunk_row(index: usize, hunk: &MergeHunk) -> String {
+    let kind = match hunk.kind {
+        MergeHunkKind::Agent => "agent only",
+        MergeHunkKind::User => "user only",
+        MergeHunkKind::Same => "same change",
+        MergeHunkKind::Conflict => "conflict",
+    };
+    let (mark, choice) = match hunk.choice {
+        Some(choice) => ("[✓]", choice.label()),
+        None => ("[ ]", "undecided"),
+    };
+    format!(
+        "{mark} #{} line {}  {kind} → {choice}",
+        index + 1,
+        hunk.base_line + 1
+    )
+}
+
+/// Hunk list with the selected hunk's original, agent and on-disk versions below it.
+pub(super) fn build_edit_conflict_panel_view(panel: &EditConflictPanelState) -> PanelView {
+    let mut lines = vec![
+        "a agent · u user · b both · o original · Enter send with next prompt · Esc discard"
+            .to_string(),
+        String::new(),
+    ];
+    let header_len = lines.len();
+    for (index, hunk) in panel.merge.hunks.iter().enumerate() {
+        lines.push(merge_hunk_row(index, hunk));
+    }
+    if let Some(hunk) = panel.merge.hunks.get(panel.selected) {
+        lines.push(String::new());
+        push_merge_side(&mut lines, "original", &hunk.base);
+        push_merge_side(&mut lines, "agent", &hunk.agent);
+        push_merge_side(&mut lines, "user (on disk)", &hunk.user);
+    }
+    let undecided = panel.merge.undecided();
+    PanelView {
+        title: Some(format!(
+            "Edit conflict: {} ({undecided} undecided)",
+            panel.file_path
+        )),
+        lines,
+        header_index: None,
+        selected: Some(header_len + panel.selected),
+        wrap_lines: false,
+        tail_pinned_from: None,
+        wide_lines: None,
+        underlined: Vec::new(),
+    }
+}
+
+fn build_hunk_review_hint(review: &HunkReviewState) -> String {
+    let decided = review
+        .hunks
+        .iter()
+        .filter(|hunk| hunk.decision.is_some())
+        .count();
+    if decided == 0 {
+        format!("[H to review {} hunks]", review.hunks.len())
+    } else {
+        format!(
+            "[H to review {} hunks; {decided} decided]",
+            review.hunks.len()
+        )
+    }
+}
+
+/// `head + text + tail` with the char index of the accelerator inside `text`; a letter
+/// the text lacks is appended as ` [x]`.
+fn confirm_option_row(
+    head: &str,
+    text: &str,
+    tail: &str,
+    accelerator: Option<char>,
+) -> (String, Option<usize>) {
+    let line = format!("{head}{text}{tail}");
+    let Some(key) = accelerator else {
+        return (line, None);
+    };
+    let head_chars = head.chars().count();
+    match text.chars().position(|ch| ch.to_ascii_lowercase() == key) {
+        Some(index) => (line, Some(head_chars + index)),
+        None => {
+            let mark = line.chars().count() + 2;
+            (format!("{line} [{key}]"), Some(mark))
+        }
+    }
+}
+
 pub(super) fn build_confirm_panel_view(panel: &ConfirmDialogState) -> PanelView {
     let mut lines = Vec::new();
     let mut title = panel.title.clone();
@@ -113,6 +247,10 @@ pub(super) fn build_confirm_panel_view(panel: &ConfirmDialogState) -> PanelView
         title = format!("DANGER: {title}");
     }
 
+    if let Some(review) = panel.hunk_review.as_ref().filter(|review| review.active) {
+        return build_hunk_review_view(review, title);
+    }
+
     if panel.command_view {
         lines.extend(build_command_review_lines(&panel.message));
         if let Some(toggle_hint) = build_confirm_detail_toggle_hint(panel, true) {
@@ -128,6 +266,8 @@ pub(super) fn build_confirm_panel_view(panel: &ConfirmDialogState) -> PanelView
             selected: None,
             wrap_lines: true,
             tail_pinned_from: None,
+            wide_lines: None,
+            underlined: Vec::new(),
         };
     }
 
@@ -181,23 +321,46 @@ pub(super) fn build_confirm_panel_view(panel: &ConfirmDialogState) -> PanelView
         lines.push(format!("Command: {}", preview[0]));
         lines.push(String::new());
     }
+    if let Some(review) = panel.hunk_review.as_ref() {
+        lines.push(build_hunk_review_hint(review));
+    }
     let option_start = lines.len();
-    lines.push(format!("1. {}", panel.confirm_label));
+    let keys = panel.accelerators;
+    let mut underlined = Vec::new();
+    let mut push_option = |lines: &mut Vec<String>, row: (String, Option<usize>)| {
+        if let Some(mark) = row.1 {
+            underlined.push((lines.len(), mark));
+        }
+        lines.push(row.0);
+    };
+    push_option(
+        &mut lines,
+        confirm_option_row("1. ", &panel.confirm_label, "", keys.allow),
+    );
+    let reason_hint = if panel.allow_reason {
+        " (Tab to add reason)"
+    } else {
+        ""
+    };
     if panel.allow_remember {
-        lines.push(format!("2. {} (don't ask again)", panel.confirm_label));
-        let cancel_line = if panel.allow_reason {
-            format!("3. {} (Tab to add reason)", panel.cancel_label)
-        } else {
-            format!("3. {}", panel.cancel_label)
-        };
-        lines.push(cancel_line);
+        push_option(
+            &mut lines,
+            confirm_option_row(
+                &format!("2. {} ", panel.confirm_label),
+                "(don't ask again)",
+                "",
+                keys.remember,
+            ),
+        );
+        push_option(
+            &mut lines,
+            confirm_option_row("3. ", &panel.cancel_label, reason_hint, keys.deny),
+        );
     } else {
-        let cancel_line = if panel.allow_reason {
-            format!("2. {} (Tab to add reason)", panel.cancel_label)
-        } else {
-            format!("2. {}", panel.cancel_label)
-        };
-        lines.push(cancel_line);
+        push_option(
+            &mut lines,
+            confirm_option_row("2. ", &panel.cancel_label, reason_hint, keys.deny),
+        );
     }
     if panel.allow_reason && panel.mode == crate::app::ConfirmMode::Reason {
         lines.push(String::new());
@@ -217,6 +380,8 @@ pub(super) fn build_confirm_panel_view(panel: &ConfirmDialogState) -> PanelView
         } else {
             option_start
         }),
+        wide_lines: None,
+        underlined,
     }
 }
 
@@ -239,6 +404,8 @@ pub(super) fn build_prompt_panel_view(panel: &PromptDialogState) -> PanelView {
         selected: None,
         wrap_lines: true,
         tail_pinned_from: None,
+        wide_lines: None,
+        underlined: Vec::new(),
     }
 }
 
@@ -278,12 +445,15 @@ pub(super) fn build_pick_panel_view(panel: &PickDialogState) -> PanelView {
         selected: Some(option_start + panel.selected),
         wrap_lines: true,
         tail_pinned_from: None,
+        wide_lines: None,
+        underlined: Vec::new(),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::build_confirm_panel_view;
+    use crate::app::state::ConfirmAccelerators;
     use crate::app::{ConfirmDialogState, ConfirmMode};
 
     fn confirm_dialog(
@@ -293,6 +463,7 @@ mod tests {
     ) -> ConfirmDialogState {
         ConfirmDialogState {
             id: "confirm-1".to_string(),
+            tool: None,
             title: "Run command?".to_string(),
             message: message.to_string(),
             danger_level: None,
@@ -303,6 +474,8 @@ mod tests {
             command_view: false,
             selected: 0,
             mode: ConfirmMode::Select,
+            accelerators: ConfirmAccelerators::default(),
+            hunk_review: None,
         }
     }
 
@@ -421,4 +594,28 @@ mod tests {
         assert_eq!(view.lines[5], "1. Allow");
         assert_eq!(view.tail_pinned_from, Some(3));
     }
+
+    #[test]
+    fn confirm_options_mark_accelerator_letters() {
+        let mut panel = confirm_dialog("rm build", true, false);
+        panel.accelerators = ConfirmAccelerators {
+            allow: Some('a'),
+            deny: Some('e'),
+            remember: Some('r'),
+        };
+
+        let view = build_confirm_panel_view(&panel);
+
+        let start = view
+            .lines
+            .iter()
+            .position(|line| line == "1. Allow")
+            .expect("options");
+        assert_eq!(view.lines[start + 1], "2. Allow (don't ask again) [r]");
+        assert_eq!(view.lines[start + 2], "3. Deny");
+        assert_eq!(
+            view.underlined,
+            vec![(start, 3), (start + 1, 28), (start + 2, 4)]
+        );
+    }
 }
@@ -1,101 +1,292 @@
 use crate::app::{
-    ContextPanelState, LaneListPanelState, SessionListPanelState, SkillsListPanelState,
-    ThemeListPanelState,
+    CacheListPanelState, ContextPanelState, LaneAttachState, LaneListPanelState, McpListPanelState,
+    PermissionsPanelState, QueuePanelState, SecretsPanelState, SessionListPanelState,
+    SkillsListPanelState, ThemeListPanelState, ToolsPanelState, WhatsNewPanelState, WideListRows,
 };
 
 use super::types::PanelView;
 
-pub(super) fn build_session_list_panel_view(panel: &SessionListPanelState) -> PanelView {
-    let mut lines = Vec::with_capacity(panel.rows.len().saturating_add(1));
-    lines.push(panel.header.clone());
-    lines.extend(panel.rows.iter().cloned());
-    let selected = if panel.rows.is_empty() {
+/// Rows kept on each side of the selection; more than any panel can show, so scrolling is
+/// unchanged while huge lists only clone and wrap this window each frame.
+const LIST_WINDOW_ROWS: usize = 256;
+
+/// Header + rows list shared by the list panels; `wide` rows replace the narrow ones on wide terminals.
+pub(super) fn list_panel_view(
+    title: &str,
+    header: &str,
+    rows: &[String],
+    selected: usize,
+    wrap_lines: bool,
+    wide: Option<&WideListRows>,
+) -> PanelView {
+    let selected = selected.min(rows.len().saturating_sub(1));
+    let window = selected.saturating_sub(LIST_WINDOW_ROWS)
+        ..rows
+            .len()
+            .min(selected.saturating_add(LIST_WINDOW_ROWS + 1));
+    let with_header = |header: &str, rows: &[String]| {
+        let rows = &rows[window.clone()];
+        let mut lines = Vec::with_capacity(rows.len().saturating_add(1));
+        lines.push(header.to_string());
+        lines.extend(rows.iter().cloned());
+        lines
+    };
+    let selected_line = if rows.is_empty() {
         None
     } else {
-        Some(panel.selected.saturating_add(1))
+        Some(selected - window.start + 1)
     };
     PanelView {
-        title: Some(panel.title.clone()),
-        lines,
+        title: Some(title.to_string()),
+        lines: with_header(header, rows),
         header_index: Some(0),
-        selected,
-        wrap_lines: true,
+        selected: selected_line,
+        wrap_lines,
         tail_pinned_from: None,
+        wide_lines: wide
+            .filter(|wide| wide.rows.len() == rows.len())
+            .map(|wide| with_header(&wide.header, &wide.rows)),
+        underlined: Vec::new(),
     }
 }
 
-pub(super) fn build_context_panel_view(panel: &ContextPanelState) -> PanelView {
-    let mut lines = Vec::with_capacity(panel.rows.len().saturating_add(1));
-    lines.push(panel.header.clone());
-    lines.extend(panel.rows.iter().cloned());
-    let selected = if panel.rows.is_empty() {
-        None
-    } else {
-        Some(panel.selected.saturating_add(1))
-    };
-    PanelView {
-        title: Some(panel.title.clone()),
-        lines,
-        header_index: Some(0),
-        selected,
-        wrap_lines: true,
-        tail_pinned_from: None,
-    }
+pub(super) fn build_session_list_panel_view(panel: &SessionListPanelState) -> PanelView {
+    list_panel_view(
+        &panel.title,
+        &panel.header,
+        &panel.rows,
+        panel.selected,
+        true,
+        panel.wide.as_ref(),
+    )
+}
+
+/// `title` carries the breadcrumb when the panel was opened from a list.
+pub(super) fn build_context_panel_view(panel: &ContextPanelState, title: &str) -> PanelView {
+    list_panel_view(
+        title,
+        &panel.header,
+        &panel.rows,
+        panel.selected,
+        true,
+        None,
+    )
 }
 
 pub(super) fn build_lane_list_panel_view(panel: &LaneListPanelState) -> PanelView {
-    let mut lines = Vec::with_capacity(panel.rows.len().saturating_add(1));
-    lines.push(panel.header.clone());
-    lines.extend(panel.rows.iter().cloned());
-    let selected = if panel.rows.is_empty() {
-        None
+    list_panel_view(
+        &panel.title,
+        &panel.header,
+        &panel.rows,
+        panel.selected,
+        true,
+        None,
+    )
+}
+
+/// Pane output ending `scroll` lines above the newest one, kept at the bottom of the panel.
+pub(super) fn build_lane_attach_panel_view(panel: &LaneAttachState) -> PanelView {
+    let liveness = if panel.backend_alive {
+        "live"
     } else {
-        Some(panel.selected.saturating_add(1))
+        "pane gone"
     };
+    let position = match (panel.follow, panel.incoming.is_some()) {
+        (true, _) => "follow".to_string(),
+        (false, true) => format!("{} lines back · new output", panel.scroll),
+        (false, false) => format!("{} lines back", panel.scroll),
+    };
+    let mut header = format!("{} · {liveness} · {position}", panel.state);
+    if let Some(error) = &panel.error {
+        header.push_str(&format!(" · error: {error}"));
+    }
+    header.push_str(" — ↑↓ PgUp/PgDn scroll · f follow · d detach");
+    let end = panel.lines.len().saturating_sub(panel.scroll);
+    let start = end.saturating_sub(LIST_WINDOW_ROWS);
+    let mut lines = Vec::with_capacity(end - start + 1);
+    lines.push(header);
+    if panel.lines.is_empty() {
+        lines.push("(waiting for output)".to_string());
+    }
+    lines.extend(panel.lines[start..end].iter().cloned());
     PanelView {
-        title: Some(panel.title.clone()),
+        title: Some(format!("Lane {} · attached", panel.lane_id)),
+        tail_pinned_from: Some(lines.len() - 1),
         lines,
         header_index: Some(0),
-        selected,
+        selected: None,
         wrap_lines: true,
-        tail_pinned_from: None,
+        wide_lines: None,
+        underlined: Vec::new(),
     }
 }
 
+pub(super) fn build_whats_new_panel_view(panel: &WhatsNewPanelState) -> PanelView {
+    list_panel_view(
+        &panel.title,
+        &panel.header,
+        &panel.rows,
+        panel.selected,
+        true,
+        None,
+    )
+}
+
+pub(super) fn build_mcp_list_panel_view(panel: &McpListPanelState) -> PanelView {
+    list_panel_view(
+        &panel.title,
+        &panel.header,
+        &panel.rows,
+        panel.selected,
+        true,
+        None,
+    )
+}
+
 pub(super) fn build_skills_list_panel_view(panel: &SkillsListPanelState) -> PanelView {
-    let mut lines = Vec::with_capacity(panel.rows.len().saturating_add(1));
-    lines.push(panel.header.clone());
-    lines.extend(panel.rows.iter().cloned());
-    let selected = if panel.rows.is_empty() {
-        None
-    } else {
-        Some(panel.selected.saturating_add(1))
-    };
-    PanelView {
-        title: Some(panel.title.clone()),
-        lines,
-        header_index: Some(0),
-        selected,
-        wrap_lines: true,
-        tail_pinned_from: None,
-    }
+    list_panel_view(
+        &panel.title,
+        &panel.header,
+        &panel.rows,
+        panel.selected,
+        true,
+        panel.wide.as_ref(),
+    )
 }
 
 pub(super) fn build_theme_list_panel_view(panel: &ThemeListPanelState) -> PanelView {
-    let mut lines = Vec::with_capacity(panel.rows.len().saturating_add(1));
-    lines.push(panel.header.clone());
-    lines.extend(panel.rows.iter().cloned());
-    let selected = if panel.rows.is_empty() {
-        None
-    } else {
-        Some(panel.selected.saturating_add(1))
-    };
-    PanelView {
-        title: Some(panel.title.clone()),
-        lines,
-        header_index: Some(0),
-        selected,
-        wrap_lines: true,
-        tail_pinned_from: None,
+    list_panel_view(
+        &panel.title,
+        &panel.header,
+        &panel.rows,
+        panel.selected,
+        true,
+        None,
+    )
+}
+
+pub(super) fn build_secrets_panel_view(panel: &SecretsPanelState) -> PanelView {
+    list_panel_view(
+        &panel.title,
+        &panel.header,
+        &panel.rows,
+        panel.selected,
+        false,
+        None,
+    )
+}
+
+pub(super) fn build_permissions_panel_view(panel: &PermissionsPanelState) -> PanelView {
+    list_panel_view(
+        &panel.title,
+        &panel.header,
+        &panel.rows,
+        panel.selected,
+        false,
+        None,
+    )
+}
+
+pub(super) fn build_tools_panel_view(panel: &ToolsPanelState) -> PanelView {
+    list_panel_view(
+        &panel.title,
+        &panel.header,
+        &panel.rows,
+        panel.selected,
+        false,
+        None,
+    )
+}
+
+pub(super) fn build_queue_list_panel_view(panel: &QueuePanelState) -> PanelView {
+    list_panel_view(
+        &panel.title,
+        &panel.header,
+        &panel.rows,
+        panel.selected,
+        false,
+        None,
+    )
+}
+
+pub(super) fn build_cache_list_panel_view(panel: &CacheListPanelState) -> PanelView {
+    list_panel_view(
+        &panel.title,
+        &panel.header,
+        &panel.rows,
+        panel.selected,
+        false,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::render::build_panel_render;
+    use super::list_panel_view;
+    use crate::app::WideListRows;
+
+    fn rendered_text(lines: &[ratatui::text::Line<'_>]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn list_panel_switches_to_wide_rows_on_wide_terminals() {
+        let rows = vec!["a | short".to_string(), "b | short".to_string()];
+        let wide = WideListRows {
+            header: "id | detail | more".to_string(),
+            rows: vec!["a | long | x".to_string(), "b | long | y".to_string()],
+        };
+        let view = list_panel_view("Title", "id | detail", &rows, 1, true, Some(&wide));
+
+        let narrow = rendered_text(&build_panel_render(&view, 10, 80));
+        assert_eq!(narrow[1], "  id | detail");
+        assert_eq!(narrow[3], "> b | short");
+
+        let wide_text = rendered_text(&build_panel_render(&view, 10, 200));
+        assert_eq!(wide_text[1], "  id | detail | more");
+        assert_eq!(wide_text[3], "> b | long | y");
+
+        let mismatched = WideListRows {
+            header: "x".to_string(),
+            rows: Vec::new(),
+        };
+        let view = list_panel_view("Title", "id", &rows, 0, true, Some(&mismatched));
+        assert!(view.wide_lines.is_none());
+    }
+
+    #[test]
+    fn large_list_renders_a_window_with_sticky_header() {
+        let rows = (0..10_000)
+            .map(|index| format!("row {index}"))
+            .collect::<Vec<_>>();
+        let view = list_panel_view("Big", "name", &rows, 5_000, true, None);
+        assert!(view.lines.len() <= 2 * super::LIST_WINDOW_ROWS + 2);
+        assert_eq!(view.lines[view.selected.expect("selected")], "row 5000");
+
+        let text = rendered_text(&build_panel_render(&view, 6, 80));
+        assert_eq!(
+            text,
+            vec![
+                "Big",
+                "  name",
+                "  row 4997",
+                "  row 4998",
+                "  row 4999",
+                "> row 5000"
+            ]
+        );
+
+        let top = list_panel_view("Big", "name", &rows, 1, true, None);
+        let text = rendered_text(&build_panel_render(&top, 4, 80));
+        assert_eq!(text, vec!["Big", "  name", "  row 0", "> row 1"]);
     }
 }
@@ -8,16 +8,22 @@ mod types;
 
 use crate::app::AppState;
 
-use dialogs::{build_confirm_panel_view, build_pick_panel_view, build_prompt_panel_view};
+use dialogs::{
+    build_confirm_panel_view, build_edit_conflict_panel_view, build_pick_panel_view,
+    build_prompt_panel_view,
+};
 use lists::{
-    build_context_panel_view, build_lane_list_panel_view, build_session_list_panel_view,
-    build_skills_list_panel_view, build_theme_list_panel_view,
+    build_cache_list_panel_view, build_context_panel_view, build_lane_attach_panel_view,
+    build_lane_list_panel_view, build_mcp_list_panel_view, build_permissions_panel_view,
+    build_queue_list_panel_view, build_secrets_panel_view, build_session_list_panel_view,
+    build_skills_list_panel_view, build_theme_list_panel_view, build_tools_panel_view,
+    build_whats_new_panel_view,
 };
 use model::build_model_list_panel_view;
 use picker::build_picker_panel_view;
 use suggestions::{
-    build_attachment_panel_view, build_command_panel_view, build_queue_panel_view,
-    build_skill_suggestion_panel_view,
+    build_attachment_panel_view, build_command_panel_view, build_file_mention_panel_view,
+    build_motd_panel_view, build_queue_panel_view, build_skill_suggestion_panel_view,
 };
 
 pub(super) use render::{build_panel_render, render_input_panel};
@@ -36,6 +42,10 @@ pub(super) fn build_panel_view(app: &AppState) -> Option<PanelView> {
         return Some(build_pick_panel_view(panel));
     }
 
+    if let Some(panel) = &app.edit_conflict_panel {
+        return Some(build_edit_conflict_panel_view(panel));
+    }
+
     if let Some(panel) = &app.session_list_panel {
         return Some(build_session_list_panel_view(panel));
     }
@@ -44,8 +54,17 @@ pub(super) fn build_panel_view(app: &AppState) -> Option<PanelView> {
         return Some(build_lane_list_panel_view(panel));
     }
 
+    if let Some(panel) = &app.lane_attach {
+        return Some(build_lane_attach_panel_view(panel));
+    }
+
+    if let Some(panel) = &app.mcp_list_panel {
+        return Some(build_mcp_list_panel_view(panel));
+    }
+
     if let Some(panel) = &app.context_panel {
-        return Some(build_context_panel_view(panel));
+        let title = app.panel_stack.breadcrumb(&panel.title);
+        return Some(build_context_panel_view(panel, &title));
     }
 
     if let Some(panel) = &app.skills_list_panel {
@@ -56,6 +75,30 @@ pub(super) fn build_panel_view(app: &AppState) -> Option<PanelView> {
         return Some(build_theme_list_panel_view(panel));
     }
 
+    if let Some(panel) = &app.secrets_panel {
+        return Some(build_secrets_panel_view(panel));
+    }
+
+    if let Some(panel) = &app.permissions_panel {
+        return Some(build_permissions_panel_view(panel));
+    }
+
+    if let Some(panel) = &app.tools_panel {
+        return Some(build_tools_panel_view(panel));
+    }
+
+    if let Some(panel) = &app.queue_panel {
+        return Some(build_queue_list_panel_view(panel));
+    }
+
+    if let Some(panel) = &app.cache_list_panel {
+        return Some(build_cache_list_panel_view(panel));
+    }
+
+    if let Some(panel) = &app.whats_new_panel {
+        return Some(build_whats_new_panel_view(panel));
+    }
+
     if let Some(panel) = &app.model_list_panel {
         return Some(build_model_list_panel_view(panel));
     }
@@ -98,5 +141,7 @@ pub(super) fn build_panel_view(app: &AppState) -> Option<PanelView> {
     build_queue_panel_view(app)
         .or_else(|| build_command_panel_view(app))
         .or_else(|| build_skill_suggestion_panel_view(app))
+        .or_else(|| build_file_mention_panel_view(app))
         .or_else(|| build_attachment_panel_view(app))
+        .or_else(|| build_motd_panel_view(app))
 }
@@ -19,5 +19,7 @@ pub(super) fn build_picker_panel_view(
         selected: Some(selected),
         wrap_lines: true,
         tail_pinned_from: None,
+        wide_lines: None,
+        underlined: Vec::new(),
     }
 }
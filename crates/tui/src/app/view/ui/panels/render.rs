@@ -5,7 +5,7 @@ use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::{Block, Paragraph};
 
 use super::super::super::theme::ui_colors;
-use super::super::constants::{input_bg, INPUT_PADDING_X, INPUT_PADDING_Y};
+use super::super::constants::{input_bg, INPUT_PADDING_X, INPUT_PADDING_Y, WIDE_LIST_MIN_WIDTH};
 use super::super::input::{render_input, InputLayout};
 use super::super::text::truncate_to_width;
 use super::types::PanelView;
@@ -38,7 +38,12 @@ pub(in crate::app::view::ui) fn build_panel_render(
 
     let mut expanded: Vec<(usize, String)> = Vec::new();
     let content_width = max_width.saturating_sub(2).max(1);
-    for (line_index, line) in panel.lines.iter().enumerate() {
+    let lines = panel
+        .wide_lines
+        .as_ref()
+        .filter(|_| content_width >= WIDE_LIST_MIN_WIDTH)
+        .unwrap_or(&panel.lines);
+    for (line_index, line) in lines.iter().enumerate() {
         if line.is_empty() {
             expanded.push((line_index, String::new()));
             continue;
@@ -84,28 +89,70 @@ pub(in crate::app::view::ui) fn build_panel_render(
         }
     }
     let end = usize::min(start + visible, total);
+    // Keep the column header on screen once the selection scrolls past it.
+    let sticky_header = panel.header_index.and_then(|header| {
+        let position = expanded.iter().position(|(idx, _)| *idx == header)?;
+        (position < start && end - start > 1).then_some(position)
+    });
+    let mut body_start = start;
+    if let Some(position) = sticky_header {
+        let (line_index, line) = &expanded[position];
+        out.push(panel_line(panel, *line_index, line, false));
+        body_start = start + 1;
+    }
     let mut prev_line_index: Option<usize> = None;
-    for (line_index, line) in expanded[start..end].iter() {
-        let is_selected_line = panel.selected == Some(*line_index);
+    for (line_index, line) in expanded[body_start..end].iter() {
         let is_first_visual_line = prev_line_index != Some(*line_index);
-        let mut style = Style::default();
-        if panel.header_index == Some(*line_index) {
-            style = style.add_modifier(theme.low_emphasis_modifier);
-        }
-        if is_selected_line {
-            style = style.add_modifier(Modifier::BOLD);
-        }
-        let marker = if is_selected_line && is_first_visual_line {
-            "> "
-        } else {
-            "  "
-        };
-        out.push(Line::from(Span::styled(format!("{marker}{line}"), style)));
+        out.push(panel_line(panel, *line_index, line, is_first_visual_line));
         prev_line_index = Some(*line_index);
     }
     out
 }
 
+fn panel_line(
+    panel: &PanelView,
+    line_index: usize,
+    line: &str,
+    is_first_visual_line: bool,
+) -> Line<'static> {
+    let theme = ui_colors();
+    let is_selected_line = panel.selected == Some(line_index);
+    let mut style = Style::default();
+    if panel.header_index == Some(line_index) {
+        style = style.add_modifier(theme.low_emphasis_modifier);
+    }
+    if is_selected_line {
+        style = style.add_modifier(Modifier::BOLD);
+    }
+    let marker = if is_selected_line && is_first_visual_line {
+        "> "
+    } else {
+        "  "
+    };
+    let mark = panel
+        .underlined
+        .iter()
+        .find(|(index, _)| *index == line_index && is_first_visual_line)
+        .map(|(_, mark)| *mark)
+        .filter(|mark| *mark < line.chars().count());
+    let Some(mark) = mark else {
+        return Line::from(Span::styled(format!("{marker}{line}"), style));
+    };
+    let (before, rest) = line.split_at(
+        line.char_indices()
+            .nth(mark)
+            .map(|(offset, _)| offset)
+            .unwrap_or(line.len()),
+    );
+    let mut rest_chars = rest.chars();
+    let letter = rest_chars.next().map(String::from).unwrap_or_default();
+    Line::from(vec![
+        Span::styled(format!("{marker}{before}"), style),
+        Span::styled(letter, style.add_modifier(Modifier::UNDERLINED)),
+        Span::styled(rest_chars.as_str().to_string(), style),
+    ])
+}
+
 pub(in crate::app::view::ui) fn render_input_panel(
     f: &mut ratatui::Frame<'_>,
     area: Rect,
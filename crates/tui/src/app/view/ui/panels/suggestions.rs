@@ -1,12 +1,17 @@
 use crate::app::state::{
-    active_skill_mention_token, command_suggestion_rows, skill_suggestion_rows,
+    active_dir_mention_token, active_file_mention_token, active_skill_mention_token,
+    command_suggestion_rows, skill_suggestion_rows,
 };
+use crate::app::util::dir_tree::DIR_MENTION_PREFIX;
+use crate::app::util::settings::{active_setting_key_prefix, setting_suggestion_rows};
 use crate::app::AppState;
 
 use super::types::PanelView;
 
 const COMMAND_PANEL_LIMIT: usize = 6;
 const QUEUE_PANEL_LIMIT: usize = 4;
+const FILE_PREVIEW_LINES: usize = 3;
+const TREE_PREVIEW_LINES: usize = 8;
 
 pub(super) fn build_queue_panel_view(app: &AppState) -> Option<PanelView> {
     if app.pending_prompt_queue.is_empty() {
@@ -50,6 +55,24 @@ pub(super) fn build_queue_panel_view(app: &AppState) -> Option<PanelView> {
         selected: None,
         wrap_lines: true,
         tail_pinned_from: None,
+        wide_lines: None,
+        underlined: Vec::new(),
+    })
+}
+
+pub(super) fn build_motd_panel_view(app: &AppState) -> Option<PanelView> {
+    let motd = app.motd.as_deref()?;
+    let mut lines = motd.lines().map(str::to_string).collect::<Vec<_>>();
+    lines.push("Esc dismiss".to_string());
+    Some(PanelView {
+        title: Some("Message of the day".to_string()),
+        header_index: Some(lines.len() - 1),
+        lines,
+        selected: None,
+        wrap_lines: true,
+        tail_pinned_from: None,
+        wide_lines: None,
+        underlined: Vec::new(),
     })
 }
 
@@ -59,6 +82,9 @@ pub(super) fn build_command_panel_view(app: &AppState) -> Option<PanelView> {
     if !trimmed.starts_with('/') {
         return None;
     }
+    if let Some(prefix) = active_setting_key_prefix(trimmed) {
+        return build_setting_suggestion_view(app, prefix);
+    }
     if trimmed.chars().any(char::is_whitespace) {
         return None;
     }
@@ -84,6 +110,27 @@ pub(super) fn build_command_panel_view(app: &AppState) -> Option<PanelView> {
         selected: None,
         wrap_lines: true,
         tail_pinned_from: None,
+        wide_lines: None,
+        underlined: Vec::new(),
+    })
+}
+
+fn build_setting_suggestion_view(app: &AppState, prefix: &str) -> Option<PanelView> {
+    let rows = setting_suggestion_rows(prefix, &app.settings);
+    if rows.is_empty() {
+        return None;
+    }
+    let mut lines = vec![format!("settings: {prefix}")];
+    lines.extend(rows);
+    Some(PanelView {
+        title: Some("Settings (tui.toml)".to_string()),
+        lines,
+        header_index: Some(0),
+        selected: None,
+        wrap_lines: true,
+        tail_pinned_from: None,
+        wide_lines: None,
+        underlined: Vec::new(),
     })
 }
 
@@ -111,18 +158,103 @@ pub(super) fn build_skill_suggestion_panel_view(app: &AppState) -> Option<PanelV
         selected: None,
         wrap_lines: true,
         tail_pinned_from: None,
+        wide_lines: None,
+        underlined: Vec::new(),
+    })
+}
+
+/// Candidates for the `@` word being typed, from the cached `mention_matches`; while the
+/// workspace index is still being built, a placeholder row.
+pub(super) fn build_file_mention_panel_view(app: &AppState) -> Option<PanelView> {
+    let text = app.input.current();
+    let (dir, query) = match active_dir_mention_token(&text) {
+        Some(query) => (true, query),
+        None => (false, active_file_mention_token(&text)?),
+    };
+    let (kind, title, empty) = if dir {
+        (
+            "attach tree",
+            "Directories",
+            "No matching workspace directory.",
+        )
+    } else {
+        ("attach", "Files", "No matching workspace file.")
+    };
+    let prefix = if dir {
+        format!("@{DIR_MENTION_PREFIX}")
+    } else {
+        "@".to_string()
+    };
+    let mut lines = vec![format!(
+        "matching: {prefix}{query} · ↑↓ choose · Tab {kind}"
+    )];
+    let mut selected = None;
+    match app.mention_matches.as_ref() {
+        Some(matches) if matches.paths.is_empty() => lines.push(empty.to_string()),
+        Some(matches) => {
+            selected = Some(matches.selected + 1);
+            lines.extend(matches.paths.iter().map(|path| format!("{prefix}{path}")));
+        }
+        None if app.workspace_index_load.is_running() => {
+            lines.push("Indexing workspace files…".to_string());
+        }
+        None => return None,
+    }
+    Some(PanelView {
+        title: Some(title.to_string()),
+        lines,
+        header_index: Some(0),
+        selected,
+        wrap_lines: true,
+        tail_pinned_from: None,
+        wide_lines: None,
+        underlined: Vec::new(),
     })
 }
 
+/// Attached `@` files with their first lines, so the content can be checked before sending.
+fn file_attachment_preview_lines(app: &AppState, text: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    for file in app.referenced_file_attachments(text) {
+        if let Some(dir) = file.path.strip_prefix(DIR_MENTION_PREFIX) {
+            let mut rows = file.content.lines();
+            let summary = rows.next().unwrap_or_default();
+            let summary = summary.strip_prefix(dir).unwrap_or(summary);
+            lines.push(format!("[Tree] {dir}{summary}"));
+            lines.extend(
+                rows.take(TREE_PREVIEW_LINES)
+                    .map(|line| format!("  │ {line}")),
+            );
+            continue;
+        }
+        let truncated = if file.truncated {
+            format!(", first {}KB sent", file.content.len() / 1024)
+        } else {
+            String::new()
+        };
+        lines.push(format!(
+            "[File] {} ({} lines, {}KB{truncated})",
+            file.path,
+            file.content.lines().count(),
+            file.bytes.div_ceil(1024)
+        ));
+        lines.extend(
+            file.content
+                .lines()
+                .take(FILE_PREVIEW_LINES)
+                .map(|line| format!("  │ {line}")),
+        );
+    }
+    lines
+}
+
 pub(super) fn build_attachment_panel_view(app: &AppState) -> Option<PanelView> {
-    if app.pending_image_attachments.is_empty() {
+    if app.pending_image_attachments.is_empty() && app.pending_file_attachments.is_empty() {
         return None;
     }
+    let text = app.input.current();
     let ids = app.referenced_attachment_ids();
-    if ids.is_empty() {
-        return None;
-    }
-    let lines = ids
+    let mut lines = ids
         .iter()
         .enumerate()
         .filter_map(|(index, attachment_id)| {
@@ -139,6 +271,10 @@ pub(super) fn build_attachment_panel_view(app: &AppState) -> Option<PanelView> {
                 })
         })
         .collect::<Vec<_>>();
+    lines.extend(file_attachment_preview_lines(app, &text));
+    if lines.is_empty() {
+        return None;
+    }
     Some(PanelView {
         title: Some("Attachments".to_string()),
         lines,
@@ -146,13 +282,15 @@ pub(super) fn build_attachment_panel_view(app: &AppState) -> Option<PanelView> {
         selected: None,
         wrap_lines: false,
         tail_pinned_from: None,
+        wide_lines: None,
+        underlined: Vec::new(),
     })
 }
 
 #[cfg(test)]
 mod tests {
     use super::build_queue_panel_view;
-    use crate::app::{AppState, PendingPromptRun};
+    use crate::app::{AppState, PendingPromptRun, QueuedAttachments};
     use serde_json::json;
     use std::time::Instant;
 
@@ -166,6 +304,9 @@ mod tests {
             attachment_count: 0,
             shell_result_count: 0,
             dispatch_attempts: 0,
+            dry_run: false,
+            json_request: None,
+            attachments: QueuedAttachments::default(),
         }
     }
 
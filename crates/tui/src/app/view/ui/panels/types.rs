@@ -5,4 +5,8 @@ pub(in crate::app::view::ui) struct PanelView {
     pub(super) selected: Option<usize>,
     pub(super) wrap_lines: bool,
     pub(super) tail_pinned_from: Option<usize>,
+    /// Index-aligned replacement for `lines` once the panel is at least `WIDE_LIST_MIN_WIDTH` wide.
+    pub(super) wide_lines: Option<Vec<String>>,
+    /// `(line, char)` positions drawn underlined, e.g. confirm accelerator letters.
+    pub(super) underlined: Vec<(usize, usize)>,
 }
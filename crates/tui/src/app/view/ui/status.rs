@@ -1,17 +1,31 @@
+use crate::app::markdown::highlight_assets_load_time;
+use crate::app::state::working_dir_label;
+use crate::app::util::local_models::LOCAL_MODEL_PROVIDER;
+use crate::app::util::settings::{render_run_line, RunLineFields};
 use crate::app::{AppState, StatusLineMode};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
+use std::time::Instant;
 
 use super::super::theme::ui_colors;
 use super::text::truncate_to_width;
 
 pub(super) fn build_run_line(app: &AppState) -> Line<'static> {
     let run_status = app.run_status.as_deref().unwrap_or("idle");
-    let label = if app.is_running() {
-        format!("● {run_status} {}", app.spinner_frame())
-    } else {
-        format!("● {run_status}")
-    };
+    let running = app.is_running();
+    let status = app.settings.status_verbs.verb(run_status);
+    let label = render_run_line(
+        &app.settings.status_format,
+        &RunLineFields {
+            status: &status,
+            spinner: if running { app.spinner_frame() } else { "" },
+            tool: match app.active_tool.as_ref() {
+                Some((_, tool)) if running => tool,
+                _ => "",
+            },
+            elapsed: app.run_duration(),
+        },
+    );
     let theme = ui_colors();
     let style = match run_status {
         "starting" | "running" | "awaiting_ui" => Style::default().fg(theme.run_ready_fg),
@@ -29,10 +43,35 @@ pub(super) fn build_run_line(app: &AppState) -> Line<'static> {
     Line::from(Span::styled(label, style))
 }
 
+/// Plain text at full contrast; the row stays empty until the first announcement.
+pub(super) fn build_announcement_line(app: &AppState) -> Line<'static> {
+    Line::from(app.live_region.row().unwrap_or_default())
+}
+
 pub(super) fn build_status_line(app: &AppState) -> Line<'static> {
     let mut segments = Vec::new();
+    if let Some(search) = app.log_search.as_ref() {
+        let position = search.position_label(app.render_state.inserted_until);
+        segments.push(if search.editing {
+            format!("Find: {}▏ {position}", search.query)
+        } else {
+            format!("Find: {} {position} · n/N", search.query)
+        });
+    }
+    if let Some(reconnect) = app.runtime_reconnect.status_segment() {
+        segments.push(reconnect);
+    }
+    if let Some(pending) = app.chord.pending_label() {
+        segments.push(format!("keys: {pending}"));
+    }
+    if let Some(action) = app.next_esc_action(Instant::now()) {
+        segments.push(format!("Esc: {}", action.hint()));
+    }
     match app.status_line_mode {
         StatusLineMode::Info => {
+            if let Some(tabs) = app.tab_strip.status_segment() {
+                segments.push(tabs);
+            }
             let provider = app.runtime_info.current_provider.as_deref().unwrap_or("-");
             let model = app.runtime_info.current_model.as_deref().unwrap_or("-");
             let reasoning = app.runtime_info.current_reasoning.as_deref().unwrap_or("-");
@@ -47,8 +86,41 @@ pub(super) fn build_status_line(app: &AppState) -> Line<'static> {
                 ""
             };
             segments.push(format!("{label}: {provider}/{model} [{reasoning}{fast}]"));
+            if provider == LOCAL_MODEL_PROVIDER {
+                // Prompts stay on this machine while a local server answers them.
+                segments.push("offline".to_string());
+            }
+            if let Some(cwd) =
+                app.runtime_info.working_dir.as_deref().and_then(|cwd| {
+                    working_dir_label(cwd, app.runtime_info.sandbox_root.as_deref())
+                })
+            {
+                segments.push(format!("cwd: {cwd}"));
+            }
+            if let Some(host) = app
+                .runtime_info
+                .launch
+                .as_ref()
+                .and_then(|launch| launch.ssh_host())
+            {
+                segments.push(app.connection_health.status_segment(host, Instant::now()));
+            }
+            if !app.disabled_tools.is_empty() {
+                let names = app
+                    .disabled_tools
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>();
+                segments.push(format!("tools off: {}", names.join(",")));
+            }
             if let Some(percent) = app.context_left_percent {
-                segments.push(format!("context left: {percent}%"));
+                match app.context_history.sparkline() {
+                    Some(spark) => segments.push(format!("context left: {percent}% {spark}")),
+                    None => segments.push(format!("context left: {percent}%")),
+                }
+            }
+            if let Some(usage) = app.usage.status_segment() {
+                segments.push(usage);
             }
             let image_count = app.referenced_attachment_count();
             if image_count > 0 {
@@ -57,9 +129,35 @@ pub(super) fn build_status_line(app: &AppState) -> Line<'static> {
             if !app.pending_prompt_queue.is_empty() {
                 segments.push(format!("queue: {}", app.pending_prompt_queue.len()));
             }
+            if let (Some(batch), Some(waiting)) =
+                (app.prompt_batch.as_ref(), app.prompt_batch_waiting())
+            {
+                segments.push(batch.status_segment(waiting));
+            }
+            if app.ci_watch.watching {
+                if let Some(summary) = app.ci_watch.summary.as_ref() {
+                    segments.push(summary.status_segment());
+                }
+            }
+            if app.dry_run_active {
+                segments.push("mode: plan (dry run)".to_string());
+            } else if app.ready_plan.is_some() && !app.is_running() {
+                segments.push("plan ready: Ctrl+G run".to_string());
+            }
+            if app.json_output.armed.is_some() {
+                segments.push("json: next prompt".to_string());
+            }
             if app.bang_input_mode {
                 segments.push("mode: !shell".to_string());
             }
+            if app.low_bandwidth {
+                segments.push("low bandwidth".to_string());
+            } else if app.effects_reduced() {
+                segments.push("effects: reduced".to_string());
+            }
+            if let Some(median) = app.perf_debug.rpc_latency.slow_median_ms() {
+                segments.push(format!("⚠ runtime slow: median {median:.0}ms"));
+            }
             segments.push("Alt+H help".to_string());
         }
         StatusLineMode::Help => {
@@ -67,6 +165,12 @@ pub(super) fn build_status_line(app: &AppState) -> Line<'static> {
             segments.push("Esc/Backspace at empty: exit !mode".to_string());
             segments.push("Ctrl+J/Shift+Enter newline".to_string());
             segments.push("Alt+V paste image".to_string());
+            segments.push("Alt+P pin answer".to_string());
+            segments.push("Alt+O open link".to_string());
+            segments.push("Ctrl+X g / Ctrl+X G top/bottom".to_string());
+            if app.tab_strip.tabs.len() > 1 {
+                segments.push("Ctrl+Tab next tab".to_string());
+            }
             segments.push(format!(
                 "F2 mouse: {}",
                 if app.mouse_capture_enabled {
@@ -81,12 +185,28 @@ pub(super) fn build_status_line(app: &AppState) -> Line<'static> {
     }
     let status_text = segments.join("  •  ");
     let theme = ui_colors();
-    Line::from(Span::styled(
+    let mut spans = Vec::new();
+    if app.read_only {
+        spans.push(Span::styled(
+            "READ-ONLY",
+            Style::default()
+                .fg(theme.bang_prefix_fg)
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::styled(
+            "  •  ",
+            Style::default()
+                .fg(theme.log_muted_fg)
+                .add_modifier(theme.low_emphasis_modifier),
+        ));
+    }
+    spans.push(Span::styled(
         status_text,
         Style::default()
             .fg(theme.log_muted_fg)
             .add_modifier(theme.low_emphasis_modifier),
-    ))
+    ));
+    Line::from(spans)
 }
 
 fn format_memory_bytes(bytes: Option<u64>) -> String {
@@ -127,8 +247,14 @@ fn build_debug_perf_line_texts(app: &AppState) -> Vec<String> {
             app.perf_debug.wrapped_total
         ),
         format!(
-            "cache hit:{} miss:{} rate:{:.1}% redraw:{}",
-            hits, misses, hit_rate, app.perf_debug.redraw_count
+            "cache hit:{} miss:{} rate:{:.1}% redraw:{} syntect:{}",
+            hits,
+            misses,
+            hit_rate,
+            app.perf_debug.redraw_count,
+            highlight_assets_load_time()
+                .map(|load| format!("{:.1}ms", load.as_secs_f64() * 1000.0))
+                .unwrap_or_else(|| "-".to_string())
         ),
         format!(
             "mem tui:{} runtime:{} total:{}",
@@ -136,9 +262,52 @@ fn build_debug_perf_line_texts(app: &AppState) -> Vec<String> {
             format_memory_bytes(app.perf_debug.runtime_rss_bytes),
             format_memory_bytes(total_memory)
         ),
+        format_held_memory_line(app),
+        format_rpc_latency_line(app),
     ]
 }
 
+/// Held bytes against their `*_memory_mb` cap (`∞` when the cap is off).
+fn format_held_memory_line(app: &AppState) -> String {
+    let held = app.perf_debug.held_memory;
+    let settings = &app.settings;
+    let part = |bytes: usize, cap_mb: u64| {
+        let cap = if cap_mb == 0 {
+            "∞".to_string()
+        } else {
+            format!("{cap_mb}")
+        };
+        format!("{:.1}/{cap}MiB", bytes as f64 / (1024.0 * 1024.0))
+    };
+    format!(
+        "held img:{} shell:{} log:{} evicted:{}",
+        part(held.image_bytes, settings.image_memory_mb),
+        part(held.shell_bytes, settings.shell_memory_mb),
+        part(held.log_bytes, settings.log_memory_mb),
+        held.evicted
+    )
+}
+
+const RPC_LATENCY_METHODS_SHOWN: usize = 4;
+
+fn format_rpc_latency_line(app: &AppState) -> String {
+    let latency = &app.perf_debug.rpc_latency;
+    let methods = latency.method_averages();
+    if methods.is_empty() {
+        return "rpc -".to_string();
+    }
+    let median = latency
+        .median_ms()
+        .map(|median| format!("{median:.0}ms"))
+        .unwrap_or_else(|| "-".to_string());
+    let rows = methods
+        .iter()
+        .take(RPC_LATENCY_METHODS_SHOWN)
+        .map(|(method, average, samples)| format!("{method}:{average:.0}ms×{samples}"))
+        .collect::<Vec<_>>();
+    format!("rpc median:{median} {}", rows.join(" "))
+}
+
 pub(super) fn build_debug_perf_lines(app: &AppState, width: usize) -> Vec<Line<'static>> {
     if width == 0 {
         return Vec::new();
@@ -161,8 +330,10 @@ pub(super) fn build_debug_perf_lines(app: &AppState, width: usize) -> Vec<Line<'
 #[cfg(test)]
 mod tests {
     use super::{build_debug_perf_line_texts, build_run_line, build_status_line};
+    use crate::app::state::{TabIndicator, TabStrip};
     use crate::app::theme::ui_colors;
     use crate::app::AppState;
+    use ratatui::style::Modifier;
 
     #[test]
     fn debug_perf_lines_include_memory_summary() {
@@ -177,10 +348,44 @@ mod tests {
         app.perf_debug.wrapped_total = 42;
         app.perf_debug.tui_rss_bytes = Some(8 * 1024 * 1024);
         app.perf_debug.runtime_rss_bytes = Some(12 * 1024 * 1024);
+        app.perf_debug.held_memory.log_bytes = 3 * 1024 * 1024 / 2;
+        app.settings.image_memory_mb = 0;
 
         let lines = build_debug_perf_line_texts(&app);
-        assert_eq!(lines.len(), 3);
+        assert_eq!(lines.len(), 5);
         assert_eq!(lines[2], "mem tui:8.0 MiB runtime:12.0 MiB total:20.0 MiB");
+        assert_eq!(
+            lines[3],
+            "held img:0.0/∞MiB shell:0.0/32MiB log:1.5/128MiB evicted:0"
+        );
+        assert_eq!(lines[4], "rpc -");
+    }
+
+    #[test]
+    fn rpc_latency_shows_method_averages_and_slow_warning() {
+        let mut app = AppState {
+            debug_perf_enabled: true,
+            ..AppState::default()
+        };
+        for latency in [100.0, 300.0] {
+            app.perf_debug.rpc_latency.record("model.list", latency);
+        }
+        for _ in 0..3 {
+            app.perf_debug.rpc_latency.record("run.start", 2000.0);
+        }
+        let lines = build_debug_perf_line_texts(&app);
+        assert_eq!(
+            lines[4],
+            "rpc median:2000ms run.start:2000ms×3 model.list:200ms×2"
+        );
+        let status = build_status_line(&app).spans[0].content.to_string();
+        assert!(status.contains("⚠ runtime slow: median 2000ms"));
+
+        for _ in 0..5 {
+            app.perf_debug.rpc_latency.record("run.start", 50.0);
+        }
+        let status = build_status_line(&app).spans[0].content.to_string();
+        assert!(!status.contains("runtime slow"));
     }
 
     #[test]
@@ -197,4 +402,35 @@ mod tests {
         assert_eq!(build_run_line(&app).spans[0].style.fg, expected);
         assert_eq!(build_status_line(&app).spans[0].style.fg, expected);
     }
+
+    #[test]
+    fn read_only_sessions_lead_the_status_line_with_a_badge() {
+        let app = AppState {
+            read_only: true,
+            ..AppState::default()
+        };
+        let line = build_status_line(&app);
+        assert_eq!(line.spans[0].content, "READ-ONLY");
+        assert!(line.spans[0].style.add_modifier.contains(Modifier::BOLD));
+        assert!(line.spans[2].content.contains("Alt+H help"));
+    }
+
+    #[test]
+    fn tab_indicators_lead_the_info_segments_once_a_second_tab_opens() {
+        let mut app = AppState {
+            tab_strip: TabStrip {
+                tabs: vec![TabIndicator::default()],
+                active: 0,
+            },
+            ..AppState::default()
+        };
+        assert!(!build_status_line(&app).spans[0].content.contains("tabs:"));
+
+        app.tab_strip.tabs.push(TabIndicator {
+            running: true,
+            ..TabIndicator::default()
+        });
+        let status = build_status_line(&app).spans[0].content.to_string();
+        assert!(status.starts_with("tabs: [1] 2●  •  model:"), "{status}");
+    }
 }
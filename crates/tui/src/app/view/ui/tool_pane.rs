@@ -0,0 +1,47 @@
+use crate::app::log_wrap::{log_lines_to_lines, wrap_log_lines};
+use crate::app::state::ToolPane;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::Paragraph;
+
+use super::super::theme::ui_colors;
+
+/// Divider column, then a title row and the tail of the pane body.
+pub(super) fn render_tool_pane(f: &mut ratatui::Frame<'_>, pane: &ToolPane, area: Rect) {
+    if area.width < 3 || area.height == 0 {
+        return;
+    }
+    let theme = ui_colors();
+    let divider = (0..area.height)
+        .map(|_| {
+            Line::from(Span::styled(
+                "│",
+                Style::default().fg(theme.panel_divider_fg),
+            ))
+        })
+        .collect::<Vec<_>>();
+    f.render_widget(
+        Paragraph::new(Text::from(divider)),
+        Rect { width: 1, ..area },
+    );
+
+    let content = Rect {
+        x: area.x + 2,
+        width: area.width - 2,
+        ..area
+    };
+    let title_fg = match (pane.running, pane.failed) {
+        (true, _) => theme.log_tool_call_fg,
+        (false, true) => theme.log_error_fg,
+        (false, false) => theme.log_muted_fg,
+    };
+    let body = wrap_log_lines(&pane.body_lines(), content.width as usize);
+    let rows = (content.height as usize).saturating_sub(1);
+    let mut lines = vec![Line::from(Span::styled(
+        pane.title(),
+        Style::default().fg(title_fg).add_modifier(Modifier::BOLD),
+    ))];
+    lines.extend(log_lines_to_lines(&body[body.len().saturating_sub(rows)..]));
+    f.render_widget(Paragraph::new(Text::from(lines)), content);
+}
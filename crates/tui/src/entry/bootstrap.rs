@@ -1,10 +1,15 @@
 use crate::app::handlers::panels::{request_session_history, request_session_list};
-use crate::app::runtime::send_model_list;
-use crate::app::state::LogKind;
+use crate::app::handlers::settings::{apply_startup_settings, sync_effects};
+use crate::app::handlers::update::start_update_check;
+use crate::app::render::palette::set_ansi16_only;
+use crate::app::runtime::{send_model_list, RuntimeStdin};
+use crate::app::state::{parse_session_permalink, LogKind, PromptBatch};
+use crate::app::util::audit::audit_log_path;
+use crate::app::util::draft::DraftAutosave;
+use crate::app::util::secrets::load_secrets_from_keychain;
+use crate::app::util::settings::{load_banner_lines, load_custom_themes, load_settings};
 use crate::app::{AppState, ModelListMode};
 use crate::entry::cli::{resolve_version_label, ResumeMode};
-use std::io::BufWriter;
-use std::process::ChildStdin;
 
 const LOGO_LINES: [&str; 7] = [
     "┌─────────────────────────────────────┐",
@@ -16,8 +21,6 @@ const LOGO_LINES: [&str; 7] = [
     "└─────────────────────────────────────┘",
 ];
 
-type RuntimeStdin = BufWriter<ChildStdin>;
-
 pub(crate) fn build_initial_app(
     debug_print: bool,
     debug_perf: bool,
@@ -27,11 +30,36 @@ pub(crate) fn build_initial_app(
     let mut app = AppState::default();
     app.enable_debug_print = debug_print;
     app.debug_perf_enabled = debug_perf;
+    // Before the settings, so a custom theme pinned in tui.toml resolves.
+    let theme_warnings = load_custom_themes();
+    let (settings, settings_warnings) = load_settings();
+    apply_startup_settings(&mut app, settings, settings_warnings);
+    for warning in theme_warnings {
+        app.push_line(LogKind::Error, format!("theme: {warning}"));
+    }
+    app.audit_log_path = audit_log_path();
+    start_update_check(&mut app);
 
-    for line in LOGO_LINES {
-        app.push_line(LogKind::System, line);
+    match load_banner_lines(&app.settings.banner) {
+        Ok(Some(lines)) => {
+            let has_banner = !lines.is_empty();
+            for line in lines {
+                app.push_line(LogKind::System, line);
+            }
+            if has_banner {
+                app.push_line(LogKind::Space, "");
+            }
+        }
+        result => {
+            if let Err(error) = result {
+                app.push_line(LogKind::Error, format!("settings: {error}"));
+            }
+            for line in LOGO_LINES {
+                app.push_line(LogKind::System, line);
+            }
+            app.push_line(LogKind::Space, "");
+        }
     }
-    app.push_line(LogKind::Space, "");
     app.push_line(LogKind::System, "Welcome to Codelia!");
     app.push_line(
         LogKind::System,
@@ -68,9 +96,99 @@ pub(crate) fn build_initial_app(
         app.push_line(LogKind::Space, "");
     }
 
+    let (secrets, secret_errors) = load_secrets_from_keychain();
+    if !secrets.is_empty() {
+        app.secrets = secrets;
+        app.push_line(
+            LogKind::Status,
+            format!(
+                "Loaded {} secret(s) from the OS keychain (/secrets)",
+                app.secrets.entries().len()
+            ),
+        );
+    }
+    for error in secret_errors {
+        app.push_line(LogKind::Error, format!("secrets: {error}"));
+    }
+
+    app
+}
+
+/// A `/tab new` app: startup flags and the live settings carry over; the banner does not.
+pub(crate) fn build_tab_app(current: &AppState, number: usize) -> AppState {
+    let mut app = AppState {
+        enable_debug_print: current.enable_debug_print,
+        debug_perf_enabled: current.debug_perf_enabled,
+        read_only: current.read_only,
+        low_bandwidth: current.low_bandwidth,
+        audit_log_path: current.audit_log_path.clone(),
+        draft: DraftAutosave::new(current.draft.path.clone()),
+        ..AppState::default()
+    };
+    apply_startup_settings(&mut app, current.settings.clone(), Vec::new());
+    app.mouse_capture_enabled = current.mouse_capture_enabled;
+    let (secrets, secret_errors) = load_secrets_from_keychain();
+    app.secrets = secrets;
+    for error in secret_errors {
+        app.push_line(LogKind::Error, format!("secrets: {error}"));
+    }
+    app.push_line(
+        LogKind::Status,
+        format!("Tab {number}: new runtime session"),
+    );
+    if app.read_only {
+        app.push_line(
+            LogKind::Status,
+            "Read-only session: write and exec tools are disabled",
+        );
+    }
+    app.push_line(LogKind::Space, "");
     app
 }
 
+/// `--read-only`: the runtime denies write/exec tools; the TUI also stops offering "remember".
+pub(crate) fn apply_read_only_startup(app: &mut AppState, requested_approval_mode: Option<&str>) {
+    app.read_only = true;
+    app.push_line(
+        LogKind::Status,
+        "Read-only session (`--read-only` or CODELIA_READ_ONLY=1): write and exec tools are disabled",
+    );
+    if let Some(mode) = requested_approval_mode.filter(|mode| *mode != "minimal") {
+        app.push_line(
+            LogKind::Status,
+            format!("Ignoring `--approval-mode {mode}`: read-only sessions run in minimal mode"),
+        );
+    }
+    app.push_line(LogKind::Space, "");
+}
+
+/// `--low-bandwidth`: plain code blocks, batched redraws, no spinner frames, no image previews
+/// and 16 colors, for slow links where escape-sequence volume makes the UI lag.
+pub(crate) fn apply_low_bandwidth_startup(app: &mut AppState) {
+    app.low_bandwidth = true;
+    sync_effects(app);
+    set_ansi16_only(true);
+    app.push_line(
+        LogKind::Status,
+        "Low-bandwidth mode (`--low-bandwidth` or CODELIA_LOW_BANDWIDTH=1): 16 colors, no syntax highlighting, spinner animation or image previews, batched redraws",
+    );
+    app.push_line(LogKind::Space, "");
+}
+
+/// Holds a `--prompt-file` batch until the runtime is ready (see `start_pending_prompt_batch`).
+pub(crate) fn apply_prompt_file_startup(app: &mut AppState, batch: PromptBatch) {
+    app.push_line(
+        LogKind::Status,
+        format!(
+            "Queued {} prompt(s) from {} (`--prompt-file`)",
+            batch.total(),
+            batch.label
+        ),
+    );
+    app.push_line(LogKind::Space, "");
+    app.prompt_batch = Some(batch);
+}
+
 pub(crate) fn request_initial_model_list(
     app: &mut AppState,
     child_stdin: &mut RuntimeStdin,
@@ -91,7 +209,14 @@ pub(crate) fn apply_resume_startup(
     resume_mode: ResumeMode,
 ) {
     match resume_mode {
-        ResumeMode::Id(session_id) => {
+        ResumeMode::Id(value) => {
+            let session_id = match parse_session_permalink(&value) {
+                Some((session_id, turn)) => {
+                    app.pending_turn_jump = turn;
+                    session_id
+                }
+                None => value,
+            };
             let short_id: String = session_id.chars().take(8).collect();
             app.runtime_info.session_id = Some(session_id);
             app.push_line(LogKind::Status, format!("Resume session {short_id}"));
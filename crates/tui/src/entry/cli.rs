@@ -1,4 +1,5 @@
 use std::env;
+use std::io::{IsTerminal, Read};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum ResumeMode {
@@ -59,9 +60,19 @@ pub(crate) fn print_basic_help() {
     println!("  --debug[=true|false]             Enable debug runtime/RPC log lines");
     println!("  --diagnostics[=true|false]       Enable per-call LLM diagnostics");
     println!("  -r, --resume [session_id]        Resume latest/session picker/session id");
+    println!("                                   (or a codelia://session/<id>#turn-<n> link)");
     println!("  --initial-message <text>         Queue initial prompt");
     println!("  --initial-user-message <text>    Alias of --initial-message");
+    println!("  --prompt-file <path>             Queue blank-line-separated prompts from a file");
     println!("  --debug-perf[=true|false]        Enable perf panel");
+    println!("  --read-only[=true|false]         Disable write/exec tools for the session");
+    println!("  --low-bandwidth[=true|false]     16 colors, no highlighting or animation (SSH)");
+    println!("  --editor-socket[=<path>]         Listen for editor plugins on a Unix socket");
+    println!(
+        "  --headless                       No UI: run one prompt (--initial-message or stdin),"
+    );
+    println!("                                   print output, exit 0/1 (130 if cancelled)");
+    println!("  -p, --print <text>               Headless run of <text>");
 }
 
 pub(crate) fn parse_resume_mode() -> ResumeMode {
@@ -139,6 +150,133 @@ pub(crate) fn parse_approval_mode_from_args(
     Ok(None)
 }
 
+pub(crate) fn parse_prompt_file() -> Result<Option<String>, String> {
+    parse_prompt_file_from_args(env::args().skip(1))
+}
+
+pub(crate) fn parse_prompt_file_from_args(
+    args: impl IntoIterator<Item = impl AsRef<str>>,
+) -> Result<Option<String>, String> {
+    let mut args = args.into_iter().map(|arg| arg.as_ref().to_string());
+    let mut path: Option<String> = None;
+    while let Some(arg) = args.next() {
+        let value = if let Some(value) = arg.strip_prefix("--prompt-file=") {
+            Some(value.to_string())
+        } else if arg == "--prompt-file" {
+            args.next()
+        } else {
+            continue;
+        };
+        match value.map(|value| value.trim().to_string()) {
+            Some(value) if !value.is_empty() => path = Some(value),
+            _ => return Err("--prompt-file requires a path".to_string()),
+        }
+    }
+    Ok(path)
+}
+
+/// `--editor-socket[=<path>]`, else `CODELIA_EDITOR_SOCKET` (`1`/`true` or a path): `None` keeps
+/// the socket off, `Some(None)` uses the default path.
+pub(crate) fn parse_editor_socket() -> Option<Option<String>> {
+    parse_editor_socket_from_values(
+        env::args().skip(1),
+        env::var("CODELIA_EDITOR_SOCKET").ok().as_deref(),
+    )
+}
+
+pub(crate) fn parse_editor_socket_from_values(
+    args: impl IntoIterator<Item = impl AsRef<str>>,
+    env_value: Option<&str>,
+) -> Option<Option<String>> {
+    let mut socket = None;
+    for arg in args {
+        let arg = arg.as_ref();
+        if arg == "--editor-socket" {
+            socket = Some(None);
+        } else if let Some(path) = arg.strip_prefix("--editor-socket=") {
+            let path = path.trim();
+            socket = Some((!path.is_empty()).then(|| path.to_string()));
+        }
+    }
+    if socket.is_some() {
+        return socket;
+    }
+    match env_value.map(str::trim) {
+        None | Some("") | Some("0") => None,
+        Some(value) if value.eq_ignore_ascii_case("false") => None,
+        Some(value) if value == "1" || value.eq_ignore_ascii_case("true") => Some(None),
+        Some(path) => Some(Some(path.to_string())),
+    }
+}
+
+/// `--headless` / `-p, --print <text>`: `Ok(None)` when neither is given, `Ok(Some(None))` when
+/// the prompt has to come from `--initial-message` or stdin.
+pub(crate) fn parse_headless_mode() -> Result<Option<Option<String>>, String> {
+    parse_headless_mode_from_args(env::args().skip(1))
+}
+
+pub(crate) fn parse_headless_mode_from_args(
+    args: impl IntoIterator<Item = impl AsRef<str>>,
+) -> Result<Option<Option<String>>, String> {
+    let mut args = args.into_iter().map(|arg| arg.as_ref().to_string());
+    let mut mode: Option<Option<String>> = None;
+    while let Some(arg) = args.next() {
+        if arg == "--headless" {
+            mode = Some(mode.flatten());
+            continue;
+        }
+        let value = if let Some(value) = arg.strip_prefix("--print=") {
+            Some(value.to_string())
+        } else if arg == "-p" || arg == "--print" {
+            args.next()
+        } else {
+            continue;
+        };
+        match value {
+            Some(value) if !value.trim().is_empty() => mode = Some(Some(value)),
+            _ => return Err("--print requires a prompt".to_string()),
+        }
+    }
+    Ok(mode)
+}
+
+/// Headless prompt: `--print` text, else `--initial-message`, else all of stdin unless it is a
+/// terminal.
+pub(crate) fn resolve_headless_prompt(
+    print_prompt: Option<String>,
+    initial_message: Option<String>,
+) -> Result<String, String> {
+    let prompt = match print_prompt.or(initial_message) {
+        Some(prompt) => prompt,
+        None if std::io::stdin().is_terminal() => String::new(),
+        None => {
+            let mut text = String::new();
+            std::io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|error| format!("failed to read the prompt from stdin: {error}"))?;
+            text
+        }
+    };
+    if prompt.trim().is_empty() {
+        return Err(
+            "--headless needs a prompt (--print <text>, --initial-message, or stdin)".to_string(),
+        );
+    }
+    Ok(prompt)
+}
+
+/// Read-only sessions always run the runtime in `minimal` so nothing is auto-approved.
+pub(crate) fn resolve_runtime_approval_mode(
+    requested: Option<String>,
+    read_only: bool,
+) -> Option<String> {
+    if read_only {
+        Some("minimal".to_string())
+    } else {
+        requested
+    }
+}
+
 pub(crate) fn parse_initial_message_from_args(
     args: impl IntoIterator<Item = impl AsRef<str>>,
 ) -> Option<String> {
@@ -216,3 +354,11 @@ pub(crate) fn debug_perf_enabled() -> bool {
 pub(crate) fn diagnostics_enabled() -> bool {
     cli_flag_enabled("--diagnostics") || env_truthy("CODELIA_DIAGNOSTICS")
 }
+
+pub(crate) fn read_only_enabled() -> bool {
+    cli_flag_enabled("--read-only") || env_truthy("CODELIA_READ_ONLY")
+}
+
+pub(crate) fn low_bandwidth_enabled() -> bool {
+    cli_flag_enabled("--low-bandwidth") || env_truthy("CODELIA_LOW_BANDWIDTH")
+}
@@ -0,0 +1,187 @@
+use crate::app::handlers::command::{start_prompt_run, try_dispatch_queued_prompt};
+use crate::app::handlers::confirm::activate_pending_confirm_dialog;
+use crate::app::state::LogKind;
+use crate::app::AppState;
+use crate::entry::line_mode::{
+    active_dialog_id, close_interactive_panels, is_idle, stable_log_len,
+};
+use crate::event_loop::input::handle_non_main_key;
+use crate::event_loop::runtime::{can_auto_start_initial_message, process_runtime_messages};
+use crate::event_loop::{RuntimeReceiver, RuntimeStdin};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::io::Write;
+use std::process::Child;
+use std::time::Duration;
+
+const HEADLESS_TICK: Duration = Duration::from_millis(50);
+const HEADLESS_EXIT_CANCELLED: i32 = 130;
+
+/// Where a log line goes: the answer and tool activity on stdout, failures on stderr, and
+/// TUI chatter (echoed prompt, queue/status notes, RPC traces) nowhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HeadlessStream {
+    Stdout,
+    Stderr,
+    Skip,
+}
+
+fn headless_stream(kind: LogKind) -> HeadlessStream {
+    match kind {
+        LogKind::Assistant
+        | LogKind::AssistantCode
+        | LogKind::ToolCall
+        | LogKind::ToolResult
+        | LogKind::TodoPending
+        | LogKind::TodoInProgress
+        | LogKind::TodoCompleted
+        | LogKind::DiffMeta
+        | LogKind::DiffContext
+        | LogKind::DiffCode
+        | LogKind::DiffAdded
+        | LogKind::DiffRemoved
+        | LogKind::Shell
+        | LogKind::Space => HeadlessStream::Stdout,
+        LogKind::Error | LogKind::Runtime => HeadlessStream::Stderr,
+        LogKind::System
+        | LogKind::User
+        | LogKind::Reasoning
+        | LogKind::Status
+        | LogKind::Compaction
+        | LogKind::Rpc => HeadlessStream::Skip,
+    }
+}
+
+fn flush_headless_log(
+    app: &AppState,
+    printed_until: &mut usize,
+    all: bool,
+    out: &mut impl Write,
+    err: &mut impl Write,
+) {
+    let end = if all {
+        app.log.len()
+    } else {
+        stable_log_len(app)
+    };
+    for line in app.log.iter().take(end).skip(*printed_until) {
+        let text = line.plain_text();
+        let _ = match headless_stream(line.kind()) {
+            HeadlessStream::Stdout => writeln!(out, "{}", text.trim_end()),
+            HeadlessStream::Stderr => writeln!(err, "{}", text.trim_end()),
+            HeadlessStream::Skip => Ok(()),
+        };
+    }
+    *printed_until = (*printed_until).max(end);
+    let _ = out.flush();
+    let _ = err.flush();
+}
+
+/// Nobody can answer dialogs here: confirms are denied, picks and prompts cancelled.
+fn decline_dialog(
+    app: &mut AppState,
+    err: &mut impl Write,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) {
+    let (key, title) = if let Some(dialog) = &app.confirm_dialog {
+        (KeyCode::Char('n'), dialog.title.clone())
+    } else if let Some(dialog) = &app.prompt_dialog {
+        (KeyCode::Esc, dialog.title.clone())
+    } else if let Some(dialog) = &app.pick_dialog {
+        (KeyCode::Esc, dialog.title.clone())
+    } else {
+        return;
+    };
+    let _ = writeln!(
+        err,
+        "[headless] declined: {title} (pass --approval-mode to allow tools without asking)"
+    );
+    let _ = handle_non_main_key(app, key, KeyModifiers::NONE, child_stdin, next_id);
+}
+
+fn exit_code_for(run_status: Option<&str>) -> i32 {
+    match run_status {
+        Some("completed") => 0,
+        Some("cancelled") => HEADLESS_EXIT_CANCELLED,
+        _ => 1,
+    }
+}
+
+/// `--headless`: sends `prompt` as a single `run.start`, streams the run as plain text, and
+/// returns the process exit code (0 completed, 130 cancelled, 1 anything else).
+pub(crate) fn run_headless(
+    app: &mut AppState,
+    rx: &RuntimeReceiver,
+    child: &mut Child,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+    prompt: &str,
+) -> i32 {
+    let mut out = std::io::stdout().lock();
+    let mut err = std::io::stderr().lock();
+    let mut printed_until = app.log.len();
+    let mut dispatched = false;
+    let mut declined: Option<String> = None;
+
+    loop {
+        process_runtime_messages(app, rx, child_stdin, next_id);
+        activate_pending_confirm_dialog(app);
+        close_interactive_panels(app);
+        let dialog_id = active_dialog_id(app);
+        if dialog_id.is_some() && dialog_id != declined {
+            flush_headless_log(app, &mut printed_until, true, &mut out, &mut err);
+            decline_dialog(app, &mut err, child_stdin, next_id);
+        }
+        declined = dialog_id;
+
+        if !dispatched && can_auto_start_initial_message(app) {
+            dispatched = true;
+            start_prompt_run(app, child_stdin, next_id, prompt);
+        }
+        try_dispatch_queued_prompt(app, child_stdin, next_id);
+        flush_headless_log(app, &mut printed_until, false, &mut out, &mut err);
+
+        if let Ok(Some(status)) = child.try_wait() {
+            app.push_line(LogKind::Runtime, format!("runtime exited: {status}"));
+            flush_headless_log(app, &mut printed_until, true, &mut out, &mut err);
+            return 1;
+        }
+        if dispatched && is_idle(app) {
+            flush_headless_log(app, &mut printed_until, true, &mut out, &mut err);
+            return exit_code_for(app.run_status.as_deref());
+        }
+        std::thread::sleep(HEADLESS_TICK);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{exit_code_for, flush_headless_log};
+    use crate::app::state::LogKind;
+    use crate::app::AppState;
+
+    #[test]
+    fn headless_output_splits_streams_and_maps_run_status() {
+        let mut app = AppState::default();
+        app.push_line(LogKind::User, "> fix it");
+        app.push_line(LogKind::ToolCall, "bash cargo test");
+        app.push_line(LogKind::Status, "Queued prompt q1");
+        app.push_line(LogKind::Assistant, "Fixed the failing test.");
+        app.push_line(LogKind::Error, "run error: quota");
+
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        let mut printed_until = 0;
+        flush_headless_log(&app, &mut printed_until, true, &mut out, &mut err);
+        assert_eq!(
+            String::from_utf8_lossy(&out),
+            "bash cargo test\nFixed the failing test.\n"
+        );
+        assert_eq!(String::from_utf8_lossy(&err), "run error: quota\n");
+        assert_eq!(printed_until, 5);
+
+        assert_eq!(exit_code_for(Some("completed")), 0);
+        assert_eq!(exit_code_for(Some("cancelled")), 130);
+        assert_eq!(exit_code_for(Some("error")), 1);
+        assert_eq!(exit_code_for(None), 1);
+    }
+}
@@ -0,0 +1,310 @@
+use crate::app::handlers::command::{
+    finish_prompt_batch_if_done, start_pending_prompt_batch, start_prompt_run,
+    try_dispatch_queued_prompt,
+};
+use crate::app::handlers::confirm::activate_pending_confirm_dialog;
+use crate::app::handlers::handle_enter;
+use crate::app::state::LogKind;
+use crate::app::{AppState, PickDialogState};
+use crate::event_loop::input::handle_non_main_key;
+use crate::event_loop::runtime::{can_auto_start_initial_message, process_runtime_messages};
+use crate::event_loop::{RuntimeReceiver, RuntimeStdin};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::io::{BufRead, Write};
+use std::process::Child;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+const LINE_MODE_TICK: Duration = Duration::from_millis(50);
+
+/// Lines past the first still-updating component (tool call awaiting its result, progress row)
+/// are held back so each printed line is final.
+pub(crate) fn stable_log_len(app: &AppState) -> usize {
+    let pending = app
+        .pending_component_lines
+        .values()
+        .map(|span| span.first_index());
+    let progress = app.progress_component_lines.values().copied();
+    pending
+        .chain(progress)
+        .min()
+        .unwrap_or(app.log.len())
+        .min(app.log.len())
+}
+
+fn flush_log(app: &AppState, printed_until: &mut usize, all: bool, out: &mut impl Write) {
+    let end = if all {
+        app.log.len()
+    } else {
+        stable_log_len(app)
+    };
+    for line in app.log.iter().take(end).skip(*printed_until) {
+        let _ = writeln!(out, "{}", line.plain_text().trim_end());
+    }
+    *printed_until = (*printed_until).max(end);
+    let _ = out.flush();
+}
+
+/// Applies `2` / `1,3` style answers; returns false when the line should cancel the pick.
+fn apply_pick_answer(pick: &mut PickDialogState, answer: &str) -> bool {
+    let indexes = answer
+        .split([',', ' '])
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse::<usize>().ok())
+        .collect::<Option<Vec<_>>>()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|index| (1..=pick.items.len()).contains(index))
+        .collect::<Vec<_>>();
+    let Some(first) = indexes.first() else {
+        return false;
+    };
+    if pick.multi {
+        for index in &indexes {
+            if let Some(chosen) = pick.chosen.get_mut(index - 1) {
+                *chosen = true;
+            }
+        }
+    } else {
+        pick.selected = first - 1;
+    }
+    true
+}
+
+pub(crate) fn active_dialog_id(app: &AppState) -> Option<String> {
+    app.confirm_dialog
+        .as_ref()
+        .map(|dialog| dialog.id.clone())
+        .or_else(|| app.prompt_dialog.as_ref().map(|dialog| dialog.id.clone()))
+        .or_else(|| app.pick_dialog.as_ref().map(|dialog| dialog.id.clone()))
+}
+
+fn announce_dialog(app: &AppState, out: &mut impl Write) {
+    if let Some(dialog) = &app.confirm_dialog {
+        let _ = writeln!(out, "[confirm] {}: {} (y/N)", dialog.title, dialog.message);
+    } else if let Some(dialog) = &app.prompt_dialog {
+        let _ = writeln!(out, "[prompt] {}: {}", dialog.title, dialog.message);
+    } else if let Some(dialog) = &app.pick_dialog {
+        let _ = writeln!(out, "[pick] {}", dialog.title);
+        for (index, item) in dialog.items.iter().enumerate() {
+            let _ = writeln!(out, "  {}. {}", index + 1, item.label);
+        }
+        let hint = if dialog.multi {
+            "numbers separated by commas"
+        } else {
+            "a number"
+        };
+        let _ = writeln!(out, "(enter {hint}; empty line cancels)");
+    }
+    let _ = out.flush();
+}
+
+/// Panels are keyboard-driven views; they cannot be used here and would block the queue.
+pub(crate) fn close_interactive_panels(app: &mut AppState) {
+    let had_panel = app.provider_picker.take().is_some()
+        | app.model_picker.take().is_some()
+        | app.reasoning_picker.take().is_some()
+        | app.model_list_panel.take().is_some()
+        | app.session_list_panel.take().is_some()
+        | app.lane_list_panel.take().is_some()
+        | app.lane_attach.take().is_some()
+        | app.mcp_list_panel.take().is_some()
+        | app.context_panel.take().is_some()
+        | app.skills_list_panel.take().is_some()
+        | app.theme_list_panel.take().is_some()
+        | app.secrets_panel.take().is_some()
+        | app.permissions_panel.take().is_some()
+        | app.tools_panel.take().is_some()
+        // A post-run queue review is dropped quietly: the queue simply goes on.
+        | app.queue_panel.take().is_some_and(|panel| !panel.review)
+        | app.whats_new_panel.take().is_some()
+        | app.edit_conflict_panel.take().is_some();
+    app.panel_stack.clear();
+    if had_panel {
+        app.push_line(
+            LogKind::Status,
+            "(interactive panel skipped in line mode; pass arguments to the command instead)",
+        );
+    }
+}
+
+fn answer_dialog(
+    app: &mut AppState,
+    answer: &str,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) {
+    let key = if app.confirm_dialog.is_some() {
+        match answer.trim().to_ascii_lowercase().as_str() {
+            "y" | "yes" => KeyCode::Char('y'),
+            _ => KeyCode::Char('n'),
+        }
+    } else if app.prompt_dialog.is_some() {
+        app.prompt_input.set_from(answer);
+        KeyCode::Enter
+    } else {
+        let picked = app
+            .pick_dialog
+            .as_mut()
+            .is_some_and(|pick| apply_pick_answer(pick, answer.trim()));
+        if picked {
+            KeyCode::Enter
+        } else {
+            KeyCode::Esc
+        }
+    };
+    let _ = handle_non_main_key(app, key, KeyModifiers::NONE, child_stdin, next_id);
+}
+
+fn spawn_stdin_reader() -> Receiver<String> {
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lock().lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+pub(crate) fn is_idle(app: &AppState) -> bool {
+    !app.is_running()
+        && app.rpc_pending.run_start_id.is_none()
+        && app.dispatching_prompt.is_none()
+        && app.pending_prompt_queue.is_empty()
+        && app.prompt_batch.is_none()
+        && active_dialog_id(app).is_none()
+        && app.pending_confirm_dialog.is_none()
+}
+
+/// Line-oriented fallback used when stdout is not a terminal: plain log lines on stdout, one
+/// composer submission (or dialog answer) per stdin line, exit once stdin closes and work drains.
+pub(crate) fn run_line_mode(
+    app: &mut AppState,
+    rx: &RuntimeReceiver,
+    child: &mut Child,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+    pending_initial_message: &mut Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut out = std::io::stdout().lock();
+    let input = spawn_stdin_reader();
+    let mut input_closed = false;
+    let mut printed_until = 0_usize;
+    let mut announced_dialog: Option<String> = None;
+
+    loop {
+        process_runtime_messages(app, rx, child_stdin, next_id);
+        activate_pending_confirm_dialog(app);
+        close_interactive_panels(app);
+
+        if pending_initial_message.is_some() && can_auto_start_initial_message(app) {
+            if let Some(message) = pending_initial_message.take() {
+                start_prompt_run(app, child_stdin, next_id, &message);
+            }
+        }
+        start_pending_prompt_batch(app, child_stdin, next_id);
+        try_dispatch_queued_prompt(app, child_stdin, next_id);
+        finish_prompt_batch_if_done(app);
+
+        let runtime_exited = match child.try_wait() {
+            Ok(Some(status)) => {
+                app.push_line(LogKind::Runtime, format!("runtime exited: {}", status));
+                true
+            }
+            _ => false,
+        };
+
+        let dialog_id = active_dialog_id(app);
+        if dialog_id.is_some() && dialog_id != announced_dialog {
+            flush_log(app, &mut printed_until, true, &mut out);
+            announce_dialog(app, &mut out);
+        }
+        announced_dialog = dialog_id;
+        flush_log(app, &mut printed_until, false, &mut out);
+
+        let drained = input_closed && is_idle(app) && pending_initial_message.is_none();
+        if runtime_exited || drained {
+            flush_log(app, &mut printed_until, true, &mut out);
+            break;
+        }
+
+        match input.recv_timeout(LINE_MODE_TICK) {
+            Ok(line) => {
+                if announced_dialog.is_some() {
+                    answer_dialog(app, &line, child_stdin, next_id);
+                } else {
+                    app.input.set_from(&line);
+                    handle_enter(app, child_stdin, next_id);
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => {
+                input_closed = true;
+                std::thread::sleep(LINE_MODE_TICK);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_pick_answer, flush_log, stable_log_len};
+    use crate::app::state::LogKind;
+    use crate::app::{AppState, LogComponentSpan, PickDialogItem, PickDialogState};
+
+    #[test]
+    fn flush_holds_back_lines_from_pending_components() {
+        let mut app = AppState::default();
+        app.push_line(LogKind::Status, "first");
+        app.push_line(LogKind::ToolCall, "tool call (running)");
+        app.push_line(LogKind::Status, "after");
+        app.pending_component_lines
+            .insert("call-1".to_string(), LogComponentSpan::single(1));
+        assert_eq!(stable_log_len(&app), 1);
+
+        let mut out = Vec::new();
+        let mut printed_until = 0;
+        flush_log(&app, &mut printed_until, false, &mut out);
+        assert_eq!(String::from_utf8_lossy(&out), "first\n");
+
+        app.pending_component_lines.clear();
+        flush_log(&app, &mut printed_until, false, &mut out);
+        assert_eq!(
+            String::from_utf8_lossy(&out),
+            "first\ntool call (running)\nafter\n"
+        );
+    }
+
+    #[test]
+    fn pick_answers_select_by_number() {
+        let item = |id: &str| PickDialogItem {
+            id: id.to_string(),
+            label: id.to_string(),
+            detail: None,
+        };
+        let mut pick = PickDialogState {
+            id: "pick".to_string(),
+            title: "Choose".to_string(),
+            message: None,
+            items: vec![item("a"), item("b"), item("c")],
+            selected: 0,
+            multi: false,
+            chosen: vec![false; 3],
+        };
+        assert!(apply_pick_answer(&mut pick, "2"));
+        assert_eq!(pick.selected, 1);
+        assert!(!apply_pick_answer(&mut pick, ""));
+        assert!(!apply_pick_answer(&mut pick, "9"));
+
+        pick.multi = true;
+        assert!(apply_pick_answer(&mut pick, "1, 3"));
+        assert_eq!(pick.chosen, vec![true, false, true]);
+    }
+}
@@ -1,4 +1,8 @@
 pub(crate) mod bootstrap;
 pub(crate) mod cli;
+pub(crate) mod headless;
+pub(crate) mod line_mode;
 pub(crate) mod run_loop;
+pub(crate) mod suspend;
+pub(crate) mod tabs;
 pub(crate) mod terminal;
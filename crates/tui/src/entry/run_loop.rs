@@ -1,23 +1,40 @@
 use crate::app::handlers::confirm::activate_pending_confirm_dialog;
+use crate::app::handlers::editor_bridge::apply_editor_requests;
+use crate::app::handlers::external_editor::apply_external_edit;
+use crate::app::handlers::memory_caps::enforce_memory_caps;
+use crate::app::handlers::watchdog::check_runtime_stall;
+use crate::app::markdown::set_markdown_width;
+use crate::app::render::glyphs::{apply_ascii_glyphs, ascii_glyphs};
 use crate::app::render::inline::apply_terminal_effects;
+use crate::app::render::palette::{ansi16_only, apply_ansi16_colors};
 use crate::app::state::LogKind;
+use crate::app::util::editor_bridge::EditorBridge;
+use crate::app::util::external_editor::{edit_in_external_editor, editor_from_env};
+use crate::app::util::input_log::record_input_event;
+use crate::app::util::notify::{send_desktop_notification, set_terminal_focused};
 use crate::app::util::sample_memory;
-use crate::app::view::draw_ui;
-use crate::app::AppState;
-use crate::entry::terminal::TuiTerminal;
+use crate::app::view::{desired_height, draw_ui};
+use crate::entry::suspend::{stop_process, take_continued, take_stop_request};
+use crate::entry::tabs::{RuntimeLaunchConfig, RuntimeWatch, SessionTab, SessionTabs};
+use crate::entry::terminal::{
+    restore_inline_cursor, resume_terminal, ring_bell, set_mouse_capture, set_terminal_title,
+    suspend_terminal, SavedTerminalTitle, TuiTerminal,
+};
 use crate::event_loop::input::{
-    apply_redraw, blocks_input_paste, handle_ctrl_c, handle_main_key, handle_mouse_event,
-    handle_non_main_key, handle_paste, maybe_request_skills_catalog,
+    apply_redraw, blocks_input_paste, expire_pending_chord, handle_ctrl_c, handle_main_key,
+    handle_mouse_event, handle_non_main_key, handle_paste, maybe_request_skills_catalog,
 };
 use crate::event_loop::runtime::{can_auto_start_initial_message, process_runtime_messages};
-use crate::event_loop::{RuntimeReceiver, RuntimeStdin};
 use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
 use std::fmt;
-use std::process::Child;
 use std::time::{Duration, Instant};
 
 const CTRL_C_FORCE_QUIT_WINDOW: Duration = Duration::from_secs(2);
 const DEBUG_PERF_MEMORY_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+const MEMORY_CAP_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+const IDLE_POLL_TIMEOUT: Duration = Duration::from_millis(50);
+// Reduced effects cap redraws so scrollback inserts go out in fewer, larger batches.
+const REDUCED_EFFECTS_FRAME_INTERVAL: Duration = Duration::from_millis(100);
 
 #[derive(Clone, Copy, Debug)]
 struct KeyDebugLog {
@@ -46,29 +63,160 @@ impl fmt::Display for KeyDebugLog {
     }
 }
 
+/// Suspends the TUI for `$EDITOR` on the composer text. Runtime events queue up meanwhile and
+/// are processed once the editor exits.
+fn run_external_editor(
+    app: &mut crate::app::AppState,
+    terminal: &mut TuiTerminal,
+    use_alt_screen: bool,
+) -> std::io::Result<()> {
+    app.external_edit_requested = false;
+    let editor = editor_from_env();
+    let text = app.input.current();
+    suspend_terminal(terminal, use_alt_screen);
+    let result = edit_in_external_editor(&editor, &text);
+    resume_terminal(terminal, use_alt_screen, app.mouse_capture_enabled, None)?;
+    apply_external_edit(app, &editor, result);
+    Ok(())
+}
+
+/// Inline viewport rows for the current terminal size; at least 12 when the screen allows.
+pub(crate) fn inline_viewport_height(app: &mut crate::app::AppState) -> std::io::Result<u16> {
+    let (width, height) = crossterm::terminal::size()?;
+    Ok(desired_height(app, width, height)
+        .max(12)
+        .min(height)
+        .max(1))
+}
+
+/// `Ctrl+Z` or `SIGTSTP`: hands the terminal back and stops until `fg`. The shell prints below
+/// the viewport meanwhile, so the inline viewport is re-anchored under its output afterwards.
+fn suspend_tui(
+    app: &mut crate::app::AppState,
+    terminal: &mut TuiTerminal,
+    use_alt_screen: bool,
+) -> std::io::Result<()> {
+    app.suspend_requested = false;
+    if !use_alt_screen {
+        restore_inline_cursor(terminal);
+    }
+    suspend_terminal(terminal, use_alt_screen);
+    stop_process();
+    // Our own SIGCONT; the resume below covers it.
+    take_continued();
+    resume_after_stop(app, terminal, use_alt_screen)
+}
+
+fn resume_after_stop(
+    app: &mut crate::app::AppState,
+    terminal: &mut TuiTerminal,
+    use_alt_screen: bool,
+) -> std::io::Result<()> {
+    let inline_height = inline_viewport_height(app)?;
+    resume_terminal(
+        terminal,
+        use_alt_screen,
+        app.mouse_capture_enabled,
+        Some(inline_height),
+    )
+}
+
 pub(crate) fn run_tui_loop(
-    app: &mut AppState,
+    tabs: &mut SessionTabs,
+    config: &RuntimeLaunchConfig,
     terminal: &mut TuiTerminal,
-    rx: &RuntimeReceiver,
-    child: &mut Child,
-    child_stdin: &mut RuntimeStdin,
-    next_id: &mut impl FnMut() -> String,
     pending_initial_message: &mut Option<String>,
     use_alt_screen: bool,
+    saved_title: &SavedTerminalTitle,
+    editor_bridge: Option<&EditorBridge>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let mut needs_redraw = true;
     let mut should_exit = false;
     let key_debug = std::env::var("CODELIA_TUI_KEY_DEBUG").ok().as_deref() == Some("1");
     let mut last_ctrl_c_at: Option<Instant> = None;
     let mut last_memory_sample_at = Instant::now() - DEBUG_PERF_MEMORY_SAMPLE_INTERVAL;
+    let mut last_memory_cap_check_at = Instant::now();
+    let mut last_draw_at = Instant::now() - IDLE_POLL_TIMEOUT;
+    let mut mouse_capture_applied = tabs.active_mut().app.mouse_capture_enabled;
+    let launch_dir = std::env::current_dir()
+        .ok()
+        .map(|dir| dir.to_string_lossy().into_owned());
+    let mut title_applied: Option<String> = None;
 
     loop {
+        if let Some(request) = tabs.active_mut().app.tab_request.take() {
+            tabs.apply_request(request, config);
+            needs_redraw = true;
+        }
+        if tabs.tick_background(config, !use_alt_screen) {
+            needs_redraw = true;
+        }
+        match tabs.active_mut().watch_runtime(config, Instant::now()) {
+            RuntimeWatch::Unchanged => {}
+            RuntimeWatch::Changed => needs_redraw = true,
+            RuntimeWatch::Exited => {
+                needs_redraw = true;
+                should_exit = tabs.tabs.len() == 1;
+            }
+        }
+        let SessionTab {
+            app,
+            child_stdin,
+            rx,
+            rpc_id,
+            ..
+        } = tabs.active_mut();
+        let mut next_id = || {
+            *rpc_id += 1;
+            rpc_id.to_string()
+        };
+        let next_id = &mut next_id;
+
+        if app.external_edit_requested {
+            run_external_editor(app, terminal, use_alt_screen)?;
+            needs_redraw = true;
+        }
+        if app.suspend_requested || take_stop_request() {
+            suspend_tui(app, terminal, use_alt_screen)?;
+            needs_redraw = true;
+        } else if take_continued() {
+            // Stopped by SIGSTOP, which cannot be caught: only the screen needs repairing.
+            resume_after_stop(app, terminal, use_alt_screen)?;
+            needs_redraw = true;
+        }
+
         if process_runtime_messages(app, rx, child_stdin, next_id) {
             needs_redraw = true;
         }
 
         maybe_request_skills_catalog(app, child_stdin, next_id);
 
+        if let Some(bridge) = editor_bridge {
+            if apply_editor_requests(app, child_stdin, next_id, bridge) {
+                needs_redraw = true;
+            }
+        }
+
+        if crate::app::handlers::forge::apply_forge_results(app) {
+            needs_redraw = true;
+        }
+        if crate::app::handlers::forge::tick_ci_watch(app) {
+            needs_redraw = true;
+        }
+        if crate::app::handlers::settings::reload_settings_if_changed(app) {
+            needs_redraw = true;
+        }
+        crate::app::handlers::draft::autosave_draft(app, Instant::now());
+        if crate::app::handlers::update::apply_update_check_result(app) {
+            needs_redraw = true;
+        }
+        if crate::app::handlers::translate::apply_translation_results(app, !use_alt_screen) {
+            needs_redraw = true;
+        }
+        if crate::app::handlers::local_models::apply_local_model_probe_result(app) {
+            needs_redraw = true;
+        }
+
         if pending_initial_message.is_some() && can_auto_start_initial_message(app) {
             if let Some(message) = pending_initial_message.take() {
                 if crate::app::handlers::command::start_prompt_run(
@@ -83,23 +231,41 @@ pub(crate) fn run_tui_loop(
             }
         }
 
-        if crate::app::handlers::command::try_dispatch_queued_prompt(app, child_stdin, next_id) {
+        if crate::app::handlers::command::start_pending_prompt_batch(app, child_stdin, next_id) {
             needs_redraw = true;
         }
 
-        if let Ok(Some(status)) = child.try_wait() {
-            app.push_line(LogKind::Runtime, format!("runtime exited: {}", status));
+        if crate::app::handlers::command::try_dispatch_queued_prompt(app, child_stdin, next_id) {
+            needs_redraw = true;
+        }
+        if crate::app::handlers::command::finish_prompt_batch_if_done(app) {
             needs_redraw = true;
-            should_exit = true;
         }
 
-        let timeout = Duration::from_millis(50);
+        let frame_interval = if app.effects_reduced() {
+            app.settings
+                .frame_interval()
+                .max(REDUCED_EFFECTS_FRAME_INTERVAL)
+        } else {
+            app.settings.frame_interval()
+        };
+        // With an FPS cap, a pending redraw only waits until the next frame slot.
+        let timeout = if needs_redraw && !frame_interval.is_zero() {
+            frame_interval
+                .saturating_sub(last_draw_at.elapsed())
+                .min(IDLE_POLL_TIMEOUT)
+        } else {
+            IDLE_POLL_TIMEOUT
+        };
         if event::poll(timeout)? {
-            match event::read()? {
+            let event = event::read()?;
+            record_input_event(&event);
+            match event {
                 Event::Key(key) => {
                     if key.kind != KeyEventKind::Press {
                         continue;
                     }
+                    app.live_region.settle();
                     if key_debug {
                         eprintln!("key: {}", KeyDebugLog::from_event(&key));
                     }
@@ -151,24 +317,57 @@ pub(crate) fn run_tui_loop(
                 Event::Resize(_, _) => {
                     needs_redraw = true;
                 }
-                _ => {}
+                Event::FocusGained => set_terminal_focused(true),
+                Event::FocusLost => set_terminal_focused(false),
             }
         }
 
+        if expire_pending_chord(app, terminal, child_stdin, next_id) {
+            app.prune_unreferenced_attachments();
+            needs_redraw = true;
+        }
+
         let now = Instant::now();
         if app.update_spinner(now) {
             needs_redraw = true;
         }
+        if check_runtime_stall(app, now) {
+            needs_redraw = true;
+        }
+        if crate::app::handlers::file_mentions::refresh_mention_matches(app) {
+            needs_redraw = true;
+        }
         if app.debug_perf_enabled
             && now.duration_since(last_memory_sample_at) >= DEBUG_PERF_MEMORY_SAMPLE_INTERVAL
         {
             last_memory_sample_at = now;
-            if app.record_memory_sample(sample_memory(Some(child.id()))) {
+            if app.record_memory_sample(sample_memory(
+                app.runtime_info.launch.as_ref().map(|launch| launch.pid),
+            )) {
                 needs_redraw = true;
             }
         }
 
-        if needs_redraw {
+        if app.mouse_capture_enabled != mouse_capture_applied {
+            mouse_capture_applied = app.mouse_capture_enabled;
+            set_mouse_capture(terminal, mouse_capture_applied);
+        }
+        let title = app
+            .settings
+            .terminal_title
+            .then(|| app.terminal_title(launch_dir.as_deref()));
+        if title != title_applied {
+            set_terminal_title(terminal, title.as_deref(), saved_title);
+            title_applied = title;
+        }
+        if let Some(body) = app.pending_notification.take() {
+            if send_desktop_notification("codelia", &body).is_err() {
+                ring_bell(terminal);
+            }
+        }
+
+        if needs_redraw && last_draw_at.elapsed() >= frame_interval {
+            last_draw_at = Instant::now();
             let frame_started = Instant::now();
             let mut followup_redraw = false;
             let log_changed_for_scrollback = app.log_changed;
@@ -177,8 +376,16 @@ pub(crate) fn run_tui_loop(
             terminal.draw(|f| {
                 viewport_width = f.area().width.max(1);
                 draw_ui(f, app);
+                let area = f.area();
+                if ascii_glyphs() {
+                    apply_ascii_glyphs(f.buffer_mut(), area);
+                }
+                if ansi16_only() {
+                    apply_ansi16_colors(f.buffer_mut(), area);
+                }
             })?;
             app.record_perf_frame(frame_started.elapsed(), draw_started.elapsed());
+            set_markdown_width(app.last_wrap_width);
             if !use_alt_screen {
                 let effects = apply_terminal_effects(
                     terminal,
@@ -190,17 +397,30 @@ pub(crate) fn run_tui_loop(
                     followup_redraw = true;
                 }
             }
+            if crate::app::handlers::settings::record_frame_time(app, frame_started.elapsed()) {
+                followup_redraw = true;
+            }
             if activate_pending_confirm_dialog(app) {
                 needs_redraw = true;
                 continue;
             }
             needs_redraw = followup_redraw;
             app.assert_render_invariants();
+            // Right after a draw the wrapped log matches the log, which log trimming needs.
+            if last_memory_cap_check_at.elapsed() >= MEMORY_CAP_CHECK_INTERVAL {
+                last_memory_cap_check_at = Instant::now();
+                if enforce_memory_caps(app, !use_alt_screen) {
+                    needs_redraw = true;
+                }
+            }
         }
         if should_exit {
             break;
         }
     }
 
+    for tab in tabs.tabs.iter_mut() {
+        crate::app::handlers::draft::flush_draft(&mut tab.app);
+    }
     Ok(())
 }
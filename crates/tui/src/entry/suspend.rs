@@ -0,0 +1,78 @@
+//! Job control: `Ctrl+Z` on an empty composer and `SIGTSTP` from outside stop the process the
+//! way a shell expects, with the terminal handed back first. Raw mode turns off `ISIG`, so the
+//! key never raises the signal itself; the run loop does both through [`stop_process`].
+
+#[cfg(unix)]
+mod imp {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    static STOP_REQUESTED: AtomicBool = AtomicBool::new(false);
+    static CONTINUED: AtomicBool = AtomicBool::new(false);
+
+    extern "C" fn on_tstp(_: libc::c_int) {
+        STOP_REQUESTED.store(true, Ordering::SeqCst);
+    }
+
+    extern "C" fn on_cont(_: libc::c_int) {
+        CONTINUED.store(true, Ordering::SeqCst);
+    }
+
+    fn catch_tstp() {
+        // SAFETY: the handler only stores to an atomic, which is async-signal-safe.
+        unsafe {
+            libc::signal(
+                libc::SIGTSTP,
+                on_tstp as extern "C" fn(libc::c_int) as libc::sighandler_t,
+            );
+        }
+    }
+
+    pub(crate) fn install_signal_handlers() {
+        catch_tstp();
+        // SAFETY: as above.
+        unsafe {
+            libc::signal(
+                libc::SIGCONT,
+                on_cont as extern "C" fn(libc::c_int) as libc::sighandler_t,
+            );
+        }
+    }
+
+    pub(crate) fn take_stop_request() -> bool {
+        STOP_REQUESTED.swap(false, Ordering::SeqCst)
+    }
+
+    pub(crate) fn take_continued() -> bool {
+        CONTINUED.swap(false, Ordering::SeqCst)
+    }
+
+    pub(crate) fn stop_process() {
+        // SAFETY: restoring the default action and raising the signal stops this process
+        // until SIGCONT; the handler is put back once it runs again.
+        unsafe {
+            libc::signal(libc::SIGTSTP, libc::SIG_DFL);
+            libc::raise(libc::SIGTSTP);
+        }
+        catch_tstp();
+    }
+}
+
+#[cfg(not(unix))]
+mod imp {
+    pub(crate) fn install_signal_handlers() {}
+
+    pub(crate) fn take_stop_request() -> bool {
+        false
+    }
+
+    pub(crate) fn take_continued() -> bool {
+        false
+    }
+
+    pub(crate) fn stop_process() {}
+}
+
+pub(crate) use imp::{install_signal_handlers, stop_process, take_continued, take_stop_request};
+
+/// Whether this platform has job control for `Ctrl+Z` to use.
+pub(crate) const CAN_SUSPEND: bool = cfg!(unix);
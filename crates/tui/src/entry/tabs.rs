@@ -0,0 +1,363 @@
+use crate::app::handlers::command::{
+    finish_prompt_batch_if_done, start_pending_prompt_batch, try_dispatch_queued_prompt,
+};
+use crate::app::handlers::connection_health::send_keepalive_if_due;
+use crate::app::handlers::lane_attach::poll_lane_attach_if_due;
+use crate::app::handlers::tool_pane::poll_tool_pane_if_due;
+use crate::app::handlers::translate::apply_translation_results;
+use crate::app::runtime::reconnect::{
+    begin_runtime_reconnect, finish_runtime_reconnect, is_abnormal_exit,
+};
+use crate::app::runtime::{runtime_command, send_initialize, spawn_runtime};
+use crate::app::state::{LogKind, TabIndicator, TabRequest, TabStrip};
+use crate::app::{AppState, RuntimeLaunchInfo};
+use crate::entry::bootstrap::{build_tab_app, request_initial_model_list};
+use crate::event_loop::runtime::process_runtime_messages;
+use crate::event_loop::{RuntimeReceiver, RuntimeStdin};
+use std::process::Child;
+use std::time::Instant;
+
+/// How `main.rs` launched the first runtime; `/tab new` launches the same way.
+pub(crate) struct RuntimeLaunchConfig {
+    pub diagnostics: bool,
+    pub approval_mode: Option<String>,
+    pub read_only: bool,
+}
+
+/// Outcome of checking a tab's runtime process once per loop tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RuntimeWatch {
+    Unchanged,
+    /// Crashed (restart scheduled) or restarted; the log and status line changed.
+    Changed,
+    /// Gone for good: a clean exit or the restarts were used up.
+    Exited,
+}
+
+/// One session: its own app state (log, composer, panels) and its own runtime process.
+pub(crate) struct SessionTab {
+    pub app: AppState,
+    pub child: Child,
+    pub child_stdin: RuntimeStdin,
+    pub rx: RuntimeReceiver,
+    pub rpc_id: u64,
+    /// The runtime has exited; the tab stays open so its log can still be read.
+    pub exited: bool,
+}
+
+impl SessionTab {
+    pub(crate) fn new(
+        app: AppState,
+        child: Child,
+        child_stdin: RuntimeStdin,
+        rx: RuntimeReceiver,
+        rpc_id: u64,
+    ) -> Self {
+        Self {
+            app,
+            child,
+            child_stdin,
+            rx,
+            rpc_id,
+            exited: false,
+        }
+    }
+
+    fn indicator(&self) -> TabIndicator {
+        let app = &self.app;
+        TabIndicator {
+            session: app
+                .runtime_info
+                .session_id
+                .as_deref()
+                .map(|id| id.chars().take(8).collect())
+                .unwrap_or_default(),
+            running: app.is_running(),
+            waiting: app.confirm_dialog.is_some()
+                || app.pending_confirm_dialog.is_some()
+                || app.prompt_dialog.is_some()
+                || app.pick_dialog.is_some()
+                || app.run_status.as_deref() == Some("awaiting_ui"),
+        }
+    }
+
+    /// Detects a runtime exit and restarts a crashed runtime with backoff; `AppState` is kept,
+    /// so the session id and the prompt queue carry over to the new process.
+    pub(crate) fn watch_runtime(
+        &mut self,
+        config: &RuntimeLaunchConfig,
+        now: Instant,
+    ) -> RuntimeWatch {
+        if self.exited {
+            return RuntimeWatch::Unchanged;
+        }
+        if self.app.runtime_reconnect.due(now) {
+            return self.respawn_runtime(config, now);
+        }
+        if self.app.runtime_reconnect.reconnecting() {
+            return RuntimeWatch::Unchanged;
+        }
+        let (status, abnormal) = if let Some(waited) = self.app.connection_health.overdue(now) {
+            // A dead network can leave ssh hanging; end it so the reconnect can start.
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+            (
+                format!("no keepalive reply for {}s", waited.as_secs()),
+                true,
+            )
+        } else if let Ok(Some(status)) = self.child.try_wait() {
+            (status.to_string(), is_abnormal_exit(&status))
+        } else {
+            self.send_periodic_requests(now);
+            return RuntimeWatch::Unchanged;
+        };
+        if abnormal && begin_runtime_reconnect(&mut self.app, &status, now) {
+            return RuntimeWatch::Changed;
+        }
+        if !abnormal {
+            self.app
+                .push_line(LogKind::Runtime, format!("runtime exited: {status}"));
+        }
+        // Other tabs keep running; the exited one stays readable until closed.
+        self.exited = true;
+        RuntimeWatch::Exited
+    }
+
+    /// Keepalive pings, the attached lane's `lane_tail` polls and the split pane's shell tails.
+    fn send_periodic_requests(&mut self, now: Instant) {
+        let Self {
+            app,
+            child_stdin,
+            rpc_id,
+            ..
+        } = self;
+        let mut next_id = || {
+            *rpc_id += 1;
+            rpc_id.to_string()
+        };
+        send_keepalive_if_due(app, child_stdin, &mut next_id, now);
+        poll_lane_attach_if_due(app, child_stdin, &mut next_id, now);
+        poll_tool_pane_if_due(app, child_stdin, &mut next_id, now);
+    }
+
+    fn respawn_runtime(&mut self, config: &RuntimeLaunchConfig, now: Instant) -> RuntimeWatch {
+        let approval_mode = config.approval_mode.as_deref();
+        let (child, child_stdin, rx) =
+            match spawn_runtime(config.diagnostics, approval_mode, config.read_only) {
+                Ok(spawned) => spawned,
+                Err(error) => {
+                    let status = format!("respawn failed: {error}");
+                    if begin_runtime_reconnect(&mut self.app, &status, now) {
+                        return RuntimeWatch::Changed;
+                    }
+                    self.exited = true;
+                    return RuntimeWatch::Exited;
+                }
+            };
+        let _ = self.child.kill();
+        self.child = child;
+        self.child_stdin = child_stdin;
+        self.rx = rx;
+        let (program, args) = runtime_command(approval_mode, config.read_only);
+        let pid = self.child.id();
+        self.app.runtime_info.launch = Some(RuntimeLaunchInfo { program, args, pid });
+        self.rpc_id += 1;
+        if let Err(error) = send_initialize(&mut self.child_stdin, &self.rpc_id.to_string()) {
+            self.app.push_error_report("send error", error.to_string());
+        }
+        finish_runtime_reconnect(&mut self.app, pid, now);
+        RuntimeWatch::Changed
+    }
+
+    /// Drains runtime output, queued prompts and finished translations while another tab is
+    /// on screen.
+    fn tick_background(&mut self, config: &RuntimeLaunchConfig, inline: bool) {
+        self.watch_runtime(config, Instant::now());
+        let Self {
+            app,
+            child_stdin,
+            rx,
+            rpc_id,
+            ..
+        } = self;
+        let mut next_id = || {
+            *rpc_id += 1;
+            rpc_id.to_string()
+        };
+        process_runtime_messages(app, rx, child_stdin, &mut next_id);
+        start_pending_prompt_batch(app, child_stdin, &mut next_id);
+        try_dispatch_queued_prompt(app, child_stdin, &mut next_id);
+        finish_prompt_batch_if_done(app);
+        apply_translation_results(app, inline);
+    }
+}
+
+/// The interactive TUI's tabs; line mode and `--print` keep a single bare runtime.
+pub(crate) struct SessionTabs {
+    pub tabs: Vec<SessionTab>,
+    pub active: usize,
+}
+
+impl SessionTabs {
+    pub(crate) fn new(first: SessionTab) -> Self {
+        Self {
+            tabs: vec![first],
+            active: 0,
+        }
+    }
+
+    pub(crate) fn active_mut(&mut self) -> &mut SessionTab {
+        &mut self.tabs[self.active]
+    }
+
+    /// Applies a queued `/tab` or `Ctrl+Tab` request; true when the active tab changed.
+    pub(crate) fn apply_request(
+        &mut self,
+        request: TabRequest,
+        config: &RuntimeLaunchConfig,
+    ) -> bool {
+        let count = self.tabs.len();
+        let target = match request {
+            TabRequest::New => return self.open_tab(config),
+            TabRequest::Next | TabRequest::Previous if count < 2 => {
+                self.active_mut().app.push_line(
+                    LogKind::Status,
+                    "Only one tab is open (/tab new opens another)",
+                );
+                return false;
+            }
+            TabRequest::Next => (self.active + 1) % count,
+            TabRequest::Previous => (self.active + count - 1) % count,
+            TabRequest::Switch(number) => match self.checked_index(number) {
+                Some(index) => index,
+                None => return false,
+            },
+            TabRequest::Close(number) => {
+                let index = match number {
+                    Some(number) => match self.checked_index(number) {
+                        Some(index) => index,
+                        None => return false,
+                    },
+                    None => self.active,
+                };
+                return self.close_tab(index);
+            }
+        };
+        self.switch_to(target)
+    }
+
+    fn checked_index(&mut self, number: usize) -> Option<usize> {
+        if (1..=self.tabs.len()).contains(&number) {
+            return Some(number - 1);
+        }
+        let message = format!("No tab {number} (1-{} are open)", self.tabs.len());
+        self.active_mut().app.push_line(LogKind::Error, message);
+        None
+    }
+
+    fn switch_to(&mut self, index: usize) -> bool {
+        if index == self.active {
+            return false;
+        }
+        self.active = index;
+        let app = &mut self.active_mut().app;
+        app.log_changed = true;
+        app.request_scrollback_sync();
+        true
+    }
+
+    fn open_tab(&mut self, config: &RuntimeLaunchConfig) -> bool {
+        let number = self.tabs.len() + 1;
+        let approval_mode = config.approval_mode.as_deref();
+        let (child, mut child_stdin, rx) =
+            match spawn_runtime(config.diagnostics, approval_mode, config.read_only) {
+                Ok(spawned) => spawned,
+                Err(error) => {
+                    let app = &mut self.active_mut().app;
+                    app.push_error_report("tab: runtime spawn failed", error.to_string());
+                    return false;
+                }
+            };
+        let mut app = build_tab_app(&self.active_mut().app, number);
+        let (program, args) = runtime_command(approval_mode, config.read_only);
+        app.runtime_info.launch = Some(RuntimeLaunchInfo {
+            program,
+            args,
+            pid: child.id(),
+        });
+        let mut rpc_id = 0_u64;
+        let mut next_id = || {
+            rpc_id += 1;
+            rpc_id.to_string()
+        };
+        if let Err(error) = send_initialize(&mut child_stdin, &next_id()) {
+            app.push_error_report("send error", error.to_string());
+        }
+        request_initial_model_list(&mut app, &mut child_stdin, &mut next_id);
+        self.tabs
+            .push(SessionTab::new(app, child, child_stdin, rx, rpc_id));
+        self.switch_to(number - 1)
+    }
+
+    fn close_tab(&mut self, index: usize) -> bool {
+        if self.tabs.len() < 2 {
+            self.active_mut().app.push_line(
+                LogKind::Status,
+                "The last tab cannot be closed (Ctrl+C quits)",
+            );
+            return false;
+        }
+        let mut closed = self.tabs.remove(index);
+        let _ = closed.child.kill();
+        let was_active = index == self.active;
+        if index < self.active || self.active == self.tabs.len() {
+            self.active -= 1;
+        }
+        let app = &mut self.active_mut().app;
+        app.push_line(LogKind::Status, format!("Closed tab {}", index + 1));
+        if was_active {
+            app.log_changed = true;
+            app.request_scrollback_sync();
+        }
+        true
+    }
+
+    /// Keeps inactive tabs' runtimes drained; their notifications go out through the visible tab.
+    pub(crate) fn tick_background(&mut self, config: &RuntimeLaunchConfig, inline: bool) -> bool {
+        let active = self.active;
+        let mut notification = None;
+        for (index, tab) in self.tabs.iter_mut().enumerate() {
+            if index == active {
+                continue;
+            }
+            tab.tick_background(config, inline);
+            if let Some(body) = tab.app.pending_notification.take() {
+                notification = Some(format!("tab {}: {body}", index + 1));
+            }
+        }
+        if notification.is_some() {
+            self.active_mut().app.pending_notification = notification;
+        }
+        self.refresh_strip()
+    }
+
+    /// Copies every tab's state into the active app's strip; true when it changed.
+    fn refresh_strip(&mut self) -> bool {
+        let strip = TabStrip {
+            tabs: self.tabs.iter().map(SessionTab::indicator).collect(),
+            active: self.active,
+        };
+        let app = &mut self.active_mut().app;
+        if app.tab_strip == strip {
+            return false;
+        }
+        app.tab_strip = strip;
+        true
+    }
+
+    pub(crate) fn kill_all(&mut self) {
+        for tab in &mut self.tabs {
+            let _ = tab.child.kill();
+        }
+    }
+}
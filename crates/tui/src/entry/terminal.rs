@@ -1,27 +1,78 @@
+use crate::app::render::glyphs::probe_glyph_widths;
 use crossterm::cursor::Show;
 use crossterm::event::{
-    DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
-    KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+    EnableFocusChange, EnableMouseCapture, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+    PushKeyboardEnhancementFlags,
 };
+use crossterm::style::Print;
 use crossterm::terminal::{
-    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen, SetTitle,
 };
 use crossterm::ExecutableCommand;
 use ratatui::backend::Backend;
 use ratatui::backend::CrosstermBackend;
 use ratatui::layout::Position;
 use ratatui::{Terminal, TerminalOptions, Viewport};
+use std::process::Command;
+
+// xterm window-title stack (XTWINOPS 22/23); terminals without it ignore the sequences.
+const PUSH_TITLE: &str = "\x1b[22;0t";
+const POP_TITLE: &str = "\x1b[23;0t";
+// DECSTBM with no margins; scrollback inserts set a scroll region that a shell must not inherit.
+const RESET_SCROLL_REGION: &str = "\x1b[r";
 
 pub(crate) type TerminalBackend = CrosstermBackend<std::io::Stdout>;
 pub(crate) type TuiTerminal = Terminal<TerminalBackend>;
 
+/// Title in place before the TUI started: pushed on the xterm title stack, plus the tmux
+/// pane title, which that stack does not cover.
+pub(crate) struct SavedTerminalTitle {
+    tmux_pane_title: Option<String>,
+}
+
+impl SavedTerminalTitle {
+    pub(crate) fn save() -> Self {
+        let _ = std::io::stdout().execute(Print(PUSH_TITLE));
+        let tmux_pane_title = std::env::var_os("TMUX")
+            .and_then(|_| {
+                Command::new("tmux")
+                    .args(["display-message", "-p", "#{pane_title}"])
+                    .output()
+                    .ok()
+            })
+            .filter(|output| output.status.success())
+            .map(|output| {
+                String::from_utf8_lossy(&output.stdout)
+                    .trim_end()
+                    .to_string()
+            });
+        Self { tmux_pane_title }
+    }
+
+    fn restore(&self, stdout: &mut impl std::io::Write) {
+        if let Some(title) = &self.tmux_pane_title {
+            let _ = stdout.execute(SetTitle(title));
+        }
+        let _ = stdout.execute(Print(POP_TITLE));
+    }
+}
+
 pub(crate) struct TerminalRestoreGuard {
     use_alt_screen: bool,
+    title: SavedTerminalTitle,
 }
 
 impl TerminalRestoreGuard {
-    pub(crate) fn new(use_alt_screen: bool) -> Self {
-        Self { use_alt_screen }
+    pub(crate) fn new(use_alt_screen: bool, title: SavedTerminalTitle) -> Self {
+        Self {
+            use_alt_screen,
+            title,
+        }
+    }
+
+    pub(crate) fn saved_title(&self) -> &SavedTerminalTitle {
+        &self.title
     }
 }
 
@@ -31,24 +82,17 @@ impl Drop for TerminalRestoreGuard {
         let mut stdout = std::io::stdout();
         let _ = stdout.execute(PopKeyboardEnhancementFlags);
         let _ = stdout.execute(DisableBracketedPaste);
+        let _ = stdout.execute(DisableFocusChange);
         let _ = stdout.execute(DisableMouseCapture);
         if self.use_alt_screen {
             let _ = stdout.execute(LeaveAlternateScreen);
         }
         let _ = stdout.execute(Show);
+        self.title.restore(&mut stdout);
     }
 }
 
-pub(crate) fn setup_terminal(
-    use_alt_screen: bool,
-    inline_height: u16,
-) -> Result<TuiTerminal, Box<dyn std::error::Error>> {
-    let mut stdout = std::io::stdout();
-    if use_alt_screen {
-        stdout.execute(EnterAlternateScreen)?;
-    }
-    enable_raw_mode()?;
-
+fn enable_input_modes(stdout: &mut std::io::Stdout) {
     // Try to enable the kitty keyboard protocol so we can reliably distinguish Shift+Enter and
     // other modifier combos on terminals that support it. On unsupported terminals this is a noop.
     let _ = stdout.execute(PushKeyboardEnhancementFlags(
@@ -58,6 +102,21 @@ pub(crate) fn setup_terminal(
     ));
     // Ensure multi-line paste is delivered as Event::Paste instead of a stream of Enter keypresses.
     let _ = stdout.execute(EnableBracketedPaste);
+    // Focus reports let `notifications` tell a background terminal from one being watched.
+    let _ = stdout.execute(EnableFocusChange);
+}
+
+pub(crate) fn setup_terminal(
+    use_alt_screen: bool,
+    inline_height: u16,
+) -> Result<TuiTerminal, Box<dyn std::error::Error>> {
+    let mut stdout = std::io::stdout();
+    if use_alt_screen {
+        stdout.execute(EnterAlternateScreen)?;
+    }
+    enable_raw_mode()?;
+    probe_glyph_widths(&mut stdout);
+    enable_input_modes(&mut stdout);
 
     let backend = CrosstermBackend::new(stdout);
     let terminal = if use_alt_screen {
@@ -81,6 +140,77 @@ pub(crate) fn set_mouse_capture(terminal: &mut TuiTerminal, enabled: bool) {
     }
 }
 
+/// Hands the terminal to another program (an editor): the same modes the restore guard undoes,
+/// minus the title. Pair with [`resume_terminal`].
+pub(crate) fn suspend_terminal(terminal: &mut TuiTerminal, use_alt_screen: bool) {
+    let _ = disable_raw_mode();
+    let backend = terminal.backend_mut();
+    let _ = backend.execute(PopKeyboardEnhancementFlags);
+    let _ = backend.execute(DisableBracketedPaste);
+    let _ = backend.execute(DisableFocusChange);
+    let _ = backend.execute(DisableMouseCapture);
+    let _ = backend.execute(Print(RESET_SCROLL_REGION));
+    if use_alt_screen {
+        let _ = backend.execute(LeaveAlternateScreen);
+    }
+    let _ = backend.execute(Show);
+}
+
+/// Takes the terminal back after [`suspend_terminal`] and forces a full redraw. With
+/// `inline_height`, an inline viewport is re-anchored instead, since a shell moved the cursor.
+pub(crate) fn resume_terminal(
+    terminal: &mut TuiTerminal,
+    use_alt_screen: bool,
+    mouse_capture: bool,
+    inline_height: Option<u16>,
+) -> std::io::Result<()> {
+    if use_alt_screen {
+        terminal.backend_mut().execute(EnterAlternateScreen)?;
+    }
+    enable_raw_mode()?;
+    enable_input_modes(&mut std::io::stdout());
+    match inline_height {
+        Some(height) if !use_alt_screen => reset_inline_viewport(terminal, height)?,
+        _ => terminal.clear()?,
+    }
+    set_mouse_capture(terminal, mouse_capture);
+    Ok(())
+}
+
+/// Re-anchors the inline viewport at the cursor after the shell wrote below the old one, with
+/// a height fitting the terminal as it is now.
+fn reset_inline_viewport(terminal: &mut TuiTerminal, inline_height: u16) -> std::io::Result<()> {
+    *terminal = Terminal::with_options(
+        CrosstermBackend::new(std::io::stdout()),
+        TerminalOptions {
+            viewport: Viewport::Inline(inline_height.max(1)),
+        },
+    )?;
+    terminal.clear()
+}
+
+/// OSC 0 title, which tmux also takes as the pane title; `None` puts the saved title back.
+pub(crate) fn set_terminal_title(
+    terminal: &mut TuiTerminal,
+    title: Option<&str>,
+    saved: &SavedTerminalTitle,
+) {
+    let backend = terminal.backend_mut();
+    match title {
+        Some(title) => {
+            let _ = backend.execute(SetTitle(title));
+        }
+        None => {
+            saved.restore(backend);
+            let _ = backend.execute(Print(PUSH_TITLE));
+        }
+    }
+}
+
+pub(crate) fn ring_bell(terminal: &mut TuiTerminal) {
+    let _ = terminal.backend_mut().execute(Print('\x07'));
+}
+
 pub(crate) fn restore_inline_cursor(terminal: &mut TuiTerminal) {
     let area = terminal.get_frame().area();
     let screen_size = terminal.size().unwrap_or(area.as_size());
@@ -1,18 +1,41 @@
 use super::RuntimeStdin;
 use crate::app::handlers;
+use crate::app::handlers::attachment_check::{
+    handle_attachment_fixup_key, ATTACHMENT_FIXUP_PICK_ID,
+};
 use crate::app::handlers::confirm::handle_confirm_key;
+use crate::app::handlers::copy::{handle_copy_pick_key, COPY_PICK_ID};
+use crate::app::handlers::file_artifacts::{handle_file_artifact_key, FILE_ARTIFACT_PICK_ID};
+use crate::app::handlers::links::{handle_quick_jump_key, QUICK_JUMP_PICK_ID};
+use crate::app::handlers::log_search::{handle_log_search_key, open_log_search};
+use crate::app::handlers::paste_sample::{
+    handle_paste_sample_key, offer_paste_sampling, PASTE_SAMPLE_PICK_ID,
+};
+use crate::app::handlers::permissions::{submit_permissions_prompt, PERMISSIONS_ADD_PROMPT_ID};
+use crate::app::handlers::secrets::{is_local_secret_prompt, submit_secret_prompt};
+use crate::app::handlers::settings::dismiss_motd;
+use crate::app::handlers::stack_trace::{
+    handle_stack_trace_key, offer_stack_trace_resolution, STACK_TRACE_PICK_ID,
+};
+use crate::app::handlers::watchdog::{handle_runtime_stall_key, RUNTIME_STALL_PICK_ID};
+use crate::app::render::graphics::{image_preview_lines, preview_protocol};
 use crate::app::runtime::{
     send_client_tool_error, send_client_tool_success, send_pick_response, send_prompt_response,
     send_run_cancel, send_shell_detach, send_tool_call,
 };
-use crate::app::state::{InputState, LogKind};
+use crate::app::state::{
+    ChordAction, ChordBuffer, ChordStep, EscAction, InputState, LogKind, TabRequest,
+};
+use crate::app::util::attachments::{dropped_image_paths, read_image_file_attachment};
 use crate::app::util::{
     make_attachment_token, read_clipboard_image_attachment, sanitize_paste, ClipboardImageError,
 };
-use crate::app::{AppState, PromptDialogState};
+use crate::app::{AppState, PendingImageAttachment, PromptDialogState};
+use crate::entry::suspend::CAN_SUSPEND;
 use crate::entry::terminal::{set_mouse_capture, TuiTerminal};
 use crossterm::event::{KeyCode, KeyModifiers, MouseEventKind};
 use serde_json::{json, Value};
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
 const SHIFT_ENTER_BACKSLASH_WINDOW: Duration = Duration::from_millis(80);
@@ -86,6 +109,20 @@ fn handle_input_edit_key(
     allow_ctrl_j: bool,
 ) -> Option<bool> {
     match (key, modifiers) {
+        (KeyCode::Char('z' | 'Z'), mods)
+            if mods.contains(KeyModifiers::CONTROL | KeyModifiers::SHIFT) =>
+        {
+            input.redo();
+            Some(true)
+        }
+        (KeyCode::Char('z'), mods) if mods.contains(KeyModifiers::CONTROL) => {
+            input.undo();
+            Some(true)
+        }
+        (KeyCode::Char('y'), mods) if mods.contains(KeyModifiers::CONTROL) => {
+            input.redo();
+            Some(true)
+        }
         (KeyCode::Char('u'), mods) if mods.contains(KeyModifiers::CONTROL) => {
             input.kill_line();
             Some(true)
@@ -111,6 +148,39 @@ fn handle_input_edit_key(
             input.insert_char('\n');
             Some(true)
         }
+        (KeyCode::Backspace, mods) if mods.contains(KeyModifiers::ALT) => {
+            input.delete_word_back();
+            Some(true)
+        }
+        (KeyCode::Delete, mods) if mods.contains(KeyModifiers::ALT) => {
+            input.delete_word_forward();
+            Some(true)
+        }
+        (KeyCode::Char('d'), mods) if mods == KeyModifiers::ALT => {
+            input.delete_word_forward();
+            Some(true)
+        }
+        (KeyCode::Left | KeyCode::Right | KeyCode::Up | KeyCode::Down, mods)
+        | (KeyCode::Home | KeyCode::End, mods)
+            if mods.contains(KeyModifiers::SHIFT) =>
+        {
+            let by_word = mods.intersects(KeyModifiers::ALT | KeyModifiers::CONTROL);
+            input.extend_selection(|input| match key {
+                KeyCode::Left if by_word => input.move_word_left(),
+                KeyCode::Right if by_word => input.move_word_right(),
+                KeyCode::Left => input.move_left(),
+                KeyCode::Right => input.move_right(),
+                KeyCode::Up => {
+                    input.move_up();
+                }
+                KeyCode::Down => {
+                    input.move_down();
+                }
+                KeyCode::Home => input.move_home(),
+                _ => input.move_end(),
+            });
+            Some(true)
+        }
         (KeyCode::Up, _) => {
             if input.move_up() {
                 Some(true)
@@ -131,6 +201,23 @@ fn handle_input_edit_key(
                 Some(false)
             }
         }
+        (KeyCode::Left, mods) if mods.intersects(KeyModifiers::ALT | KeyModifiers::CONTROL) => {
+            input.move_word_left();
+            Some(true)
+        }
+        (KeyCode::Right, mods) if mods.intersects(KeyModifiers::ALT | KeyModifiers::CONTROL) => {
+            input.move_word_right();
+            Some(true)
+        }
+        // macOS terminals send Option+Left/Right as ESC b / ESC f.
+        (KeyCode::Char('b'), mods) if mods == KeyModifiers::ALT => {
+            input.move_word_left();
+            Some(true)
+        }
+        (KeyCode::Char('f'), mods) if mods == KeyModifiers::ALT => {
+            input.move_word_right();
+            Some(true)
+        }
         (KeyCode::Left, _) => {
             input.move_left();
             Some(true)
@@ -167,6 +254,67 @@ fn handle_input_edit_key(
     }
 }
 
+/// Ctrl+letter becomes its ASCII control character.
+fn chord_char(key: KeyCode, modifiers: KeyModifiers) -> Option<char> {
+    match key {
+        KeyCode::Char(ch) if (modifiers - KeyModifiers::SHIFT).is_empty() => Some(ch),
+        KeyCode::Char(ch) if modifiers == KeyModifiers::CONTROL && ch.is_ascii_alphabetic() => {
+            Some((ch.to_ascii_uppercase() as u8 & 0x1f) as char)
+        }
+        _ => None,
+    }
+}
+
+/// The key a chord character came from, for replaying it as ordinary input.
+fn chord_key(ch: char) -> (KeyCode, KeyModifiers) {
+    if ch.is_ascii_control() {
+        let letter = (ch as u8 | 0x60) as char;
+        return (KeyCode::Char(letter), KeyModifiers::CONTROL);
+    }
+    (KeyCode::Char(ch), KeyModifiers::NONE)
+}
+
+fn run_chord_action(app: &mut AppState, action: ChordAction) -> bool {
+    match action {
+        ChordAction::ScrollTop => app.scroll_to_top(),
+        ChordAction::ScrollBottom => app.scroll_from_bottom = 0,
+        ChordAction::QuickJump => handlers::links::open_quick_jump(app),
+        ChordAction::PinAnswer => handlers::scratchpad::pin_last_answer(app),
+        ChordAction::Scratchpad => handlers::scratchpad::open_scratchpad_panel(app),
+        ChordAction::ExternalEditor => handlers::external_editor::request_external_edit(app),
+    }
+    true
+}
+
+/// Types keys released from the chord buffer as ordinary input.
+fn replay_chord_keys(
+    app: &mut AppState,
+    keys: Vec<char>,
+    terminal: &mut TuiTerminal,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) -> bool {
+    let mut redraw = false;
+    for ch in keys {
+        let (key, modifiers) = chord_key(ch);
+        redraw |= handle_unchorded_main_key(app, key, modifiers, terminal, child_stdin, next_id);
+    }
+    redraw
+}
+
+/// Flushes a chord whose next key never came; call once per loop iteration.
+pub(crate) fn expire_pending_chord(
+    app: &mut AppState,
+    terminal: &mut TuiTerminal,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
+) -> bool {
+    match app.chord.take_expired(Instant::now()) {
+        Some(keys) => replay_chord_keys(app, keys, terminal, child_stdin, next_id),
+        None => false,
+    }
+}
+
 pub(crate) fn handle_main_key(
     app: &mut AppState,
     key: KeyCode,
@@ -174,6 +322,52 @@ pub(crate) fn handle_main_key(
     terminal: &mut TuiTerminal,
     child_stdin: &mut RuntimeStdin,
     next_id: &mut impl FnMut() -> String,
+) -> bool {
+    let now = Instant::now();
+    if let Some(keys) = app.chord.take_expired(now) {
+        replay_chord_keys(app, keys, terminal, child_stdin, next_id);
+    }
+    if !app.chord.is_pending() {
+        if let Some(redraw) = handle_log_search_key(app, key, modifiers) {
+            return redraw;
+        }
+    }
+    if app.chord.is_pending() {
+        if key == KeyCode::Esc {
+            app.chord.clear();
+            return true;
+        }
+        let Some(ch) = chord_char(key, modifiers) else {
+            let keys = app.chord.flush();
+            replay_chord_keys(app, keys, terminal, child_stdin, next_id);
+            handle_unchorded_main_key(app, key, modifiers, terminal, child_stdin, next_id);
+            return true;
+        };
+        return match app.chord.feed(ch, now) {
+            ChordStep::Pending => true,
+            ChordStep::Matched(action) => run_chord_action(app, action),
+            ChordStep::Unmatched(keys) => {
+                replay_chord_keys(app, keys, terminal, child_stdin, next_id);
+                true
+            }
+        };
+    }
+    if let Some(ch) = chord_char(key, modifiers) {
+        if ChordBuffer::starts_chord(ch) {
+            app.chord.feed(ch, now);
+            return true;
+        }
+    }
+    handle_unchorded_main_key(app, key, modifiers, terminal, child_stdin, next_id)
+}
+
+fn handle_unchorded_main_key(
+    app: &mut AppState,
+    key: KeyCode,
+    modifiers: KeyModifiers,
+    terminal: &mut TuiTerminal,
+    child_stdin: &mut RuntimeStdin,
+    next_id: &mut impl FnMut() -> String,
 ) -> bool {
     let now = Instant::now();
     let is_plain_backslash = matches!(key, KeyCode::Char('\\')) && modifiers.is_empty();
@@ -187,10 +381,23 @@ pub(crate) fn handle_main_key(
             set_mouse_capture(terminal, app.mouse_capture_enabled);
             true
         }
+        // With text in the composer Ctrl+Z stays undo; an empty one suspends like a shell job.
+        (KeyCode::Char('z'), KeyModifiers::CONTROL)
+            if CAN_SUSPEND
+                && app.input.current().is_empty()
+                && app.pending_image_attachments.is_empty() =>
+        {
+            app.suspend_requested = true;
+            true
+        }
         (KeyCode::Char('h'), mods) if mods.contains(KeyModifiers::ALT) => {
             app.toggle_status_line_mode();
             true
         }
+        (KeyCode::Char('t'), mods) if mods.contains(KeyModifiers::ALT) => {
+            handlers::tool_pane::toggle_split_pane(app);
+            true
+        }
         (KeyCode::Char('b'), mods)
             if mods.contains(KeyModifiers::CONTROL)
                 && app.runtime_info.supports_shell_detach
@@ -215,23 +422,37 @@ pub(crate) fn handle_main_key(
             app.clear_log();
             true
         }
+        (KeyCode::Char('f'), mods) if mods.contains(KeyModifiers::CONTROL) => {
+            open_log_search(app);
+            true
+        }
+        (KeyCode::Char('g'), mods)
+            if mods.contains(KeyModifiers::CONTROL)
+                && app.ready_plan.is_some()
+                && app.input.buffer.is_empty() =>
+        {
+            handlers::run_ready_plan(app, child_stdin, next_id);
+            true
+        }
         (KeyCode::Esc, _) => {
-            if app.scroll_from_bottom > 0 {
-                app.scroll_from_bottom = 0;
-                true
-            } else if app.bang_input_mode
-                && app.input.buffer.is_empty()
-                && app.pending_image_attachments.is_empty()
-            {
-                app.bang_input_mode = false;
-                true
-            } else if !app.input.current().is_empty() || !app.pending_image_attachments.is_empty() {
-                app.clear_composer();
-                true
-            } else if app.is_running() {
-                if app.rpc_pending.run_cancel_id.is_some() {
-                    true
-                } else if let Some(run_id) = app.runtime_info.active_run_id.clone() {
+            let action = app.next_esc_action(now);
+            if action != Some(EscAction::CancelRun) {
+                app.esc_cancel_armed_at = None;
+            }
+            match action {
+                Some(EscAction::CloseSearch) => app.log_search = None,
+                Some(EscAction::LeaveScrollback) => app.scroll_from_bottom = 0,
+                Some(EscAction::LeaveShellMode) => app.bang_input_mode = false,
+                Some(EscAction::ClearComposer) => app.clear_composer(),
+                Some(EscAction::ArmCancel) => {
+                    app.esc_cancel_armed_at = Some(now);
+                    app.push_line(LogKind::Status, "Press Esc again to cancel the run");
+                }
+                Some(EscAction::CancelRun) => {
+                    app.esc_cancel_armed_at = None;
+                    let Some(run_id) = app.runtime_info.active_run_id.clone() else {
+                        return false;
+                    };
                     let id = next_id();
                     app.rpc_pending.run_cancel_id = Some(id.clone());
                     if let Err(error) =
@@ -242,13 +463,32 @@ pub(crate) fn handle_main_key(
                     } else {
                         app.push_line(LogKind::Status, "Cancel requested (Esc)");
                     }
-                    true
-                } else {
-                    false
                 }
-            } else {
-                false
+                Some(EscAction::DismissMotd) => dismiss_motd(app),
+                None => return app.is_running(),
             }
+            true
+        }
+        (KeyCode::Char('o'), mods) if mods.contains(KeyModifiers::ALT) => {
+            handlers::links::open_quick_jump(app);
+            true
+        }
+        (KeyCode::Char('s'), mods) if mods.contains(KeyModifiers::ALT) => {
+            handlers::file_artifacts::open_file_artifact_save(app, child_stdin, next_id)
+        }
+        (KeyCode::Char('a'), mods) if mods.contains(KeyModifiers::ALT) => {
+            handlers::agents_suggestion::append_agents_suggestion(app, child_stdin, next_id)
+        }
+        (KeyCode::Char('c'), mods) if mods.contains(KeyModifiers::ALT) => {
+            handlers::copy::copy_last_message(app, false);
+            true
+        }
+        (KeyCode::Char('p'), mods) if mods.contains(KeyModifiers::ALT) => {
+            handlers::scratchpad::pin_last_answer(app);
+            true
+        }
+        (KeyCode::Char('r'), mods) if mods.contains(KeyModifiers::ALT) => {
+            handlers::rpc_retry::retry_failed_rpc(app, child_stdin, next_id)
         }
         (KeyCode::Char('v'), mods) if mods.contains(KeyModifiers::ALT) => {
             handle_clipboard_image_paste(app)
@@ -296,9 +536,28 @@ pub(crate) fn handle_main_key(
             app.pending_shift_enter_backslash = None;
             handlers::handle_enter(app, child_stdin, next_id)
         }
+        // Needs a terminal that reports Ctrl with Tab (kitty keyboard protocol); /tab works anywhere.
+        (KeyCode::BackTab, mods) if mods.contains(KeyModifiers::CONTROL) => {
+            handlers::tabs::request_tab(app, TabRequest::Previous)
+        }
+        (KeyCode::Tab, mods) if mods.contains(KeyModifiers::CONTROL) => {
+            let request = if mods.contains(KeyModifiers::SHIFT) {
+                TabRequest::Previous
+            } else {
+                TabRequest::Next
+            };
+            handlers::tabs::request_tab(app, request)
+        }
         (KeyCode::Tab, mods) if mods.is_empty() => {
             handlers::complete_slash_command(&mut app.input)
                 || handlers::complete_skill_mention(&mut app.input, &app.skills_catalog_items)
+                || handlers::file_mentions::complete_file_mention(app)
+        }
+        (KeyCode::Up | KeyCode::Down, mods)
+            if mods.is_empty()
+                && handlers::file_mentions::move_mention_selection(app, key == KeyCode::Down) =>
+        {
+            true
         }
         (KeyCode::PageUp, _) => {
             app.scroll_page_up();
@@ -322,8 +581,17 @@ pub(crate) fn handle_paste(app: &mut AppState, text: &str) -> bool {
     }
     if app.prompt_dialog.is_some() {
         app.prompt_input.insert_str(&cleaned);
-    } else {
+        return true;
+    }
+    // A paste ends a half-typed chord; its Ctrl leader types nothing, so it is dropped.
+    app.chord.clear();
+    if let Some(paths) = dropped_image_paths(&cleaned).filter(|_| !app.bang_input_mode) {
+        attach_dropped_images(app, &paths);
+    } else if !offer_paste_sampling(app, &cleaned) {
         app.input.insert_str(&cleaned);
+        if !app.bang_input_mode {
+            offer_stack_trace_resolution(app, &cleaned);
+        }
     }
     true
 }
@@ -338,6 +606,8 @@ pub(crate) fn blocks_input_paste(app: &AppState) -> bool {
         || app.pick_dialog.is_some()
         || app.reasoning_picker.is_some()
         || app.lane_list_panel.is_some()
+        || app.lane_attach.is_some()
+        || app.mcp_list_panel.is_some()
 }
 
 fn blocks_composer_paste(app: &AppState) -> bool {
@@ -346,6 +616,11 @@ fn blocks_composer_paste(app: &AppState) -> bool {
 
 fn append_clipboard_image(app: &mut AppState) -> Result<(), ClipboardImageError> {
     let image = read_clipboard_image_attachment(MAX_CLIPBOARD_IMAGE_BYTES)?;
+    append_image_attachment(app, image);
+    Ok(())
+}
+
+fn append_image_attachment(app: &mut AppState, image: PendingImageAttachment) {
     let attachment_id = app.next_image_attachment_id();
     let token = make_attachment_token(&app.composer_nonce, &attachment_id);
     app.add_pending_image_attachment(attachment_id, image.clone());
@@ -357,7 +632,44 @@ fn append_clipboard_image(app: &mut AppState) -> Result<(), ClipboardImageError>
         image.encoded_bytes / 1024
     );
     app.push_line(LogKind::Status, summary);
-    Ok(())
+    let protocol = preview_protocol(&app.settings, app.low_bandwidth);
+    app.extend_lines(image_preview_lines(
+        protocol,
+        &image.data_url,
+        LogKind::Status,
+    ));
+}
+
+/// A paste that is only image file paths (a file dragged into the terminal) attaches the
+/// images instead of inserting the paths.
+fn attach_dropped_images(app: &mut AppState, paths: &[PathBuf]) {
+    for path in paths {
+        if app.pending_image_attachments.len() >= MAX_CLIPBOARD_IMAGES_PER_MESSAGE {
+            app.push_line(
+                LogKind::Error,
+                format!(
+                    "Image attachment limit reached ({MAX_CLIPBOARD_IMAGES_PER_MESSAGE} per message)"
+                ),
+            );
+            return;
+        }
+        match read_image_file_attachment(path, MAX_CLIPBOARD_IMAGE_BYTES) {
+            Ok(image) => append_image_attachment(app, image),
+            Err(ClipboardImageError::TooLarge { bytes, max_bytes }) => app.push_line(
+                LogKind::Error,
+                format!(
+                    "{} is too large ({}KB > {}KB)",
+                    path.display(),
+                    bytes / 1024,
+                    max_bytes / 1024
+                ),
+            ),
+            Err(ClipboardImageError::Clipboard(error) | ClipboardImageError::Encode(error)) => {
+                app.push_line(LogKind::Error, format!("Image file attach failed: {error}"));
+            }
+            Err(ClipboardImageError::NotAvailable) => {}
+        }
+    }
 }
 
 fn report_clipboard_paste_error(app: &mut AppState, error: ClipboardImageError) {
@@ -429,7 +741,11 @@ fn handle_prompt_key(
             app.prompt_dialog = None;
             app.prompt_input.clear();
             app.rpc_pending.new_lane_seed_context = None;
-            if prompt_id != "lane:new-task" && prompt_id != "lane:new-seed" {
+            if prompt_id != "lane:new-task"
+                && prompt_id != "lane:new-seed"
+                && !is_local_secret_prompt(&prompt_id)
+                && prompt_id != PERMISSIONS_ADD_PROMPT_ID
+            {
                 if let Err(error) = send_prompt_response(child_stdin, &prompt_id, None) {
                     app.push_error_report("prompt response error", error.to_string());
                 }
@@ -443,6 +759,12 @@ fn handle_prompt_key(
             app.prompt_dialog = None;
             app.prompt_input.clear();
 
+            if submit_secret_prompt(app, &prompt_id, &value)
+                || submit_permissions_prompt(app, &prompt_id, &value)
+            {
+                return Some(true);
+            }
+
             if prompt_id == "lane:new-task" {
                 let task_id = value.trim();
                 if task_id.is_empty() {
@@ -510,6 +832,27 @@ fn handle_pick_key(
     next_id: &mut impl FnMut() -> String,
 ) -> Option<bool> {
     let pick = app.pick_dialog.as_mut()?;
+    if pick.id == QUICK_JUMP_PICK_ID {
+        return Some(handle_quick_jump_key(app, key));
+    }
+    if pick.id == ATTACHMENT_FIXUP_PICK_ID {
+        return Some(handle_attachment_fixup_key(app, key, child_stdin, next_id));
+    }
+    if pick.id == FILE_ARTIFACT_PICK_ID {
+        return Some(handle_file_artifact_key(app, key, child_stdin, next_id));
+    }
+    if pick.id == RUNTIME_STALL_PICK_ID {
+        return Some(handle_runtime_stall_key(app, key, child_stdin, next_id));
+    }
+    if pick.id == PASTE_SAMPLE_PICK_ID {
+        return Some(handle_paste_sample_key(app, key));
+    }
+    if pick.id == STACK_TRACE_PICK_ID {
+        return Some(handle_stack_trace_key(app, key));
+    }
+    if pick.id == COPY_PICK_ID {
+        return Some(handle_copy_pick_key(app, key));
+    }
     let mut handled = true;
     match key {
         KeyCode::Esc => {
@@ -566,6 +909,10 @@ fn handle_pick_key(
             }
 
             if let Some(lane_id) = id.strip_prefix("lane:action:") {
+                if ids.first().map(String::as_str) == Some("attach") {
+                    crate::app::handlers::lane_attach::open_lane_attach(app, lane_id);
+                    return Some(true);
+                }
                 if let Some(action) = ids.first() {
                     let request_id = next_id();
                     match action.as_str() {
@@ -661,6 +1008,15 @@ pub(crate) fn handle_non_main_key(
         return Some(redraw);
     }
 
+    if let Some(redraw) = crate::app::handlers::edit_conflict::handle_edit_conflict_key(
+        app,
+        key,
+        child_stdin,
+        next_id,
+    ) {
+        return Some(redraw);
+    }
+
     if let Some(redraw) =
         crate::app::handlers::panels::handle_session_list_panel_key(app, key, child_stdin, next_id)
     {
@@ -673,10 +1029,47 @@ pub(crate) fn handle_non_main_key(
         return Some(redraw);
     }
 
+    if let Some(redraw) = crate::app::handlers::lane_attach::handle_lane_attach_key(app, key) {
+        return Some(redraw);
+    }
+
+    if let Some(redraw) = crate::app::handlers::panels::handle_mcp_list_panel_key(app, key) {
+        return Some(redraw);
+    }
+
     if let Some(redraw) = crate::app::handlers::panels::handle_skills_list_panel_key(app, key) {
         return Some(redraw);
     }
 
+    if let Some(redraw) = crate::app::handlers::panels::handle_secrets_panel_key(app, key) {
+        return Some(redraw);
+    }
+
+    if let Some(redraw) = crate::app::handlers::permissions::handle_permissions_panel_key(app, key)
+    {
+        return Some(redraw);
+    }
+
+    if let Some(redraw) = crate::app::handlers::panels::handle_tools_panel_key(app, key) {
+        return Some(redraw);
+    }
+
+    if let Some(redraw) =
+        crate::app::handlers::queue_panel::handle_queue_panel_key(app, key, modifiers)
+    {
+        return Some(redraw);
+    }
+
+    if let Some(redraw) =
+        crate::app::handlers::panels::handle_cache_list_panel_key(app, key, child_stdin, next_id)
+    {
+        return Some(redraw);
+    }
+
+    if let Some(redraw) = crate::app::handlers::whats_new::handle_whats_new_panel_key(app, key) {
+        return Some(redraw);
+    }
+
     if let Some(redraw) =
         crate::app::handlers::panels::handle_theme_list_panel_key(app, key, child_stdin, next_id)
     {
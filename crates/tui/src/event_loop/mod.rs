@@ -1,9 +1,7 @@
 pub(crate) mod input;
 pub(crate) mod runtime;
 
-use std::io::BufWriter;
-use std::process::ChildStdin;
 use std::sync::mpsc::Receiver;
 
-pub(crate) type RuntimeStdin = BufWriter<ChildStdin>;
+pub(crate) use crate::app::runtime::RuntimeStdin;
 pub(crate) type RuntimeReceiver = Receiver<String>;
@@ -2,20 +2,38 @@ mod app;
 mod entry;
 mod event_loop;
 
-use crate::app::runtime::{send_initialize, spawn_runtime};
-use crate::app::view::desired_height;
-use crate::entry::run_loop::run_tui_loop;
+use crate::app::handlers::command::load_prompt_batch;
+use crate::app::handlers::draft::offer_saved_draft;
+use crate::app::handlers::editor_bridge::announce_editor_bridge;
+use crate::app::handlers::whats_new::show_whats_new_after_upgrade;
+use crate::app::markdown::preload_highlight_assets;
+use crate::app::runtime::{runtime_command, send_initialize, spawn_runtime};
+use crate::app::state::LogKind;
+use crate::app::util::editor_bridge::{default_editor_socket_path, EditorBridge};
+use crate::app::util::input_log::install_crash_report_hook;
+use crate::app::RuntimeLaunchInfo;
+use crate::entry::headless::run_headless;
+use crate::entry::line_mode::run_line_mode;
+use crate::entry::run_loop::{inline_viewport_height, run_tui_loop};
+use crate::entry::suspend::install_signal_handlers;
+use crate::entry::tabs::{RuntimeLaunchConfig, SessionTab, SessionTabs};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 
 use crate::entry::bootstrap::{
+    apply_low_bandwidth_startup, apply_prompt_file_startup, apply_read_only_startup,
     apply_resume_startup, build_initial_app, request_initial_model_list,
 };
 use crate::entry::cli::{
-    debug_perf_enabled, debug_print_enabled, diagnostics_enabled, parse_approval_mode,
-    parse_basic_cli_mode, parse_initial_message, parse_resume_mode, print_basic_help,
+    debug_perf_enabled, debug_print_enabled, diagnostics_enabled, low_bandwidth_enabled,
+    parse_approval_mode, parse_basic_cli_mode, parse_editor_socket, parse_headless_mode,
+    parse_initial_message, parse_prompt_file, parse_resume_mode, print_basic_help,
+    read_only_enabled, resolve_headless_prompt, resolve_runtime_approval_mode,
     resolve_version_label, BasicCliMode,
 };
 use crate::entry::terminal::{
-    restore_inline_cursor, set_mouse_capture, setup_terminal, TerminalRestoreGuard,
+    restore_inline_cursor, set_mouse_capture, setup_terminal, SavedTerminalTitle,
+    TerminalRestoreGuard,
 };
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -30,14 +48,39 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
         BasicCliMode::Run => {}
     }
+    install_crash_report_hook();
     let resume_mode = parse_resume_mode();
     let mut pending_initial_message = parse_initial_message();
     let debug_print = debug_print_enabled();
     let debug_perf = debug_perf_enabled();
     let diagnostics = diagnostics_enabled();
-    let approval_mode = parse_approval_mode()
+    let read_only = read_only_enabled();
+    let low_bandwidth = low_bandwidth_enabled();
+    let requested_approval_mode = parse_approval_mode()
         .map_err(|message| std::io::Error::new(std::io::ErrorKind::InvalidInput, message))?;
-    let (mut child, mut child_stdin, rx) = spawn_runtime(diagnostics, approval_mode.as_deref())?;
+    let prompt_batch = parse_prompt_file()
+        .and_then(|path| {
+            path.map(|path| load_prompt_batch(Path::new(&path)))
+                .transpose()
+        })
+        .map_err(|message| std::io::Error::new(std::io::ErrorKind::InvalidInput, message))?;
+    let headless_prompt = parse_headless_mode()
+        .and_then(|mode| {
+            mode.map(|print_prompt| {
+                resolve_headless_prompt(print_prompt, pending_initial_message.take())
+            })
+            .transpose()
+        })
+        .map_err(|message| std::io::Error::new(std::io::ErrorKind::InvalidInput, message))?;
+    let approval_mode = resolve_runtime_approval_mode(requested_approval_mode.clone(), read_only);
+    let (mut child, mut child_stdin, rx) =
+        spawn_runtime(diagnostics, approval_mode.as_deref(), read_only)?;
+    let (runtime_program, runtime_args) = runtime_command(approval_mode.as_deref(), read_only);
+    let runtime_launch = RuntimeLaunchInfo {
+        program: runtime_program,
+        args: runtime_args,
+        pid: child.id(),
+    };
 
     let mut rpc_id = 0_u64;
     let mut next_id = || {
@@ -47,37 +90,96 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     send_initialize(&mut child_stdin, &next_id())?;
 
-    let use_alt_screen = false;
     let mut app = build_initial_app(
         debug_print,
         debug_perf,
         diagnostics,
         pending_initial_message.as_deref(),
     );
-    let (terminal_width, terminal_height) = crossterm::terminal::size()?;
-    let inline_height = desired_height(&mut app, terminal_width, terminal_height)
-        .max(12)
-        .min(terminal_height)
-        .max(1);
-    let _restore_guard = TerminalRestoreGuard::new(use_alt_screen);
+    app.runtime_info.launch = Some(runtime_launch);
+    // After `build_initial_app` applied the configured theme, which picks the syntect theme.
+    preload_highlight_assets();
+    if read_only {
+        apply_read_only_startup(&mut app, requested_approval_mode.as_deref());
+    }
+    if let Some(batch) = prompt_batch {
+        apply_prompt_file_startup(&mut app, batch);
+    }
+    if let Some(prompt) = headless_prompt {
+        apply_resume_startup(&mut app, &mut child_stdin, &mut next_id, resume_mode);
+        let code = run_headless(
+            &mut app,
+            &rx,
+            &mut child,
+            &mut child_stdin,
+            &mut next_id,
+            &prompt,
+        );
+        let _ = child.kill();
+        std::process::exit(code);
+    }
+    if !std::io::stdout().is_terminal() {
+        app.push_line(
+            LogKind::Status,
+            "stdout is not a terminal; using line mode (one prompt per stdin line).",
+        );
+        request_initial_model_list(&mut app, &mut child_stdin, &mut next_id);
+        apply_resume_startup(&mut app, &mut child_stdin, &mut next_id, resume_mode);
+        run_line_mode(
+            &mut app,
+            &rx,
+            &mut child,
+            &mut child_stdin,
+            &mut next_id,
+            &mut pending_initial_message,
+        )?;
+        let _ = child.kill();
+        return Ok(());
+    }
+    show_whats_new_after_upgrade(&mut app);
+    offer_saved_draft(&mut app);
+
+    if low_bandwidth {
+        apply_low_bandwidth_startup(&mut app);
+    }
+    let use_alt_screen = app.settings.alt_screen;
+    let inline_height = inline_viewport_height(&mut app)?;
+    let restore_guard = TerminalRestoreGuard::new(use_alt_screen, SavedTerminalTitle::save());
     let mut terminal = setup_terminal(use_alt_screen, inline_height)?;
-    app.mouse_capture_enabled = use_alt_screen;
+    app.mouse_capture_enabled = use_alt_screen || app.settings.mouse_capture;
     set_mouse_capture(&mut terminal, app.mouse_capture_enabled);
+    install_signal_handlers();
     request_initial_model_list(&mut app, &mut child_stdin, &mut next_id);
     apply_resume_startup(&mut app, &mut child_stdin, &mut next_id, resume_mode);
 
-    run_tui_loop(
-        &mut app,
+    let editor_bridge = parse_editor_socket().and_then(|path| {
+        let path = path
+            .map(PathBuf::from)
+            .unwrap_or_else(default_editor_socket_path);
+        let bridge = EditorBridge::start(path.clone())
+            .map_err(|error| format!("{}: {error}", path.display()));
+        announce_editor_bridge(&mut app, &bridge);
+        bridge.ok()
+    });
+
+    let mut tabs = SessionTabs::new(SessionTab::new(app, child, child_stdin, rx, rpc_id));
+    let config = RuntimeLaunchConfig {
+        diagnostics,
+        approval_mode,
+        read_only,
+    };
+    let result = run_tui_loop(
+        &mut tabs,
+        &config,
         &mut terminal,
-        &rx,
-        &mut child,
-        &mut child_stdin,
-        &mut next_id,
         &mut pending_initial_message,
         use_alt_screen,
-    )?;
+        restore_guard.saved_title(),
+        editor_bridge.as_ref(),
+    );
 
-    let _ = child.kill();
+    tabs.kill_all();
+    result?;
     if !use_alt_screen {
         restore_inline_cursor(&mut terminal);
     }
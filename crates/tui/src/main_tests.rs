@@ -1,9 +1,10 @@
 use crate::app::runtime::RpcResponse;
-use crate::app::{AppState, PendingPromptRun, PROMPT_DISPATCH_MAX_ATTEMPTS};
+use crate::app::{AppState, PendingPromptRun, QueuedAttachments, PROMPT_DISPATCH_MAX_ATTEMPTS};
 use crate::entry::cli::{
     cli_flag_enabled_from_args, parse_approval_mode_from_args, parse_basic_cli_mode_from_args,
-    parse_initial_message_from_args, parse_resume_mode_from_args,
-    resolve_version_label_from_versions, BasicCliMode, ResumeMode,
+    parse_editor_socket_from_values, parse_headless_mode_from_args,
+    parse_initial_message_from_args, parse_prompt_file_from_args, parse_resume_mode_from_args,
+    resolve_runtime_approval_mode, resolve_version_label_from_versions, BasicCliMode, ResumeMode,
 };
 use crate::event_loop::runtime::{
     apply_lane_list_result, can_auto_start_initial_message, handle_run_start_response,
@@ -106,6 +107,83 @@ fn parse_approval_mode_rejects_missing_values() {
     assert!(parse_approval_mode_from_args(["--approval-mode="]).is_err());
 }
 
+#[test]
+fn read_only_forces_minimal_approval_mode() {
+    assert_eq!(
+        resolve_runtime_approval_mode(Some("full-access".to_string()), true),
+        Some("minimal".to_string())
+    );
+    assert_eq!(
+        resolve_runtime_approval_mode(None, true),
+        Some("minimal".to_string())
+    );
+    assert_eq!(
+        resolve_runtime_approval_mode(Some("trusted".to_string()), false),
+        Some("trusted".to_string())
+    );
+    assert!(cli_flag_enabled_from_args(
+        "--read-only",
+        ["--read-only=on"]
+    ));
+}
+
+#[test]
+fn parse_editor_socket_prefers_the_flag_over_the_env() {
+    let none: [&str; 0] = [];
+    assert_eq!(parse_editor_socket_from_values(none, None), None);
+    assert_eq!(
+        parse_editor_socket_from_values(["--editor-socket"], Some("/tmp/env.sock")),
+        Some(None)
+    );
+    assert_eq!(
+        parse_editor_socket_from_values(["--editor-socket=/tmp/a.sock"], None),
+        Some(Some("/tmp/a.sock".to_string()))
+    );
+    assert_eq!(
+        parse_editor_socket_from_values(none, Some("true")),
+        Some(None)
+    );
+    assert_eq!(parse_editor_socket_from_values(none, Some("0")), None);
+    assert_eq!(
+        parse_editor_socket_from_values(none, Some("/run/user/1/nvim.sock")),
+        Some(Some("/run/user/1/nvim.sock".to_string()))
+    );
+}
+
+#[test]
+fn parse_prompt_file_requires_a_path() {
+    assert_eq!(
+        parse_prompt_file_from_args(["--prompt-file", "evals.txt"]),
+        Ok(Some("evals.txt".to_string()))
+    );
+    assert_eq!(
+        parse_prompt_file_from_args(["--prompt-file=a.txt", "--prompt-file=b.txt"]),
+        Ok(Some("b.txt".to_string()))
+    );
+    assert_eq!(parse_prompt_file_from_args(["--debug"]), Ok(None));
+    assert!(parse_prompt_file_from_args(["--prompt-file"]).is_err());
+    assert!(parse_prompt_file_from_args(["--prompt-file= "]).is_err());
+}
+
+#[test]
+fn parse_headless_mode_accepts_flag_and_print_prompt() {
+    assert_eq!(parse_headless_mode_from_args(["--debug"]), Ok(None));
+    assert_eq!(
+        parse_headless_mode_from_args(["--headless"]),
+        Ok(Some(None))
+    );
+    assert_eq!(
+        parse_headless_mode_from_args(["-p", "fix the build"]),
+        Ok(Some(Some("fix the build".to_string())))
+    );
+    assert_eq!(
+        parse_headless_mode_from_args(["--print=hi", "--headless"]),
+        Ok(Some(Some("hi".to_string())))
+    );
+    assert!(parse_headless_mode_from_args(["--print"]).is_err());
+    assert!(parse_headless_mode_from_args(["--print= "]).is_err());
+}
+
 #[test]
 fn version_label_uses_cli_version_without_tui_suffix() {
     assert_eq!(
@@ -175,6 +253,9 @@ fn can_auto_start_initial_message_waits_for_prompt_queue_to_drain() {
         attachment_count: 0,
         shell_result_count: 0,
         dispatch_attempts: 0,
+        dry_run: false,
+        json_request: None,
+        attachments: QueuedAttachments::default(),
     });
     assert!(!can_auto_start_initial_message(&app));
 
@@ -188,6 +269,9 @@ fn can_auto_start_initial_message_waits_for_prompt_queue_to_drain() {
         attachment_count: 0,
         shell_result_count: 0,
         dispatch_attempts: 0,
+        dry_run: false,
+        json_request: None,
+        attachments: QueuedAttachments::default(),
     });
     assert!(!can_auto_start_initial_message(&app));
 }
@@ -204,6 +288,9 @@ fn run_start_error_requeues_dispatching_prompt() {
         attachment_count: 0,
         shell_result_count: 0,
         dispatch_attempts: 0,
+        dry_run: false,
+        json_request: None,
+        attachments: QueuedAttachments::default(),
     });
     app.update_run_status("starting".to_string());
 
@@ -244,6 +331,9 @@ fn run_start_error_drops_prompt_after_max_attempts() {
         attachment_count: 0,
         shell_result_count: 0,
         dispatch_attempts: PROMPT_DISPATCH_MAX_ATTEMPTS - 1,
+        dry_run: false,
+        json_request: None,
+        attachments: QueuedAttachments::default(),
     });
 
     handle_run_start_response(
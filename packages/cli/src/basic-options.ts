@@ -18,7 +18,11 @@ export const TOP_LEVEL_HELP_TEXT = [
 	"  --diagnostics[=true|false]",
 	"  --initial-message <text>",
 	"  --initial-user-message <text>",
+	"  --prompt-file <path>",
 	"  --debug-perf[=true|false]",
+	"  --read-only[=true|false]",
+	"  --low-bandwidth[=true|false]",
+	"  --editor-socket[=<path>]",
 	"  --approval-mode <minimal|trusted|full-access>",
 ].join("\n");
 
@@ -91,6 +91,9 @@ const summarizeProgressEvent = (
 				typeof event.content === "string" ? toSingleLine(event.content) : "";
 			return content ? `[text] ${content}` : "[text]";
 		}
+		case "text_delta":
+			// The finished `text` event carries the same content.
+			return null;
 		case "reasoning": {
 			const content =
 				typeof event.content === "string" ? toSingleLine(event.content) : "";
@@ -4,6 +4,8 @@ export type ModelConfig = {
 	reasoning?: string;
 	verbosity?: string;
 	fast?: boolean;
+	// OpenAI-compatible endpoint for `provider = "local"` (Ollama, llama.cpp).
+	base_url?: string;
 };
 
 export type OpenAiExperimentalConfig = {
@@ -109,6 +111,7 @@ export type SearchConfig = {
 
 export type TuiConfig = {
 	theme?: string;
+	motd?: string;
 };
 
 export type CodeliaConfig = {
@@ -535,6 +538,7 @@ export const parseConfig = (
 				reasoning: pickString(modelValue.reasoning),
 				verbosity: pickString(modelValue.verbosity),
 				fast: pickBoolean(modelValue.fast),
+				base_url: pickString(modelValue.base_url),
 			}
 		: undefined;
 	const permissionsValue = value.permissions;
@@ -560,6 +564,9 @@ export const parseConfig = (
 				...(pickString(value.tui.theme)
 					? { theme: pickString(value.tui.theme) }
 					: {}),
+				...(pickString(value.tui.motd)
+					? { motd: pickString(value.tui.motd) }
+					: {}),
 			}
 		: undefined;
 	const result: CodeliaConfig = { version, model };
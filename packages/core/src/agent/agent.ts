@@ -31,6 +31,7 @@ import type {
 	ReasoningEvent,
 	StepCompleteEvent,
 	StepStartEvent,
+	TextDeltaEvent,
 	TextEvent,
 	ToolCallEvent,
 	ToolResultEvent,
@@ -315,6 +316,56 @@ export class Agent {
 		return sessionKey ? { sessionKey } : undefined;
 	}
 
+	// Runs one model call and yields its streamed text chunks as they arrive,
+	// returning the finished completion once the call settles.
+	private async *invokeWithTextDeltas(
+		input: ChatInvokeInput,
+		context?: ChatInvokeContext,
+	): AsyncGenerator<TextDeltaEvent, ChatInvokeCompletion> {
+		const pending: string[] = [];
+		let wake: (() => void) | null = null;
+		const notify = (): void => {
+			const resume = wake;
+			wake = null;
+			resume?.();
+		};
+		let settled = false;
+		const invocation = this.llm
+			.ainvoke(
+				{
+					...input,
+					onTextDelta: (delta) => {
+						pending.push(delta);
+						notify();
+					},
+				},
+				context,
+			)
+			.finally(() => {
+				settled = true;
+				notify();
+			});
+		// Keep the rejection observed while we are still draining deltas; it is
+		// rethrown by the final await below.
+		invocation.catch(() => {});
+		while (!settled || pending.length > 0) {
+			if (pending.length === 0) {
+				await new Promise<void>((resolve) => {
+					wake = resolve;
+				});
+				continue;
+			}
+			const content = pending.splice(0).join("");
+			const deltaEvent: TextDeltaEvent = {
+				type: "text_delta",
+				content,
+				timestamp: Date.now(),
+			};
+			yield deltaEvent;
+		}
+		return await invocation;
+	}
+
 	private recordLlmResponse(
 		session: AgentSession | undefined,
 		seq: number | null,
@@ -392,7 +443,7 @@ export class Agent {
 			});
 			const invokeContext = this.buildInvokeContext(session);
 			const seq = this.recordLlmRequest(session, invokeInput, invokeContext);
-			const response = await this.llm.ainvoke(
+			const response = yield* this.invokeWithTextDeltas(
 				{
 					...invokeInput,
 					...(signal ? { signal } : {}),
@@ -566,6 +617,9 @@ export class Agent {
 						}),
 						tool_call_id: toolCall.id,
 						is_error: processedMessage.is_error,
+						...(processedMessage.output_ref?.id
+							? { output_ref_id: processedMessage.output_ref.id }
+							: {}),
 					};
 					yield toolResultEvent;
 
@@ -29,6 +29,7 @@ export type {
 	ChatInvokeContext,
 	ChatInvokeInput,
 } from "./llm/base";
+export { ChatLocal } from "./llm/local/chat";
 export { ChatMoonshot } from "./llm/moonshot/chat";
 export { ChatOpenAI } from "./llm/openai/chat";
 export { ChatOpenRouter } from "./llm/openrouter/chat";
@@ -54,6 +55,7 @@ export type {
 	ReasoningEvent,
 	StepCompleteEvent,
 	StepStartEvent,
+	TextDeltaEvent,
 	TextEvent,
 	ToolCallEvent,
 	ToolResultEvent,
@@ -8,6 +8,7 @@ export type ProviderName =
 	| "openrouter"
 	| "google"
 	| "moonshot"
+	| "local"
 	| "zai"
 	| "xai";
 
@@ -17,6 +18,9 @@ export type ChatInvokeInput = {
 	tools?: ToolDefinition[] | null;
 	toolChoice?: ToolChoice | null;
 	signal?: AbortSignal;
+	// Called with each chunk of assistant text as it streams in. Providers
+	// that do not stream ignore it and only return the final completion.
+	onTextDelta?: (delta: string) => void;
 };
 
 export type ChatInvokeContext = {
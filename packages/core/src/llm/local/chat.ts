@@ -0,0 +1,90 @@
+import OpenAI from "openai";
+import type { ChatCompletionCreateParamsStreaming } from "openai/resources/chat/completions/completions";
+import type { ChatInvokeCompletion } from "../../types/llm";
+import type { BaseChatModel, ChatInvokeInput } from "../base";
+import {
+	appendMoonshotChatCompletionChunk,
+	createMoonshotStreamAccumulator,
+	type MoonshotChatCompletionChunk,
+	toMoonshotToolChoice,
+	toMoonshotTools,
+} from "../moonshot/serializer";
+import { toLocalChatInvokeCompletion, toLocalMessages } from "./serializer";
+
+const PROVIDER_NAME = "local" as const;
+const DEFAULT_REQUEST_TIMEOUT_MS = 2 * 60 * 60 * 1000;
+// Ollama and llama.cpp ignore the key, but the SDK refuses to send without one.
+const PLACEHOLDER_API_KEY = "local";
+
+export type ChatLocalOptions = {
+	baseURL: string;
+	model: string;
+	fetch?: typeof fetch;
+	timeoutMs?: number;
+};
+
+export class ChatLocal implements BaseChatModel<typeof PROVIDER_NAME> {
+	readonly provider: typeof PROVIDER_NAME = PROVIDER_NAME;
+	readonly model: string;
+	private readonly baseURL: string;
+	private readonly client: OpenAI;
+
+	constructor(options: ChatLocalOptions) {
+		this.model = options.model;
+		this.baseURL = options.baseURL.replace(/\/+$/, "");
+		this.client = new OpenAI({
+			apiKey: PLACEHOLDER_API_KEY,
+			baseURL: this.baseURL,
+			timeout: options.timeoutMs ?? DEFAULT_REQUEST_TIMEOUT_MS,
+			...(options.fetch ? { fetch: options.fetch } : {}),
+		});
+	}
+
+	async ainvoke(input: ChatInvokeInput): Promise<ChatInvokeCompletion> {
+		const tools = toMoonshotTools(input.tools);
+		const toolChoice = toMoonshotToolChoice(input.toolChoice);
+		const request = {
+			model: input.model ?? this.model,
+			messages: toLocalMessages(input.messages),
+			stream: true as const,
+			stream_options: { include_usage: true },
+			...(tools ? { tools } : {}),
+			...(toolChoice ? { tool_choice: toolChoice } : {}),
+		};
+		const accumulator = createMoonshotStreamAccumulator();
+		try {
+			const stream = await this.client.chat.completions.create(
+				request as ChatCompletionCreateParamsStreaming,
+				{ signal: input.signal },
+			);
+			for await (const chunk of stream) {
+				const before = accumulator.content.length;
+				appendMoonshotChatCompletionChunk(
+					accumulator,
+					chunk as MoonshotChatCompletionChunk,
+				);
+				if (input.onTextDelta && accumulator.content.length > before) {
+					input.onTextDelta(accumulator.content.slice(before));
+				}
+			}
+		} catch (error) {
+			throw toLocalError(error, this.baseURL);
+		}
+		return toLocalChatInvokeCompletion(accumulator, this.baseURL);
+	}
+}
+
+const toLocalError = (error: unknown, baseURL: string): Error => {
+	if (error instanceof OpenAI.APIConnectionError) {
+		return new Error(
+			`Local model server unreachable at ${baseURL}; is it still running? (${error.message})`,
+		);
+	}
+	if (error instanceof OpenAI.APIError) {
+		return new Error(
+			`Local model server error (${error.status ?? "unknown"}): ${error.message}`,
+		);
+	}
+	if (error instanceof Error) return error;
+	return new Error(`Local model server error: ${String(error)}`);
+};
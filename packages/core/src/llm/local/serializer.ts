@@ -0,0 +1,143 @@
+import { stringifyContent } from "../../content/stringify";
+import type {
+	BaseMessage,
+	ChatInvokeCompletion,
+	ChatInvokeUsage,
+	ContentPart,
+	ToolCall,
+} from "../../types/llm";
+import type { MoonshotStreamAccumulator } from "../moonshot/serializer";
+
+// Local servers speak plain OpenAI Chat Completions, so the Moonshot stream
+// accumulator and tool mapping are reused; only message replay differs.
+type LocalContent =
+	| string
+	| Array<
+			| { type: "text"; text: string }
+			| { type: "image_url"; image_url: { url: string } }
+	  >;
+
+export type LocalChatMessage =
+	| { role: "system"; content: string }
+	| { role: "user"; content: LocalContent }
+	| {
+			role: "assistant";
+			content: string | null;
+			tool_calls?: Array<{
+				id: string;
+				type: "function";
+				function: { name: string; arguments: string };
+			}>;
+	  }
+	| { role: "tool"; tool_call_id: string; content: string };
+
+const stringify = (content: string | ContentPart[] | null): string =>
+	stringifyContent(content, { mode: "display", joiner: "" });
+
+const toUserContent = (content: string | ContentPart[]): LocalContent => {
+	if (typeof content === "string") return content;
+	return content.map((part) => {
+		if (part.type === "text") return part;
+		if (part.type === "image_url") {
+			return { type: "image_url", image_url: { url: part.image_url.url } };
+		}
+		return { type: "text", text: stringify([part]) };
+	});
+};
+
+export function toLocalMessages(messages: BaseMessage[]): LocalChatMessage[] {
+	const mapped: LocalChatMessage[] = [];
+	for (const message of messages) {
+		switch (message.role) {
+			case "system":
+				mapped.push({ role: "system", content: stringify(message.content) });
+				break;
+			case "user":
+				mapped.push({ role: "user", content: toUserContent(message.content) });
+				break;
+			case "assistant": {
+				const toolCalls = (message.tool_calls ?? []).map((call) => ({
+					id: call.id,
+					type: "function" as const,
+					function: { ...call.function },
+				}));
+				mapped.push({
+					role: "assistant",
+					content: stringify(message.content) || null,
+					...(toolCalls.length ? { tool_calls: toolCalls } : {}),
+				});
+				break;
+			}
+			case "tool":
+				mapped.push({
+					role: "tool",
+					tool_call_id: message.tool_call_id,
+					content: stringify(message.content),
+				});
+				break;
+			default:
+				// Reasoning is not replayed: local servers do not accept it back.
+				break;
+		}
+	}
+	return mapped;
+}
+
+const normalizeNumber = (value: unknown): number =>
+	typeof value === "number" && Number.isFinite(value) && value >= 0
+		? Math.trunc(value)
+		: 0;
+
+const normalizeUsage = (
+	accumulator: MoonshotStreamAccumulator,
+): ChatInvokeUsage | null => {
+	const usage = accumulator.usage;
+	if (!usage) return null;
+	const inputTokens = normalizeNumber(usage.prompt_tokens);
+	const outputTokens = normalizeNumber(usage.completion_tokens);
+	return {
+		model: accumulator.model ?? "",
+		input_tokens: inputTokens,
+		output_tokens: outputTokens,
+		total_tokens:
+			typeof usage.total_tokens === "number"
+				? normalizeNumber(usage.total_tokens)
+				: inputTokens + outputTokens,
+	};
+};
+
+export function toLocalChatInvokeCompletion(
+	accumulator: MoonshotStreamAccumulator,
+	baseURL: string,
+): ChatInvokeCompletion {
+	const messages: BaseMessage[] = [];
+	if (accumulator.reasoningContent) {
+		messages.push({
+			role: "reasoning",
+			content: accumulator.reasoningContent,
+			raw_item: { provider: "local", field: "reasoning_content" },
+		});
+	}
+	const toolCalls: ToolCall[] = accumulator.toolCalls.map((call) => ({
+		id: call.id,
+		type: "function",
+		function: { name: call.name, arguments: call.arguments },
+	}));
+	if (accumulator.content || toolCalls.length) {
+		messages.push({
+			role: "assistant",
+			content: accumulator.content || null,
+			...(toolCalls.length ? { tool_calls: toolCalls } : {}),
+		});
+	}
+	return {
+		messages,
+		usage: normalizeUsage(accumulator),
+		stop_reason: accumulator.finishReason ?? null,
+		provider_meta: {
+			response_id: accumulator.id,
+			finish_reason: accumulator.finishReason ?? null,
+			base_url: baseURL,
+		},
+	};
+}
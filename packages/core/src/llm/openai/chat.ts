@@ -25,7 +25,7 @@ import {
 	sharedPrefixChars,
 	writeProviderLogDump,
 } from "../provider-log";
-import { invokeOpenAiHttp } from "./http-transport";
+import { invokeOpenAiHttp, outputTextDelta } from "./http-transport";
 import {
 	getEmptyCompletionDebugPayload,
 	getResponseStreamEventDebugPayload,
@@ -248,6 +248,7 @@ export class ChatOpenAI
 			signal,
 			debugSeq,
 			requestMeta,
+			onTextDelta: input.onTextDelta,
 		});
 		await this.debugResponseIfEnabled(
 			transportResult.response,
@@ -295,6 +296,7 @@ export class ChatOpenAI
 		signal?: AbortSignal;
 		debugSeq: number;
 		requestMeta: OpenAiRequestMeta;
+		onTextDelta?: (delta: string) => void;
 	}): Promise<TransportInvokeResult> {
 		this.evictIdleWsSessionState();
 		if (this.websocketMode === "off") {
@@ -303,6 +305,7 @@ export class ChatOpenAI
 				debugSeq: args.debugSeq,
 				sessionIdHeader: args.sessionIdHeader,
 				signal: args.signal,
+				onTextDelta: args.onTextDelta,
 				fallbackUsed: false,
 				chainReset: false,
 			});
@@ -318,6 +321,7 @@ export class ChatOpenAI
 				debugSeq: args.debugSeq,
 				sessionIdHeader: args.sessionIdHeader,
 				signal: args.signal,
+				onTextDelta: args.onTextDelta,
 				fallbackUsed: false,
 				chainReset: false,
 			});
@@ -328,6 +332,7 @@ export class ChatOpenAI
 				debugSeq: args.debugSeq,
 				sessionIdHeader: args.sessionIdHeader,
 				signal: args.signal,
+				onTextDelta: args.onTextDelta,
 				fallbackUsed: true,
 				chainReset: true,
 			});
@@ -404,6 +409,7 @@ export class ChatOpenAI
 				request: wsExecutionPlan.request,
 				signal: args.signal,
 				sessionIdHeader: args.sessionIdHeader,
+				onTextDelta: args.onTextDelta,
 				ws:
 					wsExecutionPlan.requiresWsConnectionReset ||
 					!wsExecutionPlan.hasReusableWs
@@ -452,6 +458,7 @@ export class ChatOpenAI
 						request: args.request,
 						signal: args.signal,
 						sessionIdHeader: args.sessionIdHeader,
+						onTextDelta: args.onTextDelta,
 						ws: undefined,
 					});
 					this.wsReconnectCount += 1;
@@ -500,6 +507,7 @@ export class ChatOpenAI
 				debugSeq: args.debugSeq,
 				sessionIdHeader: args.sessionIdHeader,
 				signal: args.signal,
+				onTextDelta: args.onTextDelta,
 				fallbackUsed: true,
 				chainReset: true,
 				wsInputMode: wsExecutionPlan.wsInputMode,
@@ -512,6 +520,7 @@ export class ChatOpenAI
 		debugSeq: number;
 		sessionIdHeader?: string;
 		signal?: AbortSignal;
+		onTextDelta?: (delta: string) => void;
 		fallbackUsed: boolean;
 		chainReset: boolean;
 		wsInputMode?: OpenAiWsInputMode;
@@ -530,7 +539,7 @@ export class ChatOpenAI
 			args.request,
 			args.signal,
 			args.sessionIdHeader,
-			this.getHttpStreamDebugObserver(args.debugSeq),
+			this.getHttpStreamObserver(args.debugSeq, args.onTextDelta),
 		);
 		return {
 			response,
@@ -580,6 +589,7 @@ export class ChatOpenAI
 		signal?: AbortSignal;
 		sessionIdHeader?: string;
 		ws?: OpenAiResponsesWsLike;
+		onTextDelta?: (delta: string) => void;
 	}): Promise<{ response: Response; ws: OpenAiResponsesWsLike }> {
 		return this.wsTransport.invoke(args);
 	}
@@ -846,14 +856,22 @@ export class ChatOpenAI
 		console.error(safeJsonStringify(payload, 2));
 	}
 
-	private getHttpStreamDebugObserver(
+	private getHttpStreamObserver(
 		seq: number,
+		onTextDelta?: (delta: string) => void,
 	): ((event: ResponseStreamEvent) => void | Promise<void>) | undefined {
-		const settings = getProviderLogSettings();
-		if (!settings.enabled) {
+		const debugEnabled = getProviderLogSettings().enabled;
+		if (!debugEnabled && !onTextDelta) {
 			return undefined;
 		}
 		return (event) => {
+			const delta = outputTextDelta(event);
+			if (delta && onTextDelta) {
+				onTextDelta(delta);
+			}
+			if (!debugEnabled) {
+				return;
+			}
 			const payload = getResponseStreamEventDebugPayload(event);
 			console.error(
 				`[openai.stream.event] seq=${seq} transport=http_stream websocket_mode=${this.websocketMode} ${safeJsonStringify(payload)}`,
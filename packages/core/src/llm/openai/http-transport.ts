@@ -11,6 +11,16 @@ export type OpenAiHttpEventObserver = (
 	event: ResponseStreamEvent,
 ) => void | Promise<void>;
 
+export const outputTextDelta = (event: unknown): string | null => {
+	const candidate = event as { type?: unknown; delta?: unknown } | null;
+	if (candidate?.type !== "response.output_text.delta") {
+		return null;
+	}
+	return typeof candidate.delta === "string" && candidate.delta.length > 0
+		? candidate.delta
+		: null;
+};
+
 export const invokeOpenAiHttp = async (
 	client: OpenAI,
 	request: ResponseCreateParamsBase,
@@ -5,6 +5,7 @@ import type {
 	ResponsesClientEvent,
 } from "openai/resources/responses/responses";
 import { ResponsesWS } from "openai/resources/responses/ws";
+import { outputTextDelta } from "./http-transport";
 import { OpenAiResponseAccumulator } from "./response-accumulator";
 import type {
 	OpenAiNativeWsSocketLike,
@@ -168,6 +169,7 @@ export class OpenAiWsTransport {
 		signal?: AbortSignal;
 		sessionIdHeader?: string;
 		ws?: OpenAiResponsesWsLike;
+		onTextDelta?: (delta: string) => void;
 	}): Promise<{ response: Response; ws: OpenAiResponsesWsLike }> {
 		await this.prepareClientForWsHandshake();
 		const wsOptionsHeaders: Record<string, string> = {
@@ -204,6 +206,7 @@ export class OpenAiWsTransport {
 				ws,
 				signal: args.signal,
 				closeWs,
+				onTextDelta: args.onTextDelta,
 			});
 			const responseCreateEvent = {
 				type: "response.create",
@@ -230,8 +233,9 @@ export class OpenAiWsTransport {
 		ws: OpenAiResponsesWsLike;
 		signal?: AbortSignal;
 		closeWs: () => void;
+		onTextDelta?: (delta: string) => void;
 	}): OpenAiWsResponseWaiter {
-		const { ws, signal, closeWs } = args;
+		const { ws, signal, closeWs, onTextDelta } = args;
 		let settled = false;
 		let rejectResponsePromise: ((error: unknown) => void) | undefined;
 		const responsePromise = new Promise<Response>((resolve, reject) => {
@@ -349,6 +353,10 @@ export class OpenAiWsTransport {
 				if (eventType !== "response.done") {
 					accumulator.observeEvent(event as never);
 				}
+				const delta = outputTextDelta(event);
+				if (delta && onTextDelta) {
+					onTextDelta(delta);
+				}
 				if (eventType === "response.failed") {
 					logWsEventTrace("sdk", eventType, false);
 					onResponseFailed(event);
@@ -30,6 +30,7 @@ export function createModelRegistry(specs: ModelSpec[]): ModelRegistry {
 			openrouter: {},
 			google: {},
 			moonshot: {},
+			local: {},
 			zai: {},
 			xai: {},
 		},
@@ -129,6 +130,7 @@ function cloneAliases(
 		openrouter: { ...aliasesByProvider.openrouter },
 		google: { ...aliasesByProvider.google },
 		moonshot: { ...aliasesByProvider.moonshot },
+		local: { ...aliasesByProvider.local },
 		zai: { ...aliasesByProvider.zai },
 		xai: { ...aliasesByProvider.xai },
 	};
@@ -42,4 +42,11 @@ export type ToolOutputCacheStore = {
 		refId: string,
 		options: ToolOutputCacheSearchOptions,
 	) => Promise<string> | string;
+	readRaw?: (
+		refId: string,
+		options?: { max_bytes?: number },
+	) =>
+		| Promise<{ content: string; byte_size: number; truncated: boolean }>
+		| { content: string; byte_size: number; truncated: boolean };
+	delete?: (refId: string) => Promise<boolean> | boolean;
 };
@@ -7,6 +7,7 @@ export type {
 	ReasoningEvent,
 	StepCompleteEvent,
 	StepStartEvent,
+	TextDeltaEvent,
 	TextEvent,
 	ToolCallEvent,
 	ToolResultEvent,
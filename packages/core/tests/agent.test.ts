@@ -397,6 +397,42 @@ describe("Agent", () => {
 		expect(toolResult?.result).toBe("ok:x");
 	});
 
+	test("runStream exposes the tool output cache ref on tool_result", async () => {
+		const tool = defineTool({
+			name: "echo",
+			description: "returns a value",
+			input: z.object({ value: z.string() }),
+			execute: (input) => `ok:${input.value}`,
+		});
+		const llm = new MockChatModel([
+			assistantResponse(null, [toolCall("call_1", "echo", '{"value":"x"}')]),
+			assistantResponse("done"),
+		]);
+		const agent = new Agent({
+			llm,
+			tools: [tool],
+			services: {
+				toolOutputCacheStore: {
+					save: async (record) => ({
+						id: `ref-${record.tool_call_id}`,
+						byte_size: record.content.length,
+						line_count: 1,
+					}),
+					read: async () => "",
+					grep: async () => "",
+				},
+			},
+		});
+		const events = [] as Array<{ type: string; output_ref_id?: string }>;
+
+		for await (const event of agent.runStream("hi")) {
+			events.push(event as { type: string; output_ref_id?: string });
+		}
+
+		const toolResult = events.find((event) => event.type === "tool_result");
+		expect(toolResult?.output_ref_id).toBe("ref-call_1");
+	});
+
 	test("runStream uses monotonic duration for successful and failed tool steps", async () => {
 		const successTool = defineTool({
 			name: "success",
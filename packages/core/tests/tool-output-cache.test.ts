@@ -34,6 +34,7 @@ describe("ToolOutputCacheService", () => {
 						openrouter: {},
 						google: {},
 						moonshot: {},
+						local: {},
 						zai: {},
 						xai: {},
 					},
@@ -80,6 +81,7 @@ describe("ToolOutputCacheService", () => {
 						openrouter: {},
 						google: {},
 						moonshot: {},
+						local: {},
 						zai: {},
 						xai: {},
 					},
@@ -113,6 +115,7 @@ describe("ToolOutputCacheService", () => {
 						openrouter: {},
 						google: {},
 						moonshot: {},
+						local: {},
 						zai: {},
 						xai: {},
 					},
@@ -157,6 +160,7 @@ describe("ToolOutputCacheService", () => {
 						openrouter: {},
 						google: {},
 						moonshot: {},
+						local: {},
 						zai: {},
 						xai: {},
 					},
@@ -205,6 +209,7 @@ describe("ToolOutputCacheService", () => {
 						openrouter: {},
 						google: {},
 						moonshot: {},
+						local: {},
 						zai: {},
 						xai: {},
 					},
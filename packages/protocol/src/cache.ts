@@ -0,0 +1,38 @@
+export type CacheEntry = {
+	ref_id: string;
+	tool: string;
+	byte_size: number;
+	line_count: number;
+	created_at: string;
+	age_ms: number;
+	is_error?: boolean;
+};
+
+export type CacheListParams = {
+	limit?: number;
+};
+
+export type CacheListResult = {
+	entries: CacheEntry[];
+};
+
+export type CacheReadParams = {
+	ref_id: string;
+	max_bytes?: number;
+};
+
+export type CacheReadResult = {
+	ref_id: string;
+	content: string;
+	byte_size: number;
+	truncated: boolean;
+};
+
+export type CacheDeleteParams = {
+	ref_id: string;
+};
+
+export type CacheDeleteResult = {
+	ref_id: string;
+	deleted: boolean;
+};
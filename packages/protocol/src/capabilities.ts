@@ -10,15 +10,22 @@ export type UiCapabilities = {
 export type ServerCapabilities = {
 	supports_run_cancel?: boolean;
 	supports_run_diagnostics?: boolean;
+	supports_run_usage?: boolean;
 	supports_shell_exec?: boolean;
 	supports_shell_tasks?: boolean;
 	supports_shell_detach?: boolean;
+	supports_shell_env?: boolean;
+	supports_dry_run?: boolean;
 	supports_tasks?: boolean;
 	supports_ui_requests?: boolean;
 	supports_mcp_list?: boolean;
 	supports_skills_list?: boolean;
 	supports_context_inspect?: boolean;
 	supports_tool_call?: boolean;
+	supports_tool_call_permission?: boolean;
 	supports_theme_set?: boolean;
+	supports_cwd_set?: boolean;
+	supports_disabled_tools?: boolean;
+	supports_cache?: boolean;
 	supports_permission_preflight_events?: boolean;
 };
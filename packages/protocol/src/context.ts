@@ -35,6 +35,10 @@ export type ContextInspectSkills = {
 export type ContextInspectResult = {
 	runtime_working_dir?: string;
 	runtime_sandbox_root?: string;
+	/** Process id of the runtime (on the remote host for ssh runtimes). */
+	runtime_pid?: number;
+	/** Tool names of the current agent; absent until the first run builds it. */
+	enabled_tools?: string[];
 	execution_environment?: string;
 	ui_context?: {
 		cwd?: string;
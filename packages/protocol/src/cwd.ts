@@ -0,0 +1,9 @@
+export type CwdSetParams = {
+	/** Absolute, or relative to the current runtime working directory. */
+	path: string;
+};
+
+export type CwdSetResult = {
+	cwd: string;
+	sandbox_root: string;
+};
@@ -1,7 +1,9 @@
 export * from "./auth";
+export * from "./cache";
 export * from "./capabilities";
 export * from "./client-tools";
 export * from "./context";
+export * from "./cwd";
 export * from "./initialize";
 export * from "./mcp";
 export * from "./mcp-protocol";
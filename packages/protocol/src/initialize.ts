@@ -13,5 +13,7 @@ export type InitializeResult = {
 	server_capabilities?: ServerCapabilities;
 	tui?: {
 		theme?: string;
+		/** Message of the day (version notices, announcements) shown until dismissed. */
+		motd?: string;
 	};
 };
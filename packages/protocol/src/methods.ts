@@ -1,10 +1,12 @@
 export type ProtocolMethod =
 	| "initialize"
+	| "ping"
 	| "run.start"
 	| "run.cancel"
 	| "run.status"
 	| "run.context"
 	| "run.diagnostics"
+	| "run.usage"
 	| "agent.event"
 	| "session.list"
 	| "session.history"
@@ -31,6 +33,10 @@ export type ProtocolMethod =
 	| "skills.list"
 	| "context.inspect"
 	| "theme.set"
+	| "cwd.set"
+	| "cache.list"
+	| "cache.read"
+	| "cache.delete"
 	| "ui.context.update"
 	| "ui.confirm.request"
 	| "ui.prompt.request"
@@ -3,6 +3,8 @@ import type { ModelReasoningLevel } from "@codelia/shared-types";
 export type ModelListParams = {
 	provider?: string;
 	include_details?: boolean;
+	// Endpoint to list for `provider: "local"`; defaults to `model.base_url`.
+	base_url?: string;
 };
 
 export type ModelListDetails = {
@@ -31,6 +33,8 @@ export type ModelSetParams = {
 	fast?: boolean;
 	scope?: "config" | "session";
 	reset?: boolean;
+	// Required for `provider: "local"` unless `model.base_url` is already set.
+	base_url?: string;
 };
 
 export type ModelSetResult = {
@@ -39,4 +43,5 @@ export type ModelSetResult = {
 	source: "config" | "session";
 	reasoning?: ModelReasoningLevel;
 	fast?: boolean;
+	base_url?: string;
 };
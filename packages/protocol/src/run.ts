@@ -33,6 +33,10 @@ export type RunStartParams = {
 	tool_choice?: ClientToolChoice;
 	ui_context?: UiContextSnapshot;
 	meta?: Record<string, unknown>;
+	/** Plan only: mutating tools are previewed and denied instead of executed. */
+	dry_run?: boolean;
+	/** Tools the user switched off for this session; calls to them are denied. */
+	disabled_tools?: string[];
 };
 
 export type RunStartResult = {
@@ -70,6 +74,16 @@ export type RunContextNotify = {
 	context_left_percent: number;
 };
 
+export type RunUsageNotify = {
+	run_id: string;
+	provider?: string;
+	model: string;
+	input_tokens: number;
+	output_tokens: number;
+	cached_input_tokens?: number;
+	cache_creation_tokens?: number;
+};
+
 export type RunUsageSummary = {
 	total_calls: number;
 	total_tokens: number;
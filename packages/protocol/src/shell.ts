@@ -8,6 +8,8 @@ export type ShellExecParams = {
 	command: string;
 	timeout_seconds?: number;
 	cwd?: string;
+	/** Extra environment variables for the child process; never echoed in previews/logs. */
+	env?: Record<string, string>;
 };
 
 export type ShellExecResult = {
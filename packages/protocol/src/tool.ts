@@ -1,6 +1,8 @@
 export type ToolCallParams = {
 	name: string;
 	arguments?: Record<string, unknown>;
+	/** Evaluate the session permission policy (and UI confirm) first, as for agent tool calls. */
+	require_permission?: boolean;
 };
 
 export type ToolCallResult = {
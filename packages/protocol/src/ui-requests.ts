@@ -1,5 +1,7 @@
 export type UiConfirmRequestParams = {
 	run_id?: string;
+	/** Tool call this permission confirm is for; matches `permission.preview`/`permission.ready`. */
+	tool_call_id?: string;
 	title: string;
 	message: string;
 	confirm_label?: string;
@@ -9,10 +11,19 @@ export type UiConfirmRequestParams = {
 	allow_reason?: boolean;
 };
 
+export type UiConfirmHunkDecision = {
+	/** 1-based position in the permission preview's hunk list. */
+	index: number;
+	header: string;
+	accepted: boolean;
+};
+
 export type UiConfirmResult = {
 	ok: boolean;
 	remember?: boolean;
 	reason?: string;
+	/** Per-hunk review of a large permission preview; only reviewed hunks are listed. */
+	hunks?: UiConfirmHunkDecision[];
 };
 
 export type UiPromptRequestParams = {
@@ -24,6 +24,11 @@ import {
 	buildExecutionEnvironmentContext,
 	logInitialExecutionEnvironmentDebug,
 } from "./execution-environment";
+import {
+	isLocalProvider,
+	LOCAL_PROVIDER,
+	type RuntimeModelProvider,
+} from "./local-provider";
 import { debugLog, log } from "./logger";
 import type { McpManager, McpOAuthPromptConfig, McpOAuthTokens } from "./mcp";
 import {
@@ -205,9 +210,12 @@ export const createAgentFactory = (
 				});
 				ctx = await SandboxContext.create(sandboxRoot, {
 					approvalMode: approvalModeResolution.approvalMode,
+					...(state.workingDirOverride
+						? { workingDir: state.workingDirOverride }
+						: {}),
 				});
 				log(
-					`sandbox created at ${ctx.rootDir} approval_mode=${approvalModeResolution.approvalMode}`,
+					`sandbox created at ${ctx.rootDir} cwd=${ctx.workingDir} approval_mode=${approvalModeResolution.approvalMode}`,
 				);
 				state.runtimeWorkingDir = ctx.workingDir;
 				state.runtimeSandboxRoot = ctx.rootDir;
@@ -248,11 +256,12 @@ export const createAgentFactory = (
 			}
 
 			state.approvalMode = approvalModeResolution.approvalMode;
-			const toolOutputCacheStore =
+			const toolOutputCacheStore = state.trackToolOutputCache(
 				environment.adapters.stores?.toolOutputCacheStore ??
-				(environment.persistence.mode === "runtime"
-					? new ToolOutputCacheStoreImpl()
-					: null);
+					(environment.persistence.mode === "runtime"
+						? new ToolOutputCacheStoreImpl()
+						: null),
+			);
 			const todoSessionContextKey = createToolSessionContextKey(
 				() => state.sessionId,
 			);
@@ -378,8 +387,15 @@ export const createAgentFactory = (
 				throw new Error(message);
 			}
 			const authResolver = await createEnvironmentAuthResolver(state, log);
-			const provider = await authResolver.resolveProvider(modelConfig.provider);
-			const providerAuth = await authResolver.resolveProviderAuth(provider);
+			const provider: RuntimeModelProvider = isLocalProvider(
+				modelConfig.provider,
+			)
+				? LOCAL_PROVIDER
+				: await authResolver.resolveProvider(modelConfig.provider);
+			const providerAuth =
+				provider === LOCAL_PROVIDER
+					? null
+					: await authResolver.resolveProviderAuth(provider);
 			const searchConfig =
 				environment.tools.search === "from-config"
 					? await resolveEnvironmentSearchConfig(state, workspaceRoot)
@@ -413,8 +429,10 @@ export const createAgentFactory = (
 			state.currentModelProvider = provider;
 			state.currentModelName = resolvedModelName;
 			state.currentModelSource = modelConfig.source;
+			// Local models have no published metadata; skip the (online) lookup.
 			const modelRegistry =
-				environment.persistence.mode === "runtime"
+				environment.persistence.mode === "runtime" &&
+				provider !== LOCAL_PROVIDER
 					? await buildModelRegistry(llm, {
 							strict: provider !== "openrouter",
 						})
@@ -428,6 +446,9 @@ export const createAgentFactory = (
 				isAutoApprovedTool: (tool) =>
 					state.autoApprovedClientToolNames.has(tool),
 				supportsConfirm: () => !!state.uiCapabilities?.supports_confirm,
+				isDryRunActive: () => state.activeRunDryRun,
+				isToolDisabled: (tool) => state.activeRunDisabledTools.has(tool),
+				isReadOnly: () => state.readOnly,
 				getActiveRunId: () => state.activeRunId ?? undefined,
 				requestConfirm: (params) => requestUiConfirm(state, params),
 				emitAgentEvent: async (runId, event) => {
@@ -451,6 +472,7 @@ export const createAgentFactory = (
 				...(editTool ? { editTool } : {}),
 				...(applyPatchTool ? { applyPatchTool } : {}),
 			});
+			state.toolPermissionHook = canExecuteTool;
 			const agent = new Agent({
 				llm,
 				tools,
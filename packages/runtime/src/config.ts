@@ -103,6 +103,7 @@ export type ResolvedModelConfig = {
 	reasoning?: string;
 	verbosity?: string;
 	fast?: boolean;
+	base_url?: string;
 	experimental?: {
 		openai?: {
 			websocket_mode?: "off" | "auto" | "on";
@@ -121,6 +122,7 @@ export const resolveModelConfig = async (
 		reasoning: effective.model?.reasoning,
 		verbosity: effective.model?.verbosity,
 		fast: effective.model?.fast,
+		base_url: effective.model?.base_url,
 		experimental: effective.experimental,
 	};
 };
@@ -129,11 +131,13 @@ export const resolveTuiConfig = async (
 	workingDir?: string,
 ): Promise<{
 	theme?: string;
+	motd?: string;
 }> => {
 	const { globalConfig, projectConfig } = await loadConfigLayers(workingDir);
 	const effective = configRegistry.resolve([globalConfig, projectConfig]);
 	return {
 		theme: effective.tui?.theme,
+		motd: effective.tui?.motd,
 	};
 };
 
@@ -515,6 +519,7 @@ export const updateModel = async (
 		name: string;
 		reasoning?: ModelReasoningLevel;
 		fast?: boolean;
+		base_url?: string;
 	},
 ): Promise<WriteTarget> => {
 	const target = await resolveWriteTarget(workingDir, "model");
@@ -24,6 +24,7 @@ const mergeModelOverride = (
 		name: override.name ?? config.name,
 		reasoning: override.reasoning ?? config.reasoning,
 		fast: override.fast ?? config.fast,
+		base_url: override.base_url ?? config.base_url,
 	};
 };
 
@@ -47,6 +48,7 @@ export const setSessionModelOverride = (
 		name: string;
 		reasoning?: ModelReasoningLevel;
 		fast?: boolean;
+		base_url?: string;
 	},
 ): ResolvedModelConfig => {
 	const current = mergeModelOverride(baseConfig, state.sessionModelOverride);
@@ -55,6 +57,7 @@ export const setSessionModelOverride = (
 		name: next.name,
 		reasoning: next.reasoning ?? current.reasoning,
 		fast: next.fast ?? current.fast,
+		base_url: next.base_url ?? current.base_url,
 	};
 	return mergeModelOverride(baseConfig, state.sessionModelOverride);
 };
@@ -77,12 +80,14 @@ export const readSessionModelOverride = (
 	const provider = pickString(raw.provider);
 	const name = pickString(raw.name);
 	const reasoning = pickString(raw.reasoning);
+	const baseUrl = pickString(raw.base_url);
 	if (!provider || !name) return null;
 	return {
 		provider,
 		name,
 		...(reasoning ? { reasoning } : {}),
 		...(typeof raw.fast === "boolean" ? { fast: raw.fast } : {}),
+		...(baseUrl ? { base_url: baseUrl } : {}),
 	};
 };
 
@@ -97,6 +102,7 @@ export const mergeSessionModelOverrideIntoMeta = (
 			name: override.name,
 			...(override.reasoning ? { reasoning: override.reasoning } : {}),
 			...(override.fast !== undefined ? { fast: override.fast } : {}),
+			...(override.base_url ? { base_url: override.base_url } : {}),
 		};
 	} else {
 		delete nextMeta[MODEL_OVERRIDE_SESSION_META_KEY];
@@ -105,7 +105,7 @@ export const resolveEnvironmentExecutionEnvironmentConfig = async (
 export const resolveEnvironmentTuiConfig = async (
 	state: RuntimeState,
 	workingDir?: string,
-): Promise<{ theme?: string }> => {
+): Promise<{ theme?: string; motd?: string }> => {
 	if (state.effectiveEnvironment.config.source === "host") {
 		const provider = state.effectiveEnvironment.adapters.configProvider;
 		if (!provider?.resolveTuiConfig) {
@@ -127,6 +127,7 @@ export const updateEnvironmentModel = async (
 		name: string;
 		reasoning?: ModelReasoningLevel;
 		fast?: boolean;
+		base_url?: string;
 	},
 ): Promise<WriteTarget> => {
 	if (state.effectiveEnvironment.config.source === "host") {
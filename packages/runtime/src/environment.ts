@@ -67,7 +67,9 @@ export type RuntimeConfigProvider = {
 	resolveExecutionEnvironmentConfig?: (
 		workingDir?: string,
 	) => Promise<ResolvedExecutionEnvironmentConfig>;
-	resolveTuiConfig?: (workingDir?: string) => Promise<{ theme?: string }>;
+	resolveTuiConfig?: (
+		workingDir?: string,
+	) => Promise<{ theme?: string; motd?: string }>;
 	updateModel?: (
 		workingDir: string | undefined,
 		model: {
@@ -75,6 +77,7 @@ export type RuntimeConfigProvider = {
 			name: string;
 			reasoning?: ModelReasoningLevel;
 			fast?: boolean;
+			base_url?: string;
 		},
 	) => Promise<WriteTarget>;
 	updateTuiTheme?: (
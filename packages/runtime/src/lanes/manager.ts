@@ -381,6 +381,34 @@ export class LaneManager {
 		return { lane, backend_alive: alive };
 	}
 
+	async tail(
+		laneId: string,
+		lines: number,
+	): Promise<{ lane: LaneRecord; backend_alive: boolean; lines: string[] }> {
+		const status = await this.status(laneId);
+		if (!status.backend_alive || status.lane.mux_backend !== "tmux") {
+			return { ...status, lines: [] };
+		}
+		const { stdout } = await this.cmd(
+			"tmux",
+			[
+				"capture-pane",
+				"-p",
+				"-J",
+				"-t",
+				`${status.lane.mux_target}:0.0`,
+				"-S",
+				`-${lines}`,
+			],
+			{ timeoutMs: 5_000 },
+		);
+		const output = stdout.replace(/\s+$/, "");
+		return {
+			...status,
+			lines: output ? output.split("\n").slice(-lines) : [],
+		};
+	}
+
 	async close(input: LaneCloseInput): Promise<LaneRecord> {
 		const lane = await this.registry.get(input.lane_id);
 		if (!lane) {
@@ -0,0 +1,53 @@
+import type { SupportedProvider } from "./auth/resolver";
+
+// Models served on the user's machine (Ollama, llama.cpp) through their
+// OpenAI-compatible API. Unlike SUPPORTED_PROVIDERS there are no credentials;
+// the endpoint comes from `model.base_url`.
+export const LOCAL_PROVIDER = "local" as const;
+
+export type RuntimeModelProvider = SupportedProvider | typeof LOCAL_PROVIDER;
+
+const LOCAL_MODEL_LIST_TIMEOUT_MS = 3_000;
+
+export const isLocalProvider = (
+	provider: string | null | undefined,
+): provider is typeof LOCAL_PROVIDER => provider === LOCAL_PROVIDER;
+
+// Accepts a server root such as `http://127.0.0.1:11434` (as reported by the
+// TUI probe) and returns the OpenAI-compatible `/v1` endpoint.
+export const normalizeLocalBaseUrl = (value: string): string | null => {
+	let url: URL;
+	try {
+		url = new URL(value.trim());
+	} catch {
+		return null;
+	}
+	if (url.protocol !== "http:" && url.protocol !== "https:") {
+		return null;
+	}
+	const path = url.pathname.replace(/\/+$/, "");
+	return `${url.origin}${path || "/v1"}`;
+};
+
+export const fetchLocalModelIds = async (baseUrl: string): Promise<string[]> => {
+	const response = await fetch(`${baseUrl}/models`, {
+		signal: AbortSignal.timeout(LOCAL_MODEL_LIST_TIMEOUT_MS),
+	});
+	if (!response.ok) {
+		throw new Error(
+			`local model server at ${baseUrl} answered ${response.status}`,
+		);
+	}
+	const body = (await response.json()) as { data?: unknown };
+	if (!Array.isArray(body.data)) {
+		return [];
+	}
+	return body.data
+		.map((entry) =>
+			typeof entry === "object" && entry !== null && "id" in entry
+				? (entry as { id?: unknown }).id
+				: undefined,
+		)
+		.filter((id): id is string => typeof id === "string" && id.length > 0)
+		.sort();
+};
@@ -2,6 +2,7 @@ import type { BaseChatModel } from "@codelia/core";
 import {
 	ANTHROPIC_DEFAULT_MODEL,
 	ChatAnthropic,
+	ChatLocal,
 	ChatMoonshot,
 	ChatOpenAI,
 	ChatOpenRouter,
@@ -20,7 +21,6 @@ import {
 import { ModelMetadataServiceImpl } from "@codelia/model-metadata";
 import { StoragePathServiceImpl } from "@codelia/storage";
 import { OPENAI_OAUTH_BASE_URL } from "./auth/openai-oauth";
-import type { SupportedProvider } from "./auth/resolver";
 import type { ProviderAuth } from "./auth/store";
 import {
 	type ResolvedModelConfig,
@@ -28,6 +28,11 @@ import {
 	resolveReasoningEffort,
 	resolveTextVerbosity,
 } from "./config";
+import {
+	LOCAL_PROVIDER,
+	normalizeLocalBaseUrl,
+	type RuntimeModelProvider,
+} from "./local-provider";
 import { resolveFastMode } from "./model-fast";
 import {
 	resolveAnthropicMaxTokens,
@@ -42,9 +47,10 @@ const OPENAI_OAUTH_ORIGINATOR = "codelia";
 const OPENAI_OAUTH_USER_AGENT = "codelia-cli";
 
 export type RuntimeModelFactoryInput = {
-	provider: SupportedProvider;
+	provider: RuntimeModelProvider;
 	config: ResolvedModelConfig;
-	auth: ProviderAuth;
+	// Null only for the local provider, which has no credentials.
+	auth: ProviderAuth | null;
 	useMetadata: boolean;
 	log: (message: string) => void;
 	getOpenAiAccessToken?: () => Promise<{
@@ -262,6 +268,22 @@ export const createRuntimeModel = async ({
 		resolveReasoningEffort(config.reasoning) ?? "medium";
 	const isFastRequested = config.fast === true;
 
+	if (provider === LOCAL_PROVIDER) {
+		const baseURL = normalizeLocalBaseUrl(config.base_url ?? "");
+		if (!baseURL || !config.name) {
+			throw new Error(
+				"model.provider=local needs model.base_url and model.name (run /local-models in the TUI)",
+			);
+		}
+		return {
+			llm: new ChatLocal({ baseURL, model: config.name }),
+			resolvedModelName: config.name,
+		};
+	}
+	if (!auth) {
+		throw new Error(`${provider} credentials are missing`);
+	}
+
 	switch (provider) {
 		case "openai": {
 			const modelName = config.name ?? OPENAI_DEFAULT_MODEL;
@@ -122,6 +122,7 @@ const withOpenRouterDynamicModel = (
 			openrouter: { ...registry.aliasesByProvider.openrouter },
 			google: { ...registry.aliasesByProvider.google },
 			moonshot: { ...registry.aliasesByProvider.moonshot },
+			local: { ...registry.aliasesByProvider.local },
 			zai: { ...registry.aliasesByProvider.zai },
 			xai: { ...registry.aliasesByProvider.xai },
 		},
@@ -12,12 +12,21 @@ import type {
 import type { SandboxContext } from "../sandbox/context";
 import type { PermissionService } from "./service";
 import { buildPermissionPreview } from "./preview";
+import { extractCommand, isShellPermissionTool } from "./utils";
+
+const describeDryRunCall = (tool: string, rawArgs: string): string =>
+	isShellPermissionTool(tool)
+		? (extractCommand(rawArgs) ?? rawArgs)
+		: `${tool} ${rawArgs}`;
 
 export type ToolPermissionHookCapabilities = {
 	permissionService: PermissionService;
 	hostToolNames: ReadonlySet<string>;
 	isAutoApprovedTool: (tool: string) => boolean;
 	supportsConfirm: () => boolean;
+	isDryRunActive: () => boolean;
+	isToolDisabled: (tool: string) => boolean;
+	isReadOnly: () => boolean;
 	getActiveRunId: () => string | undefined;
 	requestConfirm: (
 		params: UiConfirmRequestParams,
@@ -38,6 +47,9 @@ export const createToolPermissionHook = ({
 	hostToolNames,
 	isAutoApprovedTool,
 	supportsConfirm,
+	isDryRunActive,
+	isToolDisabled,
+	isReadOnly,
 	getActiveRunId,
 	requestConfirm,
 	emitAgentEvent,
@@ -56,6 +68,14 @@ export const createToolPermissionHook = ({
 			debug(`permission.evaluate tool=${tool} decision=allow reason=host-tool`);
 			return { decision: "allow" };
 		}
+		if (isToolDisabled(tool)) {
+			debug(`permission.evaluate tool=${tool} decision=deny reason=disabled`);
+			return {
+				decision: "deny",
+				reason: `${tool} is disabled for this session by the user; continue with the other tools`,
+				stop_turn: false,
+			};
+		}
 		if (isAutoApprovedTool(tool)) {
 			debug(
 				`permission.evaluate tool=${tool} decision=allow reason=client-tool-auto-approved`,
@@ -63,6 +83,46 @@ export const createToolPermissionHook = ({
 			return { decision: "allow" };
 		}
 
+		if (isReadOnly() && !permissionService.isDryRunSafe(tool, rawArgs)) {
+			debug(`permission.evaluate tool=${tool} decision=deny reason=read-only`);
+			return {
+				decision: "deny",
+				reason:
+					"read-only session: write and exec tools are disabled; answer from what read-only tools can show",
+				stop_turn: false,
+			};
+		}
+
+		if (isDryRunActive() && !permissionService.isDryRunSafe(tool, rawArgs)) {
+			const runId = getActiveRunId();
+			const preview = await buildPermissionPreview({
+				tool,
+				rawArgs,
+				toolContext,
+				sandboxKey,
+				...(editTool ? { editTool } : {}),
+				...(applyPatchTool ? { applyPatchTool } : {}),
+			});
+			if (runId) {
+				await emitAgentEvent(runId, {
+					type: "permission.preview",
+					tool,
+					tool_call_id: call.id,
+					...(preview.filePath ? { file_path: preview.filePath } : {}),
+					...(preview.language ? { language: preview.language } : {}),
+					...(preview.diff ? { diff: preview.diff } : {}),
+					summary: preview.summary ?? describeDryRunCall(tool, rawArgs),
+					...(preview.truncated ? { truncated: true } : {}),
+				});
+			}
+			debug(`permission.evaluate tool=${tool} decision=deny reason=dry-run`);
+			return {
+				decision: "deny",
+				reason:
+					"dry run (plan only): this call was previewed, not executed; continue planning as if it succeeded",
+			};
+		}
+
 		const decision = permissionService.evaluate(tool, rawArgs);
 		debug(
 			`permission.evaluate tool=${tool} decision=${decision.decision}${decision.reason ? ` reason=${decision.reason}` : ""}`,
@@ -115,6 +175,7 @@ export const createToolPermissionHook = ({
 
 		const confirmResult = await requestConfirm({
 			run_id: runId,
+			tool_call_id: call.id,
 			title: prompt.title,
 			message: prompt.message,
 			confirm_label: "Allow",
@@ -134,6 +195,19 @@ export const createToolPermissionHook = ({
 			);
 			return { decision: "deny", reason, stop_turn: stopTurn };
 		}
+		const rejectedHunks = (confirmResult.hunks ?? []).filter(
+			(hunk) => !hunk.accepted,
+		);
+		if (rejectedHunks.length > 0) {
+			// Partial application is left to the model: deny with the hunks to drop so it can retry.
+			const reason = `user rejected ${rejectedHunks.length} hunk(s) of this change: ${rejectedHunks
+				.map((hunk) => `#${hunk.index} ${hunk.header}`)
+				.join("; ")}. Apply the change again without those hunks.`;
+			debug(
+				`permission.confirm hunks_rejected tool=${tool} count=${rejectedHunks.length}`,
+			);
+			return { decision: "deny", reason, stop_turn: false };
+		}
 		if (confirmResult.remember) {
 			const rules = permissionService.rememberAllow(tool, rawArgs);
 			debug(`permission.remember tool=${tool} rules=${rules.length}`);
@@ -49,6 +49,7 @@ const SYSTEM_TOOL_ALLOWLIST_MINIMAL = [
 	"shell_stdin_write",
 	"lane_list",
 	"lane_status",
+	"lane_tail",
 	"done",
 ] as const;
 
@@ -255,6 +256,15 @@ export class PermissionService {
 		return { title: "Run tool?", message: toolName };
 	}
 
+	/** Dry runs only execute what the minimal system policy allows without confirmation. */
+	isDryRunSafe(toolName: string, rawArgs: string): boolean {
+		const readOnly = new PermissionService({
+			system: buildSystemPermissions("minimal"),
+			...(this.bashPathGuard ? { bashPathGuard: this.bashPathGuard } : {}),
+		});
+		return readOnly.evaluate(toolName, rawArgs).decision === "allow";
+	}
+
 	evaluate(toolName: string, rawArgs: string): PermissionDecision {
 		if (this.approvalMode === "full-access") {
 			return this.evaluateFullAccess(toolName, rawArgs);
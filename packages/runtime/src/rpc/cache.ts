@@ -0,0 +1,126 @@
+import {
+	type CacheDeleteParams,
+	type CacheDeleteResult,
+	type CacheEntry,
+	type CacheListParams,
+	type CacheListResult,
+	type CacheReadParams,
+	type CacheReadResult,
+	RPC_ERROR_CODE,
+} from "@codelia/protocol";
+import type { RuntimeState } from "../runtime-state";
+import { sendError, sendResult } from "./transport";
+
+const DEFAULT_CACHE_LIST_LIMIT = 100;
+const DEFAULT_CACHE_READ_MAX_BYTES = 1024 * 1024;
+
+export type CacheHandlersDeps = {
+	state: RuntimeState;
+	log: (message: string) => void;
+};
+
+export const createCacheHandlers = ({
+	state,
+	log,
+}: CacheHandlersDeps): {
+	handleCacheList: (id: string, params: CacheListParams | undefined) => void;
+	handleCacheRead: (id: string, params: CacheReadParams) => Promise<void>;
+	handleCacheDelete: (id: string, params: CacheDeleteParams) => Promise<void>;
+} => {
+	// Only outputs saved under the current session are exposed; refs from other
+	// sessions stay reachable by the agent but are not listed.
+	const findEntry = (refId: string | undefined) => {
+		if (!refId) return null;
+		const entry = state.toolOutputCacheEntries.get(refId);
+		return entry && entry.session_id === state.sessionId ? entry : null;
+	};
+
+	const rejectUnknownRef = (id: string, refId: string | undefined): void => {
+		sendError(id, {
+			code: RPC_ERROR_CODE.INVALID_PARAMS,
+			message: refId ? `unknown tool output ref: ${refId}` : "ref_id is required",
+		});
+	};
+
+	const handleCacheList = (
+		id: string,
+		params: CacheListParams | undefined,
+	): void => {
+		const limit = Math.max(1, params?.limit ?? DEFAULT_CACHE_LIST_LIMIT);
+		const now = Date.now();
+		const entries: CacheEntry[] = Array.from(
+			state.toolOutputCacheEntries.values(),
+		)
+			.filter((entry) => entry.session_id === state.sessionId)
+			.reverse()
+			.slice(0, limit)
+			.map((entry) => ({
+				ref_id: entry.ref_id,
+				tool: entry.tool,
+				byte_size: entry.byte_size,
+				line_count: entry.line_count,
+				created_at: new Date(entry.created_at_ms).toISOString(),
+				age_ms: Math.max(0, now - entry.created_at_ms),
+				...(entry.is_error ? { is_error: true } : {}),
+			}));
+		const result: CacheListResult = { entries };
+		sendResult(id, result);
+	};
+
+	const handleCacheRead = async (
+		id: string,
+		params: CacheReadParams,
+	): Promise<void> => {
+		const entry = findEntry(params?.ref_id);
+		if (!entry) {
+			rejectUnknownRef(id, params?.ref_id);
+			return;
+		}
+		const store = state.toolOutputCache;
+		if (!store?.readRaw) {
+			sendError(id, {
+				code: RPC_ERROR_CODE.INVALID_PARAMS,
+				message: "tool output cache is unavailable",
+			});
+			return;
+		}
+		try {
+			const raw = await store.readRaw(entry.ref_id, {
+				max_bytes: params.max_bytes ?? DEFAULT_CACHE_READ_MAX_BYTES,
+			});
+			const result: CacheReadResult = { ref_id: entry.ref_id, ...raw };
+			sendResult(id, result);
+		} catch (error) {
+			sendError(id, {
+				code: RPC_ERROR_CODE.RUNTIME_INTERNAL,
+				message: `cache read failed: ${String(error)}`,
+			});
+		}
+	};
+
+	const handleCacheDelete = async (
+		id: string,
+		params: CacheDeleteParams,
+	): Promise<void> => {
+		const entry = findEntry(params?.ref_id);
+		if (!entry) {
+			rejectUnknownRef(id, params?.ref_id);
+			return;
+		}
+		try {
+			const deleted =
+				(await state.toolOutputCache?.delete?.(entry.ref_id)) ?? false;
+			state.toolOutputCacheEntries.delete(entry.ref_id);
+			const result: CacheDeleteResult = { ref_id: entry.ref_id, deleted };
+			sendResult(id, result);
+			log(`cache.delete ${entry.ref_id} deleted=${deleted}`);
+		} catch (error) {
+			sendError(id, {
+				code: RPC_ERROR_CODE.RUNTIME_INTERNAL,
+				message: `cache delete failed: ${String(error)}`,
+			});
+		}
+	};
+
+	return { handleCacheList, handleCacheRead, handleCacheDelete };
+};
@@ -125,6 +125,8 @@ export const createContextHandlers = ({
 			const result: ContextInspectResult = {
 				runtime_working_dir: state.runtimeWorkingDir ?? undefined,
 				runtime_sandbox_root: state.runtimeSandboxRoot ?? undefined,
+				runtime_pid: process.pid,
+				enabled_tools: state.tools?.map((tool) => tool.name),
 				execution_environment: executionEnvironment ?? undefined,
 				runtime_environment: state.effectiveEnvironment.summary,
 				ui_context: {
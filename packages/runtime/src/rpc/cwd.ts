@@ -0,0 +1,95 @@
+import { promises as fs } from "node:fs";
+import path from "node:path";
+import {
+	type CwdSetParams,
+	type CwdSetResult,
+	RPC_ERROR_CODE,
+} from "@codelia/protocol";
+import type { RuntimeState } from "../runtime-state";
+import { sendError, sendResult } from "./transport";
+
+export type CwdHandlersDeps = {
+	state: RuntimeState;
+	log: (message: string) => void;
+};
+
+const isInside = (rootDir: string, target: string): boolean => {
+	const relative = path.relative(rootDir, target);
+	return (
+		relative === "" ||
+		(!relative.startsWith("..") && !path.isAbsolute(relative))
+	);
+};
+
+export const createCwdHandlers = ({
+	state,
+	log,
+}: CwdHandlersDeps): {
+	handleCwdSet: (id: string, params: CwdSetParams | undefined) => Promise<void>;
+} => {
+	const handleCwdSet = async (
+		id: string,
+		params: CwdSetParams | undefined,
+	): Promise<void> => {
+		if (state.activeRunId) {
+			sendError(id, {
+				code: RPC_ERROR_CODE.RUNTIME_BUSY,
+				message: "runtime busy",
+			});
+			return;
+		}
+		const requested = params?.path?.trim();
+		if (!requested) {
+			sendError(id, {
+				code: RPC_ERROR_CODE.INVALID_PARAMS,
+				message: "path is required",
+			});
+			return;
+		}
+		const sandboxRoot = path.resolve(
+			state.runtimeSandboxRoot ??
+				state.effectiveEnvironment.workspace.root ??
+				process.cwd(),
+		);
+		const currentDir = state.runtimeWorkingDir ?? sandboxRoot;
+		const target = path.resolve(currentDir, requested);
+		// Sandboxed modes keep the agent inside the workspace; full-access may leave it.
+		if (
+			state.approvalMode !== "full-access" &&
+			!isInside(sandboxRoot, target)
+		) {
+			sendError(id, {
+				code: RPC_ERROR_CODE.INVALID_PARAMS,
+				message: `path escapes sandbox root ${sandboxRoot}: ${target}`,
+			});
+			return;
+		}
+		try {
+			const stat = await fs.stat(target);
+			if (!stat.isDirectory()) {
+				sendError(id, {
+					code: RPC_ERROR_CODE.INVALID_PARAMS,
+					message: `not a directory: ${target}`,
+				});
+				return;
+			}
+		} catch {
+			sendError(id, {
+				code: RPC_ERROR_CODE.INVALID_PARAMS,
+				message: `no such directory: ${target}`,
+			});
+			return;
+		}
+		state.runtimeSandboxRoot = sandboxRoot;
+		state.runtimeWorkingDir = target;
+		state.workingDirOverride = target === sandboxRoot ? null : target;
+		// The next run rebuilds the agent (history is restored from the session)
+		// so tools and the bash path guard pick up the new directory.
+		state.agent = null;
+		const result: CwdSetResult = { cwd: target, sandbox_root: sandboxRoot };
+		sendResult(id, result);
+		log(`cwd.set ${target} root=${sandboxRoot}`);
+	};
+
+	return { handleCwdSet };
+};
@@ -9,7 +9,11 @@ import type {
 import {
 	type AuthLogoutParams,
 	type AuthLogoutResult,
+	type CacheDeleteParams,
+	type CacheListParams,
+	type CacheReadParams,
 	type ContextInspectParams,
+	type CwdSetParams,
 	type InitializeParams,
 	type InitializeResult,
 	type McpListParams,
@@ -55,10 +59,12 @@ import { PROTOCOL_VERSION, SERVER_NAME, SERVER_VERSION } from "../constants";
 import { isTuiLocalEnvironment } from "../environment";
 import {
 	createEnvironmentAuthResolver,
+	resolveEnvironmentModelConfig,
 	resolveEnvironmentTuiConfig,
 	updateEnvironmentModel,
 	updateEnvironmentTuiTheme,
 } from "../environment-services";
+import { isLocalProvider } from "../local-provider";
 import type { McpManager } from "../mcp";
 import type { RuntimeState } from "../runtime-state";
 import type { TaskManager } from "../tasks";
@@ -67,7 +73,9 @@ import {
 	VolatileSessionStateStore,
 	VolatileToolOutputCacheStore,
 } from "../volatile-stores";
+import { createCacheHandlers } from "./cache";
 import { createContextHandlers } from "./context";
+import { createCwdHandlers } from "./cwd";
 import { createHistoryHandlers } from "./history";
 import {
 	buildProviderModelList as buildProviderModelListDefault,
@@ -210,6 +218,14 @@ export const createRuntimeHandlers = ({
 		if (authResolver.hasAnyAvailableAuth()) {
 			return;
 		}
+		// A local model server needs no credentials, so there is nothing to set up.
+		const configuredModel = await resolveEnvironmentModelConfig(
+			state,
+			state.lastUiContext?.cwd ?? state.runtimeWorkingDir ?? undefined,
+		);
+		if (isLocalProvider(configuredModel.provider)) {
+			return;
+		}
 
 		const providerPick = await requestUiPick(state, {
 			title: "Let's set up your provider to get started.",
@@ -372,6 +388,12 @@ export const createRuntimeHandlers = ({
 		state,
 		log,
 	});
+	const { handleCacheList, handleCacheRead, handleCacheDelete } =
+		createCacheHandlers({
+			state,
+			log,
+		});
+	const { handleCwdSet } = createCwdHandlers({ state, log });
 	const { handleToolCall } = createToolHandlers({
 		state,
 		getAgent,
@@ -399,14 +421,13 @@ export const createRuntimeHandlers = ({
 		id: string,
 		params: InitializeParams,
 	): Promise<void> => {
-		let resolvedTheme: string | undefined;
+		let resolvedTui: { theme?: string; motd?: string } = {};
 		const environment = state.effectiveEnvironment;
 		if (isTuiLocalEnvironment(environment)) {
 			try {
 				const workingDir =
 					state.lastUiContext?.cwd ?? state.runtimeWorkingDir ?? process.cwd();
-				resolvedTheme = (await resolveEnvironmentTuiConfig(state, workingDir))
-					.theme;
+				resolvedTui = await resolveEnvironmentTuiConfig(state, workingDir);
 			} catch (error) {
 				log(`initialize tui config load failed: ${String(error)}`);
 			}
@@ -414,6 +435,8 @@ export const createRuntimeHandlers = ({
 		const processEnabled = environment.workspace.process === "runtime";
 		const mcpEnabled = environment.tools.mcp === "from-config";
 		const skillsEnabled = environment.context.skills === "from-config";
+		const cwdSetEnabled =
+			environment.workspace.filesystem === "enabled" || processEnabled;
 		const themeSetEnabled =
 			isTuiLocalEnvironment(environment) &&
 			environment.config.source !== "disabled";
@@ -423,19 +446,33 @@ export const createRuntimeHandlers = ({
 			server_capabilities: {
 				supports_run_cancel: true,
 				supports_run_diagnostics: true,
-				supports_shell_exec: processEnabled,
+				supports_run_usage: true,
+				supports_shell_exec: processEnabled && !state.readOnly,
 				supports_shell_tasks: processEnabled,
 				supports_shell_detach: processEnabled,
+				supports_shell_env: processEnabled,
+				supports_dry_run: true,
 				supports_tasks: processEnabled,
 				supports_ui_requests: isTuiLocalEnvironment(environment),
 				supports_mcp_list: mcpEnabled,
 				supports_skills_list: skillsEnabled,
 				supports_context_inspect: true,
 				supports_tool_call: true,
+				supports_tool_call_permission: true,
 				supports_theme_set: themeSetEnabled,
+				supports_cwd_set: cwdSetEnabled,
+				supports_disabled_tools: true,
+				supports_cache: true,
 				supports_permission_preflight_events: true,
 			},
-			...(resolvedTheme ? { tui: { theme: resolvedTheme } } : {}),
+			...(resolvedTui.theme || resolvedTui.motd
+				? {
+						tui: {
+							...(resolvedTui.theme ? { theme: resolvedTui.theme } : {}),
+							...(resolvedTui.motd ? { motd: resolvedTui.motd } : {}),
+						},
+					}
+				: {}),
 		};
 		sendResult(id, result);
 		log(`initialize from ${params.client?.name ?? "unknown"}`);
@@ -598,6 +635,9 @@ export const createRuntimeHandlers = ({
 		const themeSetEnabled =
 			isTuiLocalEnvironment(state.effectiveEnvironment) &&
 			state.effectiveEnvironment.config.source !== "disabled";
+		const cwdSetEnabled =
+			state.effectiveEnvironment.workspace.filesystem === "enabled" ||
+			state.effectiveEnvironment.workspace.process === "runtime";
 		const rejectDisabled = (message: string): void =>
 			sendError(req.id, {
 				code: RPC_ERROR_CODE.INVALID_PARAMS,
@@ -606,6 +646,8 @@ export const createRuntimeHandlers = ({
 		switch (req.method) {
 			case "initialize":
 				return handleInitialize(req.id, req.params as InitializeParams);
+			case "ping":
+				return sendResult(req.id, {});
 			case "run.start":
 				return handleRunStart(req.id, req.params as RunStartParams);
 			case "run.cancel":
@@ -625,6 +667,10 @@ export const createRuntimeHandlers = ({
 			case "shell.exec":
 				if (!shellHandlers)
 					return rejectDisabled("process execution is disabled");
+				if (state.readOnly)
+					return rejectDisabled(
+						"shell commands are disabled in read-only mode",
+					);
 				return shellHandlers.handleShellExec(
 					req.id,
 					req.params as ShellExecParams,
@@ -632,6 +678,10 @@ export const createRuntimeHandlers = ({
 			case "shell.start":
 				if (!shellHandlers)
 					return rejectDisabled("process execution is disabled");
+				if (state.readOnly)
+					return rejectDisabled(
+						"shell commands are disabled in read-only mode",
+					);
 				return shellHandlers.handleShellStart(
 					req.id,
 					req.params as ShellStartParams,
@@ -738,6 +788,16 @@ export const createRuntimeHandlers = ({
 				if (!themeSetEnabled)
 					return rejectDisabled("theme setting is disabled");
 				return handleThemeSet(req.id, req.params as ThemeSetParams);
+			case "cwd.set":
+				if (!cwdSetEnabled)
+					return rejectDisabled("workspace access is disabled");
+				return handleCwdSet(req.id, req.params as CwdSetParams);
+			case "cache.list":
+				return handleCacheList(req.id, req.params as CacheListParams);
+			case "cache.read":
+				return handleCacheRead(req.id, req.params as CacheReadParams);
+			case "cache.delete":
+				return handleCacheDelete(req.id, req.params as CacheDeleteParams);
 			default:
 				return sendError(req.id, {
 					code: RPC_ERROR_CODE.METHOD_NOT_FOUND,
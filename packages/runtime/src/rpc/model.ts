@@ -32,6 +32,11 @@ import {
 	resolveEnvironmentModelConfig,
 	updateEnvironmentModel,
 } from "../environment-services";
+import {
+	fetchLocalModelIds,
+	LOCAL_PROVIDER,
+	normalizeLocalBaseUrl,
+} from "../local-provider";
 import { resolveFastMode } from "../model-fast";
 import type { RuntimeState } from "../runtime-state";
 import { sendError, sendResult } from "./transport";
@@ -48,8 +53,12 @@ type SupportedModelProvider =
 	| "openrouter"
 	| "moonshot"
 	| "zai"
-	| "xai";
-type StaticModelProvider = Exclude<SupportedModelProvider, "openrouter">;
+	| "xai"
+	| typeof LOCAL_PROVIDER;
+type StaticModelProvider = Exclude<
+	SupportedModelProvider,
+	"openrouter" | typeof LOCAL_PROVIDER
+>;
 
 const isSupportedProvider = (
 	provider: string,
@@ -59,7 +68,11 @@ const isSupportedProvider = (
 	provider === "openrouter" ||
 	provider === "moonshot" ||
 	provider === "zai" ||
-	provider === "xai";
+	provider === "xai" ||
+	provider === LOCAL_PROVIDER;
+
+const LOCAL_BASE_URL_MISSING =
+	"local provider needs a base_url (run /local-models in the TUI or set model.base_url)";
 
 const resolveProviderModelEntry = (
 	providerEntries: Record<string, ModelEntry> | null,
@@ -392,16 +405,24 @@ export const buildProviderModelList = async ({
 	state,
 	log,
 	providerEntriesOverride,
+	baseUrl,
 }: {
 	provider: SupportedModelProvider;
 	includeDetails: boolean;
 	state?: RuntimeState;
 	log: (message: string) => void;
 	providerEntriesOverride?: Record<string, ModelEntry> | null;
+	baseUrl?: string;
 }): Promise<Pick<ModelListResult, "models" | "details">> => {
 	if (provider === "openrouter") {
 		return buildOpenRouterModelList({ includeDetails, state, log });
 	}
+	if (provider === LOCAL_PROVIDER) {
+		if (!baseUrl) {
+			throw new Error(LOCAL_BASE_URL_MISSING);
+		}
+		return { models: await fetchLocalModelIds(baseUrl) };
+	}
 
 	let providerEntries: Record<string, ModelEntry> | null = null;
 	if (providerEntriesOverride !== undefined) {
@@ -505,6 +526,7 @@ export const createModelHandlers = ({
 		let configuredProvider: string | undefined;
 		let configuredReasoning: ModelReasoningLevel | undefined;
 		let configuredFast: boolean | undefined;
+		let configuredBaseUrl: string | undefined;
 		try {
 			const workingDir =
 				state.lastUiContext?.cwd ?? state.runtimeWorkingDir ?? undefined;
@@ -512,6 +534,7 @@ export const createModelHandlers = ({
 			source = config.source;
 			configuredProvider = config.provider ?? "openai";
 			configuredReasoning = resolveReasoningEffort(config.reasoning);
+			configuredBaseUrl = config.base_url;
 			if (!requestedProvider || requestedProvider === configuredProvider) {
 				current = config.name;
 			}
@@ -543,12 +566,16 @@ export const createModelHandlers = ({
 		}
 		let models: string[];
 		let details: Record<string, ModelListDetails> | undefined;
+		const baseUrl = normalizeLocalBaseUrl(
+			params?.base_url ?? configuredBaseUrl ?? "",
+		);
 		try {
 			const result = await buildProviderModelList({
 				provider,
 				includeDetails,
 				state,
 				log,
+				...(baseUrl ? { baseUrl } : {}),
 			});
 			models = result.models;
 			details = result.details;
@@ -666,21 +693,32 @@ export const createModelHandlers = ({
 				return;
 			}
 		}
-		if (
-			provider !== "openai" &&
-			provider !== "anthropic" &&
-			provider !== "openrouter" &&
-			provider !== "moonshot" &&
-			provider !== "zai" &&
-			provider !== "xai"
-		) {
+		if (!isSupportedProvider(provider)) {
 			sendError(id, {
 				code: RPC_ERROR_CODE.INVALID_PARAMS,
 				message: `unsupported provider: ${provider}`,
 			});
 			return;
 		}
-		if (provider !== "openrouter") {
+		let baseUrl: string | undefined;
+		if (provider === LOCAL_PROVIDER) {
+			const requestedBaseUrl =
+				params.base_url ??
+				(await resolveEffectiveModelConfig(state, workingDir)).base_url;
+			const normalized = requestedBaseUrl
+				? normalizeLocalBaseUrl(requestedBaseUrl)
+				: null;
+			if (!normalized) {
+				sendError(id, {
+					code: RPC_ERROR_CODE.INVALID_PARAMS,
+					message: requestedBaseUrl
+						? `invalid local base_url: ${requestedBaseUrl}`
+						: LOCAL_BASE_URL_MISSING,
+				});
+				return;
+			}
+			baseUrl = normalized;
+		} else if (provider !== "openrouter") {
 			const spec = resolveModel(DEFAULT_MODEL_REGISTRY, name, provider);
 			if (!spec) {
 				sendError(id, {
@@ -699,6 +737,7 @@ export const createModelHandlers = ({
 					name,
 					...(reasoning ? { reasoning } : {}),
 					...(params.fast !== undefined ? { fast: params.fast } : {}),
+					...(baseUrl ? { base_url: baseUrl } : {}),
 				});
 				clearSessionModelOverride(state);
 				await persistSessionModelOverride();
@@ -712,6 +751,7 @@ export const createModelHandlers = ({
 					name,
 					...(reasoning ? { reasoning } : {}),
 					...(params.fast !== undefined ? { fast: params.fast } : {}),
+					...(baseUrl ? { base_url: baseUrl } : {}),
 				});
 				await persistSessionModelOverride();
 			}
@@ -735,6 +775,7 @@ export const createModelHandlers = ({
 				provider,
 				name,
 				source: scope,
+				...(baseUrl ? { base_url: baseUrl } : {}),
 				...(effectiveReasoning ? { reasoning: effectiveReasoning } : {}),
 				...(updatedConfig.fast !== undefined ? { fast: effectiveFast } : {}),
 			};
@@ -60,6 +60,7 @@ import {
 	sendResult,
 	sendRunContext,
 	sendRunDiagnostics,
+	sendRunUsage,
 	sendRunStatus,
 } from "./transport";
 
@@ -427,7 +428,14 @@ export const createRunHandlers = ({
 				return;
 			}
 			const startedAt = nowIso();
-			state.beginRun(runId, params.ui_context ?? state.lastUiContext);
+			state.beginRun(
+				runId,
+				params.ui_context ?? state.lastUiContext,
+				params.dry_run === true,
+				(params.disabled_tools ?? []).filter(
+					(tool): tool is string => typeof tool === "string",
+				),
+			);
 			const runAbortController = new AbortController();
 			activeRunAbort = { runId, controller: runAbortController };
 			const sessionStore = runEventStoreFactory.create({ runId, startedAt });
@@ -474,20 +482,38 @@ export const createRunHandlers = ({
 				}
 			};
 			const sessionAppend = (record: SessionRecord): void => {
-				if (state.diagnosticsEnabled) {
-					try {
-						if (record.type === "llm.request") {
-							const modelName =
-								record.model?.name ?? record.input.model ?? "unknown";
-							pendingLlmRequests.set(record.seq, {
-								ts: record.ts,
-								provider: record.model?.provider,
-								model: modelName,
+				try {
+					if (record.type === "llm.request") {
+						const modelName =
+							record.model?.name ?? record.input.model ?? "unknown";
+						pendingLlmRequests.set(record.seq, {
+							ts: record.ts,
+							provider: record.model?.provider,
+							model: modelName,
+						});
+					}
+					if (record.type === "llm.response") {
+						const request = pendingLlmRequests.get(record.seq);
+						const usage = record.output.usage;
+						if (usage) {
+							sendRunUsage(state, {
+								run_id: runId,
+								...(request?.provider ? { provider: request.provider } : {}),
+								model: usage.model ?? request?.model ?? "unknown",
+								input_tokens: usage.input_tokens,
+								output_tokens: usage.output_tokens,
+								cached_input_tokens: usage.input_cached_tokens ?? 0,
+								cache_creation_tokens: usage.input_cache_creation_tokens ?? 0,
 							});
 						}
+					}
+				} catch (error) {
+					log(`run usage emit failed: ${String(error)}`);
+				}
+				if (state.diagnosticsEnabled) {
+					try {
 						if (record.type === "llm.response") {
 							const request = pendingLlmRequests.get(record.seq);
-							pendingLlmRequests.delete(record.seq);
 							const usage = record.output.usage ?? null;
 							const cacheReadTokens = usage?.input_cached_tokens ?? 0;
 							const cacheCreationTokens =
@@ -538,6 +564,9 @@ export const createRunHandlers = ({
 						log(`run diagnostics build failed: ${String(error)}`);
 					}
 				}
+				if (record.type === "llm.response") {
+					pendingLlmRequests.delete(record.seq);
+				}
 				if (
 					record.type === "llm.response" &&
 					shouldEmitOpenAiWsFallbackWarning(record.output.provider_meta)
@@ -733,7 +762,10 @@ export const createRunHandlers = ({
 								`event.sent ${event.type} seq=${seq === null ? "suppressed" : String(seq)}`,
 							);
 						}
-						if (seq !== null) {
+						// Deltas are live-only: the `text` event that follows carries the
+						// full content, so replaying them from the session log would
+						// duplicate it.
+						if (seq !== null && event.type !== "text_delta") {
 							appendSession({
 								type: "agent.event",
 								run_id: runId,
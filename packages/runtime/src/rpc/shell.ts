@@ -400,6 +400,8 @@ const resolveShellWaitTimeoutSeconds = (
 const formatShellTimeoutForLog = (value: number | undefined): string =>
 	value === undefined ? "none" : String(value);
 
+const SHELL_ENV_NAME_PATTERN = /^[A-Za-z_][A-Za-z0-9_]*$/;
+
 const parseShellStartRequest = (
 	state: RuntimeState,
 	params: ShellExecParams | ShellStartParams | undefined,
@@ -409,6 +411,7 @@ const parseShellStartRequest = (
 			command: string;
 			timeoutSeconds?: number;
 			cwd: string;
+			env?: Record<string, string>;
 			commandSummary: string;
 			commandPreview: string;
 	  }
@@ -440,10 +443,29 @@ const parseShellStartRequest = (
 			},
 		};
 	}
+	const env = params?.env;
+	if (env !== undefined) {
+		const valid =
+			typeof env === "object" &&
+			env !== null &&
+			Object.entries(env).every(
+				([name, value]) =>
+					SHELL_ENV_NAME_PATTERN.test(name) && typeof value === "string",
+			);
+		if (!valid) {
+			return {
+				error: {
+					code: RPC_ERROR_CODE.INVALID_PARAMS,
+					message: "env must map variable names to string values",
+				},
+			};
+		}
+	}
 	return {
 		command,
 		timeoutSeconds,
 		cwd,
+		env,
 		commandSummary: summarizeCommand(command),
 		commandPreview: truncateCommandPreview(command),
 	};
@@ -491,7 +513,7 @@ export const createShellHandlers = ({
 			throw parsed.error;
 		}
 		log(
-			`${toolName}.start origin=ui_bang cwd=${parsed.cwd} timeout_s=${formatShellTimeoutForLog(parsed.timeoutSeconds)} command="${parsed.commandSummary}"`,
+			`${toolName}.start origin=ui_bang cwd=${parsed.cwd} timeout_s=${formatShellTimeoutForLog(parsed.timeoutSeconds)} env_keys=${Object.keys(parsed.env ?? {}).length} command="${parsed.commandSummary}"`,
 		);
 		const task = await shellTaskManager.spawn(
 			{
@@ -506,6 +528,7 @@ export const createShellHandlers = ({
 					command: parsed.command,
 					cwd: parsed.cwd,
 					timeoutSeconds: parsed.timeoutSeconds,
+					env: parsed.env,
 					toolName,
 					outputCache: shellOutputCache,
 				}),
@@ -70,11 +70,36 @@ export const createToolHandlers = ({
 			return;
 		}
 
+		const rawArgs = JSON.stringify(params?.arguments ?? {});
+		const toolContext = createToolContext();
+		if (params?.require_permission) {
+			const hook = state.toolPermissionHook;
+			const decision = hook
+				? await hook(
+						{
+							id: `tool.call-${id}`,
+							type: "function",
+							function: { name: toolName, arguments: rawArgs },
+						},
+						rawArgs,
+						toolContext,
+					)
+				: {
+						decision: "deny" as const,
+						reason: "permission policy unavailable",
+					};
+			if (decision.decision !== "allow") {
+				const response: ToolCallResult = {
+					ok: false,
+					result: decision.reason ?? "permission denied",
+				};
+				sendResult(id, response);
+				return;
+			}
+		}
+
 		try {
-			const result = await tool.executeRaw(
-				JSON.stringify(params?.arguments ?? {}),
-				createToolContext(),
-			);
+			const result = await tool.executeRaw(rawArgs, toolContext);
 			const response: ToolCallResult = {
 				ok: true,
 				result: normalizeToolResult(result),
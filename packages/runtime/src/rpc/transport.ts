@@ -6,6 +6,7 @@ import type {
 	RpcResponse,
 	RunContextNotify,
 	RunDiagnosticsNotify,
+	RunUsageNotify,
 } from "@codelia/protocol";
 import { debugLog, log } from "../logger";
 import type { RuntimeState } from "../runtime-state";
@@ -207,6 +208,18 @@ export const sendRunContext = (
 	sendNotification(state, notify);
 };
 
+export const sendRunUsage = (
+	state: RuntimeState,
+	params: RunUsageNotify,
+): void => {
+	const notify: RpcNotification = {
+		jsonrpc: "2.0",
+		method: "run.usage",
+		params,
+	};
+	sendNotification(state, notify);
+};
+
 export const sendRunDiagnostics = (
 	state: RuntimeState,
 	params: RunDiagnosticsNotify,
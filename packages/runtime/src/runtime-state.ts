@@ -1,5 +1,12 @@
 import crypto from "node:crypto";
-import type { Agent, SessionRecord, Tool, ToolDefinition } from "@codelia/core";
+import type {
+	Agent,
+	SessionRecord,
+	Tool,
+	ToolDefinition,
+	ToolOutputCacheStore,
+	ToolPermissionHook,
+} from "@codelia/core";
 import type {
 	RpcResponse,
 	UiCapabilities,
@@ -16,11 +23,24 @@ import type { SkillsResolver } from "./skills";
 
 export type RuntimeModelSource = "config" | "session";
 
+export type ToolOutputCacheEntryRecord = {
+	ref_id: string;
+	tool: string;
+	byte_size: number;
+	line_count: number;
+	created_at_ms: number;
+	is_error?: boolean;
+	session_id: string | null;
+};
+
+const MAX_TRACKED_TOOL_OUTPUTS = 500;
+
 export type RuntimeModelOverride = {
 	provider?: string;
 	name?: string;
 	reasoning?: string;
 	fast?: boolean;
+	base_url?: string;
 };
 
 export class RuntimeState {
@@ -37,6 +57,8 @@ export class RuntimeState {
 		}
 	>();
 	activeRunId: string | null = null;
+	activeRunDryRun = false;
+	activeRunDisabledTools: ReadonlySet<string> = new Set();
 	cancelRequested = false;
 	lastUiContext: UiContextUpdateParams | null = null;
 	lastContextLeftPercent: number | null = null;
@@ -47,6 +69,8 @@ export class RuntimeState {
 	executionEnvironmentDebugLogged = false;
 	toolDefinitions: ToolDefinition[] | null = null;
 	tools: Tool[] | null = null;
+	/** The agent's permission hook, reused by `tool.call` with `require_permission`. */
+	toolPermissionHook: ToolPermissionHook | null = null;
 	autoApprovedClientToolNames = new Set<string>();
 	sessionId: string | null = null;
 	sessionMeta: Record<string, unknown> | null = null;
@@ -58,12 +82,61 @@ export class RuntimeState {
 	loadedSkillVersions = new Map<string, number>();
 	runtimeWorkingDir: string | null = null;
 	runtimeSandboxRoot: string | null = null;
+	/** `cwd.set`: working directory for tools when it differs from the sandbox root. */
+	workingDirOverride: string | null = null;
 	approvalMode: ApprovalMode | null = null;
 	currentModelProvider: string | null = null;
 	currentModelName: string | null = null;
 	currentModelSource: RuntimeModelSource | null = null;
 	sessionModelOverride: RuntimeModelOverride | null = null;
 	diagnosticsEnabled = false;
+	/** `CODELIA_READ_ONLY=1`: only tools the minimal policy allows outright may run. */
+	readOnly = false;
+	toolOutputCache: ToolOutputCacheStore | null = null;
+	toolOutputCacheEntries = new Map<string, ToolOutputCacheEntryRecord>();
+
+	/**
+	 * Wraps the agent's tool output cache so every save is listed by `cache.list`
+	 * under the session that produced it.
+	 */
+	trackToolOutputCache(
+		store: ToolOutputCacheStore | null,
+	): ToolOutputCacheStore | null {
+		if (!store) {
+			this.toolOutputCache = null;
+			return null;
+		}
+		const tracked: ToolOutputCacheStore = {
+			save: async (record) => {
+				const ref = await store.save(record);
+				this.toolOutputCacheEntries.delete(ref.id);
+				this.toolOutputCacheEntries.set(ref.id, {
+					ref_id: ref.id,
+					tool: record.tool_name,
+					byte_size:
+						ref.byte_size ?? Buffer.byteLength(record.content, "utf8"),
+					line_count:
+						ref.line_count ?? record.content.split(/\r?\n/).length,
+					created_at_ms: Date.now(),
+					...(record.is_error ? { is_error: true } : {}),
+					session_id: this.sessionId,
+				});
+				while (this.toolOutputCacheEntries.size > MAX_TRACKED_TOOL_OUTPUTS) {
+					const oldest = this.toolOutputCacheEntries.keys().next().value;
+					if (oldest === undefined) break;
+					this.toolOutputCacheEntries.delete(oldest);
+				}
+				return ref;
+			},
+			read: store.read?.bind(store),
+			readLine: store.readLine?.bind(store),
+			grep: store.grep?.bind(store),
+			readRaw: store.readRaw?.bind(store),
+			delete: store.delete?.bind(store),
+		};
+		this.toolOutputCache = tracked;
+		return tracked;
+	}
 
 	setRuntimeEnvironment(options: RuntimeOptions): void {
 		this.effectiveEnvironment = resolveRuntimeEnvironment(options);
@@ -77,8 +150,15 @@ export class RuntimeState {
 		return crypto.randomUUID();
 	}
 
-	beginRun(runId: string, uiContext?: UiContextUpdateParams | null): void {
+	beginRun(
+		runId: string,
+		uiContext?: UiContextUpdateParams | null,
+		dryRun = false,
+		disabledTools: readonly string[] = [],
+	): void {
 		this.activeRunId = runId;
+		this.activeRunDryRun = dryRun;
+		this.activeRunDisabledTools = new Set(disabledTools);
 		this.cancelRequested = false;
 		this.lastContextLeftPercent = null;
 		if (uiContext) {
@@ -90,6 +170,8 @@ export class RuntimeState {
 	finishRun(runId: string): void {
 		if (this.activeRunId === runId) {
 			this.activeRunId = null;
+			this.activeRunDryRun = false;
+			this.activeRunDisabledTools = new Set();
 		}
 		this.cancelRequested = false;
 		this.lastContextLeftPercent = null;
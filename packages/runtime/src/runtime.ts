@@ -32,6 +32,9 @@ export const startRuntime = async (
 	state.setRuntimeEnvironment(options);
 	const environment = state.effectiveEnvironment;
 	state.diagnosticsEnabled = envTruthy(process.env.CODELIA_DIAGNOSTICS);
+	state.readOnly =
+		process.argv.slice(2).includes("--read-only") ||
+		envTruthy(process.env.CODELIA_READ_ONLY);
 	const workingDir = environment.workspace.root;
 	if (workingDir) {
 		state.runtimeWorkingDir = workingDir;
@@ -33,14 +33,16 @@ export class SandboxContext {
 
 	static async create(
 		rootDir?: string,
-		options: { approvalMode?: ApprovalMode } = {},
+		options: { approvalMode?: ApprovalMode; workingDir?: string } = {},
 	): Promise<SandboxContext> {
 		const sessionId = crypto.randomUUID().slice(0, 8);
 		const root = rootDir ? path.resolve(rootDir) : process.cwd();
 		await fs.mkdir(root, { recursive: true });
 		return new SandboxContext({
 			rootDir: root,
-			workingDir: root,
+			workingDir: options.workingDir
+				? path.resolve(root, options.workingDir)
+				: root,
 			sessionId,
 			approvalMode: options.approvalMode ?? "minimal",
 		});
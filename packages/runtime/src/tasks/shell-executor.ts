@@ -160,25 +160,30 @@ type ShellTaskChildFactory = (
 	command: string,
 	cwd: string,
 	stdinMode?: ShellStdinMode,
+	env?: Record<string, string>,
 ) => ShellChildProcess;
 
 export const spawnShellProcess: ShellTaskChildFactory = (
 	command,
 	cwd,
 	stdinMode,
+	env,
 ) => {
 	const stdin = stdinMode === "pipe" ? "pipe" : "ignore";
+	const childEnv = env ? { ...process.env, ...env } : undefined;
 	const shellPath =
 		process.platform === "win32" ? "" : process.env.SHELL?.trim() || "";
 	if (shellPath) {
 		return spawn(shellPath, ["-lc", command], {
 			cwd,
+			env: childEnv,
 			stdio: [stdin, "pipe", "pipe"],
 			detached: true,
 		}) as ShellChildProcess;
 	}
 	return spawn(command, {
 		cwd,
+		env: childEnv,
 		shell: true,
 		stdio: [stdin, "pipe", "pipe"],
 		detached: process.platform !== "win32",
@@ -216,6 +221,7 @@ export const startShellTask = (options: {
 	monotonicNowMs?: () => number;
 	stdinMode?: ShellStdinMode;
 	stdinWriteTimeoutMs?: number;
+	env?: Record<string, string>;
 }): TaskExecutionHandle => {
 	const timeoutSeconds = options.timeoutSeconds;
 	if (
@@ -237,6 +243,7 @@ export const startShellTask = (options: {
 		options.command,
 		options.cwd,
 		stdinMode,
+		options.env,
 	);
 	if (stdinMode === "pipe" && child.stdin === null) {
 		throw new Error("stdin_mode=pipe requires a writable child stdin stream");
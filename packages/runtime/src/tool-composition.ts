@@ -1,11 +1,11 @@
 import type { Tool, ToolDefinition } from "@codelia/core";
-import type { SupportedProvider } from "./auth/resolver";
 import type { ResolvedSearchConfig } from "./config";
 import type { ToolProvider } from "./environment";
+import { LOCAL_PROVIDER, type RuntimeModelProvider } from "./local-provider";
 import { createSearchTool } from "./tools/search";
 
 export type RuntimeToolCompositionInput = {
-	provider: SupportedProvider;
+	provider: RuntimeModelProvider;
 	baseTools: Tool[];
 	mcpTools: Tool[];
 	hostTools: Tool[];
@@ -22,11 +22,12 @@ export type RuntimeToolCompositionResult = {
 };
 
 const buildHostedWebSearchToolDefinitions = (
-	provider: SupportedProvider,
+	provider: RuntimeModelProvider,
 	options: ResolvedSearchConfig,
 ): ToolDefinition[] => {
 	if (
 		options.mode === "local" ||
+		provider === LOCAL_PROVIDER ||
 		!options.native.providers.includes(provider)
 	) {
 		return [];
@@ -53,7 +54,7 @@ const buildHostedWebSearchToolDefinitions = (
 };
 
 const buildHostedXSearchToolDefinitions = (
-	provider: SupportedProvider,
+	provider: RuntimeModelProvider,
 	options: ResolvedSearchConfig,
 ): ToolDefinition[] => {
 	const config = options.xai.xSearch;
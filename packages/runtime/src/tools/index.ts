@@ -13,6 +13,7 @@ import {
 	createLaneGcTool,
 	createLaneListTool,
 	createLaneStatusTool,
+	createLaneTailTool,
 } from "./lane";
 import { createReadTool } from "./read";
 import { createReadLineTool } from "./read-line";
@@ -117,6 +118,7 @@ export const createTools = (
 	createLaneCreateTool(sandboxKey),
 	createLaneListTool(sandboxKey),
 	createLaneStatusTool(sandboxKey),
+	createLaneTailTool(sandboxKey),
 	createLaneCloseTool(sandboxKey),
 	createLaneGcTool(sandboxKey),
 	createDoneTool(),
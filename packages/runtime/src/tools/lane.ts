@@ -156,6 +156,32 @@ export const createLaneStatusTool = (
 		},
 	});
 
+export const createLaneTailTool = (
+	sandboxKey: DependencyKey<SandboxContext>,
+): Tool =>
+	defineTool({
+		name: "lane_tail",
+		description: "Read the latest terminal output of a Task lane.",
+		input: z.object({
+			lane_id: z.string().describe("Lane id."),
+			lines: z
+				.number()
+				.int()
+				.min(1)
+				.max(1000)
+				.optional()
+				.describe("Trailing lines to return. Default: 200."),
+		}),
+		execute: async (input, ctx) => {
+			await getSandboxContext(ctx, sandboxKey);
+			try {
+				return await laneManager.tail(input.lane_id, input.lines ?? 200);
+			} catch (error) {
+				throw formatError(error);
+			}
+		},
+	});
+
 export const createLaneCloseTool = (
 	sandboxKey: DependencyKey<SandboxContext>,
 ): Tool =>
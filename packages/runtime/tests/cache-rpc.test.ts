@@ -0,0 +1,141 @@
+import { describe, expect, test } from "bun:test";
+import type { Agent } from "@codelia/core";
+import type { RpcRequest, RpcResponse } from "@codelia/protocol";
+import { createRuntimeHandlers } from "../src/rpc/handlers";
+import { RuntimeState } from "../src/runtime-state";
+
+const isRecord = (value: unknown): value is Record<string, unknown> =>
+	typeof value === "object" && value !== null;
+
+const captureResponse = async (
+	run: () => void,
+	id: string,
+): Promise<RpcResponse> => {
+	const originalWrite = process.stdout.write.bind(process.stdout);
+	let buffer = "";
+	const responses: RpcResponse[] = [];
+	process.stdout.write = ((chunk: string | Uint8Array) => {
+		const text =
+			typeof chunk === "string" ? chunk : Buffer.from(chunk).toString("utf8");
+		buffer += text;
+		let index = buffer.indexOf("\n");
+		while (index >= 0) {
+			const line = buffer.slice(0, index).trim();
+			buffer = buffer.slice(index + 1);
+			if (line) {
+				try {
+					const parsed = JSON.parse(line) as unknown;
+					if (isRecord(parsed) && typeof parsed.id === "string") {
+						responses.push(parsed as RpcResponse);
+					}
+				} catch {
+					// ignore
+				}
+			}
+			index = buffer.indexOf("\n");
+		}
+		return true;
+	}) as typeof process.stdout.write;
+	try {
+		run();
+		const deadline = Date.now() + 1_000;
+		while (Date.now() < deadline) {
+			const response = responses.find((entry) => entry.id === id);
+			if (response) {
+				return response;
+			}
+			await Bun.sleep(10);
+		}
+		throw new Error("response timeout");
+	} finally {
+		process.stdout.write = originalWrite;
+	}
+};
+
+describe("cache rpc", () => {
+	test("lists, reads, and deletes tool outputs of the current session", async () => {
+		const files = new Map<string, string>();
+		const state = new RuntimeState();
+		state.sessionId = "session-1";
+		const store = state.trackToolOutputCache({
+			save: (record) => {
+				files.set(record.tool_call_id, record.content);
+				return { id: record.tool_call_id, byte_size: record.content.length };
+			},
+			readRaw: (refId) => {
+				const content = files.get(refId) ?? "";
+				return { content, byte_size: content.length, truncated: false };
+			},
+			delete: (refId) => files.delete(refId),
+		});
+		await store?.save({
+			tool_call_id: "call_read",
+			tool_name: "read",
+			content: "a\nb",
+		});
+		state.sessionId = "session-2";
+		await store?.save({
+			tool_call_id: "call_bash",
+			tool_name: "bash",
+			content: "ok",
+		});
+		state.sessionId = "session-1";
+		const handlers = createRuntimeHandlers({
+			state,
+			getAgent: async () => ({}) as Agent,
+			log: () => {},
+		});
+
+		const list = await captureResponse(() => {
+			handlers.processMessage({
+				jsonrpc: "2.0",
+				id: "cache-list",
+				method: "cache.list",
+			} satisfies RpcRequest);
+		}, "cache-list");
+		expect(list.result).toMatchObject({
+			entries: [
+				{ ref_id: "call_read", tool: "read", byte_size: 3, line_count: 2 },
+			],
+		});
+
+		const read = await captureResponse(() => {
+			handlers.processMessage({
+				jsonrpc: "2.0",
+				id: "cache-read",
+				method: "cache.read",
+				params: { ref_id: "call_read" },
+			} satisfies RpcRequest);
+		}, "cache-read");
+		expect(read.result).toEqual({
+			ref_id: "call_read",
+			content: "a\nb",
+			byte_size: 3,
+			truncated: false,
+		});
+
+		const otherSession = await captureResponse(() => {
+			handlers.processMessage({
+				jsonrpc: "2.0",
+				id: "cache-read-other",
+				method: "cache.read",
+				params: { ref_id: "call_bash" },
+			} satisfies RpcRequest);
+		}, "cache-read-other");
+		expect(otherSession.error?.message).toBe(
+			"unknown tool output ref: call_bash",
+		);
+
+		const deleted = await captureResponse(() => {
+			handlers.processMessage({
+				jsonrpc: "2.0",
+				id: "cache-delete",
+				method: "cache.delete",
+				params: { ref_id: "call_read" },
+			} satisfies RpcRequest);
+		}, "cache-delete");
+		expect(deleted.result).toEqual({ ref_id: "call_read", deleted: true });
+		expect(files.has("call_read")).toBe(false);
+		expect(state.toolOutputCacheEntries.has("call_read")).toBe(false);
+	});
+});
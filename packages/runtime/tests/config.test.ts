@@ -477,6 +477,18 @@ describe("runtime config resolvers", () => {
 			);
 			expect(projectRaw.tui).toEqual({ theme: "rose" });
 			expect(await resolveTuiConfig(projectDir)).toEqual({ theme: "rose" });
+
+			await fs.writeFile(
+				globalConfigPath,
+				`${JSON.stringify({
+					version: 1,
+					tui: { theme: "ocean", motd: "Maintenance at 18:00 UTC" },
+				})}\n`,
+			);
+			expect(await resolveTuiConfig(projectDir)).toEqual({
+				theme: "rose",
+				motd: "Maintenance at 18:00 UTC",
+			});
 		} finally {
 			for (const [key, value] of restore.reverse()) {
 				if (value === undefined) {
@@ -0,0 +1,112 @@
+import { afterEach, beforeEach, describe, expect, test } from "bun:test";
+import { promises as fs } from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import type { Agent } from "@codelia/core";
+import type { RpcRequest, RpcResponse } from "@codelia/protocol";
+import { createRuntimeHandlers } from "../src/rpc/handlers";
+import { RuntimeState } from "../src/runtime-state";
+
+const isRecord = (value: unknown): value is Record<string, unknown> =>
+	typeof value === "object" && value !== null;
+
+const captureResponse = async (
+	run: () => void,
+	id: string,
+): Promise<RpcResponse> => {
+	const originalWrite = process.stdout.write.bind(process.stdout);
+	let buffer = "";
+	const responses: RpcResponse[] = [];
+	process.stdout.write = ((chunk: string | Uint8Array) => {
+		const text =
+			typeof chunk === "string" ? chunk : Buffer.from(chunk).toString("utf8");
+		buffer += text;
+		let index = buffer.indexOf("\n");
+		while (index >= 0) {
+			const line = buffer.slice(0, index).trim();
+			buffer = buffer.slice(index + 1);
+			if (line) {
+				try {
+					const parsed = JSON.parse(line) as unknown;
+					if (isRecord(parsed) && typeof parsed.id === "string") {
+						responses.push(parsed as RpcResponse);
+					}
+				} catch {
+					// ignore
+				}
+			}
+			index = buffer.indexOf("\n");
+		}
+		return true;
+	}) as typeof process.stdout.write;
+	try {
+		run();
+		const deadline = Date.now() + 1_000;
+		while (Date.now() < deadline) {
+			const response = responses.find((entry) => entry.id === id);
+			if (response) {
+				return response;
+			}
+			await Bun.sleep(10);
+		}
+		throw new Error("response timeout");
+	} finally {
+		process.stdout.write = originalWrite;
+	}
+};
+
+describe("cwd.set rpc", () => {
+	let tempRoot = "";
+
+	beforeEach(async () => {
+		tempRoot = await fs.realpath(
+			await fs.mkdtemp(path.join(os.tmpdir(), "codelia-cwd-set-")),
+		);
+		await fs.mkdir(path.join(tempRoot, "packages", "api"), { recursive: true });
+		await fs.writeFile(path.join(tempRoot, "README.md"), "hi");
+	});
+
+	afterEach(async () => {
+		await fs.rm(tempRoot, { recursive: true, force: true });
+	});
+
+	test("moves the working directory inside the sandbox and rejects escapes", async () => {
+		const state = new RuntimeState();
+		state.runtimeSandboxRoot = tempRoot;
+		state.agent = {} as Agent;
+		const handlers = createRuntimeHandlers({
+			state,
+			getAgent: async () => ({}) as Agent,
+			log: () => {},
+		});
+		const cwdSet = (id: string, target: string) =>
+			captureResponse(() => {
+				handlers.processMessage({
+					jsonrpc: "2.0",
+					id,
+					method: "cwd.set",
+					params: { path: target },
+				} satisfies RpcRequest);
+			}, id);
+
+		const moved = await cwdSet("cd-api", "packages/api");
+		expect(moved.result).toEqual({
+			cwd: path.join(tempRoot, "packages", "api"),
+			sandbox_root: tempRoot,
+		});
+		expect(state.runtimeWorkingDir).toBe(path.join(tempRoot, "packages", "api"));
+		expect(state.workingDirOverride).toBe(
+			path.join(tempRoot, "packages", "api"),
+		);
+		expect(state.agent).toBeNull();
+
+		const escaped = await cwdSet("cd-out", "../../..");
+		expect(escaped.error?.message).toContain("escapes sandbox root");
+		const file = await cwdSet("cd-file", path.join(tempRoot, "README.md"));
+		expect(file.error?.message).toContain("not a directory");
+
+		const back = await cwdSet("cd-root", "../..");
+		expect(back.result).toMatchObject({ cwd: tempRoot });
+		expect(state.workingDirOverride).toBeNull();
+	});
+});
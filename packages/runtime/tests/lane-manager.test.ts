@@ -34,6 +34,12 @@ const createFakeRunner = (state: FakeState): CommandRunner => {
 			}
 			return { stdout: "", stderr: "" };
 		}
+		if (command === "tmux" && args[0] === "capture-pane") {
+			return {
+				stdout: "$ codelia\nthinking...\nwrote src/a.ts\n\n\n",
+				stderr: "",
+			};
+		}
 		if (command === "tmux" && args[0] === "kill-session") {
 			const idx = args.indexOf("-t");
 			const target = idx >= 0 ? args[idx + 1] : "";
@@ -157,6 +163,29 @@ describe("LaneManager", () => {
 		}
 	});
 
+	test("tail captures the pane of a live lane only", async () => {
+		const env = await setup();
+		try {
+			const lane = await env.manager.create(
+				{
+					task_id: "Watch Me",
+				},
+				{ workingDir: env.repoRoot },
+			);
+			const live = await env.manager.tail(lane.lane_id, 2);
+			expect(live.backend_alive).toBe(true);
+			expect(live.lines).toEqual(["thinking...", "wrote src/a.ts"]);
+
+			env.state.aliveSessions.delete(lane.mux_target);
+			const gone = await env.manager.tail(lane.lane_id, 2);
+			expect(gone.backend_alive).toBe(false);
+			expect(gone.lane.state).toBe("finished");
+			expect(gone.lines).toEqual([]);
+		} finally {
+			await env.cleanup();
+		}
+	});
+
 	test("close rejects running lane without force", async () => {
 		const env = await setup();
 		try {
@@ -47,6 +47,9 @@ const createCapabilities = (
 	hostToolNames: new Set<string>(),
 	isAutoApprovedTool: () => false,
 	supportsConfirm: () => true,
+	isDryRunActive: () => false,
+	isToolDisabled: () => false,
+	isReadOnly: () => false,
 	getActiveRunId: () => "run-1",
 	requestConfirm: async () => ({ ok: true }),
 	emitAgentEvent: async () => {},
@@ -158,6 +161,132 @@ describe("runtime tool permission hook", () => {
 		);
 	});
 
+	test("dry run previews mutating tools and denies them without confirmation", async () => {
+		let confirmCount = 0;
+		const events: AgentEvent[] = [];
+		const hook = createToolPermissionHook(
+			createCapabilities({
+				isDryRunActive: () => true,
+				requestConfirm: async () => {
+					confirmCount += 1;
+					return { ok: true };
+				},
+				emitAgentEvent: async (_runId, event) => {
+					events.push(event);
+				},
+			}),
+		);
+
+		const decision = await hook(
+			call("shell"),
+			JSON.stringify({ command: "rm -rf build" }),
+			toolContext,
+		);
+		expect(decision.decision).toBe("deny");
+		expect(decision.stop_turn).toBeUndefined();
+		expect(events).toEqual([
+			expect.objectContaining({
+				type: "permission.preview",
+				tool: "shell",
+				summary: "rm -rf build",
+			}),
+		]);
+		expect(
+			await hook(
+				call("shell"),
+				JSON.stringify({ command: "git status" }),
+				toolContext,
+			),
+		).toEqual({ decision: "allow" });
+		expect(confirmCount).toBe(0);
+	});
+
+	test("read-only sessions deny mutating tools even in full-access mode", async () => {
+		let confirmCount = 0;
+		const hook = createToolPermissionHook(
+			createCapabilities({
+				permissionService: new PermissionService({
+					approvalMode: "full-access",
+					system: buildSystemPermissions("full-access"),
+				}),
+				isReadOnly: () => true,
+				requestConfirm: async () => {
+					confirmCount += 1;
+					return { ok: true };
+				},
+			}),
+		);
+
+		const decision = await hook(
+			call("shell"),
+			JSON.stringify({ command: "rm -rf build" }),
+			toolContext,
+		);
+		expect(decision.decision).toBe("deny");
+		expect(decision.stop_turn).toBe(false);
+		expect(
+			await hook(
+				call("shell"),
+				JSON.stringify({ command: "git status" }),
+				toolContext,
+			),
+		).toEqual({ decision: "allow" });
+		expect(confirmCount).toBe(0);
+	});
+
+	test("tools disabled for the session are denied without confirmation", async () => {
+		let confirmCount = 0;
+		const hook = createToolPermissionHook(
+			createCapabilities({
+				isToolDisabled: (tool) => tool === "shell",
+				isAutoApprovedTool: () => true,
+				requestConfirm: async () => {
+					confirmCount += 1;
+					return { ok: true };
+				},
+			}),
+		);
+
+		const decision = await hook(
+			call("shell"),
+			JSON.stringify({ command: "git status" }),
+			toolContext,
+		);
+		expect(decision.decision).toBe("deny");
+		expect(decision.stop_turn).toBe(false);
+		expect(await hook(call("read"), "{}", toolContext)).toEqual({
+			decision: "allow",
+		});
+		expect(confirmCount).toBe(0);
+	});
+
+	test("rejected hunks turn an allow into a retryable denial", async () => {
+		const hook = createToolPermissionHook(
+			createCapabilities({
+				requestConfirm: async () => ({
+					ok: true,
+					hunks: [
+						{ index: 1, header: "a.ts @@ -1 +1 @@", accepted: true },
+						{ index: 2, header: "a.ts @@ -9 +9 @@", accepted: false },
+					],
+				}),
+			}),
+		);
+
+		expect(
+			await hook(
+				call("shell"),
+				JSON.stringify({ command: "unknown-command" }),
+				toolContext,
+			),
+		).toEqual({
+			decision: "deny",
+			reason:
+				"user rejected 1 hunk(s) of this change: #2 a.ts @@ -9 +9 @@. Apply the change again without those hunks.",
+			stop_turn: false,
+		});
+	});
+
 	test("denial without a reason stops the turn", async () => {
 		const hook = createToolPermissionHook(
 			createCapabilities({ requestConfirm: async () => ({ ok: false }) }),
@@ -18,6 +18,7 @@ import type {
 	RunDiagnosticsNotify,
 	RunStartResult,
 	RunStatusNotify,
+	RunUsageNotify,
 } from "@codelia/protocol";
 import { createRuntimeHandlers } from "../src/rpc/handlers";
 import { RuntimeState } from "../src/runtime-state";
@@ -301,6 +302,61 @@ describe("run.diagnostics notifications", () => {
 		).toBe(false);
 	});
 
+	test("emits run.usage per call without diagnostics enabled", async () => {
+		const llm = new MockChatModel();
+		const agent = new CoreAgent({ llm, tools: [] }) as unknown as Agent;
+		const records: SessionRecord[] = [];
+		const capture = createStdoutCapture();
+		capture.start();
+		let runId = "";
+		try {
+			const state = new RuntimeState();
+			const stores = createStores(records);
+			const handlers = createRuntimeHandlers({
+				state,
+				getAgent: async () => agent,
+				log: () => {},
+				...stores,
+			});
+
+			handlers.processMessage({
+				jsonrpc: "2.0",
+				id: "run-usage",
+				method: "run.start",
+				params: {
+					input: { type: "text", text: "hello" },
+				},
+			} satisfies RpcRequest);
+
+			const response = await capture.waitForResponse("run-usage");
+			const result = response.result as RunStartResult | undefined;
+			runId = result?.run_id ?? "";
+			await capture.waitForRunStatus(runId, "completed");
+		} finally {
+			capture.stop();
+		}
+
+		const usage = capture.messages.filter(
+			(msg): msg is RpcNotification =>
+				isRpcNotification(msg) && msg.method === "run.usage",
+		);
+		expect(usage).toHaveLength(1);
+		expect(usage[0]?.params as RunUsageNotify).toEqual({
+			run_id: runId,
+			provider: "openai",
+			model: "mock-model",
+			input_tokens: 120,
+			output_tokens: 20,
+			cached_input_tokens: 60,
+			cache_creation_tokens: 0,
+		});
+		expect(
+			capture.messages.some(
+				(msg) => isRpcNotification(msg) && msg.method === "run.diagnostics",
+			),
+		).toBe(false);
+	});
+
 	test("emits a visible warning once when openai auto websocket falls back to http", async () => {
 		const llm = new MockChatModel({
 			transport: "http_stream",
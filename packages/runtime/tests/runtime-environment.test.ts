@@ -426,6 +426,7 @@ describe("runtime environment contract", () => {
 			const context = await capture.waitForResponse("context-embedded");
 			expect(context.error).toBeUndefined();
 			expect(context.result).toMatchObject({
+				runtime_pid: process.pid,
 				runtime_environment: {
 					source_preset: "embedded-no-local-tools",
 					workspace: {
@@ -122,4 +122,57 @@ describe("tool.call rpc", () => {
 			}),
 		);
 	});
+
+	test("require_permission runs the permission hook before the tool", async () => {
+		const state = new RuntimeState();
+		const calls: string[] = [];
+		state.tools = [
+			{
+				name: "write",
+				description: "mock",
+				definition: { name: "write", description: "mock", parameters: {} },
+				executeRaw: async (rawArgsJson) => {
+					calls.push(rawArgsJson);
+					return { type: "text", text: "wrote" };
+				},
+			} satisfies Tool,
+		];
+		const seen: string[] = [];
+		let allow = false;
+		state.toolPermissionHook = async (call, rawArgs) => {
+			seen.push(`${call.function.name} ${rawArgs}`);
+			return allow
+				? { decision: "allow" }
+				: { decision: "deny", reason: "permission denied" };
+		};
+		const handlers = createRuntimeHandlers({
+			state,
+			getAgent: async () => ({}) as Agent,
+			log: () => {},
+		});
+		const call = (id: string) =>
+			captureResponse(() => {
+				handlers.processMessage({
+					jsonrpc: "2.0",
+					id,
+					method: "tool.call",
+					params: {
+						name: "write",
+						arguments: { file_path: "a.txt", content: "x" },
+						require_permission: true,
+					},
+				} satisfies RpcRequest);
+			}, id);
+
+		const denied = await call("tool-denied");
+		expect(denied.result).toEqual({ ok: false, result: "permission denied" });
+		expect(calls).toEqual([]);
+
+		allow = true;
+		const allowed = await call("tool-allowed");
+		expect(allowed.result).toEqual({ ok: true, result: "wrote" });
+		const args = JSON.stringify({ file_path: "a.txt", content: "x" });
+		expect(calls).toEqual([args]);
+		expect(seen).toEqual([`write ${args}`, `write ${args}`]);
+	});
 });
@@ -4,6 +4,14 @@ export type TextEvent = {
 	timestamp: number;
 };
 
+// Partial assistant text streamed while the model is still responding. The
+// complete text still arrives as a `text` event once the response finishes.
+export type TextDeltaEvent = {
+	type: "text_delta";
+	content: string;
+	timestamp: number;
+};
+
 export type ReasoningEvent = {
 	type: "reasoning";
 	content: string;
@@ -33,6 +41,8 @@ export type ToolResultEvent = {
 	tool_call_id: string;
 	is_error?: boolean;
 	screenshot_base64?: string | null;
+	// Tool output cache ref for the full result; clients page it via tool_output_cache.
+	output_ref_id?: string;
 };
 
 export type PermissionPreviewEvent = {
@@ -82,6 +92,7 @@ export type FinalResponseEvent = {
 
 export type AgentEvent =
 	| TextEvent
+	| TextDeltaEvent
 	| ReasoningEvent
 	| StepStartEvent
 	| ToolCallEvent
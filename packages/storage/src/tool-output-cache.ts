@@ -262,6 +262,35 @@ export class ToolOutputCacheStoreImpl implements ToolOutputCacheStore {
 		};
 	}
 
+	async readRaw(
+		refId: string,
+		options: { max_bytes?: number } = {},
+	): Promise<{ content: string; byte_size: number; truncated: boolean }> {
+		const filePath = this.resolvePath(refId);
+		const content = await fs.readFile(filePath, "utf8");
+		const byteSize = Buffer.byteLength(content, "utf8");
+		const maxBytes = options.max_bytes;
+		if (maxBytes === undefined || byteSize <= maxBytes) {
+			return { content, byte_size: byteSize, truncated: false };
+		}
+		return {
+			content: clipUtf8ToBytes(content, maxBytes),
+			byte_size: byteSize,
+			truncated: true,
+		};
+	}
+
+	async delete(refId: string): Promise<boolean> {
+		const filePath = this.resolvePath(refId);
+		try {
+			await fs.unlink(filePath);
+			return true;
+		} catch (error) {
+			if ((error as NodeJS.ErrnoException).code === "ENOENT") return false;
+			throw error;
+		}
+	}
+
 	async readLine(
 		refId: string,
 		options: { line_number: number; char_offset?: number; char_limit?: number },
@@ -43,6 +43,34 @@ describe("@codelia/storage ToolOutputCacheStoreImpl", () => {
 		}
 	});
 
+	test("readRaw returns unnumbered content and delete removes the entry", async () => {
+		const root = await mkdtemp(path.join(os.tmpdir(), "codelia-cache-"));
+		try {
+			const paths = resolveStoragePaths({ rootOverride: root });
+			const store = new ToolOutputCacheStoreImpl({ paths });
+			const ref = await store.save({
+				tool_call_id: "call_raw",
+				tool_name: "bash",
+				content: "first\nsecond",
+			});
+
+			expect(await store.readRaw(ref.id)).toEqual({
+				content: "first\nsecond",
+				byte_size: 12,
+				truncated: false,
+			});
+			expect(await store.readRaw(ref.id, { max_bytes: 5 })).toEqual({
+				content: "first",
+				byte_size: 12,
+				truncated: true,
+			});
+			expect(await store.delete(ref.id)).toBe(true);
+			expect(await store.delete(ref.id)).toBe(false);
+		} finally {
+			await rm(root, { recursive: true, force: true });
+		}
+	});
+
 	test("readLine paginates long single line by char offset", async () => {
 		const root = await mkdtemp(path.join(os.tmpdir(), "codelia-cache-"));
 		try {